@@ -168,7 +168,7 @@ impl WindowBehavior for Sprites {
         mut window: kludgine::app::Window<'_, ()>,
         _kludgine: &mut kludgine::Kludgine,
         _device_id: appit::winit::event::DeviceId,
-        input: appit::winit::event::KeyEvent,
+        input: kludgine::app::KeyEvent,
         _is_synthetic: bool,
     ) {
         let tag = match (input.logical_key, input.text.as_deref()) {
@@ -1,27 +1,112 @@
+use std::any::Any;
+use std::collections::HashSet;
 use std::marker::PhantomData;
 use std::num::NonZeroU32;
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
 use std::time::{Duration, Instant};
 
 use appit::winit::dpi::{PhysicalPosition, PhysicalSize};
-use appit::winit::error::{EventLoopError, OsError};
+use appit::winit::error::{EventLoopError, ExternalError, OsError};
 use appit::winit::event::{
-    AxisId, DeviceId, ElementState, Ime, KeyEvent, Modifiers, MouseButton, MouseScrollDelta, Touch,
+    AxisId, DeviceId, ElementState, Ime, Modifiers, MouseButton, MouseScrollDelta, Touch,
     TouchPhase,
 };
 use appit::winit::event_loop::OwnedDisplayHandle;
-use appit::winit::keyboard::PhysicalKey;
+use appit::winit::keyboard::{Key, NamedKey, PhysicalKey};
 use appit::winit::monitor::{MonitorHandle, VideoModeHandle};
-use appit::winit::window::{ImePurpose, Theme, WindowId};
+#[cfg(feature = "image")]
+use appit::winit::window::BadImage;
+use appit::winit::window::{
+    CursorGrabMode, CursorIcon, CustomCursor, CustomCursorSource, ImePurpose, Theme,
+    UserAttentionType, WindowId,
+};
 pub use appit::{winit, Application, AsApplication, Message, WindowAttributes};
 use appit::{RunningWindow, WindowBehavior as _};
 use figures::units::{Px, UPx};
-use figures::{Fraction, IntoSigned, Point, Rect, Size};
+use figures::{
+    FloatConversion, Fraction, IntoSigned, IntoUnsigned, Point, Px2D, Rect, Size, UPx2D,
+};
 use intentional::{Assert, Cast};
 
+use crate::convert::DisplayScale;
 use crate::drawing::{Drawing, Renderer};
-use crate::{Color, Graphics, Kludgine, RenderingGraphics};
+use crate::input::{KeyboardState, MouseState};
+use crate::shapes::Shape;
+use crate::{
+    pop_error_scopes, push_error_scopes, Color, Error, Frame, Graphics, Kludgine, PreparedGraphic,
+    RenderingGraphics, SharedTexture, Texture,
+};
+
+#[cfg(feature = "clipboard")]
+mod clipboard;
+mod focus;
+mod headless;
+mod keyboard;
+mod overlay;
+mod placement;
+mod pointer;
+mod relationships;
+mod synthetic;
+
+#[cfg(feature = "clipboard")]
+pub use self::clipboard::{Clipboard, ClipboardError, ClipboardImage};
+pub use self::focus::{FocusChain, FocusDirection, FocusTraversal};
+pub use self::headless::HeadlessWindow;
+use self::headless::VirtualWindow;
+pub use self::keyboard::KeyEvent;
+use self::overlay::{panic_message, PanicOverlay};
+pub use self::placement::WindowPlacement;
+use self::pointer::PointerTracker;
+pub use self::pointer::{Click, DragStart, PointerSettings};
+use self::relationships::WindowRegistration;
+pub use self::relationships::{centered_in, ParentWindow};
+use self::synthetic::synthetic_device_id;
+pub use self::synthetic::SyntheticInput;
+
+/// Options for mirroring the frames presented by a window.
+///
+/// See [`WindowBehavior::mirror()`] for more information.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct FrameMirror {
+    /// The minimum duration between mirrored frames being read back to the
+    /// CPU and passed to [`WindowBehavior::frame_captured()`]. If `None`,
+    /// frames are only mirrored on the GPU.
+    pub capture_interval: Option<Duration>,
+}
+
+impl FrameMirror {
+    /// Mirrors frames on the GPU without reading them back to the CPU.
+    pub const GPU_ONLY: Self = Self {
+        capture_interval: None,
+    };
+
+    /// Mirrors frames on the GPU and reads a copy back to the CPU at most once
+    /// per `interval`.
+    #[must_use]
+    pub const fn capturing_every(interval: Duration) -> Self {
+        Self {
+            capture_interval: Some(interval),
+        }
+    }
+}
+
+/// A mirrored frame that has been read back from the GPU.
+///
+/// See [`WindowBehavior::frame_captured()`] for more information.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedFrame {
+    /// The size of the frame.
+    pub size: Size<UPx>,
+    /// The format of the frame's pixels. This is the window surface's format,
+    /// which is commonly a BGRA format.
+    pub format: wgpu::TextureFormat,
+    /// The frame's pixels, tightly packed with no padding between rows.
+    pub data: Vec<u8>,
+}
 
 /// A `Kludgine` application that enables opening multiple windows.
 pub struct PendingApp<WindowEvent = ()>(appit::PendingApp<AppEvent<WindowEvent>>)
@@ -131,6 +216,33 @@ where
             });
     }
 
+    /// Installs `on_raw_event` to observe every event received from winit
+    /// before Kludgine processes it.
+    ///
+    /// This allows embedders to implement global shortcuts, debug overlays, or
+    /// support for custom devices without reimplementing Kludgine's window
+    /// handling. If `on_raw_event` returns [`RawEventHandling::Consume`], the
+    /// event is not processed by Kludgine and no [`WindowBehavior`] functions
+    /// are invoked for it.
+    ///
+    /// Events sent internally by Kludgine, such as [`App::execute`] requests,
+    /// are not delivered to `on_raw_event`. Consuming events that Kludgine
+    /// depends on, such as [`winit::event::WindowEvent::RedrawRequested`] or
+    /// [`winit::event::WindowEvent::Resized`], prevents windows from being
+    /// drawn correctly.
+    pub fn on_raw_event<F>(&mut self, mut on_raw_event: F)
+    where
+        F: FnMut(&winit::event::Event<()>, ExecutingApp<'_, WindowEvent>) -> RawEventHandling
+            + 'static,
+    {
+        self.0.on_event(
+            move |event: &winit::event::Event<()>,
+                  app: appit::ExecutingApp<'_, AppEvent<WindowEvent>>| {
+                on_raw_event(event, ExecutingApp(app)) == RawEventHandling::Consume
+            },
+        );
+    }
+
     /// Begins running the application.
     ///
     /// On some platforms, this function may never return. If it does return, it
@@ -185,6 +297,13 @@ where
     pub fn owned_display_handle(&self) -> OwnedDisplayHandle {
         self.0.owned_display_handle()
     }
+
+    /// Creates a cursor that can be applied to windows using
+    /// [`winit::window::Window::set_cursor`].
+    #[must_use]
+    pub fn create_custom_cursor(&self, source: CustomCursorSource) -> CustomCursor {
+        self.0.create_custom_cursor(source)
+    }
 }
 
 /// A handle to a running Kludgine application.
@@ -268,37 +387,75 @@ pub struct Window<'window, WindowEvent = ()>
 where
     WindowEvent: Send + 'static,
 {
-    window: &'window mut RunningWindow<AppEvent<WindowEvent>>,
+    window: WindowRef<'window, WindowEvent>,
     elapsed: Duration,
     last_frame_rendered_in: Duration,
     pending_inner_size: Option<&'window mut Option<Size<UPx>>>,
 }
 
+enum WindowRef<'window, WindowEvent>
+where
+    WindowEvent: Send + 'static,
+{
+    Running(
+        &'window mut RunningWindow<AppEvent<WindowEvent>>,
+        &'window WindowInput,
+    ),
+    Headless(
+        &'window mut VirtualWindow,
+        &'window WindowHandle<WindowEvent>,
+    ),
+}
+
+/// The input state tracked for a window.
+#[derive(Default)]
+struct WindowInput {
+    keyboard: KeyboardState,
+    mouse: MouseState,
+}
+
 impl<'window, WindowEvent> Window<'window, WindowEvent>
 where
     WindowEvent: Send + 'static,
 {
     fn new(
         window: &'window mut RunningWindow<AppEvent<WindowEvent>>,
+        input: &'window WindowInput,
         elapsed: Duration,
         last_frame_rendered_in: Duration,
     ) -> Self {
         Self {
-            window,
+            window: WindowRef::Running(window, input),
             elapsed,
             last_frame_rendered_in,
             pending_inner_size: None,
         }
     }
 
+    fn headless(
+        window: &'window mut VirtualWindow,
+        handle: &'window WindowHandle<WindowEvent>,
+        elapsed: Duration,
+        last_frame_rendered_in: Duration,
+        pending_inner_size: Option<&'window mut Option<Size<UPx>>>,
+    ) -> Self {
+        Self {
+            window: WindowRef::Headless(window, handle),
+            elapsed,
+            last_frame_rendered_in,
+            pending_inner_size,
+        }
+    }
+
     fn new_in_frame(
         window: &'window mut RunningWindow<AppEvent<WindowEvent>>,
+        input: &'window WindowInput,
         elapsed: Duration,
         last_frame_rendered_in: Duration,
         pending_inner_size: &'window mut Option<Size<UPx>>,
     ) -> Self {
         Self {
-            window,
+            window: WindowRef::Running(window, input),
             elapsed,
             last_frame_rendered_in,
             pending_inner_size: Some(pending_inner_size),
@@ -309,56 +466,188 @@ where
     /// `WindowEvent`s to it.
     #[must_use]
     pub fn handle(&self) -> WindowHandle<WindowEvent> {
-        WindowHandle(self.window.handle())
+        match &self.window {
+            WindowRef::Running(window, _) => WindowHandle::new(window.handle()),
+            WindowRef::Headless(_, handle) => (*handle).clone(),
+        }
     }
 
     /// Returns a handle to the application.
     ///
     /// This is useful for opening additional windows in a multi-window
     /// application.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this window is a [`HeadlessWindow`], which does not run
+    /// within an application. Use [`try_app()`](Self::try_app) for windows
+    /// that may be headless.
     #[must_use]
     pub fn app(&self) -> App<WindowEvent> {
-        App(self.window.app())
+        self.try_app().expect("headless windows do not have an app")
+    }
+
+    /// Returns a handle to the application, or `None` if this window is a
+    /// [`HeadlessWindow`].
+    #[must_use]
+    pub fn try_app(&self) -> Option<App<WindowEvent>> {
+        match &self.window {
+            WindowRef::Running(window, _) => Some(App(window.app())),
+            WindowRef::Headless(..) => None,
+        }
     }
 
     /// Returns a reference to the underlying winit window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this window is a [`HeadlessWindow`], which does not have a
+    /// winit window. Use [`try_winit()`](Self::try_winit) for windows that
+    /// may be headless.
     #[must_use]
     pub fn winit(&self) -> &Arc<winit::window::Window> {
-        self.window.winit()
+        self.try_winit()
+            .expect("headless windows do not have a winit window")
+    }
+
+    /// Returns a reference to the underlying winit window, or `None` if this
+    /// window is a [`HeadlessWindow`].
+    #[must_use]
+    pub fn try_winit(&self) -> Option<&Arc<winit::window::Window>> {
+        match &self.window {
+            WindowRef::Running(window, _) => Some(window.winit()),
+            WindowRef::Headless(..) => None,
+        }
+    }
+
+    /// Returns the unique id of this window.
+    ///
+    /// Each [`HeadlessWindow`] is assigned an id that is distinct from the ids
+    /// of all other windows.
+    #[must_use]
+    pub fn id(&self) -> WindowId {
+        match &self.window {
+            WindowRef::Running(window, _) => window.winit().id(),
+            WindowRef::Headless(window, _) => window.id,
+        }
+    }
+
+    /// Returns this window's relationship with its parent window, if it has
+    /// one.
+    #[must_use]
+    pub fn parent(&self) -> Option<ParentWindow> {
+        self.try_winit()
+            .and_then(|winit| relationships::parent_of(winit.id()))
+    }
+
+    /// Returns the ids of the open windows whose parent is this window.
+    #[must_use]
+    pub fn children(&self) -> Vec<WindowId> {
+        self.try_winit()
+            .map(|winit| relationships::children_of(winit.id()))
+            .unwrap_or_default()
+    }
+
+    /// Returns true if input to this window is blocked by an open modal child
+    /// window.
+    #[must_use]
+    pub fn blocked_by_modal(&self) -> bool {
+        self.try_winit()
+            .is_some_and(|winit| relationships::modal_child(winit.id()).is_some())
+    }
+
+    /// Moves this window so that it is centered within `area`.
+    pub fn center_on(&self, area: Rect<Px>) {
+        self.set_outer_position(centered_in(area, self.outer_size()));
+    }
+
+    /// Moves this window onto `monitor` using `placement`.
+    ///
+    /// Placements that resize the window request a new inner size, which the
+    /// platform may adjust or ignore.
+    pub fn place_on(&mut self, monitor: &Monitor, placement: WindowPlacement) {
+        let region = placement.region(monitor.region(), self.outer_size());
+        if placement.resizes() {
+            let _ = self.request_inner_size(region.size.into_unsigned());
+        }
+        self.set_outer_position(region.origin);
+    }
+
+    /// Moves this window so that it is centered above its parent window.
+    ///
+    /// Returns false if this window has no parent or the parent's position
+    /// is unavailable.
+    pub fn center_on_parent(&self) -> bool {
+        let Some(area) = self
+            .parent()
+            .and_then(|parent| relationships::outer_rect(parent.id))
+        else {
+            return false;
+        };
+        self.center_on(area);
+        true
+    }
+
+    /// Returns true if this window is a [`HeadlessWindow`].
+    #[must_use]
+    pub const fn is_headless(&self) -> bool {
+        matches!(self.window, WindowRef::Headless(..))
     }
 
     /// Closes this window as soon as control returns to `Kludgine`.
     pub fn close(&mut self) {
-        self.window.close();
+        match &mut self.window {
+            WindowRef::Running(window, _) => window.close(),
+            WindowRef::Headless(window, _) => window.close_requested = true,
+        }
     }
 
     /// Returns the current inner position of the window.
     #[must_use]
     pub fn inner_position(&self) -> Point<Px> {
-        self.window.inner_position().into()
+        match &self.window {
+            WindowRef::Running(window, _) => window.inner_position().into(),
+            WindowRef::Headless(window, _) => window.position,
+        }
     }
 
     /// Returns the current outer position of the window.
     #[must_use]
     pub fn outer_position(&self) -> Point<Px> {
-        self.window.outer_position().into()
+        match &self.window {
+            WindowRef::Running(window, _) => window.outer_position().into(),
+            WindowRef::Headless(window, _) => window.position,
+        }
     }
 
     /// Sets the current outer position of the window.
     pub fn set_outer_position(&self, position: Point<Px>) {
-        self.window.set_outer_position(position.into());
+        if let WindowRef::Running(window, _) = &self.window {
+            window.set_outer_position(position.into());
+        }
     }
 
     /// Returns the current DPI scale of the window.
+    ///
+    /// This scale does not include Kludgine's zoom or
+    /// [scale override](Kludgine::set_scale_override). To convert event
+    /// coordinates into the units graphics are drawn with, use
+    /// [`Kludgine::display_scale()`].
     #[must_use]
     pub fn scale(&self) -> f64 {
-        self.window.scale()
+        match &self.window {
+            WindowRef::Running(window, _) => window.scale(),
+            WindowRef::Headless(window, _) => window.scale,
+        }
     }
 
     /// Returns the inner size of the window.
     #[must_use]
     pub fn inner_size(&self) -> Size<UPx> {
-        self.window.inner_size().into()
+        match &self.window {
+            WindowRef::Running(window, _) => window.inner_size().into(),
+            WindowRef::Headless(window, _) => window.size,
+        }
     }
 
     /// Sets the inner size of the window.
@@ -377,64 +666,163 @@ where
             **pending_inner_size = Some(inner_size);
             None
         } else {
-            self.window
-                .request_inner_size(inner_size.into())
-                .map(Size::from)
+            match &mut self.window {
+                WindowRef::Running(window, _) => {
+                    window.request_inner_size(inner_size.into()).map(Size::from)
+                }
+                WindowRef::Headless(window, _) => {
+                    window.size = inner_size;
+                    Some(inner_size)
+                }
+            }
         }
     }
 
     /// Returns the size of the window, including decorations.
     #[must_use]
     pub fn outer_size(&self) -> Size<UPx> {
-        self.window.outer_size().into()
+        match &self.window {
+            WindowRef::Running(window, _) => window.outer_size().into(),
+            WindowRef::Headless(window, _) => window.size,
+        }
     }
 
     /// Returns true if the window is currently focused for keyboard input.
     #[must_use]
-    pub const fn focused(&self) -> bool {
-        self.window.focused()
+    pub fn focused(&self) -> bool {
+        match &self.window {
+            WindowRef::Running(window, _) => window.focused(),
+            WindowRef::Headless(window, _) => window.focused,
+        }
     }
 
     /// Returns the current user interface theme for the window.
     #[must_use]
-    pub const fn theme(&self) -> Theme {
-        self.window.theme()
+    pub fn theme(&self) -> Theme {
+        match &self.window {
+            WindowRef::Running(window, _) => window.theme(),
+            WindowRef::Headless(window, _) => window.theme,
+        }
     }
 
     /// Returns true if the window is currenly not visible because it is
     /// completely hidden behind other windows, offcreen, or minimized.
     #[must_use]
-    pub const fn occluded(&self) -> bool {
-        self.window.occluded()
+    pub fn occluded(&self) -> bool {
+        match &self.window {
+            WindowRef::Running(window, _) => window.occluded(),
+            WindowRef::Headless(window, _) => window.occluded,
+        }
     }
 
     /// Returns the current title of the window.
     #[must_use]
     pub fn title(&self) -> String {
-        self.window.title()
+        match &self.window {
+            WindowRef::Running(window, _) => window.title(),
+            WindowRef::Headless(window, _) => window.title.clone(),
+        }
     }
 
     /// Sets the title of the window.
     pub fn set_title(&mut self, new_title: &str) {
-        self.window.set_title(new_title);
+        match &mut self.window {
+            WindowRef::Running(window, _) => window.set_title(new_title),
+            WindowRef::Headless(window, _) => new_title.clone_into(&mut window.title),
+        }
     }
 
     /// Sets whether IME input is allowed on the window.
     pub fn set_ime_allowed(&self, allowed: bool) {
-        self.window.winit().set_ime_allowed(allowed);
+        if let Some(winit) = self.try_winit() {
+            winit.set_ime_allowed(allowed);
+        }
     }
 
     /// Sets the IME purpose.
     pub fn set_ime_purpose(&self, purpose: ImePurpose) {
-        self.window.winit().set_ime_purpose(purpose);
+        if let Some(winit) = self.try_winit() {
+            winit.set_ime_purpose(purpose);
+        }
     }
 
     /// Sets the cursor area for IME input suggestions.
+    ///
+    /// `area` is in window coordinates and should cover the text caret so that
+    /// the input method's candidate window is positioned next to it.
     pub fn set_ime_cursor_area(&self, area: Rect<UPx>) {
-        self.window.winit().set_ime_cursor_area(
-            PhysicalPosition::<u32>::new(area.origin.x.into(), area.origin.y.into()),
-            PhysicalSize::<u32>::new(area.size.width.into(), area.size.height.into()),
-        );
+        if let Some(winit) = self.try_winit() {
+            winit.set_ime_cursor_area(
+                PhysicalPosition::<u32>::new(area.origin.x.into(), area.origin.y.into()),
+                PhysicalSize::<u32>::new(area.size.width.into(), area.size.height.into()),
+            );
+        }
+    }
+
+    /// Sets the cursor icon displayed while the cursor is over this window.
+    pub fn set_cursor_icon(&self, icon: CursorIcon) {
+        if let Some(winit) = self.try_winit() {
+            winit.set_cursor(icon);
+        }
+    }
+
+    /// Sets a custom cursor image displayed while the cursor is over this
+    /// window.
+    ///
+    /// `hotspot` is the location within `image` that corresponds to the
+    /// cursor's position. Custom cursors must be created on the event loop, so
+    /// the cursor is applied asynchronously.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image's dimensions are not supported by the
+    /// windowing system.
+    #[cfg(feature = "image")]
+    pub fn set_custom_cursor(
+        &self,
+        image: &image::DynamicImage,
+        hotspot: Point<UPx>,
+    ) -> Result<(), BadImage> {
+        let image = image.to_rgba8();
+        let to_u16 = |value: u32| u16::try_from(value).unwrap_or(u16::MAX);
+        let (width, height) = image.dimensions();
+        let source = CustomCursor::from_rgba(
+            image.into_raw(),
+            to_u16(width),
+            to_u16(height),
+            to_u16(hotspot.x.get()),
+            to_u16(hotspot.y.get()),
+        )?;
+        if let WindowRef::Running(window, _) = &self.window {
+            let winit = window.winit().clone();
+            App(window.app()).execute(move |app| {
+                winit.set_cursor(app.create_custom_cursor(source));
+            });
+        }
+        Ok(())
+    }
+
+    /// Shows or hides the cursor while it is over this window.
+    pub fn set_cursor_visible(&self, visible: bool) {
+        if let Some(winit) = self.try_winit() {
+            winit.set_cursor_visible(visible);
+        }
+    }
+
+    /// Grabs the cursor, restricting its movement to this window.
+    ///
+    /// [`CursorGrabMode::Confined`] keeps the cursor within the window's
+    /// bounds, while [`CursorGrabMode::Locked`] keeps the cursor in place and
+    /// is useful for mouse-look controls. [`CursorGrabMode::None`] releases
+    /// the cursor.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `mode` is not supported on the current platform.
+    /// Not all platforms support both grab modes.
+    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
+        self.try_winit()
+            .map_or(Ok(()), |winit| winit.set_cursor_grab(mode))
     }
 
     /// Sets the window to redraw after a `duration`.
@@ -442,7 +830,10 @@ where
     /// If the window is already set to redraw sooner, this function does
     /// nothing.
     pub fn redraw_in(&mut self, duration: Duration) {
-        self.window.redraw_in(duration);
+        match &mut self.window {
+            WindowRef::Running(window, _) => window.redraw_in(duration),
+            WindowRef::Headless(window, _) => window.redraw_in(duration),
+        }
     }
 
     /// Sets the window to redraw at the provided time.
@@ -450,12 +841,20 @@ where
     /// If the window is already set to redraw sooner, this function does
     /// nothing.
     pub fn redraw_at(&mut self, time: Instant) {
-        self.window.redraw_at(time);
+        match &mut self.window {
+            WindowRef::Running(window, _) => window.redraw_at(time),
+            WindowRef::Headless(window, _) => {
+                window.redraw_in(time.saturating_duration_since(Instant::now()));
+            }
+        }
     }
 
     /// Sets the window to redraw as soon as it can.
     pub fn set_needs_redraw(&mut self) {
-        self.window.set_needs_redraw();
+        match &mut self.window {
+            WindowRef::Running(window, _) => window.set_needs_redraw(),
+            WindowRef::Headless(window, _) => window.redraw_in(Duration::ZERO),
+        }
     }
 
     /// Returns the duration that has elapsed since the last frame start and the
@@ -478,35 +877,147 @@ where
     /// Returns the position of the mouse cursor within this window, if the
     /// cursor is currently above the window.
     pub fn cursor_position(&self) -> Option<Point<Px>> {
-        self.window.cursor_position().map(Point::from)
+        self.mouse().position()
     }
 
     /// Returns true if the given button is currently pressed.
     #[must_use]
     pub fn mouse_button_pressed(&self, button: MouseButton) -> bool {
-        self.window.mouse_button_pressed(&button)
+        self.mouse().is_pressed(button)
     }
 
     /// Returns true if the given virtual key code is currently pressed.
     #[must_use]
     pub fn key_pressed(&self, key: impl Into<PhysicalKey>) -> bool {
-        self.window.key_pressed(&key.into())
+        self.keyboard().is_pressed(key)
     }
 
     /// Returns currently active modifiers.
     #[must_use]
     pub fn modifiers(&self) -> Modifiers {
-        self.window.modifiers()
+        self.keyboard().modifiers()
+    }
+
+    /// Returns the state of the keyboard for this window.
+    ///
+    /// The returned state is kept up to date as keyboard events are received,
+    /// making it suitable for checking which keys are held while preparing a
+    /// frame.
+    #[must_use]
+    pub fn keyboard(&self) -> &KeyboardState {
+        match &self.window {
+            WindowRef::Running(_, input) => &input.keyboard,
+            WindowRef::Headless(window, _) => &window.input.keyboard,
+        }
+    }
+
+    /// Returns the state of the mouse for this window.
+    ///
+    /// The returned state is kept up to date as mouse events are received.
+    #[must_use]
+    pub fn mouse(&self) -> &MouseState {
+        match &self.window {
+            WindowRef::Running(_, input) => &input.mouse,
+            WindowRef::Headless(window, _) => &window.input.mouse,
+        }
     }
 
     /// Sets the window's minimum inner size.
     pub fn set_min_inner_size(&self, min_size: Option<Size<UPx>>) {
-        self.window.set_min_inner_size(min_size.map(Into::into));
+        if let WindowRef::Running(window, _) = &self.window {
+            window.set_min_inner_size(min_size.map(Into::into));
+        }
     }
 
     /// Sets the window's maximum inner size.
     pub fn set_max_inner_size(&self, max_size: Option<Size<UPx>>) {
-        self.window.set_max_inner_size(max_size.map(Into::into));
+        if let WindowRef::Running(window, _) = &self.window {
+            window.set_max_inner_size(max_size.map(Into::into));
+        }
+    }
+
+    /// Returns the monitor the window is currently on, if it can be determined.
+    #[must_use]
+    pub fn current_monitor(&self) -> Option<Monitor> {
+        self.try_winit()
+            .and_then(|winit| winit.current_monitor())
+            .map(Monitor)
+    }
+
+    /// Returns the current fullscreen mode of the window, or `None` if the
+    /// window is not fullscreen.
+    #[must_use]
+    pub fn fullscreen(&self) -> Option<Fullscreen> {
+        self.try_winit()
+            .and_then(|winit| winit.fullscreen())
+            .map(Fullscreen::from)
+    }
+
+    /// Sets the fullscreen mode of the window. Passing `None` restores the
+    /// window to its windowed state.
+    pub fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        if let Some(window) = self.try_winit() {
+            window.set_fullscreen(fullscreen.map(winit::window::Fullscreen::from));
+        }
+    }
+
+    /// Toggles between the windowed state and borderless fullscreen on the
+    /// window's current monitor.
+    pub fn toggle_fullscreen(&self) {
+        if self.fullscreen().is_some() {
+            self.set_fullscreen(None);
+        } else {
+            self.set_fullscreen(Some(Fullscreen::Borderless(None)));
+        }
+    }
+
+    /// Returns true if the window is maximized.
+    #[must_use]
+    pub fn maximized(&self) -> bool {
+        self.try_winit().map_or(false, |winit| winit.is_maximized())
+    }
+
+    /// Maximizes or restores the window.
+    pub fn set_maximized(&self, maximized: bool) {
+        if let Some(winit) = self.try_winit() {
+            winit.set_maximized(maximized);
+        }
+    }
+
+    /// Returns true if the window is minimized, or `None` if the platform is
+    /// unable to determine whether the window is minimized.
+    #[must_use]
+    pub fn minimized(&self) -> Option<bool> {
+        self.try_winit()
+            .map_or(Some(false), |winit| winit.is_minimized())
+    }
+
+    /// Minimizes or restores the window.
+    pub fn set_minimized(&self, minimized: bool) {
+        if let Some(winit) = self.try_winit() {
+            winit.set_minimized(minimized);
+        }
+    }
+
+    /// Requests the user's attention for this window. Passing `None` cancels a
+    /// previous request.
+    ///
+    /// The effect of this request is platform specific. For example, the
+    /// taskbar entry may flash or the application icon may bounce.
+    pub fn request_user_attention(&self, request: Option<UserAttentionType>) {
+        if let Some(winit) = self.try_winit() {
+            winit.request_user_attention(request);
+        }
+    }
+
+    /// Returns a handle to the system clipboard.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the system clipboard cannot be accessed.
+    #[cfg(feature = "clipboard")]
+    pub fn clipboard(&self) -> Result<Clipboard, ClipboardError> {
+        Clipboard::shared()
     }
 }
 
@@ -542,6 +1053,31 @@ where
         WindowAttributes::default()
     }
 
+    /// Returns the window that this window should be a child of, if any.
+    ///
+    /// Modal children block keyboard, mouse, and touch input to their parent
+    /// until they are closed. Input received by the parent while blocked
+    /// focuses the modal child instead.
+    #[must_use]
+    #[allow(unused_variables)]
+    fn parent_window(context: &Self::Context) -> Option<ParentWindow> {
+        None
+    }
+
+    /// Returns the monitor to open this window on and how to place it, if the
+    /// platform's default placement should not be used.
+    ///
+    /// `monitors` contains the monitors connected when the window is created.
+    /// The placement is applied before `wgpu` is initialized for this window.
+    #[must_use]
+    #[allow(unused_variables)]
+    fn initial_placement(
+        context: &Self::Context,
+        monitors: &Monitors,
+    ) -> Option<(Monitor, WindowPlacement)> {
+        None
+    }
+
     /// Returns the power preference to initialize `wgpu` with.
     #[must_use]
     #[allow(unused_variables)]
@@ -563,16 +1099,98 @@ where
         wgpu::Limits::downlevel_webgl2_defaults().using_resolution(adapter_limits)
     }
 
+    /// Returns the `wgpu` features that must be enabled in addition to
+    /// [`Kludgine::REQURED_FEATURES`].
+    ///
+    /// The window fails to open if the graphics adapter does not support every
+    /// required feature. The enabled features can be checked using
+    /// [`Graphics::device()`](crate::Graphics::device).
+    #[must_use]
+    #[allow(unused_variables)]
+    fn required_features(context: &Self::Context) -> wgpu::Features {
+        wgpu::Features::empty()
+    }
+
+    /// Returns the `wgpu` features to enable if the graphics adapter supports
+    /// them.
+    ///
+    /// The enabled features can be checked using
+    /// [`Graphics::device()`](crate::Graphics::device).
+    #[must_use]
+    #[allow(unused_variables)]
+    fn optional_features(context: &Self::Context) -> wgpu::Features {
+        wgpu::Features::empty()
+    }
+
+    /// Returns the backends `wgpu` may choose an adapter from.
+    #[must_use]
+    #[allow(unused_variables)]
+    fn backends(context: &Self::Context) -> wgpu::Backends {
+        wgpu::Backends::all()
+    }
+
+    /// Returns true if `wgpu` must choose a fallback adapter, such as a
+    /// software renderer.
+    #[must_use]
+    #[allow(unused_variables)]
+    fn force_fallback_adapter(context: &Self::Context) -> bool {
+        false
+    }
+
     /// Returns the number of multisamples to perform when rendering this
     /// window.
     ///
-    /// When 1 is returned, multisampling will be fully disabled.
+    /// When 1 is returned, multisampling will be fully disabled. When the
+    /// graphics device does not support the returned count, the largest
+    /// supported count that is less than it is used instead. The count can
+    /// be changed after the window is opened using
+    /// [`Kludgine::set_multisample()`].
     #[must_use]
     #[allow(unused_variables)]
     fn multisample_count(context: &Self::Context) -> NonZeroU32 {
         NonZeroU32::new(4).assert("4 is less than u32::MAX")
     }
 
+    /// Returns true if this window should render with a depth buffer.
+    ///
+    /// When enabled, [`Drawable::z`](crate::Drawable::z) controls which
+    /// graphics are drawn above others, regardless of the order they are
+    /// drawn in. Custom pipelines must support
+    /// [`Kludgine::DEPTH_FORMAT`] depth attachments.
+    ///
+    /// The default implementation returns false.
+    #[must_use]
+    #[allow(unused_variables)]
+    fn depth_testing(context: &Self::Context) -> bool {
+        false
+    }
+
+    /// Returns true if this window should only redraw the regions that have
+    /// changed between frames.
+    ///
+    /// When enabled, the window's contents are preserved between frames, and
+    /// only the regions reported using [`Graphics::add_damage()`] during
+    /// [`prepare()`](Self::prepare) are redrawn. Damaged regions are filled
+    /// with [`clear_color()`](Self::clear_color) before rendering. If no
+    /// damage is reported, the entire window is redrawn.
+    ///
+    /// The entire window is also redrawn when the previous frame's contents
+    /// are unavailable, such as after the window is resized, or when the
+    /// clear color is not opaque.
+    /// [`RenderingGraphics::damaged_region()`] returns the region being
+    /// redrawn while a partial redraw is active.
+    ///
+    /// Without multisampling, the window's contents are preserved in an
+    /// additional texture, which requires the surface to support
+    /// [`wgpu::TextureUsages::COPY_DST`].
+    ///
+    /// The default implementation returns false.
+    #[must_use]
+    #[allow(unused_variables)]
+    fn partial_redraw(context: &Self::Context) -> bool {
+        false
+    }
+
     /// Executed once after the window has been fully initialized.
     #[allow(unused_variables)]
     fn initialized(&mut self, window: Window<'_, WindowEvent>, kludgine: &mut Kludgine) {}
@@ -591,6 +1209,67 @@ where
         graphics: &mut RenderingGraphics<'_, 'pass>,
     );
 
+    /// Returns true if panics that occur in [`prepare()`](Self::prepare) or
+    /// [`render()`](Self::render) should be recovered from.
+    ///
+    /// When a panic is recovered from, the frame is aborted,
+    /// [`panicked()`](Self::panicked) is invoked, and an overlay showing the
+    /// panic message is displayed until the window is clicked or a key is
+    /// pressed. Afterwards, rendering resumes normally.
+    ///
+    /// When false, the panic continues to unwind after the frame is aborted.
+    /// The default implementation returns false.
+    #[must_use]
+    fn recover_from_panics(&self) -> bool {
+        false
+    }
+
+    /// A panic with `message` was recovered from while preparing or rendering
+    /// this window.
+    ///
+    /// This is only invoked when
+    /// [`recover_from_panics()`](Self::recover_from_panics) returns true.
+    #[allow(unused_variables)]
+    fn panicked(
+        &mut self,
+        window: Window<'_, WindowEvent>,
+        kludgine: &mut Kludgine,
+        message: &str,
+    ) {
+    }
+
+    /// The graphics device reported `error` while this window was preparing or
+    /// rendering a frame.
+    ///
+    /// # Panics
+    ///
+    /// The default implementation panics with the error, which matches wgpu's
+    /// behavior for errors that are not captured. If
+    /// [`recover_from_panics()`](Self::recover_from_panics) returns true, the
+    /// panic is recovered from like any other panic while rendering.
+    #[allow(unused_variables)]
+    fn gpu_error(
+        &mut self,
+        window: Window<'_, WindowEvent>,
+        kludgine: &mut Kludgine,
+        error: Error,
+    ) {
+        panic!("{error}");
+    }
+
+    /// The graphics device was lost and has been replaced by a new device.
+    ///
+    /// The window's [`Kludgine`] instance keeps its fonts, text attributes,
+    /// scale, and settings, and its own GPU resources are
+    /// [recreated](Kludgine::recreate_gpu_resources) before this function is
+    /// called. All other resources created using the previous device,
+    /// including textures, prepared graphics, and [`Drawing`]s, can no longer
+    /// be used and must be recreated using `graphics`. Glyph atlases that were
+    /// [shared](Kludgine::set_glyph_atlas) with other instances must also be
+    /// shared again.
+    #[allow(unused_variables)]
+    fn gpu_reset(&mut self, window: Window<'_, WindowEvent>, graphics: &mut Graphics<'_>) {}
+
     /// Returns the swap chain present mode to use for this window.
     #[must_use]
     fn present_mode(&self) -> wgpu::PresentMode {
@@ -619,6 +1298,61 @@ where
         supported_modes[0]
     }
 
+    /// Returns true if this window should continue rendering while it is
+    /// occluded or minimized.
+    ///
+    /// When false, redraws requested while the window is hidden are skipped,
+    /// which stops animations from rendering frames nobody can see. A single
+    /// redraw is performed once the window becomes visible again.
+    ///
+    /// The default implementation returns true while
+    /// [`mirror()`](Self::mirror) returns a value, ensuring mirrored frames
+    /// continue to be produced, and false otherwise.
+    #[must_use]
+    fn render_while_occluded(&self) -> bool {
+        self.mirror().is_some()
+    }
+
+    /// Returns the options for mirroring each presented frame into a texture,
+    /// or `None` to disable mirroring.
+    ///
+    /// Mirroring copies the presented frame rather than rendering the scene a
+    /// second time, which makes it suitable for feeding virtual cameras or
+    /// streaming. Mirroring is unavailable if the window's surface does not
+    /// support [`wgpu::TextureUsages::COPY_SRC`].
+    ///
+    /// The default implementation returns `None`.
+    #[must_use]
+    fn mirror(&self) -> Option<FrameMirror> {
+        None
+    }
+
+    /// A frame has been presented and copied into `frame`.
+    ///
+    /// This is only invoked while [`mirror()`](Self::mirror) returns a value.
+    /// The same texture is reused until the window's size or format changes.
+    #[allow(unused_variables)]
+    fn frame_mirrored(
+        &mut self,
+        window: Window<'_, WindowEvent>,
+        kludgine: &mut Kludgine,
+        frame: &SharedTexture,
+    ) {
+    }
+
+    /// A copy of a mirrored frame has been read back from the GPU.
+    ///
+    /// This is invoked at most once per [`FrameMirror::capture_interval`].
+    /// Reading the frame blocks until the GPU has finished rendering it.
+    #[allow(unused_variables)]
+    fn frame_captured(
+        &mut self,
+        window: Window<'_, WindowEvent>,
+        kludgine: &mut Kludgine,
+        frame: CapturedFrame,
+    ) {
+    }
+
     /// Launches a Kludgine app using this window as the primary window.
     ///
     /// # Panics
@@ -679,7 +1413,7 @@ where
     {
         KludgineWindow::<Self>::new(app, <Self::Context>::default())
             .open()
-            .map(|opt| opt.map(WindowHandle))
+            .map(|opt| opt.map(WindowHandle::new))
     }
 
     /// Opens a new window with the provided [`Context`](Self::Context). The
@@ -701,7 +1435,7 @@ where
     {
         KludgineWindow::<Self>::new(app, context)
             .open()
-            .map(|opt| opt.map(WindowHandle))
+            .map(|opt| opt.map(WindowHandle::new))
     }
 
     /// The window has been requested to be closed. This can happen as a result
@@ -725,11 +1459,18 @@ where
 
     /// The window has been occluded or revealed. [`Window::occluded()`] returns
     /// the current state.
+    ///
+    /// Unless [`render_while_occluded()`](Self::render_while_occluded)
+    /// returns true, redraws requested while the window is occluded are
+    /// deferred until it is revealed.
     #[allow(unused_variables)]
     fn occlusion_changed(&mut self, window: Window<'_, WindowEvent>, kludgine: &mut Kludgine) {}
 
     /// The window's scale factor has changed. [`Window::scale()`] returns the
     /// current scale.
+    ///
+    /// The window's surface and `kludgine` have already been updated to match
+    /// the window's new size and scale when this function is invoked.
     #[allow(unused_variables)]
     fn scale_factor_changed(&mut self, window: Window<'_, WindowEvent>, kludgine: &mut Kludgine) {}
 
@@ -749,23 +1490,35 @@ where
     fn theme_changed(&mut self, window: Window<'_, WindowEvent>, kludgine: &mut Kludgine) {}
 
     /// A file has been dropped on the window.
+    ///
+    /// `position` is the last known location of the cursor within the window,
+    /// measured in physical pixels. This is only invoked for files that were accepted by
+    /// [`hovered_file()`](Self::hovered_file).
     #[allow(unused_variables)]
     fn dropped_file(
         &mut self,
         window: Window<'_, WindowEvent>,
         kludgine: &mut Kludgine,
         path: PathBuf,
+        position: Option<Point<Px>>,
     ) {
     }
 
     /// A file is hovering over the window.
+    ///
+    /// `position` is the last known location of the cursor within the window,
+    /// measured in physical pixels. Returning [`FileDrop::Reject`] prevents
+    /// [`dropped_file()`](Self::dropped_file) from being invoked if the file
+    /// is dropped.
     #[allow(unused_variables)]
     fn hovered_file(
         &mut self,
         window: Window<'_, WindowEvent>,
         kludgine: &mut Kludgine,
         path: PathBuf,
-    ) {
+        position: Option<Point<Px>>,
+    ) -> FileDrop {
+        FileDrop::Accept
     }
 
     /// A file being overed has been cancelled.
@@ -784,6 +1537,15 @@ where
     }
 
     /// A keyboard event occurred while the window was focused.
+    ///
+    /// When [`focus_chain()`](Self::focus_chain) returns a non-empty chain,
+    /// presses of Tab and Shift+Tab traverse the chain and are reported to
+    /// [`focus_traversed()`](Self::focus_traversed) instead of this function.
+    ///
+    /// `is_synthetic` is true when winit generated the event, such as for
+    /// keys that were already held when the window gained focus. Input
+    /// delivered as [`SyntheticInput`] is reported the same as input from the
+    /// operating system.
     #[allow(unused_variables)]
     fn keyboard_input(
         &mut self,
@@ -801,10 +1563,75 @@ where
     fn modifiers_changed(&mut self, window: Window<'_, WindowEvent>, kludgine: &mut Kludgine) {}
 
     /// An international input even thas occurred for the window.
+    ///
+    /// IME events are only delivered after
+    /// [`Window::set_ime_allowed(true)`](Window::set_ime_allowed) has been
+    /// called.
+    ///
+    /// The provided implementation invokes
+    /// [`ime_enabled()`](Self::ime_enabled),
+    /// [`ime_preedit()`](Self::ime_preedit),
+    /// [`ime_commit()`](Self::ime_commit), or
+    /// [`ime_disabled()`](Self::ime_disabled) depending on the event.
+    fn ime(&mut self, window: Window<'_, WindowEvent>, kludgine: &mut Kludgine, ime: Ime) {
+        match ime {
+            Ime::Enabled => self.ime_enabled(window, kludgine),
+            Ime::Preedit(text, cursor) => self.ime_preedit(
+                window,
+                kludgine,
+                &text,
+                cursor.map(|(start, end)| start..end),
+            ),
+            Ime::Commit(text) => self.ime_commit(window, kludgine, text),
+            Ime::Disabled => self.ime_disabled(window, kludgine),
+        }
+    }
+
+    /// The input method has been enabled for this window.
+    ///
+    /// This is a good time to call [`Window::set_ime_cursor_area()`] so that
+    /// the candidate window appears next to the text caret.
+    #[allow(unused_variables)]
+    fn ime_enabled(&mut self, window: Window<'_, WindowEvent>, kludgine: &mut Kludgine) {}
+
+    /// The input method's composition text has been updated.
+    ///
+    /// `text` should be displayed at the caret as a preview without being
+    /// inserted. `cursor` is the byte range within `text` that the input
+    /// method has selected, or `None` if the cursor should be hidden. An empty
+    /// `text` indicates the preview should be cleared.
+    #[allow(unused_variables)]
+    fn ime_preedit(
+        &mut self,
+        window: Window<'_, WindowEvent>,
+        kludgine: &mut Kludgine,
+        text: &str,
+        cursor: Option<Range<usize>>,
+    ) {
+    }
+
+    /// The input method has committed `text`, which should be inserted at the
+    /// caret.
+    #[allow(unused_variables)]
+    fn ime_commit(
+        &mut self,
+        window: Window<'_, WindowEvent>,
+        kludgine: &mut Kludgine,
+        text: String,
+    ) {
+    }
+
+    /// The input method has been disabled for this window.
+    ///
+    /// Any composition preview being displayed should be cleared.
     #[allow(unused_variables)]
-    fn ime(&mut self, window: Window<'_, WindowEvent>, kludgine: &mut Kludgine, ime: Ime) {}
+    fn ime_disabled(&mut self, window: Window<'_, WindowEvent>, kludgine: &mut Kludgine) {}
 
     /// A cursor has moved over the window.
+    ///
+    /// `position` is measured in physical pixels.
+    /// `kludgine.display_scale().position_to_logical(position)` converts it
+    /// into the logical units graphics are drawn with.
     #[allow(unused_variables)]
     fn cursor_moved(
         &mut self,
@@ -859,6 +1686,66 @@ where
     ) {
     }
 
+    /// A mouse button was pressed while the cursor was above the window.
+    ///
+    /// This is invoked after [`WindowBehavior::mouse_input`].
+    /// [`Click::count`] is 2 for a double click, 3 for a triple click, and so
+    /// on, as determined by [`WindowBehavior::pointer_settings`].
+    #[allow(unused_variables)]
+    fn mouse_clicked(
+        &mut self,
+        window: Window<'_, WindowEvent>,
+        kludgine: &mut Kludgine,
+        device_id: DeviceId,
+        click: Click,
+    ) {
+    }
+
+    /// The cursor moved farther than [`PointerSettings::drag_threshold`] while
+    /// a mouse button was pressed.
+    ///
+    /// This is invoked after [`WindowBehavior::cursor_moved`], and is only
+    /// invoked once per button press.
+    #[allow(unused_variables)]
+    fn drag_started(
+        &mut self,
+        window: Window<'_, WindowEvent>,
+        kludgine: &mut Kludgine,
+        device_id: DeviceId,
+        drag: DragStart,
+    ) {
+    }
+
+    /// Returns the settings used to recognize multiple clicks and drags.
+    #[must_use]
+    fn pointer_settings(&self) -> PointerSettings {
+        PointerSettings::default()
+    }
+
+    /// Returns the chain of focusable regions in this window, if keyboard
+    /// focus traversal is desired.
+    ///
+    /// When a non-empty chain is returned, pressing Tab focuses the next
+    /// region and pressing Shift+Tab focuses the previous region. Each
+    /// traversal is reported to [`focus_traversed()`](Self::focus_traversed).
+    ///
+    /// The default implementation returns `None`.
+    fn focus_chain(&mut self) -> Option<&mut FocusChain> {
+        None
+    }
+
+    /// Keyboard focus has moved between the regions of this window's
+    /// [`focus_chain()`](Self::focus_chain) due to Tab or Shift+Tab being
+    /// pressed.
+    #[allow(unused_variables)]
+    fn focus_traversed(
+        &mut self,
+        window: Window<'_, WindowEvent>,
+        kludgine: &mut Kludgine,
+        traversal: FocusTraversal,
+    ) {
+    }
+
     /// A pressure-sensitive touchpad was touched.
     #[allow(unused_variables)]
     fn touchpad_pressure(
@@ -944,6 +1831,29 @@ where
     }
 }
 
+/// Whether a file being dragged over a window should be accepted if dropped.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Default)]
+pub enum FileDrop {
+    /// The file will be delivered to
+    /// [`WindowBehavior::dropped_file`] if it is dropped.
+    #[default]
+    Accept,
+    /// The file will be ignored if it is dropped.
+    Reject,
+}
+
+/// Whether a raw event observed by [`PendingApp::on_raw_event`] should
+/// continue to be processed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Default)]
+pub enum RawEventHandling {
+    /// The event will be processed by Kludgine and delivered to the window's
+    /// [`WindowBehavior`].
+    #[default]
+    Propagate,
+    /// The event will not be processed any further.
+    Consume,
+}
+
 /// A Kludgine application event.
 pub struct AppEvent<User>(AppEventKind<User>);
 
@@ -1030,10 +1940,9 @@ impl Monitor {
     }
 
     /// Returns the DPI scaling factor applied to this monitor.
-    #[allow(clippy::cast_possible_truncation)]
     #[must_use]
     pub fn scale_factor(&self) -> Fraction {
-        Fraction::from(self.0.scale_factor() as f32)
+        DisplayScale::from_factor(self.0.scale_factor()).get()
     }
 
     /// Returns the refresh rate of this display, in millihertz.
@@ -1042,6 +1951,13 @@ impl Monitor {
         self.0.refresh_rate_millihertz()
     }
 
+    /// Returns the refresh rate of this display, in hertz.
+    #[must_use]
+    pub fn refresh_rate_hertz(&self) -> Option<f32> {
+        self.refresh_rate_millihertz()
+            .map(|millihertz| millihertz.cast::<f32>() / 1000.)
+    }
+
     /// Returns an iterator of the video modes supported by this monitor.
     pub fn video_modes(&self) -> impl Iterator<Item = VideoMode> {
         self.0.video_modes().map(VideoMode)
@@ -1054,6 +1970,37 @@ impl Monitor {
     }
 }
 
+/// A fullscreen mode for a window.
+#[derive(Clone, Debug)]
+pub enum Fullscreen {
+    /// The window covers the monitor without changing its video mode. If
+    /// `None`, the window's current monitor is used.
+    Borderless(Option<Monitor>),
+    /// The window takes exclusive control of a monitor using a specific video
+    /// mode.
+    Exclusive(VideoMode),
+}
+
+impl From<Fullscreen> for winit::window::Fullscreen {
+    fn from(fullscreen: Fullscreen) -> Self {
+        match fullscreen {
+            Fullscreen::Borderless(monitor) => Self::Borderless(monitor.map(|m| m.0)),
+            Fullscreen::Exclusive(mode) => Self::Exclusive(mode.0),
+        }
+    }
+}
+
+impl From<winit::window::Fullscreen> for Fullscreen {
+    fn from(fullscreen: winit::window::Fullscreen) -> Self {
+        match fullscreen {
+            winit::window::Fullscreen::Borderless(monitor) => {
+                Self::Borderless(monitor.map(Monitor))
+            }
+            winit::window::Fullscreen::Exclusive(mode) => Self::Exclusive(VideoMode(mode)),
+        }
+    }
+}
+
 /// A specific video mode for a [`Monitor`].
 #[derive(Clone, Debug)]
 pub struct VideoMode(VideoModeHandle);
@@ -1109,7 +2056,16 @@ where
 {
     type Error = UnrecoverableError;
     type Response = AppResponse;
-    type Window = User;
+    type Window = WindowMessage<User>;
+}
+
+/// A message sent to a window through a [`WindowHandle`].
+pub struct WindowMessage<User>(WindowMessageKind<User>);
+
+enum WindowMessageKind<User> {
+    User(User),
+    Redraw(Arc<AtomicBool>),
+    Input(SyntheticInput),
 }
 
 struct KludgineWindow<Behavior> {
@@ -1117,14 +2073,40 @@ struct KludgineWindow<Behavior> {
     kludgine: Kludgine,
     last_render: Instant,
     last_render_duration: Duration,
+    rejected_files: HashSet<PathBuf>,
+    opened_at: Instant,
+    pointer: PointerTracker,
+    input: WindowInput,
 
     config: wgpu::SurfaceConfiguration,
     surface: wgpu::Surface<'static>,
     msaa_texture: Option<wgpu::Texture>,
+    depth_texture: Option<wgpu::Texture>,
+    /// The texture the window's contents are preserved in when partially
+    /// redrawing without multisampling.
+    retained_texture: Option<wgpu::Texture>,
+    /// True if the previous frame is preserved in the multisampled or
+    /// retained texture.
+    retained_contents: bool,
     queue: wgpu::Queue,
     wgpu: Arc<wgpu::Instance>,
     device: wgpu::Device,
     multisample_count: u32,
+    depth_testing: bool,
+    partial_redraw: bool,
+    /// True if a redraw was skipped because the window was hidden.
+    redraw_deferred: bool,
+    _registration: WindowRegistration,
+    panic_overlay: Option<PanicOverlay>,
+    mirror: Option<SharedTexture>,
+    last_capture: Option<Instant>,
+    device_lost: Arc<AtomicBool>,
+    power_preference: wgpu::PowerPreference,
+    force_fallback_adapter: bool,
+    required_features: wgpu::Features,
+    optional_features: wgpu::Features,
+    limits: wgpu::Limits,
+    memory_hints: wgpu::MemoryHints,
 }
 
 impl<Behavior> KludgineWindow<Behavior> {
@@ -1209,28 +2191,49 @@ impl<Behavior> KludgineWindow<Behavior> {
         Behavior: WindowBehavior<User> + 'static,
         User: Send + 'static,
     {
+        self.kludgine.animations_mut().advance(elapsed);
+        self.kludgine.clock_mut().tick(elapsed);
         let mut frame = self.kludgine.next_frame();
         let mut pending_inner_size = None;
 
-        self.behavior.prepare(
-            Window::new_in_frame(
-                window,
-                elapsed,
-                self.last_render_duration,
-                &mut pending_inner_size,
-            ),
-            &mut frame.prepare(&self.device, &self.queue),
-        );
+        if let Some(overlay) = &mut self.panic_overlay {
+            overlay.prepare(&mut frame.prepare(&self.device, &self.queue));
+        } else if let Err(panic) = panic::catch_unwind(AssertUnwindSafe(|| {
+            self.behavior.prepare(
+                Window::new_in_frame(
+                    window,
+                    &self.input,
+                    elapsed,
+                    self.last_render_duration,
+                    &mut pending_inner_size,
+                ),
+                &mut frame.prepare(&self.device, &self.queue),
+            );
+        })) {
+            frame.abort();
+            self.recover_from_panic(window, panic);
+            return pending_inner_size;
+        }
 
         let surface_view = surface
             .texture
             .create_view(&wgpu::TextureViewDescriptor::default());
+        let retains_contents = self.partial_redraw
+            && (self.multisample_count > 1
+                || self.config.usage.contains(wgpu::TextureUsages::COPY_DST));
+        // The behavior may have changed the multisample count while preparing.
+        if frame.kludgine.multisample.count != self.multisample_count {
+            self.multisample_count = frame.kludgine.multisample.count;
+            self.msaa_texture = None;
+            self.retained_contents = false;
+        }
         let (view, resolve_target) = if self.multisample_count > 1 {
             if self.msaa_texture.as_ref().map_or(true, |msaa| {
                 msaa.width() != surface.texture.width() || msaa.height() != surface.texture.height()
             }) {
+                self.retained_contents = false;
                 self.msaa_texture = Some(self.device.create_texture(&wgpu::TextureDescriptor {
-                    label: None,
+                    label: Some("kludgine::surface (multisampled)"),
                     size: wgpu::Extent3d {
                         width: surface.texture.width(),
                         height: surface.texture.height(),
@@ -1245,62 +2248,500 @@ impl<Behavior> KludgineWindow<Behavior> {
                 }));
             }
 
-            (
-                self.msaa_texture
-                    .as_ref()
-                    .assert("always initialized")
-                    .create_view(&wgpu::TextureViewDescriptor::default()),
-                Some(surface_view),
-            )
-        } else {
-            (surface_view, None)
-        };
+            (
+                self.msaa_texture
+                    .as_ref()
+                    .assert("always initialized")
+                    .create_view(&wgpu::TextureViewDescriptor::default()),
+                Some(surface_view),
+            )
+        } else if retains_contents {
+            if self.retained_texture.as_ref().map_or(true, |retained| {
+                retained.width() != surface.texture.width()
+                    || retained.height() != surface.texture.height()
+            }) {
+                self.retained_contents = false;
+                self.retained_texture =
+                    Some(self.device.create_texture(&wgpu::TextureDescriptor {
+                        label: Some("kludgine::surface (retained)"),
+                        size: surface.texture.size(),
+                        mip_level_count: 1,
+                        sample_count: 1,
+                        dimension: wgpu::TextureDimension::D2,
+                        format: surface.texture.format(),
+                        usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                            | wgpu::TextureUsages::COPY_SRC,
+                        view_formats: &[],
+                    }));
+            }
+
+            (
+                self.retained_texture
+                    .as_ref()
+                    .assert("always initialized")
+                    .create_view(&wgpu::TextureViewDescriptor::default()),
+                None,
+            )
+        } else {
+            (surface_view, None)
+        };
+
+        let clear_color = if self.panic_overlay.is_some() {
+            Some(PanicOverlay::BACKGROUND)
+        } else {
+            self.behavior.clear_color()
+        };
+        let partial = redraws_partially(
+            std::mem::take(&mut self.retained_contents),
+            self.panic_overlay.is_some(),
+            clear_color,
+        );
+        if !partial {
+            frame.kludgine.damage = None;
+        }
+        let damage_fill = prepare_damage_fill(&mut frame, clear_color, &self.device, &self.queue);
+
+        let color_attachments = [Some(wgpu::RenderPassColorAttachment {
+            view: &view,
+            resolve_target: resolve_target.as_ref(),
+            ops: wgpu::Operations {
+                load: clear_color
+                    .filter(|_| !partial)
+                    .map_or(wgpu::LoadOp::Load, |color| {
+                        wgpu::LoadOp::Clear(color.into())
+                    }),
+                store: wgpu::StoreOp::Store,
+            },
+        })];
+        let depth_view = self.depth_testing.then(|| {
+            frame
+                .kludgine
+                .depth_view(&mut self.depth_texture, &self.device)
+        });
+        let mut gfx = frame.render(
+            &wgpu::RenderPassDescriptor {
+                label: Some("kludgine::render surface"),
+                color_attachments: &color_attachments,
+                depth_stencil_attachment: depth_view.as_ref().map(Kludgine::depth_attachment),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            },
+            &self.device,
+            &self.queue,
+        );
+        if let Some(fill) = &damage_fill {
+            fill.render(&mut gfx);
+        }
+        let rendered = if let Some(overlay) = &self.panic_overlay {
+            overlay.render(&mut gfx);
+            Ok(())
+        } else {
+            panic::catch_unwind(AssertUnwindSafe(|| {
+                self.behavior.render(
+                    Window::new_in_frame(
+                        window,
+                        &self.input,
+                        elapsed,
+                        self.last_render_duration,
+                        &mut pending_inner_size,
+                    ),
+                    &mut gfx,
+                );
+            }))
+        };
+        drop(gfx);
+        if let Err(panic) = rendered {
+            frame.abort();
+            self.recover_from_panic(window, panic);
+            return pending_inner_size;
+        }
+        if let (Some(retained), Some(commands)) = (&self.retained_texture, frame.commands.as_mut())
+        {
+            commands.copy_texture_to_texture(
+                retained.as_image_copy(),
+                surface.texture.as_image_copy(),
+                surface.texture.size(),
+            );
+        }
+        let mirrored = self
+            .behavior
+            .mirror()
+            .filter(|_| {
+                self.panic_overlay.is_none()
+                    && self.config.usage.contains(wgpu::TextureUsages::COPY_SRC)
+            })
+            .and_then(|options| {
+                let size = Size::upx(surface.texture.width(), surface.texture.height());
+                let format = surface.texture.format();
+                if self.mirror.as_ref().map_or(true, |mirror| {
+                    mirror.size() != size || mirror.format() != format
+                }) {
+                    self.mirror = Some(SharedTexture::from(Texture::new_generic(
+                        &frame.prepare(&self.device, &self.queue),
+                        1,
+                        size,
+                        format,
+                        wgpu::TextureUsages::COPY_DST
+                            | wgpu::TextureUsages::COPY_SRC
+                            | wgpu::TextureUsages::TEXTURE_BINDING,
+                        wgpu::FilterMode::Linear,
+                        Some("kludgine::surface mirror"),
+                    )));
+                }
+                let mirror = self.mirror.clone().assert("always initialized");
+                frame.commands.as_mut()?.copy_texture_to_texture(
+                    surface.texture.as_image_copy(),
+                    mirror.wgpu().as_image_copy(),
+                    surface.texture.size(),
+                );
+
+                let now = Instant::now();
+                let capture = options.capture_interval.is_some_and(|interval| {
+                    self.last_capture
+                        .map_or(true, |last| now.duration_since(last) >= interval)
+                });
+                if capture {
+                    self.last_capture = Some(now);
+                }
+                Some((mirror, capture))
+            });
+        let id = frame.submit(&self.queue);
+        self.retained_contents = retains_contents && self.panic_overlay.is_none();
+        window.winit().pre_present_notify();
+        surface.present();
+        if let Some(id) = id {
+            self.device.poll(wgpu::Maintain::WaitForSubmissionIndex(id));
+        }
+        if self.kludgine.animations().is_animating() {
+            window.set_needs_redraw();
+        }
+
+        if let Some((mirror, capture)) = mirrored {
+            self.behavior.frame_mirrored(
+                Window::new_in_frame(
+                    window,
+                    &self.input,
+                    elapsed,
+                    self.last_render_duration,
+                    &mut pending_inner_size,
+                ),
+                &mut self.kludgine,
+                &mirror,
+            );
+            if let Some(data) = capture
+                .then(|| mirror.read_pixels(&self.device, &self.queue))
+                .flatten()
+            {
+                self.behavior.frame_captured(
+                    Window::new_in_frame(
+                        window,
+                        &self.input,
+                        elapsed,
+                        self.last_render_duration,
+                        &mut pending_inner_size,
+                    ),
+                    &mut self.kludgine,
+                    CapturedFrame {
+                        size: mirror.size(),
+                        format: mirror.format(),
+                        data,
+                    },
+                );
+            }
+        }
+        pending_inner_size
+    }
+
+    /// Replaces the lost graphics device with a new one, returning false if
+    /// the window was closed because no device could be created.
+    fn recreate_device<User>(&mut self, window: &mut RunningWindow<AppEvent<User>>) -> bool
+    where
+        Behavior: WindowBehavior<User> + 'static,
+        User: Send + 'static,
+    {
+        let device = pollster::block_on(self.wgpu.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: self.power_preference,
+            force_fallback_adapter: self.force_fallback_adapter,
+            compatible_surface: Some(&self.surface),
+        }))
+        .ok_or(UnrecoverableError::NoAdapter)
+        .and_then(|adapter| {
+            let (device, queue) = pollster::block_on(adapter.request_device(
+                &wgpu::DeviceDescriptor {
+                    label: Some("kludgine::device"),
+                    required_features: device_features(
+                        self.required_features,
+                        self.optional_features,
+                        &adapter,
+                    ),
+                    required_limits: self.limits.clone(),
+                    memory_hints: self.memory_hints.clone(),
+                },
+                None,
+            ))
+            .map_err(UnrecoverableError::Device)?;
+            Ok((adapter, device, queue))
+        });
+        let (adapter, device, queue) = match device {
+            Ok(device) => device,
+            Err(err) => {
+                let _ = window.app().send_error(err);
+                window.close();
+                return false;
+            }
+        };
+
+        self.device_lost = watch_device_lost(
+            &device,
+            Window::new(
+                window,
+                &WindowInput::default(),
+                Duration::ZERO,
+                Duration::ZERO,
+            )
+            .handle(),
+        );
+        self.kludgine.recreate_gpu_resources(&device, &queue);
+        self.kludgine.set_adapter(&adapter);
+        let zoom = self.kludgine.zoom;
+        self.kludgine.resize(
+            window.inner_size().into(),
+            window.scale().cast::<f32>(),
+            zoom,
+            &queue,
+        );
+        self.surface.configure(&device, &self.config);
+        self.device = device;
+        self.queue = queue;
+        self.msaa_texture = None;
+        self.depth_texture = None;
+        self.retained_texture = None;
+        self.retained_contents = false;
+        self.mirror = None;
+
+        self.behavior.gpu_reset(
+            Window::new(
+                window,
+                &self.input,
+                self.last_render.elapsed(),
+                self.last_render_duration,
+            ),
+            &mut Graphics::new(&mut self.kludgine, &self.device, &self.queue),
+        );
+        true
+    }
+
+    /// Notifies the behavior of `error`, recovering if the behavior panics in
+    /// response.
+    fn report_gpu_error<User>(
+        &mut self,
+        window: &mut RunningWindow<AppEvent<User>>,
+        elapsed: Duration,
+        error: Error,
+    ) where
+        Behavior: WindowBehavior<User> + 'static,
+        User: Send + 'static,
+    {
+        if let Err(panic) = panic::catch_unwind(AssertUnwindSafe(|| {
+            self.behavior.gpu_error(
+                Window::new(window, &self.input, elapsed, self.last_render_duration),
+                &mut self.kludgine,
+                error,
+            );
+        })) {
+            self.recover_from_panic(window, panic);
+        }
+    }
+
+    /// Shows the panic overlay if the behavior recovers from panics. Otherwise,
+    /// continues unwinding `panic`.
+    fn recover_from_panic<User>(
+        &mut self,
+        window: &mut RunningWindow<AppEvent<User>>,
+        panic: Box<dyn Any + Send>,
+    ) where
+        Behavior: WindowBehavior<User> + 'static,
+        User: Send + 'static,
+    {
+        if !self.behavior.recover_from_panics() {
+            panic::resume_unwind(panic);
+        }
+
+        let message = panic_message(&*panic);
+        self.behavior.panicked(
+            Window::new(
+                window,
+                &self.input,
+                self.last_render.elapsed(),
+                self.last_render_duration,
+            ),
+            &mut self.kludgine,
+            &message,
+        );
+        self.panic_overlay = Some(PanicOverlay::new(message));
+        window.set_needs_redraw();
+    }
+
+    /// Hides the panic overlay in response to input, returning true if the
+    /// overlay was visible.
+    fn dismiss_panic_overlay<User>(&mut self, window: &mut RunningWindow<AppEvent<User>>) -> bool
+    where
+        User: Send + 'static,
+    {
+        if self.panic_overlay.take().is_some() {
+            window.set_needs_redraw();
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Returns true if `window` has an open modal child, focusing the child.
+fn blocked_by_modal<User>(window: &RunningWindow<AppEvent<User>>) -> bool
+where
+    User: Send + 'static,
+{
+    if let Some(child) = relationships::modal_child(window.winit().id()) {
+        child.focus_window();
+        true
+    } else {
+        false
+    }
+}
+
+/// Delivers `event` to `behavior`, or traverses its
+/// [focus chain](WindowBehavior::focus_chain) if `event` is a press of Tab or
+/// Shift+Tab and the chain is not empty.
+fn deliver_key_event<Behavior, WindowEvent>(
+    behavior: &mut Behavior,
+    window: Window<'_, WindowEvent>,
+    kludgine: &mut Kludgine,
+    device_id: DeviceId,
+    event: KeyEvent,
+    is_synthetic: bool,
+) where
+    Behavior: WindowBehavior<WindowEvent>,
+    WindowEvent: Send + 'static,
+{
+    if let Some(direction) = focus_direction(&event, window.modifiers()) {
+        if let Some(chain) = behavior.focus_chain().filter(|chain| !chain.is_empty()) {
+            let traversal = chain.traverse(direction);
+            behavior.focus_traversed(window, kludgine, traversal);
+            return;
+        }
+    }
+
+    behavior.keyboard_input(window, kludgine, device_id, event, is_synthetic);
+}
+
+/// Returns the direction to traverse focus in if `event` is a press of Tab or
+/// Shift+Tab.
+fn focus_direction(event: &KeyEvent, modifiers: Modifiers) -> Option<FocusDirection> {
+    let state = modifiers.state();
+    if event.state != ElementState::Pressed
+        || event.logical_key != Key::Named(NamedKey::Tab)
+        || state.control_key()
+        || state.alt_key()
+        || state.super_key()
+    {
+        return None;
+    }
+
+    Some(if state.shift_key() {
+        FocusDirection::Previous
+    } else {
+        FocusDirection::Next
+    })
+}
+
+/// Returns a flag that is set when `device` is unexpectedly lost. When the
+/// device is lost, `window` is asked to redraw so that the device can be
+/// recreated.
+fn watch_device_lost<User>(device: &wgpu::Device, window: WindowHandle<User>) -> Arc<AtomicBool>
+where
+    User: Send + 'static,
+{
+    let lost = Arc::new(AtomicBool::new(false));
+    device.set_device_lost_callback({
+        let lost = lost.clone();
+        move |reason, _message| {
+            if matches!(reason, wgpu::DeviceLostReason::Unknown) {
+                lost.store(true, Ordering::Release);
+                window.request_redraw();
+            }
+        }
+    });
+    lost
+}
+
+/// Returns the features to request from `adapter`: Kludgine's required
+/// features, `required`, and the features in `optional` that `adapter`
+/// supports.
+fn device_features(
+    required: wgpu::Features,
+    optional: wgpu::Features,
+    adapter: &wgpu::Adapter,
+) -> wgpu::Features {
+    Kludgine::REQURED_FEATURES | required | (optional & adapter.features())
+}
 
-        let color_attachments = [Some(wgpu::RenderPassColorAttachment {
-            view: &view,
-            resolve_target: resolve_target.as_ref(),
-            ops: wgpu::Operations {
-                load: self
-                    .behavior
-                    .clear_color()
-                    .map_or(wgpu::LoadOp::Load, |color| {
-                        wgpu::LoadOp::Clear(color.into())
-                    }),
-                store: wgpu::StoreOp::Store,
-            },
-        })];
-        let mut gfx = frame.render(
-            &wgpu::RenderPassDescriptor {
-                label: None,
-                color_attachments: &color_attachments,
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            },
-            &self.device,
-            &self.queue,
-        );
-        self.behavior.render(
-            Window::new_in_frame(
-                window,
-                elapsed,
-                self.last_render_duration,
-                &mut pending_inner_size,
+/// Returns the largest multisample count no greater than `requested` that
+/// `device` supports when rendering to `format`.
+fn supported_multisample_count(
+    requested: u32,
+    format: wgpu::TextureFormat,
+    device: &wgpu::Device,
+    adapter: &wgpu::Adapter,
+) -> u32 {
+    let features = crate::format_features(format, device.features(), Some(adapter));
+    [16, 8, 4, 2]
+        .into_iter()
+        .find(|count| *count <= requested && features.flags.sample_count_supported(*count))
+        .unwrap_or(1)
+}
+
+/// Returns true if a frame can redraw only its damaged region on top of the
+/// previous frame's retained contents.
+///
+/// The entire frame is redrawn while the panic overlay is shown, and when the
+/// clear color isn't opaque, because only opaque clear colors can be drawn
+/// over the damaged region to clear it.
+fn redraws_partially(
+    retained_contents: bool,
+    showing_panic_overlay: bool,
+    clear_color: Option<Color>,
+) -> bool {
+    retained_contents
+        && !showing_panic_overlay
+        && clear_color.map_or(true, |color| color.alpha() == u8::MAX)
+}
+
+/// Prepares a shape that fills the damaged region of `frame` with
+/// `clear_color`, if any region has been damaged.
+fn prepare_damage_fill(
+    frame: &mut Frame<'_>,
+    clear_color: Option<Color>,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+) -> Option<PreparedGraphic<Px>> {
+    let color = frame.damaged_region().and(clear_color)?;
+    let size = frame.kludgine.size.into_signed();
+    // The fill extends past the edges of the window to ensure anti-aliasing
+    // never blends its edges. Rendering clips it to the damaged region.
+    Some(
+        Shape::filled_rect(
+            Rect::new(
+                Point::px(-1, -1),
+                Size::new(size.width + Px::new(2), size.height + Px::new(2)),
             ),
-            &mut gfx,
-        );
-        drop(gfx);
-        let id = frame.submit(&self.queue);
-        window.winit().pre_present_notify();
-        surface.present();
-        if let Some(id) = id {
-            self.device.poll(wgpu::Maintain::WaitForSubmissionIndex(id));
-        }
-        pending_inner_size
-    }
+            color,
+        )
+        .prepare(&frame.prepare(device, queue)),
+    )
 }
 
-fn new_wgpu_instance() -> wgpu::Instance {
+fn new_wgpu_instance(backends: wgpu::Backends) -> wgpu::Instance {
     let flags;
     #[cfg(debug_assertions)]
     {
@@ -1311,15 +2752,17 @@ fn new_wgpu_instance() -> wgpu::Instance {
         flags = wgpu::InstanceFlags::empty();
     }
     wgpu::Instance::new(wgpu::InstanceDescriptor {
+        backends,
         flags,
         ..wgpu::InstanceDescriptor::default()
     })
 }
 
 impl<T> KludgineWindow<T> {
-    fn resized<User>(&mut self, window: &mut RunningWindow<AppEvent<User>>)
+    /// Reconfigures the surface and Kludgine to match the window's current
+    /// size and scale.
+    fn apply_window_size<User>(&mut self, window: &mut RunningWindow<AppEvent<User>>)
     where
-        T: WindowBehavior<User> + 'static,
         User: Send + 'static,
     {
         self.config.width = window.inner_size().width;
@@ -1328,15 +2771,24 @@ impl<T> KludgineWindow<T> {
             self.surface.configure(&self.device, &self.config);
             self.kludgine.resize(
                 window.inner_size().into(),
-                window.scale().cast::<f32>(),
+                DisplayScale::from_factor(window.scale()).get(),
                 self.kludgine.zoom,
                 &self.queue,
             );
             window.set_needs_redraw();
         }
+    }
+
+    fn resized<User>(&mut self, window: &mut RunningWindow<AppEvent<User>>)
+    where
+        T: WindowBehavior<User> + 'static,
+        User: Send + 'static,
+    {
+        self.apply_window_size(window);
         self.behavior.resized(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1357,7 +2809,20 @@ where
         context: Self::Context,
     ) -> Result<Self, UnrecoverableError> {
         T::pre_initialize(&context, window.winit());
-        let wgpu = Arc::new(new_wgpu_instance());
+        let monitors = Monitors {
+            primary: window.winit().primary_monitor().map(Monitor),
+            available: window.winit().available_monitors().map(Monitor).collect(),
+        };
+        if let Some((monitor, placement)) = T::initial_placement(&context, &monitors) {
+            let size = Size::<UPx>::from(window.outer_size());
+            let region = placement.region(monitor.region(), size);
+            if placement.resizes() {
+                let _ = window.request_inner_size(region.size.into_unsigned().into());
+            }
+            window.set_outer_position(region.origin.into());
+        }
+        let registration = WindowRegistration::new(window.winit(), T::parent_window(&context));
+        let wgpu = Arc::new(new_wgpu_instance(T::backends(&context)));
         let surface = window
             .send(AppEvent(AppEventKind::CreateSurface(
                 CreateSurfaceRequest {
@@ -1368,26 +2833,49 @@ where
             )))
             .expect("app not running")
             .expect_surface()?;
+        let power_preference = T::power_preference(&context);
+        let force_fallback_adapter = T::force_fallback_adapter(&context);
         let adapter = pollster::block_on(wgpu.request_adapter(&wgpu::RequestAdapterOptions {
-            power_preference: T::power_preference(&context),
-            force_fallback_adapter: false,
+            power_preference,
+            force_fallback_adapter,
             compatible_surface: Some(&surface),
         }))
         .ok_or(UnrecoverableError::NoAdapter)?;
+        let limits = Kludgine::adjust_limits(T::limits(adapter.limits(), &context));
+        let memory_hints = T::memory_hints(&context);
+        let required_features = T::required_features(&context);
+        let optional_features = T::optional_features(&context);
         let (device, queue) = pollster::block_on(adapter.request_device(
             &wgpu::DeviceDescriptor {
-                label: None,
-                required_features: Kludgine::REQURED_FEATURES,
-                required_limits: Kludgine::adjust_limits(T::limits(adapter.limits(), &context)),
-                memory_hints: T::memory_hints(&context),
+                label: Some("kludgine::device"),
+                required_features: device_features(required_features, optional_features, &adapter),
+                required_limits: limits.clone(),
+                memory_hints: memory_hints.clone(),
             },
             None,
         ))
         .map_err(UnrecoverableError::Device)?;
+        let device_lost = watch_device_lost(
+            &device,
+            Window::new(
+                window,
+                &WindowInput::default(),
+                Duration::ZERO,
+                Duration::ZERO,
+            )
+            .handle(),
+        );
 
         let swapchain_capabilities = surface.get_capabilities(&adapter);
         let swapchain_format = swapchain_capabilities.formats[0];
-        let multisample_count = T::multisample_count(&context).get();
+        let multisample_count = supported_multisample_count(
+            T::multisample_count(&context).get(),
+            swapchain_format,
+            &device,
+            &adapter,
+        );
+        let depth_testing = T::depth_testing(&context);
+        let partial_redraw = T::partial_redraw(&context);
         let multisample = wgpu::MultisampleState {
             count: multisample_count,
             ..Default::default()
@@ -1401,17 +2889,31 @@ where
             window.inner_size().into(),
             window.scale().cast::<f32>(),
         );
+        state.set_adapter(&adapter);
         let mut graphics = Graphics::new(&mut state, &device, &queue);
 
         let last_render = Instant::now();
         let behavior = T::initialize(
-            Window::new(window, Duration::ZERO, Duration::ZERO),
+            Window::new(
+                window,
+                &WindowInput::default(),
+                Duration::ZERO,
+                Duration::ZERO,
+            ),
             &mut graphics,
             context,
         );
 
+        // Allowing the surface to be copied enables mirroring frames, and
+        // copying into the surface enables partial redraws without
+        // multisampling.
+        let mut usage = wgpu::TextureUsages::RENDER_ATTACHMENT
+            | (swapchain_capabilities.usages & wgpu::TextureUsages::COPY_SRC);
+        if partial_redraw && multisample_count == 1 {
+            usage |= swapchain_capabilities.usages & wgpu::TextureUsages::COPY_DST;
+        }
         let config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            usage,
             format: swapchain_format,
             width: window.inner_size().width,
             height: window.inner_size().height,
@@ -1426,7 +2928,14 @@ where
             kludgine: state,
             last_render,
             last_render_duration: Duration::ZERO,
+            rejected_files: HashSet::new(),
+            opened_at: Instant::now(),
+            pointer: PointerTracker::default(),
+            input: WindowInput::default(),
             msaa_texture: None,
+            depth_texture: None,
+            retained_texture: None,
+            retained_contents: false,
             behavior,
             config,
             surface,
@@ -1434,6 +2943,20 @@ where
             queue,
             wgpu,
             multisample_count,
+            depth_testing,
+            partial_redraw,
+            redraw_deferred: false,
+            _registration: registration,
+            panic_overlay: None,
+            mirror: None,
+            last_capture: None,
+            device_lost,
+            power_preference,
+            force_fallback_adapter,
+            required_features,
+            optional_features,
+            limits,
+            memory_hints,
         })
     }
 
@@ -1441,6 +2964,7 @@ where
         self.behavior.initialized(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1449,6 +2973,14 @@ where
     }
 
     fn redraw(&mut self, window: &mut RunningWindow<AppEvent<User>>) {
+        if !self.behavior.render_while_occluded()
+            && (window.occluded() || window.winit().is_minimized() == Some(true))
+        {
+            self.redraw_deferred = true;
+            return;
+        }
+        self.redraw_deferred = false;
+
         if self.config.width > 0 && self.config.height > 0 {
             // When using winit's request_inner_size, some platforms may
             // immediately resize and not emit a Resized event through winit.
@@ -1461,6 +2993,10 @@ where
                 self.resized(window);
             }
 
+            if self.device_lost.load(Ordering::Acquire) && !self.recreate_device(window) {
+                return;
+            }
+
             let mut render_start = None;
             let Some(surface) = self.current_surface_texture(window) else {
                 return;
@@ -1473,17 +3009,22 @@ where
             });
             let elapsed = render_start - self.last_render;
 
-            if let Some(new_inner_size) = self.render_to_surface(surface, elapsed, window) {
+            push_error_scopes(&self.device);
+            let new_inner_size = self.render_to_surface(surface, elapsed, window);
+            if let Some(error) = pop_error_scopes(&self.device) {
+                self.report_gpu_error(window, elapsed, Error::Gpu(error));
+            }
+            if let Some(new_inner_size) = new_inner_size {
                 if let Some(applied_size) = window.request_inner_size(new_inner_size.into()) {
                     self.kludgine.resize(
                         applied_size.into(),
-                        self.kludgine.scale(),
+                        self.kludgine.dpi_scale(),
                         self.kludgine.zoom(),
                         &self.queue,
                     );
                     window.set_needs_redraw();
                     self.behavior.resized(
-                        Window::new(window, elapsed, self.last_render_duration),
+                        Window::new(window, &self.input, elapsed, self.last_render_duration),
                         &mut self.kludgine,
                     );
                 }
@@ -1497,6 +3038,7 @@ where
         self.behavior.close_requested(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1505,9 +3047,16 @@ where
     }
 
     fn focus_changed(&mut self, window: &mut RunningWindow<AppEvent<User>>) {
+        if !window.focused() {
+            self.pointer.reset();
+            // Releases are not delivered to unfocused windows.
+            self.input.keyboard.release_all();
+            self.input.mouse.release_all();
+        }
         self.behavior.focus_changed(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1516,14 +3065,19 @@ where
     }
 
     fn occlusion_changed(&mut self, window: &mut RunningWindow<AppEvent<User>>) {
+        self.kludgine.clock_mut().set_occluded(window.occluded());
         self.behavior.occlusion_changed(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
             &mut self.kludgine,
         );
+        if !window.occluded() && self.redraw_deferred {
+            window.set_needs_redraw();
+        }
     }
 
     fn resized(&mut self, window: &mut RunningWindow<AppEvent<User>>) {
@@ -1534,6 +3088,7 @@ where
         self.behavior.moved(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1542,9 +3097,13 @@ where
     }
 
     fn scale_factor_changed(&mut self, window: &mut RunningWindow<AppEvent<User>>) {
+        // The window's physical size may change along with its scale, and not
+        // every platform follows a scale change with a resize event.
+        self.apply_window_size(window);
         self.behavior.scale_factor_changed(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1556,6 +3115,7 @@ where
         self.behavior.theme_changed(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1564,33 +3124,53 @@ where
     }
 
     fn dropped_file(&mut self, window: &mut RunningWindow<AppEvent<User>>, path: PathBuf) {
+        if self.rejected_files.remove(&path) {
+            return;
+        }
+
+        let position = window.cursor_position().map(Point::from);
         self.behavior.dropped_file(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
             &mut self.kludgine,
             path,
+            position,
         );
     }
 
     fn hovered_file(&mut self, window: &mut RunningWindow<AppEvent<User>>, path: PathBuf) {
-        self.behavior.hovered_file(
+        let position = window.cursor_position().map(Point::from);
+        let drop = self.behavior.hovered_file(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
             &mut self.kludgine,
-            path,
+            path.clone(),
+            position,
         );
+        match drop {
+            FileDrop::Accept => {
+                self.rejected_files.remove(&path);
+            }
+            FileDrop::Reject => {
+                self.rejected_files.insert(path);
+            }
+        }
     }
 
     fn hovered_file_cancelled(&mut self, window: &mut RunningWindow<AppEvent<User>>) {
+        self.rejected_files.clear();
         self.behavior.hovered_file_cancelled(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1599,9 +3179,14 @@ where
     }
 
     fn received_character(&mut self, window: &mut RunningWindow<AppEvent<User>>, char: char) {
+        if blocked_by_modal(window) {
+            return;
+        }
+
         self.behavior.received_character(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1614,26 +3199,18 @@ where
         &mut self,
         window: &mut RunningWindow<AppEvent<User>>,
         device_id: DeviceId,
-        event: KeyEvent,
+        event: winit::event::KeyEvent,
         is_synthetic: bool,
     ) {
-        self.behavior.keyboard_input(
-            Window::new(
-                window,
-                self.last_render.elapsed(),
-                self.last_render_duration,
-            ),
-            &mut self.kludgine,
-            device_id,
-            event,
-            is_synthetic,
-        );
+        self.key_event(window, device_id, event.into(), is_synthetic);
     }
 
     fn modifiers_changed(&mut self, window: &mut RunningWindow<AppEvent<User>>) {
+        self.input.keyboard.set_modifiers(window.modifiers());
         self.behavior.modifiers_changed(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1642,9 +3219,14 @@ where
     }
 
     fn ime(&mut self, window: &mut RunningWindow<AppEvent<User>>, ime: Ime) {
+        if blocked_by_modal(window) {
+            return;
+        }
+
         self.behavior.ime(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1659,9 +3241,11 @@ where
         device_id: DeviceId,
         position: PhysicalPosition<f64>,
     ) {
+        self.input.mouse.set_position(Some(Point::from(position)));
         self.behavior.cursor_moved(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1669,12 +3253,30 @@ where
             device_id,
             position,
         );
+
+        for drag in self
+            .pointer
+            .moved(Point::from(position), &self.behavior.pointer_settings())
+        {
+            self.behavior.drag_started(
+                Window::new(
+                    window,
+                    &self.input,
+                    self.last_render.elapsed(),
+                    self.last_render_duration,
+                ),
+                &mut self.kludgine,
+                device_id,
+                drag,
+            );
+        }
     }
 
     fn cursor_entered(&mut self, window: &mut RunningWindow<AppEvent<User>>, device_id: DeviceId) {
         self.behavior.cursor_entered(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1684,9 +3286,11 @@ where
     }
 
     fn cursor_left(&mut self, window: &mut RunningWindow<AppEvent<User>>, device_id: DeviceId) {
+        self.input.mouse.set_position(None);
         self.behavior.cursor_left(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1702,9 +3306,14 @@ where
         delta: MouseScrollDelta,
         phase: TouchPhase,
     ) {
+        if blocked_by_modal(window) {
+            return;
+        }
+
         self.behavior.mouse_wheel(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1722,9 +3331,19 @@ where
         state: ElementState,
         button: MouseButton,
     ) {
+        self.input.mouse.set_pressed(button, state.is_pressed());
+        if blocked_by_modal(window) {
+            return;
+        }
+
+        if state.is_pressed() && self.dismiss_panic_overlay(window) {
+            return;
+        }
+
         self.behavior.mouse_input(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1733,6 +3352,32 @@ where
             state,
             button,
         );
+
+        match state {
+            ElementState::Pressed => {
+                let Some(position) = self.input.mouse.position() else {
+                    return;
+                };
+                let click = self.pointer.press(
+                    button,
+                    position,
+                    self.opened_at.elapsed(),
+                    &self.behavior.pointer_settings(),
+                );
+                self.behavior.mouse_clicked(
+                    Window::new(
+                        window,
+                        &self.input,
+                        self.last_render.elapsed(),
+                        self.last_render_duration,
+                    ),
+                    &mut self.kludgine,
+                    device_id,
+                    click,
+                );
+            }
+            ElementState::Released => self.pointer.release(button),
+        }
     }
 
     fn touchpad_pressure(
@@ -1742,9 +3387,14 @@ where
         pressure: f32,
         stage: i64,
     ) {
+        if blocked_by_modal(window) {
+            return;
+        }
+
         self.behavior.touchpad_pressure(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1765,6 +3415,7 @@ where
         self.behavior.axis_motion(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1776,9 +3427,14 @@ where
     }
 
     fn touch(&mut self, window: &mut RunningWindow<AppEvent<User>>, touch: Touch) {
+        if blocked_by_modal(window) {
+            return;
+        }
+
         self.behavior.touch(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1794,9 +3450,14 @@ where
         delta: f64,
         phase: TouchPhase,
     ) {
+        if blocked_by_modal(window) {
+            return;
+        }
+
         self.behavior.pinch_gesture(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1814,9 +3475,14 @@ where
         delta: PhysicalPosition<f32>,
         phase: TouchPhase,
     ) {
+        if blocked_by_modal(window) {
+            return;
+        }
+
         self.behavior.pan_gesture(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1832,9 +3498,14 @@ where
         window: &mut RunningWindow<AppEvent<User>>,
         device_id: DeviceId,
     ) {
+        if blocked_by_modal(window) {
+            return;
+        }
+
         self.behavior.double_tap_gesture(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1850,9 +3521,14 @@ where
         delta: f32,
         phase: TouchPhase,
     ) {
+        if blocked_by_modal(window) {
+            return;
+        }
+
         self.behavior.touchpad_rotate(
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
@@ -1868,24 +3544,163 @@ where
         window: &mut RunningWindow<AppEvent<User>>,
         event: <AppEvent<User> as Message>::Window,
     ) {
-        self.behavior.event(
+        match event.0 {
+            WindowMessageKind::User(event) => {
+                self.behavior.event(
+                    Window::new(
+                        window,
+                        &self.input,
+                        self.last_render.elapsed(),
+                        self.last_render_duration,
+                    ),
+                    &mut self.kludgine,
+                    event,
+                );
+            }
+            WindowMessageKind::Redraw(pending) => {
+                pending.store(false, Ordering::Release);
+                window.set_needs_redraw();
+            }
+            WindowMessageKind::Input(input) => self.inject(window, input),
+        }
+    }
+}
+
+impl<T> KludgineWindow<T> {
+    /// Handles a key event from the operating system or from
+    /// [`SyntheticInput::Key`].
+    fn key_event<User>(
+        &mut self,
+        window: &mut RunningWindow<AppEvent<User>>,
+        device_id: DeviceId,
+        event: KeyEvent,
+        is_synthetic: bool,
+    ) where
+        T: WindowBehavior<User> + 'static,
+        User: Send + 'static,
+    {
+        self.input
+            .keyboard
+            .set_pressed(event.physical_key, event.state.is_pressed());
+        if blocked_by_modal(window) {
+            return;
+        }
+
+        if event.state.is_pressed() && self.dismiss_panic_overlay(window) {
+            return;
+        }
+
+        deliver_key_event(
+            &mut self.behavior,
             Window::new(
                 window,
+                &self.input,
                 self.last_render.elapsed(),
                 self.last_render_duration,
             ),
             &mut self.kludgine,
+            device_id,
             event,
+            is_synthetic,
         );
     }
+
+    fn inject<User>(&mut self, window: &mut RunningWindow<AppEvent<User>>, input: SyntheticInput)
+    where
+        T: WindowBehavior<User> + 'static,
+        User: Send + 'static,
+    {
+        match input {
+            SyntheticInput::CursorMoved(position) => {
+                if self.input.mouse.position().is_none() {
+                    self.cursor_entered(window, synthetic_device_id());
+                }
+                self.cursor_moved(
+                    window,
+                    synthetic_device_id(),
+                    PhysicalPosition::new(
+                        f64::from(position.x.into_float()),
+                        f64::from(position.y.into_float()),
+                    ),
+                );
+            }
+            SyntheticInput::CursorLeft => {
+                if self.input.mouse.position().is_some() {
+                    self.cursor_left(window, synthetic_device_id());
+                }
+            }
+            SyntheticInput::MouseInput { button, state } => {
+                self.mouse_input(window, synthetic_device_id(), state, button);
+            }
+            SyntheticInput::Click { button, position } => {
+                self.inject(window, SyntheticInput::CursorMoved(position));
+                self.mouse_input(window, synthetic_device_id(), ElementState::Pressed, button);
+                self.mouse_input(
+                    window,
+                    synthetic_device_id(),
+                    ElementState::Released,
+                    button,
+                );
+            }
+            SyntheticInput::MouseWheel { delta, phase } => {
+                self.mouse_wheel(window, synthetic_device_id(), delta, phase);
+            }
+            SyntheticInput::Key(event) => {
+                self.key_event(window, synthetic_device_id(), event, false);
+            }
+            SyntheticInput::Modifiers(modifiers) => {
+                self.input.keyboard.set_modifiers(modifiers);
+                self.behavior.modifiers_changed(
+                    Window::new(
+                        window,
+                        &self.input,
+                        self.last_render.elapsed(),
+                        self.last_render_duration,
+                    ),
+                    &mut self.kludgine,
+                );
+            }
+            SyntheticInput::Character(char) => self.received_character(window, char),
+            SyntheticInput::Ime(ime) => self.ime(window, ime),
+        }
+    }
 }
 
-struct CallbackWindow<C> {
+pub(crate) struct CallbackWindow<C> {
     callback: C,
     rendering: Drawing,
+    translation: Point<Px>,
     keep_running: bool,
 }
 
+#[cfg(test)]
+impl<C> CallbackWindow<C> {
+    /// Returns the drawing prepared by the callback during the last frame.
+    pub(crate) const fn drawing(&self) -> &Drawing {
+        &self.rendering
+    }
+
+    /// Sets the translation the prepared drawing is rendered with.
+    pub(crate) fn set_translation(&mut self, translation: Point<Px>) {
+        self.translation = translation;
+    }
+}
+
+/// Returns a headless window that prepares each frame by invoking
+/// `render_fn`, allowing tests to render a single callback.
+#[cfg(test)]
+pub(crate) fn headless_window<RenderFn>(
+    size: Size<UPx>,
+    render_fn: RenderFn,
+) -> Result<HeadlessWindow<CallbackWindow<RenderFn>>, UnrecoverableError>
+where
+    RenderFn: for<'render, 'gfx, 'window> FnMut(Renderer<'render, 'gfx>, Window<'window>) -> bool
+        + Send
+        + 'static,
+{
+    HeadlessWindow::new(size, render_fn)
+}
+
 impl<T> WindowBehavior for CallbackWindow<T>
 where
     T: for<'render, 'gfx, 'window> FnMut(Renderer<'render, 'gfx>, Window<'window>) -> bool
@@ -1902,6 +3717,7 @@ where
         Self {
             callback: context,
             rendering: Drawing::default(),
+            translation: Point::ZERO,
             keep_running: true,
         }
     }
@@ -1916,7 +3732,8 @@ where
         mut window: Window<'_>,
         graphics: &mut RenderingGraphics<'_, 'pass>,
     ) {
-        self.rendering.render(1., graphics);
+        self.rendering
+            .render(1., &mut graphics.translated(self.translation));
         if !self.keep_running {
             window.close();
         }
@@ -1942,11 +3759,57 @@ where
 
 /// A handle to a window.
 ///
-/// This handle does not prevent the window from being closed.
+/// This handle does not prevent the window from being closed. Handles can be
+/// cloned cheaply and used from any thread, allowing other threads to send
+/// messages to the window and to request that it redraws.
 #[derive(Debug)]
-pub struct WindowHandle<Message = ()>(appit::Window<Message>);
+pub struct WindowHandle<Message = ()> {
+    window: HandleTarget<Message>,
+    redraw_pending: Arc<AtomicBool>,
+}
+
+/// The window that a [`WindowHandle`] sends messages to.
+#[derive(Debug)]
+enum HandleTarget<Message> {
+    Running(appit::Window<WindowMessage<Message>>),
+    Headless(mpsc::Sender<WindowMessage<Message>>),
+}
+
+impl<Message> HandleTarget<Message> {
+    fn send(&self, message: WindowMessage<Message>) -> Result<(), WindowMessage<Message>> {
+        match self {
+            HandleTarget::Running(window) => window.send(message),
+            HandleTarget::Headless(sender) => sender.send(message).map_err(|err| err.0),
+        }
+    }
+}
+
+impl<Message> Clone for HandleTarget<Message> {
+    fn clone(&self) -> Self {
+        match self {
+            HandleTarget::Running(window) => HandleTarget::Running(window.clone()),
+            HandleTarget::Headless(sender) => HandleTarget::Headless(sender.clone()),
+        }
+    }
+}
 
 impl<Message> WindowHandle<Message> {
+    fn new(window: appit::Window<WindowMessage<Message>>) -> Self {
+        Self {
+            window: HandleTarget::Running(window),
+            redraw_pending: Arc::default(),
+        }
+    }
+
+    /// Returns a handle that sends messages to a [`HeadlessWindow`] through
+    /// `sender`.
+    fn headless(sender: mpsc::Sender<WindowMessage<Message>>) -> Self {
+        Self {
+            window: HandleTarget::Headless(sender),
+            redraw_pending: Arc::default(),
+        }
+    }
+
     /// Sends `message` to the window. If the message cannot be
     ///
     /// Returns `Ok` if the message was successfully sent. The message may not
@@ -1957,14 +3820,278 @@ impl<Message> WindowHandle<Message> {
     ///
     /// If the window is already closed, this function returns `Err(message)`.
     pub fn send(&self, message: Message) -> Result<(), Message> {
-        self.0.send(message)
+        self.window
+            .send(WindowMessage(WindowMessageKind::User(message)))
+            .map_err(|WindowMessage(kind)| match kind {
+                WindowMessageKind::User(message) => message,
+                WindowMessageKind::Redraw(_) | WindowMessageKind::Input(_) => {
+                    unreachable!("user message sent")
+                }
+            })
+    }
+
+    /// Delivers synthetic `input` to the window, as if it were received from
+    /// the operating system.
+    ///
+    /// Returns false if the window has been closed.
+    #[allow(clippy::must_use_candidate)]
+    pub fn inject(&self, input: SyntheticInput) -> bool {
+        self.window
+            .send(WindowMessage(WindowMessageKind::Input(input)))
+            .is_ok()
+    }
+
+    /// Requests that the window redraws as soon as it can.
+    ///
+    /// Requests are coalesced: while a request from this handle or its clones
+    /// is waiting to be processed by the window, additional requests are
+    /// ignored. A flood of requests results in at most one redraw per frame.
+    ///
+    /// Returns false if the window has been closed.
+    #[allow(clippy::must_use_candidate)]
+    pub fn request_redraw(&self) -> bool {
+        if self.redraw_pending.swap(true, Ordering::AcqRel) {
+            return true;
+        }
+
+        let sent = self
+            .window
+            .send(WindowMessage(WindowMessageKind::Redraw(
+                self.redraw_pending.clone(),
+            )))
+            .is_ok();
+        if !sent {
+            self.redraw_pending.store(false, Ordering::Release);
+        }
+        sent
     }
 }
 
 impl<Message> Clone for WindowHandle<Message> {
     fn clone(&self) -> Self {
-        Self(self.0.clone())
+        Self {
+            window: self.window.clone(),
+            redraw_pending: self.redraw_pending.clone(),
+        }
+    }
+}
+
+#[test]
+fn window_handles_are_thread_safe() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<WindowHandle<()>>();
+}
+
+#[test]
+fn headless_window_handles() {
+    let Ok(mut window) = headless_window(Size::upx(16, 16), |_renderer, window| {
+        assert!(window.try_app().is_none());
+        assert!(window.try_winit().is_none());
+        true
+    }) else {
+        return;
+    };
+    window.render();
+    assert!(!window.needs_redraw());
+    assert!(window.handle().request_redraw());
+    assert_eq!(window.process_messages(), 1);
+    assert!(window.needs_redraw());
+
+    let Ok(other) = headless_window(Size::upx(16, 16), |_renderer, _window| true) else {
+        return;
+    };
+    assert_ne!(window.id(), other.id());
+}
+
+#[test]
+fn headless_key_input_traverses_focus() {
+    use appit::winit::keyboard::{KeyCode, ModifiersState};
+
+    struct Focusable {
+        chain: FocusChain,
+        keys: Vec<Key>,
+    }
+
+    impl WindowBehavior for Focusable {
+        type Context = ();
+
+        fn initialize(
+            _window: Window<'_>,
+            _graphics: &mut Graphics<'_>,
+            _context: Self::Context,
+        ) -> Self {
+            let mut chain = FocusChain::new();
+            chain.register(1);
+            chain.register(2);
+            Self {
+                chain,
+                keys: Vec::new(),
+            }
+        }
+
+        fn render<'pass>(
+            &'pass mut self,
+            _window: Window<'_>,
+            _graphics: &mut RenderingGraphics<'_, 'pass>,
+        ) {
+        }
+
+        fn focus_chain(&mut self) -> Option<&mut FocusChain> {
+            Some(&mut self.chain)
+        }
+
+        fn keyboard_input(
+            &mut self,
+            _window: Window<'_>,
+            _kludgine: &mut Kludgine,
+            _device_id: DeviceId,
+            input: KeyEvent,
+            _is_synthetic: bool,
+        ) {
+            self.keys.push(input.logical_key);
+        }
+    }
+
+    let Ok(mut window) = HeadlessWindow::<Focusable>::new(Size::upx(16, 16), ()) else {
+        // No graphics adapter is available.
+        return;
+    };
+    let tab = |state| KeyEvent::new(KeyCode::Tab, NamedKey::Tab, state);
+    window.key_input(tab(ElementState::Pressed));
+    window.key_input(tab(ElementState::Released));
+    assert_eq!(window.behavior().chain.focused(), Some(1));
+
+    window.modifiers_changed(Modifiers::from(ModifiersState::SHIFT));
+    window.inject(SyntheticInput::Key(tab(ElementState::Pressed)));
+    assert_eq!(window.behavior().chain.focused(), Some(2));
+
+    // Releasing Tab and pressing other keys are delivered to the behavior.
+    window.key_input(KeyEvent::new(
+        KeyCode::Enter,
+        NamedKey::Enter,
+        ElementState::Pressed,
+    ));
+    assert_eq!(
+        window.behavior().keys,
+        [Key::Named(NamedKey::Tab), Key::Named(NamedKey::Enter)]
+    );
+}
+
+#[test]
+fn partial_redraw_fallbacks() {
+    assert!(redraws_partially(true, false, Some(Color::BLACK)));
+    assert!(redraws_partially(true, false, None));
+    // The previous frame's contents are unavailable, such as after a resize.
+    assert!(!redraws_partially(false, false, Some(Color::BLACK)));
+    // The panic overlay always redraws the entire window.
+    assert!(!redraws_partially(true, true, Some(Color::BLACK)));
+    // Translucent clear colors cannot be drawn over the damaged region.
+    assert!(!redraws_partially(true, false, Some(Color::CLEAR_BLACK)));
+    assert!(!redraws_partially(
+        true,
+        false,
+        Some(Color::BLACK.with_alpha(254))
+    ));
+}
+
+#[test]
+fn headless_partial_redraw_preserves_undamaged_pixels() {
+    struct Damaged {
+        color: Color,
+        clear_color: Option<Color>,
+        damage: Option<Rect<UPx>>,
+        fill: Option<PreparedGraphic<Px>>,
+    }
+
+    impl WindowBehavior for Damaged {
+        type Context = ();
+
+        fn initialize(
+            _window: Window<'_>,
+            _graphics: &mut Graphics<'_>,
+            _context: Self::Context,
+        ) -> Self {
+            Self {
+                color: Color::RED,
+                clear_color: Some(Color::BLACK),
+                damage: None,
+                fill: None,
+            }
+        }
+
+        fn partial_redraw(_context: &Self::Context) -> bool {
+            true
+        }
+
+        fn clear_color(&self) -> Option<Color> {
+            self.clear_color
+        }
+
+        fn prepare(&mut self, _window: Window<'_>, graphics: &mut Graphics<'_>) {
+            if let Some(damage) = self.damage {
+                graphics.add_damage(damage);
+            }
+            let size = graphics.size().into_signed();
+            self.fill = Some(Shape::filled_rect(Rect::from(size), self.color).prepare(graphics));
+        }
+
+        fn render<'pass>(
+            &'pass mut self,
+            _window: Window<'_>,
+            graphics: &mut RenderingGraphics<'_, 'pass>,
+        ) {
+            if let Some(fill) = &self.fill {
+                fill.render(graphics);
+            }
+        }
     }
+
+    fn pixel(window: &HeadlessWindow<Damaged>, location: Point<u32>) -> [u8; 4] {
+        let pixels = window.read_pixels().expect("pixels can be read");
+        let width = window.texture().size().width.get();
+        let offset = usize::try_from((location.y * width + location.x) * 4).expect("small window");
+        pixels[offset..offset + 4].try_into().expect("4 bytes")
+    }
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+    // Both locations are away from the edges of the window and the damage,
+    // avoiding any anti-aliasing.
+    let outside = Point::new(1, 1);
+    let inside = Point::new(4, 4);
+
+    let Ok(mut window) = HeadlessWindow::<Damaged>::new(Size::upx(8, 8), ()) else {
+        // No graphics adapter is available.
+        return;
+    };
+    // The first frame has no previous contents and is fully drawn.
+    window.render();
+    assert_eq!(pixel(&window, outside), RED);
+    assert_eq!(pixel(&window, inside), RED);
+
+    // Only the damaged region is redrawn.
+    let damage = Rect::new(Point::upx(2, 2), Size::upx(4, 4));
+    window.behavior_mut().color = Color::BLUE;
+    window.behavior_mut().damage = Some(damage);
+    window.render();
+    assert_eq!(pixel(&window, outside), RED);
+    assert_eq!(pixel(&window, inside), BLUE);
+
+    // Resizing discards the previous contents.
+    window.behavior_mut().color = Color::RED;
+    window.resize(Size::upx(10, 10));
+    window.render();
+    assert_eq!(pixel(&window, outside), RED);
+    window.behavior_mut().color = Color::BLUE;
+    window.render();
+    assert_eq!(pixel(&window, outside), RED);
+    assert_eq!(pixel(&window, inside), BLUE);
+
+    // Translucent clear colors redraw the entire window.
+    window.behavior_mut().clear_color = Some(Color::CLEAR_BLACK);
+    window.render();
+    assert_eq!(pixel(&window, outside), BLUE);
+    assert_eq!(pixel(&window, inside), BLUE);
 }
 
 /// An unrecoverable error
@@ -0,0 +1,214 @@
+use std::borrow::Cow;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use figures::units::UPx;
+use figures::{Size, UPx2D};
+use intentional::Cast;
+
+/// A handle to the system clipboard.
+///
+/// All clones of this type share the same underlying connection to the system
+/// clipboard. [`Clipboard::shared()`] returns the process-wide instance, which
+/// can be used from any window callback or thread without needing to
+/// coordinate with the event loop.
+#[derive(Clone)]
+pub struct Clipboard(Arc<Mutex<arboard::Clipboard>>);
+
+impl Clipboard {
+    /// Returns the process-wide clipboard instance, connecting to the system
+    /// clipboard if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the system clipboard cannot be accessed.
+    pub fn shared() -> Result<Self, ClipboardError> {
+        static SHARED: Mutex<Option<Clipboard>> = Mutex::new(None);
+
+        let mut shared = SHARED.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(clipboard) = &*shared {
+            return Ok(clipboard.clone());
+        }
+
+        let clipboard = Self(Arc::new(Mutex::new(arboard::Clipboard::new()?)));
+        *shared = Some(clipboard.clone());
+        Ok(clipboard)
+    }
+
+    fn with_clipboard<R>(
+        &self,
+        callback: impl FnOnce(&mut arboard::Clipboard) -> Result<R, arboard::Error>,
+    ) -> Result<R, ClipboardError> {
+        let mut clipboard = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        callback(&mut clipboard).map_err(ClipboardError::from)
+    }
+
+    /// Returns the text currently stored in the clipboard.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClipboardError::Empty`] if the clipboard does not contain
+    /// text.
+    pub fn get_text(&self) -> Result<String, ClipboardError> {
+        self.with_clipboard(arboard::Clipboard::get_text)
+    }
+
+    /// Replaces the contents of the clipboard with `text`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the clipboard could not be updated.
+    pub fn set_text<'a>(&self, text: impl Into<Cow<'a, str>>) -> Result<(), ClipboardError> {
+        self.with_clipboard(|clipboard| clipboard.set_text(text))
+    }
+
+    /// Returns the image currently stored in the clipboard.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClipboardError::Empty`] if the clipboard does not contain an
+    /// image.
+    pub fn get_image(&self) -> Result<ClipboardImage, ClipboardError> {
+        let image = self.with_clipboard(arboard::Clipboard::get_image)?;
+        Ok(ClipboardImage {
+            size: Size::upx(image.width.cast::<u32>(), image.height.cast::<u32>()),
+            rgba: image.bytes.into_owned(),
+        })
+    }
+
+    /// Replaces the contents of the clipboard with `image`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClipboardError::ConversionFailure`] if the length of
+    /// `image.rgba` does not match `image.size`, or another error if the
+    /// clipboard could not be updated.
+    pub fn set_image(&self, image: &ClipboardImage) -> Result<(), ClipboardError> {
+        if !image.is_valid() {
+            return Err(ClipboardError::ConversionFailure);
+        }
+        self.with_clipboard(|clipboard| {
+            clipboard.set_image(arboard::ImageData {
+                width: image.size.width.get().cast(),
+                height: image.size.height.get().cast(),
+                bytes: Cow::Borrowed(&image.rgba),
+            })
+        })
+    }
+
+    /// Clears the contents of the clipboard.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the clipboard could not be updated.
+    pub fn clear(&self) -> Result<(), ClipboardError> {
+        self.with_clipboard(arboard::Clipboard::clear)
+    }
+}
+
+impl Debug for Clipboard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Clipboard").finish_non_exhaustive()
+    }
+}
+
+/// An image stored in the clipboard.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ClipboardImage {
+    /// The size of the image.
+    pub size: Size<UPx>,
+    /// The 8-bit RGBA pixel data of the image, row by row.
+    pub rgba: Vec<u8>,
+}
+
+impl ClipboardImage {
+    /// Returns true if the length of `rgba` matches `size`.
+    fn is_valid(&self) -> bool {
+        self.size
+            .width
+            .get()
+            .cast::<usize>()
+            .checked_mul(self.size.height.get().cast::<usize>())
+            .and_then(|pixels| pixels.checked_mul(4))
+            == Some(self.rgba.len())
+    }
+
+    /// Converts this image into an [`image::RgbaImage`].
+    ///
+    /// Returns `None` if the length of `rgba` does not match `size`.
+    #[cfg(feature = "image")]
+    #[must_use]
+    pub fn into_rgba_image(self) -> Option<image::RgbaImage> {
+        image::RgbaImage::from_raw(self.size.width.get(), self.size.height.get(), self.rgba)
+    }
+}
+
+#[test]
+fn clipboard_image_validity() {
+    let image = |size, len| ClipboardImage {
+        size,
+        rgba: vec![0; len],
+    };
+    assert!(image(Size::upx(3, 2), 24).is_valid());
+    assert!(image(Size::upx(0, 0), 0).is_valid());
+    assert!(!image(Size::upx(3, 2), 23).is_valid());
+    assert!(!image(Size::upx(3, 2), 25).is_valid());
+}
+
+#[cfg(feature = "image")]
+impl From<image::RgbaImage> for ClipboardImage {
+    fn from(image: image::RgbaImage) -> Self {
+        Self {
+            size: Size::upx(image.width(), image.height()),
+            rgba: image.into_raw(),
+        }
+    }
+}
+
+/// An error interacting with the system clipboard.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ClipboardError {
+    /// The clipboard is empty or its contents are not available in the
+    /// requested format.
+    Empty,
+    /// The clipboard is not supported on the current system.
+    Unsupported,
+    /// The clipboard is currently held by another party.
+    Occupied,
+    /// The clipboard contents could not be converted to or from the
+    /// requested format.
+    ConversionFailure,
+    /// Another error occurred.
+    Other(String),
+}
+
+impl From<arboard::Error> for ClipboardError {
+    fn from(error: arboard::Error) -> Self {
+        match error {
+            arboard::Error::ContentNotAvailable => Self::Empty,
+            arboard::Error::ClipboardNotSupported => Self::Unsupported,
+            arboard::Error::ClipboardOccupied => Self::Occupied,
+            arboard::Error::ConversionFailure => Self::ConversionFailure,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for ClipboardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClipboardError::Empty => {
+                f.write_str("the clipboard does not contain the requested format")
+            }
+            ClipboardError::Unsupported => f.write_str("the clipboard is not supported"),
+            ClipboardError::Occupied => f.write_str("the clipboard is held by another party"),
+            ClipboardError::ConversionFailure => {
+                f.write_str("the clipboard contents could not be converted")
+            }
+            ClipboardError::Other(description) => write!(f, "clipboard error: {description}"),
+        }
+    }
+}
+
+impl std::error::Error for ClipboardError {}
@@ -0,0 +1,192 @@
+/// An ordered list of focusable regions, tracking which region has keyboard
+/// focus.
+///
+/// Regions are identified by ids chosen by the application. The order regions
+/// are [registered](Self::register) in determines the order that
+/// [`traverse()`](Self::traverse) moves focus in. Applications that redraw
+/// their regions each frame can [`clear()`](Self::clear) the chain before
+/// registering regions again: the focused region is kept even if it is not
+/// registered again.
+///
+/// When returned from
+/// [`WindowBehavior::focus_chain`](super::WindowBehavior::focus_chain), the
+/// window traverses the chain when Tab or Shift+Tab is pressed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FocusChain {
+    order: Vec<u64>,
+    focused: Option<u64>,
+    wraps: bool,
+}
+
+impl FocusChain {
+    /// Returns a new, empty chain that wraps around when traversing past
+    /// either end.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            order: Vec::new(),
+            focused: None,
+            wraps: true,
+        }
+    }
+
+    /// Sets whether traversing past the last region focuses the first region,
+    /// and vice versa.
+    ///
+    /// When wrapping is disabled, traversing past either end clears the focus.
+    pub fn set_wrapping(&mut self, wraps: bool) {
+        self.wraps = wraps;
+    }
+
+    /// Returns true if traversing past either end of the chain wraps around.
+    #[must_use]
+    pub const fn wraps(&self) -> bool {
+        self.wraps
+    }
+
+    /// Adds `id` to the end of the chain. If `id` is already registered, its
+    /// position is unchanged.
+    pub fn register(&mut self, id: u64) {
+        if !self.order.contains(&id) {
+            self.order.push(id);
+        }
+    }
+
+    /// Removes `id` from the chain, clearing the focus if `id` was focused.
+    pub fn remove(&mut self, id: u64) {
+        self.order.retain(|registered| *registered != id);
+        if self.focused == Some(id) {
+            self.focused = None;
+        }
+    }
+
+    /// Removes all registered regions without changing the focus.
+    pub fn clear(&mut self) {
+        self.order.clear();
+    }
+
+    /// Returns the registered region ids in traversal order.
+    #[must_use]
+    pub fn regions(&self) -> &[u64] {
+        &self.order
+    }
+
+    /// Returns the number of registered regions.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Returns true if no regions are registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Returns the id of the focused region, if any.
+    #[must_use]
+    pub const fn focused(&self) -> Option<u64> {
+        self.focused
+    }
+
+    /// Returns true if `id` is the focused region.
+    #[must_use]
+    pub fn is_focused(&self, id: u64) -> bool {
+        self.focused == Some(id)
+    }
+
+    /// Focuses `id`. Returns false if `id` is not registered, leaving the
+    /// focus unchanged.
+    pub fn focus(&mut self, id: u64) -> bool {
+        if self.order.contains(&id) {
+            self.focused = Some(id);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Clears the focus, returning the previously focused region.
+    pub fn blur(&mut self) -> Option<u64> {
+        self.focused.take()
+    }
+
+    /// Moves the focus in `direction`, returning the resulting change.
+    ///
+    /// If the focused region is not registered, traversal starts from the
+    /// beginning or end of the chain.
+    pub fn traverse(&mut self, direction: FocusDirection) -> FocusTraversal {
+        let previous = self.focused;
+        let current = previous.and_then(|id| self.order.iter().position(|r| *r == id));
+        let last = self.order.len().checked_sub(1);
+        let next = match (direction, current) {
+            (_, _) if self.order.is_empty() => None,
+            (FocusDirection::Next, None) => Some(0),
+            (FocusDirection::Previous, None) => last,
+            (FocusDirection::Next, Some(index)) if Some(index) == last => self.wraps.then_some(0),
+            (FocusDirection::Next, Some(index)) => Some(index + 1),
+            (FocusDirection::Previous, Some(0)) => self.wraps.then_some(last).flatten(),
+            (FocusDirection::Previous, Some(index)) => Some(index - 1),
+        };
+        self.focused = next.map(|index| self.order[index]);
+        FocusTraversal {
+            direction,
+            previous,
+            focused: self.focused,
+        }
+    }
+}
+
+impl Default for FocusChain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A direction to move keyboard focus in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum FocusDirection {
+    /// Focus the next region, as when Tab is pressed.
+    Next,
+    /// Focus the previous region, as when Shift+Tab is pressed.
+    Previous,
+}
+
+/// A change of focus produced by [`FocusChain::traverse()`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct FocusTraversal {
+    /// The direction focus moved in.
+    pub direction: FocusDirection,
+    /// The region that was focused before traversing.
+    pub previous: Option<u64>,
+    /// The region that is now focused.
+    pub focused: Option<u64>,
+}
+
+#[test]
+fn focus_traversal() {
+    let mut chain = FocusChain::new();
+    for id in [1, 2, 3] {
+        chain.register(id);
+    }
+    assert_eq!(chain.traverse(FocusDirection::Next).focused, Some(1));
+    assert_eq!(chain.traverse(FocusDirection::Previous).focused, Some(3));
+    assert_eq!(chain.traverse(FocusDirection::Next).focused, Some(1));
+
+    assert!(chain.focus(2));
+    assert!(!chain.focus(4));
+    let traversal = chain.traverse(FocusDirection::Next);
+    assert_eq!(traversal.previous, Some(2));
+    assert_eq!(traversal.focused, Some(3));
+
+    chain.set_wrapping(false);
+    assert_eq!(chain.traverse(FocusDirection::Next).focused, None);
+    assert_eq!(chain.traverse(FocusDirection::Previous).focused, Some(3));
+
+    // Re-registering each frame keeps the focus.
+    chain.clear();
+    assert_eq!(chain.focused(), Some(3));
+    chain.register(3);
+    chain.remove(3);
+    assert_eq!(chain.focused(), None);
+}
@@ -0,0 +1,787 @@
+use std::marker::PhantomData;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use appit::winit::dpi::PhysicalPosition;
+use appit::winit::event::{
+    ElementState, Ime, Modifiers, MouseButton, MouseScrollDelta, TouchPhase,
+};
+use appit::winit::window::{Theme, WindowId};
+use figures::units::{Px, UPx};
+use figures::{FloatConversion, Point, Size};
+
+use super::synthetic::{synthetic_device_id, synthetic_window_id, SyntheticInput};
+use super::{
+    deliver_key_event, device_features, new_wgpu_instance, prepare_damage_fill, redraws_partially,
+    supported_multisample_count, FocusDirection, FocusTraversal, KeyEvent, PointerTracker,
+    UnrecoverableError, Window, WindowBehavior, WindowHandle, WindowInput, WindowMessage,
+    WindowMessageKind,
+};
+use crate::convert::DisplayScale;
+use crate::{Graphics, Kludgine, Texture};
+
+/// The state of a [`HeadlessWindow`] that is exposed through [`Window`].
+pub(super) struct VirtualWindow {
+    pub(super) id: WindowId,
+    pub(super) size: Size<UPx>,
+    pub(super) scale: f64,
+    pub(super) position: Point<Px>,
+    pub(super) title: String,
+    pub(super) focused: bool,
+    pub(super) occluded: bool,
+    pub(super) theme: Theme,
+    pub(super) close_requested: bool,
+    pub(super) input: WindowInput,
+    now: Duration,
+    next_redraw: Option<Duration>,
+}
+
+impl VirtualWindow {
+    pub(super) fn redraw_in(&mut self, duration: Duration) {
+        let target = self.now.saturating_add(duration);
+        if self.next_redraw.map_or(true, |next| target < next) {
+            self.next_redraw = Some(target);
+        }
+    }
+}
+
+/// A window that renders into a texture instead of a surface.
+///
+/// This type drives a [`WindowBehavior`] without a display server, making it
+/// possible to write end-to-end rendering tests that run in CI. The window has
+/// a fixed size until [`resize()`](Self::resize) is called, input is provided
+/// by calling functions such as [`cursor_moved()`](Self::cursor_moved), and
+/// time only passes when [`advance()`](Self::advance) is called.
+///
+/// Each headless window has a unique [`WindowId`] and a [`WindowHandle`] that
+/// can be used to send messages to it. Messages are delivered when
+/// [`process_messages()`](Self::process_messages) is called. Functions on
+/// [`Window`] that require a real window, such as [`Window::winit()`], return
+/// `None`. Functions that control the platform's window, such as
+/// [`Window::set_fullscreen()`], have no effect.
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+///
+/// use kludgine::app::{HeadlessWindow, WindowBehavior};
+/// use kludgine::figures::{Point, Size};
+/// # struct MyBehavior;
+/// # impl WindowBehavior for MyBehavior {
+/// #     type Context = ();
+/// #     fn initialize(
+/// #         _window: kludgine::app::Window<'_>,
+/// #         _graphics: &mut kludgine::Graphics<'_>,
+/// #         _context: (),
+/// #     ) -> Self {
+/// #         Self
+/// #     }
+/// #     fn render<'pass>(
+/// #         &'pass mut self,
+/// #         _window: kludgine::app::Window<'_>,
+/// #         _graphics: &mut kludgine::RenderingGraphics<'_, 'pass>,
+/// #     ) {
+/// #     }
+/// # }
+///
+/// let mut window = HeadlessWindow::<MyBehavior>::new(Size::new(320, 240), ()).unwrap();
+/// window.cursor_moved(Point::new(10, 10));
+/// window.advance(Duration::from_millis(16));
+/// window.render();
+/// let pixels = window.read_pixels().unwrap();
+/// ```
+pub struct HeadlessWindow<Behavior, WindowEvent = ()>
+where
+    Behavior: WindowBehavior<WindowEvent>,
+    WindowEvent: Send + 'static,
+{
+    behavior: Behavior,
+    kludgine: Kludgine,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    target: Texture,
+    msaa_texture: Option<wgpu::Texture>,
+    depth_texture: Option<wgpu::Texture>,
+    multisample_count: u32,
+    depth_testing: bool,
+    partial_redraw: bool,
+    retained_contents: bool,
+    window: VirtualWindow,
+    handle: WindowHandle<WindowEvent>,
+    messages: mpsc::Receiver<WindowMessage<WindowEvent>>,
+    pointer: PointerTracker,
+    last_render: Duration,
+    _event: PhantomData<WindowEvent>,
+}
+
+impl<Behavior, WindowEvent> HeadlessWindow<Behavior, WindowEvent>
+where
+    Behavior: WindowBehavior<WindowEvent>,
+    WindowEvent: Send + 'static,
+{
+    /// The format of the texture that headless windows render into.
+    pub const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+    /// Returns a new headless window of `size` with a scale of 1.0,
+    /// initializing `Behavior` with `context`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no compatible graphics adapter is available.
+    pub fn new(size: Size<UPx>, context: Behavior::Context) -> Result<Self, UnrecoverableError> {
+        let wgpu = new_wgpu_instance(Behavior::backends(&context));
+        let adapter = pollster::block_on(wgpu.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: Behavior::power_preference(&context),
+            force_fallback_adapter: Behavior::force_fallback_adapter(&context),
+            compatible_surface: None,
+        }))
+        .ok_or(UnrecoverableError::NoAdapter)?;
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("kludgine::device"),
+                required_features: device_features(
+                    Behavior::required_features(&context),
+                    Behavior::optional_features(&context),
+                    &adapter,
+                ),
+                required_limits: Kludgine::adjust_limits(Behavior::limits(
+                    adapter.limits(),
+                    &context,
+                )),
+                memory_hints: Behavior::memory_hints(&context),
+            },
+            None,
+        ))
+        .map_err(UnrecoverableError::Device)?;
+
+        let multisample_count = supported_multisample_count(
+            Behavior::multisample_count(&context).get(),
+            Self::FORMAT,
+            &device,
+            &adapter,
+        );
+        let depth_testing = Behavior::depth_testing(&context);
+        let partial_redraw = Behavior::partial_redraw(&context);
+        let mut window = VirtualWindow {
+            id: synthetic_window_id(),
+            size: nonzero_size(size),
+            scale: 1.0,
+            position: Point::default(),
+            title: Behavior::initial_window_attributes(&context).title,
+            focused: true,
+            occluded: false,
+            theme: Theme::Light,
+            close_requested: false,
+            input: WindowInput::default(),
+            now: Duration::ZERO,
+            next_redraw: Some(Duration::ZERO),
+        };
+        let mut kludgine = Kludgine::new(
+            &device,
+            &queue,
+            Self::FORMAT,
+            wgpu::MultisampleState {
+                count: multisample_count,
+                ..Default::default()
+            },
+            window.size,
+            1.0,
+        );
+        kludgine.set_adapter(&adapter);
+        let (target, msaa_texture) = create_targets(
+            &mut kludgine,
+            &device,
+            &queue,
+            window.size,
+            multisample_count,
+        );
+        let (sender, messages) = mpsc::channel();
+        let handle = WindowHandle::headless(sender);
+        let behavior = Behavior::initialize(
+            Window::headless(&mut window, &handle, Duration::ZERO, Duration::ZERO, None),
+            &mut Graphics::new(&mut kludgine, &device, &queue),
+            context,
+        );
+
+        let mut this = Self {
+            behavior,
+            kludgine,
+            device,
+            queue,
+            target,
+            msaa_texture,
+            depth_texture: None,
+            multisample_count,
+            depth_testing,
+            partial_redraw,
+            retained_contents: false,
+            window,
+            handle,
+            messages,
+            pointer: PointerTracker::default(),
+            last_render: Duration::ZERO,
+            _event: PhantomData,
+        };
+        let (behavior, window, kludgine) = this.parts();
+        behavior.initialized(window, kludgine);
+        Ok(this)
+    }
+
+    fn parts(&mut self) -> (&mut Behavior, Window<'_, WindowEvent>, &mut Kludgine) {
+        let elapsed = self.window.now.saturating_sub(self.last_render);
+        (
+            &mut self.behavior,
+            Window::headless(
+                &mut self.window,
+                &self.handle,
+                elapsed,
+                Duration::ZERO,
+                None,
+            ),
+            &mut self.kludgine,
+        )
+    }
+
+    /// Returns a reference to the window's behavior.
+    #[must_use]
+    pub const fn behavior(&self) -> &Behavior {
+        &self.behavior
+    }
+
+    /// Returns an exclusive reference to the window's behavior.
+    #[must_use]
+    pub fn behavior_mut(&mut self) -> &mut Behavior {
+        &mut self.behavior
+    }
+
+    /// Returns the Kludgine instance used to render this window.
+    #[must_use]
+    pub const fn kludgine(&self) -> &Kludgine {
+        &self.kludgine
+    }
+
+    /// Returns the device used to render this window.
+    #[must_use]
+    pub const fn device(&self) -> &wgpu::Device {
+        &self.device
+    }
+
+    /// Returns the queue used to render this window.
+    #[must_use]
+    pub const fn queue(&self) -> &wgpu::Queue {
+        &self.queue
+    }
+
+    /// Returns the unique id of this window.
+    #[must_use]
+    pub const fn id(&self) -> WindowId {
+        self.window.id
+    }
+
+    /// Returns a handle that can be used to send messages to this window.
+    ///
+    /// Messages sent through the handle are delivered when
+    /// [`process_messages()`](Self::process_messages) is called.
+    #[must_use]
+    pub fn handle(&self) -> WindowHandle<WindowEvent> {
+        self.handle.clone()
+    }
+
+    /// Delivers the messages that have been sent to this window's
+    /// [`WindowHandle`]s, and returns the number of messages delivered.
+    ///
+    /// [`advance()`](Self::advance) and
+    /// [`render_if_needed()`](Self::render_if_needed) call this function
+    /// before doing anything else.
+    pub fn process_messages(&mut self) -> usize {
+        let mut delivered = 0;
+        while let Ok(WindowMessage(message)) = self.messages.try_recv() {
+            delivered += 1;
+            match message {
+                WindowMessageKind::User(event) => self.send(event),
+                WindowMessageKind::Redraw(pending) => {
+                    pending.store(false, Ordering::Release);
+                    self.window.redraw_in(Duration::ZERO);
+                }
+                WindowMessageKind::Input(input) => self.inject(input),
+            }
+        }
+        delivered
+    }
+
+    /// Returns the texture this window renders into.
+    #[must_use]
+    pub const fn texture(&self) -> &Texture {
+        &self.target
+    }
+
+    /// Returns the amount of virtual time that has passed since this window
+    /// was created.
+    #[must_use]
+    pub const fn now(&self) -> Duration {
+        self.window.now
+    }
+
+    /// Advances the virtual clock by `duration`.
+    pub fn advance(&mut self, duration: Duration) {
+        self.process_messages();
+        self.window.now = self.window.now.saturating_add(duration);
+    }
+
+    /// Returns the virtual time at which the next redraw has been requested,
+    /// if one has been requested.
+    #[must_use]
+    pub const fn next_redraw(&self) -> Option<Duration> {
+        self.window.next_redraw
+    }
+
+    /// Returns true if a redraw has been requested at or before the current
+    /// virtual time.
+    #[must_use]
+    pub fn needs_redraw(&self) -> bool {
+        self.window
+            .next_redraw
+            .map_or(false, |next| next <= self.window.now)
+    }
+
+    /// Returns true if the behavior has closed the window, or if a close
+    /// request was accepted.
+    #[must_use]
+    pub const fn closed(&self) -> bool {
+        self.window.close_requested
+    }
+
+    /// Renders a frame into this window's texture and returns the texture.
+    ///
+    /// The elapsed time provided to the behavior is the amount of virtual time
+    /// that has passed since the previous frame was rendered.
+    pub fn render(&mut self) -> &Texture {
+        let elapsed = self.window.now.saturating_sub(self.last_render);
+        self.last_render = self.window.now;
+        self.window.next_redraw = None;
+        self.kludgine.animations_mut().advance(elapsed);
+        self.kludgine.clock_mut().tick(elapsed);
+
+        let mut frame = self.kludgine.next_frame();
+        let mut pending_inner_size = None;
+        self.behavior.prepare(
+            Window::headless(
+                &mut self.window,
+                &self.handle,
+                elapsed,
+                Duration::ZERO,
+                Some(&mut pending_inner_size),
+            ),
+            &mut frame.prepare(&self.device, &self.queue),
+        );
+
+        // The behavior may have changed the multisample count while preparing.
+        if frame.kludgine.multisample.count != self.multisample_count {
+            self.multisample_count = frame.kludgine.multisample.count;
+            self.msaa_texture =
+                create_msaa_texture(&self.device, self.window.size, self.multisample_count);
+            self.retained_contents = false;
+        }
+        let msaa_view = self
+            .msaa_texture
+            .as_ref()
+            .map(|msaa| msaa.create_view(&wgpu::TextureViewDescriptor::default()));
+        let (view, resolve_target) = match &msaa_view {
+            Some(msaa) => (msaa, Some(&*self.target.data.view)),
+            None => (&*self.target.data.view, None),
+        };
+        let clear_color = self.behavior.clear_color();
+        let partial = redraws_partially(
+            std::mem::take(&mut self.retained_contents),
+            false,
+            clear_color,
+        );
+        if !partial {
+            frame.kludgine.damage = None;
+        }
+        let damage_fill = prepare_damage_fill(&mut frame, clear_color, &self.device, &self.queue);
+        let color_attachments = [Some(wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target,
+            ops: wgpu::Operations {
+                load: clear_color
+                    .filter(|_| !partial)
+                    .map_or(wgpu::LoadOp::Load, |color| {
+                        wgpu::LoadOp::Clear(color.into())
+                    }),
+                store: wgpu::StoreOp::Store,
+            },
+        })];
+        let depth_view = self.depth_testing.then(|| {
+            frame
+                .kludgine
+                .depth_view(&mut self.depth_texture, &self.device)
+        });
+        let mut gfx = frame.render(
+            &wgpu::RenderPassDescriptor {
+                label: Some("kludgine::render"),
+                color_attachments: &color_attachments,
+                depth_stencil_attachment: depth_view.as_ref().map(Kludgine::depth_attachment),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            },
+            &self.device,
+            &self.queue,
+        );
+        if let Some(fill) = &damage_fill {
+            fill.render(&mut gfx);
+        }
+        self.behavior.render(
+            Window::headless(
+                &mut self.window,
+                &self.handle,
+                elapsed,
+                Duration::ZERO,
+                Some(&mut pending_inner_size),
+            ),
+            &mut gfx,
+        );
+        drop(gfx);
+        if let Some(id) = frame.submit(&self.queue) {
+            self.device.poll(wgpu::Maintain::WaitForSubmissionIndex(id));
+        }
+        // The render target and multisample texture preserve their contents
+        // until they are recreated.
+        self.retained_contents = self.partial_redraw;
+        if self.kludgine.animations().is_animating() {
+            self.window.redraw_in(Duration::ZERO);
+        }
+
+        if let Some(new_size) = pending_inner_size {
+            self.window.size = nonzero_size(new_size);
+        }
+        self.apply_size();
+        &self.target
+    }
+
+    /// Renders a frame if a redraw has been requested at or before the current
+    /// virtual time. Returns true if a frame was rendered.
+    pub fn render_if_needed(&mut self) -> bool {
+        self.process_messages();
+        if self.needs_redraw() {
+            self.render();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Reads the most recently rendered frame as tightly packed RGBA data.
+    ///
+    /// Returns `None` if the data could not be read from the GPU.
+    #[must_use]
+    pub fn read_pixels(&self) -> Option<Vec<u8>> {
+        self.target.read_pixels(&self.device, &self.queue)
+    }
+
+    /// Returns the most recently rendered frame as an image.
+    ///
+    /// Returns `None` if the data could not be read from the GPU.
+    #[cfg(feature = "image")]
+    #[must_use]
+    pub fn capture(&self) -> Option<image::RgbaImage> {
+        let size = self.target.size();
+        image::RgbaImage::from_raw(size.width.get(), size.height.get(), self.read_pixels()?)
+    }
+
+    fn apply_size(&mut self) {
+        let scale = DisplayScale::from_factor(self.window.scale).get();
+        let size_changed = self.window.size != self.kludgine.size();
+        let scale_changed = scale != self.kludgine.dpi_scale();
+        if !size_changed && !scale_changed {
+            return;
+        }
+
+        self.kludgine
+            .resize(self.window.size, scale, self.kludgine.zoom(), &self.queue);
+        if size_changed {
+            self.retained_contents = false;
+            (self.target, self.msaa_texture) = create_targets(
+                &mut self.kludgine,
+                &self.device,
+                &self.queue,
+                self.window.size,
+                self.multisample_count,
+            );
+        }
+        self.window.redraw_in(Duration::ZERO);
+
+        let (behavior, window, kludgine) = self.parts();
+        if scale_changed {
+            behavior.scale_factor_changed(window, kludgine);
+        } else {
+            behavior.resized(window, kludgine);
+        }
+        if scale_changed && size_changed {
+            let (behavior, window, kludgine) = self.parts();
+            behavior.resized(window, kludgine);
+        }
+    }
+
+    /// Resizes the window to `size`.
+    pub fn resize(&mut self, size: Size<UPx>) {
+        self.window.size = nonzero_size(size);
+        self.apply_size();
+    }
+
+    /// Sets the DPI scale of the window.
+    pub fn set_scale(&mut self, scale: f64) {
+        self.window.scale = scale;
+        self.apply_size();
+    }
+
+    /// Sets whether the window is focused.
+    pub fn set_focused(&mut self, focused: bool) {
+        if self.window.focused != focused {
+            self.window.focused = focused;
+            if !focused {
+                self.pointer.reset();
+                self.window.input.keyboard.release_all();
+                self.window.input.mouse.release_all();
+            }
+            let (behavior, window, kludgine) = self.parts();
+            behavior.focus_changed(window, kludgine);
+        }
+    }
+
+    /// Sets whether the window is occluded.
+    pub fn set_occluded(&mut self, occluded: bool) {
+        if self.window.occluded != occluded {
+            self.window.occluded = occluded;
+            let (behavior, window, kludgine) = self.parts();
+            behavior.occlusion_changed(window, kludgine);
+        }
+    }
+
+    /// Sets the window's theme.
+    pub fn set_theme(&mut self, theme: Theme) {
+        if self.window.theme != theme {
+            self.window.theme = theme;
+            let (behavior, window, kludgine) = self.parts();
+            behavior.theme_changed(window, kludgine);
+        }
+    }
+
+    /// Requests that the window be closed. Returns true if the behavior
+    /// allowed the window to close.
+    pub fn request_close(&mut self) -> bool {
+        let (behavior, window, kludgine) = self.parts();
+        let close = behavior.close_requested(window, kludgine);
+        if close {
+            self.window.close_requested = true;
+        }
+        close
+    }
+
+    /// Moves the cursor to `position` within the window.
+    ///
+    /// If the cursor was not previously over the window,
+    /// [`WindowBehavior::cursor_entered`] is invoked first.
+    pub fn cursor_moved(&mut self, position: Point<Px>) {
+        if self.window.input.mouse.position().is_none() {
+            let (behavior, window, kludgine) = self.parts();
+            behavior.cursor_entered(window, kludgine, synthetic_device_id());
+        }
+        self.window.input.mouse.set_position(Some(position));
+        let (behavior, window, kludgine) = self.parts();
+        behavior.cursor_moved(
+            window,
+            kludgine,
+            synthetic_device_id(),
+            PhysicalPosition::new(
+                f64::from(position.x.into_float()),
+                f64::from(position.y.into_float()),
+            ),
+        );
+
+        for drag in self
+            .pointer
+            .moved(position, &self.behavior.pointer_settings())
+        {
+            let (behavior, window, kludgine) = self.parts();
+            behavior.drag_started(window, kludgine, synthetic_device_id(), drag);
+        }
+    }
+
+    /// Moves the cursor outside of the window.
+    pub fn cursor_left(&mut self) {
+        if self.window.input.mouse.position().is_some() {
+            self.window.input.mouse.set_position(None);
+            let (behavior, window, kludgine) = self.parts();
+            behavior.cursor_left(window, kludgine, synthetic_device_id());
+        }
+    }
+
+    /// Presses or releases a mouse button.
+    pub fn mouse_input(&mut self, button: MouseButton, state: ElementState) {
+        self.window
+            .input
+            .mouse
+            .set_pressed(button, state.is_pressed());
+        let (behavior, window, kludgine) = self.parts();
+        behavior.mouse_input(window, kludgine, synthetic_device_id(), state, button);
+
+        match state {
+            ElementState::Pressed => {
+                if let Some(position) = self.window.input.mouse.position() {
+                    let click = self.pointer.press(
+                        button,
+                        position,
+                        self.window.now,
+                        &self.behavior.pointer_settings(),
+                    );
+                    let (behavior, window, kludgine) = self.parts();
+                    behavior.mouse_clicked(window, kludgine, synthetic_device_id(), click);
+                }
+            }
+            ElementState::Released => self.pointer.release(button),
+        }
+    }
+
+    /// Clicks `button` at `position`, pressing and releasing it.
+    pub fn click(&mut self, button: MouseButton, position: Point<Px>) {
+        self.cursor_moved(position);
+        self.mouse_input(button, ElementState::Pressed);
+        self.mouse_input(button, ElementState::Released);
+    }
+
+    /// Scrolls the mouse wheel.
+    pub fn mouse_wheel(&mut self, delta: MouseScrollDelta, phase: TouchPhase) {
+        let (behavior, window, kludgine) = self.parts();
+        behavior.mouse_wheel(window, kludgine, synthetic_device_id(), delta, phase);
+    }
+
+    /// Presses or releases a key.
+    ///
+    /// The event is handled the same as keyboard input from the operating
+    /// system: presses of Tab and Shift+Tab traverse the behavior's
+    /// [`focus_chain()`](WindowBehavior::focus_chain) if it is not empty, and
+    /// all other events are delivered to [`WindowBehavior::keyboard_input`].
+    /// Use [`received_character()`](Self::received_character) or
+    /// [`ime()`](Self::ime) to simulate text input.
+    pub fn key_input(&mut self, event: KeyEvent) {
+        self.window
+            .input
+            .keyboard
+            .set_pressed(event.physical_key, event.state.is_pressed());
+        let (behavior, window, kludgine) = self.parts();
+        deliver_key_event(
+            behavior,
+            window,
+            kludgine,
+            synthetic_device_id(),
+            event,
+            false,
+        );
+    }
+
+    /// Simulates pressing Tab, or Shift+Tab when `direction` is
+    /// [`FocusDirection::Previous`].
+    ///
+    /// If the behavior's [`focus_chain()`](WindowBehavior::focus_chain) is
+    /// not empty, the chain is traversed and
+    /// [`WindowBehavior::focus_traversed`] is invoked. Returns the resulting
+    /// traversal, if any.
+    pub fn traverse_focus(&mut self, direction: FocusDirection) -> Option<FocusTraversal> {
+        let traversal = self
+            .behavior
+            .focus_chain()
+            .filter(|chain| !chain.is_empty())?
+            .traverse(direction);
+        let (behavior, window, kludgine) = self.parts();
+        behavior.focus_traversed(window, kludgine, traversal);
+        Some(traversal)
+    }
+
+    /// Sets the currently pressed modifiers.
+    pub fn modifiers_changed(&mut self, modifiers: Modifiers) {
+        self.window.input.keyboard.set_modifiers(modifiers);
+        let (behavior, window, kludgine) = self.parts();
+        behavior.modifiers_changed(window, kludgine);
+    }
+
+    /// Simulates a character being typed.
+    pub fn received_character(&mut self, char: char) {
+        let (behavior, window, kludgine) = self.parts();
+        behavior.received_character(window, kludgine, char);
+    }
+
+    /// Simulates an input method event.
+    pub fn ime(&mut self, ime: Ime) {
+        let (behavior, window, kludgine) = self.parts();
+        behavior.ime(window, kludgine, ime);
+    }
+
+    /// Delivers synthetic `input` to the window.
+    ///
+    /// Each kind of input is equivalent to calling the function on this type
+    /// that simulates it, such as [`cursor_moved()`](Self::cursor_moved) for
+    /// [`SyntheticInput::CursorMoved`].
+    pub fn inject(&mut self, input: SyntheticInput) {
+        match input {
+            SyntheticInput::CursorMoved(position) => self.cursor_moved(position),
+            SyntheticInput::CursorLeft => self.cursor_left(),
+            SyntheticInput::MouseInput { button, state } => self.mouse_input(button, state),
+            SyntheticInput::Click { button, position } => self.click(button, position),
+            SyntheticInput::MouseWheel { delta, phase } => self.mouse_wheel(delta, phase),
+            SyntheticInput::Key(event) => self.key_input(event),
+            SyntheticInput::Modifiers(modifiers) => self.modifiers_changed(modifiers),
+            SyntheticInput::Character(char) => self.received_character(char),
+            SyntheticInput::Ime(ime) => self.ime(ime),
+        }
+    }
+
+    /// Delivers `event` to the behavior.
+    pub fn send(&mut self, event: WindowEvent) {
+        let (behavior, window, kludgine) = self.parts();
+        behavior.event(window, kludgine, event);
+    }
+}
+
+fn nonzero_size(size: Size<UPx>) -> Size<UPx> {
+    Size::new(size.width.get().max(1), size.height.get().max(1))
+}
+
+fn create_targets(
+    kludgine: &mut Kludgine,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    size: Size<UPx>,
+    multisample_count: u32,
+) -> (Texture, Option<wgpu::Texture>) {
+    let target = Texture::new_labeled(
+        &Graphics::new(kludgine, device, queue),
+        "kludgine::render target",
+        size,
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        wgpu::TextureUsages::RENDER_ATTACHMENT
+            | wgpu::TextureUsages::COPY_SRC
+            | wgpu::TextureUsages::TEXTURE_BINDING,
+        wgpu::FilterMode::Linear,
+    );
+    (target, create_msaa_texture(device, size, multisample_count))
+}
+
+fn create_msaa_texture(
+    device: &wgpu::Device,
+    size: Size<UPx>,
+    multisample_count: u32,
+) -> Option<wgpu::Texture> {
+    (multisample_count > 1).then(|| {
+        device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("kludgine::render target (multisampled)"),
+            size: size.into(),
+            mip_level_count: 1,
+            sample_count: multisample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        })
+    })
+}
@@ -0,0 +1,74 @@
+use appit::winit;
+use appit::winit::event::ElementState;
+use appit::winit::keyboard::{Key, KeyLocation, PhysicalKey, SmolStr};
+
+/// A key being pressed or released.
+///
+/// This type contains the same information as winit's
+/// [`KeyEvent`](winit::event::KeyEvent), which cannot be constructed outside
+/// of winit. Unlike winit's type, key events can be created by applications,
+/// allowing them to be [synthesized](super::SyntheticInput::Key) and delivered
+/// through the same code path as keyboard input from the operating system.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct KeyEvent {
+    /// The key's physical location on the keyboard, regardless of the
+    /// keyboard layout.
+    pub physical_key: PhysicalKey,
+    /// The key as interpreted by the current keyboard layout and modifiers.
+    pub logical_key: Key,
+    /// The text produced by this key press, if any.
+    pub text: Option<SmolStr>,
+    /// The location of the key, for keys that appear more than once on a
+    /// keyboard.
+    pub location: KeyLocation,
+    /// Whether the key was pressed or released.
+    pub state: ElementState,
+    /// True if this event was produced by holding the key down.
+    pub repeat: bool,
+}
+
+impl KeyEvent {
+    /// Returns an event for `physical_key` changing to `state`, which the
+    /// keyboard layout interprets as `logical_key`.
+    ///
+    /// The returned event does not produce any text and is not a repeat.
+    #[must_use]
+    pub fn new(
+        physical_key: impl Into<PhysicalKey>,
+        logical_key: impl Into<Key>,
+        state: ElementState,
+    ) -> Self {
+        Self {
+            physical_key: physical_key.into(),
+            logical_key: logical_key.into(),
+            text: None,
+            location: KeyLocation::Standard,
+            state,
+            repeat: false,
+        }
+    }
+}
+
+impl From<winit::event::KeyEvent> for KeyEvent {
+    fn from(event: winit::event::KeyEvent) -> Self {
+        Self {
+            physical_key: event.physical_key,
+            logical_key: event.logical_key,
+            text: event.text,
+            location: event.location,
+            state: event.state,
+            repeat: event.repeat,
+        }
+    }
+}
+
+#[test]
+fn new_key_events() {
+    use appit::winit::keyboard::{KeyCode, NamedKey};
+
+    let event = KeyEvent::new(KeyCode::Tab, NamedKey::Tab, ElementState::Pressed);
+    assert_eq!(event.physical_key, PhysicalKey::Code(KeyCode::Tab));
+    assert_eq!(event.logical_key, Key::Named(NamedKey::Tab));
+    assert_eq!(event.text, None);
+    assert!(!event.repeat);
+}
@@ -0,0 +1,70 @@
+use std::any::Any;
+
+#[cfg(feature = "cosmic-text")]
+use figures::units::Px;
+#[cfg(feature = "cosmic-text")]
+use figures::{IntoSigned, Point};
+
+use crate::drawing::Drawing;
+#[cfg(feature = "cosmic-text")]
+use crate::text::Text;
+#[cfg(feature = "cosmic-text")]
+use crate::DrawableExt;
+use crate::{Color, Graphics, RenderingGraphics};
+
+/// An overlay displayed in place of a window's contents after a panic was
+/// caught while preparing or rendering.
+pub(super) struct PanicOverlay {
+    message: String,
+    drawing: Drawing,
+}
+
+impl PanicOverlay {
+    pub(super) const BACKGROUND: Color = Color::new(80, 16, 16, 255);
+
+    pub(super) fn new(message: String) -> Self {
+        Self {
+            message: format!("{message}\n\nClick or press a key to continue."),
+            drawing: Drawing::default(),
+        }
+    }
+
+    #[cfg_attr(not(feature = "cosmic-text"), allow(unused_mut))]
+    pub(super) fn prepare(&mut self, graphics: &mut Graphics<'_>) {
+        let mut renderer = self.drawing.new_frame(graphics);
+        #[cfg(feature = "cosmic-text")]
+        {
+            let margin = Px::new(16);
+            let width = renderer.size().width.into_signed() - margin - margin;
+            renderer.draw_text(
+                Text::new(&self.message, Color::WHITE)
+                    .wrap_at(width)
+                    .translate_by(Point::squared(margin)),
+            );
+        }
+        drop(renderer);
+    }
+
+    pub(super) fn render<'pass>(&'pass self, graphics: &mut RenderingGraphics<'_, 'pass>) {
+        self.drawing.render(1., graphics);
+    }
+}
+
+/// Returns the message a panic was raised with.
+pub(super) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("a panic occurred")
+    }
+}
+
+#[test]
+fn panic_messages() {
+    let payload = std::panic::catch_unwind(|| panic!("static message")).unwrap_err();
+    assert_eq!(panic_message(&*payload), "static message");
+    let payload = std::panic::catch_unwind(|| panic!("formatted {}", 1)).unwrap_err();
+    assert_eq!(panic_message(&*payload), "formatted 1");
+}
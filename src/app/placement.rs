@@ -0,0 +1,109 @@
+use figures::units::{Px, UPx};
+use figures::{IntoSigned, IntoUnsigned, Point, Rect, Size};
+use intentional::Cast;
+
+use super::centered_in;
+
+/// A placement of a window on a [`Monitor`](super::Monitor).
+///
+/// Placements are resolved against the monitor's full
+/// [region](super::Monitor::region). Not every platform reports the area of a
+/// monitor that excludes taskbars and docks, so windows placed near the edges
+/// of a monitor may be partially covered by them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WindowPlacement {
+    /// The window keeps its size and is centered on the monitor.
+    Centered,
+    /// The window is resized to a fraction of the monitor's size and centered
+    /// on the monitor.
+    Relative {
+        /// The fraction of the monitor's width, from `0.0` to `1.0`.
+        width: f32,
+        /// The fraction of the monitor's height, from `0.0` to `1.0`.
+        height: f32,
+    },
+    /// The window keeps its size and its top-left corner is placed at an
+    /// offset from the monitor's top-left corner.
+    Offset(Point<Px>),
+    /// The window is resized to cover the monitor without becoming
+    /// fullscreen.
+    Fill,
+}
+
+impl WindowPlacement {
+    /// Returns a placement that resizes the window to `fraction` of the
+    /// monitor's width and height and centers it.
+    #[must_use]
+    pub const fn relative(fraction: f32) -> Self {
+        Self::Relative {
+            width: fraction,
+            height: fraction,
+        }
+    }
+
+    /// Returns the region a window that is currently `size` large occupies
+    /// when placed within `area`.
+    #[must_use]
+    pub fn region(self, area: Rect<Px>, size: Size<UPx>) -> Rect<Px> {
+        match self {
+            WindowPlacement::Centered => Rect::new(centered_in(area, size), size.into_signed()),
+            WindowPlacement::Relative { width, height } => {
+                let scale = |length: Px, fraction: f32| {
+                    Px::new(
+                        (length.get().cast::<f32>() * fraction.clamp(0., 1.))
+                            .round()
+                            .cast::<i32>()
+                            .max(1),
+                    )
+                };
+                let size = Size::new(
+                    scale(area.size.width, width),
+                    scale(area.size.height, height),
+                );
+                Rect::new(centered_in(area, size.into_unsigned()), size)
+            }
+            WindowPlacement::Offset(offset) => Rect::new(area.origin + offset, size.into_signed()),
+            WindowPlacement::Fill => area,
+        }
+    }
+
+    /// Returns true if this placement changes the size of windows.
+    #[must_use]
+    pub const fn resizes(self) -> bool {
+        matches!(
+            self,
+            WindowPlacement::Relative { .. } | WindowPlacement::Fill
+        )
+    }
+}
+
+#[test]
+fn placement_regions() {
+    let area = Rect::new(
+        Point::new(Px::new(-1920), Px::new(0)),
+        Size::new(Px::new(1920), Px::new(1080)),
+    );
+    let size = Size::new(UPx::new(800), UPx::new(600));
+    assert_eq!(
+        WindowPlacement::Centered.region(area, size),
+        Rect::new(
+            Point::new(Px::new(-1360), Px::new(240)),
+            Size::new(Px::new(800), Px::new(600))
+        )
+    );
+    assert_eq!(
+        WindowPlacement::relative(0.5).region(area, size),
+        Rect::new(
+            Point::new(Px::new(-1440), Px::new(270)),
+            Size::new(Px::new(960), Px::new(540))
+        )
+    );
+    assert_eq!(
+        WindowPlacement::Offset(Point::new(Px::new(10), Px::new(20))).region(area, size),
+        Rect::new(
+            Point::new(Px::new(-1910), Px::new(20)),
+            Size::new(Px::new(800), Px::new(600))
+        )
+    );
+    assert_eq!(WindowPlacement::Fill.region(area, size), area);
+}
@@ -0,0 +1,180 @@
+use std::time::Duration;
+
+use appit::winit::event::MouseButton;
+use figures::units::Px;
+use figures::{FloatConversion, Point};
+
+/// Settings that control how clicks and drags are recognized.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PointerSettings {
+    /// The maximum amount of time between presses of the same button for
+    /// them to be counted as a double or triple click.
+    pub multi_click_interval: Duration,
+    /// The maximum distance the cursor can move between presses of the same
+    /// button for them to be counted as a double or triple click.
+    pub multi_click_distance: Px,
+    /// The distance the cursor must move while a button is pressed before
+    /// [`WindowBehavior::drag_started`](super::WindowBehavior::drag_started)
+    /// is invoked.
+    pub drag_threshold: Px,
+}
+
+impl Default for PointerSettings {
+    /// Returns settings matching the defaults of most desktop platforms: a
+    /// 500 millisecond multi-click interval and 4 pixel distances.
+    fn default() -> Self {
+        Self {
+            multi_click_interval: Duration::from_millis(500),
+            multi_click_distance: Px::new(4),
+            drag_threshold: Px::new(4),
+        }
+    }
+}
+
+/// A mouse button press, including how many times the button has been pressed
+/// in quick succession.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Click {
+    /// The button that was pressed.
+    pub button: MouseButton,
+    /// The location of the cursor when the button was pressed.
+    pub position: Point<Px>,
+    /// The number of consecutive clicks: 1 for a single click, 2 for a double
+    /// click, 3 for a triple click, and so on.
+    pub count: u32,
+}
+
+/// The start of a drag operation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DragStart {
+    /// The button being held.
+    pub button: MouseButton,
+    /// The location of the cursor when the button was pressed.
+    pub origin: Point<Px>,
+    /// The current location of the cursor.
+    pub position: Point<Px>,
+}
+
+/// Synthesizes click counts and drag starts from raw mouse input.
+#[derive(Debug, Default)]
+pub(super) struct PointerTracker {
+    last_click: Option<(Click, Duration)>,
+    pressed: Vec<PressedButton>,
+}
+
+#[derive(Debug)]
+struct PressedButton {
+    button: MouseButton,
+    origin: Point<Px>,
+    dragging: bool,
+}
+
+impl PointerTracker {
+    /// Records `button` being pressed at `position`. `now` is the time elapsed
+    /// since an arbitrary, fixed point in time.
+    pub(super) fn press(
+        &mut self,
+        button: MouseButton,
+        position: Point<Px>,
+        now: Duration,
+        settings: &PointerSettings,
+    ) -> Click {
+        let count = match self.last_click {
+            Some((last, at))
+                if last.button == button
+                    && now.saturating_sub(at) <= settings.multi_click_interval
+                    && distance(last.position, position)
+                        <= settings.multi_click_distance.into_float() =>
+            {
+                last.count.saturating_add(1)
+            }
+            _ => 1,
+        };
+        let click = Click {
+            button,
+            position,
+            count,
+        };
+        self.last_click = Some((click, now));
+
+        self.pressed.retain(|pressed| pressed.button != button);
+        self.pressed.push(PressedButton {
+            button,
+            origin: position,
+            dragging: false,
+        });
+        click
+    }
+
+    /// Records `button` being released.
+    pub(super) fn release(&mut self, button: MouseButton) {
+        self.pressed.retain(|pressed| pressed.button != button);
+    }
+
+    /// Records the cursor moving to `position`, returning any drags that
+    /// started as a result.
+    pub(super) fn moved(
+        &mut self,
+        position: Point<Px>,
+        settings: &PointerSettings,
+    ) -> Vec<DragStart> {
+        let threshold = settings.drag_threshold.into_float();
+        let mut started = Vec::new();
+        for pressed in &mut self.pressed {
+            if !pressed.dragging && distance(pressed.origin, position) >= threshold {
+                pressed.dragging = true;
+                started.push(DragStart {
+                    button: pressed.button,
+                    origin: pressed.origin,
+                    position,
+                });
+            }
+        }
+        started
+    }
+
+    /// Forgets all pressed buttons, such as when the window loses focus.
+    pub(super) fn reset(&mut self) {
+        self.pressed.clear();
+        self.last_click = None;
+    }
+}
+
+fn distance(a: Point<Px>, b: Point<Px>) -> f32 {
+    let delta = (b - a).into_float();
+    delta.x.hypot(delta.y)
+}
+
+#[test]
+fn click_counting() {
+    let settings = PointerSettings::default();
+    let mut tracker = PointerTracker::default();
+    let origin = Point::new(Px::new(10), Px::new(10));
+    let mut now = Duration::ZERO;
+    for expected in 1..=3 {
+        let click = tracker.press(MouseButton::Left, origin, now, &settings);
+        assert_eq!(click.count, expected);
+        tracker.release(MouseButton::Left);
+        now += Duration::from_millis(100);
+    }
+
+    // Too slow.
+    now += settings.multi_click_interval;
+    assert_eq!(
+        tracker
+            .press(MouseButton::Left, origin, now, &settings)
+            .count,
+        1
+    );
+
+    // Dragging only starts once the threshold is reached, and only once.
+    assert!(tracker
+        .moved(Point::new(Px::new(12), Px::new(10)), &settings)
+        .is_empty());
+    let drags = tracker.moved(Point::new(Px::new(20), Px::new(10)), &settings);
+    assert_eq!(drags.len(), 1);
+    assert_eq!(drags[0].origin, origin);
+    assert!(tracker
+        .moved(Point::new(Px::new(30), Px::new(10)), &settings)
+        .is_empty());
+}
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError};
+
+use appit::winit::dpi::PhysicalPosition;
+use appit::winit::window::{Window as WinitWindow, WindowId};
+use figures::units::{Px, UPx};
+use figures::{IntoSigned, Point, Rect, Size};
+
+/// The relationship between a window and the window that opened it.
+///
+/// Returned from
+/// [`WindowBehavior::parent_window`](super::WindowBehavior::parent_window).
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct ParentWindow {
+    /// The id of the parent window.
+    pub id: WindowId,
+    /// When true, the parent window does not receive keyboard, mouse, or touch
+    /// input while the child window is open.
+    pub modal: bool,
+    /// When true, the child window is centered above its parent when it is
+    /// opened.
+    pub centered: bool,
+}
+
+impl ParentWindow {
+    /// Returns a non-modal relationship with the window `id`.
+    #[must_use]
+    pub const fn new(id: WindowId) -> Self {
+        Self {
+            id,
+            modal: false,
+            centered: false,
+        }
+    }
+
+    /// Returns this relationship with input to the parent window blocked while
+    /// the child is open.
+    #[must_use]
+    pub const fn modal(mut self) -> Self {
+        self.modal = true;
+        self
+    }
+
+    /// Returns this relationship with the child window centered above its
+    /// parent when it is opened.
+    #[must_use]
+    pub const fn centered(mut self) -> Self {
+        self.centered = true;
+        self
+    }
+}
+
+/// Returns the position that centers a window of `size` within `area`.
+#[must_use]
+pub fn centered_in(area: Rect<Px>, size: Size<UPx>) -> Point<Px> {
+    let size = size.into_signed();
+    Point::new(
+        area.origin.x + (area.size.width - size.width) / 2,
+        area.origin.y + (area.size.height - size.height) / 2,
+    )
+}
+
+struct RegisteredWindow {
+    winit: Arc<WinitWindow>,
+    parent: Option<ParentWindow>,
+}
+
+fn windows() -> MutexGuard<'static, HashMap<WindowId, RegisteredWindow>> {
+    static WINDOWS: OnceLock<Mutex<HashMap<WindowId, RegisteredWindow>>> = OnceLock::new();
+    WINDOWS
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Keeps a window's relationships registered until dropped.
+pub(super) struct WindowRegistration(WindowId);
+
+impl WindowRegistration {
+    pub(super) fn new(winit: &Arc<WinitWindow>, parent: Option<ParentWindow>) -> Self {
+        let id = winit.id();
+        if let Some(parent) = parent.filter(|parent| parent.centered) {
+            if let Some(area) = outer_rect(parent.id) {
+                let origin = centered_in(area, Size::from(winit.outer_size()));
+                winit.set_outer_position(PhysicalPosition::<i32>::from(origin));
+            }
+        }
+        windows().insert(
+            id,
+            RegisteredWindow {
+                winit: winit.clone(),
+                parent,
+            },
+        );
+        Self(id)
+    }
+}
+
+impl Drop for WindowRegistration {
+    fn drop(&mut self) {
+        let mut windows = windows();
+        windows.remove(&self.0);
+        // Any children outlive their parent as independent windows.
+        for window in windows.values_mut() {
+            if window.parent.is_some_and(|parent| parent.id == self.0) {
+                window.parent = None;
+            }
+        }
+    }
+}
+
+/// Returns the relationship of `id` to its parent, if it has one.
+pub(super) fn parent_of(id: WindowId) -> Option<ParentWindow> {
+    windows().get(&id).and_then(|window| window.parent)
+}
+
+/// Returns the ids of all open windows whose parent is `id`.
+pub(super) fn children_of(id: WindowId) -> Vec<WindowId> {
+    windows()
+        .iter()
+        .filter(|(_, window)| window.parent.is_some_and(|parent| parent.id == id))
+        .map(|(child, _)| *child)
+        .collect()
+}
+
+/// Returns an open modal child of `id`, if one exists.
+pub(super) fn modal_child(id: WindowId) -> Option<Arc<WinitWindow>> {
+    windows()
+        .values()
+        .find(|window| {
+            window
+                .parent
+                .is_some_and(|parent| parent.id == id && parent.modal)
+        })
+        .map(|window| window.winit.clone())
+}
+
+/// Returns the outer rectangle of the open window `id`.
+pub(super) fn outer_rect(id: WindowId) -> Option<Rect<Px>> {
+    let winit = windows().get(&id)?.winit.clone();
+    let origin = Point::from(winit.outer_position().ok()?);
+    Some(Rect::new(
+        origin,
+        Size::<UPx>::from(winit.outer_size()).into_signed(),
+    ))
+}
+
+#[test]
+fn centering() {
+    let area = Rect::new(
+        Point::new(Px::new(100), Px::new(50)),
+        Size::new(Px::new(400), Px::new(300)),
+    );
+    assert_eq!(
+        centered_in(area, Size::new(UPx::new(200), UPx::new(100))),
+        Point::new(Px::new(200), Px::new(150))
+    );
+    // Windows larger than the area overhang it evenly.
+    assert_eq!(
+        centered_in(area, Size::new(UPx::new(600), UPx::new(300))),
+        Point::new(Px::new(0), Px::new(50))
+    );
+}
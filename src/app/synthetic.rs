@@ -0,0 +1,86 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use appit::winit::event::{
+    DeviceId, ElementState, Ime, Modifiers, MouseButton, MouseScrollDelta, TouchPhase,
+};
+use appit::winit::window::WindowId;
+use figures::units::Px;
+use figures::Point;
+
+use super::KeyEvent;
+
+/// An input event that is delivered to a window without involving the
+/// operating system.
+///
+/// Synthetic input is delivered to running windows using
+/// [`WindowHandle::inject()`](super::WindowHandle::inject) and to headless
+/// windows using
+/// [`HeadlessWindow::inject()`](super::HeadlessWindow::inject). Each event
+/// invokes the same [`WindowBehavior`](super::WindowBehavior) functions as
+/// the equivalent input from the operating system, and updates the state
+/// returned from [`Window::keyboard()`](super::Window::keyboard) and
+/// [`Window::mouse()`](super::Window::mouse). This enables automated tests
+/// that interact with a window.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum SyntheticInput {
+    /// The cursor moved to a position within the window.
+    ///
+    /// [`WindowBehavior::cursor_entered`](super::WindowBehavior::cursor_entered)
+    /// is invoked first if the cursor was not above the window.
+    CursorMoved(Point<Px>),
+    /// The cursor moved outside of the window.
+    CursorLeft,
+    /// A mouse button was pressed or released.
+    MouseInput {
+        /// The button that changed.
+        button: MouseButton,
+        /// The new state of the button.
+        state: ElementState,
+    },
+    /// The cursor moved to a position within the window, and a mouse button
+    /// was pressed and released.
+    Click {
+        /// The button that was clicked.
+        button: MouseButton,
+        /// The position the button was clicked at.
+        position: Point<Px>,
+    },
+    /// The mouse wheel was scrolled.
+    MouseWheel {
+        /// The amount scrolled.
+        delta: MouseScrollDelta,
+        /// The phase of the scroll gesture.
+        phase: TouchPhase,
+    },
+    /// A key was pressed or released.
+    ///
+    /// The event is delivered to
+    /// [`WindowBehavior::keyboard_input`](super::WindowBehavior::keyboard_input),
+    /// or traverses the window's
+    /// [focus chain](super::WindowBehavior::focus_chain) if it is a press of
+    /// Tab or Shift+Tab.
+    Key(KeyEvent),
+    /// The active modifiers changed.
+    Modifiers(Modifiers),
+    /// A character was typed.
+    Character(char),
+    /// An input method event occurred.
+    Ime(Ime),
+}
+
+/// Returns the device id reported with synthetic input.
+#[allow(unsafe_code)]
+pub(super) fn synthetic_device_id() -> DeviceId {
+    // SAFETY: The dummy device id is only passed to `WindowBehavior`
+    // callbacks and is never given to winit.
+    unsafe { DeviceId::dummy() }
+}
+
+/// Returns a new, unique id for a headless window.
+pub(super) fn synthetic_window_id() -> WindowId {
+    // Ids are allocated downward from `u64::MAX` so that they do not collide
+    // with the ids the platform assigns to windows.
+    static NEXT_ID: AtomicU64 = AtomicU64::new(u64::MAX);
+    WindowId::from(NEXT_ID.fetch_sub(1, Ordering::Relaxed))
+}
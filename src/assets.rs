@@ -0,0 +1,703 @@
+//! Loading and caching of textures, sprites, and fonts.
+//!
+//! An [`AssetCache`] loads assets by key from a directory on disk or from a
+//! [`Bundle`] of embedded files. Each asset is loaded once on a background thread,
+//! and every request for the same key returns a handle to the same [`Asset`].
+//!
+//! When loading from a directory, debug builds watch the files of each loaded
+//! asset and reload the asset when its files change. This allows editing
+//! images and [Aseprite](https://www.aseprite.org/) exports while an
+//! application is running.
+//!
+//! ```rust,no_run
+//! use kludgine::assets::AssetCache;
+//!
+//! let assets = AssetCache::new("assets");
+//! let player = assets.sprite("player");
+//! let background = assets.texture("background.png");
+//!
+//! // Each frame, draw the assets that have finished loading. When the files
+//! // change, the next call to `get()` returns the reloaded asset.
+//! if let Some(background) = background.get() {
+//!     // draw background
+//! }
+//! ```
+
+use std::any::Any;
+use std::borrow::Cow;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Condvar, Mutex, MutexGuard, PoisonError, Weak};
+use std::time::{Duration, SystemTime};
+use std::{fs, io, thread};
+
+use ahash::AHashMap;
+pub use bundle::{Bundle, BundleError};
+
+use crate::sprite::{Sprite, SpriteParseError};
+use crate::LazyTexture;
+#[cfg(feature = "cosmic-text")]
+use crate::{cosmic_text, Kludgine};
+
+mod bundle;
+
+/// How often files are checked for changes when hot reloading is enabled.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// The largest number of threads used to load assets.
+const MAXIMUM_WORKERS: usize = 4;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// A cache of assets loaded from a directory or an embedded bundle.
+///
+/// Cloning a cache is cheap and produces a handle to the same cache. The
+/// threads used to load assets exit once every handle to the cache has been
+/// dropped.
+#[derive(Clone)]
+pub struct AssetCache {
+    data: Arc<CacheData>,
+}
+
+struct CacheData {
+    source: Arc<AssetSource>,
+    filter_mode: Mutex<wgpu::FilterMode>,
+    assets: Mutex<AHashMap<(AssetKind, String), Arc<dyn Entry>>>,
+    jobs: Mutex<Option<mpsc::Sender<Job>>>,
+    hot_reload: AtomicBool,
+    watching: AtomicBool,
+}
+
+impl AssetCache {
+    /// Returns a cache that loads assets from files within `root`.
+    ///
+    /// Hot reloading is enabled by default in debug builds.
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self::with_source(AssetSource::Directory(root.into()), cfg!(debug_assertions))
+    }
+
+    /// Returns a cache that loads assets from `files`, a collection of keys
+    /// and their contents.
+    ///
+    /// This is typically used with [`include_bytes!`] to embed assets in an
+    /// executable. Embedded assets are never hot reloaded. To embed an entire
+    /// directory, see [`include_assets!`](crate::include_assets).
+    ///
+    /// ```rust
+    /// use kludgine::assets::AssetCache;
+    ///
+    /// let files: [(&str, &[u8]); 2] = [("player.png", b"..."), ("player.json", b"...")];
+    /// let assets = AssetCache::embedded(files);
+    /// ```
+    #[must_use]
+    pub fn embedded(files: impl IntoIterator<Item = (&'static str, &'static [u8])>) -> Self {
+        Self::from_bundle(files.into_iter().collect())
+    }
+
+    /// Returns a cache that loads assets from the files in `bundle`.
+    ///
+    /// Keys are the paths of files relative to the directory the bundle was
+    /// packed from. Bundled assets are never hot reloaded.
+    #[must_use]
+    pub fn from_bundle(bundle: Bundle) -> Self {
+        Self::with_source(AssetSource::Bundle(bundle), false)
+    }
+
+    fn with_source(source: AssetSource, hot_reload: bool) -> Self {
+        Self {
+            data: Arc::new(CacheData {
+                source: Arc::new(source),
+                filter_mode: Mutex::new(wgpu::FilterMode::Linear),
+                assets: Mutex::default(),
+                jobs: Mutex::default(),
+                hot_reload: AtomicBool::new(hot_reload),
+                watching: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    /// Sets whether assets loaded from a directory are reloaded when their
+    /// files change.
+    pub fn set_hot_reload(&self, enabled: bool) {
+        self.data.hot_reload.store(enabled, Ordering::Relaxed);
+        self.watch_if_needed();
+    }
+
+    /// Returns true if assets loaded from a directory are reloaded when their
+    /// files change.
+    #[must_use]
+    pub fn hot_reload(&self) -> bool {
+        self.data.hot_reload.load(Ordering::Relaxed)
+    }
+
+    /// Sets the filter mode used for textures loaded after this call.
+    ///
+    /// The default filter mode is [`wgpu::FilterMode::Linear`]. Pixel art is
+    /// typically loaded using [`wgpu::FilterMode::Nearest`].
+    pub fn set_filter_mode(&self, filter_mode: wgpu::FilterMode) {
+        *self
+            .data
+            .filter_mode
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = filter_mode;
+    }
+
+    /// Returns the filter mode used for textures loaded by this cache.
+    #[must_use]
+    pub fn filter_mode(&self) -> wgpu::FilterMode {
+        *self
+            .data
+            .filter_mode
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the texture decoded from the image at `key`.
+    ///
+    /// Any image format enabled in the `image` crate can be loaded. Images
+    /// are assumed to contain sRGB colors with straight alpha.
+    #[must_use]
+    pub fn texture(&self, key: &str) -> Asset<LazyTexture> {
+        self.load(AssetKind::Texture, key, vec![key.to_string()], load_texture)
+    }
+
+    /// Returns the sprite loaded from an [Aseprite](https://www.aseprite.org/)
+    /// sprite sheet export.
+    ///
+    /// The sprite sheet's image is loaded from `{key}.png`, and its animations
+    /// are loaded from `{key}.json`. See
+    /// [`Sprite::load_aseprite_json()`] for the supported export settings.
+    #[must_use]
+    pub fn sprite(&self, key: &str) -> Asset<Sprite> {
+        self.load(
+            AssetKind::Sprite,
+            key,
+            vec![format!("{key}.json"), format!("{key}.png")],
+            load_sprite,
+        )
+    }
+
+    /// Returns the font data at `key`.
+    ///
+    /// Fonts must be registered with each [`Kludgine`] instance that renders
+    /// them using [`FontData::register()`].
+    #[cfg(feature = "cosmic-text")]
+    #[must_use]
+    pub fn font(&self, key: &str) -> Asset<FontData> {
+        self.load(AssetKind::Font, key, vec![key.to_string()], |files, _| {
+            Ok(FontData(Arc::new(files[0].to_vec())))
+        })
+    }
+
+    fn load<T>(&self, kind: AssetKind, key: &str, files: Vec<String>, load: Loader<T>) -> Asset<T>
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let mut assets = self
+            .data
+            .assets
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(entry) = assets.get(&(kind, key.to_string())) {
+            if let Ok(data) = entry.clone().into_any().downcast::<AssetData<T>>() {
+                return Asset { data };
+            }
+        }
+
+        let data = Arc::new(AssetData {
+            key: key.to_string(),
+            files,
+            load,
+            modified: Mutex::default(),
+            state: Mutex::new(AssetSlot {
+                value: None,
+                error: None,
+                loading: false,
+                generation: 0,
+            }),
+            changed: Condvar::new(),
+        });
+        let entry: Arc<dyn Entry> = data.clone();
+        assets.insert((kind, key.to_string()), entry.clone());
+        drop(assets);
+
+        self.data.queue(&entry);
+        self.watch_if_needed();
+        Asset { data }
+    }
+
+    fn watch_if_needed(&self) {
+        if self.hot_reload()
+            && matches!(&*self.data.source, AssetSource::Directory(_))
+            && !self.data.watching.swap(true, Ordering::Relaxed)
+        {
+            let cache = Arc::downgrade(&self.data);
+            thread::Builder::new()
+                .name(String::from("kludgine::assets watcher"))
+                .spawn(move || watch(&cache))
+                .expect("error spawning asset watcher thread");
+        }
+    }
+}
+
+impl Debug for AssetCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AssetCache")
+            .field("source", &self.data.source)
+            .field("hot_reload", &self.hot_reload())
+            .finish_non_exhaustive()
+    }
+}
+
+impl CacheData {
+    /// Queues `entry` to be loaded by a worker thread, starting the workers if
+    /// needed.
+    fn queue(&self, entry: &Arc<dyn Entry>) {
+        entry.start(&self.source);
+        let source = self.source.clone();
+        let filter_mode = *self
+            .filter_mode
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let entry = entry.clone();
+        let job: Job = Box::new(move || entry.load(&source, filter_mode));
+
+        let mut jobs = self.jobs.lock().unwrap_or_else(PoisonError::into_inner);
+        let jobs = jobs.get_or_insert_with(spawn_workers);
+        jobs.send(job).expect("asset workers are running");
+    }
+}
+
+fn spawn_workers() -> mpsc::Sender<Job> {
+    let (sender, receiver) = mpsc::channel::<Job>();
+    let receiver = Arc::new(Mutex::new(receiver));
+    let workers = thread::available_parallelism()
+        .map_or(1, usize::from)
+        .min(MAXIMUM_WORKERS);
+    for _ in 0..workers {
+        let receiver = receiver.clone();
+        thread::Builder::new()
+            .name(String::from("kludgine::assets loader"))
+            .spawn(move || loop {
+                let job = receiver
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .recv();
+                match job {
+                    Ok(job) => job(),
+                    Err(_) => break,
+                }
+            })
+            .expect("error spawning asset loader thread");
+    }
+    sender
+}
+
+fn watch(cache: &Weak<CacheData>) {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let Some(cache) = cache.upgrade() else {
+            break;
+        };
+        if !cache.hot_reload.load(Ordering::Relaxed) {
+            cache.watching.store(false, Ordering::Relaxed);
+            break;
+        }
+
+        let entries = cache
+            .assets
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        for entry in entries {
+            if entry.changed_on_disk(&cache.source) {
+                cache.queue(&entry);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+enum AssetKind {
+    Texture,
+    Sprite,
+    #[cfg(feature = "cosmic-text")]
+    Font,
+}
+
+enum AssetSource {
+    Directory(PathBuf),
+    Bundle(Bundle),
+}
+
+impl AssetSource {
+    fn read(&self, file: &str) -> Result<Cow<'_, [u8]>, AssetError> {
+        match self {
+            AssetSource::Directory(root) => match fs::read(root.join(file)) {
+                Ok(data) => Ok(Cow::Owned(data)),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                    Err(AssetError::NotFound(file.to_string()))
+                }
+                Err(err) => Err(AssetError::Io(Arc::new(err))),
+            },
+            AssetSource::Bundle(bundle) => bundle
+                .get(file)
+                .map(Cow::Borrowed)
+                .ok_or_else(|| AssetError::NotFound(file.to_string())),
+        }
+    }
+
+    fn modified(&self, file: &str) -> Option<SystemTime> {
+        match self {
+            AssetSource::Directory(root) => fs::metadata(root.join(file))
+                .and_then(|metadata| metadata.modified())
+                .ok(),
+            AssetSource::Bundle(_) => None,
+        }
+    }
+}
+
+impl Debug for AssetSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AssetSource::Directory(root) => f.debug_tuple("Directory").field(root).finish(),
+            AssetSource::Bundle(bundle) => f.debug_tuple("Bundle").field(bundle).finish(),
+        }
+    }
+}
+
+type Loader<T> = fn(&[Cow<'_, [u8]>], wgpu::FilterMode) -> Result<T, AssetError>;
+
+fn load_texture(
+    files: &[Cow<'_, [u8]>],
+    filter_mode: wgpu::FilterMode,
+) -> Result<LazyTexture, AssetError> {
+    let image = image::load_from_memory(&files[0])?;
+    Ok(LazyTexture::from_image(image, filter_mode))
+}
+
+fn load_sprite(
+    files: &[Cow<'_, [u8]>],
+    filter_mode: wgpu::FilterMode,
+) -> Result<Sprite, AssetError> {
+    let json = std::str::from_utf8(&files[0]).map_err(|_| AssetError::InvalidUtf8)?;
+    let texture = load_texture(&files[1..], filter_mode)?;
+    Ok(Sprite::load_aseprite_json(json, texture)?)
+}
+
+/// A type-erased [`AssetData`].
+trait Entry: Send + Sync {
+    fn into_any(self: Arc<Self>) -> Arc<dyn Any + Send + Sync>;
+    fn start(&self, source: &AssetSource);
+    fn load(&self, source: &AssetSource, filter_mode: wgpu::FilterMode);
+    fn changed_on_disk(&self, source: &AssetSource) -> bool;
+}
+
+struct AssetData<T> {
+    key: String,
+    files: Vec<String>,
+    load: Loader<T>,
+    /// The modification times of `files` when they were last loaded.
+    modified: Mutex<Vec<Option<SystemTime>>>,
+    state: Mutex<AssetSlot<T>>,
+    changed: Condvar,
+}
+
+struct AssetSlot<T> {
+    value: Option<T>,
+    error: Option<AssetError>,
+    loading: bool,
+    generation: u64,
+}
+
+impl<T> AssetData<T> {
+    fn state(&self) -> MutexGuard<'_, AssetSlot<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T> Entry for AssetData<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn into_any(self: Arc<Self>) -> Arc<dyn Any + Send + Sync> {
+        self
+    }
+
+    fn start(&self, source: &AssetSource) {
+        // The modification times are recorded before reading the files to
+        // ensure changes made while loading cause another reload.
+        *self.modified.lock().unwrap_or_else(PoisonError::into_inner) = self
+            .files
+            .iter()
+            .map(|file| source.modified(file))
+            .collect();
+        self.state().loading = true;
+    }
+
+    fn load(&self, source: &AssetSource, filter_mode: wgpu::FilterMode) {
+        let result = self
+            .files
+            .iter()
+            .map(|file| source.read(file))
+            .collect::<Result<Vec<_>, _>>()
+            .and_then(|files| (self.load)(&files, filter_mode));
+
+        let mut state = self.state();
+        match result {
+            Ok(value) => {
+                state.value = Some(value);
+                state.error = None;
+                state.generation += 1;
+            }
+            // When a reload fails, the previously loaded value is kept.
+            Err(err) => state.error = Some(err),
+        }
+        state.loading = false;
+        drop(state);
+        self.changed.notify_all();
+    }
+
+    fn changed_on_disk(&self, source: &AssetSource) -> bool {
+        let modified = self.modified.lock().unwrap_or_else(PoisonError::into_inner);
+        !self.state().loading
+            && self
+                .files
+                .iter()
+                .zip(modified.iter())
+                .any(|(file, modified)| source.modified(file) != *modified)
+    }
+}
+
+/// A handle to an asset loaded by an [`AssetCache`].
+///
+/// Cloning an asset is cheap and produces a handle to the same asset.
+pub struct Asset<T> {
+    data: Arc<AssetData<T>>,
+}
+
+impl<T> Asset<T>
+where
+    T: Clone,
+{
+    /// Returns the key this asset was loaded from.
+    #[must_use]
+    pub fn key(&self) -> &str {
+        &self.data.key
+    }
+
+    /// Returns the current state of this asset.
+    #[must_use]
+    pub fn state(&self) -> AssetState {
+        let state = self.data.state();
+        if state.loading {
+            AssetState::Loading
+        } else if state.error.is_some() {
+            AssetState::Failed
+        } else {
+            AssetState::Loaded
+        }
+    }
+
+    /// Returns the most recently loaded value of this asset, or `None` if it
+    /// has not been loaded yet.
+    ///
+    /// While an asset is being reloaded, or if reloading it fails, the
+    /// previously loaded value is returned.
+    #[must_use]
+    pub fn get(&self) -> Option<T> {
+        self.data.state().value.clone()
+    }
+
+    /// Returns the error from the most recent attempt to load this asset, if
+    /// it failed.
+    #[must_use]
+    pub fn error(&self) -> Option<AssetError> {
+        self.data.state().error.clone()
+    }
+
+    /// Returns the number of times this asset has been loaded successfully.
+    ///
+    /// This value increments each time the asset is reloaded, which allows
+    /// detecting when derived data needs to be updated.
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        self.data.state().generation
+    }
+
+    /// Blocks the current thread until this asset has finished loading,
+    /// returning the loaded value.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error that occurred while loading the asset.
+    pub fn wait(&self) -> Result<T, AssetError> {
+        let mut state = self.data.state();
+        while state.loading {
+            state = self
+                .data
+                .changed
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        match (&state.error, &state.value) {
+            (Some(err), _) => Err(err.clone()),
+            (None, Some(value)) => Ok(value.clone()),
+            (None, None) => unreachable!("loading finished without a result"),
+        }
+    }
+}
+
+impl<T> Clone for Asset<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<T> PartialEq for Asset<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.data, &other.data)
+    }
+}
+
+impl<T> Debug for Asset<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let state = self.data.state();
+        f.debug_struct("Asset")
+            .field("key", &self.data.key)
+            .field("value", &state.value)
+            .field("error", &state.error)
+            .field("loading", &state.loading)
+            .field("generation", &state.generation)
+            .finish()
+    }
+}
+
+/// The loading state of an [`Asset`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum AssetState {
+    /// The asset is being loaded or reloaded.
+    Loading,
+    /// The asset has been loaded.
+    Loaded,
+    /// The most recent attempt to load the asset failed.
+    Failed,
+}
+
+/// Font data loaded by an [`AssetCache`].
+#[cfg(feature = "cosmic-text")]
+#[derive(Debug, Clone)]
+pub struct FontData(Arc<Vec<u8>>);
+
+#[cfg(feature = "cosmic-text")]
+impl FontData {
+    /// Returns the raw font data.
+    #[must_use]
+    pub fn bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Loads this font into `kludgine`'s font system, returning the family of
+    /// the first face loaded.
+    ///
+    /// Returns `None` if the data does not contain any faces. When a font is
+    /// reloaded, the reloaded data must be registered again. Faces registered
+    /// from previous versions of the font remain loaded.
+    pub fn register(&self, kludgine: &mut Kludgine) -> Option<cosmic_text::FamilyOwned> {
+        let db = kludgine.font_system().db_mut();
+        let faces = db.load_font_source(cosmic_text::fontdb::Source::Binary(self.0.clone()));
+        let Some(family) = faces
+            .first()
+            .and_then(|id| db.face(*id))
+            .and_then(|face| face.families.first())
+            .map(|(name, _)| cosmic_text::FamilyOwned::Name(name.clone()))
+        else {
+            for id in faces {
+                db.remove_face(id);
+            }
+            return None;
+        };
+        kludgine.rebuild_font_system();
+        Some(family)
+    }
+}
+
+/// An error loading an asset.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum AssetError {
+    /// The file was not found.
+    NotFound(String),
+    /// An error occurred reading a file.
+    Io(Arc<io::Error>),
+    /// An error occurred decoding an image.
+    Image(Arc<image::ImageError>),
+    /// An error occurred loading a sprite.
+    Sprite(Arc<SpriteParseError>),
+    /// A text file was not valid UTF-8.
+    InvalidUtf8,
+}
+
+impl Display for AssetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AssetError::NotFound(file) => write!(f, "asset file not found: {file}"),
+            AssetError::Io(err) => write!(f, "error reading asset: {err}"),
+            AssetError::Image(err) => write!(f, "error decoding image: {err}"),
+            AssetError::Sprite(err) => write!(f, "error loading sprite: {err:?}"),
+            AssetError::InvalidUtf8 => f.write_str("asset is not valid UTF-8"),
+        }
+    }
+}
+
+impl std::error::Error for AssetError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AssetError::Io(err) => Some(&**err),
+            AssetError::Image(err) => Some(&**err),
+            _ => None,
+        }
+    }
+}
+
+impl From<image::ImageError> for AssetError {
+    fn from(err: image::ImageError) -> Self {
+        Self::Image(Arc::new(err))
+    }
+}
+
+impl From<SpriteParseError> for AssetError {
+    fn from(err: SpriteParseError) -> Self {
+        Self::Sprite(Arc::new(err))
+    }
+}
+
+#[test]
+fn embedded_assets() {
+    let mut png = Vec::new();
+    image::RgbaImage::new(2, 2)
+        .write_to(&mut io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .unwrap();
+    let png: &'static [u8] = png.leak();
+
+    use figures::{Size, UPx2D};
+
+    let assets = AssetCache::embedded([("pixel.png", png)]);
+    let texture = assets.texture("pixel.png");
+    assert_eq!(texture, assets.texture("pixel.png"));
+    assert_eq!(texture.wait().unwrap().size(), Size::upx(2, 2));
+    assert_eq!(texture.state(), AssetState::Loaded);
+    assert_eq!(texture.generation(), 1);
+
+    let missing = assets.texture("missing.png");
+    assert!(matches!(missing.wait(), Err(AssetError::NotFound(_))));
+    assert_eq!(missing.state(), AssetState::Failed);
+}
@@ -0,0 +1,319 @@
+use std::borrow::Cow;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+use std::{env, fs, io};
+
+use ahash::AHashMap;
+use intentional::{Assert, Cast};
+
+const MAGIC: &[u8; 8] = b"KLBUNDLE";
+const VERSION: u32 = 1;
+
+/// Includes a directory of assets packed into a [`Bundle`] by a build script.
+/// This macro returns a `Result<Bundle, BundleError>`.
+///
+/// The directory must be packed by invoking [`Bundle::prepare_include()`]
+/// with the same path from the crate's build script. The path is relative to
+/// the crate's manifest directory.
+///
+/// ```rust,ignore
+/// // build.rs
+/// fn main() {
+///     kludgine::assets::Bundle::prepare_include("assets").unwrap();
+/// }
+///
+/// // main.rs
+/// let assets = kludgine::assets::AssetCache::from_bundle(
+///     kludgine::include_assets!("assets").expect("valid bundle"),
+/// );
+/// let player = assets.sprite("characters/player");
+/// ```
+#[macro_export]
+macro_rules! include_assets {
+    ($dir:literal) => {
+        $crate::assets::Bundle::from_static(std::include_bytes!(concat!(
+            env!("OUT_DIR"),
+            "/",
+            $dir,
+            ".bundle"
+        )))
+    };
+}
+
+/// A collection of files packed into a single buffer.
+///
+/// Files are looked up by their path relative to the directory they were
+/// packed from, using `/` to separate path components. Bundles can be
+/// embedded in an executable using [`include_assets!`](crate::include_assets)
+/// and loaded using [`AssetCache::from_bundle()`](super::AssetCache::from_bundle).
+///
+/// Cloning a bundle is cheap and shares its data between clones.
+#[derive(Clone)]
+pub struct Bundle(Arc<BundleData>);
+
+struct BundleData {
+    data: Cow<'static, [u8]>,
+    files: AHashMap<String, Range<usize>>,
+}
+
+impl Bundle {
+    /// Returns the bundle contained in `data` without copying the contained
+    /// files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is not a valid bundle.
+    pub fn from_static(data: &'static [u8]) -> Result<Self, BundleError> {
+        Self::parse(Cow::Borrowed(data))
+    }
+
+    /// Returns the bundle contained in `data`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is not a valid bundle.
+    pub fn from_vec(data: Vec<u8>) -> Result<Self, BundleError> {
+        Self::parse(Cow::Owned(data))
+    }
+
+    fn parse(data: Cow<'static, [u8]>) -> Result<Self, BundleError> {
+        let mut reader = Reader {
+            data: &data,
+            offset: 0,
+        };
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(BundleError::InvalidHeader);
+        }
+        let version = reader.u32()?;
+        if version != VERSION {
+            return Err(BundleError::UnsupportedVersion(version));
+        }
+
+        let count = reader.u32()?;
+        let mut files = AHashMap::with_capacity(count.cast::<usize>().min(1024));
+        for _ in 0..count {
+            let path_len = reader.u32()?.cast::<usize>();
+            let path = std::str::from_utf8(reader.take(path_len)?)
+                .map_err(|_| BundleError::InvalidPath)?
+                .to_string();
+            let len = usize::try_from(reader.u64()?).map_err(|_| BundleError::Truncated)?;
+            let start = reader.offset;
+            reader.take(len)?;
+            files.insert(path, start..reader.offset);
+        }
+
+        Ok(Self(Arc::new(BundleData { data, files })))
+    }
+
+    /// Returns the contents of the file at `path`, if it exists.
+    #[must_use]
+    pub fn get(&self, path: &str) -> Option<&[u8]> {
+        self.0
+            .files
+            .get(path)
+            .map(|range| &self.0.data[range.clone()])
+    }
+
+    /// Returns an iterator over the paths of the files in this bundle.
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.0.files.keys().map(String::as_str)
+    }
+
+    /// Returns the number of files in this bundle.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.files.len()
+    }
+
+    /// Returns true if this bundle contains no files.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.files.is_empty()
+    }
+
+    /// Packs `files`, a collection of paths and their contents, into the
+    /// bundle format.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a path or file is larger than the bundle format supports.
+    #[must_use]
+    pub fn pack<Name, Data>(files: impl IntoIterator<Item = (Name, Data)>) -> Vec<u8>
+    where
+        Name: AsRef<str>,
+        Data: AsRef<[u8]>,
+    {
+        let files = files.into_iter().collect::<Vec<_>>();
+        let mut packed = Vec::new();
+        packed.extend_from_slice(MAGIC);
+        packed.extend_from_slice(&VERSION.to_le_bytes());
+        packed.extend_from_slice(
+            &u32::try_from(files.len())
+                .assert("too many files")
+                .to_le_bytes(),
+        );
+        for (path, data) in &files {
+            let (path, data) = (path.as_ref().as_bytes(), data.as_ref());
+            packed.extend_from_slice(
+                &u32::try_from(path.len())
+                    .assert("path too long")
+                    .to_le_bytes(),
+            );
+            packed.extend_from_slice(path);
+            packed.extend_from_slice(&data.len().cast::<u64>().to_le_bytes());
+            packed.extend_from_slice(data);
+        }
+        packed
+    }
+
+    /// Packs every file within `dir` into the bundle format.
+    ///
+    /// Files are stored using their paths relative to `dir`, sorted to ensure
+    /// the packed bundle is reproducible.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be read or a path is not valid
+    /// UTF-8.
+    pub fn pack_directory(dir: impl AsRef<Path>) -> io::Result<Vec<u8>> {
+        let mut files = Vec::new();
+        collect_files(dir.as_ref(), "", &mut files)?;
+        files.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        Ok(Self::pack(files))
+    }
+
+    /// Packs `dir` for use with [`include_assets!`](crate::include_assets).
+    ///
+    /// This function is meant to be invoked from a build script. `dir` is
+    /// resolved relative to the crate's manifest directory, and the bundle is
+    /// written to the build script's output directory. Cargo is instructed to
+    /// run the build script again when the directory's contents change.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this function is not invoked from a build script,
+    /// or if an error occurs reading `dir` or writing the bundle.
+    pub fn prepare_include(dir: &str) -> io::Result<()> {
+        let manifest_dir = env::var_os("CARGO_MANIFEST_DIR")
+            .ok_or_else(|| io::Error::other("CARGO_MANIFEST_DIR is not set"))?;
+        let out_dir =
+            env::var_os("OUT_DIR").ok_or_else(|| io::Error::other("OUT_DIR is not set"))?;
+
+        let source = Path::new(&manifest_dir).join(dir);
+        let bundle = Path::new(&out_dir).join(format!("{dir}.bundle"));
+        if let Some(parent) = bundle.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&bundle, Self::pack_directory(&source)?)?;
+        println!("cargo:rerun-if-changed={}", source.display());
+        Ok(())
+    }
+}
+
+impl<'a> FromIterator<(&'a str, &'a [u8])> for Bundle {
+    fn from_iter<T: IntoIterator<Item = (&'a str, &'a [u8])>>(iter: T) -> Self {
+        Self::from_vec(Self::pack(iter)).assert("packed bundle is valid")
+    }
+}
+
+impl Debug for Bundle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.paths()).finish()
+    }
+}
+
+fn collect_files(dir: &Path, prefix: &str, files: &mut Vec<(String, Vec<u8>)>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().into_string().map_err(|name| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("file name is not valid UTF-8: {name:?}"),
+            )
+        })?;
+        let path = format!("{prefix}{name}");
+        if entry.file_type()?.is_dir() {
+            collect_files(&entry.path(), &format!("{path}/"), files)?;
+        } else {
+            files.push((path, fs::read(entry.path())?));
+        }
+    }
+    Ok(())
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], BundleError> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or(BundleError::Truncated)?;
+        let bytes = &self.data[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, BundleError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes(bytes.try_into().assert("4 bytes")))
+    }
+
+    fn u64(&mut self) -> Result<u64, BundleError> {
+        let bytes = self.take(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().assert("8 bytes")))
+    }
+}
+
+/// An error reading a [`Bundle`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum BundleError {
+    /// The data does not begin with the bundle header.
+    InvalidHeader,
+    /// The bundle was packed using an unsupported version of the format.
+    UnsupportedVersion(u32),
+    /// The data ended before the end of the bundle.
+    Truncated,
+    /// A file's path is not valid UTF-8.
+    InvalidPath,
+}
+
+impl Display for BundleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BundleError::InvalidHeader => f.write_str("data is not an asset bundle"),
+            BundleError::UnsupportedVersion(version) => {
+                write!(f, "unsupported asset bundle version: {version}")
+            }
+            BundleError::Truncated => f.write_str("asset bundle is truncated"),
+            BundleError::InvalidPath => f.write_str("asset bundle path is not valid UTF-8"),
+        }
+    }
+}
+
+impl std::error::Error for BundleError {}
+
+#[test]
+fn bundle_round_trip() {
+    let packed = Bundle::pack([("a.txt", &b"hello"[..]), ("dir/b.bin", &[1, 2, 3][..])]);
+    let bundle = Bundle::from_vec(packed.clone()).unwrap();
+    assert_eq!(bundle.len(), 2);
+    assert_eq!(bundle.get("a.txt"), Some(&b"hello"[..]));
+    assert_eq!(bundle.get("dir/b.bin"), Some(&[1, 2, 3][..]));
+    assert_eq!(bundle.get("missing"), None);
+
+    assert_eq!(
+        Bundle::from_vec(packed[..packed.len() - 1].to_vec()).unwrap_err(),
+        BundleError::Truncated
+    );
+    assert_eq!(
+        Bundle::from_vec(b"not a bundle".to_vec()).unwrap_err(),
+        BundleError::InvalidHeader
+    );
+}
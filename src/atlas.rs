@@ -6,9 +6,14 @@ use alot::{LotId, Lots};
 use etagere::{Allocation, BucketedAtlasAllocator};
 use figures::units::UPx;
 use figures::{IntoSigned, IntoUnsigned, Point, Px2D, Rect, Size, UPx2D};
+use intentional::{Assert, Cast};
 
-use crate::pipeline::{PreparedGraphic, Vertex};
-use crate::{sealed, CanRenderTo, Graphics, Kludgine, KludgineGraphics, Texture, TextureSource};
+use crate::pipeline::{ArrayTexture, PreparedGraphic, Vertex};
+use crate::upload::{LoadProgress, PendingUpload, UploadTarget};
+use crate::{
+    sealed, CanRenderTo, Error, Graphics, Kludgine, KludgineGraphics, Texture, TextureLoadState,
+    TextureSource,
+};
 
 fn atlas_usages() -> wgpu::TextureUsages {
     wgpu::TextureUsages::TEXTURE_BINDING
@@ -19,9 +24,12 @@ fn atlas_usages() -> wgpu::TextureUsages {
 /// A collection of multiple textures, managed as a single texture on the GPU.
 /// This type is often called an atlas.
 ///
-/// The collection is currently fixed-size and will panic when an allocation
-/// fails. In the future, this type will dynamically grow as more textures are
-/// added to it.
+/// The collection grows as more textures are added to it, up to the maximum
+/// texture size supported by the graphics device. Collections created using
+/// [`new_with_options()`](Self::new_with_options) can be configured to limit
+/// how large their texture grows and to spread textures across multiple GPU
+/// textures, called pages, once a page is full. Each [`CollectedTexture`] is
+/// assigned to a page automatically.
 ///
 /// In general, this type should primarly be used with similarly-sized graphics,
 /// otherwise the packing may be inefficient. For example, packing many images
@@ -31,42 +39,244 @@ fn atlas_usages() -> wgpu::TextureUsages {
 pub struct TextureCollection {
     format: wgpu::TextureFormat,
     filter_mode: wgpu::FilterMode,
+    usage: wgpu::TextureUsages,
+    options: AtlasOptions,
     data: Arc<RwLock<Data>>,
 }
 
 struct Data {
+    pages: Vec<Page>,
+    textures: Lots<Entry>,
+    used_area: u64,
+    /// If true, each page can be rendered by multiple [`Kludgine`] instances.
+    shared: bool,
+}
+
+struct Page {
     rects: BucketedAtlasAllocator,
     texture: Texture,
-    textures: Lots<Allocation>,
+}
+
+struct Entry {
+    page: usize,
+    allocation: Allocation,
+    size: Size<UPx>,
+    progress: LoadProgress,
+}
+
+/// Options controlling how a [`TextureCollection`] allocates textures on the
+/// GPU.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct AtlasOptions {
+    /// The size of each page when it is created.
+    ///
+    /// Pages created to hold textures larger than this size are created large
+    /// enough to hold the texture.
+    pub initial_size: Size<UPx>,
+    /// How a page grows when it has no room for a texture.
+    pub growth: AtlasGrowth,
+    /// The largest size a page may grow to. Regardless of this value, pages
+    /// never grow larger than the maximum texture size supported by the
+    /// graphics device. Defaults to no limit.
+    pub maximum_page_size: Option<Size<UPx>>,
+    /// The maximum number of pages the collection may contain. Defaults to 1.
+    pub maximum_pages: usize,
+    /// The label given to each page's texture, which is shown in graphics
+    /// debugging tools.
+    pub label: Option<&'static str>,
+}
+
+impl AtlasOptions {
+    /// Returns options for a single page atlas of `initial_size` that doubles
+    /// in size as needed, up to the device's limits.
+    #[must_use]
+    pub const fn new(initial_size: Size<UPx>) -> Self {
+        Self {
+            initial_size,
+            growth: AtlasGrowth::Double,
+            maximum_page_size: None,
+            maximum_pages: 1,
+            label: None,
+        }
+    }
+
+    /// Sets how pages grow and returns self.
+    #[must_use]
+    pub const fn growth(mut self, growth: AtlasGrowth) -> Self {
+        self.growth = growth;
+        self
+    }
+
+    /// Sets the largest size a page may grow to and returns self.
+    #[must_use]
+    pub const fn maximum_page_size(mut self, size: Size<UPx>) -> Self {
+        self.maximum_page_size = Some(size);
+        self
+    }
+
+    /// Sets the maximum number of pages and returns self.
+    #[must_use]
+    pub const fn maximum_pages(mut self, pages: usize) -> Self {
+        self.maximum_pages = pages;
+        self
+    }
+
+    /// Sets the label given to each page's texture and returns self.
+    #[must_use]
+    pub const fn label(mut self, label: &'static str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Returns the largest size a page may be on `device`.
+    fn page_limit(&self, device: &wgpu::Device) -> Size<UPx> {
+        let maximum = UPx::new(device.limits().max_texture_dimension_2d);
+        let limit = self
+            .maximum_page_size
+            .unwrap_or_else(|| Size::squared(maximum));
+        Size::new(limit.width.min(maximum), limit.height.min(maximum))
+    }
+}
+
+/// How a page of a [`TextureCollection`] grows when it has no room for a
+/// texture.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum AtlasGrowth {
+    /// The page's width and height are doubled.
+    #[default]
+    Double,
+    /// The page's width and height are increased by a fixed amount.
+    Linear(UPx),
+    /// The page never grows. A new page is created instead.
+    Fixed,
+}
+
+impl AtlasGrowth {
+    fn grow(self, size: Size<UPx>, limit: Size<UPx>) -> Size<UPx> {
+        let grown = match self {
+            AtlasGrowth::Double => Size::upx(
+                size.width.get().saturating_mul(2),
+                size.height.get().saturating_mul(2),
+            ),
+            AtlasGrowth::Linear(step) => Size::upx(
+                size.width.get().saturating_add(step.get()),
+                size.height.get().saturating_add(step.get()),
+            ),
+            AtlasGrowth::Fixed => size,
+        };
+        Size::new(grown.width.min(limit.width), grown.height.min(limit.height))
+    }
+}
+
+impl Page {
+    fn new(
+        size: Size<UPx>,
+        format: wgpu::TextureFormat,
+        filter_mode: wgpu::FilterMode,
+        usage: wgpu::TextureUsages,
+        label: Option<&str>,
+        graphics: &impl KludgineGraphics,
+    ) -> Self {
+        let texture = Texture::new_generic(graphics, 1, size, format, usage, filter_mode, label);
+        let size = size.into_signed();
+        Self {
+            rects: BucketedAtlasAllocator::new(etagere::euclid::Size2D::new(
+                size.width.into(),
+                size.height.into(),
+            )),
+            texture,
+        }
+    }
+
+    /// Allocates `size` in this page, growing the page according to `growth`
+    /// as needed without exceeding `limit`.
+    fn allocate(
+        &mut self,
+        size: Size<UPx>,
+        growth: AtlasGrowth,
+        limit: Size<UPx>,
+        usage: wgpu::TextureUsages,
+        filter_mode: wgpu::FilterMode,
+        label: Option<&str>,
+        graphics: &impl KludgineGraphics,
+    ) -> Option<Allocation> {
+        let allocation_size = size.into_signed();
+        loop {
+            if let Some(allocation) = self.rects.allocate(etagere::euclid::Size2D::new(
+                allocation_size.width.get(),
+                allocation_size.height.get(),
+            )) {
+                return Some(allocation);
+            }
+
+            let current_size = self.texture.size;
+            let new_size = growth.grow(current_size, limit);
+            if new_size.width <= current_size.width && new_size.height <= current_size.height {
+                return None;
+            }
+            let new_texture = Texture::new_generic(
+                graphics,
+                1,
+                new_size,
+                self.texture.format,
+                usage,
+                filter_mode,
+                label,
+            );
+            let mut commands = graphics
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            commands.copy_texture_to_texture(
+                self.texture.data.wgpu.as_image_copy(),
+                new_texture.data.wgpu.as_image_copy(),
+                current_size.into(),
+            );
+            graphics.queue().submit([commands.finish()]);
+
+            self.rects.grow(etagere::euclid::Size2D::new(
+                new_size.width.into_signed().get(),
+                new_size.height.into_signed().get(),
+            ));
+            self.texture = new_texture;
+        }
+    }
 }
 
 impl TextureCollection {
     pub(crate) fn new_generic(
-        initial_size: Size<UPx>,
+        options: AtlasOptions,
         format: wgpu::TextureFormat,
         filter_mode: wgpu::FilterMode,
         graphics: &impl KludgineGraphics,
     ) -> Self {
-        let texture = Texture::new_generic(
-            graphics,
-            1,
-            initial_size,
+        Self::with_usage(options, format, filter_mode, atlas_usages(), graphics)
+    }
+
+    fn with_usage(
+        options: AtlasOptions,
+        format: wgpu::TextureFormat,
+        filter_mode: wgpu::FilterMode,
+        usage: wgpu::TextureUsages,
+        graphics: &impl KludgineGraphics,
+    ) -> Self {
+        let page = Page::new(
+            options.initial_size,
             format,
-            atlas_usages(),
             filter_mode,
+            usage,
+            options.label,
+            graphics,
         );
-
-        let initial_size = initial_size.into_signed();
         Self {
             format,
             filter_mode,
+            usage,
+            options,
             data: Arc::new(RwLock::new(Data {
-                rects: BucketedAtlasAllocator::new(etagere::euclid::Size2D::new(
-                    initial_size.width.into(),
-                    initial_size.height.into(),
-                )),
-                texture,
+                pages: vec![page],
                 textures: Lots::new(),
+                used_area: 0,
+                shared: false,
             })),
         }
     }
@@ -79,7 +289,183 @@ impl TextureCollection {
         filter_mode: wgpu::FilterMode,
         graphics: &Graphics<'_>,
     ) -> Self {
-        Self::new_generic(initial_size, format, filter_mode, graphics)
+        Self::new_generic(
+            AtlasOptions::new(initial_size),
+            format,
+            filter_mode,
+            graphics,
+        )
+    }
+
+    /// Returns a new atlas of the given format that allocates its textures
+    /// according to `options`.
+    #[must_use]
+    pub fn new_with_options(
+        options: AtlasOptions,
+        format: wgpu::TextureFormat,
+        filter_mode: wgpu::FilterMode,
+        graphics: &Graphics<'_>,
+    ) -> Self {
+        Self::with_usage(options, format, filter_mode, atlas_usages(), graphics)
+    }
+
+    /// Returns a new atlas of the given size and format whose regions can be
+    /// rendered into using
+    /// [`Frame::render_into_collected()`](crate::Frame::render_into_collected).
+    ///
+    /// `format` must be a format that supports being used as a render
+    /// attachment, such as [`Kludgine::texture_format()`].
+    #[must_use]
+    pub fn new_render_target(
+        initial_size: Size<UPx>,
+        format: wgpu::TextureFormat,
+        filter_mode: wgpu::FilterMode,
+        graphics: &Graphics<'_>,
+    ) -> Self {
+        Self::new_render_target_with_options(
+            AtlasOptions::new(initial_size),
+            format,
+            filter_mode,
+            graphics,
+        )
+    }
+
+    /// Returns a new atlas of the given format whose regions can be rendered
+    /// into, allocating its textures according to `options`.
+    ///
+    /// See [`new_render_target()`](Self::new_render_target) for more
+    /// information.
+    #[must_use]
+    pub fn new_render_target_with_options(
+        options: AtlasOptions,
+        format: wgpu::TextureFormat,
+        filter_mode: wgpu::FilterMode,
+        graphics: &Graphics<'_>,
+    ) -> Self {
+        Self::with_usage(
+            options,
+            format,
+            filter_mode,
+            atlas_usages() | wgpu::TextureUsages::RENDER_ATTACHMENT,
+            graphics,
+        )
+    }
+
+    /// Returns the options controlling how this collection allocates
+    /// textures.
+    #[must_use]
+    pub const fn options(&self) -> &AtlasOptions {
+        &self.options
+    }
+
+    /// Returns true if regions of this collection can be rendered into.
+    #[must_use]
+    pub fn is_render_target(&self) -> bool {
+        self.usage.contains(wgpu::TextureUsages::RENDER_ATTACHMENT)
+    }
+
+    /// Reserves a region of `size` without initializing its contents.
+    ///
+    /// This is intended for collections created with
+    /// [`new_render_target()`](Self::new_render_target), where the region is
+    /// filled using
+    /// [`Frame::render_into_collected()`](crate::Frame::render_into_collected).
+    ///
+    /// The returned [`CollectedTexture`] will automatically free the space it
+    /// occupies when the last instance is dropped.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the region cannot fit in this collection. See
+    /// [`try_allocate()`](Self::try_allocate) for a non-panicking version.
+    pub fn allocate(&mut self, size: Size<UPx>, graphics: &Graphics<'_>) -> CollectedTexture {
+        self.try_allocate(size, graphics)
+            .assert("region does not fit in collection")
+    }
+
+    /// Reserves a region of `size` without initializing its contents.
+    ///
+    /// See [`allocate()`](Self::allocate) for more information.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::TextureTooLarge`] if `size` exceeds the device limits.
+    /// - [`Error::AtlasFull`] if the collection has reached its maximum size
+    ///   and number of pages and has no room for `size`.
+    pub fn try_allocate(
+        &mut self,
+        size: Size<UPx>,
+        graphics: &Graphics<'_>,
+    ) -> Result<CollectedTexture, Error> {
+        let mut this = self.data.write().unwrap_or_else(PoisonError::into_inner);
+        let (id, page, region) = self.allocate_region(&mut this, size, graphics)?;
+        Ok(CollectedTexture {
+            collection: self.clone(),
+            id: Arc::new(id),
+            page,
+            region,
+        })
+    }
+
+    /// Reserves a region of `size` that is cleared to transparent and can be
+    /// rendered into.
+    ///
+    /// The region can be rendered into using
+    /// [`Frame::render_into_collected()`](crate::Frame::render_into_collected),
+    /// or used as the color attachment of a custom render pass using
+    /// [`CollectedTexture::with_render_attachment()`]. This allows
+    /// rasterizing content, such as pre-composited widgets, directly into the
+    /// collection on the GPU.
+    ///
+    /// The returned [`CollectedTexture`] will automatically free the space it
+    /// occupies when the last instance is dropped.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if this collection was not created using
+    /// [`new_render_target()`](Self::new_render_target) or if the region
+    /// cannot fit in this collection. See
+    /// [`try_push_render_target()`](Self::try_push_render_target) for a
+    /// non-panicking version.
+    pub fn push_render_target(
+        &mut self,
+        size: Size<UPx>,
+        graphics: &Graphics<'_>,
+    ) -> CollectedTexture {
+        self.try_push_render_target(size, graphics)
+            .assert("render target could not be added to collection")
+    }
+
+    /// Reserves a region of `size` that is cleared to transparent and can be
+    /// rendered into.
+    ///
+    /// See [`push_render_target()`](Self::push_render_target) for more
+    /// information.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::MissingUsage`] if this collection was not created using
+    ///   [`new_render_target()`](Self::new_render_target).
+    /// - [`Error::TextureTooLarge`] if `size` exceeds the device limits.
+    /// - [`Error::AtlasFull`] if the collection has reached its maximum size
+    ///   and number of pages and has no room for `size`.
+    pub fn try_push_render_target(
+        &mut self,
+        size: Size<UPx>,
+        graphics: &Graphics<'_>,
+    ) -> Result<CollectedTexture, Error> {
+        if !self.is_render_target() {
+            return Err(Error::MissingUsage(wgpu::TextureUsages::RENDER_ATTACHMENT));
+        }
+        let mut this = self.data.write().unwrap_or_else(PoisonError::into_inner);
+        let (id, page, region) = self.allocate_region(&mut this, size, graphics)?;
+        clear_region(&this.pages[page].texture, region, graphics.queue());
+        Ok(CollectedTexture {
+            collection: self.clone(),
+            id: Arc::new(id),
+            page,
+            region,
+        })
     }
 
     /// Pushes image data to a specific region of the texture.
@@ -89,6 +475,18 @@ impl TextureCollection {
     ///
     /// The returned [`CollectedTexture`] will automatically free the space it
     /// occupies when the last instance is dropped.
+    ///
+    /// When [asynchronous texture
+    /// uploads](crate::Kludgine::set_async_texture_uploads) are enabled, the
+    /// data is copied into the collection over the next frames. Until then,
+    /// [`CollectedTexture::load_state()`] returns
+    /// [`TextureLoadState::Loading`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the texture cannot fit in this collection. See
+    /// [`try_push_texture()`](Self::try_push_texture) for a non-panicking
+    /// version.
     pub fn push_texture(
         &mut self,
         data: &[u8],
@@ -96,6 +494,30 @@ impl TextureCollection {
         size: Size<UPx>,
         graphics: &Graphics<'_>,
     ) -> CollectedTexture {
+        self.try_push_texture(data, data_layout, size, graphics)
+            .assert("texture does not fit in collection")
+    }
+
+    /// Pushes image data to a specific region of the texture.
+    ///
+    /// The data format must match the format of the texture, and must be sized
+    /// exactly according to the `data_layout` and `size` and format.
+    ///
+    /// The returned [`CollectedTexture`] will automatically free the space it
+    /// occupies when the last instance is dropped.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::TextureTooLarge`] if `size` exceeds the device limits.
+    /// - [`Error::AtlasFull`] if the collection has reached its maximum size
+    ///   and number of pages and has no room for `size`.
+    pub fn try_push_texture(
+        &mut self,
+        data: &[u8],
+        data_layout: wgpu::ImageDataLayout,
+        size: Size<UPx>,
+        graphics: &Graphics<'_>,
+    ) -> Result<CollectedTexture, Error> {
         self.push_texture_generic(data, data_layout, size, graphics)
     }
 
@@ -105,64 +527,135 @@ impl TextureCollection {
         data_layout: wgpu::ImageDataLayout,
         size: Size<UPx>,
         graphics: &impl KludgineGraphics,
-    ) -> CollectedTexture {
+    ) -> Result<CollectedTexture, Error> {
         let mut this = self.data.write().unwrap_or_else(PoisonError::into_inner);
-        let allocation_size = size.into_signed();
-        let allocation = loop {
-            if let Some(allocation) = this.rects.allocate(etagere::euclid::Size2D::new(
-                allocation_size.width.get(),
-                allocation_size.height.get(),
-            )) {
-                break allocation;
-            }
+        let (id, page, region) = self.allocate_region(&mut this, size, graphics)?;
 
-            let new_size = this.texture.size * 2;
-            let new_texture = Texture::new_generic(
-                graphics,
-                1,
-                new_size,
-                self.format,
-                atlas_usages(),
-                self.filter_mode,
+        let uploads = graphics
+            .texture_uploads()
+            .filter(|uploads| uploads.accepts(self.format, size));
+        let progress = if uploads.is_some() {
+            let progress = LoadProgress::loading();
+            this.textures.get_mut(id).assert("just allocated").progress = progress.clone();
+            progress
+        } else {
+            graphics.queue().write_texture(
+                wgpu::ImageCopyTexture {
+                    texture: &this.pages[page].texture.data.wgpu,
+                    mip_level: 0,
+                    origin: region.origin.into(),
+                    aspect: wgpu::TextureAspect::All,
+                },
+                data,
+                data_layout,
+                size.into(),
             );
-            let mut commands = graphics
-                .device()
-                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
-            commands.copy_texture_to_texture(
-                this.texture.data.wgpu.as_image_copy(),
-                new_texture.data.wgpu.as_image_copy(),
-                this.texture.size.into(),
-            );
-            graphics.queue().submit([commands.finish()]);
+            LoadProgress::default()
+        };
+        // The collection must be unlocked before queueing the upload, as
+        // uploading locks the collection while the upload queue is locked.
+        drop(this);
 
-            this.rects.grow(etagere::euclid::Size2D::new(
-                new_size.width.into_signed().get(),
-                new_size.height.into_signed().get(),
-            ));
-            this.texture = new_texture;
+        let texture = CollectedTexture {
+            collection: self.clone(),
+            id: Arc::new(id),
+            page,
+            region,
         };
+        if let Some(uploads) = uploads {
+            uploads.push(PendingUpload {
+                target: UploadTarget::Collected(texture.clone()),
+                origin: region.origin,
+                size,
+                format: self.format,
+                data: Arc::new(data.to_vec()),
+                layout: data_layout,
+                progress,
+            });
+        }
+        Ok(texture)
+    }
+
+    /// Allocates a region of `size`, returning the id of its entry, the index
+    /// of the page it was allocated in, and the region within the page.
+    fn allocate_region(
+        &self,
+        this: &mut Data,
+        size: Size<UPx>,
+        graphics: &impl KludgineGraphics,
+    ) -> Result<(LotId, usize, Rect<UPx>), Error> {
+        Error::check_texture_size(size, graphics.device())?;
+        let limit = self.options.page_limit(graphics.device());
+        if size.width > limit.width || size.height > limit.height {
+            return Err(Error::AtlasFull { requested: size });
+        }
+
+        let mut allocated = None;
+        for (index, page) in this.pages.iter_mut().enumerate() {
+            if let Some(allocation) = page.allocate(
+                size,
+                self.options.growth,
+                limit,
+                self.usage,
+                self.filter_mode,
+                self.options.label,
+                graphics,
+            ) {
+                allocated = Some((index, allocation));
+                break;
+            }
+        }
+        let (page, allocation) = match allocated {
+            Some(allocated) => allocated,
+            None if this.pages.len() < self.options.maximum_pages => {
+                let initial_size = self.options.initial_size;
+                let mut page = Page::new(
+                    Size::new(
+                        initial_size.width.max(size.width).min(limit.width),
+                        initial_size.height.max(size.height).min(limit.height),
+                    ),
+                    self.format,
+                    self.filter_mode,
+                    self.usage,
+                    self.options.label,
+                    graphics,
+                );
+                let allocation = page
+                    .allocate(
+                        size,
+                        self.options.growth,
+                        limit,
+                        self.usage,
+                        self.filter_mode,
+                        self.options.label,
+                        graphics,
+                    )
+                    .ok_or(Error::AtlasFull { requested: size })?;
+                this.pages.push(page);
+                (this.pages.len() - 1, allocation)
+            }
+            None => return Err(Error::AtlasFull { requested: size }),
+        };
+        if this.shared {
+            // Allocating may have grown or added pages, replacing their
+            // textures.
+            for page in &mut this.pages {
+                page.texture.share_between_instances();
+            }
+        }
 
         let region = Rect::new(
             Point::px(allocation.rectangle.min.x, allocation.rectangle.min.y).into_unsigned(),
             size,
         );
-
-        graphics.queue().write_texture(
-            wgpu::ImageCopyTexture {
-                texture: &this.texture.data.wgpu,
-                mip_level: 0,
-                origin: region.origin.into(),
-                aspect: wgpu::TextureAspect::All,
-            },
-            data,
-            data_layout,
-            size.into(),
-        );
-        CollectedTexture {
-            collection: self.clone(),
-            id: Arc::new(this.textures.push(allocation)),
-            region,
-        }
+        this.used_area += area(size);
+        let id = this.textures.push(Entry {
+            page,
+            allocation,
+            size,
+            progress: LoadProgress::default(),
+        });
+        Ok((id, page, region))
     }
 
     /// Pushes an image to this collection.
@@ -172,17 +665,43 @@ impl TextureCollection {
     ///
     /// # Panics
     ///
-    /// Currently this only supports uploading to Rgba8 formatted textures.
+    /// This function panics if the image cannot be added to this collection.
+    /// See [`try_push_image()`](Self::try_push_image) for a non-panicking
+    /// version.
     #[cfg(feature = "image")]
     pub fn push_image(
         &mut self,
         image: &image::DynamicImage,
         graphics: &Graphics<'_>,
     ) -> CollectedTexture {
-        assert!(matches!(
+        self.try_push_image(image, graphics)
+            .assert("image could not be added to collection")
+    }
+
+    /// Pushes an image to this collection.
+    ///
+    /// The returned [`CollectedTexture`] will automatically free the space it
+    /// occupies when the last instance is dropped.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::UnsupportedFormat`] if this collection is not an Rgba8
+    ///   formatted texture. Currently only Rgba8 is supported.
+    /// - [`Error::TextureTooLarge`] if `image` exceeds the device limits.
+    /// - [`Error::AtlasFull`] if the collection has reached its maximum size
+    ///   and number of pages and has no room for `image`.
+    #[cfg(feature = "image")]
+    pub fn try_push_image(
+        &mut self,
+        image: &image::DynamicImage,
+        graphics: &Graphics<'_>,
+    ) -> Result<CollectedTexture, Error> {
+        if !matches!(
             self.format,
             wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb
-        ));
+        ) {
+            return Err(Error::UnsupportedFormat(self.format));
+        }
         // TODO this isn't correct for all texture formats, but there's limited
         // conversion format support for the image crate. We will have to create
         // our own conversion formats for other texture formats, or we could add
@@ -190,7 +709,7 @@ impl TextureCollection {
         // format, allowing this function to only be present on types that we
         // can convert to using the image crate.
         let image = image.to_rgba8();
-        self.push_texture(
+        self.try_push_texture(
             image.as_raw(),
             wgpu::ImageDataLayout {
                 offset: 0,
@@ -202,20 +721,68 @@ impl TextureCollection {
         )
     }
 
-    /// Returns the current size of the underlying texture.
+    /// Returns the current size of the underlying texture of the first page.
     pub fn size(&self) -> Size<UPx> {
         let data = self.data.read().unwrap_or_else(PoisonError::into_inner);
-        data.texture.size()
+        data.pages[0].texture.size()
+    }
+
+    /// Returns the number of pages in this collection.
+    #[must_use]
+    pub fn page_count(&self) -> usize {
+        let data = self.data.read().unwrap_or_else(PoisonError::into_inner);
+        data.pages.len()
+    }
+
+    /// Returns the current size of each page in this collection.
+    #[must_use]
+    pub fn page_sizes(&self) -> Vec<Size<UPx>> {
+        let data = self.data.read().unwrap_or_else(PoisonError::into_inner);
+        data.pages.iter().map(|page| page.texture.size()).collect()
+    }
+
+    /// Returns the number of texels occupied by the textures in this
+    /// collection.
+    ///
+    /// Comparing this value against [`total_area()`](Self::total_area) can be
+    /// used to monitor how full the collection is.
+    #[must_use]
+    pub fn used_area(&self) -> u64 {
+        let data = self.data.read().unwrap_or_else(PoisonError::into_inner);
+        data.used_area
+    }
+
+    /// Returns the number of texels in all of this collection's pages.
+    #[must_use]
+    pub fn total_area(&self) -> u64 {
+        let data = self.data.read().unwrap_or_else(PoisonError::into_inner);
+        data.pages
+            .iter()
+            .map(|page| area(page.texture.size()))
+            .sum()
+    }
+
+    /// Allows this collection's pages to be rendered by any [`Kludgine`]
+    /// instance that uses the same graphics device as the instance that
+    /// created the collection.
+    pub(crate) fn share_between_instances(&self) {
+        let mut data = self.data.write().unwrap_or_else(PoisonError::into_inner);
+        data.shared = true;
+        for page in &mut data.pages {
+            page.texture.share_between_instances();
+        }
     }
 
     fn free(&mut self, id: LotId) {
         let mut data = self.data.write().unwrap_or_else(PoisonError::into_inner);
-        let allocation = data.textures.remove(id).expect("invalid texture free");
-        data.rects.deallocate(allocation.id);
+        let entry = data.textures.remove(id).expect("invalid texture free");
+        data.used_area -= area(entry.size);
+        data.pages[entry.page].rects.deallocate(entry.allocation.id);
     }
 
     fn prepare<Unit>(
         &self,
+        page: usize,
         src: Rect<UPx>,
         dest: Rect<Unit>,
         graphics: &Graphics<'_>,
@@ -225,10 +792,13 @@ impl TextureCollection {
         Vertex<Unit>: bytemuck::Pod,
     {
         let data = self.data.read().unwrap_or_else(PoisonError::into_inner);
-        data.texture.prepare_partial(src, dest, graphics)
+        data.pages[page]
+            .texture
+            .prepare_partial(src, dest, graphics)
     }
 
-    /// Returns a [`PreparedGraphic`] for the entire texture.
+    /// Returns a [`PreparedGraphic`] for the entire texture of the first
+    /// page.
     ///
     /// This is primarily a debugging tool, as generally the
     /// [`CollectedTexture`]s are rendered instead.
@@ -242,7 +812,7 @@ impl TextureCollection {
         Vertex<Unit>: bytemuck::Pod,
     {
         let data = self.data.read().unwrap_or_else(PoisonError::into_inner);
-        data.texture.prepare(dest, graphics)
+        data.pages[0].texture.prepare(dest, graphics)
     }
 
     /// Returns the format of the texture backing this collection.
@@ -257,35 +827,73 @@ impl CanRenderTo for TextureCollection {
         self.data
             .read()
             .unwrap_or_else(PoisonError::into_inner)
+            .pages[0]
             .texture
             .can_render_to(kludgine)
     }
 }
 
+/// When used as a texture, a collection refers to its first page.
 impl TextureSource for TextureCollection {}
 
 impl sealed::TextureSource for TextureCollection {
     fn bind_group(&self, graphics: &impl sealed::KludgineGraphics) -> Arc<wgpu::BindGroup> {
         let data = self.data.read().unwrap_or_else(PoisonError::into_inner);
-        data.texture.bind_group(graphics)
+        data.pages[0].texture.bind_group(graphics)
+    }
+
+    fn array_texture(&self, graphics: &impl sealed::KludgineGraphics) -> Option<ArrayTexture> {
+        let data = self.data.read().unwrap_or_else(PoisonError::into_inner);
+        data.pages[0].texture.array_texture(graphics)
     }
 
     fn id(&self) -> sealed::TextureId {
         let data = self.data.read().unwrap_or_else(PoisonError::into_inner);
-        data.texture.id()
+        data.pages[0].texture.id()
     }
 
     fn is_mask(&self) -> bool {
         let data = self.data.read().unwrap_or_else(PoisonError::into_inner);
-        data.texture.is_mask()
+        data.pages[0].texture.is_mask()
     }
 
     fn default_rect(&self) -> Rect<UPx> {
         let data = self.data.read().unwrap_or_else(PoisonError::into_inner);
-        data.texture.default_rect()
+        data.pages[0].texture.default_rect()
     }
 }
 
+fn area(size: Size<UPx>) -> u64 {
+    u64::from(size.width.get()) * u64::from(size.height.get())
+}
+
+/// Clears `region` of `texture` to transparent.
+pub(crate) fn clear_region(texture: &Texture, region: Rect<UPx>, queue: &wgpu::Queue) {
+    let bytes_per_pixel = texture
+        .data
+        .wgpu
+        .format()
+        .block_copy_size(None)
+        .assert("collections use color formats");
+    let bytes_per_row = region.size.width.get() * bytes_per_pixel;
+    let zeroes = vec![0; (bytes_per_row * region.size.height.get()).cast::<usize>()];
+    queue.write_texture(
+        wgpu::ImageCopyTexture {
+            texture: &texture.data.wgpu,
+            mip_level: 0,
+            origin: region.origin.into(),
+            aspect: wgpu::TextureAspect::All,
+        },
+        &zeroes,
+        wgpu::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: Some(bytes_per_row),
+            rows_per_image: None,
+        },
+        region.size.into(),
+    );
+}
+
 impl PartialEq for TextureCollection {
     fn eq(&self, other: &Self) -> bool {
         Arc::ptr_eq(&self.data, &other.data)
@@ -297,6 +905,7 @@ impl PartialEq for TextureCollection {
 pub struct CollectedTexture {
     collection: TextureCollection,
     id: Arc<LotId>,
+    page: usize,
     pub(crate) region: Rect<UPx>,
 }
 
@@ -304,6 +913,7 @@ impl Debug for CollectedTexture {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("CollectedTexture")
             .field("id", &self.id)
+            .field("page", &self.page)
             .field("region", &self.region)
             .finish_non_exhaustive()
     }
@@ -316,7 +926,72 @@ impl CollectedTexture {
         Unit: figures::Unit + Div<i32, Output = Unit>,
         Vertex<Unit>: bytemuck::Pod,
     {
-        self.collection.prepare(self.region, dest, graphics)
+        self.collection
+            .prepare(self.page, self.region, dest, graphics)
+    }
+
+    /// Returns the index of the [`TextureCollection`] page containing this
+    /// texture.
+    #[must_use]
+    pub const fn page(&self) -> usize {
+        self.page
+    }
+
+    /// Returns the region of the page's texture that this texture occupies.
+    #[must_use]
+    pub const fn region(&self) -> Rect<UPx> {
+        self.region
+    }
+
+    /// Returns whether this texture's contents have finished uploading.
+    ///
+    /// Only textures pushed while [asynchronous texture
+    /// uploads](crate::Kludgine::set_async_texture_uploads) are enabled can be
+    /// [`TextureLoadState::Loading`].
+    #[must_use]
+    pub fn load_state(&self) -> TextureLoadState {
+        let data = self
+            .collection
+            .data
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        data.textures
+            .get(*self.id)
+            .map_or(TextureLoadState::Ready, |entry| entry.progress.state())
+    }
+
+    /// Invokes `render` with the view of the page's texture and the region
+    /// this texture occupies, returning the result.
+    ///
+    /// This allows using this texture as the color attachment of a custom
+    /// render pass. The view covers the entire page, so the pass must
+    /// use `render`'s region as its viewport and scissor rectangle to avoid
+    /// drawing over other textures in the collection. The collection must
+    /// not grow while the view is in use, as anything rendered into the
+    /// previous texture will be lost.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::MissingUsage`] if the collection was not created
+    /// using [`TextureCollection::new_render_target()`].
+    pub fn with_render_attachment<R>(
+        &self,
+        render: impl FnOnce(&wgpu::TextureView, Rect<UPx>) -> R,
+    ) -> Result<R, Error> {
+        if !self.collection.is_render_target() {
+            return Err(Error::MissingUsage(wgpu::TextureUsages::RENDER_ATTACHMENT));
+        }
+        Ok(self.with_collection_texture(|texture| render(&*texture.data.view, self.region)))
+    }
+
+    /// Invokes `with_texture` with the current texture of this texture's page.
+    pub(crate) fn with_collection_texture<R>(&self, with_texture: impl FnOnce(&Texture) -> R) -> R {
+        let data = self
+            .collection
+            .data
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        with_texture(&data.pages[self.page].texture)
     }
 }
 
@@ -338,11 +1013,15 @@ impl TextureSource for CollectedTexture {}
 
 impl sealed::TextureSource for CollectedTexture {
     fn bind_group(&self, graphics: &impl sealed::KludgineGraphics) -> Arc<wgpu::BindGroup> {
-        self.collection.bind_group(graphics)
+        self.with_collection_texture(|texture| texture.bind_group(graphics))
+    }
+
+    fn array_texture(&self, graphics: &impl sealed::KludgineGraphics) -> Option<ArrayTexture> {
+        self.with_collection_texture(|texture| texture.array_texture(graphics))
     }
 
     fn id(&self) -> sealed::TextureId {
-        self.collection.id()
+        self.with_collection_texture(sealed::TextureSource::id)
     }
 
     fn is_mask(&self) -> bool {
@@ -353,3 +1032,24 @@ impl sealed::TextureSource for CollectedTexture {
         self.region
     }
 }
+
+#[test]
+fn atlas_growth() {
+    let limit = Size::upx(300, 300);
+    assert_eq!(
+        AtlasGrowth::Double.grow(Size::upx(128, 128), limit),
+        Size::upx(256, 256)
+    );
+    assert_eq!(
+        AtlasGrowth::Double.grow(Size::upx(256, 128), limit),
+        Size::upx(300, 256)
+    );
+    assert_eq!(
+        AtlasGrowth::Linear(UPx::new(100)).grow(Size::upx(128, 250), limit),
+        Size::upx(228, 300)
+    );
+    assert_eq!(
+        AtlasGrowth::Fixed.grow(Size::upx(128, 128), limit),
+        Size::upx(128, 128)
+    );
+}
@@ -1,5 +1,5 @@
 use std::marker::PhantomData;
-use std::mem::size_of;
+use std::mem::{size_of, size_of_val};
 use std::ops::Deref;
 
 use wgpu::util::DeviceExt;
@@ -23,9 +23,14 @@ where
     T: bytemuck::Pod,
 {
     /// Returns a new buffer containing `contents`.
-    pub fn new(contents: &[T], usage: wgpu::BufferUsages, device: &wgpu::Device) -> Self {
+    pub fn new(
+        contents: &[T],
+        usage: wgpu::BufferUsages,
+        label: Option<&str>,
+        device: &wgpu::Device,
+    ) -> Self {
         let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: None,
+            label,
             contents: bytemuck::cast_slice(contents),
             usage,
         });
@@ -106,6 +111,7 @@ where
 pub struct DiffableBuffer<T> {
     buffer: Buffer<T>,
     usage: wgpu::BufferUsages,
+    label: Option<String>,
     data: Vec<T>,
 }
 
@@ -114,12 +120,18 @@ where
     T: bytemuck::Pod + Clone + Eq,
 {
     /// Returns a new buffer containing `contents`.
-    pub fn new(contents: &[T], usage: wgpu::BufferUsages, device: &wgpu::Device) -> Self {
+    pub fn new(
+        contents: &[T],
+        usage: wgpu::BufferUsages,
+        label: Option<String>,
+        device: &wgpu::Device,
+    ) -> Self {
         let usage = usage | wgpu::BufferUsages::COPY_DST;
-        let buffer = Buffer::new(contents, usage, device);
+        let buffer = Buffer::new(contents, usage, label.as_deref(), device);
         Self {
             buffer,
             usage,
+            label,
             data: contents.to_vec(),
         }
     }
@@ -128,8 +140,14 @@ where
     ///
     /// This function attempts to strike a balance between copying only data
     /// that has changed and minimizing the number of individual copy commands
-    /// issued to `queue`.
-    pub fn update(&mut self, new_contents: &[T], device: &wgpu::Device, queue: &wgpu::Queue) {
+    /// issued to `queue`. The number of bytes written is returned.
+    pub fn update(
+        &mut self,
+        new_contents: &[T],
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> usize {
+        let mut written = 0;
         if new_contents.len() <= self.buffer.len() {
             let mut index = 0;
             let mut cant_align = false;
@@ -190,6 +208,7 @@ where
                     // Update the changed range in the buffers.
                     let copy_range = &new_contents[start_index..=last_changed];
                     self.buffer.update(start_index, copy_range, queue);
+                    written += size_of_val(copy_range);
                     self.data[start_index..=last_changed].copy_from_slice(copy_range);
                 }
                 index += 1;
@@ -198,15 +217,16 @@ where
             // If we were able to do delta updates without alignment issues, we
             // can avoid creating the new buffer.
             if !cant_align {
-                return;
+                return written;
             }
         }
 
         // We need to grow to store the new data, or we had alignment issues
         // when trying to do a delta update.
-        self.buffer = Buffer::new(new_contents, self.usage, device);
+        self.buffer = Buffer::new(new_contents, self.usage, self.label.as_deref(), device);
         self.data.clear();
         self.data.extend_from_slice(new_contents);
+        written + size_of_val(new_contents)
     }
 }
 
@@ -0,0 +1,172 @@
+//! Conversions between [`figures`] types and the types used by [`wgpu`] and
+//! `winit`.
+//!
+//! [`figures`] implements [`From`] for most of these conversions, such as
+//! between `Size<UPx>` and [`wgpu::Extent3d`] or winit's `PhysicalSize<u32>`,
+//! and those implementations should be used directly. Texture copy operations
+//! also need [`wgpu::Origin3d`], which [`figures`] does not convert. Because
+//! both types are defined outside of Kludgine, Kludgine cannot implement
+//! [`From`] for them, so this module provides [`IntoForeign`] and
+//! [`FromForeign`] for points and rectangles instead.
+//!
+//! [`DisplayScale`] converts between physical pixels and logical units using
+//! the scale that Kludgine renders with.
+//!
+//! ```rust
+//! use kludgine::convert::{FromForeign, IntoForeign};
+//! use kludgine::figures::units::UPx;
+//! use kludgine::figures::{Point, Rect, Size};
+//!
+//! let region = Rect::<UPx>::new(Point::new(16, 32), Size::new(640, 480));
+//! let copy: (kludgine::wgpu::Origin3d, kludgine::wgpu::Extent3d) = region.into_foreign();
+//! assert_eq!(Rect::<UPx>::from_foreign(copy), region);
+//! ```
+
+#[cfg(feature = "app")]
+use appit::winit::dpi::PhysicalPosition;
+#[cfg(feature = "app")]
+use figures::units::Lp;
+use figures::units::{Px, UPx};
+use figures::{Fraction, IntoUnsigned, Point, Rect, ScreenScale, Size};
+
+/// Converts a [`figures`] type into a type from another crate.
+pub trait IntoForeign<T> {
+    /// Returns this value converted into `T`.
+    #[must_use]
+    fn into_foreign(self) -> T;
+}
+
+/// Converts a type from another crate into a [`figures`] type.
+pub trait FromForeign<T>: Sized {
+    /// Returns `value` converted into `Self`.
+    #[must_use]
+    fn from_foreign(value: T) -> Self;
+}
+
+impl IntoForeign<wgpu::Origin3d> for Point<UPx> {
+    fn into_foreign(self) -> wgpu::Origin3d {
+        wgpu::Origin3d {
+            x: self.x.get(),
+            y: self.y.get(),
+            z: 0,
+        }
+    }
+}
+
+/// Negative components are clamped to 0.
+impl IntoForeign<wgpu::Origin3d> for Point<Px> {
+    fn into_foreign(self) -> wgpu::Origin3d {
+        self.into_unsigned().into_foreign()
+    }
+}
+
+impl FromForeign<wgpu::Origin3d> for Point<UPx> {
+    fn from_foreign(value: wgpu::Origin3d) -> Self {
+        Point::new(value.x, value.y)
+    }
+}
+
+/// Converts into the origin and extent used by texture copy operations.
+impl IntoForeign<(wgpu::Origin3d, wgpu::Extent3d)> for Rect<UPx> {
+    fn into_foreign(self) -> (wgpu::Origin3d, wgpu::Extent3d) {
+        (self.origin.into_foreign(), self.size.into())
+    }
+}
+
+impl FromForeign<(wgpu::Origin3d, wgpu::Extent3d)> for Rect<UPx> {
+    fn from_foreign((origin, extent): (wgpu::Origin3d, wgpu::Extent3d)) -> Self {
+        Rect::new(
+            Point::from_foreign(origin),
+            Size::new(extent.width, extent.height),
+        )
+    }
+}
+
+/// A scale used to convert between physical pixels and logical units.
+///
+/// Use [`Kludgine::display_scale()`](crate::Kludgine::display_scale) to
+/// convert input event coordinates. It includes the scale override and zoom,
+/// matching the scale used when rendering, which ensures converted
+/// coordinates line up with drawn graphics.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DisplayScale(Fraction);
+
+impl DisplayScale {
+    /// Returns a new scale. A scale of 1 corresponds to 96 pixels per inch.
+    #[must_use]
+    pub fn new(scale: impl Into<Fraction>) -> Self {
+        Self(scale.into())
+    }
+
+    /// Returns a new scale from a floating point scale factor, such as the one
+    /// reported by winit.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn from_factor(factor: f64) -> Self {
+        Self(Fraction::from(factor as f32))
+    }
+
+    /// Returns the scale as a [`Fraction`].
+    #[must_use]
+    pub const fn get(self) -> Fraction {
+        self.0
+    }
+
+    /// Converts `physical` into logical units.
+    #[must_use]
+    pub fn to_logical<T>(self, physical: T) -> T::Lp
+    where
+        T: ScreenScale,
+    {
+        physical.into_lp(self.0)
+    }
+
+    /// Converts `logical` into physical pixels.
+    #[must_use]
+    pub fn to_physical<T>(self, logical: T) -> T::Px
+    where
+        T: ScreenScale,
+    {
+        logical.into_px(self.0)
+    }
+
+    /// Converts a winit event position into logical units.
+    #[cfg(feature = "app")]
+    #[must_use]
+    pub fn position_to_logical(self, position: PhysicalPosition<f64>) -> Point<Lp> {
+        self.to_logical(Point::<Px>::from(position))
+    }
+}
+
+impl From<Fraction> for DisplayScale {
+    fn from(scale: Fraction) -> Self {
+        Self(scale)
+    }
+}
+
+#[test]
+fn display_scale_round_trip() {
+    let scale = DisplayScale::new(2.);
+    let physical = Point::<Px>::new(192, -96);
+    let logical = scale.to_logical(physical);
+    assert_eq!(
+        logical,
+        DisplayScale::new(1.).to_logical(Point::<Px>::new(96, -48))
+    );
+    assert_eq!(scale.to_physical(logical), physical);
+}
+
+#[test]
+fn wgpu_round_trip() {
+    let rect = Rect::<UPx>::new(Point::new(3, 5), Size::new(7, 11));
+    let (origin, extent): (wgpu::Origin3d, wgpu::Extent3d) = rect.into_foreign();
+    assert_eq!((origin.x, origin.y, origin.z), (3, 5, 0));
+    assert_eq!(
+        (extent.width, extent.height, extent.depth_or_array_layers),
+        (7, 11, 1)
+    );
+    assert_eq!(Rect::from_foreign((origin, extent)), rect);
+
+    let clamped: wgpu::Origin3d = Point::<Px>::new(-4, 2).into_foreign();
+    assert_eq!((clamped.x, clamped.y), (0, 2));
+}
@@ -1,28 +1,40 @@
 use std::any::{type_name, Any, TypeId};
 use std::collections::{hash_map, HashMap};
 use std::fmt::Debug;
-use std::ops::{Deref, DerefMut, Range};
+use std::ops::{AddAssign, Deref, DerefMut, Range};
 use std::sync::Arc;
 
+use bytemuck::Zeroable;
 use figures::units::{Px, UPx};
-use figures::{Angle, IntoSigned, Point, Rect, ScreenScale, ScreenUnit, Size, UnscaledUnit, Zero};
-use intentional::CastInto;
+use figures::{
+    Angle, FloatConversion, IntoSigned, IntoUnsigned, PixelScaling, Point, Rect, ScreenScale,
+    ScreenUnit, Size, UnscaledUnit, Zero,
+};
+use intentional::{Cast, CastInto};
 
 use crate::buffer::DiffableBuffer;
 use crate::pipeline::{
-    PushConstants, ShaderScalable, Vertex, FLAG_MASKED, FLAG_ROTATE, FLAG_SCALE, FLAG_TEXTURED,
-    FLAG_TRANSLATE,
+    pivot_offset, transformed_bounds, vertex_bounds, ArrayTexture, PushConstants, RotatedClip,
+    ShaderScalable, TextureArrays, TexturePattern, Vertex, FLAG_CLIP_ROTATED, FLAG_DEPTH,
+    FLAG_MASKED, FLAG_ROTATE, FLAG_SCALE, FLAG_TEXTURED, FLAG_TRANSLATE, TEXTURE_ARRAY_SIZE,
+    TEXTURE_SLOT_LINEAR,
 };
-use crate::shapes::Shape;
+use crate::shapes::{FillOptions, Path, Shape, StrokeOptions};
 use crate::{
-    sealed, Assert, ClipGuard, ClipRect, Clipped, Color, DefaultHasher, Drawable, DrawableExt,
-    Graphics, RenderingGraphics, ShapeSource, Texture, TextureBlit, TextureSource,
-    VertexCollection,
+    sealed, Assert, ClipGuard, ClipStack, Clipped, Color, DefaultHasher, Drawable, DrawableExt,
+    Graphics, RenderingGraphics, ShapeSource, TexelSampling, Texture, TextureBlit, TextureFlip,
+    TextureSource, UnpairedPopClip, VertexCollection,
 };
 
+mod batch;
+mod group;
+mod overlay;
 #[cfg(feature = "plotters")]
 mod plotters;
 
+pub use self::batch::SpriteBatch;
+pub use self::group::OpacityGroup;
+pub use self::overlay::DebugOverlay;
 #[cfg(feature = "plotters")]
 pub use self::plotters::PlotterBackend;
 
@@ -38,7 +50,10 @@ pub struct Renderer<'render, 'gfx> {
     pub(crate) graphics: &'render mut Graphics<'gfx>,
     data: &'render mut Drawing,
     clip_index: u32,
+    layer: i32,
     opacity: f32,
+    batching: bool,
+    sort_on_drop: bool,
 }
 
 impl<'gfx> Deref for Renderer<'_, 'gfx> {
@@ -58,6 +73,7 @@ impl DerefMut for Renderer<'_, '_> {
 #[derive(Debug)]
 struct Command {
     clip_index: u32,
+    layer: i32,
     kind: CommandKind,
 }
 
@@ -66,9 +82,17 @@ enum CommandKind {
     BuiltIn {
         indices: Range<u32>,
         constants: PushConstants,
+        /// The texture sampled by this command. After sorting, this is the
+        /// first texture of commands that were merged because their textures
+        /// share a texture array.
         texture: Option<sealed::TextureId>,
+        lookup: Option<sealed::TextureId>,
+        /// The area covered by this command, including any rotation or scaling
+        /// applied by the shader.
+        bounds: Rect<Px>,
     },
     Custom(TypeId, usize),
+    Drawing(usize),
 }
 
 impl Renderer<'_, '_> {
@@ -82,6 +106,65 @@ impl Renderer<'_, '_> {
         self.inner_draw(&shape.into(), Option::<&Texture>::None);
     }
 
+    /// Fills `path` with `color` and draws it at the origin.
+    ///
+    /// The path is tessellated using the [scaled tessellation
+    /// tolerance](crate::Kludgine::scaled_tessellation_tolerance), keeping
+    /// curves smooth regardless of the display's scale. To avoid tessellating
+    /// the path each frame, use a [`CachedPath`](crate::shapes::CachedPath).
+    ///
+    /// If [shape anti-aliasing](crate::Kludgine::set_shape_anti_aliasing) is
+    /// enabled, the shape's edges are feathered.
+    pub fn fill_path<Unit>(&mut self, path: &Path<Unit, false>, color: Color)
+    where
+        Unit: Zero
+            + ShaderScalable
+            + ScreenUnit
+            + figures::Unit
+            + FloatConversion<Float = f32>
+            + PixelScaling
+            + Copy,
+    {
+        let options = FillOptions::tolerance(self.scaled_tessellation_tolerance::<Unit>());
+        let mut shape = path.fill_opt(color, &options);
+        if let Some(width) = self.anti_aliasing_width::<Unit>() {
+            shape.feather(width);
+        }
+        self.draw_shape(&shape);
+    }
+
+    /// Strokes `path` with `options` and draws it at the origin.
+    ///
+    /// The path is tessellated using the [scaled tessellation
+    /// tolerance](crate::Kludgine::scaled_tessellation_tolerance) in place of
+    /// [`StrokeOptions::tolerance`], keeping curves smooth regardless of the
+    /// display's scale.
+    ///
+    /// If [shape anti-aliasing](crate::Kludgine::set_shape_anti_aliasing) is
+    /// enabled, the shape's edges are feathered.
+    pub fn stroke_path<Unit>(
+        &mut self,
+        path: &Path<Unit, false>,
+        options: impl Into<StrokeOptions<Unit>>,
+    ) where
+        Unit: Zero
+            + ShaderScalable
+            + ScreenUnit
+            + figures::Unit
+            + FloatConversion<Float = f32>
+            + PixelScaling
+            + Copy,
+    {
+        let options = options
+            .into()
+            .tolerance(self.scaled_tessellation_tolerance::<Unit>());
+        let mut shape = path.stroke(options);
+        if let Some(width) = self.anti_aliasing_width::<Unit>() {
+            shape.feather(width);
+        }
+        self.draw_shape(&shape);
+    }
+
     /// Draws `texture` at `destination`, scaling as necessary.
     pub fn draw_texture<Unit>(
         &mut self,
@@ -93,7 +176,53 @@ impl Renderer<'_, '_> {
         i32: From<<Unit as IntoSigned>::Signed>,
     {
         self.draw_textured_shape(
-            TextureBlit::new(texture.default_rect(), destination, Color::WHITE).opacity(opacity),
+            TextureBlit::new(texture.uv_rect(), destination, Color::WHITE).opacity(opacity),
+            texture,
+        );
+    }
+
+    /// Draws `texture` at `destination`, scaling as necessary, and mirroring
+    /// it according to `flip`.
+    ///
+    /// This can be used to draw a left-facing sprite using right-facing art.
+    pub fn draw_texture_flipped<Unit>(
+        &mut self,
+        texture: &impl TextureSource,
+        destination: Rect<Unit>,
+        flip: TextureFlip,
+        opacity: f32,
+    ) where
+        Unit: figures::Unit + ScreenUnit + ShaderScalable,
+        i32: From<<Unit as IntoSigned>::Signed>,
+    {
+        self.draw_textured_shape(
+            TextureBlit::new(texture.uv_rect(), destination, Color::WHITE)
+                .flipped(flip)
+                .opacity(opacity),
+            texture,
+        );
+    }
+
+    /// Draws `texture` at `destination`, scaling as necessary. The texture
+    /// coordinates are computed using `sampling`, overriding any sampling mode
+    /// configured on `texture`.
+    pub fn draw_texture_sampled<Unit>(
+        &mut self,
+        texture: &impl TextureSource,
+        destination: Rect<Unit>,
+        sampling: TexelSampling,
+        opacity: f32,
+    ) where
+        Unit: figures::Unit + ScreenUnit + ShaderScalable,
+        i32: From<<Unit as IntoSigned>::Signed>,
+    {
+        self.draw_textured_shape(
+            TextureBlit::new(
+                sampling.uv_rect(texture.default_rect()),
+                destination,
+                Color::WHITE,
+            )
+            .opacity(opacity),
             texture,
         );
     }
@@ -112,7 +241,7 @@ impl Renderer<'_, '_> {
         let scaled_size = Size::<Unit>::from_upx(texture_rect.size, self.effective_scale);
         self.draw_textured_shape(
             TextureBlit::new(
-                texture_rect,
+                texture.uv_rect(),
                 Rect::new(destination, scaled_size),
                 Color::WHITE,
             )
@@ -143,14 +272,47 @@ impl Renderer<'_, '_> {
         Unit: Zero + ShaderScalable + ScreenUnit + figures::Unit + Copy,
         Shape: ShapeSource<Unit, TEXTURED>,
     {
-        // Merge the vertices into the graphics
         let vertices = shape.source.vertices();
+        let (min, max) = vertex_bounds(vertices).extents();
+        let (min, max) = (
+            min.into_px(self.graphics.scale()),
+            max.into_px(self.graphics.scale()),
+        );
+        let offset = self.clip.current.origin.into_signed()
+            + shape.translation.into_px(self.graphics.scale())
+            + pivot_offset(
+                shape.pivot,
+                min,
+                max,
+                shape.rotation,
+                shape.scale,
+                self.graphics.scale(),
+            );
+        // When batching, draws that are only translated are positioned using
+        // their vertices so that their push constants match other draws.
+        let batched = (self.batching || self.data.sort_draws)
+            && shape.rotation.is_none()
+            && shape.scale.is_none();
+        let (vertex_offset, vertex_opacity) = if batched {
+            (
+                shape.translation.map(|u| u.into_unscaled().cast_into()),
+                shape.opacity,
+            )
+        } else {
+            (Point::<i32>::ZERO, None)
+        };
+
+        // Merge the vertices into the graphics
         let mut vertex_map = Vec::with_capacity(vertices.len());
         for vertex in vertices {
             let vertex = Vertex {
-                location: vertex.location.map(|u| u.into_unscaled().cast_into()),
+                location: vertex.location.map(|u| u.into_unscaled().cast_into()) + vertex_offset,
                 texture: vertex.texture,
-                color: vertex.color,
+                color: vertex_opacity.map_or(vertex.color, |opacity| {
+                    vertex
+                        .color
+                        .with_alpha_f32(vertex.color.alpha_f32() * opacity)
+                }),
             };
             let index = self.data.vertices.get_or_insert(vertex);
             vertex_map.push(index);
@@ -163,7 +325,8 @@ impl Renderer<'_, '_> {
                 .push(vertex_map[usize::try_from(vertex_index).assert("too many drawn indices")]);
         }
 
-        let mut flags = Unit::flags();
+        let (mut flags, pattern) = TexturePattern::constants(shape.source.pattern());
+        flags |= Unit::flags();
         assert_eq!(TEXTURED, texture.is_some());
         let texture = if let Some(texture) = texture {
             flags |= FLAG_TEXTURED;
@@ -172,7 +335,7 @@ impl Renderer<'_, '_> {
             }
             let id = texture.id();
             if let hash_map::Entry::Vacant(entry) = self.data.textures.entry(id) {
-                entry.insert(texture.bind_group(self.graphics));
+                entry.insert(DrawingTexture::new(texture, self.graphics));
             }
             Some(id)
         } else {
@@ -186,34 +349,63 @@ impl Renderer<'_, '_> {
             flags |= FLAG_ROTATE;
             rotation.into_raidans_f()
         });
-        let translation = (self.clip.current.origin.into_signed()
-            + shape.translation.into_px(self.graphics.scale()))
-        .map(Px::into_unscaled);
+        let translation = if batched {
+            self.clip
+                .current
+                .origin
+                .into_signed()
+                .map(Px::into_unscaled)
+        } else {
+            offset.map(Px::into_unscaled)
+        };
         if !translation.is_zero() {
             flags |= FLAG_TRANSLATE;
         }
+        let z = shape.z.map_or(0., |z| {
+            flags |= FLAG_DEPTH;
+            z
+        });
+        let lookup = shape.lookup.as_ref().map(|lookup| {
+            flags |= lookup.flag();
+            self.data
+                .lookups
+                .entry(lookup.id)
+                .or_insert_with(|| lookup.bind_group.clone());
+            lookup.id
+        });
 
+        let bounds = transformed_bounds(min, max, shape.rotation, shape.scale, offset);
         let constants = PushConstants {
             flags,
             scale,
             rotation,
             opacity: shape
                 .opacity
+                .filter(|_| !batched)
                 .map_or(self.opacity, |opacity| opacity * self.opacity),
             translation,
+            z,
+            target_scale: Point::squared(1.),
+            pattern,
+            clip: RotatedClip::zeroed(),
         };
 
         match self.data.commands.last_mut() {
             Some(Command {
                 clip_index,
+                layer,
                 kind:
                     CommandKind::BuiltIn {
                         texture: last_texture,
                         indices,
                         constants: last_constants,
+                        lookup: last_lookup,
+                        bounds: last_bounds,
                     },
             }) if clip_index == &self.clip_index
+                && layer == &self.layer
                 && last_texture == &texture
+                && last_lookup == &lookup
                 && last_constants == &constants =>
             {
                 // Batch this draw operation with the previous one.
@@ -223,10 +415,12 @@ impl Renderer<'_, '_> {
                     .len()
                     .try_into()
                     .expect("too many drawn verticies");
+                *last_bounds = union_bounds(*last_bounds, bounds);
             }
             _ => {
                 self.data.commands.push(Command {
                     clip_index: self.clip_index,
+                    layer: self.layer,
                     kind: CommandKind::BuiltIn {
                         indices: first_index_drawn
                             .try_into()
@@ -239,6 +433,8 @@ impl Renderer<'_, '_> {
                                 .expect("too many drawn verticies"),
                         constants,
                         texture,
+                        lookup,
+                        bounds,
                     },
                 });
             }
@@ -264,10 +460,42 @@ impl Renderer<'_, '_> {
             .prepare_push(context, self.graphics);
         self.data.commands.push(Command {
             clip_index: self.clip_index,
+            layer: self.layer,
             kind: CommandKind::Custom(op_id, prepared),
         });
     }
 
+    /// Draws the graphics prepared in `drawing` with its origin at `origin`,
+    /// multiplying their opacity by `opacity`.
+    ///
+    /// `drawing` is not prepared again. Each time this drawing is rendered,
+    /// the graphics prepared during `drawing`'s last frame are rendered
+    /// offset by `origin` and limited to the current clip rect. This allows
+    /// parts of a scene that rarely change, such as individual widgets, to be
+    /// prepared once and cheaply assembled into a new drawing each frame.
+    ///
+    /// `drawing` is shared with this drawing until this drawing's next frame
+    /// begins. Once no other drawings share it, [`Arc::get_mut()`] can be used
+    /// to prepare new graphics in it. Hit regions registered in `drawing` are
+    /// not included in this drawing's hit regions.
+    pub fn draw_drawing<Unit>(&mut self, drawing: &Arc<Drawing>, origin: Point<Unit>, opacity: f32)
+    where
+        Unit: ScreenUnit,
+    {
+        let offset = self.clip.current.origin.into_signed() + origin.into_px(self.graphics.scale());
+        let index = self.data.children.len();
+        self.data.children.push(ChildDrawing {
+            drawing: drawing.clone(),
+            offset,
+            opacity: opacity * self.opacity,
+        });
+        self.data.commands.push(Command {
+            clip_index: self.clip_index,
+            layer: self.layer,
+            kind: CommandKind::Drawing(index),
+        });
+    }
+
     /// Returns the number of vertexes that compose the drawing commands.
     #[must_use]
     pub fn vertex_count(&self) -> usize {
@@ -288,6 +516,24 @@ impl Renderer<'_, '_> {
         self.data.commands.len()
     }
 
+    /// Sets the layer that subsequent drawing operations are placed on.
+    ///
+    /// Layers only have an effect when draw sorting is enabled using
+    /// [`Drawing::set_draw_sorting`]. When enabled, layers are drawn in
+    /// ascending order, and operations within the same layer that do not
+    /// overlap may be reordered to reduce the number of draw calls.
+    ///
+    /// The default layer is 0.
+    pub fn set_layer(&mut self, layer: i32) {
+        self.layer = layer;
+    }
+
+    /// Returns the layer that drawing operations are currently placed on.
+    #[must_use]
+    pub const fn layer(&self) -> i32 {
+        self.layer
+    }
+
     /// Returns a [`ClipGuard`] that causes all drawing operations to be offset
     /// and clipped to `clip` until it is dropped.
     ///
@@ -300,21 +546,76 @@ impl Renderer<'_, '_> {
     /// current clipping rectangle.
     pub fn clipped_to(&mut self, clip: Rect<UPx>) -> ClipGuard<'_, Self> {
         self.push_clip(clip);
-        self.clip_index = self.data.get_or_lookup_clip(self.clip.current);
+        ClipGuard { clipped: self }
+    }
 
+    /// Returns a [`ClipGuard`] that causes all drawing operations to be offset
+    /// and clipped to `clip` rotated by `angle` around its center until it is
+    /// dropped.
+    ///
+    /// See [`Clipped::push_rotated_clip()`] for more information.
+    pub fn rotated_clipped_to(&mut self, clip: Rect<UPx>, angle: Angle) -> ClipGuard<'_, Self> {
+        self.push_rotated_clip(clip, angle);
         ClipGuard { clipped: self }
     }
+
+    /// Registers `region` as an interactive area identified by `id`.
+    ///
+    /// `region` is relative to the current clip rect, in the same coordinates
+    /// used for drawing. The area that can be hit is limited to the current
+    /// clip rect. Translations, rotations, and scales applied to individual
+    /// drawables are not considered.
+    ///
+    /// Regions are only recorded if hit testing has been enabled with
+    /// [`Drawing::set_hit_testing`]. Once the frame has been prepared, use
+    /// [`Drawing::hit_test`] to find the region beneath a point.
+    pub fn register_hit_region<Unit>(&mut self, id: u64, region: Rect<Unit>)
+    where
+        Unit: ScreenUnit,
+    {
+        if !self.data.hit_testing {
+            return;
+        }
+        let scale = self.graphics.scale();
+        let clip = Rect::new(
+            self.clip.current.origin.into_signed(),
+            self.clip.current.size.into_signed(),
+        );
+        let size = Point::new(region.size.width, region.size.height).into_px(scale);
+        self.data.hit_regions.push(HitRegion {
+            id,
+            rect: Rect::new(
+                clip.origin + region.origin.into_px(scale),
+                Size::new(size.x, size.y),
+            ),
+            clip,
+        });
+    }
 }
 
 impl Clipped for Renderer<'_, '_> {
     fn push_clip(&mut self, clip: Rect<UPx>) {
         self.clip.push_clip(clip);
-        self.clip_index = self.data.get_or_lookup_clip(self.clip.current);
+        self.clip_index = self.data.get_or_lookup_clip(&self.graphics.clip);
+    }
+
+    fn push_rotated_clip(&mut self, clip: Rect<UPx>, angle: Angle) {
+        self.clip.push_rotated_clip(clip, angle);
+        self.clip_index = self.data.get_or_lookup_clip(&self.graphics.clip);
+    }
+
+    fn try_pop_clip(&mut self) -> Result<(), UnpairedPopClip> {
+        self.graphics.try_pop_clip()?;
+        self.clip_index = self.data.get_or_lookup_clip(&self.graphics.clip);
+        Ok(())
     }
 
-    fn pop_clip(&mut self) {
-        self.graphics.pop_clip();
-        self.clip_index = self.data.get_or_lookup_clip(self.clip.current);
+    fn clip_depth(&self) -> usize {
+        self.graphics.clip_depth()
+    }
+
+    fn clip_stack(&self) -> Vec<Rect<UPx>> {
+        self.graphics.clip_stack()
     }
 }
 
@@ -324,28 +625,28 @@ impl sealed::Clipped for Renderer<'_, '_> {}
 mod text {
     use std::array;
     use std::collections::{hash_map, HashMap};
-    use std::sync::Arc;
 
     use figures::units::{Px, UPx};
-    use figures::{Round, ScreenScale, ScreenUnit, UnscaledUnit};
+    use figures::{Fraction, Round, ScreenScale, ScreenUnit, UnscaledUnit};
     use intentional::Assert;
 
     use super::{
-        Angle, Color, Command, CommandKind, IntoSigned, Point, PushConstants, Renderer, Vertex,
-        Zero, FLAG_MASKED, FLAG_ROTATE, FLAG_SCALE, FLAG_TEXTURED, FLAG_TRANSLATE,
+        pivot_offset, Angle, Color, Command, CommandKind, DrawingTexture, IntoSigned, Point,
+        PushConstants, Renderer, Vertex, Zero, FLAG_DEPTH, FLAG_MASKED, FLAG_ROTATE, FLAG_SCALE,
+        FLAG_TEXTURED, FLAG_TRANSLATE,
     };
     use crate::sealed::{ShaderScalableSealed, ShapeSource, TextureId, TextureSource};
     use crate::text::{
-        map_each_glyph, measure_text, CachedGlyphHandle, GlyphBlit, MeasuredText, Text, TextOrigin,
-    };
-    use crate::{
-        DefaultHasher, Drawable, KludgineGraphics, ProtoGraphics, TextureBlit, VertexCollection,
+        cache_glyph, group_glyphs_by_atlas, map_each_glyph, measure_text, CachedGlyphHandle,
+        GlyphBlit, MeasuredText, Text, TextOrigin,
     };
+    use crate::{DefaultHasher, Drawable, KludgineGraphics, Origin, TextureBlit, VertexCollection};
 
     impl Renderer<'_, '_> {
         /// Measures `text` using the current text settings.
         ///
-        /// `default_color` does not affect the
+        /// The measured text keeps the shaped text, which allows it to be
+        /// [rewrapped](MeasuredText::rewrap) without shaping it again.
         pub fn measure_text<'a, Unit>(
             &mut self,
             text: impl Into<Text<'a, Unit>>,
@@ -353,21 +654,7 @@ mod text {
         where
             Unit: figures::ScreenUnit,
         {
-            let text = text.into();
-            let scale = self.graphics.effective_scale;
-            self.update_scratch_buffer(
-                text.text,
-                text.wrap_at.map(|width| width.into_px(scale)),
-                text.align,
-            );
-            measure_text::<Unit, true>(
-                None,
-                text.color,
-                self.graphics.kludgine,
-                self.graphics.device,
-                self.graphics.queue,
-                &mut self.data.glyphs,
-            )
+            self.graphics.kludgine.measure_text(text)
         }
 
         /// Draws `text` using the current text settings.
@@ -377,13 +664,8 @@ mod text {
             Source: Into<Drawable<Text<'a, Unit>, Unit>>,
         {
             let text = text.into();
-            self.graphics.kludgine.update_scratch_buffer(
-                text.source.text,
-                text.source
-                    .wrap_at
-                    .map(|width| width.into_px(self.graphics.effective_scale)),
-                text.source.align,
-            );
+            self.data.text_runs += 1;
+            self.graphics.kludgine.update_scratch_buffer(&text.source);
             self.draw_text_buffer_inner(
                 None,
                 text.source.color,
@@ -391,7 +673,9 @@ mod text {
                 text.translation,
                 text.rotation,
                 text.scale,
+                text.pivot,
                 text.opacity,
+                text.z,
             );
         }
 
@@ -411,6 +695,7 @@ mod text {
             Unit: ScreenUnit,
         {
             let buffer = buffer.into();
+            self.data.text_runs += 1;
             self.draw_text_buffer_inner(
                 Some(buffer.source),
                 default_color,
@@ -418,7 +703,9 @@ mod text {
                 buffer.translation,
                 buffer.rotation,
                 buffer.scale,
+                buffer.pivot,
                 buffer.opacity,
+                buffer.z,
             );
         }
 
@@ -432,13 +719,12 @@ mod text {
         where
             Unit: figures::ScreenUnit,
         {
+            let scale = self.effective_scale;
             measure_text::<Unit, true>(
                 Some(buffer),
                 default_color,
-                self.graphics.kludgine,
-                self.graphics.device,
-                self.graphics.queue,
-                &mut self.data.glyphs,
+                &mut self.graphics.kludgine.text,
+                scale,
             )
         }
 
@@ -457,6 +743,7 @@ mod text {
             Unit: ScreenUnit + Round,
         {
             let text = text.into();
+            self.data.text_runs += 1;
             let translation = text.translation.into_px(self.effective_scale);
             let origin = match origin {
                 TextOrigin::TopLeft => Point::default(),
@@ -470,25 +757,69 @@ mod text {
                 TextOrigin::Custom(offset) => offset.into_px(self.effective_scale),
             }
             .round();
+            // Measured glyphs are uploaded the first time they are drawn.
             for glyph in &text.source.glyphs {
-                let GlyphBlit::Visible {
-                    blit,
-                    glyph: cached,
-                } = &glyph.blit
-                else {
-                    continue;
+                if let GlyphBlit::Pending { cache_key, .. } = &glyph.blit {
+                    if let hash_map::Entry::Vacant(vacant) = self.data.glyphs.entry(*cache_key) {
+                        if let Some(cached) = cache_glyph(
+                            self.graphics.kludgine,
+                            self.graphics.device,
+                            self.graphics.queue,
+                            *cache_key,
+                        ) {
+                            vacant.insert(cached);
+                        }
+                    }
+                }
+            }
+
+            let mut glyphs = Vec::with_capacity(text.source.glyphs.len());
+            for glyph in &text.source.glyphs {
+                let (mut blit, cached) = match &glyph.blit {
+                    GlyphBlit::Invisible { .. } => continue,
+                    GlyphBlit::Visible {
+                        blit,
+                        glyph: cached,
+                    } => (*blit, cached),
+                    GlyphBlit::Pending {
+                        rect,
+                        cache_key,
+                        color,
+                    } => {
+                        let Some(cached) = self.data.glyphs.get(cache_key) else {
+                            continue;
+                        };
+                        (
+                            TextureBlit::new(cached.texture.region, *rect, *color),
+                            cached,
+                        )
+                    }
                 };
-                let mut blit = *blit;
                 blit.translate_by(-origin);
+                glyphs.push((blit, cached));
+            }
+            group_glyphs_by_atlas(&mut glyphs);
+            let translation = translation
+                + glyph_pivot_offset(
+                    text.pivot,
+                    &glyphs,
+                    text.rotation,
+                    text.scale,
+                    self.effective_scale,
+                );
+
+            for (blit, cached) in glyphs {
                 render_one_glyph(
                     translation,
                     text.rotation,
                     text.scale,
                     text.opacity,
+                    text.z,
                     blit,
                     cached,
                     self.clip_index,
-                    self.clip.current.origin,
+                    self.layer,
+                    self.graphics.clip.current.origin,
                     self.graphics,
                     &mut self.data.vertices,
                     &mut self.data.indices,
@@ -507,11 +838,14 @@ mod text {
             translation: Point<Unit>,
             rotation: Option<Angle>,
             scale: Option<Point<f32>>,
+            pivot: Option<Origin<Unit>>,
             opacity: Option<f32>,
+            z: Option<f32>,
         ) where
             Unit: ScreenUnit,
         {
             let translation = translation.into_px(self.effective_scale);
+            let mut glyphs = Vec::new();
             map_each_glyph(
                 buffer,
                 default_color,
@@ -520,56 +854,99 @@ mod text {
                 self.graphics.device,
                 self.graphics.queue,
                 &mut self.data.glyphs,
-                |blit, _glyph, _is_first_line, _baseline, _line_w, kludgine| {
+                |blit, _glyph, _is_first_line, _baseline, _line_w, _kludgine| {
                     if let GlyphBlit::Visible {
                         blit,
                         glyph: cached,
                     } = blit
                     {
-                        render_one_glyph(
-                            translation,
-                            rotation,
-                            scale,
-                            opacity,
-                            blit,
-                            &cached,
-                            self.clip_index,
-                            self.graphics.clip.current.origin,
-                            &ProtoGraphics::new(
-                                self.graphics.device,
-                                self.graphics.queue,
-                                kludgine,
-                            ),
-                            &mut self.data.vertices,
-                            &mut self.data.indices,
-                            &mut self.data.textures,
-                            &mut self.data.commands,
-                        );
+                        glyphs.push((blit, cached));
                     }
                 },
             );
+            group_glyphs_by_atlas(&mut glyphs);
+            let translation = translation
+                + glyph_pivot_offset(pivot, &glyphs, rotation, scale, self.effective_scale);
+
+            for (blit, cached) in &glyphs {
+                render_one_glyph(
+                    translation,
+                    rotation,
+                    scale,
+                    opacity,
+                    z,
+                    *blit,
+                    cached,
+                    self.clip_index,
+                    self.layer,
+                    self.graphics.clip.current.origin,
+                    self.graphics,
+                    &mut self.data.vertices,
+                    &mut self.data.indices,
+                    &mut self.data.textures,
+                    &mut self.data.commands,
+                );
+            }
         }
     }
 
+    /// Returns the offset that causes `glyphs` to be rotated and scaled around
+    /// `pivot`, using the bounds of the glyphs as the bounds of the text.
+    fn glyph_pivot_offset<Unit, Glyph>(
+        pivot: Option<Origin<Unit>>,
+        glyphs: &[(TextureBlit<Px>, Glyph)],
+        rotation: Option<Angle>,
+        scale: Option<Point<f32>>,
+        effective_scale: Fraction,
+    ) -> Point<Px>
+    where
+        Unit: ScreenUnit,
+    {
+        let mut corners = glyphs
+            .iter()
+            .flat_map(|(blit, _)| [blit.top_left().location, blit.bottom_right().location]);
+        let Some(first) = corners.next() else {
+            return Point::ZERO;
+        };
+        let (min, max) = corners.fold((first, first), |(min, max), corner| {
+            (
+                Point::new(min.x.min(corner.x), min.y.min(corner.y)),
+                Point::new(max.x.max(corner.x), max.y.max(corner.y)),
+            )
+        });
+        pivot_offset(pivot, min, max, rotation, scale, effective_scale)
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn render_one_glyph(
         translation: Point<Px>,
         rotation: Option<Angle>,
         scale: Option<Point<f32>>,
         opacity: Option<f32>,
+        z: Option<f32>,
         blit: TextureBlit<Px>,
         cached: &CachedGlyphHandle,
         clip_index: u32,
+        layer: i32,
         clip_origin: Point<UPx>,
         graphics: &impl KludgineGraphics,
         vertices: &mut VertexCollection<i32>,
         indices: &mut Vec<u32>,
-        textures: &mut HashMap<TextureId, Arc<wgpu::BindGroup>, DefaultHasher>,
+        textures: &mut HashMap<TextureId, DrawingTexture, DefaultHasher>,
         commands: &mut Vec<Command>,
     ) {
-        let translation = (clip_origin.into_signed() + translation)
-            .round()
-            .map(Px::into_unscaled);
+        let translation = (clip_origin.into_signed() + translation).round();
+        let (min, max) = blit.verticies.iter().skip(1).fold(
+            (blit.verticies[0].location, blit.verticies[0].location),
+            |(min, max), vertex| {
+                (
+                    Point::new(min.x.min(vertex.location.x), min.y.min(vertex.location.y)),
+                    Point::new(max.x.max(vertex.location.x), max.y.max(vertex.location.y)),
+                )
+            },
+        );
+        let bounds = transformed_bounds(min, max, rotation, scale, translation);
+        let translation = translation.map(Px::into_unscaled);
         let corners: [u32; 4] = array::from_fn(|index| {
             let vertex = &blit.verticies[index];
             vertices.get_or_insert(Vertex {
@@ -584,7 +961,7 @@ mod text {
         }
         let mut flags = Px::flags() | FLAG_TEXTURED;
         if let hash_map::Entry::Vacant(vacant) = textures.entry(cached.texture.id()) {
-            vacant.insert(cached.texture.bind_group(graphics));
+            vacant.insert(DrawingTexture::new(&cached.texture, graphics));
         }
 
         if cached.is_mask {
@@ -601,6 +978,10 @@ mod text {
         if !translation.is_zero() {
             flags |= FLAG_TRANSLATE;
         }
+        let z = z.map_or(0., |z| {
+            flags |= FLAG_DEPTH;
+            z
+        });
 
         let constants = PushConstants {
             flags,
@@ -608,31 +989,43 @@ mod text {
             rotation,
             translation,
             opacity: opacity.unwrap_or(1.),
+            z,
+            target_scale: Point::squared(1.),
+            pattern: [0.; 4],
+            clip: RotatedClip::zeroed(),
         };
         let end_index = u32::try_from(indices.len()).expect("too many drawn indices");
         match commands.last_mut() {
             Some(Command {
                 clip_index: command_clip,
+                layer: command_layer,
                 kind:
                     CommandKind::BuiltIn {
                         texture,
                         constants: command_constants,
                         indices,
+                        lookup: None,
+                        bounds: command_bounds,
                     },
             }) if clip_index == *command_clip
+                && layer == *command_layer
                 && *texture == Some(cached.texture.id())
                 && constants == *command_constants =>
             {
                 // The last command was from the same texture source, we can stend the previous range to the new end.
                 indices.end = end_index;
+                *command_bounds = union_bounds(*command_bounds, bounds);
             }
             _ => {
                 commands.push(Command {
                     clip_index,
+                    layer,
                     kind: CommandKind::BuiltIn {
                         indices: start_index..end_index,
                         constants,
                         texture: Some(cached.texture.id()),
+                        lookup: None,
+                        bounds,
                     },
                 });
             }
@@ -645,34 +1038,140 @@ impl Drop for Renderer<'_, '_> {
         for state in self.data.custom.values_mut() {
             state.finish(self.graphics);
         }
+        // Texture array slots are assigned first, allowing sorting to merge
+        // commands whose textures share an array.
+        self.data.prepare_texture_arrays(self.graphics);
+        self.data.assign_vertex_slots();
+        if self.sort_on_drop || self.data.sort_draws {
+            self.data.sort_commands();
+        }
+        let mut buffer_bytes_written = 0;
         if !self.data.indices.is_empty() {
             if let Some(buffers) = &mut self.data.buffers {
-                buffers.vertex.update(
+                buffer_bytes_written += buffers.vertex.update(
                     &self.data.vertices.vertices,
                     self.graphics.device,
                     self.graphics.queue,
                 );
-                buffers.index.update(
+                buffer_bytes_written += buffers.index.update(
                     &self.data.indices,
                     self.graphics.device,
                     self.graphics.queue,
                 );
             } else {
+                buffer_bytes_written +=
+                    std::mem::size_of_val(self.data.vertices.vertices.as_slice())
+                        + std::mem::size_of_val(self.data.indices.as_slice());
                 // Create new buffers
+                let label = self.data.label.as_deref().unwrap_or("kludgine::drawing");
                 self.data.buffers = Some(RenderingBuffers {
                     vertex: DiffableBuffer::new(
                         &self.data.vertices.vertices,
                         wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                        Some(format!("{label} vertices")),
                         self.graphics.device,
                     ),
                     index: DiffableBuffer::new(
                         &self.data.indices,
                         wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
+                        Some(format!("{label} indices")),
                         self.graphics.device,
                     ),
+                    slots: None,
                 });
             }
+
+            if !self.data.vertex_slots.is_empty() {
+                let buffers = self.data.buffers.as_mut().assert("buffers created");
+                if let Some(slots) = &mut buffers.slots {
+                    buffer_bytes_written += slots.update(
+                        &self.data.vertex_slots,
+                        self.graphics.device,
+                        self.graphics.queue,
+                    );
+                } else {
+                    buffer_bytes_written +=
+                        std::mem::size_of_val(self.data.vertex_slots.as_slice());
+                    let label = self.data.label.as_deref().unwrap_or("kludgine::drawing");
+                    buffers.slots = Some(DiffableBuffer::new(
+                        &self.data.vertex_slots,
+                        wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                        Some(format!("{label} texture slots")),
+                        self.graphics.device,
+                    ));
+                }
+            }
         }
+
+        self.data.stats = RenderStats {
+            draw_calls: self.data.commands.len(),
+            vertices: self.data.vertices.vertices.len(),
+            triangles: self.data.indices.len() / 3,
+            unique_textures: self.data.textures.len(),
+            text_runs: self.data.text_runs,
+            buffer_bytes_written,
+            glyph_atlas_used: 0,
+            glyph_atlas_total: 0,
+            glyphs_rasterized: 0,
+            glyphs_evicted: 0,
+        };
+        self.graphics.kludgine.frame_stats += self.data.stats;
+    }
+}
+
+/// Statistics about the graphics prepared for rendering.
+///
+/// The statistics for a single [`Drawing`] are available through
+/// [`Drawing::stats`], and the statistics for every [`Drawing`] prepared
+/// during the previous frame are available through
+/// [`Kludgine::last_frame_stats`](crate::Kludgine::last_frame_stats).
+#[derive(Default, Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RenderStats {
+    /// The number of draw calls issued when rendering.
+    pub draw_calls: usize,
+    /// The number of vertices uploaded.
+    pub vertices: usize,
+    /// The number of triangles drawn.
+    pub triangles: usize,
+    /// The number of distinct textures bound when rendering.
+    ///
+    /// For an entire frame, this is the sum of each [`Drawing`]'s count.
+    pub unique_textures: usize,
+    /// The number of blocks of text drawn.
+    pub text_runs: usize,
+    /// The number of bytes written to vertex and index buffers.
+    pub buffer_bytes_written: usize,
+    /// The number of pixels occupied by glyphs in the text atlases.
+    ///
+    /// This is only measured for the statistics of an entire frame.
+    pub glyph_atlas_used: u64,
+    /// The number of pixels allocated for the text atlases.
+    ///
+    /// This is only measured for the statistics of an entire frame.
+    pub glyph_atlas_total: u64,
+    /// The number of glyphs rasterized and added to the text atlases.
+    ///
+    /// This is only measured for the statistics of an entire frame.
+    pub glyphs_rasterized: usize,
+    /// The number of unused glyphs evicted from the text atlases to keep them
+    /// within their [budget](crate::Kludgine::set_glyph_atlas_budget).
+    ///
+    /// This is only measured for the statistics of an entire frame.
+    pub glyphs_evicted: usize,
+}
+
+impl AddAssign for RenderStats {
+    fn add_assign(&mut self, rhs: Self) {
+        self.draw_calls += rhs.draw_calls;
+        self.vertices += rhs.vertices;
+        self.triangles += rhs.triangles;
+        self.unique_textures += rhs.unique_textures;
+        self.text_runs += rhs.text_runs;
+        self.buffer_bytes_written += rhs.buffer_bytes_written;
+        self.glyph_atlas_used += rhs.glyph_atlas_used;
+        self.glyph_atlas_total += rhs.glyph_atlas_total;
+        self.glyphs_rasterized += rhs.glyphs_rasterized;
+        self.glyphs_evicted += rhs.glyphs_evicted;
     }
 }
 
@@ -691,20 +1190,173 @@ impl Drop for Renderer<'_, '_> {
 pub struct Drawing {
     buffers: Option<RenderingBuffers>,
     vertices: VertexCollection<i32>,
-    clips: Vec<Rect<UPx>>,
-    clip_lookup: HashMap<Rect<UPx>, u32, DefaultHasher>,
+    clips: Vec<(Rect<UPx>, Option<RotatedClip>)>,
+    clip_lookup: HashMap<(Rect<UPx>, Option<RotatedClip>), u32, DefaultHasher>,
     indices: Vec<u32>,
-    textures: HashMap<sealed::TextureId, Arc<wgpu::BindGroup>, DefaultHasher>,
+    textures: HashMap<sealed::TextureId, DrawingTexture, DefaultHasher>,
+    texture_arrays: Option<PreparedTextureArrays>,
+    texture_slots: HashMap<sealed::TextureId, TextureSlot, DefaultHasher>,
+    /// The texture array slot of each vertex, which is only populated when
+    /// textures are drawn using texture arrays.
+    vertex_slots: Vec<u32>,
+    lookups: HashMap<sealed::TextureId, Arc<wgpu::BindGroup>, DefaultHasher>,
     commands: Vec<Command>,
+    children: Vec<ChildDrawing>,
     custom: HashMap<TypeId, Box<dyn RenderOpState>, DefaultHasher>,
     #[cfg(feature = "cosmic-text")]
     glyphs: HashMap<cosmic_text::CacheKey, crate::text::CachedGlyphHandle, DefaultHasher>,
+    hit_testing: bool,
+    hit_regions: Vec<HitRegion>,
+    sort_draws: bool,
+    text_runs: usize,
+    stats: RenderStats,
+    label: Option<String>,
+}
+
+/// An interactive area recorded by [`Renderer::register_hit_region`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct HitRegion {
+    /// The id provided when this region was registered.
+    pub id: u64,
+    /// The area of this region, in physical pixels relative to the render
+    /// target.
+    pub rect: Rect<Px>,
+    /// The clip rect that was active when this region was registered.
+    pub clip: Rect<Px>,
+}
+
+impl HitRegion {
+    /// Returns true if `point` is within both this region's area and its clip
+    /// rect.
+    #[must_use]
+    pub fn contains(&self, point: Point<Px>) -> bool {
+        rect_contains(self.rect, point) && rect_contains(self.clip, point)
+    }
+}
+
+fn rect_contains(rect: Rect<Px>, point: Point<Px>) -> bool {
+    let (top_left, bottom_right) = rect.extents();
+    point.x >= top_left.x
+        && point.y >= top_left.y
+        && point.x < bottom_right.x
+        && point.y < bottom_right.y
+}
+
+#[test]
+fn hit_test_order() {
+    let mut drawing = Drawing::default();
+    drawing.set_hit_testing(true);
+    let clip = Rect::new(
+        Point::new(Px::ZERO, Px::ZERO),
+        Size::new(Px::new(100), Px::new(100)),
+    );
+    drawing.hit_regions.push(HitRegion {
+        id: 1,
+        rect: Rect::new(
+            Point::new(Px::new(0), Px::new(0)),
+            Size::new(Px::new(50), Px::new(50)),
+        ),
+        clip,
+    });
+    drawing.hit_regions.push(HitRegion {
+        id: 2,
+        rect: Rect::new(
+            Point::new(Px::new(25), Px::new(25)),
+            Size::new(Px::new(200), Px::new(50)),
+        ),
+        clip,
+    });
+    assert_eq!(
+        drawing.hit_test(Point::new(Px::new(10), Px::new(10))),
+        Some(1)
+    );
+    assert_eq!(
+        drawing.hit_test(Point::new(Px::new(30), Px::new(30))),
+        Some(2)
+    );
+    assert_eq!(
+        drawing
+            .hit_test_all(Point::new(Px::new(30), Px::new(30)))
+            .map(|region| region.id)
+            .collect::<Vec<_>>(),
+        [2, 1]
+    );
+    // Outside of the clip rect.
+    assert_eq!(
+        drawing.hit_test(Point::new(Px::new(150), Px::new(30))),
+        None
+    );
+}
+
+/// A [`Drawing`] drawn within another [`Drawing`].
+#[derive(Debug)]
+struct ChildDrawing {
+    drawing: Arc<Drawing>,
+    offset: Point<Px>,
+    opacity: f32,
+}
+
+/// A texture used by a [`Drawing`].
+#[derive(Debug)]
+struct DrawingTexture {
+    bind_group: Arc<wgpu::BindGroup>,
+    array: Option<ArrayTexture>,
+}
+
+impl DrawingTexture {
+    fn new(texture: &impl sealed::TextureSource, graphics: &impl sealed::KludgineGraphics) -> Self {
+        Self {
+            bind_group: texture.bind_group(graphics),
+            array: texture.array_texture(graphics),
+        }
+    }
+}
+
+/// The texture array bind groups used to render a [`Drawing`].
+#[derive(Debug)]
+struct PreparedTextureArrays {
+    arrays: Arc<TextureArrays>,
+    views: Vec<Arc<wgpu::TextureView>>,
+    bind_groups: Vec<wgpu::BindGroup>,
+}
+
+/// The location of a texture within a [`Drawing`]'s texture arrays.
+#[derive(Debug, Clone, Copy)]
+struct TextureSlot {
+    array: usize,
+    index: u32,
+    linear: bool,
+}
+
+impl TextureSlot {
+    /// Returns the value provided to the shader by each vertex sampling this
+    /// slot.
+    const fn vertex_value(&self) -> u32 {
+        if self.linear {
+            self.index | TEXTURE_SLOT_LINEAR
+        } else {
+            self.index
+        }
+    }
+}
+
+/// The bind group bound while rendering a [`Drawing`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Binding {
+    /// A bind group bound before the drawing began rendering.
+    Inherited,
+    Default,
+    Texture(sealed::TextureId),
+    Array(usize),
 }
 
 #[derive(Debug)]
 struct RenderingBuffers {
     vertex: DiffableBuffer<Vertex<i32>>,
     index: DiffableBuffer<u32>,
+    /// The texture array slot of each vertex, bound alongside `vertex` when
+    /// rendering with texture arrays.
+    slots: Option<DiffableBuffer<u32>>,
 }
 
 impl Drawing {
@@ -717,135 +1369,612 @@ impl Drawing {
         graphics: &'rendering mut Graphics<'gfx>,
     ) -> Renderer<'rendering, 'gfx> {
         self.commands.clear();
+        self.children.clear();
         self.indices.clear();
         self.textures.clear();
+        self.lookups.clear();
         self.vertices.vertex_index_by_id.clear();
         self.vertices.vertices.clear();
         self.clip_lookup.clear();
         self.clips.clear();
-        self.get_or_lookup_clip(graphics.clip.current);
+        self.get_or_lookup_clip(&graphics.clip);
         for state in self.custom.values_mut() {
             state.clear();
         }
         #[cfg(feature = "cosmic-text")]
         self.glyphs.clear();
+        self.hit_regions.clear();
+        self.text_runs = 0;
 
         Renderer {
             graphics,
             clip_index: 0,
+            layer: 0,
             data: self,
             opacity: 1.,
+            batching: false,
+            sort_on_drop: false,
+        }
+    }
+
+    /// Sets the label used for this drawing's vertex and index buffers, which
+    /// is shown in graphics debugging tools.
+    ///
+    /// The buffers are recreated during the next frame.
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.label = Some(label.into());
+        self.buffers = None;
+    }
+
+    /// Returns the label of this drawing, if one has been set.
+    #[must_use]
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Sets whether drawing operations are sorted to reduce the number of draw
+    /// calls.
+    ///
+    /// When enabled, operations are ordered by their
+    /// [layer](Renderer::set_layer) when the [`Renderer`] is dropped. Within
+    /// each layer, an operation is merged into an earlier draw call using the
+    /// same texture when it does not overlap any operation drawn between them,
+    /// so sorting never changes the rendered result. Operations that are
+    /// rotated or scaled are assumed to overlap everything. The relative order
+    /// of operations sharing a texture is preserved. Custom
+    /// [`RenderOperation`]s and [child drawings](Renderer::draw_drawing) are
+    /// never reordered, and operations are not moved across them.
+    ///
+    /// While sorting, every operation is drawn as if it were drawn through a
+    /// [`SpriteBatch`], allowing operations at different locations to be
+    /// merged. To only batch some operations, use [`Renderer::batched()`]
+    /// instead.
+    ///
+    /// Sorting is disabled by default, which draws operations in the order
+    /// they were submitted.
+    pub fn set_draw_sorting(&mut self, enabled: bool) {
+        self.sort_draws = enabled;
+    }
+
+    /// Returns true if drawing operations are sorted to reduce the number of
+    /// draw calls.
+    #[must_use]
+    pub const fn draw_sorting(&self) -> bool {
+        self.sort_draws
+    }
+
+    /// Returns the number of draw calls that will be issued by
+    /// [`render()`](Self::render).
+    #[must_use]
+    pub fn command_count(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Returns the statistics of the graphics prepared by the last
+    /// [`Renderer`] created by [`new_frame()`](Self::new_frame).
+    #[must_use]
+    pub const fn stats(&self) -> RenderStats {
+        self.stats
+    }
+
+    /// Reorders the built-in commands by layer and merges commands that share
+    /// a texture or texture array, rebuilding the index list to match.
+    ///
+    /// A command is only moved ahead of earlier commands on its layer that it
+    /// does not overlap, which keeps the rendered result identical to drawing
+    /// the commands in the order they were submitted.
+    fn sort_commands(&mut self) {
+        let mut sorted = Vec::with_capacity(self.commands.len());
+        let mut indices = Vec::with_capacity(self.indices.len());
+        let mut commands = std::mem::take(&mut self.commands).into_iter().peekable();
+        let mut segment = Vec::new();
+        let mut batches = Vec::<SortedBatch>::new();
+        loop {
+            // Custom operations and child drawings act as barriers: only the
+            // built-in commands between them are reordered.
+            while let Some(command) =
+                commands.next_if(|command| matches!(command.kind, CommandKind::BuiltIn { .. }))
+            {
+                segment.push(command);
+            }
+            segment.sort_by_key(|command: &Command| command.layer);
+
+            for command in segment.drain(..) {
+                let CommandKind::BuiltIn {
+                    indices: range,
+                    constants,
+                    texture,
+                    lookup,
+                    bounds,
+                } = command.kind
+                else {
+                    unreachable!("only built-in commands are sorted")
+                };
+                // Search backwards for a batch this command can join. The
+                // search stops at the first batch using the same bindings to
+                // preserve the order of operations sharing a texture, and at
+                // any batch this command overlaps.
+                let binding = texture.map(|id| self.texture_binding(id));
+                let mut target = None;
+                for (index, batch) in batches.iter().enumerate().rev() {
+                    if batch.layer != command.layer {
+                        break;
+                    } else if batch.binding == binding {
+                        if batch.clip_index == command.clip_index
+                            && batch.lookup == lookup
+                            && batch.constants == constants
+                        {
+                            target = Some(index);
+                        }
+                        break;
+                    } else if bounds_overlap(batch.bounds, bounds) {
+                        break;
+                    }
+                }
+
+                if let Some(index) = target {
+                    let batch = &mut batches[index];
+                    batch.bounds = union_bounds(batch.bounds, bounds);
+                    batch.ranges.push(range);
+                } else {
+                    batches.push(SortedBatch {
+                        clip_index: command.clip_index,
+                        layer: command.layer,
+                        constants,
+                        texture,
+                        binding,
+                        lookup,
+                        bounds,
+                        ranges: vec![range],
+                    });
+                }
+            }
+
+            for batch in batches.drain(..) {
+                let start = u32::try_from(indices.len()).expect("too many drawn indices");
+                for range in batch.ranges {
+                    indices
+                        .extend_from_slice(&self.indices[range.start as usize..range.end as usize]);
+                }
+                let end = u32::try_from(indices.len()).expect("too many drawn indices");
+                sorted.push(Command {
+                    clip_index: batch.clip_index,
+                    layer: batch.layer,
+                    kind: CommandKind::BuiltIn {
+                        indices: start..end,
+                        constants: batch.constants,
+                        texture: batch.texture,
+                        lookup: batch.lookup,
+                        bounds: batch.bounds,
+                    },
+                });
+            }
+
+            match commands.next() {
+                Some(custom) => sorted.push(custom),
+                None => break,
+            }
+        }
+        self.commands = sorted;
+        self.indices = indices;
+    }
+
+    /// Sets whether regions registered with
+    /// [`Renderer::register_hit_region`] are recorded.
+    ///
+    /// Hit testing is disabled by default.
+    pub fn set_hit_testing(&mut self, enabled: bool) {
+        self.hit_testing = enabled;
+        if !enabled {
+            self.hit_regions.clear();
+        }
+    }
+
+    /// Returns true if hit regions are being recorded.
+    #[must_use]
+    pub const fn hit_testing(&self) -> bool {
+        self.hit_testing
+    }
+
+    /// Returns the hit regions registered during the last frame, in the order
+    /// they were registered.
+    #[must_use]
+    pub fn hit_regions(&self) -> &[HitRegion] {
+        &self.hit_regions
+    }
+
+    /// Returns the id of the topmost region registered during the last frame
+    /// that contains `point`.
+    ///
+    /// Regions registered later are considered to be above regions registered
+    /// earlier.
+    #[must_use]
+    pub fn hit_test(&self, point: Point<Px>) -> Option<u64> {
+        self.hit_test_all(point).next().map(|region| region.id)
+    }
+
+    /// Returns an iterator over every region registered during the last frame
+    /// that contains `point`, from topmost to bottommost.
+    pub fn hit_test_all(&self, point: Point<Px>) -> impl Iterator<Item = &HitRegion> + '_ {
+        self.hit_regions
+            .iter()
+            .rev()
+            .filter(move |region| region.contains(point))
+    }
+
+    /// Assigns each texture that can be bound in a texture array to a slot,
+    /// in the order the textures are first drawn.
+    ///
+    /// The bind groups from the previous frame are reused if the same textures
+    /// are assigned to the same slots.
+    fn prepare_texture_arrays(&mut self, graphics: &Graphics<'_>) {
+        self.texture_slots.clear();
+        let Some(arrays) = &graphics.kludgine.texture_arrays else {
+            self.texture_arrays = None;
+            return;
+        };
+
+        let mut views = Vec::new();
+        for command in &self.commands {
+            let CommandKind::BuiltIn {
+                texture: Some(id), ..
+            } = &command.kind
+            else {
+                continue;
+            };
+            let Some(array) = &self.textures[id].array else {
+                continue;
+            };
+            if let hash_map::Entry::Vacant(entry) = self.texture_slots.entry(*id) {
+                entry.insert(TextureSlot {
+                    array: views.len() / TEXTURE_ARRAY_SIZE,
+                    index: (views.len() % TEXTURE_ARRAY_SIZE).cast::<u32>(),
+                    linear: array.linear,
+                });
+                views.push(array.view.clone());
+            }
+        }
+
+        if views.is_empty() {
+            self.texture_arrays = None;
+        } else if !self.texture_arrays.as_ref().is_some_and(|prepared| {
+            Arc::ptr_eq(&prepared.arrays, arrays)
+                && prepared.views.len() == views.len()
+                && prepared
+                    .views
+                    .iter()
+                    .zip(&views)
+                    .all(|(a, b)| Arc::ptr_eq(a, b))
+        }) {
+            let bind_groups = views
+                .chunks(TEXTURE_ARRAY_SIZE)
+                .map(|chunk| {
+                    arrays.bind_group(
+                        graphics,
+                        &chunk.iter().map(|view| &**view).collect::<Vec<_>>(),
+                    )
+                })
+                .collect();
+            self.texture_arrays = Some(PreparedTextureArrays {
+                arrays: arrays.clone(),
+                views,
+                bind_groups,
+            });
+        }
+    }
+
+    /// Returns the bind group used to draw `texture`.
+    fn texture_binding(&self, texture: sealed::TextureId) -> Binding {
+        self.texture_slots
+            .get(&texture)
+            .map_or(Binding::Texture(texture), |slot| Binding::Array(slot.array))
+    }
+
+    /// Records the texture array slot sampled by each vertex, duplicating
+    /// vertices that are shared by commands drawing from different slots.
+    ///
+    /// Vertices that are not drawn with a texture array are left unassigned.
+    fn assign_vertex_slots(&mut self) {
+        const UNASSIGNED: u32 = u32::MAX;
+
+        self.vertex_slots.clear();
+        if self.texture_slots.is_empty() {
+            return;
+        }
+        self.vertex_slots
+            .resize(self.vertices.vertices.len(), UNASSIGNED);
+        let mut duplicates = HashMap::<(u32, u32), u32, DefaultHasher>::default();
+        for command in &self.commands {
+            let CommandKind::BuiltIn {
+                indices,
+                texture: Some(texture),
+                ..
+            } = &command.kind
+            else {
+                continue;
+            };
+            let Some(slot) = self.texture_slots.get(texture) else {
+                continue;
+            };
+            let slot = slot.vertex_value();
+            for index in &mut self.indices[indices.start as usize..indices.end as usize] {
+                let vertex = *index;
+                let assigned = &mut self.vertex_slots[vertex as usize];
+                if *assigned == UNASSIGNED {
+                    *assigned = slot;
+                } else if *assigned != slot {
+                    *index = *duplicates.entry((vertex, slot)).or_insert_with(|| {
+                        let duplicate = u32::try_from(self.vertices.vertices.len())
+                            .expect("too many drawn verticies");
+                        self.vertices
+                            .vertices
+                            .push(self.vertices.vertices[vertex as usize]);
+                        self.vertex_slots.push(slot);
+                        duplicate
+                    });
+                }
+            }
         }
     }
 
-    fn get_or_lookup_clip(&mut self, clip: ClipRect) -> u32 {
-        *self.clip_lookup.entry(clip.0).or_insert_with(|| {
+    fn get_or_lookup_clip(&mut self, clip: &ClipStack) -> u32 {
+        let clip = (clip.current.0, clip.rotated);
+        *self.clip_lookup.entry(clip).or_insert_with(|| {
             let id = u32::try_from(self.clips.len()).expect("too many clips");
-            self.clips.push(clip.0);
+            self.clips.push(clip);
             id
         })
     }
 
     /// Renders the prepared graphics from the last frame.
     pub fn render<'pass>(&'pass self, opacity: f32, graphics: &mut RenderingGraphics<'_, 'pass>) {
-        if let Some(buffers) = &self.buffers {
-            let mut current_texture_id = None;
-            let mut needs_texture_binding = graphics.active_pipeline_if_needed();
-            let drawing_translation = graphics
-                .clip
-                .current
-                .origin
-                .into_signed()
-                .map(Px::into_unscaled);
-
-            graphics
-                .pass
-                .set_vertex_buffer(0, buffers.vertex.as_slice());
-            graphics
-                .pass
-                .set_index_buffer(buffers.index.as_slice(), wgpu::IndexFormat::Uint32);
-
-            let mut current_clip_index = u32::MAX;
-            let original_clip = graphics.clip.current;
-
-            for command in &self.commands {
-                if current_clip_index != command.clip_index {
-                    current_clip_index = command.clip_index;
-                    graphics.clip.current.0 = self.clips[command.clip_index as usize];
-                    if graphics.clip.current.size.width == 0
-                        || graphics.clip.current.size.height == 0
-                    {
-                        continue;
-                    }
+        let translation = graphics.clip.current.origin.into_signed() + graphics.translation;
+        self.render_within(opacity, translation, None, graphics);
+    }
 
+    /// Renders the prepared graphics from the last frame, translated by
+    /// `translation`.
+    ///
+    /// When this drawing is rendered as a child of another drawing, `parent`
+    /// contains the offset of this drawing's clip rects and the parent's clip
+    /// rect that they are limited to.
+    fn render_within<'pass>(
+        &'pass self,
+        opacity: f32,
+        translation: Point<Px>,
+        parent: Option<(Point<Px>, Rect<UPx>)>,
+        graphics: &mut RenderingGraphics<'_, 'pass>,
+    ) {
+        let mut current_binding = if graphics.active_pipeline_if_needed() {
+            None
+        } else {
+            Some(Binding::Inherited)
+        };
+        let mut array_pipeline = false;
+        // The lookup table bound to the second bind group, if known.
+        let mut current_lookup = None;
+        // The vertex and index buffers must be bound again after rendering a
+        // child drawing.
+        let mut buffers_bound = false;
+        let drawing_translation = translation.map(Px::into_unscaled);
+
+        let mut current_clip_index = u32::MAX;
+        let mut clip_visible = false;
+        let original_clip = graphics.clip.current;
+        let original_rotated_clip = graphics.clip.rotated;
+        let mut rotated_clip = None;
+
+        for command in &self.commands {
+            if current_clip_index != command.clip_index {
+                current_clip_index = command.clip_index;
+                let (clip, rotated) = self.clips[command.clip_index as usize];
+                (graphics.clip.current.0, rotated_clip) = match parent {
+                    Some((offset, bounds)) => (
+                        offset_clip(clip, offset, bounds),
+                        rotated
+                            .map(|rotated| rotated.offset(offset))
+                            .or(original_rotated_clip),
+                    ),
+                    None => (clip, rotated),
+                };
+                let scissor = graphics.scissor_rect(graphics.clip.current.0);
+                clip_visible = scissor.size.width > 0 && scissor.size.height > 0;
+                if clip_visible {
                     graphics.pass.set_scissor_rect(
-                        graphics.clip.current.origin.x.into(),
-                        graphics.clip.current.origin.y.into(),
-                        graphics.clip.current.size.width.into(),
-                        graphics.clip.current.size.height.into(),
+                        scissor.origin.x.into(),
+                        scissor.origin.y.into(),
+                        scissor.size.width.into(),
+                        scissor.size.height.into(),
                     );
-                } else if graphics.clip.current.size.width == 0
-                    || graphics.clip.current.size.height == 0
-                {
-                    continue;
                 }
-
-                match &command.kind {
-                    CommandKind::BuiltIn {
-                        indices,
-                        constants,
-                        texture,
-                    } => {
-                        if let Some(texture_id) = texture {
-                            if current_texture_id != Some(*texture_id) {
-                                needs_texture_binding = false;
-                                current_texture_id = Some(*texture_id);
-                                graphics.pass.set_bind_group(
-                                    0,
-                                    &**self.textures.get(texture_id).assert("texture missing"),
-                                    &[],
-                                );
-                            }
-                        } else if needs_texture_binding {
-                            needs_texture_binding = false;
-                            current_texture_id = None;
-                            graphics.pass.set_bind_group(
-                                0,
-                                &graphics.kludgine.default_bindings,
-                                &[],
-                            );
+            }
+            if !clip_visible {
+                continue;
+            }
+            graphics.clip.rotated = rotated_clip;
+
+            match &command.kind {
+                // Commands without any indices have nothing to draw, and the
+                // buffers are only created once indices have been drawn.
+                CommandKind::BuiltIn { indices, .. } if indices.is_empty() => {}
+                // Commands are culled using the translation and clip in effect
+                // when rendering, allowing a drawing to be reused at another
+                // location.
+                CommandKind::BuiltIn { bounds, .. }
+                    if is_culled(*bounds, translation, graphics.clip.current.0) => {}
+                CommandKind::BuiltIn {
+                    indices,
+                    constants,
+                    texture,
+                    lookup,
+                    ..
+                } => {
+                    if !buffers_bound {
+                        buffers_bound = true;
+                        let buffers = self.buffers.as_ref().assert("indices drawn");
+                        graphics
+                            .pass
+                            .set_vertex_buffer(0, buffers.vertex.as_slice());
+                        if let Some(slots) = &buffers.slots {
+                            graphics.pass.set_vertex_buffer(1, slots.as_slice());
                         }
-
-                        let mut constants = *constants;
-                        constants.opacity *= opacity;
-                        constants.translation += drawing_translation;
-                        if constants.translation.is_zero() {
-                            constants.flags ^= FLAG_TRANSLATE;
+                        graphics
+                            .pass
+                            .set_index_buffer(buffers.index.as_slice(), wgpu::IndexFormat::Uint32);
+                    }
+                    let mut constants = *constants;
+                    let binding = match texture {
+                        Some(id) => self.texture_binding(*id),
+                        None => current_binding.unwrap_or(Binding::Default),
+                    };
+                    if current_binding != Some(binding) {
+                        current_binding = Some(binding);
+                        current_lookup = None;
+                        if let Binding::Array(array) = binding {
+                            let arrays = self
+                                .texture_arrays
+                                .as_ref()
+                                .assert("texture arrays prepared");
+                            if !array_pipeline {
+                                array_pipeline = true;
+                                // Kludgine's pipeline must be set again before it is
+                                // used after the texture array pipeline.
+                                graphics.pipeline_is_active = false;
+                                graphics.pass.set_pipeline(if graphics.depth_tested {
+                                    &arrays.arrays.depth_pipeline
+                                } else {
+                                    &arrays.arrays.pipeline
+                                });
+                            }
+                            graphics
+                                .pass
+                                .set_bind_group(0, &arrays.bind_groups[array], &[]);
                         } else {
-                            constants.flags |= FLAG_TRANSLATE;
+                            array_pipeline = false;
+                            graphics.active_pipeline_if_needed();
+                            let bind_group = match binding {
+                                Binding::Texture(id) => {
+                                    &*self.textures.get(&id).assert("texture missing").bind_group
+                                }
+                                _ => &graphics.kludgine.default_bindings,
+                            };
+                            graphics.pass.set_bind_group(0, bind_group, &[]);
                         }
-                        graphics.pass.set_push_constants(
-                            wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
-                            0,
-                            bytemuck::bytes_of(&constants),
+                    }
+
+                    if current_lookup != Some(*lookup) {
+                        current_lookup = Some(*lookup);
+                        graphics.pass.set_bind_group(
+                            1,
+                            lookup.map_or(&graphics.kludgine.default_lookup, |id| {
+                                &*self.lookups[&id]
+                            }),
+                            &[],
                         );
-                        graphics.pass.draw_indexed(indices.clone(), 0, 0..1);
                     }
-                    CommandKind::Custom(op_id, prepared) => {
-                        self.custom
-                            .get(op_id)
-                            .assert("op drawn")
-                            .render(*prepared, opacity, graphics);
-                        needs_texture_binding = true;
+
+                    constants.opacity *= opacity;
+                    constants.target_scale = graphics.target_scale;
+                    constants.translation += drawing_translation;
+                    if constants.translation.is_zero() {
+                        constants.flags ^= FLAG_TRANSLATE;
+                    } else {
+                        constants.flags |= FLAG_TRANSLATE;
+                    }
+                    if let Some(clip) = rotated_clip {
+                        constants.flags |= FLAG_CLIP_ROTATED;
+                        constants.clip = clip;
                     }
+                    graphics.pass.set_push_constants(
+                        wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                        0,
+                        bytemuck::bytes_of(&constants),
+                    );
+                    graphics.pass.draw_indexed(indices.clone(), 0, 0..1);
+                }
+                CommandKind::Drawing(child) => {
+                    let child = &self.children[*child];
+                    let clip_offset =
+                        parent.map_or(child.offset, |(offset, _)| offset + child.offset);
+                    child.drawing.render_within(
+                        opacity * child.opacity,
+                        translation + child.offset,
+                        Some((clip_offset, graphics.clip.current.0)),
+                        graphics,
+                    );
+                    current_binding = None;
+                    current_lookup = None;
+                    array_pipeline = false;
+                    buffers_bound = false;
+                    // The child changes the scissor rect.
+                    current_clip_index = u32::MAX;
+                }
+                CommandKind::Custom(op_id, prepared) => {
+                    self.custom
+                        .get(op_id)
+                        .assert("op drawn")
+                        .render(*prepared, opacity, graphics);
+                    current_binding = None;
+                    current_lookup = None;
+                    array_pipeline = false;
                 }
-
-                graphics.clip.current = original_clip;
             }
+
+            graphics.clip.current = original_clip;
+            graphics.clip.rotated = original_rotated_clip;
         }
     }
 }
 
+/// A group of built-in commands that are drawn with a single draw call after
+/// sorting.
+struct SortedBatch {
+    clip_index: u32,
+    layer: i32,
+    constants: PushConstants,
+    texture: Option<sealed::TextureId>,
+    binding: Option<Binding>,
+    lookup: Option<sealed::TextureId>,
+    bounds: Rect<Px>,
+    ranges: Vec<Range<u32>>,
+}
+
+/// Returns the smallest area containing both `a` and `b`.
+fn union_bounds(a: Rect<Px>, b: Rect<Px>) -> Rect<Px> {
+    let (a_min, a_max) = a.extents();
+    let (b_min, b_max) = b.extents();
+    Rect::from_extents(
+        Point::new(a_min.x.min(b_min.x), a_min.y.min(b_min.y)),
+        Point::new(a_max.x.max(b_max.x), a_max.y.max(b_max.y)),
+    )
+}
+
+/// Returns true if `a` and `b` share any pixels.
+fn bounds_overlap(a: Rect<Px>, b: Rect<Px>) -> bool {
+    let (a_min, a_max) = a.extents();
+    let (b_min, b_max) = b.extents();
+    a_min.x < b_max.x && b_min.x < a_max.x && a_min.y < b_max.y && b_min.y < a_max.y
+}
+
+/// Returns true if `bounds` moved by `translation` is entirely outside of
+/// `clip`.
+fn is_culled(bounds: Rect<Px>, translation: Point<Px>, clip: Rect<UPx>) -> bool {
+    !bounds_overlap(
+        Rect::new(bounds.origin + translation, bounds.size),
+        Rect::new(clip.origin.into_signed(), clip.size.into_signed()),
+    )
+}
+
+/// Returns `clip` moved by `offset` and limited to `bounds`.
+fn offset_clip(clip: Rect<UPx>, offset: Point<Px>, bounds: Rect<UPx>) -> Rect<UPx> {
+    let clip = Rect::new(clip.origin.into_signed() + offset, clip.size.into_signed());
+    let bounds = Rect::new(bounds.origin.into_signed(), bounds.size.into_signed());
+    clip.intersection(&bounds)
+        .map_or_else(Rect::default, |clip| {
+            Rect::new(clip.origin.into_unsigned(), clip.size.into_unsigned())
+        })
+}
+
 struct RenderOperationState<Op>
 where
     Op: RenderOperation,
@@ -980,3 +2109,243 @@ pub trait RenderOperation: Send + Sync + 'static {
         Err(other)
     }
 }
+
+#[cfg(test)]
+fn push_sorted_test_command(
+    drawing: &mut Drawing,
+    texture: Option<sealed::TextureId>,
+    layer: i32,
+    clip_index: u32,
+    origin: Point<Px>,
+) {
+    use figures::Px2D;
+
+    let start = u32::try_from(drawing.indices.len()).unwrap();
+    drawing.indices.extend(start..start + 3);
+    drawing.commands.push(Command {
+        clip_index,
+        layer,
+        kind: CommandKind::BuiltIn {
+            indices: start..start + 3,
+            constants: PushConstants::zeroed(),
+            texture,
+            lookup: None,
+            bounds: Rect::new(origin, Size::px(10, 10)),
+        },
+    });
+}
+
+#[cfg(test)]
+fn sorted_test_textures(drawing: &Drawing) -> Vec<Option<sealed::TextureId>> {
+    drawing
+        .commands
+        .iter()
+        .map(|command| match &command.kind {
+            CommandKind::BuiltIn { texture, .. } => *texture,
+            CommandKind::Custom(..) | CommandKind::Drawing(_) => unreachable!(),
+        })
+        .collect()
+}
+
+#[test]
+fn sorted_commands_merge() {
+    use figures::Px2D;
+
+    let first = Some(sealed::TextureId::new_unique_id());
+    let second = Some(sealed::TextureId::new_unique_id());
+    let mut drawing = Drawing::default();
+    drawing.set_draw_sorting(true);
+    // Alternate between two textures on one layer without overlapping, with a
+    // final command on a lower layer.
+    for (index, (texture, layer)) in [(first, 0), (second, 0), (first, 0), (second, 0), (None, -1)]
+        .into_iter()
+        .enumerate()
+    {
+        let offset = i32::try_from(index).unwrap() * 20;
+        push_sorted_test_command(&mut drawing, texture, layer, 0, Point::px(offset, 0));
+        assert_eq!(drawing.command_count(), index + 1);
+    }
+
+    drawing.sort_commands();
+    assert_eq!(drawing.command_count(), 3);
+    assert_eq!(
+        drawing.indices,
+        [12, 13, 14, 0, 1, 2, 6, 7, 8, 3, 4, 5, 9, 10, 11]
+    );
+    assert_eq!(sorted_test_textures(&drawing), [None, first, second]);
+}
+
+#[test]
+fn sorted_commands_preserve_overlaps() {
+    use figures::Px2D;
+
+    let first = Some(sealed::TextureId::new_unique_id());
+    let second = Some(sealed::TextureId::new_unique_id());
+    let mut drawing = Drawing::default();
+    drawing.set_draw_sorting(true);
+    // B overlaps both draws of A, so the second A must stay on top of B.
+    push_sorted_test_command(&mut drawing, first, 0, 0, Point::px(0, 0));
+    push_sorted_test_command(&mut drawing, second, 0, 0, Point::px(5, 5));
+    push_sorted_test_command(&mut drawing, first, 0, 0, Point::px(0, 0));
+    drawing.sort_commands();
+    assert_eq!(sorted_test_textures(&drawing), [first, second, first]);
+    assert_eq!(drawing.indices, [0, 1, 2, 3, 4, 5, 6, 7, 8]);
+
+    // Draws sharing a texture keep their order even when their clip differs.
+    let mut drawing = Drawing::default();
+    drawing.set_draw_sorting(true);
+    push_sorted_test_command(&mut drawing, first, 0, 1, Point::px(0, 0));
+    push_sorted_test_command(&mut drawing, first, 0, 0, Point::px(0, 0));
+    push_sorted_test_command(&mut drawing, first, 0, 1, Point::px(0, 0));
+    drawing.sort_commands();
+    let clips = drawing
+        .commands
+        .iter()
+        .map(|command| command.clip_index)
+        .collect::<Vec<_>>();
+    assert_eq!(clips, [1, 0, 1]);
+}
+
+#[test]
+fn sorted_commands_merge_texture_arrays() {
+    use figures::Px2D;
+
+    let first = sealed::TextureId::new_unique_id();
+    let second = sealed::TextureId::new_unique_id();
+    let third = sealed::TextureId::new_unique_id();
+    let mut drawing = Drawing::default();
+    drawing.set_draw_sorting(true);
+    for (texture, array, index) in [(first, 0, 0), (second, 0, 1), (third, 1, 0)] {
+        drawing.texture_slots.insert(
+            texture,
+            TextureSlot {
+                array,
+                index,
+                linear: false,
+            },
+        );
+    }
+    // The first two textures share an array, so alternating between them
+    // only requires a single draw call.
+    for (index, texture) in [first, second, third, first, second]
+        .into_iter()
+        .enumerate()
+    {
+        let offset = i32::try_from(index).unwrap() * 20;
+        push_sorted_test_command(&mut drawing, Some(texture), 0, 0, Point::px(offset, 0));
+    }
+
+    drawing.sort_commands();
+    assert_eq!(sorted_test_textures(&drawing), [Some(first), Some(third)]);
+    assert_eq!(
+        drawing.indices,
+        [0, 1, 2, 3, 4, 5, 9, 10, 11, 12, 13, 14, 6, 7, 8]
+    );
+}
+
+#[test]
+fn vertex_slots_are_assigned_per_texture() {
+    let first = sealed::TextureId::new_unique_id();
+    let second = sealed::TextureId::new_unique_id();
+    let mut drawing = Drawing::default();
+    drawing.texture_slots.insert(
+        first,
+        TextureSlot {
+            array: 0,
+            index: 0,
+            linear: false,
+        },
+    );
+    drawing.texture_slots.insert(
+        second,
+        TextureSlot {
+            array: 0,
+            index: 1,
+            linear: true,
+        },
+    );
+    for x in 0..4 {
+        drawing.vertices.vertices.push(Vertex {
+            location: Point::new(x, 0),
+            texture: Point::default(),
+            color: Color::WHITE,
+        });
+    }
+    // Both commands share two vertices, which must be duplicated because they
+    // sample different slots.
+    for (texture, indices) in [(first, [0, 1, 2]), (second, [1, 2, 3])] {
+        let start = u32::try_from(drawing.indices.len()).unwrap();
+        drawing.indices.extend(indices);
+        drawing.commands.push(Command {
+            clip_index: 0,
+            layer: 0,
+            kind: CommandKind::BuiltIn {
+                indices: start..start + 3,
+                constants: PushConstants::zeroed(),
+                texture: Some(texture),
+                lookup: None,
+                bounds: Rect::default(),
+            },
+        });
+    }
+
+    drawing.assign_vertex_slots();
+    let linear = 1 | TEXTURE_SLOT_LINEAR;
+    assert_eq!(drawing.indices, [0, 1, 2, 4, 5, 3]);
+    assert_eq!(drawing.vertex_slots, [0, 0, 0, linear, linear, linear]);
+    assert_eq!(drawing.vertices.vertices[4], drawing.vertices.vertices[1]);
+    assert_eq!(drawing.vertices.vertices[5], drawing.vertices.vertices[2]);
+
+    // Without texture arrays, no slots are recorded.
+    drawing.texture_slots.clear();
+    drawing.assign_vertex_slots();
+    assert!(drawing.vertex_slots.is_empty());
+}
+
+#[test]
+fn child_clips_are_offset() {
+    use figures::{Px2D, UPx2D};
+
+    let bounds = Rect::new(Point::upx(0, 0), Size::upx(100, 60));
+    assert_eq!(
+        offset_clip(
+            Rect::new(Point::upx(0, 0), Size::upx(50, 50)),
+            Point::px(-10, 20),
+            bounds
+        ),
+        Rect::new(Point::upx(0, 20), Size::upx(40, 40))
+    );
+    assert_eq!(
+        offset_clip(
+            Rect::new(Point::upx(0, 0), Size::upx(50, 50)),
+            Point::px(200, 0),
+            bounds
+        ),
+        Rect::default()
+    );
+}
+
+#[test]
+#[cfg(feature = "app")]
+fn offscreen_commands_are_culled_when_rendering() {
+    use figures::{Px2D, UPx2D};
+
+    let Ok(mut window) = crate::app::headless_window(Size::upx(64, 64), |mut renderer, _window| {
+        // Entirely outside of the window when recorded.
+        renderer.draw_shape(&Shape::filled_rect(
+            Rect::new(Point::px(100, 100), Size::px(16, 16)),
+            Color::RED,
+        ));
+        true
+    }) else {
+        // No graphics adapter is available.
+        return;
+    };
+    window.behavior_mut().set_translation(Point::px(-90, -90));
+    window.render();
+    assert_eq!(window.behavior().drawing().command_count(), 1);
+    let pixels = window.read_pixels().expect("pixels read");
+    let pixel = |x: usize, y: usize| &pixels[(y * 64 + x) * 4..(y * 64 + x + 1) * 4];
+    assert_eq!(pixel(15, 15), [255, 0, 0, 255]);
+    assert_eq!(pixel(30, 30), [0, 0, 0, 255]);
+}
@@ -0,0 +1,118 @@
+use std::ops::{Deref, DerefMut};
+use std::time::Duration;
+
+use figures::{IntoSigned, Rect, ScreenUnit};
+
+use super::Renderer;
+use crate::pipeline::ShaderScalable;
+use crate::sprite::Sprite;
+
+/// A [`Renderer`] that merges drawing operations sharing a texture into as few
+/// draw calls as possible.
+///
+/// A batch is created with [`Renderer::batched()`]. While a batch exists,
+/// operations that are not rotated or scaled apply their translation and
+/// opacity to their vertices instead of the shader's parameters. This allows
+/// operations drawn at different locations to share a single draw call. When
+/// the [`Renderer`] is dropped, the operations are
+/// [sorted](super::Drawing::set_draw_sorting) so that operations sharing a
+/// texture are merged, without changing the rendered result.
+///
+/// [`tilemap::draw`](crate::tilemap::draw) always draws its layers in a batch.
+/// Drawing a tile map and the sprites above it through the same batch allows
+/// a scene whose tiles and sprites come from a single sprite sheet or
+/// [`TextureCollection`](crate::TextureCollection) to be rendered using very
+/// few draw calls.
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+///
+/// use kludgine::drawing::Renderer;
+/// use kludgine::figures::units::Px;
+/// use kludgine::figures::Rect;
+/// use kludgine::sprite::Sprite;
+/// use kludgine::tilemap::{self, Layers, TileMapFocus};
+///
+/// fn draw_scene(
+///     renderer: &mut Renderer<'_, '_>,
+///     map: &mut impl Layers,
+///     player: &mut Sprite,
+///     elapsed: Duration,
+/// ) {
+///     let mut batch = renderer.batched();
+///     tilemap::draw(map, TileMapFocus::default(), 1., elapsed, &mut batch);
+///     batch.draw_sprite(player, Rect::<Px>::default(), Some(elapsed), 1.);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SpriteBatch<'batch, 'render, 'gfx> {
+    renderer: &'batch mut Renderer<'render, 'gfx>,
+    was_batching: bool,
+}
+
+impl<'render, 'gfx> Renderer<'render, 'gfx> {
+    /// Returns a [`SpriteBatch`] that merges the operations drawn through it
+    /// into as few draw calls as possible.
+    ///
+    /// Batches can be nested, and the batch ends when the returned value is
+    /// dropped.
+    pub fn batched(&mut self) -> SpriteBatch<'_, 'render, 'gfx> {
+        let was_batching = std::mem::replace(&mut self.batching, true);
+        self.sort_on_drop = true;
+        SpriteBatch {
+            renderer: self,
+            was_batching,
+        }
+    }
+
+    /// Returns true if operations are currently being drawn into a
+    /// [`SpriteBatch`].
+    #[must_use]
+    pub const fn is_batching(&self) -> bool {
+        self.batching
+    }
+}
+
+impl SpriteBatch<'_, '_, '_> {
+    /// Draws the frame of `sprite` after advancing its animation by `elapsed`
+    /// into `destination`.
+    ///
+    /// Returns the amount of time until the sprite's next frame should be
+    /// shown, or `None` if the sprite is not animating or its current tag is
+    /// invalid.
+    pub fn draw_sprite<Unit>(
+        &mut self,
+        sprite: &mut Sprite,
+        destination: Rect<Unit>,
+        elapsed: Option<Duration>,
+        opacity: f32,
+    ) -> Option<Duration>
+    where
+        Unit: figures::Unit + ScreenUnit + ShaderScalable,
+        i32: From<<Unit as IntoSigned>::Signed>,
+    {
+        let frame = sprite.get_frame(elapsed).ok()?;
+        self.renderer.draw_texture(&frame, destination, opacity);
+        sprite.remaining_frame_duration().ok().flatten()
+    }
+}
+
+impl Drop for SpriteBatch<'_, '_, '_> {
+    fn drop(&mut self) {
+        self.renderer.batching = self.was_batching;
+    }
+}
+
+impl<'render, 'gfx> Deref for SpriteBatch<'_, 'render, 'gfx> {
+    type Target = Renderer<'render, 'gfx>;
+
+    fn deref(&self) -> &Self::Target {
+        self.renderer
+    }
+}
+
+impl DerefMut for SpriteBatch<'_, '_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.renderer
+    }
+}
@@ -0,0 +1,116 @@
+use figures::units::Px;
+use figures::{IntoSigned, Point, Rect};
+use intentional::Assert;
+
+use super::{Drawing, Renderer};
+use crate::{ClipStack, Texture};
+
+/// A group of graphics that fades as a single layer.
+///
+/// When overlapping graphics are drawn with an opacity, each graphic blends
+/// with the graphics beneath it, causing the overlapping regions to show
+/// through each other. An opacity group instead renders its graphics into an
+/// intermediate texture, and then draws the texture with the group's opacity.
+/// This is useful for fading out a collection of graphics, such as during a
+/// transition.
+///
+/// The group's textures are reused between frames as long as the size of the
+/// area being drawn into does not change. Each group should only be drawn
+/// once per frame, as drawing it again replaces the texture's contents before
+/// the frame is rendered.
+///
+/// Graphics within the group that are partially transparent are blended with
+/// the group's transparent background, which causes them to appear slightly
+/// more transparent than when drawn directly.
+///
+/// ```rust,no_run
+/// use kludgine::drawing::{OpacityGroup, Renderer};
+/// use kludgine::figures::units::Px;
+/// use kludgine::figures::{Point, Rect, Size};
+/// use kludgine::shapes::Shape;
+/// use kludgine::Color;
+///
+/// fn draw_faded(renderer: &mut Renderer<'_, '_>, group: &mut OpacityGroup, opacity: f32) {
+///     group.draw(renderer, opacity, |renderer| {
+///         for (offset, color) in [(0, Color::RED), (20, Color::BLUE)] {
+///             let origin = Point::new(Px::new(offset), Px::new(offset));
+///             let size = Size::new(Px::new(40), Px::new(40));
+///             renderer.draw_shape(&Shape::filled_rect(Rect::new(origin, size), color));
+///         }
+///     });
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct OpacityGroup {
+    drawing: Drawing,
+    texture: Option<Texture>,
+    multisampled: Option<wgpu::Texture>,
+}
+
+impl OpacityGroup {
+    /// Returns a new, empty opacity group.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prepares the graphics drawn by `draw` and draws them into `renderer` as
+    /// a single layer with `opacity`.
+    ///
+    /// `draw` is invoked with a [`Renderer`] whose origin and size match the
+    /// current clip rect of `renderer`. The graphics are rendered into the
+    /// group's texture immediately, and the texture is drawn using
+    /// `renderer`.
+    ///
+    /// If `opacity` is zero or the current clip rect is empty, `draw` is not
+    /// invoked. Hit regions registered within `draw` are ignored.
+    pub fn draw(
+        &mut self,
+        renderer: &mut Renderer<'_, '_>,
+        opacity: f32,
+        draw: impl FnOnce(&mut Renderer<'_, '_>),
+    ) {
+        let size = renderer.clip.current.size;
+        if opacity <= 0. || size.width == 0 || size.height == 0 {
+            return;
+        }
+
+        // Prepare the group's graphics relative to the group's texture.
+        let outer_clip = std::mem::replace(&mut renderer.graphics.clip, ClipStack::new(size));
+        let mut group = self.drawing.new_frame(renderer.graphics);
+        draw(&mut group);
+        drop(group);
+        renderer.graphics.clip = outer_clip;
+
+        let graphics = &mut *renderer.graphics;
+        if self
+            .texture
+            .as_ref()
+            .map_or(true, |texture| texture.size() != size)
+        {
+            self.texture = Some(Texture::new_generic(
+                &*graphics,
+                1,
+                size,
+                graphics.kludgine.format,
+                wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::RENDER_ATTACHMENT,
+                wgpu::FilterMode::Nearest,
+                Some("kludgine::opacity group"),
+            ));
+        }
+        let texture = self.texture.as_ref().assert("created above");
+        graphics.render_into_texture(
+            texture,
+            &mut self.multisampled,
+            "kludgine::opacity group",
+            &self.drawing,
+            |drawing, rendering| drawing.render(1., rendering),
+        );
+
+        renderer.draw_texture(
+            texture,
+            Rect::new(Point::<Px>::default(), size.into_signed()),
+            opacity,
+        );
+    }
+}
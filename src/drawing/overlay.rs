@@ -0,0 +1,173 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use figures::units::Px;
+use figures::{Point, Rect, Size};
+use intentional::Cast;
+
+use super::Renderer;
+use crate::shapes::Shape;
+#[cfg(feature = "cosmic-text")]
+use crate::text::{Text, TextOrigin};
+use crate::Color;
+#[cfg(feature = "cosmic-text")]
+use crate::{Drawable, DrawableExt};
+
+const BAR_WIDTH: i32 = 2;
+const GRAPH_HEIGHT: i32 = 40;
+const PADDING: i32 = 6;
+const TARGET_FRAME_TIME: Duration = Duration::from_micros(16_667);
+const BACKGROUND: Color = Color::new(0, 0, 0, 192);
+
+/// An overlay that displays [`RenderStats`](super::RenderStats) and a graph
+/// of recent frame times.
+///
+/// The overlay is drawn in the top-left corner of a [`Renderer`] by
+/// [`draw()`](Self::draw), which should be invoked once per frame after all
+/// other graphics have been drawn. Statistics are only available once a frame
+/// has been prepared, so the overlay displays the statistics of the previous
+/// frame. These statistics include the overlay itself.
+///
+/// The frame time graph is scaled so that its midpoint represents 60 frames
+/// per second.
+#[derive(Debug, Clone)]
+pub struct DebugOverlay {
+    frame_times: VecDeque<Duration>,
+    last_frame: Option<Instant>,
+    history: usize,
+}
+
+impl Default for DebugOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DebugOverlay {
+    /// Returns a new overlay that graphs the last 120 frames.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_history(120)
+    }
+
+    /// Returns a new overlay that graphs the last `frames` frames.
+    #[must_use]
+    pub fn with_history(frames: usize) -> Self {
+        let history = frames.max(1);
+        Self {
+            frame_times: VecDeque::with_capacity(history),
+            last_frame: None,
+            history,
+        }
+    }
+
+    /// Returns the average number of frames per second across the graphed
+    /// frames.
+    #[must_use]
+    pub fn frames_per_second(&self) -> f32 {
+        let total = self.frame_times.iter().sum::<Duration>();
+        if total.is_zero() {
+            0.
+        } else {
+            self.frame_times.len().cast::<f32>() / total.as_secs_f32()
+        }
+    }
+
+    /// Records the time since this overlay was last drawn and draws the
+    /// overlay using `renderer`.
+    pub fn draw(&mut self, renderer: &mut Renderer<'_, '_>) {
+        let now = Instant::now();
+        if let Some(last_frame) = self.last_frame.replace(now) {
+            if self.frame_times.len() == self.history {
+                self.frame_times.pop_front();
+            }
+            self.frame_times
+                .push_back(now.saturating_duration_since(last_frame));
+        }
+
+        let padding = Px::new(PADDING);
+        let graph_size = Size::new(
+            Px::new(BAR_WIDTH) * self.history.cast::<i32>(),
+            Px::new(GRAPH_HEIGHT),
+        );
+
+        #[cfg(feature = "cosmic-text")]
+        let label = {
+            let stats = renderer.last_frame_stats();
+            let atlas_percent = if stats.glyph_atlas_total > 0 {
+                stats.glyph_atlas_used.cast::<f32>() * 100. / stats.glyph_atlas_total.cast::<f32>()
+            } else {
+                0.
+            };
+            let label = format!(
+                "{:.1} fps\n{} draw calls\n{} vertices\n{} triangles\n{} textures\n{} text \
+                 runs\n{:.1} KiB written\n{atlas_percent:.0}% glyph atlas used",
+                self.frames_per_second(),
+                stats.draw_calls,
+                stats.vertices,
+                stats.triangles,
+                stats.unique_textures,
+                stats.text_runs,
+                stats.buffer_bytes_written.cast::<f32>() / 1024.,
+            );
+            renderer.measure_text(Text::<Px>::new(&label, Color::WHITE))
+        };
+        #[cfg(feature = "cosmic-text")]
+        let label_height = label.size.height + padding;
+        #[cfg(not(feature = "cosmic-text"))]
+        let label_height = Px::new(0);
+        #[cfg(feature = "cosmic-text")]
+        let width = graph_size.width.max(label.size.width);
+        #[cfg(not(feature = "cosmic-text"))]
+        let width = graph_size.width;
+
+        renderer.draw_shape(&Shape::filled_rect(
+            Rect::new(
+                Point::new(Px::new(0), Px::new(0)),
+                Size::new(
+                    width + padding * 2,
+                    label_height + graph_size.height + padding * 2,
+                ),
+            ),
+            BACKGROUND,
+        ));
+
+        #[cfg(feature = "cosmic-text")]
+        renderer.draw_measured_text(
+            Drawable::from(&label).translate_by(Point::new(padding, padding)),
+            TextOrigin::TopLeft,
+        );
+
+        let graph_bottom = padding + label_height + graph_size.height;
+        renderer.draw_shape(&Shape::filled_rect(
+            Rect::new(
+                Point::new(padding, graph_bottom - graph_size.height / 2),
+                Size::new(graph_size.width, Px::new(1)),
+            ),
+            Color::new(255, 255, 255, 96),
+        ));
+        let mut x = padding + graph_size.width;
+        for frame_time in self.frame_times.iter().rev() {
+            x -= Px::new(BAR_WIDTH);
+            let ratio = frame_time.as_secs_f32() / TARGET_FRAME_TIME.as_secs_f32();
+            let height = (GRAPH_HEIGHT.cast::<f32>() * (ratio / 2.).min(1.))
+                .ceil()
+                .cast::<i32>()
+                .max(1);
+            let color = if ratio <= 1. {
+                Color::new(64, 200, 64, 255)
+            } else if ratio <= 2. {
+                Color::new(220, 200, 64, 255)
+            } else {
+                Color::new(220, 64, 64, 255)
+            };
+            renderer.draw_shape(&Shape::filled_rect(
+                Rect::new(
+                    Point::new(x, graph_bottom - Px::new(height)),
+                    Size::new(Px::new(BAR_WIDTH), Px::new(height)),
+                ),
+                color,
+            ));
+        }
+    }
+}
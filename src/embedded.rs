@@ -0,0 +1,314 @@
+#[cfg(feature = "app")]
+use std::collections::HashSet;
+
+#[cfg(feature = "app")]
+use appit::winit::event::{ElementState, MouseButton, WindowEvent};
+#[cfg(feature = "app")]
+use figures::units::{Lp, Px};
+use figures::units::UPx;
+#[cfg(feature = "app")]
+use figures::Point;
+use figures::{Fraction, Size};
+
+use crate::convert::DisplayScale;
+use crate::drawing::{Drawing, Renderer};
+use crate::{Color, Kludgine};
+
+/// Kludgine rendering hosted by an application that manages its own `wgpu`
+/// device and event loop.
+///
+/// This type owns a [`Kludgine`] instance and a [`Drawing`], and renders into
+/// any [`wgpu::TextureView`] the host provides each frame. The host remains
+/// responsible for acquiring and presenting surface textures.
+///
+/// ```rust,no_run
+/// # fn example(
+/// #     device: &kludgine::wgpu::Device,
+/// #     queue: &kludgine::wgpu::Queue,
+/// #     surface: &kludgine::wgpu::Surface<'_>,
+/// #     format: kludgine::wgpu::TextureFormat,
+/// # ) {
+/// use kludgine::embedded::Embedded;
+/// use kludgine::figures::units::Px;
+/// use kludgine::figures::{Point, Rect, Size};
+/// use kludgine::shapes::Shape;
+/// use kludgine::Color;
+///
+/// let mut kludgine = Embedded::new(device, queue, format, Size::new(800, 600), 1.0);
+///
+/// // Each frame, after rendering the host's scene:
+/// let frame = surface.get_current_texture().unwrap();
+/// let view = frame.texture.create_view(&Default::default());
+/// kludgine.render(device, queue, &view, None, |renderer| {
+///     renderer.draw_shape(&Shape::filled_rect(
+///         Rect::new(
+///             Point::new(Px::new(10), Px::new(10)),
+///             Size::new(Px::new(100), Px::new(50)),
+///         ),
+///         Color::RED,
+///     ));
+/// });
+/// frame.present();
+/// # }
+/// ```
+pub struct Embedded {
+    kludgine: Kludgine,
+    drawing: Drawing,
+    depth_texture: Option<wgpu::Texture>,
+    depth_testing: bool,
+    #[cfg(feature = "app")]
+    cursor_position: Option<Point<Px>>,
+    #[cfg(feature = "app")]
+    mouse_buttons: HashSet<MouseButton>,
+}
+
+impl Embedded {
+    /// Returns a new instance that renders to textures of `format` that are
+    /// `size` pixels large, using a DPI scale of `scale`.
+    #[must_use]
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        format: wgpu::TextureFormat,
+        size: Size<UPx>,
+        scale: f32,
+    ) -> Self {
+        Self::with_multisample(
+            device,
+            queue,
+            format,
+            wgpu::MultisampleState::default(),
+            size,
+            scale,
+        )
+    }
+
+    /// Returns a new instance that renders to multisampled textures.
+    ///
+    /// When `multisample.count` is greater than 1, the views passed to
+    /// [`render()`](Self::render) must be multisampled with the same count,
+    /// and a resolve target should be provided.
+    #[must_use]
+    pub fn with_multisample(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        format: wgpu::TextureFormat,
+        multisample: wgpu::MultisampleState,
+        size: Size<UPx>,
+        scale: f32,
+    ) -> Self {
+        Self {
+            kludgine: Kludgine::new(device, queue, format, multisample, size, scale),
+            drawing: Drawing::default(),
+            depth_texture: None,
+            depth_testing: false,
+            #[cfg(feature = "app")]
+            cursor_position: None,
+            #[cfg(feature = "app")]
+            mouse_buttons: HashSet::new(),
+        }
+    }
+
+    /// Returns the Kludgine instance used for rendering.
+    #[must_use]
+    pub const fn kludgine(&self) -> &Kludgine {
+        &self.kludgine
+    }
+
+    /// Returns an exclusive reference to the Kludgine instance used for
+    /// rendering.
+    #[must_use]
+    pub fn kludgine_mut(&mut self) -> &mut Kludgine {
+        &mut self.kludgine
+    }
+
+    /// Returns the size being rendered.
+    #[must_use]
+    pub const fn size(&self) -> Size<UPx> {
+        self.kludgine.size()
+    }
+
+    /// Returns the scale used to convert between physical pixels and the
+    /// logical units used when drawing.
+    #[must_use]
+    pub fn display_scale(&self) -> DisplayScale {
+        self.kludgine.display_scale()
+    }
+
+    /// Updates the size of the textures being rendered to.
+    pub fn resize(&mut self, size: Size<UPx>, queue: &wgpu::Queue) {
+        self.kludgine
+            .resize(size, self.kludgine.dpi_scale(), self.kludgine.zoom(), queue);
+    }
+
+    /// Updates the DPI scale.
+    pub fn set_scale(&mut self, scale: impl Into<Fraction>, queue: &wgpu::Queue) {
+        self.kludgine.set_dpi_scale(scale, queue);
+    }
+
+    /// Sets whether frames are rendered with a depth buffer.
+    ///
+    /// When enabled, [`Drawable::z`](crate::Drawable::z) controls which
+    /// graphics are drawn above others, regardless of the order they are
+    /// drawn in. The depth buffer is managed by this type and matches the
+    /// size and multisample state of the rendered views.
+    pub fn set_depth_testing(&mut self, enabled: bool) {
+        self.depth_testing = enabled;
+        if !enabled {
+            self.depth_texture = None;
+        }
+    }
+
+    /// Returns true if frames are rendered with a depth buffer.
+    #[must_use]
+    pub const fn depth_testing(&self) -> bool {
+        self.depth_testing
+    }
+
+    /// Draws a frame into `view`.
+    ///
+    /// `draw` is invoked with a [`Renderer`] to issue drawing commands. If
+    /// `clear_color` is provided, `view` is cleared before drawing. Otherwise,
+    /// Kludgine's graphics are drawn above the view's existing contents.
+    ///
+    /// The commands are submitted to `queue` before returning.
+    #[allow(clippy::must_use_candidate)]
+    pub fn render<F>(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        view: &wgpu::TextureView,
+        clear_color: Option<Color>,
+        draw: F,
+    ) -> Option<wgpu::SubmissionIndex>
+    where
+        F: FnOnce(&mut Renderer<'_, '_>),
+    {
+        self.render_with_resolve(device, queue, view, None, clear_color, draw)
+    }
+
+    /// Draws a frame into the multisampled `view`, resolving it into
+    /// `resolve_target`.
+    ///
+    /// See [`render()`](Self::render) for more information.
+    #[allow(clippy::must_use_candidate)]
+    pub fn render_with_resolve<F>(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        view: &wgpu::TextureView,
+        resolve_target: Option<&wgpu::TextureView>,
+        clear_color: Option<Color>,
+        draw: F,
+    ) -> Option<wgpu::SubmissionIndex>
+    where
+        F: FnOnce(&mut Renderer<'_, '_>),
+    {
+        let mut frame = self.kludgine.next_frame();
+        let mut graphics = frame.prepare(device, queue);
+        let mut renderer = self.drawing.new_frame(&mut graphics);
+        draw(&mut renderer);
+        drop(renderer);
+        drop(graphics);
+
+        let depth_view = self
+            .depth_testing
+            .then(|| frame.kludgine.depth_view(&mut self.depth_texture, device));
+        let mut rendering = frame.render(
+            &wgpu::RenderPassDescriptor {
+                label: Some("kludgine::render"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target,
+                    ops: wgpu::Operations {
+                        load: clear_color.map_or(wgpu::LoadOp::Load, |color| {
+                            wgpu::LoadOp::Clear(color.into())
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: depth_view.as_ref().map(Kludgine::depth_attachment),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            },
+            device,
+            queue,
+        );
+        self.drawing.render(1., &mut rendering);
+        drop(rendering);
+        frame.submit(queue)
+    }
+
+    /// Returns the last cursor position observed by
+    /// [`handle_window_event()`](Self::handle_window_event), if the cursor is
+    /// above the window.
+    #[cfg(feature = "app")]
+    #[must_use]
+    pub const fn cursor_position(&self) -> Option<Point<Px>> {
+        self.cursor_position
+    }
+
+    /// Returns the [last cursor position](Self::cursor_position) converted
+    /// into logical units using [`display_scale()`](Self::display_scale).
+    #[cfg(feature = "app")]
+    #[must_use]
+    pub fn logical_cursor_position(&self) -> Option<Point<Lp>> {
+        let scale = self.display_scale();
+        self.cursor_position
+            .map(|position| scale.to_logical(position))
+    }
+
+    /// Returns true if `button` was pressed according to the events observed
+    /// by [`handle_window_event()`](Self::handle_window_event).
+    #[cfg(feature = "app")]
+    #[must_use]
+    pub fn mouse_button_pressed(&self, button: MouseButton) -> bool {
+        self.mouse_buttons.contains(&button)
+    }
+
+    /// Updates this instance based on `event`.
+    ///
+    /// Resizes and scale factor changes are applied automatically, and the
+    /// cursor position and mouse button state are tracked. Returns true if the
+    /// size or scale changed.
+    ///
+    /// The host should continue to handle `event` normally. This function does
+    /// not consume events.
+    #[cfg(feature = "app")]
+    pub fn handle_window_event(&mut self, event: &WindowEvent, queue: &wgpu::Queue) -> bool {
+        match event {
+            WindowEvent::Resized(size) => {
+                let size = Size::from(*size);
+                let changed = size != self.size();
+                self.resize(size, queue);
+                changed
+            }
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                let scale = DisplayScale::from_factor(*scale_factor).get();
+                let changed = scale != self.kludgine.dpi_scale();
+                self.set_scale(scale, queue);
+                changed
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                self.cursor_position = Some(Point::from(*position));
+                false
+            }
+            WindowEvent::CursorLeft { .. } => {
+                self.cursor_position = None;
+                false
+            }
+            WindowEvent::MouseInput { state, button, .. } => {
+                match state {
+                    ElementState::Pressed => self.mouse_buttons.insert(*button),
+                    ElementState::Released => self.mouse_buttons.remove(button),
+                };
+                false
+            }
+            WindowEvent::Focused(false) => {
+                self.mouse_buttons.clear();
+                false
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,888 @@
+//! A minimal C interface for driving Kludgine from non-Rust hosts.
+//!
+//! Each [`KludgineContext`] owns its own `wgpu` device and renders into an
+//! offscreen texture. A frame is drawn by calling [`kludgine_begin_frame`],
+//! issuing any number of draw calls, and finishing with [`kludgine_submit`],
+//! which can optionally copy the rendered pixels into a buffer owned by the
+//! host.
+//!
+//! # Handles
+//!
+//! Apart from the context itself, every object created through this interface
+//! is identified by a [`KludgineHandle`]: an integer that is only meaningful to
+//! the context that created it. Handles are never reused, so passing a handle
+//! after it has been released is detected and ignored rather than causing
+//! undefined behavior. A handle of `0` is never valid. This makes the interface
+//! straightforward to wrap in garbage collected languages such as Python or
+//! JavaScript: a wrapper object stores the handle and releases it from its
+//! finalizer.
+//!
+//! - Textures are created with [`kludgine_texture_load_rgba`] and released with
+//!   [`kludgine_texture_release`].
+//! - Drawings are reusable lists of draw calls. They are created with
+//!   [`kludgine_drawing_create`], recorded between [`kludgine_drawing_begin`]
+//!   and [`kludgine_drawing_end`], drawn with [`kludgine_draw_drawing`], and
+//!   released with [`kludgine_drawing_release`].
+//! - Fonts are loaded with `kludgine_font_load` and released with
+//!   `kludgine_font_release`.
+//!
+//! Releasing a handle only releases the context's reference to the object.
+//! Drawings and pending frames that use a released texture keep it alive until
+//! they no longer need it.
+//!
+//! Panics and errors reported by the graphics device are never propagated to
+//! the host. Functions instead report failure through their return values.
+//!
+//! Colors are passed as `0xRRGGBBAA` integers, and all coordinates are in
+//! pixels. To produce a linkable library, build this crate with the `ffi`
+//! feature using a `cdylib` or `staticlib` crate type, for example with
+//! `cargo rustc --features ffi --crate-type staticlib`.
+#![allow(unsafe_code)]
+
+#[cfg(feature = "cosmic-text")]
+use std::ffi::{c_char, CStr};
+use std::panic::{self, AssertUnwindSafe};
+#[cfg(feature = "cosmic-text")]
+use std::sync::Arc;
+
+#[cfg(feature = "cosmic-text")]
+use cosmic_text::fontdb;
+use figures::units::{Px, UPx};
+use figures::{Point, Rect, Size};
+
+use crate::drawing::Drawing;
+use crate::shapes::Shape;
+#[cfg(feature = "cosmic-text")]
+use crate::DrawableExt;
+use crate::{Color, ErrorScopes, Graphics, Kludgine, SharedTexture, Texture};
+
+const TARGET_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+/// An identifier for an object owned by a [`KludgineContext`].
+///
+/// `0` is never a valid handle.
+pub type KludgineHandle = u64;
+
+/// A Kludgine instance rendering into an offscreen texture.
+pub struct KludgineContext {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    kludgine: Kludgine,
+    target: Texture,
+    drawing: Drawing,
+    clear_color: Color,
+    commands: Vec<Command>,
+    recording: Option<KludgineHandle>,
+    textures: Registry<SharedTexture>,
+    drawings: Registry<Vec<Command>>,
+    #[cfg(feature = "cosmic-text")]
+    fonts: Registry<LoadedFont>,
+}
+
+#[cfg(feature = "cosmic-text")]
+struct LoadedFont {
+    faces: Vec<fontdb::ID>,
+    family: cosmic_text::FamilyOwned,
+}
+
+#[derive(Clone)]
+enum Command {
+    Texture {
+        texture: SharedTexture,
+        destination: Rect<Px>,
+        opacity: f32,
+    },
+    Rect {
+        rect: Rect<Px>,
+        color: Color,
+    },
+    #[cfg(feature = "cosmic-text")]
+    Text {
+        text: String,
+        origin: Point<Px>,
+        color: Color,
+        family: Option<cosmic_text::FamilyOwned>,
+        size: Option<Px>,
+    },
+}
+
+impl Command {
+    fn translate_by(&mut self, offset: Point<Px>) {
+        match self {
+            Command::Texture { destination, .. } => destination.origin += offset,
+            Command::Rect { rect, .. } => rect.origin += offset,
+            #[cfg(feature = "cosmic-text")]
+            Command::Text { origin, .. } => *origin += offset,
+        }
+    }
+}
+
+/// A collection of values identified by generational handles.
+///
+/// The lower 32 bits of a handle are one more than the slot index, and the
+/// upper 32 bits are the slot's generation. Each time a slot is vacated its
+/// generation is incremented, preventing stale handles from resolving to a
+/// newer value.
+struct Registry<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<u32>,
+    len: usize,
+}
+
+struct Slot<T> {
+    generation: u32,
+    value: Option<T>,
+}
+
+impl<T> Default for Registry<T> {
+    fn default() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<T> Registry<T> {
+    fn insert(&mut self, value: T) -> KludgineHandle {
+        let index = if let Some(index) = self.free.pop() {
+            index
+        } else {
+            let Ok(index) = u32::try_from(self.slots.len()) else {
+                return 0;
+            };
+            if index == u32::MAX {
+                return 0;
+            }
+            self.slots.push(Slot {
+                generation: 0,
+                value: None,
+            });
+            index
+        };
+        let slot = &mut self.slots[index as usize];
+        slot.value = Some(value);
+        self.len += 1;
+        (u64::from(slot.generation) << 32) | u64::from(index + 1)
+    }
+
+    fn slot_index(&self, handle: KludgineHandle) -> Option<usize> {
+        #[allow(clippy::cast_possible_truncation)]
+        let (index, generation) = ((handle as u32).checked_sub(1)?, (handle >> 32) as u32);
+        let index = index as usize;
+        let slot = self.slots.get(index)?;
+        (slot.generation == generation && slot.value.is_some()).then_some(index)
+    }
+
+    fn get(&self, handle: KludgineHandle) -> Option<&T> {
+        let index = self.slot_index(handle)?;
+        self.slots[index].value.as_ref()
+    }
+
+    fn get_mut(&mut self, handle: KludgineHandle) -> Option<&mut T> {
+        let index = self.slot_index(handle)?;
+        self.slots[index].value.as_mut()
+    }
+
+    fn remove(&mut self, handle: KludgineHandle) -> Option<T> {
+        let index = self.slot_index(handle)?;
+        let slot = &mut self.slots[index];
+        let value = slot.value.take();
+        self.len -= 1;
+        // A slot whose generation is exhausted is retired rather than reused.
+        if let Some(generation) = slot.generation.checked_add(1) {
+            slot.generation = generation;
+            #[allow(clippy::cast_possible_truncation)]
+            self.free.push(index as u32);
+        }
+        value
+    }
+
+    const fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl KludgineContext {
+    fn new(width: u32, height: u32, scale: f32) -> Option<Self> {
+        if !scale.is_finite() || scale <= 0. {
+            return None;
+        }
+        let instance = wgpu::Instance::default();
+        let adapter =
+            pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))?;
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("kludgine::device"),
+                required_features: Kludgine::REQURED_FEATURES,
+                required_limits: Kludgine::adjust_limits(wgpu::Limits::downlevel_defaults()),
+                memory_hints: wgpu::MemoryHints::default(),
+            },
+            None,
+        ))
+        .ok()?;
+        let size = texture_size(&device, width, height)?;
+        let scopes = ErrorScopes::push(&device);
+        let mut kludgine = Kludgine::new(
+            &device,
+            &queue,
+            TARGET_FORMAT,
+            wgpu::MultisampleState::default(),
+            size,
+            scale,
+        );
+        kludgine.set_adapter(&adapter);
+        let target = Texture::new(
+            &Graphics::new(&mut kludgine, &device, &queue),
+            size,
+            TARGET_FORMAT,
+            wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            wgpu::FilterMode::Linear,
+        );
+        if scopes.pop().is_some() {
+            return None;
+        }
+        Some(Self {
+            device,
+            queue,
+            kludgine,
+            target,
+            drawing: Drawing::default(),
+            clear_color: Color::CLEAR_BLACK,
+            commands: Vec::new(),
+            recording: None,
+            textures: Registry::default(),
+            drawings: Registry::default(),
+            #[cfg(feature = "cosmic-text")]
+            fonts: Registry::default(),
+        })
+    }
+
+    /// Returns the list that draw calls are currently being added to: either
+    /// the drawing being recorded or the current frame.
+    fn current_commands(&mut self) -> &mut Vec<Command> {
+        match self
+            .recording
+            .and_then(|handle| self.drawings.get_mut(handle))
+        {
+            Some(commands) => commands,
+            None => &mut self.commands,
+        }
+    }
+
+    /// Renders the recorded commands into the target, returning the first
+    /// error reported by the graphics device.
+    fn submit(&mut self) -> Option<wgpu::Error> {
+        let scopes = ErrorScopes::push(&self.device);
+        let mut frame = self.kludgine.next_frame();
+        let mut graphics = frame.prepare(&self.device, &self.queue);
+        let mut renderer = self.drawing.new_frame(&mut graphics);
+        for command in self.commands.drain(..) {
+            match command {
+                Command::Texture {
+                    texture,
+                    destination,
+                    opacity,
+                } => renderer.draw_texture(&texture, destination, opacity),
+                Command::Rect { rect, color } => {
+                    renderer.draw_shape(&Shape::filled_rect(rect, color));
+                }
+                #[cfg(feature = "cosmic-text")]
+                Command::Text {
+                    text,
+                    origin,
+                    color,
+                    family,
+                    size,
+                } => {
+                    renderer.reset_text_attributes();
+                    if let Some(family) = family {
+                        renderer.set_font_family(family);
+                    }
+                    if let Some(size) = size {
+                        renderer.set_font_size(size);
+                    }
+                    renderer.draw_text(crate::text::Text::new(&text, color).translate_by(origin));
+                }
+            }
+        }
+        drop(renderer);
+        drop(graphics);
+
+        let mut rendering = frame.render_into(
+            &self.target,
+            wgpu::LoadOp::Clear(self.clear_color),
+            &self.device,
+            &self.queue,
+        );
+        self.drawing.render(1., &mut rendering);
+        drop(rendering);
+        frame.submit(&self.queue);
+        scopes.pop()
+    }
+
+    fn read_pixels(&self, pixels: &mut [u8]) -> bool {
+        let Some(data) = self.target.read_pixels(&self.device, &self.queue) else {
+            return false;
+        };
+        let Some(pixels) = pixels.get_mut(..data.len()) else {
+            return false;
+        };
+        pixels.copy_from_slice(&data);
+        true
+    }
+}
+
+/// Returns `width` by `height` if it is a valid size for a texture created by
+/// `device`.
+fn texture_size(device: &wgpu::Device, width: u32, height: u32) -> Option<Size<UPx>> {
+    let max = device.limits().max_texture_dimension_2d;
+    (width > 0 && height > 0 && width <= max && height <= max).then(|| Size::new(width, height))
+}
+
+/// Returns the number of bytes in tightly packed, 8-bit RGBA data that is
+/// `width` by `height` pixels, or `None` if it does not fit in a `usize`.
+fn rgba_len(width: u32, height: u32) -> Option<usize> {
+    usize::try_from(width)
+        .ok()?
+        .checked_mul(usize::try_from(height).ok()?)?
+        .checked_mul(4)
+}
+
+/// Invokes `body`, returning `fallback` if it panics.
+///
+/// Unwinding out of an `extern "C"` function aborts the host process, so every
+/// entry point catches panics before returning to the host.
+fn guarded<R>(fallback: R, body: impl FnOnce() -> R) -> R {
+    panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or(fallback)
+}
+
+fn rect(x: f32, y: f32, width: f32, height: f32) -> Rect<Px> {
+    Rect::new(
+        Point::new(Px::from(x), Px::from(y)),
+        Size::new(Px::from(width), Px::from(height)),
+    )
+}
+
+/// Creates a new context that renders into a `width` by `height` pixel
+/// texture. `scale` is the DPI scale applied to graphics.
+///
+/// Returns null if no compatible graphics adapter is available, if either
+/// dimension is zero or exceeds the device's maximum texture size, or if
+/// `scale` is not a positive number. The returned context must be freed using
+/// [`kludgine_destroy`].
+#[no_mangle]
+pub extern "C" fn kludgine_create(width: u32, height: u32, scale: f32) -> *mut KludgineContext {
+    guarded(std::ptr::null_mut(), || {
+        KludgineContext::new(width, height, scale).map_or(std::ptr::null_mut(), |context| {
+            Box::into_raw(Box::new(context))
+        })
+    })
+}
+
+/// Frees a context created by [`kludgine_create`], releasing every object
+/// that it owns.
+///
+/// # Safety
+///
+/// `context` must be null or a pointer returned from [`kludgine_create`] that
+/// has not already been destroyed.
+#[no_mangle]
+pub unsafe extern "C" fn kludgine_destroy(context: *mut KludgineContext) {
+    guarded((), || {
+        if !context.is_null() {
+            drop(Box::from_raw(context));
+        }
+    })
+}
+
+/// Returns the number of textures, drawings, and fonts that have not been
+/// released.
+///
+/// Bindings can use this function to verify that their wrappers release every
+/// object they create.
+///
+/// # Safety
+///
+/// `context` must be a valid context.
+#[no_mangle]
+pub unsafe extern "C" fn kludgine_live_objects(context: *const KludgineContext) -> usize {
+    guarded(0, || {
+        let Some(context) = context.as_ref() else {
+            return 0;
+        };
+        let count = context.textures.len() + context.drawings.len();
+        #[cfg(feature = "cosmic-text")]
+        let count = count + context.fonts.len();
+        count
+    })
+}
+
+/// Loads a texture from tightly packed, 8-bit RGBA pixel data.
+///
+/// Returns `0` if either dimension is zero or exceeds the device's maximum
+/// texture size, or if `data_len` does not match `width * height * 4`. The
+/// returned texture must be released using [`kludgine_texture_release`].
+///
+/// # Safety
+///
+/// `context` must be a valid context, and `data` must point to at least
+/// `data_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn kludgine_texture_load_rgba(
+    context: *mut KludgineContext,
+    width: u32,
+    height: u32,
+    data: *const u8,
+    data_len: usize,
+) -> KludgineHandle {
+    guarded(0, || {
+        let Some(context) = context.as_mut() else {
+            return 0;
+        };
+        let Some(size) = texture_size(&context.device, width, height) else {
+            return 0;
+        };
+        if data.is_null() || rgba_len(width, height) != Some(data_len) {
+            return 0;
+        }
+        let data = std::slice::from_raw_parts(data, data_len);
+        let Ok(texture) = Graphics::new(&mut context.kludgine, &context.device, &context.queue)
+            .capture_errors(|graphics| {
+                Texture::new_with_data(
+                    graphics,
+                    size,
+                    wgpu::TextureFormat::Rgba8UnormSrgb,
+                    wgpu::TextureUsages::TEXTURE_BINDING,
+                    wgpu::FilterMode::Nearest,
+                    data,
+                )
+            })
+        else {
+            return 0;
+        };
+        context.textures.insert(SharedTexture::from(texture))
+    })
+}
+
+/// Writes the size of `texture` into `width` and `height`.
+///
+/// Returns false if `texture` is not a valid handle.
+///
+/// # Safety
+///
+/// `context` must be a valid context, and `width` and `height` must be valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn kludgine_texture_size(
+    context: *const KludgineContext,
+    texture: KludgineHandle,
+    width: *mut u32,
+    height: *mut u32,
+) -> bool {
+    guarded(false, || {
+        let Some(texture) = context
+            .as_ref()
+            .and_then(|context| context.textures.get(texture))
+        else {
+            return false;
+        };
+        let size = texture.size();
+        if let Some(width) = width.as_mut() {
+            *width = size.width.get();
+        }
+        if let Some(height) = height.as_mut() {
+            *height = size.height.get();
+        }
+        true
+    })
+}
+
+/// Releases a texture loaded by [`kludgine_texture_load_rgba`].
+///
+/// Returns false if `texture` is not a valid handle.
+///
+/// # Safety
+///
+/// `context` must be a valid context.
+#[no_mangle]
+pub unsafe extern "C" fn kludgine_texture_release(
+    context: *mut KludgineContext,
+    texture: KludgineHandle,
+) -> bool {
+    guarded(false, || {
+        context
+            .as_mut()
+            .and_then(|context| context.textures.remove(texture))
+            .is_some()
+    })
+}
+
+/// Creates a new, empty drawing.
+///
+/// The returned drawing must be released using [`kludgine_drawing_release`].
+///
+/// # Safety
+///
+/// `context` must be a valid context.
+#[no_mangle]
+pub unsafe extern "C" fn kludgine_drawing_create(context: *mut KludgineContext) -> KludgineHandle {
+    guarded(0, || {
+        context
+            .as_mut()
+            .map_or(0, |context| context.drawings.insert(Vec::new()))
+    })
+}
+
+/// Clears `drawing` and begins recording into it.
+///
+/// Until [`kludgine_drawing_end`] is called, draw calls are added to `drawing`
+/// instead of the current frame. Returns false if `drawing` is not a valid
+/// handle.
+///
+/// # Safety
+///
+/// `context` must be a valid context.
+#[no_mangle]
+pub unsafe extern "C" fn kludgine_drawing_begin(
+    context: *mut KludgineContext,
+    drawing: KludgineHandle,
+) -> bool {
+    guarded(false, || {
+        let Some(context) = context.as_mut() else {
+            return false;
+        };
+        let Some(commands) = context.drawings.get_mut(drawing) else {
+            return false;
+        };
+        commands.clear();
+        context.recording = Some(drawing);
+        true
+    })
+}
+
+/// Stops recording into the current drawing. Subsequent draw calls are added
+/// to the current frame.
+///
+/// # Safety
+///
+/// `context` must be a valid context.
+#[no_mangle]
+pub unsafe extern "C" fn kludgine_drawing_end(context: *mut KludgineContext) {
+    guarded((), || {
+        if let Some(context) = context.as_mut() {
+            context.recording = None;
+        }
+    })
+}
+
+/// Releases a drawing created by [`kludgine_drawing_create`]. If the drawing
+/// is being recorded, recording is stopped.
+///
+/// Returns false if `drawing` is not a valid handle.
+///
+/// # Safety
+///
+/// `context` must be a valid context.
+#[no_mangle]
+pub unsafe extern "C" fn kludgine_drawing_release(
+    context: *mut KludgineContext,
+    drawing: KludgineHandle,
+) -> bool {
+    guarded(false, || {
+        let Some(context) = context.as_mut() else {
+            return false;
+        };
+        if context.recording == Some(drawing) {
+            context.recording = None;
+        }
+        context.drawings.remove(drawing).is_some()
+    })
+}
+
+/// Draws the contents of `drawing` offset by `x`, `y`.
+///
+/// The drawing's current contents are copied, so later changes to `drawing`
+/// do not affect draw calls that have already been made. Returns false if
+/// `drawing` is not a valid handle.
+///
+/// # Safety
+///
+/// `context` must be a valid context.
+#[no_mangle]
+pub unsafe extern "C" fn kludgine_draw_drawing(
+    context: *mut KludgineContext,
+    drawing: KludgineHandle,
+    x: f32,
+    y: f32,
+) -> bool {
+    guarded(false, || {
+        let Some(context) = context.as_mut() else {
+            return false;
+        };
+        let Some(commands) = context.drawings.get(drawing).cloned() else {
+            return false;
+        };
+        let offset = Point::new(Px::from(x), Px::from(y));
+        context
+            .current_commands()
+            .extend(commands.into_iter().map(|mut command| {
+                command.translate_by(offset);
+                command
+            }));
+        true
+    })
+}
+
+/// Loads a TrueType or OpenType font, or a font collection, from `data`.
+///
+/// Returns `0` if `data` contains no usable fonts. The returned font must be
+/// released using [`kludgine_font_release`].
+///
+/// # Safety
+///
+/// `context` must be a valid context, and `data` must point to at least
+/// `data_len` readable bytes.
+#[cfg(feature = "cosmic-text")]
+#[no_mangle]
+pub unsafe extern "C" fn kludgine_font_load(
+    context: *mut KludgineContext,
+    data: *const u8,
+    data_len: usize,
+) -> KludgineHandle {
+    guarded(0, || {
+        let Some(context) = context.as_mut() else {
+            return 0;
+        };
+        if data.is_null() {
+            return 0;
+        }
+        let data = std::slice::from_raw_parts(data, data_len).to_vec();
+        let db = context.kludgine.font_system().db_mut();
+        let faces = db
+            .load_font_source(fontdb::Source::Binary(Arc::new(data)))
+            .to_vec();
+        let Some(family) = faces
+            .first()
+            .and_then(|id| db.face(*id))
+            .and_then(|face| face.families.first())
+            .map(|(name, _)| cosmic_text::FamilyOwned::Name(name.clone()))
+        else {
+            for id in faces {
+                db.remove_face(id);
+            }
+            return 0;
+        };
+        context.kludgine.rebuild_font_system();
+        context.fonts.insert(LoadedFont { faces, family })
+    })
+}
+
+/// Releases a font loaded by [`kludgine_font_load`].
+///
+/// Returns false if `font` is not a valid handle.
+///
+/// # Safety
+///
+/// `context` must be a valid context.
+#[cfg(feature = "cosmic-text")]
+#[no_mangle]
+pub unsafe extern "C" fn kludgine_font_release(
+    context: *mut KludgineContext,
+    font: KludgineHandle,
+) -> bool {
+    guarded(false, || {
+        let Some(context) = context.as_mut() else {
+            return false;
+        };
+        let Some(font) = context.fonts.remove(font) else {
+            return false;
+        };
+        let db = context.kludgine.font_system().db_mut();
+        for id in font.faces {
+            db.remove_face(id);
+        }
+        context.kludgine.rebuild_font_system();
+        true
+    })
+}
+
+/// Begins a new frame that will be cleared to `clear_color` before drawing.
+///
+/// Any draw calls that were not submitted are discarded, and any drawing being
+/// recorded is ended.
+///
+/// # Safety
+///
+/// `context` must be a valid context.
+#[no_mangle]
+pub unsafe extern "C" fn kludgine_begin_frame(context: *mut KludgineContext, clear_color: u32) {
+    guarded((), || {
+        if let Some(context) = context.as_mut() {
+            context.commands.clear();
+            context.recording = None;
+            context.clear_color = Color(clear_color);
+        }
+    })
+}
+
+/// Draws `texture` stretched to fill the rectangle at `x`, `y` with the
+/// provided size.
+///
+/// Returns false if `texture` is not a valid handle.
+///
+/// # Safety
+///
+/// `context` must be a valid context.
+#[no_mangle]
+pub unsafe extern "C" fn kludgine_draw_texture(
+    context: *mut KludgineContext,
+    texture: KludgineHandle,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    opacity: f32,
+) -> bool {
+    guarded(false, || {
+        let Some(context) = context.as_mut() else {
+            return false;
+        };
+        let Some(texture) = context.textures.get(texture).cloned() else {
+            return false;
+        };
+        context.current_commands().push(Command::Texture {
+            texture,
+            destination: rect(x, y, width, height),
+            opacity,
+        });
+        true
+    })
+}
+
+/// Fills a rectangle at `x`, `y` with the provided size and `color`.
+///
+/// # Safety
+///
+/// `context` must be a valid context.
+#[no_mangle]
+pub unsafe extern "C" fn kludgine_draw_rect(
+    context: *mut KludgineContext,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    color: u32,
+) {
+    guarded((), || {
+        if let Some(context) = context.as_mut() {
+            context.current_commands().push(Command::Rect {
+                rect: rect(x, y, width, height),
+                color: Color(color),
+            });
+        }
+    })
+}
+
+/// Draws `text` with its top-left corner at `x`, `y`.
+///
+/// `font` is a handle returned from [`kludgine_font_load`], or `0` to use the
+/// default font. If `size` is greater than zero, it is used as the font size
+/// in pixels. Text that is not valid UTF-8 is ignored.
+///
+/// # Safety
+///
+/// `context` must be a valid context, and `text` must be a valid
+/// null-terminated string.
+#[cfg(feature = "cosmic-text")]
+#[no_mangle]
+pub unsafe extern "C" fn kludgine_draw_text(
+    context: *mut KludgineContext,
+    text: *const c_char,
+    font: KludgineHandle,
+    size: f32,
+    x: f32,
+    y: f32,
+    color: u32,
+) {
+    guarded((), || {
+        let Some(context) = context.as_mut() else {
+            return;
+        };
+        if text.is_null() {
+            return;
+        }
+        let family = context.fonts.get(font).map(|font| font.family.clone());
+        if let Ok(text) = CStr::from_ptr(text).to_str() {
+            context.current_commands().push(Command::Text {
+                text: text.to_string(),
+                origin: Point::new(Px::from(x), Px::from(y)),
+                color: Color(color),
+                family,
+                size: (size > 0.).then(|| Px::from(size)),
+            });
+        }
+    })
+}
+
+/// Renders the current frame.
+///
+/// If `pixels` is not null, the rendered image is copied into it as tightly
+/// packed, 8-bit RGBA data. `pixels_len` must be at least `width * height * 4`
+/// bytes.
+///
+/// Returns false if the graphics device reported an error while rendering, or
+/// if the pixels could not be copied.
+///
+/// # Safety
+///
+/// `context` must be a valid context, and `pixels` must be null or point to
+/// at least `pixels_len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn kludgine_submit(
+    context: *mut KludgineContext,
+    pixels: *mut u8,
+    pixels_len: usize,
+) -> bool {
+    guarded(false, || {
+        let Some(context) = context.as_mut() else {
+            return false;
+        };
+        if context.submit().is_some() {
+            return false;
+        }
+        if pixels.is_null() {
+            true
+        } else {
+            context.read_pixels(std::slice::from_raw_parts_mut(pixels, pixels_len))
+        }
+    })
+}
+
+#[test]
+fn registry_handles() {
+    let mut registry = Registry::default();
+    let first = registry.insert("first");
+    assert_ne!(first, 0);
+    assert_eq!(registry.get(first), Some(&"first"));
+    assert_eq!(registry.remove(first), Some("first"));
+    assert_eq!(registry.get(first), None);
+    assert_eq!(registry.remove(first), None);
+
+    let second = registry.insert("second");
+    assert_ne!(first, second);
+    assert_eq!(registry.get(first), None);
+    assert_eq!(registry.get(second), Some(&"second"));
+    assert_eq!(registry.len(), 1);
+    assert_eq!(registry.get(0), None);
+}
+
+#[test]
+fn rgba_lengths() {
+    assert_eq!(rgba_len(2, 3), Some(24));
+    assert_eq!(rgba_len(0, 3), Some(0));
+    #[cfg(target_pointer_width = "64")]
+    assert_eq!(rgba_len(u32::MAX, u32::MAX), None);
+    assert_eq!(guarded(0, || panic!("caught")), 0);
+}
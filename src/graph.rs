@@ -0,0 +1,765 @@
+use std::fmt::{self, Debug, Display, Formatter};
+
+use figures::units::UPx;
+use figures::Size;
+use intentional::{Assert, Cast};
+
+use crate::{
+    ClipStack, Color, Error, Frame, Graphics, PostEffect, ProtoGraphics, RenderingGraphics,
+    SharedTexture, Texture,
+};
+
+/// A collection of render passes that draw into intermediate textures.
+///
+/// Multi-pass effects, such as bloom, render a scene into a texture and then
+/// process it through several passes before compositing the result. A render
+/// graph declares each pass along with the textures it reads and writes.
+/// When the graph is [executed](Self::execute), its passes are run in
+/// dependency order, and Kludgine allocates the textures the passes render
+/// into.
+///
+/// Textures are allocated from a pool owned by [`Kludgine`](crate::Kludgine)
+/// and reused between frames. Within a frame, a texture that is no longer
+/// going to be read shares its allocation with textures written by later
+/// passes. Textures that no pass reads are the graph's outputs, and can be
+/// drawn once the graph has been executed.
+///
+/// ```rust,no_run
+/// use kludgine::drawing::Drawing;
+/// use kludgine::figures::units::Px;
+/// use kludgine::figures::{IntoSigned, Point, Rect, Size};
+/// use kludgine::graph::{GraphTextureOptions, GraphTextureSize, RenderGraph};
+/// use kludgine::shapes::Shape;
+/// use kludgine::{wgpu, Color, Frame};
+///
+/// fn render_glow(frame: &mut Frame<'_>, device: &wgpu::Device, queue: &wgpu::Queue) {
+///     let mut scene_drawing = Drawing::default();
+///     let mut glow_drawing = Drawing::default();
+///
+///     let mut graph = RenderGraph::new();
+///     let scene = graph.texture("scene", GraphTextureOptions::default());
+///     let glow = graph.texture(
+///         "glow",
+///         GraphTextureOptions::new(GraphTextureSize::Scaled(0.5)),
+///     );
+///     graph.pass("scene", [], [scene], |pass| {
+///         let mut graphics = pass.prepare(scene);
+///         let mut renderer = scene_drawing.new_frame(&mut graphics);
+///         renderer.draw_shape(&Shape::filled_rect(
+///             Rect::new(Point::<Px>::default(), Size::new(Px::new(64), Px::new(64))),
+///             Color::WHITE,
+///         ));
+///         drop(renderer);
+///         drop(graphics);
+///         scene_drawing.render(1., &mut pass.render(scene, Color::CLEAR_BLACK));
+///     });
+///     graph.pass("glow", [scene], [glow], |pass| {
+///         let source = pass.texture(scene).clone();
+///         let size = pass.texture(glow).size();
+///         let mut graphics = pass.prepare(glow);
+///         let mut renderer = glow_drawing.new_frame(&mut graphics);
+///         renderer.draw_texture(&source, Rect::from(size).into_signed(), 0.5);
+///         drop(renderer);
+///         drop(graphics);
+///         glow_drawing.render(1., &mut pass.render(glow, Color::CLEAR_BLACK));
+///     });
+///
+///     let outputs = graph.execute(frame, device, queue).expect("valid graph");
+///     let glow = outputs.get(glow).expect("glow is an output");
+///     // Draw `glow` while rendering the frame.
+/// }
+/// ```
+#[derive(Default)]
+pub struct RenderGraph<'pass> {
+    textures: Vec<TextureNode>,
+    passes: Vec<PassNode<'pass>>,
+}
+
+impl<'pass> RenderGraph<'pass> {
+    /// Returns a new, empty graph.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares a texture named `name` that passes can read or write.
+    pub fn texture(
+        &mut self,
+        name: impl Into<String>,
+        options: GraphTextureOptions,
+    ) -> GraphTexture {
+        self.textures.push(TextureNode {
+            name: name.into(),
+            options,
+        });
+        GraphTexture(self.textures.len() - 1)
+    }
+
+    /// Declares a pass named `name` that reads `inputs` and writes `outputs`.
+    ///
+    /// When the graph is executed, `execute` is invoked after every pass that
+    /// writes one of `inputs` has been executed. Each texture may only be
+    /// written by one pass.
+    pub fn pass(
+        &mut self,
+        name: impl Into<String>,
+        inputs: impl IntoIterator<Item = GraphTexture>,
+        outputs: impl IntoIterator<Item = GraphTexture>,
+        execute: impl FnOnce(&mut PassContext<'_, '_>) + 'pass,
+    ) {
+        self.passes.push(PassNode {
+            name: name.into(),
+            inputs: inputs.into_iter().collect(),
+            outputs: outputs.into_iter().collect(),
+            execute: Box::new(execute),
+        });
+    }
+
+    /// Executes this graph's passes within `frame`, returning the graph's
+    /// outputs.
+    ///
+    /// The passes record their commands into `frame`, which must be
+    /// submitted for the passes to be rendered. This function should be
+    /// invoked before rendering any graphics that draw the graph's outputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the passes cannot be ordered. No passes are
+    /// executed when an error is returned.
+    pub fn execute(
+        self,
+        frame: &mut Frame<'_>,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> Result<GraphOutputs, GraphError> {
+        let schedule = self.schedule(frame.kludgine.size, frame.kludgine.format)?;
+
+        let mut pool = std::mem::take(&mut frame.kludgine.graph_textures);
+        let graphics = ProtoGraphics::new(device, queue, &*frame.kludgine);
+        let slots = pool.allocate(&schedule.slot_keys, &graphics);
+        drop(graphics);
+        pool.retain_multisampled(&schedule.slot_keys, frame.kludgine.multisample.count);
+
+        let textures = schedule
+            .slots
+            .iter()
+            .map(|slot| slot.map(|slot| slots[slot].clone()))
+            .collect::<Vec<_>>();
+        let mut passes = self.passes.into_iter().map(Some).collect::<Vec<_>>();
+        for index in schedule.order {
+            let pass = passes[index].take().assert("each pass is scheduled once");
+            let mut context = PassContext {
+                frame: &mut *frame,
+                device,
+                queue,
+                pool: &mut pool,
+                textures: &textures,
+                inputs: &pass.inputs,
+                outputs: &pass.outputs,
+            };
+            (pass.execute)(&mut context);
+        }
+        frame.kludgine.graph_textures = pool;
+
+        Ok(GraphOutputs {
+            textures: textures
+                .into_iter()
+                .zip(schedule.outputs)
+                .map(|(texture, output)| texture.filter(|_| output))
+                .collect(),
+        })
+    }
+
+    /// Orders the passes and assigns each written texture to an allocation.
+    fn schedule(
+        &self,
+        frame_size: Size<UPx>,
+        format: wgpu::TextureFormat,
+    ) -> Result<Schedule, GraphError> {
+        let mut writers = vec![None; self.textures.len()];
+        for (index, pass) in self.passes.iter().enumerate() {
+            for output in &pass.outputs {
+                if writers[output.0].replace(index).is_some() {
+                    return Err(GraphError::MultipleWriters {
+                        texture: self.textures[output.0].name.clone(),
+                    });
+                }
+            }
+        }
+
+        let mut blocked_by = vec![0_usize; self.passes.len()];
+        let mut dependents = vec![Vec::new(); self.passes.len()];
+        for (index, pass) in self.passes.iter().enumerate() {
+            for input in &pass.inputs {
+                let writer = writers[input.0].ok_or_else(|| GraphError::Unwritten {
+                    texture: self.textures[input.0].name.clone(),
+                    pass: pass.name.clone(),
+                })?;
+                blocked_by[index] += 1;
+                dependents[writer].push(index);
+            }
+        }
+
+        // Passes that are ready to run are executed in the order they were
+        // declared, keeping the order predictable.
+        let mut order = Vec::with_capacity(self.passes.len());
+        let mut scheduled = vec![false; self.passes.len()];
+        while order.len() < self.passes.len() {
+            let Some(next) =
+                (0..self.passes.len()).find(|&index| !scheduled[index] && blocked_by[index] == 0)
+            else {
+                return Err(GraphError::Cycle {
+                    passes: (0..self.passes.len())
+                        .filter(|&index| !scheduled[index])
+                        .map(|index| self.passes[index].name.clone())
+                        .collect(),
+                });
+            };
+            scheduled[next] = true;
+            order.push(next);
+            for &dependent in &dependents[next] {
+                blocked_by[dependent] -= 1;
+            }
+        }
+
+        let mut last_read = vec![None; self.textures.len()];
+        for (position, &index) in order.iter().enumerate() {
+            for input in &self.passes[index].inputs {
+                last_read[input.0] = Some(position);
+            }
+        }
+
+        // Each texture is assigned an allocation when it is first written,
+        // and its allocation is released once its last reader has executed.
+        let mut slots = vec![None; self.textures.len()];
+        let mut slot_keys = Vec::<SlotKey>::new();
+        let mut free = Vec::new();
+        for (position, &index) in order.iter().enumerate() {
+            let pass = &self.passes[index];
+            for output in &pass.outputs {
+                let key = self.textures[output.0].options.key(frame_size, format);
+                let slot = if let Some(free_index) =
+                    free.iter().position(|&slot| slot_keys[slot] == key)
+                {
+                    free.remove(free_index)
+                } else {
+                    slot_keys.push(key);
+                    slot_keys.len() - 1
+                };
+                slots[output.0] = Some(slot);
+            }
+            for input in &pass.inputs {
+                let slot = slots[input.0].assert("inputs are written before being read");
+                if last_read[input.0] == Some(position) && !free.contains(&slot) {
+                    free.push(slot);
+                }
+            }
+        }
+
+        Ok(Schedule {
+            order,
+            outputs: writers
+                .iter()
+                .zip(&last_read)
+                .map(|(writer, last_read)| writer.is_some() && last_read.is_none())
+                .collect(),
+            slots,
+            slot_keys,
+        })
+    }
+}
+
+impl Debug for RenderGraph<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RenderGraph")
+            .field("textures", &self.textures)
+            .field("passes", &self.passes)
+            .finish()
+    }
+}
+
+#[derive(Debug)]
+struct TextureNode {
+    name: String,
+    options: GraphTextureOptions,
+}
+
+struct PassNode<'pass> {
+    name: String,
+    inputs: Vec<GraphTexture>,
+    outputs: Vec<GraphTexture>,
+    execute: Box<dyn FnOnce(&mut PassContext<'_, '_>) + 'pass>,
+}
+
+impl Debug for PassNode<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PassNode")
+            .field("name", &self.name)
+            .field("inputs", &self.inputs)
+            .field("outputs", &self.outputs)
+            .finish_non_exhaustive()
+    }
+}
+
+struct Schedule {
+    order: Vec<usize>,
+    outputs: Vec<bool>,
+    slots: Vec<Option<usize>>,
+    slot_keys: Vec<SlotKey>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+struct SlotKey {
+    size: Size<UPx>,
+    format: wgpu::TextureFormat,
+    filter_mode: wgpu::FilterMode,
+}
+
+/// A texture declared in a [`RenderGraph`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct GraphTexture(usize);
+
+/// The size of a texture in a [`RenderGraph`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphTextureSize {
+    /// The size of the frame being rendered.
+    Frame,
+    /// The size of the frame being rendered multiplied by a factor. This is
+    /// useful for passes that operate on downsampled copies of the scene.
+    Scaled(f32),
+    /// A fixed size.
+    Fixed(Size<UPx>),
+}
+
+impl GraphTextureSize {
+    /// Returns the size of a texture for a frame that is `frame_size` large.
+    ///
+    /// Textures are always at least one pixel wide and tall.
+    #[must_use]
+    pub fn resolve(self, frame_size: Size<UPx>) -> Size<UPx> {
+        let size = match self {
+            GraphTextureSize::Frame => frame_size,
+            GraphTextureSize::Scaled(factor) => {
+                let scale = |length: UPx| {
+                    UPx::new(
+                        (length.get().cast::<f32>() * factor)
+                            .round()
+                            .max(0.)
+                            .cast::<u32>(),
+                    )
+                };
+                Size::new(scale(frame_size.width), scale(frame_size.height))
+            }
+            GraphTextureSize::Fixed(size) => size,
+        };
+        Size::new(size.width.max(UPx::new(1)), size.height.max(UPx::new(1)))
+    }
+}
+
+/// Options for a texture declared in a [`RenderGraph`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GraphTextureOptions {
+    /// The size of the texture. Defaults to [`GraphTextureSize::Frame`].
+    pub size: GraphTextureSize,
+    /// The format of the texture. Defaults to `None`, which uses the format
+    /// Kludgine renders with.
+    ///
+    /// Kludgine can only render into textures that use its format. Textures
+    /// with other formats can be written using the pass's
+    /// [encoder](PassContext::encoder).
+    pub format: Option<wgpu::TextureFormat>,
+    /// The filter mode used when drawing the texture. Defaults to
+    /// [`wgpu::FilterMode::Linear`].
+    pub filter_mode: wgpu::FilterMode,
+}
+
+impl GraphTextureOptions {
+    /// Returns options for a texture of `size` using the default format and
+    /// filter mode.
+    #[must_use]
+    pub const fn new(size: GraphTextureSize) -> Self {
+        Self {
+            size,
+            format: None,
+            filter_mode: wgpu::FilterMode::Linear,
+        }
+    }
+
+    fn key(&self, frame_size: Size<UPx>, format: wgpu::TextureFormat) -> SlotKey {
+        SlotKey {
+            size: self.size.resolve(frame_size),
+            format: self.format.unwrap_or(format),
+            filter_mode: self.filter_mode,
+        }
+    }
+}
+
+impl Default for GraphTextureOptions {
+    fn default() -> Self {
+        Self::new(GraphTextureSize::Frame)
+    }
+}
+
+/// Textures allocated for executing [`RenderGraph`]s, which are reused
+/// between frames.
+#[derive(Debug, Default)]
+pub(crate) struct TexturePool {
+    textures: Vec<(SlotKey, SharedTexture)>,
+    multisampled: Vec<(Size<UPx>, wgpu::TextureFormat, wgpu::TextureView)>,
+}
+
+impl TexturePool {
+    /// Returns a texture for each of `keys`, reusing pooled textures when
+    /// possible. Pooled textures that are not reused are released.
+    fn allocate(&mut self, keys: &[SlotKey], graphics: &ProtoGraphics<'_>) -> Vec<SharedTexture> {
+        let mut available = std::mem::take(&mut self.textures);
+        self.textures = keys
+            .iter()
+            .map(|key| {
+                let texture = if let Some(index) =
+                    available.iter().position(|(existing, _)| existing == key)
+                {
+                    available.swap_remove(index).1
+                } else {
+                    SharedTexture::from(Texture::new_generic(
+                        graphics,
+                        1,
+                        key.size,
+                        key.format,
+                        wgpu::TextureUsages::TEXTURE_BINDING
+                            | wgpu::TextureUsages::RENDER_ATTACHMENT
+                            | wgpu::TextureUsages::COPY_SRC
+                            | wgpu::TextureUsages::COPY_DST,
+                        key.filter_mode,
+                        Some("kludgine::render graph"),
+                    ))
+                };
+                (*key, texture)
+            })
+            .collect();
+        self.textures
+            .iter()
+            .map(|(_, texture)| texture.clone())
+            .collect()
+    }
+
+    fn retain_multisampled(&mut self, keys: &[SlotKey], sample_count: u32) {
+        if sample_count > 1 {
+            self.multisampled.retain(|(size, format, _)| {
+                keys.iter()
+                    .any(|key| key.size == *size && key.format == *format)
+            });
+        } else {
+            self.multisampled.clear();
+        }
+    }
+
+    fn multisampled(
+        &mut self,
+        size: Size<UPx>,
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+        device: &wgpu::Device,
+    ) -> &wgpu::TextureView {
+        let index = if let Some(index) =
+            self.multisampled
+                .iter()
+                .position(|(existing, existing_format, _)| {
+                    *existing == size && *existing_format == format
+                }) {
+            index
+        } else {
+            let view = device
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: Some("kludgine::render graph (multisampled)"),
+                    size: size.into(),
+                    mip_level_count: 1,
+                    sample_count,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                    view_formats: &[],
+                })
+                .create_view(&wgpu::TextureViewDescriptor::default());
+            self.multisampled.push((size, format, view));
+            self.multisampled.len() - 1
+        };
+        &self.multisampled[index].2
+    }
+}
+
+/// The context a [`RenderGraph`] pass is executed with.
+pub struct PassContext<'ctx, 'gfx> {
+    frame: &'ctx mut Frame<'gfx>,
+    device: &'ctx wgpu::Device,
+    queue: &'ctx wgpu::Queue,
+    pool: &'ctx mut TexturePool,
+    textures: &'ctx [Option<SharedTexture>],
+    inputs: &'ctx [GraphTexture],
+    outputs: &'ctx [GraphTexture],
+}
+
+impl PassContext<'_, '_> {
+    /// Returns the texture allocated for `texture`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `texture` is not one of this pass's inputs or outputs.
+    #[must_use]
+    pub fn texture(&self, texture: GraphTexture) -> &SharedTexture {
+        assert!(
+            self.inputs.contains(&texture) || self.outputs.contains(&texture),
+            "texture is not an input or output of this pass"
+        );
+        self.textures[texture.0]
+            .as_ref()
+            .assert("inputs and outputs are allocated")
+    }
+
+    /// Creates a [`Graphics`] context to prepare graphics that will be
+    /// rendered into `output`.
+    ///
+    /// The returned context's clip rect matches the size of `output`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `output` is not one of this pass's outputs.
+    pub fn prepare(&mut self, output: GraphTexture) -> Graphics<'_> {
+        let size = self.output(output).size();
+        let mut graphics = self.frame.prepare(self.device, self.queue);
+        graphics.clip = ClipStack::new(size);
+        graphics
+    }
+
+    /// Creates a [`RenderingGraphics`] that renders into `output` after
+    /// clearing it to `clear_color`.
+    ///
+    /// When the frame is multisampled, the graphics are rendered into a
+    /// multisampled texture that is resolved into `output`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `output` is not one of this pass's outputs.
+    pub fn render(
+        &mut self,
+        output: GraphTexture,
+        clear_color: Color,
+    ) -> RenderingGraphics<'_, '_> {
+        let texture = self.output(output).clone();
+        let size = texture.size();
+        let sample_count = self.frame.kludgine.multisample.count;
+        let multisampled = if sample_count > 1 {
+            Some(
+                self.pool
+                    .multisampled(size, texture.format(), sample_count, self.device),
+            )
+        } else {
+            None
+        };
+        self.frame.render_region(
+            &wgpu::RenderPassDescriptor {
+                label: Some("kludgine::render graph pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: multisampled.unwrap_or(texture.view()),
+                    resolve_target: multisampled.map(|_| texture.view()),
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear_color.into()),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            },
+            size,
+            size.into(),
+            self.device,
+            self.queue,
+        )
+    }
+
+    /// Records passes that apply `effects` to `input`, in order, writing the
+    /// result into `output`.
+    ///
+    /// See [`Frame::apply_post_effects()`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `input` or `output` use a format that post effects
+    /// do not support.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input` is not one of this pass's inputs or if `output` is
+    /// not one of this pass's outputs.
+    pub fn apply_post_effects(
+        &mut self,
+        input: GraphTexture,
+        output: GraphTexture,
+        effects: &[PostEffect],
+    ) -> Result<(), Error> {
+        assert!(
+            self.inputs.contains(&input),
+            "texture is not an input of this pass"
+        );
+        let source = self.texture(input).clone();
+        let destination = self.output(output).clone();
+        self.frame
+            .apply_post_effects(&source, destination.wgpu(), effects, self.device)
+    }
+
+    /// Returns the frame's command encoder, which can be used to record
+    /// commands that Kludgine does not provide, such as render passes using
+    /// custom pipelines.
+    pub fn encoder(&mut self) -> &mut wgpu::CommandEncoder {
+        let device = self.device;
+        self.frame.commands.get_or_insert_with(|| {
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default())
+        })
+    }
+
+    /// Returns the device the graph is being executed with.
+    #[must_use]
+    pub fn device(&self) -> &wgpu::Device {
+        self.device
+    }
+
+    /// Returns the queue the graph is being executed with.
+    #[must_use]
+    pub fn queue(&self) -> &wgpu::Queue {
+        self.queue
+    }
+
+    fn output(&self, output: GraphTexture) -> &SharedTexture {
+        assert!(
+            self.outputs.contains(&output),
+            "texture is not an output of this pass"
+        );
+        self.textures[output.0]
+            .as_ref()
+            .assert("outputs are allocated")
+    }
+}
+
+impl Debug for PassContext<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PassContext")
+            .field("inputs", &self.inputs)
+            .field("outputs", &self.outputs)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The textures written by an executed [`RenderGraph`] that no pass read.
+#[derive(Debug, Clone)]
+pub struct GraphOutputs {
+    textures: Vec<Option<SharedTexture>>,
+}
+
+impl GraphOutputs {
+    /// Returns the texture allocated for `texture`, if it is an output of the
+    /// graph.
+    ///
+    /// The textures are reused the next time a graph is executed, which
+    /// replaces their contents.
+    #[must_use]
+    pub fn get(&self, texture: GraphTexture) -> Option<&SharedTexture> {
+        self.textures.get(texture.0)?.as_ref()
+    }
+}
+
+/// An error ordering the passes of a [`RenderGraph`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum GraphError {
+    /// A pass reads a texture that no pass writes.
+    Unwritten {
+        /// The name of the texture.
+        texture: String,
+        /// The name of the pass reading the texture.
+        pass: String,
+    },
+    /// More than one pass writes the same texture.
+    MultipleWriters {
+        /// The name of the texture.
+        texture: String,
+    },
+    /// The passes depend on each other's outputs, or a pass reads a texture
+    /// that it writes.
+    Cycle {
+        /// The names of the passes that could not be ordered.
+        passes: Vec<String>,
+    },
+}
+
+impl Display for GraphError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::Unwritten { texture, pass } => {
+                write!(f, "pass {pass:?} reads {texture:?}, which no pass writes")
+            }
+            GraphError::MultipleWriters { texture } => {
+                write!(f, "{texture:?} is written by more than one pass")
+            }
+            GraphError::Cycle { passes } => {
+                write!(f, "passes depend on each other: {}", passes.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
+
+#[test]
+fn graph_scheduling() {
+    let format = wgpu::TextureFormat::Rgba8UnormSrgb;
+    let frame = Size::new(UPx::new(800), UPx::new(600));
+    let half = GraphTextureOptions::new(GraphTextureSize::Scaled(0.5));
+
+    let mut graph = RenderGraph::new();
+    let scene = graph.texture("scene", GraphTextureOptions::default());
+    let bright = graph.texture("bright", half);
+    let blur_h = graph.texture("blur_h", half);
+    let blur_v = graph.texture("blur_v", half);
+    let output = graph.texture("output", GraphTextureOptions::default());
+    graph.pass("composite", [scene, blur_v], [output], |_| {});
+    graph.pass("blur_v", [blur_h], [blur_v], |_| {});
+    graph.pass("scene", [], [scene], |_| {});
+    graph.pass("threshold", [scene], [bright], |_| {});
+    graph.pass("blur_h", [bright], [blur_h], |_| {});
+
+    let schedule = graph.schedule(frame, format).unwrap();
+    assert_eq!(schedule.order, [2, 3, 4, 1, 0]);
+    assert_eq!(schedule.outputs, [false, false, false, false, true]);
+    // `bright` is released after `blur_h` reads it, so `blur_v` reuses it.
+    assert_eq!(
+        schedule.slots,
+        [Some(0), Some(1), Some(2), Some(1), Some(3)]
+    );
+    assert_eq!(
+        schedule.slot_keys[1].size,
+        Size::new(UPx::new(400), UPx::new(300))
+    );
+
+    let mut graph = RenderGraph::new();
+    let a = graph.texture("a", GraphTextureOptions::default());
+    let b = graph.texture("b", GraphTextureOptions::default());
+    graph.pass("first", [b], [a], |_| {});
+    graph.pass("second", [a], [b], |_| {});
+    assert_eq!(
+        graph.schedule(frame, format).unwrap_err(),
+        GraphError::Cycle {
+            passes: vec![String::from("first"), String::from("second")]
+        }
+    );
+
+    let mut graph = RenderGraph::new();
+    let a = graph.texture("a", GraphTextureOptions::default());
+    graph.pass("reader", [a], [], |_| {});
+    assert_eq!(
+        graph.schedule(frame, format).unwrap_err(),
+        GraphError::Unwritten {
+            texture: String::from("a"),
+            pass: String::from("reader")
+        }
+    );
+}
@@ -0,0 +1,417 @@
+//! A small set of immediate-mode widgets for debug tools and options screens.
+//!
+//! This module is not a user interface framework. It offers just enough to
+//! build in-game menus without depending on a separate GUI crate: buttons,
+//! checkboxes, sliders, and labels drawn with a shared [`GuiTheme`], which can
+//! be created from a [`Palette`](crate::theme::Palette).
+//!
+//! Widgets are drawn and interacted with in a single function call. Each
+//! frame, update the [`Gui`] with the current input state, and then call
+//! widget functions on the [`GuiFrame`] returned from [`Gui::frame()`]:
+//!
+//! ```rust,no_run
+//! use kludgine::figures::units::Px;
+//! use kludgine::figures::{Point, Rect, Size};
+//! use kludgine::gui::Gui;
+//!
+//! let mut gui = Gui::default();
+//! let mut volume = 0.5;
+//! let mut fullscreen = false;
+//! kludgine::app::run(move |mut renderer, window| {
+//!     gui.update_from_window(&window);
+//!     let mut ui = gui.frame(&mut renderer);
+//!     let row = Size::new(Px::new(200), Px::new(32));
+//!     if ui.button("Play", Rect::new(Point::new(Px::new(16), Px::new(16)), row)) {
+//!         // Start the game.
+//!     }
+//!     ui.checkbox(
+//!         "Fullscreen",
+//!         &mut fullscreen,
+//!         Rect::new(Point::new(Px::new(16), Px::new(56)), row),
+//!     );
+//!     ui.slider(
+//!         &mut volume,
+//!         0.0..=1.0,
+//!         Rect::new(Point::new(Px::new(16), Px::new(96)), row),
+//!     );
+//!     true
+//! })
+//! .unwrap();
+//! ```
+//!
+//! Widgets are identified by the order they are called in each frame. A
+//! widget that appears or disappears while another widget is being dragged
+//! can cause the interaction to transfer to a different widget.
+
+use std::ops::RangeInclusive;
+
+use figures::units::Px;
+use figures::{FloatConversion, Point, Rect, Size, Zero};
+
+use crate::drawing::Renderer;
+use crate::shapes::{Shape, StrokeOptions};
+use crate::text::{Text, TextOrigin};
+use crate::theme::{Palette, ThemeMode};
+use crate::{Color, DrawableExt};
+
+/// The colors and metrics used to draw widgets.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GuiTheme {
+    /// The background color of idle widgets.
+    pub background: Color,
+    /// The background color of widgets beneath the cursor.
+    pub hovered: Color,
+    /// The background color of widgets being pressed or dragged.
+    pub active: Color,
+    /// The color used for a checkbox's check mark and a slider's filled
+    /// track.
+    pub accent: Color,
+    /// The color of widget outlines.
+    pub outline: Color,
+    /// The color of text.
+    pub text: Color,
+    /// The radius of widget corners.
+    pub corner_radius: Px,
+    /// The space between a widget's outline and its contents.
+    pub padding: Px,
+}
+
+impl Default for GuiTheme {
+    fn default() -> Self {
+        Self::from_palette(&Palette::default(), ThemeMode::default())
+    }
+}
+
+impl GuiTheme {
+    /// Returns a theme using the colors of `palette` for `mode`.
+    ///
+    /// Hovered and active colors are derived from the palette's surface color.
+    #[must_use]
+    pub fn from_palette(palette: &Palette, mode: ThemeMode) -> Self {
+        Self {
+            background: palette.surface.get(mode),
+            hovered: palette.surface.emphasize(0.06).get(mode),
+            active: palette.surface.emphasize(-0.06).get(mode),
+            accent: palette.primary.get(mode),
+            outline: palette.outline.get(mode),
+            text: palette.text.get(mode),
+            corner_radius: Px::new(4),
+            padding: Px::new(6),
+        }
+    }
+
+    /// Returns the background color for a widget in `state`.
+    #[must_use]
+    pub const fn background_for(&self, state: WidgetState) -> Color {
+        match state {
+            WidgetState::Idle => self.background,
+            WidgetState::Hovered => self.hovered,
+            WidgetState::Active => self.active,
+        }
+    }
+}
+
+/// The interaction state of a widget.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum WidgetState {
+    /// The widget is not being interacted with.
+    Idle,
+    /// The cursor is above the widget.
+    Hovered,
+    /// The widget is being pressed or dragged.
+    Active,
+}
+
+/// The persistent state of an immediate-mode user interface.
+#[derive(Debug, Default)]
+pub struct Gui {
+    /// The theme used to draw widgets.
+    pub theme: GuiTheme,
+    cursor: Option<Point<Px>>,
+    mouse_down: bool,
+    pressed: bool,
+    released: bool,
+    active: Option<u32>,
+}
+
+impl Gui {
+    /// Returns a new user interface drawn using `theme`.
+    #[must_use]
+    pub fn new(theme: GuiTheme) -> Self {
+        Self {
+            theme,
+            ..Self::default()
+        }
+    }
+
+    /// Updates the input state of this interface.
+    ///
+    /// `cursor` is the cursor's position within the window, and `mouse_down`
+    /// is whether the primary mouse button is currently held. This function
+    /// should be called once per frame before calling [`Gui::frame()`].
+    pub fn update(&mut self, cursor: Option<Point<Px>>, mouse_down: bool) {
+        self.cursor = cursor;
+        self.pressed = mouse_down && !self.mouse_down;
+        self.released = !mouse_down && self.mouse_down;
+        self.mouse_down = mouse_down;
+    }
+
+    /// Updates the input state of this interface using the cursor position and
+    /// left mouse button state of `window`.
+    #[cfg(feature = "app")]
+    pub fn update_from_window<WindowEvent>(&mut self, window: &crate::app::Window<'_, WindowEvent>)
+    where
+        WindowEvent: Send + 'static,
+    {
+        self.update(
+            window.cursor_position(),
+            window.mouse_button_pressed(appit::winit::event::MouseButton::Left),
+        );
+    }
+
+    /// Returns true if a widget is currently being pressed or dragged.
+    ///
+    /// This can be used to avoid passing mouse input to the game while it is
+    /// being handled by the interface.
+    #[must_use]
+    pub const fn is_interacting(&self) -> bool {
+        self.active.is_some()
+    }
+
+    /// Begins drawing widgets using `renderer`.
+    pub fn frame<'gui, 'render, 'ctx, 'gfx>(
+        &'gui mut self,
+        renderer: &'render mut Renderer<'ctx, 'gfx>,
+    ) -> GuiFrame<'gui, 'render, 'ctx, 'gfx> {
+        GuiFrame {
+            gui: self,
+            renderer,
+            next_id: 0,
+        }
+    }
+}
+
+/// Draws widgets for a single frame.
+///
+/// When dropped, mouse button transitions for the frame are consumed.
+pub struct GuiFrame<'gui, 'render, 'ctx, 'gfx> {
+    gui: &'gui mut Gui,
+    renderer: &'render mut Renderer<'ctx, 'gfx>,
+    next_id: u32,
+}
+
+impl<'ctx, 'gfx> GuiFrame<'_, '_, 'ctx, 'gfx> {
+    /// Returns the theme used to draw widgets.
+    #[must_use]
+    pub const fn theme(&self) -> &GuiTheme {
+        &self.gui.theme
+    }
+
+    /// Returns the renderer widgets are being drawn with.
+    pub fn renderer(&mut self) -> &mut Renderer<'ctx, 'gfx> {
+        self.renderer
+    }
+
+    /// Updates the interaction state of the next widget occupying `area`.
+    ///
+    /// Returns the widget's state and whether it was clicked this frame.
+    fn interact(&mut self, area: Rect<Px>) -> (WidgetState, bool) {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let hovered = self
+            .gui
+            .cursor
+            .map_or(false, |cursor| contains(area, cursor));
+        if hovered && self.gui.pressed && self.gui.active.is_none() {
+            self.gui.active = Some(id);
+        }
+        let is_active = self.gui.active == Some(id);
+        let clicked = is_active && hovered && self.gui.released;
+        let state = if is_active && self.gui.mouse_down {
+            WidgetState::Active
+        } else if hovered && (self.gui.active.is_none() || is_active) {
+            WidgetState::Hovered
+        } else {
+            WidgetState::Idle
+        };
+        (state, clicked)
+    }
+
+    fn draw_panel(&mut self, area: Rect<Px>, fill: Color) {
+        let theme = self.gui.theme;
+        self.renderer
+            .draw_shape(&Shape::filled_round_rect(area, theme.corner_radius, fill));
+        self.renderer.draw_shape(&Shape::stroked_round_rect(
+            area,
+            theme.corner_radius,
+            StrokeOptions::px_wide(1).colored(theme.outline),
+        ));
+    }
+
+    /// Draws `text` with its top-left corner at `origin`.
+    pub fn label(&mut self, text: &str, origin: Point<Px>) {
+        let color = self.gui.theme.text;
+        self.renderer
+            .draw_text(Text::new(text, color).translate_by(origin));
+    }
+
+    /// Draws a button containing `label` and returns true if it was clicked.
+    ///
+    /// A click is reported when the mouse button is released over the button
+    /// after being pressed over it.
+    pub fn button(&mut self, label: &str, area: Rect<Px>) -> bool {
+        let (state, clicked) = self.interact(area);
+        self.draw_panel(area, self.gui.theme.background_for(state));
+        let color = self.gui.theme.text;
+        self.renderer.draw_text(
+            Text::new(label, color)
+                .origin(TextOrigin::Center)
+                .translate_by(center(area)),
+        );
+        clicked
+    }
+
+    /// Draws a checkbox followed by `label`, toggling `checked` when clicked.
+    ///
+    /// The box is a square as tall as `area`, and the entire area responds to
+    /// clicks. Returns true if `checked` was changed.
+    pub fn checkbox(&mut self, label: &str, checked: &mut bool, area: Rect<Px>) -> bool {
+        let (state, clicked) = self.interact(area);
+        if clicked {
+            *checked = !*checked;
+        }
+
+        let theme = self.gui.theme;
+        let box_area = Rect::new(area.origin, Size::squared(area.size.height));
+        self.draw_panel(box_area, theme.background_for(state));
+        if *checked {
+            let inset = theme.padding.min(box_area.size.width / 4);
+            self.renderer.draw_shape(&Shape::filled_round_rect(
+                Rect::new(
+                    box_area.origin + Point::squared(inset),
+                    box_area.size - Size::squared(inset * 2),
+                ),
+                theme.corner_radius / 2,
+                theme.accent,
+            ));
+        }
+        self.renderer.draw_text(
+            Text::new(label, theme.text)
+                .origin(TextOrigin::Custom(Point::new(
+                    Px::ZERO,
+                    area.size.height / 2,
+                )))
+                .translate_by(Point::new(
+                    area.origin.x + box_area.size.width + theme.padding,
+                    area.origin.y + area.size.height / 2,
+                )),
+        );
+        clicked
+    }
+
+    /// Draws a horizontal slider for `value`, which is kept within `range`.
+    ///
+    /// Pressing anywhere on the slider and dragging updates `value`. Returns
+    /// true if `value` was changed.
+    pub fn slider(&mut self, value: &mut f32, range: RangeInclusive<f32>, area: Rect<Px>) -> bool {
+        let (state, _) = self.interact(area);
+        let (min, max) = (*range.start(), *range.end());
+        let theme = self.gui.theme;
+        let knob_radius = area.size.height / 2;
+        let track_start = area.origin.x + knob_radius;
+        let track_width = (area.size.width - knob_radius * 2).max(Px::new(1));
+
+        let mut changed = false;
+        if state == WidgetState::Active {
+            if let Some(cursor) = self.gui.cursor {
+                let fraction = ((cursor.x - track_start).into_float() / track_width.into_float())
+                    .clamp(0., 1.);
+                let new_value = min + (max - min) * fraction;
+                if (new_value - *value).abs() > f32::EPSILON {
+                    *value = new_value;
+                    changed = true;
+                }
+            }
+        }
+        *value = value.clamp(min.min(max), max.max(min));
+
+        let fraction = if (max - min).abs() > f32::EPSILON {
+            (*value - min) / (max - min)
+        } else {
+            0.
+        };
+        let track_height = (area.size.height / 3).max(Px::new(2));
+        let track = Rect::new(
+            Point::new(
+                area.origin.x,
+                area.origin.y + (area.size.height - track_height) / 2,
+            ),
+            Size::new(area.size.width, track_height),
+        );
+        self.draw_panel(track, theme.background);
+        let knob_x = track_start + track_width * fraction;
+        self.renderer.draw_shape(&Shape::filled_rect(
+            Rect::new(
+                track.origin,
+                Size::new(knob_x - area.origin.x, track_height),
+            ),
+            theme.accent,
+        ));
+        let knob = Rect::new(
+            Point::new(knob_x - knob_radius, area.origin.y),
+            Size::squared(area.size.height),
+        );
+        self.renderer.draw_shape(&Shape::filled_round_rect(
+            knob,
+            knob_radius,
+            theme.background_for(state),
+        ));
+        self.renderer.draw_shape(&Shape::stroked_round_rect(
+            knob,
+            knob_radius,
+            StrokeOptions::px_wide(1).colored(theme.outline),
+        ));
+        changed
+    }
+}
+
+impl Drop for GuiFrame<'_, '_, '_, '_> {
+    fn drop(&mut self) {
+        self.gui.pressed = false;
+        self.gui.released = false;
+        if !self.gui.mouse_down {
+            self.gui.active = None;
+        }
+    }
+}
+
+fn contains(area: Rect<Px>, point: Point<Px>) -> bool {
+    let (top_left, bottom_right) = area.extents();
+    point.x >= top_left.x
+        && point.y >= top_left.y
+        && point.x < bottom_right.x
+        && point.y < bottom_right.y
+}
+
+fn center(area: Rect<Px>) -> Point<Px> {
+    area.origin + Point::new(area.size.width / 2, area.size.height / 2)
+}
+
+#[test]
+fn gui_input_edges() {
+    let mut gui = Gui::default();
+    gui.update(Some(Point::new(Px::new(5), Px::new(5))), true);
+    assert!(gui.pressed && !gui.released);
+    gui.update(Some(Point::new(Px::new(5), Px::new(5))), true);
+    assert!(!gui.pressed && !gui.released);
+    gui.update(None, false);
+    assert!(!gui.pressed && gui.released);
+
+    let area = Rect::new(
+        Point::new(Px::new(0), Px::new(0)),
+        Size::squared(Px::new(10)),
+    );
+    assert!(contains(area, Point::new(Px::new(9), Px::new(0))));
+    assert!(!contains(area, Point::new(Px::new(10), Px::new(0))));
+}
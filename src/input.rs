@@ -0,0 +1,478 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+
+use ahash::{AHashMap, AHashSet};
+use appit::winit::event::{ElementState, Modifiers, MouseButton};
+use appit::winit::keyboard::{KeyCode, PhysicalKey};
+use figures::units::{Lp, Px};
+use figures::Point;
+
+use crate::app::KeyEvent;
+use crate::convert::DisplayScale;
+
+/// A button that can be bound to an action.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Input {
+    /// A key, identified by its physical location on the keyboard.
+    ///
+    /// Physical keys are unaffected by the keyboard layout, which keeps
+    /// bindings such as WASD in the same place on every keyboard.
+    Key(KeyCode),
+    /// A mouse button.
+    Mouse(MouseButton),
+    /// A gamepad button, identified by a number chosen by the application.
+    ///
+    /// Kludgine does not read gamepads. Applications using a gamepad library
+    /// report changes to these buttons using [`ActionMap::set_pressed()`].
+    Gamepad(u32),
+}
+
+impl From<KeyCode> for Input {
+    fn from(key: KeyCode) -> Self {
+        Self::Key(key)
+    }
+}
+
+impl From<MouseButton> for Input {
+    fn from(button: MouseButton) -> Self {
+        Self::Mouse(button)
+    }
+}
+
+/// One or more inputs that activate an action while they are all held.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Binding(Vec<Input>);
+
+impl Binding {
+    /// Returns a binding that is active while `input` is held.
+    #[must_use]
+    pub fn new(input: impl Into<Input>) -> Self {
+        Self(vec![input.into()])
+    }
+
+    /// Returns a binding that is active while every input in `inputs` is
+    /// held, such as [`KeyCode::ControlLeft`] and [`KeyCode::KeyS`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `inputs` is empty.
+    #[must_use]
+    pub fn chord(inputs: impl IntoIterator<Item = Input>) -> Self {
+        let mut inputs = inputs.into_iter().collect::<Vec<_>>();
+        assert!(!inputs.is_empty(), "chords must contain at least one input");
+        inputs.dedup();
+        Self(inputs)
+    }
+
+    /// Returns the inputs that must be held for this binding to be active.
+    #[must_use]
+    pub fn inputs(&self) -> &[Input] {
+        &self.0
+    }
+
+    fn is_active(&self, held: &AHashSet<Input>) -> bool {
+        self.0.iter().all(|input| held.contains(input))
+    }
+}
+
+impl From<Input> for Binding {
+    fn from(input: Input) -> Self {
+        Self::new(input)
+    }
+}
+
+impl From<KeyCode> for Binding {
+    fn from(key: KeyCode) -> Self {
+        Self::new(key)
+    }
+}
+
+impl From<MouseButton> for Binding {
+    fn from(button: MouseButton) -> Self {
+        Self::new(button)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct ActionState {
+    bindings: Vec<Binding>,
+    pressed: bool,
+    just_pressed: bool,
+    just_released: bool,
+}
+
+/// Translates keyboard, mouse, and gamepad input into actions chosen by the
+/// application, such as "Jump" or "Fire".
+///
+/// Each action can have any number of [`Binding`]s, and is pressed while any
+/// of its bindings are active. Input is reported from a window's event
+/// handlers:
+///
+/// - [`WindowBehavior::keyboard_input`](crate::app::WindowBehavior::keyboard_input):
+///   [`ActionMap::handle_keyboard_input()`]
+/// - [`WindowBehavior::mouse_input`](crate::app::WindowBehavior::mouse_input):
+///   [`ActionMap::handle_mouse_input()`]
+/// - [`WindowBehavior::focus_changed`](crate::app::WindowBehavior::focus_changed):
+///   [`ActionMap::release_all()`] when focus is lost, as releases are not
+///   reported to unfocused windows.
+///
+/// Actions are then queried while preparing each frame, after which
+/// [`ActionMap::end_frame()`] must be called to reset
+/// [`just_pressed()`](Self::just_pressed) and
+/// [`just_released()`](Self::just_released).
+#[derive(Debug, Clone)]
+pub struct ActionMap<Action> {
+    actions: AHashMap<Action, ActionState>,
+    held: AHashSet<Input>,
+    just_pressed: Vec<Input>,
+}
+
+impl<Action> ActionMap<Action>
+where
+    Action: Hash + Eq,
+{
+    /// Returns a new map with no actions.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            actions: AHashMap::new(),
+            held: AHashSet::new(),
+            just_pressed: Vec::new(),
+        }
+    }
+
+    /// Adds `binding` to `action`'s bindings.
+    pub fn bind(&mut self, action: Action, binding: impl Into<Binding>) {
+        let state = self.actions.entry(action).or_default();
+        state.bindings.push(binding.into());
+        Self::update_action(state, &self.held);
+    }
+
+    /// Replaces all of `action`'s bindings with `bindings`.
+    pub fn rebind(&mut self, action: Action, bindings: impl IntoIterator<Item = Binding>) {
+        let state = self.actions.entry(action).or_default();
+        state.bindings = bindings.into_iter().collect();
+        Self::update_action(state, &self.held);
+    }
+
+    /// Removes all of `action`'s bindings.
+    pub fn unbind<Q>(&mut self, action: &Q)
+    where
+        Action: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if let Some(state) = self.actions.get_mut(action) {
+            state.bindings.clear();
+            Self::update_action(state, &self.held);
+        }
+    }
+
+    /// Returns the bindings of `action`.
+    #[must_use]
+    pub fn bindings<Q>(&self, action: &Q) -> &[Binding]
+    where
+        Action: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.actions
+            .get(action)
+            .map(|state| state.bindings.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Updates the state of the key in `event`, returning true if the key is
+    /// bound to an action.
+    ///
+    /// Key repeats are ignored.
+    pub fn handle_keyboard_input(&mut self, event: &KeyEvent) -> bool {
+        match event.physical_key {
+            PhysicalKey::Code(key) => self.set_pressed(Input::Key(key), event.state.is_pressed()),
+            PhysicalKey::Unidentified(_) => false,
+        }
+    }
+
+    /// Updates the state of `button`, returning true if the button is bound to
+    /// an action.
+    pub fn handle_mouse_input(&mut self, state: ElementState, button: MouseButton) -> bool {
+        self.set_pressed(Input::Mouse(button), state.is_pressed())
+    }
+
+    /// Sets whether `input` is held, returning true if the input is bound to
+    /// an action.
+    pub fn set_pressed(&mut self, input: Input, pressed: bool) -> bool {
+        let changed = if pressed {
+            self.held.insert(input)
+        } else {
+            self.held.remove(&input)
+        };
+        if changed && pressed {
+            self.just_pressed.push(input);
+        }
+
+        let mut bound = false;
+        for state in self.actions.values_mut() {
+            if state
+                .bindings
+                .iter()
+                .any(|binding| binding.0.contains(&input))
+            {
+                bound = true;
+                if changed {
+                    Self::update_action(state, &self.held);
+                }
+            }
+        }
+        bound
+    }
+
+    /// Releases every held input.
+    pub fn release_all(&mut self) {
+        self.held.clear();
+        for state in self.actions.values_mut() {
+            Self::update_action(state, &self.held);
+        }
+    }
+
+    /// Returns true if `action` is currently pressed.
+    #[must_use]
+    pub fn is_pressed<Q>(&self, action: &Q) -> bool
+    where
+        Action: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.actions.get(action).is_some_and(|state| state.pressed)
+    }
+
+    /// Returns true if `action` was pressed since the last call to
+    /// [`end_frame()`](Self::end_frame).
+    ///
+    /// This returns true even if the action has already been released again.
+    #[must_use]
+    pub fn just_pressed<Q>(&self, action: &Q) -> bool
+    where
+        Action: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.actions
+            .get(action)
+            .is_some_and(|state| state.just_pressed)
+    }
+
+    /// Returns true if `action` was released since the last call to
+    /// [`end_frame()`](Self::end_frame).
+    #[must_use]
+    pub fn just_released<Q>(&self, action: &Q) -> bool
+    where
+        Action: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.actions
+            .get(action)
+            .is_some_and(|state| state.just_released)
+    }
+
+    /// Returns the inputs that were pressed since the last call to
+    /// [`end_frame()`](Self::end_frame), in the order they were pressed.
+    ///
+    /// This includes inputs that are not bound to any action, which makes it
+    /// useful for prompting players to choose a new binding.
+    #[must_use]
+    pub fn just_pressed_inputs(&self) -> &[Input] {
+        &self.just_pressed
+    }
+
+    /// Returns true if `input` is currently held.
+    #[must_use]
+    pub fn is_held(&self, input: Input) -> bool {
+        self.held.contains(&input)
+    }
+
+    /// Resets which actions and inputs were just pressed or released. This
+    /// should be called once each frame after actions have been queried.
+    pub fn end_frame(&mut self) {
+        self.just_pressed.clear();
+        for state in self.actions.values_mut() {
+            state.just_pressed = false;
+            state.just_released = false;
+        }
+    }
+
+    fn update_action(state: &mut ActionState, held: &AHashSet<Input>) {
+        let pressed = state.bindings.iter().any(|binding| binding.is_active(held));
+        if pressed != state.pressed {
+            state.pressed = pressed;
+            if pressed {
+                state.just_pressed = true;
+            } else {
+                state.just_released = true;
+            }
+        }
+    }
+}
+
+impl<Action> Default for ActionMap<Action>
+where
+    Action: Hash + Eq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The keyboard state of a window.
+///
+/// Windows keep this state up to date as keyboard events are received, and
+/// provide it through [`Window::keyboard()`](crate::app::Window::keyboard).
+/// All keys are released when the window loses focus.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyboardState {
+    keys: AHashSet<PhysicalKey>,
+    modifiers: Modifiers,
+}
+
+impl KeyboardState {
+    /// Returns true if `key` is currently held.
+    #[must_use]
+    pub fn is_pressed(&self, key: impl Into<PhysicalKey>) -> bool {
+        self.keys.contains(&key.into())
+    }
+
+    /// Returns an iterator over the keys that are currently held, in no
+    /// particular order.
+    pub fn pressed_keys(&self) -> impl Iterator<Item = PhysicalKey> + '_ {
+        self.keys.iter().copied()
+    }
+
+    /// Returns the currently active modifiers.
+    #[must_use]
+    pub const fn modifiers(&self) -> Modifiers {
+        self.modifiers
+    }
+
+    pub(crate) fn set_pressed(&mut self, key: PhysicalKey, pressed: bool) {
+        if pressed {
+            self.keys.insert(key);
+        } else {
+            self.keys.remove(&key);
+        }
+    }
+
+    pub(crate) fn set_modifiers(&mut self, modifiers: Modifiers) {
+        self.modifiers = modifiers;
+    }
+
+    pub(crate) fn release_all(&mut self) {
+        self.keys.clear();
+        self.modifiers = Modifiers::default();
+    }
+}
+
+/// The mouse state of a window.
+///
+/// Windows keep this state up to date as mouse events are received, and
+/// provide it through [`Window::mouse()`](crate::app::Window::mouse).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MouseState {
+    position: Option<Point<Px>>,
+    buttons: AHashSet<MouseButton>,
+}
+
+impl MouseState {
+    /// Returns the position of the cursor within the window, or `None` if the
+    /// cursor is not above the window.
+    #[must_use]
+    pub const fn position(&self) -> Option<Point<Px>> {
+        self.position
+    }
+
+    /// Returns the [position](Self::position) of the cursor converted into
+    /// logical units using `scale`, which should be
+    /// [`Kludgine::display_scale()`](crate::Kludgine::display_scale).
+    #[must_use]
+    pub fn logical_position(&self, scale: DisplayScale) -> Option<Point<Lp>> {
+        self.position.map(|position| scale.to_logical(position))
+    }
+
+    /// Returns true if `button` is currently held.
+    #[must_use]
+    pub fn is_pressed(&self, button: MouseButton) -> bool {
+        self.buttons.contains(&button)
+    }
+
+    /// Returns an iterator over the buttons that are currently held, in no
+    /// particular order.
+    pub fn pressed_buttons(&self) -> impl Iterator<Item = MouseButton> + '_ {
+        self.buttons.iter().copied()
+    }
+
+    pub(crate) fn set_position(&mut self, position: Option<Point<Px>>) {
+        self.position = position;
+    }
+
+    pub(crate) fn set_pressed(&mut self, button: MouseButton, pressed: bool) {
+        if pressed {
+            self.buttons.insert(button);
+        } else {
+            self.buttons.remove(&button);
+        }
+    }
+
+    pub(crate) fn release_all(&mut self) {
+        self.buttons.clear();
+    }
+}
+
+#[test]
+fn action_map() {
+    let mut actions = ActionMap::new();
+    actions.bind("jump", KeyCode::Space);
+    actions.bind("jump", Input::Gamepad(0));
+    actions.bind(
+        "save",
+        Binding::chord([Input::Key(KeyCode::ControlLeft), Input::Key(KeyCode::KeyS)]),
+    );
+
+    assert!(actions.set_pressed(Input::Key(KeyCode::Space), true));
+    assert!(actions.is_pressed("jump"));
+    assert!(actions.just_pressed("jump"));
+    actions.end_frame();
+    assert!(actions.is_pressed("jump"));
+    assert!(!actions.just_pressed("jump"));
+
+    // The action stays pressed while any binding is active.
+    actions.set_pressed(Input::Gamepad(0), true);
+    actions.set_pressed(Input::Key(KeyCode::Space), false);
+    assert!(actions.is_pressed("jump"));
+    actions.set_pressed(Input::Gamepad(0), false);
+    assert!(actions.just_released("jump"));
+    actions.end_frame();
+
+    // Chords require every input.
+    assert!(actions.set_pressed(Input::Key(KeyCode::KeyS), true));
+    assert!(!actions.is_pressed("save"));
+    actions.set_pressed(Input::Key(KeyCode::ControlLeft), true);
+    assert!(actions.just_pressed("save"));
+    assert_eq!(
+        actions.just_pressed_inputs(),
+        &[Input::Key(KeyCode::KeyS), Input::Key(KeyCode::ControlLeft)]
+    );
+    actions.release_all();
+    assert!(actions.just_released("save"));
+
+    // Rebinding applies to inputs that are already held.
+    assert!(!actions.set_pressed(Input::Key(KeyCode::KeyW), true));
+    actions.rebind("jump", [Binding::new(KeyCode::KeyW)]);
+    assert!(actions.is_pressed("jump"));
+    assert_eq!(actions.bindings("jump"), &[Binding::new(KeyCode::KeyW)]);
+}
+
+#[test]
+fn logical_mouse_position() {
+    let mut mouse = MouseState::default();
+    assert_eq!(mouse.logical_position(DisplayScale::new(2.)), None);
+    mouse.set_position(Some(Point::new(Px::new(40), Px::new(-20))));
+    assert_eq!(
+        mouse.logical_position(DisplayScale::new(2.)),
+        Some(DisplayScale::new(1.).to_logical(Point::new(Px::new(20), Px::new(-10))))
+    );
+}
@@ -11,6 +11,7 @@ use std::fmt::{self, Debug, Formatter};
 use std::hash::{self, BuildHasher, Hash};
 use std::mem::size_of;
 use std::ops::{Add, AddAssign, Deref, DerefMut, Div, Neg};
+use std::str::FromStr;
 use std::sync::atomic::{self, AtomicU64};
 use std::sync::{Arc, Mutex, Weak};
 
@@ -18,29 +19,50 @@ use ahash::{AHashMap, AHasher};
 use bytemuck::{Pod, Zeroable};
 #[cfg(feature = "cosmic-text")]
 pub use cosmic_text;
-use figures::units::UPx;
-use figures::{Angle, Fraction, FromComponents, Point, Rect, Size, UPx2D};
+use figures::units::{Px, UPx};
+use figures::{
+    Angle, FloatConversion, Fraction, FromComponents, Point, Rect, ScreenScale, ScreenUnit, Size,
+    UPx2D, UnscaledUnit,
+};
 #[cfg(feature = "image")]
 pub use image;
 use intentional::{Assert, Cast};
+pub use palette;
 use pipeline::PushConstants;
 use sealed::ShapeSource as _;
 use wgpu::util::DeviceExt;
 pub use {figures, wgpu};
 
-use crate::pipeline::{Uniforms, Vertex};
+use crate::pipeline::{ArrayTexture, RotatedClip, Uniforms, Vertex};
 use crate::sealed::{ClipRect, TextureSource as _};
 use crate::text::Text;
 
 /// Application and Windowing Support.
 #[cfg(feature = "app")]
 pub mod app;
+#[cfg(feature = "assets")]
+pub mod assets;
 mod atlas;
 mod buffer;
+pub mod convert;
 /// An easy-to-use batching renderer.
 pub mod drawing;
+/// Rendering within applications that manage their own `wgpu` device.
+pub mod embedded;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+/// Rendering multi-pass effects using intermediate textures.
+pub mod graph;
+#[cfg(feature = "gui")]
+pub mod gui;
+/// Translating keyboard, mouse, and gamepad input into actions.
+#[cfg(feature = "app")]
+pub mod input;
+mod lookup;
 mod pipeline;
 mod pod;
+mod post;
+pub mod prelude_v1;
 mod sealed;
 /// Types for drawing paths and shapes.
 pub mod shapes;
@@ -49,11 +71,21 @@ pub mod sprite;
 /// Types for text rendering.
 #[cfg(feature = "cosmic-text")]
 pub mod text;
+/// Shared colors for user interfaces that adapt to light and dark themes.
+pub mod theme;
+/// Heuristics for choosing default settings based on device capabilities.
+pub mod tier;
 pub mod tilemap;
+/// Types for animating values over time.
+pub mod tween;
+mod upload;
 
-pub use atlas::{CollectedTexture, TextureCollection};
+pub use atlas::{AtlasGrowth, AtlasOptions, CollectedTexture, TextureCollection};
 use buffer::Buffer;
+pub use lookup::{ColorLookup, ColorLookupKind};
 pub use pipeline::{PreparedGraphic, ShaderScalable};
+pub use post::PostEffect;
+pub use upload::TextureLoadState;
 
 /// A 2d graphics instance.
 ///
@@ -78,24 +110,61 @@ pub struct Kludgine {
     id: KludgineId,
     format: wgpu::TextureFormat,
     multisample: wgpu::MultisampleState,
+    shading: pipeline::Shading,
     default_bindings: wgpu::BindGroup,
+    lookup_layout: wgpu::BindGroupLayout,
+    default_lookup: wgpu::BindGroup,
     pipeline: wgpu::RenderPipeline,
-    _shader: wgpu::ShaderModule,
+    depth_pipeline: wgpu::RenderPipeline,
+    pipeline_layout: wgpu::PipelineLayout,
+    shader: wgpu::ShaderModule,
+    texture_arrays: Option<Arc<pipeline::TextureArrays>>,
+    uploads: upload::TextureUploads,
     binding_layout: wgpu::BindGroupLayout,
     linear_sampler: wgpu::Sampler,
     nearest_sampler: wgpu::Sampler,
     uniforms: Buffer<Uniforms>,
     size: Size<UPx>,
     dpi_scale: Fraction,
+    scale_override: Option<Fraction>,
     zoom: Fraction,
     effective_scale: Fraction,
+    tessellation_tolerance: f32,
+    shape_anti_aliasing: bool,
+    dithering: bool,
+    damage: Option<Rect<UPx>>,
+    animations: tween::AnimationRegistry,
+    clock: tween::AnimationClock,
+    graph_textures: graph::TexturePool,
+    post_effects: post::PostProcessor,
+    features: wgpu::Features,
+    limits: wgpu::Limits,
+    adapter_info: Option<wgpu::AdapterInfo>,
+    format_features: wgpu::TextureFormatFeatures,
+    frame_stats: drawing::RenderStats,
+    last_frame_stats: drawing::RenderStats,
     #[cfg(feature = "cosmic-text")]
     text: text::TextSystem,
+    #[cfg(feature = "cosmic-text")]
+    glyph_atlas: text::GlyphAtlas,
 }
 
 impl Kludgine {
+    /// The format of depth attachments used with Kludgine.
+    ///
+    /// When a render pass is created with a depth attachment, Kludgine renders
+    /// using depth testing. Graphics drawn with a greater
+    /// [`Drawable::z`] are drawn above graphics with a lesser value,
+    /// regardless of the order they are drawn in.
+    pub const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
     /// The features that wgpu requires in compatible devices.
     pub const REQURED_FEATURES: wgpu::Features = wgpu::Features::PUSH_CONSTANTS;
+    /// The features that wgpu requires in devices to render using
+    /// [texture arrays](Self::set_texture_arrays).
+    pub const TEXTURE_ARRAY_FEATURES: wgpu::Features = pipeline::TextureArrays::FEATURES;
+    /// The features that wgpu requires in devices to render
+    /// [wireframes](Self::set_wireframe).
+    pub const WIREFRAME_FEATURES: wgpu::Features = wgpu::Features::POLYGON_MODE_LINE;
 
     /// Returns a new instance of Kludgine with the provided parameters.
     #[must_use]
@@ -111,17 +180,19 @@ impl Kludgine {
         let id = KludgineId::unique();
         let scale = Fraction::from(scale);
         let uniforms = Buffer::new(
-            &[Uniforms::new(initial_size, scale)],
+            &[Uniforms::new(initial_size, scale, None)],
             wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            Some("kludgine::uniforms"),
             device,
         );
 
         let binding_layout = pipeline::bind_group_layout(device, false);
+        let lookup_layout = pipeline::lookup_layout(device);
 
-        let pipeline_layout = pipeline::layout(device, &binding_layout);
+        let pipeline_layout = pipeline::layout(device, &binding_layout, &lookup_layout);
 
         let empty_texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: None,
+            label: Some("kludgine::empty texture"),
             size: wgpu::Extent3d {
                 width: 1,
                 height: 1,
@@ -136,38 +207,50 @@ impl Kludgine {
         });
 
         let nearest_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("kludgine::nearest sampler"),
             min_filter: wgpu::FilterMode::Nearest,
             mag_filter: wgpu::FilterMode::Nearest,
             mipmap_filter: wgpu::FilterMode::Nearest,
             ..wgpu::SamplerDescriptor::default()
         });
         let linear_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("kludgine::linear sampler"),
             min_filter: wgpu::FilterMode::Linear,
             mag_filter: wgpu::FilterMode::Linear,
             mipmap_filter: wgpu::FilterMode::Linear,
             ..wgpu::SamplerDescriptor::default()
         });
+        let empty_view = empty_texture.create_view(&wgpu::TextureViewDescriptor::default());
         let default_bindings = pipeline::bind_group(
             device,
             &binding_layout,
             &uniforms.wgpu,
-            &empty_texture.create_view(&wgpu::TextureViewDescriptor::default()),
+            &empty_view,
             &nearest_sampler,
         );
+        let default_lookup =
+            pipeline::lookup_bind_group(device, &lookup_layout, &empty_view, &linear_sampler);
 
-        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: None,
-            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!("shader.wgsl"))),
-        });
+        let shader = pipeline::shader(device, false);
 
-        let pipeline = pipeline::new(device, &pipeline_layout, &shader, format, multisample);
+        let (pipeline, depth_pipeline) = Self::create_pipelines(
+            device,
+            &pipeline_layout,
+            &shader,
+            format,
+            multisample,
+            pipeline::Shading::default(),
+        );
 
         Self {
             id,
             format,
             multisample,
+            shading: pipeline::Shading::default(),
             #[cfg(feature = "cosmic-text")]
-            text: text::TextSystem::new(&ProtoGraphics {
+            text: text::TextSystem::new(cosmic_text::FontSystem::new()),
+            #[cfg(feature = "cosmic-text")]
+            glyph_atlas: text::GlyphAtlas::new(&ProtoGraphics {
                 id,
                 device,
                 queue,
@@ -178,14 +261,35 @@ impl Kludgine {
                 multisample,
             }),
             default_bindings,
+            lookup_layout,
+            default_lookup,
             pipeline,
-            _shader: shader,
+            depth_pipeline,
+            pipeline_layout,
+            shader,
+            texture_arrays: None,
+            uploads: upload::TextureUploads::default(),
             linear_sampler,
             nearest_sampler,
             size: initial_size,
             dpi_scale: scale,
+            scale_override: None,
             zoom: Fraction::ONE,
             effective_scale: scale,
+            tessellation_tolerance: shapes::FillOptions::DEFAULT_TOLERANCE,
+            shape_anti_aliasing: false,
+            dithering: false,
+            damage: None,
+            animations: tween::AnimationRegistry::default(),
+            clock: tween::AnimationClock::new(),
+            graph_textures: graph::TexturePool::default(),
+            post_effects: post::PostProcessor::default(),
+            features: device.features(),
+            limits: device.limits(),
+            adapter_info: None,
+            format_features: format_features(format, device.features(), None),
+            frame_stats: drawing::RenderStats::default(),
+            last_frame_stats: drawing::RenderStats::default(),
 
             uniforms,
             binding_layout,
@@ -198,12 +302,334 @@ impl Kludgine {
         self.format
     }
 
-    /// Returns the multisample state this instance was initialized with.
+    /// Returns the multisample state this instance renders with.
     #[must_use]
     pub const fn multisample_state(&self) -> wgpu::MultisampleState {
         self.multisample
     }
 
+    /// Sets the number of samples to render with, recreating the pipelines
+    /// that depend on it.
+    ///
+    /// Render targets must be multisampled using the same number of samples,
+    /// and a count of 1 disables multisampling. Windows opened using the `app`
+    /// feature, including headless windows, recreate their multisampled
+    /// textures when the count changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedSampleCount`] without changing any settings
+    /// if `count` is not [supported](Self::supports_sample_count).
+    pub fn set_multisample(&mut self, count: u32, device: &wgpu::Device) -> Result<(), Error> {
+        if !self.supports_sample_count(count) {
+            return Err(Error::UnsupportedSampleCount(count));
+        }
+        if count == self.multisample.count {
+            return Ok(());
+        }
+
+        self.multisample.count = count;
+        self.rebuild_pipelines(device);
+        Ok(())
+    }
+
+    /// Sets whether graphics are rendered as wireframes, and returns true if
+    /// wireframe rendering is enabled.
+    ///
+    /// Wireframe rendering draws the edges of each triangle rather than
+    /// filling them. This visualizes how densely shapes are tessellated and
+    /// how much graphics overlap, which is useful when optimizing scenes. All
+    /// graphics rendered by Kludgine are affected, including text and
+    /// textures.
+    ///
+    /// Wireframes can only be enabled if `device` was created with
+    /// [`Kludgine::WIREFRAME_FEATURES`]. When unsupported, this function
+    /// returns false and graphics continue to be filled.
+    ///
+    /// Wireframe rendering is disabled by default.
+    pub fn set_wireframe(&mut self, enabled: bool, device: &wgpu::Device) -> bool {
+        let polygon_mode = if enabled && device.features().contains(Self::WIREFRAME_FEATURES) {
+            wgpu::PolygonMode::Line
+        } else {
+            wgpu::PolygonMode::Fill
+        };
+        if self.shading.polygon_mode != polygon_mode {
+            self.shading.polygon_mode = polygon_mode;
+            self.rebuild_pipelines(device);
+        }
+        self.wireframe()
+    }
+
+    /// Returns true if graphics are rendered as
+    /// [wireframes](Self::set_wireframe).
+    #[must_use]
+    pub const fn wireframe(&self) -> bool {
+        matches!(self.shading.polygon_mode, wgpu::PolygonMode::Line)
+    }
+
+    /// Sets whether graphics are rendered as a count of how many times each
+    /// pixel is drawn.
+    ///
+    /// While enabled, every fragment Kludgine renders adds one 8-bit level
+    /// (`1/255`) to the alpha channel of its pixel instead of blending its
+    /// color, and the color channels of drawn pixels are set to zero. Render
+    /// into a texture that is cleared to transparent, and then use
+    /// [`Frame::apply_post_effects()`] with [`PostEffect::OverdrawHeatmap`] to
+    /// show where a scene draws the same pixels repeatedly. Counts saturate at
+    /// 255 in targets with 8-bit channels.
+    ///
+    /// Overdraw rendering is disabled by default.
+    pub fn set_overdraw(&mut self, enabled: bool, device: &wgpu::Device) {
+        if self.shading.overdraw != enabled {
+            self.shading.overdraw = enabled;
+            self.rebuild_pipelines(device);
+        }
+    }
+
+    /// Returns true if graphics are rendered as a count of how many times each
+    /// pixel is [drawn](Self::set_overdraw).
+    #[must_use]
+    pub const fn overdraw(&self) -> bool {
+        self.shading.overdraw
+    }
+
+    /// Recreates the pipelines after their multisample state or shading
+    /// changes.
+    fn rebuild_pipelines(&mut self, device: &wgpu::Device) {
+        (self.pipeline, self.depth_pipeline) = Self::create_pipelines(
+            device,
+            &self.pipeline_layout,
+            &self.shader,
+            self.format,
+            self.multisample,
+            self.shading,
+        );
+        if self.texture_arrays.is_some() {
+            self.texture_arrays = Some(Arc::new(pipeline::TextureArrays::new(
+                device,
+                self.format,
+                self.multisample,
+                self.shading,
+                &self.lookup_layout,
+            )));
+        }
+    }
+
+    fn create_pipelines(
+        device: &wgpu::Device,
+        layout: &wgpu::PipelineLayout,
+        shader: &wgpu::ShaderModule,
+        format: wgpu::TextureFormat,
+        multisample: wgpu::MultisampleState,
+        shading: pipeline::Shading,
+    ) -> (wgpu::RenderPipeline, wgpu::RenderPipeline) {
+        (
+            pipeline::new(
+                device,
+                layout,
+                shader,
+                format,
+                multisample,
+                shading,
+                false,
+                false,
+                "kludgine::pipeline",
+            ),
+            pipeline::new(
+                device,
+                layout,
+                shader,
+                format,
+                multisample,
+                shading,
+                true,
+                false,
+                "kludgine::depth tested pipeline",
+            ),
+        )
+    }
+
+    /// Records the adapter that this instance's device was created from.
+    ///
+    /// A [`wgpu::Device`] does not provide information about its adapter, so
+    /// [`adapter_info()`](Self::adapter_info) returns `None` until this
+    /// function is called. Windows opened using the `app` feature record their
+    /// adapters automatically.
+    ///
+    /// When the device was created with
+    /// [`wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`], this also
+    /// allows [`supports_sample_count()`](Self::supports_sample_count) to
+    /// report the sample counts supported by `adapter` rather than the sample
+    /// counts every adapter supports.
+    pub fn set_adapter(&mut self, adapter: &wgpu::Adapter) {
+        self.adapter_info = Some(adapter.get_info());
+        self.format_features = format_features(self.format, self.features, Some(adapter));
+    }
+
+    /// Recreates the resources this instance created on the GPU using `device`
+    /// and `queue`, such as after the previous device was lost.
+    ///
+    /// All other state is kept, including the fonts loaded into its font
+    /// system, the current text attributes, the scale, and settings such as
+    /// [shape anti-aliasing](Self::set_shape_anti_aliasing),
+    /// [dithering](Self::set_dithering), and
+    /// [texture arrays](Self::set_texture_arrays). Texture arrays and
+    /// [wireframes](Self::set_wireframe) are disabled if `device` does not
+    /// support them.
+    ///
+    /// Resources created using the previous device, including [`Texture`]s,
+    /// prepared graphics, and [`Drawing`](drawing::Drawing)s, cannot be used
+    /// with `device` and must be recreated. The glyph atlas is recreated
+    /// empty, which ends any [sharing](Self::set_glyph_atlas) with other
+    /// instances, and pending texture uploads are discarded.
+    pub fn recreate_gpu_resources(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        let fresh = Self::new(
+            device,
+            queue,
+            self.format,
+            self.multisample,
+            self.size,
+            self.dpi_scale.into_f32(),
+        );
+        let texture_arrays = self.texture_arrays.is_some();
+        #[cfg(feature = "cosmic-text")]
+        {
+            self.glyph_atlas = self.glyph_atlas.recreated(fresh.glyph_atlas);
+        }
+        self.id = fresh.id;
+        self.default_bindings = fresh.default_bindings;
+        self.lookup_layout = fresh.lookup_layout;
+        self.default_lookup = fresh.default_lookup;
+        self.pipeline = fresh.pipeline;
+        self.depth_pipeline = fresh.depth_pipeline;
+        self.pipeline_layout = fresh.pipeline_layout;
+        self.shader = fresh.shader;
+        self.texture_arrays = None;
+        let upload_budget = self.uploads.budget();
+        self.uploads = fresh.uploads;
+        self.uploads.set_budget(upload_budget);
+        self.binding_layout = fresh.binding_layout;
+        self.linear_sampler = fresh.linear_sampler;
+        self.nearest_sampler = fresh.nearest_sampler;
+        self.uniforms = fresh.uniforms;
+        self.graph_textures = fresh.graph_textures;
+        self.post_effects = fresh.post_effects;
+        self.features = fresh.features;
+        self.limits = fresh.limits;
+        self.adapter_info = None;
+        self.format_features = fresh.format_features;
+        self.damage = None;
+
+        if !device.features().contains(Self::WIREFRAME_FEATURES) {
+            self.shading.polygon_mode = wgpu::PolygonMode::Fill;
+        }
+        if self.shading != pipeline::Shading::default() {
+            self.rebuild_pipelines(device);
+        }
+        self.set_texture_arrays(texture_arrays, device);
+        self.update_uniforms(queue);
+    }
+
+    /// Returns information about the adapter this instance's device was
+    /// created from, such as its name and backend.
+    ///
+    /// Returns `None` if the adapter has not been
+    /// [recorded](Self::set_adapter).
+    #[must_use]
+    pub const fn adapter_info(&self) -> Option<&wgpu::AdapterInfo> {
+        self.adapter_info.as_ref()
+    }
+
+    /// Returns the features enabled on this instance's device.
+    #[must_use]
+    pub const fn features(&self) -> wgpu::Features {
+        self.features
+    }
+
+    /// Returns true if every feature in `features` is enabled on this
+    /// instance's device.
+    #[must_use]
+    pub const fn supports(&self, features: wgpu::Features) -> bool {
+        self.features.contains(features)
+    }
+
+    /// Returns the limits of this instance's device.
+    #[must_use]
+    pub const fn limits(&self) -> &wgpu::Limits {
+        &self.limits
+    }
+
+    /// Returns true if textures in this instance's
+    /// [format](Self::texture_format) can be multisampled using `count`
+    /// samples.
+    ///
+    /// This allows choosing a supported multisample count, such as falling
+    /// back to 4 samples when 8 are not supported.
+    #[must_use]
+    pub fn supports_sample_count(&self, count: u32) -> bool {
+        self.format_features.flags.sample_count_supported(count)
+    }
+
+    /// Returns a new depth texture that matches this instance's size and
+    /// multisample state.
+    ///
+    /// The texture can be used as a depth attachment by using
+    /// [`Kludgine::depth_attachment()`].
+    #[must_use]
+    pub fn create_depth_texture(&self, device: &wgpu::Device) -> wgpu::Texture {
+        device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("kludgine::depth texture"),
+            size: wgpu::Extent3d {
+                width: self.size.width.get().max(1),
+                height: self.size.height.get().max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: self.multisample.count,
+            dimension: wgpu::TextureDimension::D2,
+            format: Self::DEPTH_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        })
+    }
+
+    /// Returns a view of `depth`, replacing it with a new texture from
+    /// [`Kludgine::create_depth_texture()`] if it is missing or its size no
+    /// longer matches this instance.
+    pub(crate) fn depth_view(
+        &self,
+        depth: &mut Option<wgpu::Texture>,
+        device: &wgpu::Device,
+    ) -> wgpu::TextureView {
+        let texture = match depth {
+            Some(texture)
+                if texture.width() == self.size.width.get().max(1)
+                    && texture.height() == self.size.height.get().max(1)
+                    && texture.sample_count() == self.multisample.count =>
+            {
+                texture
+            }
+            _ => depth.insert(self.create_depth_texture(device)),
+        };
+        texture.create_view(&wgpu::TextureViewDescriptor::default())
+    }
+
+    /// Returns a depth attachment for `view` that clears the depth buffer
+    /// before rendering, as expected by Kludgine's depth testing.
+    #[must_use]
+    pub fn depth_attachment(
+        view: &wgpu::TextureView,
+    ) -> wgpu::RenderPassDepthStencilAttachment<'_> {
+        wgpu::RenderPassDepthStencilAttachment {
+            view,
+            depth_ops: Some(wgpu::Operations {
+                load: wgpu::LoadOp::Clear(0.),
+                store: wgpu::StoreOp::Discard,
+            }),
+            stencil_ops: None,
+        }
+    }
+
     /// Adjusts and returns the wgpu limits to support features used by
     /// Kludgine.
     #[must_use]
@@ -225,6 +651,9 @@ impl Kludgine {
     /// This function updates data stored in the GPU that affects how graphics
     /// are rendered. It should be called before calling `next_frame()` if the
     /// size or scale of the underlying surface has changed.
+    ///
+    /// If a [scale override](Self::set_scale_override) is set, `new_scale` is
+    /// recorded as the DPI scale but the override is used in its place.
     pub fn resize(
         &mut self,
         new_size: Size<UPx>,
@@ -232,21 +661,51 @@ impl Kludgine {
         new_zoom: impl Into<Fraction>,
         queue: &wgpu::Queue,
     ) {
-        let new_scale = new_scale.into();
-        let new_zoom = new_zoom.into();
-        self.effective_scale = new_scale * new_zoom;
-        if self.size != new_size || self.dpi_scale != new_scale || self.zoom != new_zoom {
+        self.dpi_scale = new_scale.into();
+        self.zoom = new_zoom.into();
+        let effective_scale = self.scale_override.unwrap_or(self.dpi_scale) * self.zoom;
+        if self.size != new_size || self.effective_scale != effective_scale {
             self.size = new_size;
-            self.dpi_scale = new_scale;
-            self.zoom = new_zoom;
-            self.uniforms
-                .update(0, &[Uniforms::new(self.size, self.effective_scale)], queue);
+            self.effective_scale = effective_scale;
+            self.update_uniforms(queue);
         }
 
         #[cfg(feature = "cosmic-text")]
         self.text.scale_changed(self.effective_scale);
     }
 
+    fn add_damage(&mut self, region: Rect<UPx>) {
+        let Some(region) = region
+            .intersection(&Rect::from(self.size))
+            .filter(|region| region.size.width > 0 && region.size.height > 0)
+        else {
+            return;
+        };
+        self.damage = Some(match self.damage {
+            Some(damage) => {
+                let (min, max) = damage.extents();
+                let (region_min, region_max) = region.extents();
+                Rect::from_extents(
+                    Point::new(min.x.min(region_min.x), min.y.min(region_min.y)),
+                    Point::new(max.x.max(region_max.x), max.y.max(region_max.y)),
+                )
+            }
+            None => region,
+        });
+    }
+
+    fn update_uniforms(&self, queue: &wgpu::Queue) {
+        self.uniforms.update(
+            0,
+            &[Uniforms::new(
+                self.size,
+                self.effective_scale,
+                self.dithering.then_some(self.format),
+            )],
+            queue,
+        );
+    }
+
     /// Sets the current zoom level.
     ///
     /// Zoom and DPI scale are multiplied to create an effective scale for all
@@ -262,10 +721,41 @@ impl Kludgine {
         self.resize(self.size, new_scale, self.zoom, queue);
     }
 
+    /// Overrides the DPI scale reported by the window server with `scale`.
+    ///
+    /// While an override is set, the override is used in place of the DPI
+    /// scale, and DPI changes reported to [`resize()`](Self::resize) or
+    /// [`set_dpi_scale()`](Self::set_dpi_scale) are recorded without affecting
+    /// rendering. This allows applications to control the size of their
+    /// content, such as through a user-configurable interface scale, without
+    /// being affected by the monitor a window is displayed on. The
+    /// [zoom](Self::set_zoom) is still applied on top of the override.
+    pub fn set_scale_override(&mut self, scale: impl Into<Fraction>, queue: &wgpu::Queue) {
+        self.scale_override = Some(scale.into());
+        self.resize(self.size, self.dpi_scale, self.zoom, queue);
+    }
+
+    /// Removes the [scale override](Self::set_scale_override), restoring the
+    /// use of the DPI scale.
+    pub fn clear_scale_override(&mut self, queue: &wgpu::Queue) {
+        self.scale_override = None;
+        self.resize(self.size, self.dpi_scale, self.zoom, queue);
+    }
+
+    /// Returns the scale set by
+    /// [`set_scale_override()`](Self::set_scale_override), if any.
+    pub const fn scale_override(&self) -> Option<Fraction> {
+        self.scale_override
+    }
+
     /// Begins rendering a new frame.
     pub fn next_frame(&mut self) -> Frame<'_> {
+        self.last_frame_stats = std::mem::take(&mut self.frame_stats);
+        self.damage = None;
+        self.uploads.new_frame();
         #[cfg(feature = "cosmic-text")]
-        self.text.new_frame();
+        self.glyph_atlas
+            .new_frame(&mut self.text, &mut self.last_frame_stats);
         Frame {
             kludgine: self,
             commands: None,
@@ -283,6 +773,9 @@ impl Kludgine {
     }
 
     /// Returns the DPI scale of the underlying context.
+    ///
+    /// This value is not affected by a
+    /// [scale override](Self::set_scale_override).
     pub const fn dpi_scale(&self) -> Fraction {
         self.dpi_scale
     }
@@ -291,6 +784,230 @@ impl Kludgine {
     pub const fn zoom(&self) -> Fraction {
         self.zoom
     }
+
+    /// Returns the effective scale as a [`DisplayScale`](convert::DisplayScale),
+    /// which can convert between physical pixels and logical units.
+    ///
+    /// The effective scale is the DPI scale, or the
+    /// [scale override](Self::set_scale_override) if one is set, multiplied by
+    /// the [zoom](Self::zoom). This is the scale graphics are rendered with,
+    /// so positions converted using it line up with drawn graphics. It is the
+    /// only scale that should be used to convert input coordinates.
+    #[must_use]
+    pub fn display_scale(&self) -> convert::DisplayScale {
+        convert::DisplayScale::from(self.effective_scale)
+    }
+
+    /// Returns the maximum distance, in physical pixels, that tessellated
+    /// curves may deviate from the curves they approximate.
+    ///
+    /// The default tolerance is
+    /// [`FillOptions::DEFAULT_TOLERANCE`](shapes::FillOptions::DEFAULT_TOLERANCE).
+    #[must_use]
+    pub const fn tessellation_tolerance(&self) -> f32 {
+        self.tessellation_tolerance
+    }
+
+    /// Sets the maximum distance, in physical pixels, that tessellated curves
+    /// may deviate from the curves they approximate.
+    ///
+    /// Smaller tolerances produce smoother curves made of more triangles.
+    pub fn set_tessellation_tolerance(&mut self, pixels: f32) {
+        self.tessellation_tolerance = pixels;
+    }
+
+    /// Returns the [tessellation
+    /// tolerance](Self::tessellation_tolerance) converted to `Unit` at the
+    /// current [effective scale](Self::scale).
+    ///
+    /// Because the tolerance is measured in physical pixels, the tolerance of
+    /// scaled units such as [`Lp`](figures::units::Lp) tightens as the scale
+    /// increases, keeping curves smooth on high-DPI displays. The returned
+    /// value can be used with [`FillOptions::tolerance`](shapes::FillOptions)
+    /// and [`StrokeOptions::tolerance`](shapes::StrokeOptions::tolerance).
+    #[must_use]
+    pub fn scaled_tessellation_tolerance<Unit>(&self) -> f32
+    where
+        Unit: figures::ScreenScale<Px = figures::units::Px> + figures::FloatConversion<Float = f32>,
+    {
+        shapes::scaled_pixel_tolerance::<Unit>(self.tessellation_tolerance, self.effective_scale)
+    }
+
+    /// Returns true if paths filled or stroked using
+    /// [`Renderer::fill_path()`](drawing::Renderer::fill_path) and
+    /// [`Renderer::stroke_path()`](drawing::Renderer::stroke_path) are
+    /// anti-aliased.
+    #[must_use]
+    pub const fn shape_anti_aliasing(&self) -> bool {
+        self.shape_anti_aliasing
+    }
+
+    /// Sets whether paths filled or stroked using
+    /// [`Renderer::fill_path()`](drawing::Renderer::fill_path) and
+    /// [`Renderer::stroke_path()`](drawing::Renderer::stroke_path) are
+    /// anti-aliased.
+    ///
+    /// When enabled, the shapes are [feathered](shapes::Shape::feathered) by
+    /// one physical pixel, which smooths their edges without multisampling.
+    /// Shapes drawn using other functions can be anti-aliased individually
+    /// using [`Shape::anti_aliased()`](shapes::Shape::anti_aliased).
+    ///
+    /// Anti-aliasing is disabled by default.
+    pub fn set_shape_anti_aliasing(&mut self, enabled: bool) {
+        self.shape_anti_aliasing = enabled;
+    }
+
+    /// Sets whether the colors of rendered graphics are dithered.
+    ///
+    /// Render targets with eight bits per channel can only represent 256
+    /// levels of each channel, which causes smooth gradients, such as subtle
+    /// shadows and vignettes, to show visible bands. When enabled, each
+    /// fragment's color is offset by up to half of one level using an ordered
+    /// dithering pattern, which replaces the bands with a fine, nearly
+    /// invisible pattern. When rendering into an sRGB target, the offset is
+    /// applied to the sRGB-encoded color, ensuring dark colors are not
+    /// dithered more heavily than light colors.
+    ///
+    /// Dithering is disabled by default.
+    pub fn set_dithering(&mut self, enabled: bool, queue: &wgpu::Queue) {
+        if self.dithering != enabled {
+            self.dithering = enabled;
+            self.update_uniforms(queue);
+        }
+    }
+
+    /// Returns true if the colors of rendered graphics are
+    /// [dithered](Self::set_dithering).
+    #[must_use]
+    pub const fn dithering(&self) -> bool {
+        self.dithering
+    }
+
+    /// Sets whether [`Drawing`](drawing::Drawing)s are rendered using texture
+    /// binding arrays, and returns true if texture arrays are enabled.
+    ///
+    /// Without texture arrays, each texture is bound using its own bind group,
+    /// and rendering a drawing rebinds textures each time it switches between
+    /// them. With texture arrays, the textures used by a drawing are bound in
+    /// groups of up to eight, which greatly reduces the number of bind group
+    /// changes in scenes that alternate between a handful of textures, such as
+    /// sprite-heavy scenes.
+    ///
+    /// Texture arrays can only be enabled if `device` was created with
+    /// [`Kludgine::TEXTURE_ARRAY_FEATURES`]. When unsupported, this function
+    /// returns false and rendering continues to use a bind group per texture.
+    /// Multisampled textures are always bound individually.
+    ///
+    /// Texture arrays are disabled by default. Changes take effect for drawings
+    /// prepared after this function is called.
+    pub fn set_texture_arrays(&mut self, enabled: bool, device: &wgpu::Device) -> bool {
+        if !enabled {
+            self.texture_arrays = None;
+        } else if self.texture_arrays.is_none() && pipeline::TextureArrays::supported_by(device) {
+            self.texture_arrays = Some(Arc::new(pipeline::TextureArrays::new(
+                device,
+                self.format,
+                self.multisample,
+                self.shading,
+                &self.lookup_layout,
+            )));
+        }
+        self.texture_arrays.is_some()
+    }
+
+    /// Returns true if [`Drawing`](drawing::Drawing)s are rendered using
+    /// [texture arrays](Self::set_texture_arrays).
+    #[must_use]
+    pub const fn texture_arrays(&self) -> bool {
+        self.texture_arrays.is_some()
+    }
+
+    /// Sets whether texture data is uploaded asynchronously, streaming at most
+    /// `budget_per_frame` bytes to the GPU each frame.
+    ///
+    /// Uploading a large texture all at once can cause a noticeable stall.
+    /// When a budget is set, the contents of [`LazyTexture`]s and images
+    /// pushed into [`TextureCollection`]s are copied through a staging belt
+    /// over as many frames as needed, starting when the next frame is
+    /// prepared. While uploading, textures can be drawn but their contents
+    /// are incomplete: rows that have not been uploaded are transparent in
+    /// new textures, but may still contain a previous image in regions of a
+    /// [`TextureCollection`] that have been freed and reused. Use
+    /// [`Texture::load_state()`],
+    /// [`LazyTexture::load_state()`], or [`CollectedTexture::load_state()`] to
+    /// check whether a texture has finished uploading.
+    ///
+    /// Each frame uploads at least one row of pixels, even if it exceeds the
+    /// budget. Glyphs are always uploaded immediately. When `None`, which is
+    /// the default, texture data is uploaded immediately, and any remaining
+    /// asynchronous uploads finish during the next frame.
+    pub fn set_async_texture_uploads(&mut self, budget_per_frame: Option<u64>) {
+        self.uploads.set_budget(budget_per_frame);
+    }
+
+    /// Returns the number of bytes uploaded each frame when [asynchronous
+    /// texture uploads](Self::set_async_texture_uploads) are enabled.
+    #[must_use]
+    pub fn async_texture_uploads(&self) -> Option<u64> {
+        self.uploads.budget()
+    }
+
+    /// Returns the number of textures whose contents are still being
+    /// uploaded.
+    #[must_use]
+    pub fn pending_texture_uploads(&self) -> usize {
+        self.uploads.pending()
+    }
+
+    /// Returns the width of a feathered edge one physical pixel wide,
+    /// converted to `Unit` at the current [effective scale](Self::scale), if
+    /// [shape anti-aliasing](Self::shape_anti_aliasing) is enabled.
+    fn anti_aliasing_width<Unit>(&self) -> Option<f32>
+    where
+        Unit: figures::ScreenScale<Px = figures::units::Px> + figures::FloatConversion<Float = f32>,
+    {
+        self.shape_anti_aliasing
+            .then(|| shapes::scaled_pixel_tolerance::<Unit>(1., self.effective_scale))
+    }
+
+    /// Returns the registry of animations that require redrawing.
+    #[must_use]
+    pub const fn animations(&self) -> &tween::AnimationRegistry {
+        &self.animations
+    }
+
+    /// Returns an exclusive reference to the registry of animations that
+    /// require redrawing.
+    pub fn animations_mut(&mut self) -> &mut tween::AnimationRegistry {
+        &mut self.animations
+    }
+
+    /// Returns the clock that measures how far animations should advance
+    /// each frame.
+    #[must_use]
+    pub const fn clock(&self) -> &tween::AnimationClock {
+        &self.clock
+    }
+
+    /// Returns an exclusive reference to the clock that measures how far
+    /// animations should advance each frame.
+    ///
+    /// Applications that do not use the `app` feature's windows should
+    /// [tick](tween::AnimationClock::tick) the clock once per frame.
+    pub fn clock_mut(&mut self) -> &mut tween::AnimationClock {
+        &mut self.clock
+    }
+
+    /// Returns the combined statistics of every
+    /// [`Drawing`](drawing::Drawing) prepared during the previous frame.
+    ///
+    /// Statistics are collected when each [`Renderer`](drawing::Renderer) is
+    /// dropped, and are moved into the previous frame's statistics when
+    /// [`next_frame()`](Self::next_frame) is invoked.
+    #[must_use]
+    pub const fn last_frame_stats(&self) -> drawing::RenderStats {
+        self.last_frame_stats
+    }
 }
 
 /// The unique ID of a [`Kludgine`] instance.
@@ -333,21 +1050,73 @@ impl Frame<'_> {
         device: &'gfx wgpu::Device,
         queue: &'gfx wgpu::Queue,
     ) -> Graphics<'gfx> {
+        self.kludgine.uploads.upload(device, queue);
         Graphics::new(self.kludgine, device, queue)
     }
 
+    /// Marks `region` as changed since the previous frame.
+    ///
+    /// When a frame has damage, [`Frame::render()`] only renders within the
+    /// bounding rectangle of all damaged regions, leaving the remainder of the
+    /// target untouched. This is only correct if the target still contains
+    /// the previous frame's contents. See [`Frame::damaged_region()`] for more
+    /// information.
+    ///
+    /// Regions are clipped to [`Kludgine::size()`].
+    pub fn add_damage(&mut self, region: Rect<UPx>) {
+        self.kludgine.add_damage(region);
+    }
+
+    /// Returns the bounding rectangle of the regions that have been
+    /// [damaged](Self::add_damage) during this frame, or `None` if the entire
+    /// frame will be rendered.
+    ///
+    /// A partial redraw is only performed when this returns a region.
+    #[must_use]
+    pub fn damaged_region(&self) -> Option<Rect<UPx>> {
+        self.kludgine.damage
+    }
+
     /// Creates a [`RenderingGraphics`] context for this frame which is used to
     /// render previously prepared graphics:
     ///
     /// - [`PreparedGraphic`]
     /// - [`PreparedText`](text::PreparedText)
     /// - [`Drawing`](drawing::Drawing)
+    ///
+    /// If `pass` has a depth attachment, graphics are rendered with depth
+    /// testing. The attachment must use [`Kludgine::DEPTH_FORMAT`] and should
+    /// be created using [`Kludgine::create_depth_texture()`] and attached
+    /// using [`Kludgine::depth_attachment()`].
+    ///
+    /// If this frame has been [damaged](Self::add_damage), all rendering is
+    /// limited to the [damaged region](Self::damaged_region). `pass` should
+    /// load the target's existing contents rather than clearing it, as clears
+    /// are applied to the entire target.
     #[must_use]
     pub fn render<'gfx, 'pass>(
         &'pass mut self,
         pass: &wgpu::RenderPassDescriptor<'_>,
         device: &'gfx wgpu::Device,
         queue: &'gfx wgpu::Queue,
+    ) -> RenderingGraphics<'gfx, 'pass> {
+        let size = self.kludgine.size;
+        let damage = self.kludgine.damage;
+        let mut rendering = self.render_region(pass, size, size.into(), device, queue);
+        if damage.is_some() {
+            rendering.damage = damage;
+            rendering.update_scissor_rect();
+        }
+        rendering
+    }
+
+    fn render_region<'gfx, 'pass>(
+        &'pass mut self,
+        pass: &wgpu::RenderPassDescriptor<'_>,
+        target_size: Size<UPx>,
+        region: Rect<UPx>,
+        device: &'gfx wgpu::Device,
+        queue: &'gfx wgpu::Queue,
     ) -> RenderingGraphics<'gfx, 'pass> {
         if self.commands.is_none() {
             self.commands =
@@ -359,6 +1128,9 @@ impl Frame<'_> {
                 .assert("initialized above")
                 .begin_render_pass(pass),
             self.kludgine,
+            pass.depth_stencil_attachment.is_some(),
+            target_size,
+            region,
             device,
             queue,
         )
@@ -371,6 +1143,9 @@ impl Frame<'_> {
     /// - [`PreparedGraphic`]
     /// - [`PreparedText`](text::PreparedText)
     /// - [`Drawing`](drawing::Drawing)
+    ///
+    /// Graphics are rendered at one pixel per pixel of `texture`, regardless
+    /// of the size of the window or surface this frame is for.
     pub fn render_into<'gfx, 'pass>(
         &'pass mut self,
         texture: &'pass Texture,
@@ -378,17 +1153,94 @@ impl Frame<'_> {
         device: &'gfx wgpu::Device,
         queue: &'gfx wgpu::Queue,
     ) -> RenderingGraphics<'gfx, 'pass> {
-        self.render(
+        self.render_into_view(
+            &texture.data.view,
+            match load_op {
+                wgpu::LoadOp::Clear(color) => wgpu::LoadOp::Clear(color.into()),
+                wgpu::LoadOp::Load => wgpu::LoadOp::Load,
+            },
+            texture.size,
+            texture.size.into(),
+            device,
+            queue,
+        )
+    }
+
+    /// Creates a [`RenderingGraphics`] that renders into the region of its
+    /// [`TextureCollection`] that `texture` occupies. The returned context
+    /// can be used to render previously prepared graphics:
+    ///
+    /// - [`PreparedGraphic`]
+    /// - [`PreparedText`](text::PreparedText)
+    /// - [`Drawing`](drawing::Drawing)
+    ///
+    /// The context's origin is the region's top-left corner, and rendering is
+    /// clipped to the region, leaving the rest of the collection untouched.
+    /// If `clear` is true, the region is cleared to transparent before any
+    /// graphics are rendered.
+    ///
+    /// The collection must have been created with
+    /// [`TextureCollection::new_render_target()`]. Regions to render into
+    /// can be reserved using [`TextureCollection::push_render_target()`] or
+    /// [`TextureCollection::allocate()`]. The
+    /// collection should not grow while the returned context is in use, as
+    /// anything rendered into the previous texture will be lost.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::MissingUsage`] if the collection's texture cannot be
+    /// used as a render attachment.
+    pub fn render_into_collected<'gfx, 'pass>(
+        &'pass mut self,
+        texture: &CollectedTexture,
+        clear: bool,
+        device: &'gfx wgpu::Device,
+        queue: &'gfx wgpu::Queue,
+    ) -> Result<RenderingGraphics<'gfx, 'pass>, Error> {
+        let region = texture.region;
+        texture.with_collection_texture(move |target| {
+            if !target
+                .data
+                .wgpu
+                .usage()
+                .contains(wgpu::TextureUsages::RENDER_ATTACHMENT)
+            {
+                return Err(Error::MissingUsage(wgpu::TextureUsages::RENDER_ATTACHMENT));
+            }
+
+            if clear {
+                atlas::clear_region(target, region, queue);
+            }
+
+            Ok(self.render_into_view(
+                &target.data.view,
+                wgpu::LoadOp::Load,
+                target.size,
+                region,
+                device,
+                queue,
+            ))
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn render_into_view<'gfx, 'pass>(
+        &'pass mut self,
+        view: &wgpu::TextureView,
+        load: wgpu::LoadOp<wgpu::Color>,
+        target_size: Size<UPx>,
+        region: Rect<UPx>,
+        device: &'gfx wgpu::Device,
+        queue: &'gfx wgpu::Queue,
+    ) -> RenderingGraphics<'gfx, 'pass> {
+        self.render_region(
             &wgpu::RenderPassDescriptor {
-                label: None,
+                label: Some("kludgine::render pass"),
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &texture.data.view,
+                    view,
                     resolve_target: None,
                     ops: wgpu::Operations {
-                        load: match load_op {
-                            wgpu::LoadOp::Clear(color) => wgpu::LoadOp::Clear(color.into()),
-                            wgpu::LoadOp::Load => wgpu::LoadOp::Load,
-                        },
+                        load,
                         store: wgpu::StoreOp::Store,
                     },
                 })],
@@ -396,11 +1248,52 @@ impl Frame<'_> {
                 timestamp_writes: None,
                 occlusion_query_set: None,
             },
+            target_size,
+            region,
             device,
             queue,
         )
     }
 
+    /// Records passes that apply `effects` to `source`, in order, writing the
+    /// result into `destination`.
+    ///
+    /// This is typically used to process a frame that was rendered into an
+    /// offscreen texture while writing it to the surface being presented.
+    /// `source` is stretched to fill `destination` if their sizes differ. If
+    /// `effects` is empty, `source` is copied into `destination`.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::UnsupportedFormat`]: `source` is multisampled or cannot be
+    ///   filtered, or `destination` is multisampled or cannot be rendered
+    ///   into by a floating point shader.
+    /// - [`Error::MissingUsage`]: `source` cannot be bound as a texture, or
+    ///   `destination` cannot be used as a render attachment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` and `destination` are the same texture.
+    pub fn apply_post_effects(
+        &mut self,
+        source: &Texture,
+        destination: &wgpu::Texture,
+        effects: &[PostEffect],
+        device: &wgpu::Device,
+    ) -> Result<(), Error> {
+        let encoder = self.commands.get_or_insert_with(|| {
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default())
+        });
+        self.kludgine.post_effects.apply(
+            source,
+            destination,
+            effects,
+            &self.kludgine.linear_sampler,
+            encoder,
+            device,
+        )
+    }
+
     /// Submits all of the commands for this frame to the GPU.
     ///
     /// This function does not block for the operations to finish. The returned
@@ -426,8 +1319,10 @@ impl Frame<'_> {
 
 impl Drop for Frame<'_> {
     fn drop(&mut self) {
+        // Asserting while unwinding would abort the process, hiding the
+        // original panic.
         assert!(
-            self.commands.is_none(),
+            self.commands.is_none() || std::thread::panicking(),
             "Frame dropped without calling finish() or abort()"
         );
     }
@@ -500,6 +1395,10 @@ impl sealed::KludgineGraphics for ProtoGraphics<'_> {
     fn multisample_state(&self) -> wgpu::MultisampleState {
         self.multisample
     }
+
+    fn texture_uploads(&self) -> Option<&upload::TextureUploads> {
+        None
+    }
 }
 
 impl KludgineGraphics for Graphics<'_> {}
@@ -536,33 +1435,185 @@ impl sealed::KludgineGraphics for Graphics<'_> {
     fn multisample_state(&self) -> wgpu::MultisampleState {
         self.multisample
     }
+
+    fn texture_uploads(&self) -> Option<&upload::TextureUploads> {
+        Some(&self.kludgine.uploads)
+    }
 }
 
 #[derive(Debug)]
 struct ClipStack {
     current: ClipRect,
-    previous_clips: Vec<ClipRect>,
+    rotated: Option<RotatedClip>,
+    previous_clips: Vec<(ClipRect, Option<RotatedClip>)>,
 }
 
 impl ClipStack {
+    /// The depth at which debug builds assume clips are being pushed without
+    /// being popped.
+    const SUSPICIOUS_DEPTH: usize = 4096;
+
     pub fn new(size: Size<UPx>) -> Self {
+        Self::from_rect(size.into())
+    }
+
+    pub fn from_rect(rect: Rect<UPx>) -> Self {
         Self {
-            current: size.into(),
+            current: ClipRect(rect),
+            rotated: None,
             previous_clips: Vec::new(),
         }
     }
 
     pub fn push_clip(&mut self, clip: Rect<UPx>) {
         let previous_clip = self.current;
-        self.current = previous_clip.clip_to(clip.expand_rounded());
-        self.previous_clips.push(previous_clip);
+        self.push_state(previous_clip.clip_to(clip.expand_rounded()), self.rotated);
+    }
+
+    pub fn push_rotated_clip(&mut self, clip: Rect<UPx>, angle: Angle) {
+        let rotated = RotatedClip::new(self.current.origin, clip, angle);
+        let (min, max) = rotated.extents();
+        let (current_min, current_max) = self.current.extents();
+        let left = min.x.floor().max(current_min.x.into_float());
+        let top = min.y.floor().max(current_min.y.into_float());
+        let right = max.x.ceil().min(current_max.x.into_float()).max(left);
+        let bottom = max.y.ceil().min(current_max.y.into_float()).max(top);
+        let bounds = Rect::new(
+            Point::new(UPx::new(left.cast::<u32>()), UPx::new(top.cast::<u32>())),
+            Size::new(
+                UPx::new((right - left).cast::<u32>()),
+                UPx::new((bottom - top).cast::<u32>()),
+            ),
+        );
+
+        // When the rotated rect is still aligned to the pixel grid, the
+        // scissor rect clips it exactly.
+        let axis_aligned = (rotated.rotation.x.abs() < f32::EPSILON
+            || rotated.rotation.y.abs() < f32::EPSILON)
+            && [min.x, min.y, max.x, max.y]
+                .into_iter()
+                .all(|value| value.fract() == 0.);
+        let rotated = if axis_aligned {
+            self.rotated
+        } else {
+            Some(rotated)
+        };
+        self.push_state(ClipRect(bounds), rotated);
+    }
+
+    fn push_state(&mut self, clip: ClipRect, rotated: Option<RotatedClip>) {
+        debug_assert!(
+            self.previous_clips.len() < Self::SUSPICIOUS_DEPTH,
+            "clip depth exceeded {}; push_clip is likely being called without pop_clip",
+            Self::SUSPICIOUS_DEPTH
+        );
+        let previous_clip = std::mem::replace(&mut self.current, clip);
+        let previous_rotated = std::mem::replace(&mut self.rotated, rotated);
+        self.previous_clips.push((previous_clip, previous_rotated));
+    }
+
+    pub fn try_pop_clip(&mut self) -> Result<(), UnpairedPopClip> {
+        (self.current, self.rotated) = self.previous_clips.pop().ok_or(UnpairedPopClip {
+            clip_rect: *self.current,
+        })?;
+        Ok(())
     }
 
-    pub fn pop_clip(&mut self) {
-        self.current = self.previous_clips.pop().expect("unpaired pop_clip");
+    pub fn depth(&self) -> usize {
+        self.previous_clips.len()
+    }
+
+    pub fn rects(&self) -> Vec<Rect<UPx>> {
+        self.previous_clips
+            .iter()
+            .map(|(clip, _)| **clip)
+            .chain([*self.current])
+            .collect()
     }
 }
 
+#[test]
+fn clip_stack() {
+    let mut stack = ClipStack::new(Size::upx(100, 100));
+    stack.push_clip(Rect::new(Point::upx(10, 10), Size::upx(50, 50)));
+    stack.push_clip(Rect::new(Point::upx(10, 10), Size::upx(100, 100)));
+    assert_eq!(stack.depth(), 2);
+    assert_eq!(
+        stack.rects(),
+        [
+            Rect::new(Point::upx(0, 0), Size::upx(100, 100)),
+            Rect::new(Point::upx(10, 10), Size::upx(50, 50)),
+            Rect::new(Point::upx(20, 20), Size::upx(40, 40)),
+        ]
+    );
+    assert_eq!(stack.try_pop_clip(), Ok(()));
+    assert_eq!(stack.try_pop_clip(), Ok(()));
+    assert_eq!(
+        stack.try_pop_clip(),
+        Err(UnpairedPopClip {
+            clip_rect: Rect::new(Point::upx(0, 0), Size::upx(100, 100))
+        })
+    );
+}
+
+#[test]
+fn rotated_clip_stack() {
+    let mut stack = ClipStack::new(Size::upx(100, 100));
+    stack.push_rotated_clip(
+        Rect::new(Point::upx(10, 10), Size::upx(40, 20)),
+        Angle::degrees(180),
+    );
+    assert_eq!(
+        *stack.current,
+        Rect::new(Point::upx(10, 10), Size::upx(40, 20))
+    );
+    assert_eq!(stack.rotated, None);
+
+    stack.push_rotated_clip(
+        Rect::new(Point::upx(0, 0), Size::upx(40, 20)),
+        Angle::degrees(45),
+    );
+    let rotated = stack.rotated.expect("rotated clip");
+    assert_eq!(rotated.center, Point::new(30., 20.));
+    assert_eq!(rotated.half_size, Point::new(20., 10.));
+    // The scissor rect contains the rotated rect, limited to the previous
+    // clip rect.
+    assert_eq!(
+        *stack.current,
+        Rect::new(Point::upx(10, 10), Size::upx(40, 20))
+    );
+
+    stack.push_clip(Rect::new(Point::upx(5, 5), Size::upx(10, 10)));
+    assert_eq!(stack.rotated, Some(rotated));
+    assert_eq!(stack.try_pop_clip(), Ok(()));
+    assert_eq!(stack.try_pop_clip(), Ok(()));
+    assert_eq!(stack.rotated, None);
+}
+
+/// [`Clipped::try_pop_clip()`] was called more times than
+/// [`Clipped::push_clip()`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct UnpairedPopClip {
+    /// The clip rect of the context when the clip was popped, which is the
+    /// rect the context was created with.
+    pub clip_rect: Rect<UPx>,
+}
+
+impl fmt::Display for UnpairedPopClip {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "pop_clip called without a matching push_clip (unclipped rect {}x{} at {},{})",
+            self.clip_rect.size.width,
+            self.clip_rect.size.height,
+            self.clip_rect.origin.x,
+            self.clip_rect.origin.y
+        )
+    }
+}
+
+impl std::error::Error for UnpairedPopClip {}
+
 /// A context used to prepare graphics to render.
 ///
 /// This type is used in these APIs:
@@ -619,6 +1670,28 @@ impl<'gfx> Graphics<'gfx> {
         self.queue
     }
 
+    /// Invokes `operation` with this context, capturing any validation or
+    /// out-of-memory errors reported by the graphics device.
+    ///
+    /// Without capturing errors, wgpu's default error handler panics when an
+    /// error is reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Gpu`] if the device reported an error while
+    /// `operation` was executing.
+    pub fn capture_errors<R>(
+        &mut self,
+        operation: impl FnOnce(&mut Self) -> R,
+    ) -> Result<R, Error> {
+        let scopes = ErrorScopes::push(self.device);
+        let result = operation(self);
+        match scopes.pop() {
+            Some(err) => Err(Error::Gpu(err)),
+            None => Ok(result),
+        }
+    }
+
     /// Returns a mutable reference to the [`cosmic_text::FontSystem`] used when
     /// rendering text.
     #[cfg(feature = "cosmic-text")]
@@ -626,6 +1699,83 @@ impl<'gfx> Graphics<'gfx> {
         self.kludgine.font_system()
     }
 
+    /// Immediately renders `source` into `texture` using `render`, after
+    /// clearing `texture` to transparent.
+    ///
+    /// Kludgine's pipeline requires the target to match its multisample count,
+    /// so when multisampling is enabled, `source` is rendered into
+    /// `multisampled` and resolved into `texture`. `multisampled` is created
+    /// or replaced when it is missing or no longer matches `texture`, allowing
+    /// callers that render repeatedly to reuse it.
+    pub(crate) fn render_into_texture<T>(
+        &mut self,
+        texture: &Texture,
+        multisampled: &mut Option<wgpu::Texture>,
+        label: &str,
+        source: &T,
+        render: impl for<'pass> FnOnce(&'pass T, &mut RenderingGraphics<'_, 'pass>),
+    ) where
+        T: ?Sized,
+    {
+        let size = texture.size();
+        let format = self.kludgine.format;
+        let sample_count = self.kludgine.multisample.count;
+        if sample_count > 1 {
+            let reusable = multisampled.as_ref().is_some_and(|multisampled| {
+                multisampled.sample_count() == sample_count
+                    && multisampled.format() == format
+                    && Size::upx(multisampled.width(), multisampled.height()) == size
+            });
+            if !reusable {
+                *multisampled = Some(self.device.create_texture(&wgpu::TextureDescriptor {
+                    label: Some(&format!("{label} (multisampled)")),
+                    size: size.into(),
+                    mip_level_count: 1,
+                    sample_count,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                    view_formats: &[],
+                }));
+            }
+        } else {
+            *multisampled = None;
+        }
+        let multisampled = multisampled
+            .as_ref()
+            .map(|multisampled| multisampled.create_view(&wgpu::TextureViewDescriptor::default()));
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        let pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(label),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: multisampled.as_ref().unwrap_or(texture.view()),
+                resolve_target: multisampled.as_ref().map(|_| texture.view()),
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        let mut rendering = RenderingGraphics::new(
+            pass,
+            self.kludgine,
+            false,
+            size,
+            size.into(),
+            self.device,
+            self.queue,
+        );
+        render(source, &mut rendering);
+        drop(rendering);
+        self.queue.submit([encoder.finish()]);
+    }
+
     /// Returns the current clipped size of the context.
     ///
     /// If this context has not been clipped, the value returned will be
@@ -651,6 +1801,13 @@ impl<'gfx> Graphics<'gfx> {
     pub fn set_zoom(&mut self, new_zoom: impl Into<Fraction>) {
         self.kludgine.set_zoom(new_zoom, self.queue);
     }
+
+    /// Marks `region` as changed since the previous frame.
+    ///
+    /// See [`Frame::add_damage()`] for more information.
+    pub fn add_damage(&mut self, region: Rect<UPx>) {
+        self.kludgine.add_damage(region);
+    }
 }
 
 impl AsRef<wgpu::Device> for Graphics<'_> {
@@ -684,8 +1841,20 @@ impl Clipped for Graphics<'_> {
         self.clip.push_clip(clip);
     }
 
-    fn pop_clip(&mut self) {
-        self.clip.pop_clip();
+    fn push_rotated_clip(&mut self, clip: Rect<UPx>, angle: Angle) {
+        self.clip.push_rotated_clip(clip, angle);
+    }
+
+    fn try_pop_clip(&mut self) -> Result<(), UnpairedPopClip> {
+        self.clip.try_pop_clip()
+    }
+
+    fn clip_depth(&self) -> usize {
+        self.clip.depth()
+    }
+
+    fn clip_stack(&self) -> Vec<Rect<UPx>> {
+        self.clip.rects()
     }
 }
 
@@ -704,26 +1873,71 @@ pub struct RenderingGraphics<'gfx, 'pass> {
     device: &'gfx wgpu::Device,
     queue: &'gfx wgpu::Queue,
     clip: ClipStack,
+    translation: Point<Px>,
     pipeline_is_active: bool,
+    depth_tested: bool,
+    target_size: Size<UPx>,
+    target_scale: Point<f32>,
+    damage: Option<Rect<UPx>>,
 }
 
 impl<'gfx, 'pass> RenderingGraphics<'gfx, 'pass> {
+    #[allow(clippy::too_many_arguments)]
     fn new(
-        pass: wgpu::RenderPass<'pass>,
+        mut pass: wgpu::RenderPass<'pass>,
         kludgine: &'pass Kludgine,
+        depth_tested: bool,
+        target_size: Size<UPx>,
+        region: Rect<UPx>,
         device: &'gfx wgpu::Device,
         queue: &'gfx wgpu::Queue,
     ) -> Self {
+        if region != Rect::from(target_size) {
+            pass.set_scissor_rect(
+                region.origin.x.get(),
+                region.origin.y.get(),
+                region.size.width.get(),
+                region.size.height.get(),
+            );
+        }
         Self {
             pass,
-            clip: ClipStack::new(kludgine.size),
+            clip: ClipStack::from_rect(region),
+            translation: Point::default(),
             kludgine,
             device,
             queue,
             pipeline_is_active: false,
+            depth_tested,
+            target_size,
+            target_scale: Point::new(
+                kludgine.size.width.get().cast::<f32>() / target_size.width.get().cast::<f32>(),
+                kludgine.size.height.get().cast::<f32>() / target_size.height.get().cast::<f32>(),
+            ),
+            damage: None,
         }
     }
 
+    /// Returns the region of the target that is being redrawn, or `None` if
+    /// the entire target is being redrawn.
+    ///
+    /// When a region is returned, everything outside of it is left untouched
+    /// regardless of what is drawn. See [`Frame::add_damage()`] for more
+    /// information.
+    #[must_use]
+    pub const fn damaged_region(&self) -> Option<Rect<UPx>> {
+        self.damage
+    }
+
+    /// Returns true if this context's render pass has a depth attachment.
+    ///
+    /// Custom pipelines used with this context must be created with a depth
+    /// stencil state using [`Kludgine::DEPTH_FORMAT`] when this returns true.
+    #[must_use]
+    pub const fn depth_tested(&self) -> bool {
+        self.depth_tested
+    }
+
     /// Returns a reference to the underlying [`wgpu::Device`].
     #[must_use]
     pub const fn device(&self) -> &'gfx wgpu::Device {
@@ -753,7 +1967,13 @@ impl<'gfx, 'pass> RenderingGraphics<'gfx, 'pass> {
             false
         } else {
             self.pipeline_is_active = true;
-            self.pass.set_pipeline(&self.kludgine.pipeline);
+            self.pass.set_pipeline(if self.depth_tested {
+                &self.kludgine.depth_pipeline
+            } else {
+                &self.kludgine.pipeline
+            });
+            self.pass
+                .set_bind_group(1, &self.kludgine.default_lookup, &[]);
             true
         }
     }
@@ -773,6 +1993,39 @@ impl<'gfx, 'pass> RenderingGraphics<'gfx, 'pass> {
         ClipGuard { clipped: self }
     }
 
+    /// Returns a [`TranslationGuard`] that offsets all graphics rendered by
+    /// this context by `offset` until it is dropped.
+    ///
+    /// Unlike [`clipped_to()`](Self::clipped_to), the clipping rect is not
+    /// changed, and `offset` may be negative. This allows scrolling previously
+    /// prepared graphics within a clip rect without preparing them again.
+    /// Translations are cumulative: translating an already translated context
+    /// adds `offset` to the current translation.
+    ///
+    /// The translation is applied by [`PreparedGraphic`]s,
+    /// [`Drawing`](drawing::Drawing)s, and text. Custom
+    /// [`RenderOperation`](drawing::RenderOperation)s can apply it using
+    /// [`translation()`](Self::translation).
+    pub fn translated<Unit>(&mut self, offset: Point<Unit>) -> TranslationGuard<'_, 'gfx, 'pass>
+    where
+        Unit: ScreenUnit,
+    {
+        let previous = self.translation;
+        self.translation += offset.into_px(self.scale());
+        TranslationGuard {
+            graphics: self,
+            previous,
+        }
+    }
+
+    /// Returns the offset applied to all graphics rendered by this context.
+    ///
+    /// See [`translated()`](Self::translated) for more information.
+    #[must_use]
+    pub const fn translation(&self) -> Point<Px> {
+        self.translation
+    }
+
     /// Returns the current size of the graphics area being rendered to.
     ///
     /// If the graphics has been clipped, this returns the current width of the
@@ -809,15 +2062,63 @@ pub trait Clipped: Sized + sealed::Clipped {
     ///
     /// To restore the clipping rect to the state it was before this function
     /// was called, use [`Clipped::pop_clip()`].
+    ///
+    /// There is no limit to how deeply clips can be nested. Debug builds panic
+    /// when clips are nested thousands of levels deep, as this usually means
+    /// that clips are being pushed without being popped.
     fn push_clip(&mut self, clip: Rect<UPx>);
+    /// Pushes a new clipping state to the clipping stack that clips to `clip`
+    /// rotated by `angle` around its center.
+    ///
+    /// `clip` is relative to the current clip rect. The new clip rect is the
+    /// smallest rectangle that contains the rotated rect, limited to the
+    /// current clip rect. Like [`Clipped::push_clip()`], the origin of the
+    /// context becomes the new clip rect's origin.
+    ///
+    /// When the rotated rect is aligned to the pixel grid, such as when
+    /// `angle` is a multiple of 180 degrees, only the clip rect is used.
+    /// Otherwise, graphics are also clipped to the rotated rect as they are
+    /// rendered, and the rotated edges are anti-aliased. Only the most
+    /// recently pushed rotated clip is applied this way: previously pushed
+    /// rotated clips only clip to the rectangles that contain them.
+    ///
+    /// Rotated clips are applied by the shaders used to render
+    /// [`PreparedGraphic`]s, [`Drawing`](drawing::Drawing)s, and text. Custom
+    /// [`RenderOperation`](drawing::RenderOperation)s that use their own
+    /// pipelines are only clipped to the clip rect.
+    ///
+    /// To restore the clipping rect to the state it was before this function
+    /// was called, use [`Clipped::pop_clip()`].
+    fn push_rotated_clip(&mut self, clip: Rect<UPx>, angle: Angle);
     /// Restores the clipping rect to the previous state before the last call to
     /// [`Clipped::push_clip()`].
     ///
     /// # Panics
     ///
     /// This function will panic if it is called more times than
+    /// [`Clipped::push_clip()`]. Use [`Clipped::try_pop_clip()`] to handle
+    /// this case without panicking.
+    fn pop_clip(&mut self) {
+        if let Err(err) = self.try_pop_clip() {
+            panic!("{err}");
+        }
+    }
+    /// Restores the clipping rect to the previous state before the last call to
     /// [`Clipped::push_clip()`].
-    fn pop_clip(&mut self);
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnpairedPopClip`] without changing the clipping rect if this
+    /// function is called more times than [`Clipped::push_clip()`].
+    fn try_pop_clip(&mut self) -> Result<(), UnpairedPopClip>;
+    /// Returns the number of clips that have been pushed and not yet popped.
+    fn clip_depth(&self) -> usize;
+    /// Returns the clipping rects of this context, starting with the rect the
+    /// context was created with and ending with the current clip rect.
+    ///
+    /// Each rect is relative to the surface being rendered to. The returned
+    /// list contains [`Clipped::clip_depth()`] + 1 rects.
+    fn clip_stack(&self) -> Vec<Rect<UPx>>;
 
     /// Returns a [`ClipGuard`] that causes all drawing operations to be offset
     /// and clipped to `clip` until it is dropped.
@@ -833,31 +2134,63 @@ pub trait Clipped: Sized + sealed::Clipped {
         self.push_clip(clip);
         ClipGuard { clipped: self }
     }
+
+    /// Returns a [`ClipGuard`] that causes all drawing operations to be offset
+    /// and clipped to `clip` rotated by `angle` around its center until it is
+    /// dropped.
+    ///
+    /// See [`Clipped::push_rotated_clip()`] for more information.
+    fn rotated_clipped_to(&mut self, clip: Rect<UPx>, angle: Angle) -> ClipGuard<'_, Self> {
+        self.push_rotated_clip(clip, angle);
+        ClipGuard { clipped: self }
+    }
 }
 
-impl Clipped for RenderingGraphics<'_, '_> {
-    fn pop_clip(&mut self) {
-        self.clip.pop_clip();
+impl RenderingGraphics<'_, '_> {
+    /// Returns `clip` limited to the region being redrawn.
+    fn scissor_rect(&self, clip: Rect<UPx>) -> Rect<UPx> {
+        match self.damage {
+            Some(damage) => clip.intersection(&damage).unwrap_or_default(),
+            None => clip,
+        }
+    }
+
+    fn update_scissor_rect(&mut self) {
         if self.clip.current.size.width > 0 && self.clip.current.size.height > 0 {
+            let scissor = self.scissor_rect(self.clip.current.0);
             self.pass.set_scissor_rect(
-                self.clip.current.origin.x.into(),
-                self.clip.current.origin.y.into(),
-                self.clip.current.size.width.into(),
-                self.clip.current.size.height.into(),
+                scissor.origin.x.into(),
+                scissor.origin.y.into(),
+                scissor.size.width.into(),
+                scissor.size.height.into(),
             );
         }
     }
+}
+
+impl Clipped for RenderingGraphics<'_, '_> {
+    fn try_pop_clip(&mut self) -> Result<(), UnpairedPopClip> {
+        self.clip.try_pop_clip()?;
+        self.update_scissor_rect();
+        Ok(())
+    }
+
+    fn clip_depth(&self) -> usize {
+        self.clip.depth()
+    }
+
+    fn clip_stack(&self) -> Vec<Rect<UPx>> {
+        self.clip.rects()
+    }
 
     fn push_clip(&mut self, clip: Rect<UPx>) {
         self.clip.push_clip(clip);
-        if self.clip.current.size.width > 0 && self.clip.current.size.height > 0 {
-            self.pass.set_scissor_rect(
-                self.clip.current.origin.x.into(),
-                self.clip.current.origin.y.into(),
-                self.clip.current.size.width.into(),
-                self.clip.current.size.height.into(),
-            );
-        }
+        self.update_scissor_rect();
+    }
+
+    fn push_rotated_clip(&mut self, clip: Rect<UPx>, angle: Angle) {
+        self.clip.push_rotated_clip(clip, angle);
+        self.update_scissor_rect();
     }
 }
 
@@ -871,8 +2204,8 @@ impl Drop for RenderingGraphics<'_, '_> {
         self.pass.set_scissor_rect(
             0,
             0,
-            self.kludgine.size.width.get(),
-            self.kludgine.size.height.get(),
+            self.target_size.width.get(),
+            self.target_size.height.get(),
         );
     }
 }
@@ -917,12 +2250,72 @@ where
     T: Clipped,
 {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        self.clipped
+        self.clipped
+    }
+}
+
+/// A translated [`RenderingGraphics`].
+///
+/// When dropped, the translation is restored to its value before
+/// [`RenderingGraphics::translated()`] was called. [`TranslationGuard`]s can be
+/// nested.
+///
+/// This type implements [`Deref`]/[`DerefMut`] to provide access to the
+/// underyling [`RenderingGraphics`].
+pub struct TranslationGuard<'guard, 'gfx, 'pass> {
+    graphics: &'guard mut RenderingGraphics<'gfx, 'pass>,
+    previous: Point<Px>,
+}
+
+impl Drop for TranslationGuard<'_, '_, '_> {
+    fn drop(&mut self) {
+        self.graphics.translation = self.previous;
+    }
+}
+
+impl<'gfx, 'pass> Deref for TranslationGuard<'_, 'gfx, 'pass> {
+    type Target = RenderingGraphics<'gfx, 'pass>;
+
+    fn deref(&self) -> &Self::Target {
+        self.graphics
+    }
+}
+
+impl DerefMut for TranslationGuard<'_, '_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.graphics
     }
 }
 
+#[test]
+#[cfg(feature = "app")]
+fn translated_drawings_reveal_offscreen_content() {
+    use figures::Px2D;
+
+    let Ok(mut window) = app::headless_window(Size::upx(64, 64), |mut renderer, _window| {
+        // Only the top-left quarter is inside of the window when recorded.
+        renderer.draw_shape(&shapes::Shape::filled_rect(
+            Rect::new(Point::px(44, 44), Size::px(40, 40)),
+            Color::BLUE,
+        ));
+        true
+    }) else {
+        // No graphics adapter is available.
+        return;
+    };
+    window.behavior_mut().set_translation(Point::px(-20, -20));
+    window.render();
+    let pixels = window.read_pixels().expect("pixels read");
+    let pixel = |x: usize, y: usize| &pixels[(y * 64 + x) * 4..(y * 64 + x + 1) * 4];
+    assert_eq!(pixel(10, 10), [0, 0, 0, 255]);
+    assert_eq!(pixel(30, 30), [0, 0, 255, 255]);
+    // Recorded at (80, 80), outside of the window.
+    assert_eq!(pixel(60, 60), [0, 0, 255, 255]);
+}
+
 /// A red, green, blue, and alpha color value stored in 32-bits.
 #[derive(Clone, Copy, Eq, PartialEq, Hash, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Color(pub u32);
 
@@ -1050,6 +2443,181 @@ impl Color {
     pub fn with_alpha_f32(self, alpha: f32) -> Self {
         self.with_alpha(f32_component_to_u8(alpha))
     }
+
+    /// Returns the linear interpolation between this color and `other` by
+    /// interpolating each sRGB component independently.
+    ///
+    /// `fraction` is clamped to `0.0..=1.0`. A value of 0.0 returns `self` and
+    /// 1.0 returns `other`. For smoother transitions between different hues,
+    /// see [`mix_oklab()`](Self::mix_oklab).
+    #[must_use]
+    pub fn lerp(self, other: Color, fraction: f32) -> Self {
+        let fraction = fraction.clamp(0., 1.);
+        let lerp = |a: f32, b: f32| a + (b - a) * fraction;
+        Self::new_f32(
+            lerp(self.red_f32(), other.red_f32()),
+            lerp(self.green_f32(), other.green_f32()),
+            lerp(self.blue_f32(), other.blue_f32()),
+            lerp(self.alpha_f32(), other.alpha_f32()),
+        )
+    }
+
+    /// Returns the mix of this color and `other` in linear RGB.
+    ///
+    /// `fraction` is clamped to `0.0..=1.0`. A value of 0.0 returns `self` and
+    /// 1.0 returns `other`. Mixing in linear RGB matches how light blends,
+    /// which avoids the dark midpoints produced by [`lerp()`](Self::lerp).
+    #[must_use]
+    pub fn mix(self, other: Color, fraction: f32) -> Self {
+        use palette::Mix;
+
+        let fraction = fraction.clamp(0., 1.);
+        Self::from_linear_srgba(
+            self.into_linear_srgba()
+                .mix(other.into_linear_srgba(), fraction),
+        )
+    }
+
+    /// Returns the mix of this color and `other` in the perceptual
+    /// [Oklab](https://bottosson.github.io/posts/oklab/) color space.
+    ///
+    /// `fraction` is clamped to `0.0..=1.0`. A value of 0.0 returns `self` and
+    /// 1.0 returns `other`. Unlike [`lerp()`](Self::lerp), intermediate colors
+    /// keep a consistent perceived lightness and avoid the muddy midpoints of
+    /// interpolating in sRGB.
+    #[must_use]
+    pub fn mix_oklab(self, other: Color, fraction: f32) -> Self {
+        use palette::{IntoColor, Mix};
+
+        let fraction = fraction.clamp(0., 1.);
+        let start: palette::Oklaba = self.into_linear_srgba().into_color();
+        let end: palette::Oklaba = other.into_linear_srgba().into_color();
+        Self::from_linear_srgba(start.mix(end, fraction).into_color())
+    }
+
+    /// Returns this color with its perceived lightness increased by `amount`.
+    ///
+    /// Lightness is adjusted in the Oklab color space, where it ranges from 0.0
+    /// (black) to 1.0 (white). The result is clamped to that range, and the
+    /// hue and alpha are preserved.
+    #[must_use]
+    pub fn lighten(self, amount: f32) -> Self {
+        self.with_lightness(self.lightness() + amount)
+    }
+
+    /// Returns this color with its perceived lightness decreased by `amount`.
+    ///
+    /// See [`lighten()`](Self::lighten) for more information.
+    #[must_use]
+    pub fn darken(self, amount: f32) -> Self {
+        self.lighten(-amount)
+    }
+
+    /// Returns the perceived lightness of this color, ranging from 0.0 (black)
+    /// to 1.0 (white).
+    #[must_use]
+    pub fn lightness(self) -> f32 {
+        use palette::IntoColor;
+
+        let oklab: palette::Oklaba = self.into_linear_srgba().into_color();
+        oklab.l
+    }
+
+    /// Returns this color with its perceived lightness set to `lightness`.
+    ///
+    /// Lightness is adjusted in the Oklab color space, where it ranges from 0.0
+    /// (black) to 1.0 (white). `lightness` is clamped to that range, and the
+    /// hue and alpha are preserved.
+    #[must_use]
+    pub fn with_lightness(self, lightness: f32) -> Self {
+        use palette::IntoColor;
+
+        let mut oklab: palette::Oklaba = self.into_linear_srgba().into_color();
+        oklab.l = lightness.clamp(0., 1.);
+        Self::from_linear_srgba(oklab.into_color())
+    }
+
+    /// Returns the [relative luminance][luminance] of this color, ranging from
+    /// 0.0 (black) to 1.0 (white). Alpha is ignored.
+    ///
+    /// [luminance]: https://www.w3.org/TR/WCAG21/#dfn-relative-luminance
+    #[must_use]
+    pub fn relative_luminance(self) -> f32 {
+        let linear = self.into_linear_srgba();
+        0.2126 * linear.red + 0.7152 * linear.green + 0.0722 * linear.blue
+    }
+
+    /// Returns the [contrast ratio][contrast] between this color and `other`,
+    /// ranging from 1.0 (no contrast) to 21.0 (black and white). Alpha is
+    /// ignored.
+    ///
+    /// The Web Content Accessibility Guidelines recommend a ratio of at least
+    /// 4.5 for body text.
+    ///
+    /// [contrast]: https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio
+    #[must_use]
+    pub fn contrast_ratio(self, other: Color) -> f32 {
+        let a = self.relative_luminance();
+        let b = other.relative_luminance();
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    fn into_srgba(self) -> palette::Srgba {
+        palette::Srgba::new(
+            self.red_f32(),
+            self.green_f32(),
+            self.blue_f32(),
+            self.alpha_f32(),
+        )
+    }
+
+    fn into_linear_srgba(self) -> palette::LinSrgba {
+        self.into_srgba().into_linear()
+    }
+
+    fn from_srgba(srgba: palette::Srgba) -> Self {
+        Self::new_f32(srgba.red, srgba.green, srgba.blue, srgba.alpha)
+    }
+
+    fn from_linear_srgba(linear: palette::LinSrgba) -> Self {
+        Self::from_srgba(palette::Srgba::from_linear(linear))
+    }
+}
+
+impl From<Color> for palette::Hsla {
+    fn from(color: Color) -> Self {
+        palette::FromColor::from_color(color.into_srgba())
+    }
+}
+
+impl From<palette::Hsla> for Color {
+    fn from(hsla: palette::Hsla) -> Self {
+        Self::from_srgba(palette::FromColor::from_color(hsla))
+    }
+}
+
+impl From<Color> for palette::Hsva {
+    fn from(color: Color) -> Self {
+        palette::FromColor::from_color(color.into_srgba())
+    }
+}
+
+impl From<palette::Hsva> for Color {
+    fn from(hsva: palette::Hsva) -> Self {
+        Self::from_srgba(palette::FromColor::from_color(hsva))
+    }
+}
+
+impl From<Color> for palette::Oklcha {
+    fn from(color: Color) -> Self {
+        palette::FromColor::from_color(color.into_linear_srgba())
+    }
+}
+
+impl From<palette::Oklcha> for Color {
+    fn from(oklcha: palette::Oklcha) -> Self {
+        Self::from_linear_srgba(palette::FromColor::from_color(oklcha))
+    }
 }
 
 fn srgb_to_linear(red: f32, green: f32, blue: f32, alpha: f32) -> Color {
@@ -1093,6 +2661,34 @@ fn color_debug() {
     assert_eq!(format!("{:?}", Color::new(1, 2, 3, 4)), "#01020304");
 }
 
+#[test]
+fn color_interpolation() {
+    let start = Color::new(255, 0, 0, 255);
+    let end = Color::new(0, 0, 255, 0);
+    assert_eq!(start.lerp(end, 0.), start);
+    assert_eq!(start.lerp(end, 1.), end);
+    assert_eq!(start.lerp(end, 0.5), Color::new(128, 0, 128, 128));
+    assert_eq!(start.mix_oklab(end, 0.), start);
+    assert_eq!(start.mix_oklab(end, 1.), end);
+    assert_eq!(Color::WHITE.mix_oklab(Color::WHITE, 0.5), Color::WHITE);
+    assert_eq!(start.mix(end, 0.), start);
+    assert_eq!(start.mix(end, 1.), end);
+}
+
+#[test]
+fn color_conversions() {
+    let color = Color::new(51, 102, 204, 128);
+    assert_eq!(Color::from(palette::Hsla::from(color)), color);
+    assert_eq!(Color::from(palette::Hsva::from(color)), color);
+    assert_eq!(Color::from(palette::Oklcha::from(color)), color);
+    let hsl = palette::Hsla::from(Color::new(255, 0, 0, 255));
+    assert_eq!(hsl.hue.into_degrees().round(), 0.);
+    assert!((hsl.lightness - 0.5).abs() < 0.001);
+    assert!((Color::BLACK.contrast_ratio(Color::WHITE) - 21.).abs() < 0.001);
+    assert!((Color::WHITE.contrast_ratio(Color::WHITE) - 1.).abs() < 0.001);
+    assert!((Color::GRAY.with_lightness(0.25).lightness() - 0.25).abs() < 0.01);
+}
+
 impl Color {
     /// Equivalent to the [CSS color keywords](https://developer.mozilla.org/en-US/docs/Web/CSS/named-color) of the same name.
     pub const ALICEBLUE: Self = Self::new(240, 248, 255, 255);
@@ -1396,6 +2992,342 @@ impl Color {
     pub const YELLOWGREEN: Self = Self::new(154, 205, 50, 255);
 }
 
+impl Color {
+    fn from_css_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "transparent" => Self::CLEAR_BLACK,
+            "aliceblue" => Self::ALICEBLUE,
+            "antiquewhite" => Self::ANTIQUEWHITE,
+            "aqua" => Self::AQUA,
+            "aquamarine" => Self::AQUAMARINE,
+            "azure" => Self::AZURE,
+            "beige" => Self::BEIGE,
+            "bisque" => Self::BISQUE,
+            "black" => Self::BLACK,
+            "blanchedalmond" => Self::BLANCHEDALMOND,
+            "blue" => Self::BLUE,
+            "blueviolet" => Self::BLUEVIOLET,
+            "brown" => Self::BROWN,
+            "burlywood" => Self::BURLYWOOD,
+            "cadetblue" => Self::CADETBLUE,
+            "chartreuse" => Self::CHARTREUSE,
+            "chocolate" => Self::CHOCOLATE,
+            "coral" => Self::CORAL,
+            "cornflowerblue" => Self::CORNFLOWERBLUE,
+            "cornsilk" => Self::CORNSILK,
+            "crimson" => Self::CRIMSON,
+            "cyan" => Self::CYAN,
+            "darkblue" => Self::DARKBLUE,
+            "darkcyan" => Self::DARKCYAN,
+            "darkgoldenrod" => Self::DARKGOLDENROD,
+            "darkgray" => Self::DARKGRAY,
+            "darkgreen" => Self::DARKGREEN,
+            "darkgrey" => Self::DARKGREY,
+            "darkkhaki" => Self::DARKKHAKI,
+            "darkmagenta" => Self::DARKMAGENTA,
+            "darkolivegreen" => Self::DARKOLIVEGREEN,
+            "darkorange" => Self::DARKORANGE,
+            "darkorchid" => Self::DARKORCHID,
+            "darkred" => Self::DARKRED,
+            "darksalmon" => Self::DARKSALMON,
+            "darkseagreen" => Self::DARKSEAGREEN,
+            "darkslateblue" => Self::DARKSLATEBLUE,
+            "darkslategray" => Self::DARKSLATEGRAY,
+            "darkslategrey" => Self::DARKSLATEGREY,
+            "darkturquoise" => Self::DARKTURQUOISE,
+            "darkviolet" => Self::DARKVIOLET,
+            "deeppink" => Self::DEEPPINK,
+            "deepskyblue" => Self::DEEPSKYBLUE,
+            "dimgray" => Self::DIMGRAY,
+            "dimgrey" => Self::DIMGREY,
+            "dodgerblue" => Self::DODGERBLUE,
+            "firebrick" => Self::FIREBRICK,
+            "floralwhite" => Self::FLORALWHITE,
+            "forestgreen" => Self::FORESTGREEN,
+            "fuchsia" => Self::FUCHSIA,
+            "gainsboro" => Self::GAINSBORO,
+            "ghostwhite" => Self::GHOSTWHITE,
+            "gold" => Self::GOLD,
+            "goldenrod" => Self::GOLDENROD,
+            "gray" => Self::GRAY,
+            "green" => Self::GREEN,
+            "greenyellow" => Self::GREENYELLOW,
+            "grey" => Self::GREY,
+            "honeydew" => Self::HONEYDEW,
+            "hotpink" => Self::HOTPINK,
+            "indianred" => Self::INDIANRED,
+            "indigo" => Self::INDIGO,
+            "ivory" => Self::IVORY,
+            "khaki" => Self::KHAKI,
+            "lavender" => Self::LAVENDER,
+            "lavenderblush" => Self::LAVENDERBLUSH,
+            "lawngreen" => Self::LAWNGREEN,
+            "lemonchiffon" => Self::LEMONCHIFFON,
+            "lightblue" => Self::LIGHTBLUE,
+            "lightcoral" => Self::LIGHTCORAL,
+            "lightcyan" => Self::LIGHTCYAN,
+            "lightgoldenrodyellow" => Self::LIGHTGOLDENRODYELLOW,
+            "lightgray" => Self::LIGHTGRAY,
+            "lightgreen" => Self::LIGHTGREEN,
+            "lightgrey" => Self::LIGHTGREY,
+            "lightpink" => Self::LIGHTPINK,
+            "lightsalmon" => Self::LIGHTSALMON,
+            "lightseagreen" => Self::LIGHTSEAGREEN,
+            "lightskyblue" => Self::LIGHTSKYBLUE,
+            "lightslategray" => Self::LIGHTSLATEGRAY,
+            "lightslategrey" => Self::LIGHTSLATEGREY,
+            "lightsteelblue" => Self::LIGHTSTEELBLUE,
+            "lightyellow" => Self::LIGHTYELLOW,
+            "lime" => Self::LIME,
+            "limegreen" => Self::LIMEGREEN,
+            "linen" => Self::LINEN,
+            "magenta" => Self::MAGENTA,
+            "maroon" => Self::MAROON,
+            "mediumaquamarine" => Self::MEDIUMAQUAMARINE,
+            "mediumblue" => Self::MEDIUMBLUE,
+            "mediumorchid" => Self::MEDIUMORCHID,
+            "mediumpurple" => Self::MEDIUMPURPLE,
+            "mediumseagreen" => Self::MEDIUMSEAGREEN,
+            "mediumslateblue" => Self::MEDIUMSLATEBLUE,
+            "mediumspringgreen" => Self::MEDIUMSPRINGGREEN,
+            "mediumturquoise" => Self::MEDIUMTURQUOISE,
+            "mediumvioletred" => Self::MEDIUMVIOLETRED,
+            "midnightblue" => Self::MIDNIGHTBLUE,
+            "mintcream" => Self::MINTCREAM,
+            "mistyrose" => Self::MISTYROSE,
+            "moccasin" => Self::MOCCASIN,
+            "navajowhite" => Self::NAVAJOWHITE,
+            "navy" => Self::NAVY,
+            "oldlace" => Self::OLDLACE,
+            "olive" => Self::OLIVE,
+            "olivedrab" => Self::OLIVEDRAB,
+            "orange" => Self::ORANGE,
+            "orangered" => Self::ORANGERED,
+            "orchid" => Self::ORCHID,
+            "palegoldenrod" => Self::PALEGOLDENROD,
+            "palegreen" => Self::PALEGREEN,
+            "paleturquoise" => Self::PALETURQUOISE,
+            "palevioletred" => Self::PALEVIOLETRED,
+            "papayawhip" => Self::PAPAYAWHIP,
+            "peachpuff" => Self::PEACHPUFF,
+            "peru" => Self::PERU,
+            "pink" => Self::PINK,
+            "plum" => Self::PLUM,
+            "powderblue" => Self::POWDERBLUE,
+            "purple" => Self::PURPLE,
+            "rebeccapurple" => Self::REBECCAPURPLE,
+            "red" => Self::RED,
+            "rosybrown" => Self::ROSYBROWN,
+            "royalblue" => Self::ROYALBLUE,
+            "saddlebrown" => Self::SADDLEBROWN,
+            "salmon" => Self::SALMON,
+            "sandybrown" => Self::SANDYBROWN,
+            "seagreen" => Self::SEAGREEN,
+            "seashell" => Self::SEASHELL,
+            "sienna" => Self::SIENNA,
+            "silver" => Self::SILVER,
+            "skyblue" => Self::SKYBLUE,
+            "slateblue" => Self::SLATEBLUE,
+            "slategray" => Self::SLATEGRAY,
+            "slategrey" => Self::SLATEGREY,
+            "snow" => Self::SNOW,
+            "springgreen" => Self::SPRINGGREEN,
+            "steelblue" => Self::STEELBLUE,
+            "tan" => Self::TAN,
+            "teal" => Self::TEAL,
+            "thistle" => Self::THISTLE,
+            "tomato" => Self::TOMATO,
+            "turquoise" => Self::TURQUOISE,
+            "violet" => Self::VIOLET,
+            "wheat" => Self::WHEAT,
+            "white" => Self::WHITE,
+            "whitesmoke" => Self::WHITESMOKE,
+            "yellow" => Self::YELLOW,
+            "yellowgreen" => Self::YELLOWGREEN,
+            _ => return None,
+        })
+    }
+}
+
+/// Parses a CSS color.
+///
+/// The supported formats are:
+///
+/// - Hexadecimal colors: `#rgb`, `#rgba`, `#rrggbb`, and `#rrggbbaa`.
+/// - `rgb()` and `rgba()`, with red, green, and blue components specified as
+///   numbers from 0 to 255 or as percentages.
+/// - `hsl()` and `hsla()`, with a hue specified in degrees, radians, or turns,
+///   and saturation and lightness specified as percentages.
+/// - The [named colors][named] and `transparent`.
+///
+/// Functions accept their arguments separated by commas or by spaces, and an
+/// optional alpha component specified as a number from 0.0 to 1.0 or as a
+/// percentage. Parsing is case insensitive.
+///
+/// [named]: https://developer.mozilla.org/en-US/docs/Web/CSS/named-color
+impl FromStr for Color {
+    type Err = ColorParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(hex) = s.strip_prefix('#') {
+            return parse_hex_color(hex);
+        }
+
+        let s = s.to_ascii_lowercase();
+        let Some((function, arguments)) = s.strip_suffix(')').and_then(|s| s.split_once('('))
+        else {
+            return Self::from_css_name(&s).ok_or(ColorParseError::UnknownName);
+        };
+        let arguments = arguments
+            .split(|ch: char| ch == ',' || ch == '/' || ch.is_ascii_whitespace())
+            .filter(|arg| !arg.is_empty())
+            .collect::<Vec<_>>();
+        let (components, alpha) = match arguments.as_slice() {
+            [a, b, c] => ([*a, *b, *c], 1.),
+            [a, b, c, alpha] => ([*a, *b, *c], parse_css_alpha(alpha)?),
+            _ => return Err(ColorParseError::InvalidArguments),
+        };
+
+        match function.trim_end() {
+            "rgb" | "rgba" => Ok(Self::new_f32(
+                parse_css_channel(components[0])?,
+                parse_css_channel(components[1])?,
+                parse_css_channel(components[2])?,
+                alpha,
+            )),
+            "hsl" | "hsla" => {
+                let hsla: palette::Hsla = palette::Hsla::new(
+                    parse_css_hue(components[0])?,
+                    parse_css_percentage(components[1])?,
+                    parse_css_percentage(components[2])?,
+                    alpha,
+                );
+                Ok(Self::from(hsla))
+            }
+            _ => Err(ColorParseError::UnknownFunction),
+        }
+    }
+}
+
+fn parse_hex_color(hex: &str) -> Result<Color, ColorParseError> {
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(ColorParseError::InvalidHex);
+    }
+    // Each digit of the short forms is repeated, which is equivalent to
+    // multiplying by 17.
+    let digit = |index: usize| u8::from_str_radix(&hex[index..=index], 16).map_or(0, |d| d * 17);
+    let value = || u32::from_str_radix(hex, 16).map_err(|_| ColorParseError::InvalidHex);
+    match hex.len() {
+        3 => Ok(Color::new(digit(0), digit(1), digit(2), 255)),
+        4 => Ok(Color::new(digit(0), digit(1), digit(2), digit(3))),
+        6 => Ok(Color((value()? << 8) | 0xFF)),
+        8 => Ok(Color(value()?)),
+        _ => Err(ColorParseError::InvalidHex),
+    }
+}
+
+fn parse_css_number(value: &str) -> Result<f32, ColorParseError> {
+    value
+        .parse::<f32>()
+        .ok()
+        .filter(|value| value.is_finite())
+        .ok_or(ColorParseError::InvalidArguments)
+}
+
+fn parse_css_channel(value: &str) -> Result<f32, ColorParseError> {
+    if let Some(percent) = value.strip_suffix('%') {
+        Ok(parse_css_number(percent)? / 100.)
+    } else {
+        Ok(parse_css_number(value)? / 255.)
+    }
+}
+
+fn parse_css_alpha(value: &str) -> Result<f32, ColorParseError> {
+    if let Some(percent) = value.strip_suffix('%') {
+        Ok(parse_css_number(percent)? / 100.)
+    } else {
+        parse_css_number(value)
+    }
+}
+
+fn parse_css_percentage(value: &str) -> Result<f32, ColorParseError> {
+    let value = value.strip_suffix('%').unwrap_or(value);
+    Ok((parse_css_number(value)? / 100.).clamp(0., 1.))
+}
+
+fn parse_css_hue(value: &str) -> Result<f32, ColorParseError> {
+    if let Some(turns) = value.strip_suffix("turn") {
+        Ok(parse_css_number(turns)? * 360.)
+    } else if let Some(radians) = value.strip_suffix("rad") {
+        Ok(parse_css_number(radians)?.to_degrees())
+    } else {
+        parse_css_number(value.strip_suffix("deg").unwrap_or(value))
+    }
+}
+
+/// An error parsing a [`Color`] from a string.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ColorParseError {
+    /// A color beginning with `#` did not contain 3, 4, 6, or 8 hexadecimal
+    /// digits.
+    InvalidHex,
+    /// The color was not a recognized color name.
+    UnknownName,
+    /// The color function was not `rgb()`, `rgba()`, `hsl()`, or `hsla()`.
+    UnknownFunction,
+    /// The color function's arguments were missing or invalid.
+    InvalidArguments,
+}
+
+impl fmt::Display for ColorParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ColorParseError::InvalidHex => f.write_str("invalid hexadecimal color"),
+            ColorParseError::UnknownName => f.write_str("unknown color name"),
+            ColorParseError::UnknownFunction => f.write_str("unknown color function"),
+            ColorParseError::InvalidArguments => f.write_str("invalid color function arguments"),
+        }
+    }
+}
+
+impl std::error::Error for ColorParseError {}
+
+#[test]
+fn color_parsing() {
+    assert_eq!("#f0a".parse(), Ok(Color::new(255, 0, 170, 255)));
+    assert_eq!("#f0a8".parse(), Ok(Color::new(255, 0, 170, 136)));
+    assert_eq!("#FF8000".parse(), Ok(Color::new(255, 128, 0, 255)));
+    assert_eq!("#ff800080".parse(), Ok(Color::new(255, 128, 0, 128)));
+    assert_eq!("rgb(255, 128, 0)".parse(), Ok(Color::new(255, 128, 0, 255)));
+    assert_eq!(
+        "rgba(100%, 0%, 0%, 0.5)".parse(),
+        Ok(Color::new(255, 0, 0, 128))
+    );
+    assert_eq!("rgb(0 0 255 / 50%)".parse(), Ok(Color::new(0, 0, 255, 128)));
+    assert_eq!(
+        "hsl(120, 100%, 50%)".parse(),
+        Ok(Color::new(0, 255, 0, 255))
+    );
+    assert_eq!("hsla(0.5turn 100% 50% / 1)".parse(), Ok(Color::CYAN));
+    assert_eq!(" RebeccaPurple ".parse(), Ok(Color::REBECCAPURPLE));
+    assert_eq!("transparent".parse(), Ok(Color::CLEAR_BLACK));
+    assert_eq!("#12345".parse::<Color>(), Err(ColorParseError::InvalidHex));
+    assert_eq!("#+1234".parse::<Color>(), Err(ColorParseError::InvalidHex));
+    assert_eq!(
+        "blurple".parse::<Color>(),
+        Err(ColorParseError::UnknownName)
+    );
+    assert_eq!(
+        "cmyk(0, 0, 0, 0)".parse::<Color>(),
+        Err(ColorParseError::UnknownFunction)
+    );
+    assert_eq!(
+        "rgb(1, 2)".parse::<Color>(),
+        Err(ColorParseError::InvalidArguments)
+    );
+}
+
 /// A [`TextureSource`] that loads its data lazily.
 ///
 /// This texture type can be shared between multiple [`wgpu::Device`]s. When a
@@ -1431,14 +3363,42 @@ impl LazyTexture {
         }
     }
 
+    /// Returns a new texture that loads its RGBA8 `data` to the gpu once
+    /// used. `data` is converted from `encoding` as needed.
+    #[must_use]
+    pub fn from_encoded_data(
+        size: Size<UPx>,
+        encoding: ColorEncoding,
+        usage: wgpu::TextureUsages,
+        filter_mode: wgpu::FilterMode,
+        mut data: Vec<u8>,
+    ) -> Self {
+        encoding.convert_rgba8(&mut data);
+        Self::from_data(size, encoding.rgba8_format(), usage, filter_mode, data)
+    }
+
     /// Returns a texture that loads `image` into the gpu when it is used.
+    ///
+    /// The image is assumed to contain sRGB colors with straight alpha.
     #[must_use]
     #[cfg(feature = "image")]
     pub fn from_image(image: image::DynamicImage, filter_mode: wgpu::FilterMode) -> Self {
+        Self::from_image_with_encoding(image, ColorEncoding::Srgb, filter_mode)
+    }
+
+    /// Returns a texture that loads `image`, whose colors are encoded using
+    /// `encoding`, into the gpu when it is used.
+    #[must_use]
+    #[cfg(feature = "image")]
+    pub fn from_image_with_encoding(
+        image: image::DynamicImage,
+        encoding: ColorEncoding,
+        filter_mode: wgpu::FilterMode,
+    ) -> Self {
         let image = image.into_rgba8();
-        Self::from_data(
+        Self::from_encoded_data(
             Size::upx(image.width(), image.height()),
-            wgpu::TextureFormat::Rgba8UnormSrgb,
+            encoding,
             wgpu::TextureUsages::TEXTURE_BINDING,
             filter_mode,
             image.into_raw(),
@@ -1466,28 +3426,54 @@ impl LazyTexture {
             return SharedTexture(loaded);
         }
 
-        let wgpu = graphics.device().create_texture_with_data(
-            graphics.queue(),
-            &wgpu::TextureDescriptor {
-                label: None,
-                size: self.data.size.into(),
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: wgpu::TextureDimension::D2,
-                format: self.data.format,
-                usage: self.data.usage,
-                view_formats: &[],
-            },
-            wgpu::util::TextureDataOrder::LayerMajor,
-            &self.data.data,
-        );
+        let descriptor = wgpu::TextureDescriptor {
+            label: Some("kludgine::lazy texture"),
+            size: self.data.size.into(),
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.data.format,
+            usage: self.data.usage | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        };
+        let uploads = graphics
+            .texture_uploads()
+            .filter(|uploads| uploads.accepts(self.data.format, self.data.size));
+        let (wgpu, progress) = if uploads.is_some() {
+            (
+                graphics.device().create_texture(&descriptor),
+                upload::LoadProgress::loading(),
+            )
+        } else {
+            (
+                graphics.device().create_texture_with_data(
+                    graphics.queue(),
+                    &descriptor,
+                    wgpu::util::TextureDataOrder::LayerMajor,
+                    &self.data.data,
+                ),
+                upload::LoadProgress::default(),
+            )
+        };
         let texture = SharedTexture::from(Texture {
             id: self.data.id,
             kludgine: graphics.id(),
             size: self.data.size,
             format: self.data.format,
             data: TextureInstance::from_wgpu(wgpu, false, self.data.filter_mode, graphics),
+            progress: progress.clone(),
         });
+        if let Some(uploads) = uploads {
+            uploads.push(upload::PendingUpload {
+                target: upload::UploadTarget::Texture(Arc::downgrade(&texture.0)),
+                origin: Point::default(),
+                size: self.data.size,
+                format: self.data.format,
+                data: self.data.clone(),
+                layout: wgpu::ImageDataLayout::default(),
+                progress,
+            });
+        }
 
         loaded.insert(graphics.id(), Arc::downgrade(&texture.0));
         *last_loaded = Some((graphics.id(), texture.clone()));
@@ -1500,6 +3486,18 @@ impl LazyTexture {
     pub fn size(&self) -> Size<UPx> {
         self.data.size
     }
+
+    /// Loads this texture to `graphics`, if needed, and returns whether its
+    /// contents have finished uploading.
+    ///
+    /// When [asynchronous texture
+    /// uploads](Kludgine::set_async_texture_uploads) are enabled, this
+    /// texture's contents are uploaded over the frames following its first
+    /// use.
+    #[must_use]
+    pub fn load_state(&self, graphics: &impl KludgineGraphics) -> TextureLoadState {
+        self.upgrade(graphics).load_state()
+    }
 }
 
 impl Clone for LazyTexture {
@@ -1533,6 +3531,10 @@ impl sealed::TextureSource for LazyTexture {
         self.upgrade(graphics).bind_group(graphics)
     }
 
+    fn array_texture(&self, graphics: &impl sealed::KludgineGraphics) -> Option<ArrayTexture> {
+        self.upgrade(graphics).array_texture(graphics)
+    }
+
     fn default_rect(&self) -> Rect<UPx> {
         self.data.size.into()
     }
@@ -1555,6 +3557,12 @@ struct LazyTextureData {
     data: Vec<u8>,
 }
 
+impl AsRef<[u8]> for LazyTextureData {
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
 /// An image stored on the GPU.
 #[derive(Debug)]
 pub struct Texture {
@@ -1563,13 +3571,19 @@ pub struct Texture {
     size: Size<UPx>,
     format: wgpu::TextureFormat,
     data: TextureInstance,
+    progress: upload::LoadProgress,
 }
 
 #[derive(Debug)]
 struct TextureInstance {
     wgpu: wgpu::Texture,
-    view: wgpu::TextureView,
+    view: Arc<wgpu::TextureView>,
     bind_group: Arc<wgpu::BindGroup>,
+    multisampled: bool,
+    linear: bool,
+    /// The bind groups used when other [`Kludgine`] instances render this
+    /// texture, if it is shared between instances.
+    instance_bind_groups: Option<Mutex<HashMap<KludgineId, Arc<wgpu::BindGroup>, DefaultHasher>>>,
 }
 
 enum MaybeRef<'a, T> {
@@ -1593,27 +3607,46 @@ impl TextureInstance {
         filter_mode: wgpu::FilterMode,
         graphics: &impl sealed::KludgineGraphics,
     ) -> Self {
-        let view = wgpu.create_view(&wgpu::TextureViewDescriptor::default());
+        let view = Arc::new(wgpu.create_view(&wgpu::TextureViewDescriptor::default()));
+        let linear = filter_mode == wgpu::FilterMode::Linear;
+        let bind_group = Arc::new(Self::create_bind_group(
+            &view,
+            multisampled,
+            linear,
+            graphics,
+        ));
+        TextureInstance {
+            wgpu,
+            view,
+            bind_group,
+            multisampled,
+            linear,
+            instance_bind_groups: None,
+        }
+    }
+
+    fn create_bind_group(
+        view: &wgpu::TextureView,
+        multisampled: bool,
+        linear: bool,
+        graphics: &impl sealed::KludgineGraphics,
+    ) -> wgpu::BindGroup {
         let layout = if multisampled {
             MaybeRef::Owned(pipeline::bind_group_layout(graphics.device(), multisampled))
         } else {
             MaybeRef::Borrowed(graphics.binding_layout())
         };
-        let bind_group = Arc::new(pipeline::bind_group(
+        pipeline::bind_group(
             graphics.device(),
             layout.as_ref(),
             graphics.uniforms(),
-            &view,
-            match filter_mode {
-                wgpu::FilterMode::Nearest => graphics.nearest_sampler(),
-                wgpu::FilterMode::Linear => graphics.linear_sampler(),
-            },
-        ));
-        TextureInstance {
-            wgpu,
             view,
-            bind_group,
-        }
+            if linear {
+                graphics.linear_sampler()
+            } else {
+                graphics.nearest_sampler()
+            },
+        )
     }
 }
 
@@ -1632,6 +3665,7 @@ impl Texture {
             size,
             format,
             data: TextureInstance::from_wgpu(wgpu, multisampled, filter_mode, graphics),
+            progress: upload::LoadProgress::default(),
         }
     }
 
@@ -1642,9 +3676,10 @@ impl Texture {
         format: wgpu::TextureFormat,
         usage: wgpu::TextureUsages,
         filter_mode: wgpu::FilterMode,
+        label: Option<&str>,
     ) -> Self {
         let wgpu = graphics.device().create_texture(&wgpu::TextureDescriptor {
-            label: None,
+            label,
             size: size.into(),
             mip_level_count: 1,
             sample_count: multisample_count,
@@ -1675,6 +3710,20 @@ impl Texture {
         Self::multisampled(graphics, 1, size, format, usage, filter_mode)
     }
 
+    /// Creates a new texture of the given size, format, and usages. `label`
+    /// is shown in graphics debugging tools.
+    #[must_use]
+    pub fn new_labeled(
+        graphics: &Graphics<'_>,
+        label: &str,
+        size: Size<UPx>,
+        format: wgpu::TextureFormat,
+        usage: wgpu::TextureUsages,
+        filter_mode: wgpu::FilterMode,
+    ) -> Self {
+        Self::new_generic(graphics, 1, size, format, usage, filter_mode, Some(label))
+    }
+
     /// Creates a new texture of the given multisample count, size, format, and usages.
     #[must_use]
     pub fn multisampled(
@@ -1692,6 +3741,7 @@ impl Texture {
             format,
             usage,
             filter_mode,
+            None,
         )
     }
 
@@ -1724,21 +3774,81 @@ impl Texture {
         Self::from_wgpu(wgpu, graphics, false, size, format, filter_mode)
     }
 
+    /// Returns a new texture of the given size and usages, initialized with
+    /// the RGBA8 pixels in `data`.
+    ///
+    /// The texture's format is chosen based on `encoding`, and `data` is
+    /// converted from `encoding` as needed.
+    #[must_use]
+    pub fn new_with_encoded_data(
+        graphics: &Graphics<'_>,
+        size: Size<UPx>,
+        encoding: ColorEncoding,
+        usage: wgpu::TextureUsages,
+        filter_mode: wgpu::FilterMode,
+        data: &[u8],
+    ) -> Self {
+        let mut data = Cow::Borrowed(data);
+        if encoding.is_premultiplied() {
+            encoding.convert_rgba8(data.to_mut());
+        }
+        Self::new_with_data(
+            graphics,
+            size,
+            encoding.rgba8_format(),
+            usage,
+            filter_mode,
+            &data,
+        )
+    }
+
+    /// Creates a texture from `image`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TextureTooLarge`] if `image` is larger than the
+    /// graphics device supports.
+    #[cfg(feature = "image")]
+    pub fn try_from_image(
+        image: image::DynamicImage,
+        filter_mode: wgpu::FilterMode,
+        graphics: &Graphics<'_>,
+    ) -> Result<Self, Error> {
+        Error::check_texture_size(Size::upx(image.width(), image.height()), graphics.device())?;
+        Ok(Self::from_image(image, filter_mode, graphics))
+    }
+
     /// Creates a texture from `image`.
+    ///
+    /// The image is assumed to contain sRGB colors with straight alpha.
     #[must_use]
     #[cfg(feature = "image")]
     pub fn from_image(
         image: image::DynamicImage,
         filter_mode: wgpu::FilterMode,
         graphics: &Graphics<'_>,
+    ) -> Self {
+        Self::from_image_with_encoding(image, ColorEncoding::Srgb, filter_mode, graphics)
+    }
+
+    /// Creates a texture from `image`, whose colors are encoded using
+    /// `encoding`.
+    #[must_use]
+    #[cfg(feature = "image")]
+    pub fn from_image_with_encoding(
+        image: image::DynamicImage,
+        encoding: ColorEncoding,
+        filter_mode: wgpu::FilterMode,
+        graphics: &Graphics<'_>,
     ) -> Self {
         // TODO is it better to force rgba8, or is it better to avoid the
         // conversion and allow multiple texture formats?
-        let image = image.into_rgba8();
+        let mut image = image.into_rgba8();
+        encoding.convert_rgba8(&mut image);
         Self::new_with_data(
             graphics,
             Size::upx(image.width(), image.height()),
-            wgpu::TextureFormat::Rgba8UnormSrgb,
+            encoding.rgba8_format(),
             wgpu::TextureUsages::TEXTURE_BINDING,
             filter_mode,
             image.as_raw(),
@@ -1789,7 +3899,24 @@ impl Texture {
         Unit: figures::Unit,
         Vertex<Unit>: bytemuck::Pod,
     {
-        TextureBlit::new(source, dest, Color::WHITE).prepare(Some(self), graphics)
+        self.prepare_partial_sampled(source, TexelSampling::Edges, dest, graphics)
+    }
+
+    /// Prepares the `source` area to be rendered at `dest`, computing the
+    /// texture coordinates using `sampling`.
+    #[must_use]
+    pub fn prepare_partial_sampled<Unit>(
+        &self,
+        source: Rect<UPx>,
+        sampling: TexelSampling,
+        dest: Rect<Unit>,
+        graphics: &Graphics<'_>,
+    ) -> PreparedGraphic<Unit>
+    where
+        Unit: figures::Unit,
+        Vertex<Unit>: bytemuck::Pod,
+    {
+        TextureBlit::new(sampling.uv_rect(source), dest, Color::WHITE).prepare(Some(self), graphics)
     }
 
     /// The size of the texture.
@@ -1804,6 +3931,58 @@ impl Texture {
         self.format
     }
 
+    /// Reads the contents of this texture back from the GPU.
+    ///
+    /// The returned data is tightly packed, with no padding between rows. This
+    /// function blocks until the GPU has finished copying the data. The
+    /// texture must have been created with [`wgpu::TextureUsages::COPY_SRC`].
+    ///
+    /// Returns `None` if the texture's format cannot be copied or if the data
+    /// could not be read.
+    #[must_use]
+    pub fn read_pixels(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> Option<Vec<u8>> {
+        let bytes_per_pixel = self.format.block_copy_size(None)?;
+        let width = self.size.width.get();
+        let height = self.size.height.get();
+        let row_length = (width * bytes_per_pixel) as usize;
+        let bytes_per_row =
+            (width * bytes_per_pixel).next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("kludgine::read pixels"),
+            size: u64::from(bytes_per_row) * u64::from(height),
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        self.copy_to_buffer(
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: None,
+                },
+            },
+            &mut encoder,
+        );
+        queue.submit([encoder.finish()]);
+
+        let slice = buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _result = sender.send(result);
+        });
+        device.poll(wgpu::Maintain::Wait);
+        receiver.recv().ok()?.ok()?;
+
+        let data = slice.get_mapped_range();
+        let mut pixels = Vec::with_capacity(row_length * height as usize);
+        for row in data.chunks_exact(bytes_per_row as usize) {
+            pixels.extend_from_slice(&row[..row_length]);
+        }
+        Some(pixels)
+    }
+
     /// Copies the contents of this texture into `destination`.
     pub fn copy_to_buffer(
         &self,
@@ -1832,6 +4011,85 @@ impl Texture {
         );
     }
 
+    /// Copies the `source` region of this texture into `destination`, placing
+    /// the region's top-left corner at `destination_origin`.
+    ///
+    /// This texture must have been created with
+    /// [`wgpu::TextureUsages::COPY_SRC`], and `destination` must have been
+    /// created with [`wgpu::TextureUsages::COPY_DST`]. The copy is performed
+    /// when `encoder` is submitted.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::MissingUsage`] if either texture was created without the
+    ///   required usage.
+    /// - [`Error::IncompatibleFormats`] if the textures' formats differ by more
+    ///   than their sRGB encoding, or if their sample counts differ.
+    /// - [`Error::OutOfBounds`] if `source` does not fit within this texture,
+    ///   or the copied region does not fit within `destination`.
+    pub fn copy_region_to(
+        &self,
+        destination: &Texture,
+        source: Rect<UPx>,
+        destination_origin: Point<UPx>,
+        encoder: &mut wgpu::CommandEncoder,
+    ) -> Result<(), Error> {
+        if !self
+            .data
+            .wgpu
+            .usage()
+            .contains(wgpu::TextureUsages::COPY_SRC)
+        {
+            return Err(Error::MissingUsage(wgpu::TextureUsages::COPY_SRC));
+        }
+        if !destination
+            .data
+            .wgpu
+            .usage()
+            .contains(wgpu::TextureUsages::COPY_DST)
+        {
+            return Err(Error::MissingUsage(wgpu::TextureUsages::COPY_DST));
+        }
+        if self.format.remove_srgb_suffix() != destination.format.remove_srgb_suffix()
+            || self.data.wgpu.sample_count() != destination.data.wgpu.sample_count()
+        {
+            return Err(Error::IncompatibleFormats {
+                source: self.format,
+                destination: destination.format,
+            });
+        }
+        Error::check_region(source, self.size)?;
+        Error::check_region(Rect::new(destination_origin, source.size), destination.size)?;
+
+        encoder.copy_texture_to_texture(
+            wgpu::ImageCopyTexture {
+                texture: &self.data.wgpu,
+                mip_level: 0,
+                origin: source.origin.into(),
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyTexture {
+                texture: &destination.data.wgpu,
+                mip_level: 0,
+                origin: destination_origin.into(),
+                aspect: wgpu::TextureAspect::All,
+            },
+            source.size.into(),
+        );
+        Ok(())
+    }
+
+    /// Allows this texture to be rendered by any [`Kludgine`] instance that
+    /// uses the same graphics device as the instance that created it.
+    ///
+    /// Each instance that renders this texture creates its own bind group the
+    /// first time it is rendered.
+    pub(crate) fn share_between_instances(&mut self) {
+        if self.data.instance_bind_groups.is_none() {
+            self.data.instance_bind_groups = Some(Mutex::default());
+        }
+    }
+
     /// Returns the underlying wgpu handle.
     #[must_use]
     pub const fn wgpu(&self) -> &wgpu::Texture {
@@ -1840,9 +4098,19 @@ impl Texture {
 
     /// Returns a view over the entire texture.
     #[must_use]
-    pub const fn view(&self) -> &wgpu::TextureView {
+    pub fn view(&self) -> &wgpu::TextureView {
         &self.data.view
     }
+
+    /// Returns whether this texture's contents have finished uploading.
+    ///
+    /// Only textures loaded while [asynchronous texture
+    /// uploads](Kludgine::set_async_texture_uploads) are enabled can be
+    /// [`TextureLoadState::Loading`].
+    #[must_use]
+    pub fn load_state(&self) -> TextureLoadState {
+        self.progress.state()
+    }
 }
 
 impl PartialEq for Texture {
@@ -1851,8 +4119,399 @@ impl PartialEq for Texture {
     }
 }
 
-/// Loads a texture's bytes into the executable. This macro returns a result
-/// containing a [`LazyTexture`].
+/// Returns the features of `format` that can be used by a device with
+/// `features` enabled that was created from `adapter`.
+pub(crate) fn format_features(
+    format: wgpu::TextureFormat,
+    features: wgpu::Features,
+    adapter: Option<&wgpu::Adapter>,
+) -> wgpu::TextureFormatFeatures {
+    match adapter {
+        Some(adapter)
+            if features.contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES) =>
+        {
+            adapter.get_texture_format_features(format)
+        }
+        _ => format.guaranteed_format_features(features),
+    }
+}
+
+/// An error loading or uploading graphics data.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// An error occurred while loading or decoding an image.
+    #[cfg(feature = "image")]
+    Image(image::ImageError),
+    /// The texture format is not supported by the operation.
+    UnsupportedFormat(wgpu::TextureFormat),
+    /// The texture is larger than the graphics device supports.
+    TextureTooLarge {
+        /// The size of the texture.
+        size: Size<UPx>,
+        /// The maximum width and height supported by the device.
+        maximum: u32,
+    },
+    /// A [`TextureCollection`] has reached its maximum size and number of
+    /// pages and has no room for a texture of this size.
+    AtlasFull {
+        /// The size of the texture that could not be allocated.
+        requested: Size<UPx>,
+    },
+    /// A texture was not created with a usage required by the operation.
+    MissingUsage(wgpu::TextureUsages),
+    /// Texture data cannot be copied between textures of these formats.
+    IncompatibleFormats {
+        /// The format of the texture being copied from.
+        source: wgpu::TextureFormat,
+        /// The format of the texture being copied to.
+        destination: wgpu::TextureFormat,
+    },
+    /// A region does not fit within a texture.
+    OutOfBounds {
+        /// The region that was requested.
+        region: Rect<UPx>,
+        /// The size of the texture.
+        size: Size<UPx>,
+    },
+    /// The graphics device reported an error while performing an operation.
+    Gpu(wgpu::Error),
+    /// A texture's size is not valid for the kind of [`ColorLookup`] table
+    /// it was used as.
+    InvalidLookupTable {
+        /// The kind of table.
+        kind: ColorLookupKind,
+        /// The size of the texture.
+        size: Size<UPx>,
+    },
+    /// The multisample count is not supported by the graphics device for the
+    /// texture format being rendered to.
+    UnsupportedSampleCount(u32),
+    /// A resource created using one graphics device was used with another.
+    DeviceMismatch,
+}
+
+impl Error {
+    fn check_texture_size(size: Size<UPx>, device: &wgpu::Device) -> Result<(), Self> {
+        let maximum = device.limits().max_texture_dimension_2d;
+        if size.width.get() > maximum || size.height.get() > maximum {
+            Err(Self::TextureTooLarge { size, maximum })
+        } else {
+            Ok(())
+        }
+    }
+
+    fn check_region(region: Rect<UPx>, size: Size<UPx>) -> Result<(), Self> {
+        let (_, max) = region.extents();
+        if max.x > size.width || max.y > size.height {
+            Err(Self::OutOfBounds { region, size })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[test]
+fn region_bounds() {
+    let size = Size::upx(64, 32);
+    assert!(Error::check_region(Rect::new(Point::upx(0, 0), size), size).is_ok());
+    assert!(Error::check_region(Rect::new(Point::upx(32, 16), Size::upx(32, 16)), size).is_ok());
+    assert!(matches!(
+        Error::check_region(Rect::new(Point::upx(33, 0), Size::upx(32, 16)), size),
+        Err(Error::OutOfBounds { .. })
+    ));
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "image")]
+            Error::Image(err) => write!(f, "error loading image: {err}"),
+            Error::UnsupportedFormat(format) => {
+                write!(f, "unsupported texture format: {format:?}")
+            }
+            Error::TextureTooLarge { size, maximum } => write!(
+                f,
+                "texture size {}x{} exceeds the device maximum of {maximum}x{maximum}",
+                size.width, size.height
+            ),
+            Error::AtlasFull { requested } => write!(
+                f,
+                "texture collection has no room for a {}x{} texture",
+                requested.width, requested.height
+            ),
+            Error::MissingUsage(usage) => write!(f, "texture is missing usage {usage:?}"),
+            Error::IncompatibleFormats {
+                source,
+                destination,
+            } => write!(
+                f,
+                "cannot copy between texture formats {source:?} and {destination:?}"
+            ),
+            Error::OutOfBounds { region, size } => write!(
+                f,
+                "region {}x{} at {},{} does not fit within a {}x{} texture",
+                region.size.width,
+                region.size.height,
+                region.origin.x,
+                region.origin.y,
+                size.width,
+                size.height
+            ),
+            Error::Gpu(err) => write!(f, "graphics device error: {err}"),
+            Error::InvalidLookupTable { kind, size } => write!(
+                f,
+                "a {}x{} texture is not a valid {kind:?} color lookup table",
+                size.width, size.height
+            ),
+            Error::UnsupportedSampleCount(count) => {
+                write!(f, "multisample count {count} is not supported")
+            }
+            Error::DeviceMismatch => {
+                f.write_str("resource was created using a different graphics device")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "image")]
+            Error::Image(err) => Some(err),
+            Error::Gpu(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<wgpu::Error> for Error {
+    fn from(err: wgpu::Error) -> Self {
+        Self::Gpu(err)
+    }
+}
+
+/// Begins capturing validation and out-of-memory errors reported by `device`.
+///
+/// Each call must be paired with a call to [`pop_error_scopes`].
+pub(crate) fn push_error_scopes(device: &wgpu::Device) {
+    device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+}
+
+/// Stops capturing errors started by [`push_error_scopes`], returning the
+/// first error reported.
+pub(crate) fn pop_error_scopes(device: &wgpu::Device) -> Option<wgpu::Error> {
+    let validation = pollster::block_on(device.pop_error_scope());
+    let out_of_memory = pollster::block_on(device.pop_error_scope());
+    validation.or(out_of_memory)
+}
+
+/// Validation and out-of-memory errors being captured from a device.
+///
+/// If these scopes are dropped without being [popped](Self::pop), such as
+/// while unwinding from a panic, the scopes are popped and their errors are
+/// discarded. This keeps the device's error scopes balanced when a panic is
+/// caught.
+#[must_use]
+pub(crate) struct ErrorScopes<'a> {
+    device: &'a wgpu::Device,
+    popped: bool,
+}
+
+impl<'a> ErrorScopes<'a> {
+    /// Begins capturing errors reported by `device`.
+    pub fn push(device: &'a wgpu::Device) -> Self {
+        push_error_scopes(device);
+        Self {
+            device,
+            popped: false,
+        }
+    }
+
+    /// Stops capturing errors, returning the first error reported.
+    pub fn pop(mut self) -> Option<wgpu::Error> {
+        self.popped = true;
+        pop_error_scopes(self.device)
+    }
+}
+
+impl Drop for ErrorScopes<'_> {
+    fn drop(&mut self) {
+        if !self.popped {
+            pop_error_scopes(self.device);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "app"))]
+fn test_instance(device: &wgpu::Device, queue: &wgpu::Queue) -> Kludgine {
+    Kludgine::new(
+        device,
+        queue,
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        wgpu::MultisampleState::default(),
+        Size::upx(4, 4),
+        1.,
+    )
+}
+
+#[test]
+#[cfg(feature = "app")]
+fn panics_pop_captured_error_scopes() {
+    use std::panic::{self, AssertUnwindSafe};
+    use std::sync::atomic::AtomicUsize;
+
+    fn report_error(device: &wgpu::Device) {
+        // Textures cannot have an empty dimension.
+        drop(device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 0,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        }));
+    }
+
+    let Ok(window) = app::headless_window(Size::upx(4, 4), |_renderer, _window| true) else {
+        // No graphics adapter is available.
+        return;
+    };
+    let mut kludgine = test_instance(window.device(), window.queue());
+    let mut graphics = Graphics::new(&mut kludgine, window.device(), window.queue());
+    assert!(matches!(
+        graphics.capture_errors(|graphics| report_error(graphics.device())),
+        Err(Error::Gpu(_))
+    ));
+
+    let panicked = panic::catch_unwind(AssertUnwindSafe(|| {
+        graphics.capture_errors(|_graphics| panic!("operation failed"))
+    }));
+    assert!(panicked.is_err());
+
+    // Had the panic left its scopes pushed, they would capture this error
+    // instead of the device's handler.
+    let uncaptured = Arc::new(AtomicUsize::new(0));
+    window.device().on_uncaptured_error(Box::new({
+        let uncaptured = uncaptured.clone();
+        move |_error| {
+            uncaptured.fetch_add(1, atomic::Ordering::Relaxed);
+        }
+    }));
+    report_error(window.device());
+    assert_eq!(uncaptured.load(atomic::Ordering::Relaxed), 1);
+}
+
+#[test]
+#[cfg(feature = "app")]
+fn recreated_gpu_resources_render_using_new_devices() {
+    let (Ok(lost), Ok(replacement)) = (
+        app::headless_window(Size::upx(4, 4), |_renderer, _window| true),
+        app::headless_window(Size::upx(4, 4), |_renderer, _window| true),
+    ) else {
+        // No graphics adapter is available.
+        return;
+    };
+    let mut kludgine = test_instance(lost.device(), lost.queue());
+    kludgine.set_shape_anti_aliasing(false);
+    kludgine.set_zoom(Fraction::from(2.), lost.queue());
+    let previous_id = kludgine.id();
+
+    kludgine.recreate_gpu_resources(replacement.device(), replacement.queue());
+    assert_ne!(kludgine.id(), previous_id);
+    assert!(!kludgine.shape_anti_aliasing());
+    assert_eq!(kludgine.zoom(), Fraction::from(2.));
+
+    // Rendering with the replacement device must not use any resources
+    // created by the lost device.
+    let (device, queue) = (replacement.device(), replacement.queue());
+    let scopes = ErrorScopes::push(device);
+    let target = Texture::new(
+        &Graphics::new(&mut kludgine, device, queue),
+        Size::upx(4, 4),
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        wgpu::FilterMode::Nearest,
+    );
+    let mut drawing = drawing::Drawing::default();
+    let mut frame = kludgine.next_frame();
+    let mut graphics = frame.prepare(device, queue);
+    drawing
+        .new_frame(&mut graphics)
+        .draw_shape(&shapes::Shape::filled_rect(
+            Rect::<Px>::new(Point::ZERO, Size::squared(Px::new(4))),
+            Color::RED,
+        ));
+    drop(graphics);
+    let mut rendering = frame.render_into(
+        &target,
+        wgpu::LoadOp::Clear(Color::CLEAR_BLACK),
+        device,
+        queue,
+    );
+    drawing.render(1., &mut rendering);
+    drop(rendering);
+    frame.submit(queue);
+    assert!(scopes.pop().is_none());
+
+    let pixels = target.read_pixels(device, queue).expect("pixels read");
+    assert_eq!(&pixels[..4], &[255, 0, 0, 255]);
+}
+
+#[test]
+#[cfg(feature = "app")]
+fn damage_is_clamped_and_merged() {
+    let Ok(window) = app::headless_window(Size::upx(4, 4), |_renderer, _window| true) else {
+        // No graphics adapter is available.
+        return;
+    };
+    let mut kludgine = test_instance(window.device(), window.queue());
+
+    // Regions outside of the frame and empty regions are ignored.
+    kludgine.add_damage(Rect::new(Point::upx(4, 0), Size::upx(2, 2)));
+    kludgine.add_damage(Rect::new(Point::upx(1, 1), Size::upx(0, 2)));
+    assert_eq!(kludgine.damage, None);
+
+    // Regions extending past the frame are clamped to it.
+    kludgine.add_damage(Rect::new(Point::upx(3, 2), Size::upx(4, 4)));
+    assert_eq!(
+        kludgine.damage,
+        Some(Rect::new(Point::upx(3, 2), Size::upx(1, 2)))
+    );
+
+    // Multiple regions are merged into their bounding rectangle.
+    kludgine.add_damage(Rect::new(Point::upx(0, 1), Size::upx(1, 1)));
+    assert_eq!(
+        kludgine.damage,
+        Some(Rect::new(Point::upx(0, 1), Size::upx(4, 3)))
+    );
+    kludgine.add_damage(Rect::new(Point::upx(1, 2), Size::upx(1, 1)));
+    assert_eq!(
+        kludgine.damage,
+        Some(Rect::new(Point::upx(0, 1), Size::upx(4, 3)))
+    );
+
+    // Each frame starts without any damage.
+    let frame = kludgine.next_frame();
+    assert_eq!(frame.damaged_region(), None);
+}
+
+#[cfg(feature = "image")]
+impl From<image::ImageError> for Error {
+    fn from(err: image::ImageError) -> Self {
+        Self::Image(err)
+    }
+}
+
+/// Loads a texture's bytes into the executable. This macro returns a
+/// `Result<LazyTexture, kludgine::Error>`.
 ///
 /// This macro takes a single parameter, which is forwarded along to
 /// [`include_bytes!`]. The bytes that are loaded are then parsed using
@@ -1866,11 +4525,13 @@ macro_rules! include_texture {
     ($path:expr, $filter_mode:expr) => {
         $crate::image::load_from_memory(std::include_bytes!($path))
             .map(|image| $crate::LazyTexture::from_image(image, $filter_mode))
+            .map_err($crate::Error::from)
     };
 }
 
 /// The origin of a prepared graphic.
 #[derive(Default, Clone, Copy, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Origin<Unit> {
     /// The graphic should be drawn so that the top-left of the graphic appears
     /// at the rendered location. When rotating the graphic, it will rotate
@@ -1887,6 +4548,76 @@ pub enum Origin<Unit> {
     Custom(Point<Unit>),
 }
 
+/// How the color channels of RGBA8 texture data are encoded.
+///
+/// Kludgine blends textures using straight, non-premultiplied alpha. Tagging
+/// uploaded data with its encoding allows Kludgine to pick a texture format
+/// that decodes the colors correctly when sampled, and to convert
+/// premultiplied data before it is uploaded. Uploading linear data as sRGB
+/// makes it appear too dark, while uploading sRGB data as linear makes it
+/// appear washed out.
+#[derive(Default, Clone, Copy, Eq, PartialEq, Debug, Hash)]
+pub enum ColorEncoding {
+    /// sRGB-encoded colors with straight alpha. Most image files are encoded
+    /// this way.
+    #[default]
+    Srgb,
+    /// Linear colors with straight alpha.
+    Linear,
+    /// sRGB-encoded colors that have been multiplied by their alpha.
+    PremultipliedSrgb,
+    /// Linear colors that have been multiplied by their alpha.
+    PremultipliedLinear,
+}
+
+impl ColorEncoding {
+    /// Returns the texture format RGBA8 data with this encoding is uploaded
+    /// as.
+    #[must_use]
+    pub const fn rgba8_format(self) -> wgpu::TextureFormat {
+        match self {
+            Self::Srgb | Self::PremultipliedSrgb => wgpu::TextureFormat::Rgba8UnormSrgb,
+            Self::Linear | Self::PremultipliedLinear => wgpu::TextureFormat::Rgba8Unorm,
+        }
+    }
+
+    /// Returns true if the color channels have been multiplied by alpha.
+    #[must_use]
+    pub const fn is_premultiplied(self) -> bool {
+        matches!(self, Self::PremultipliedSrgb | Self::PremultipliedLinear)
+    }
+
+    /// Converts the RGBA8 pixels in `data` from this encoding into the
+    /// straight alpha that Kludgine blends with.
+    ///
+    /// Fully transparent pixels become transparent black.
+    pub fn convert_rgba8(self, data: &mut [u8]) {
+        if !self.is_premultiplied() {
+            return;
+        }
+
+        for pixel in data.chunks_exact_mut(4) {
+            let alpha = u16::from(pixel[3]);
+            for channel in &mut pixel[0..3] {
+                *channel = if alpha == 0 {
+                    0
+                } else {
+                    u8::try_from((u16::from(*channel) * 255 + alpha / 2) / alpha).unwrap_or(u8::MAX)
+                };
+            }
+        }
+    }
+}
+
+#[test]
+fn color_encoding_conversion() {
+    let mut data = [128, 64, 0, 128, 10, 20, 30, 0, 1, 2, 3, 255];
+    ColorEncoding::Srgb.convert_rgba8(&mut data);
+    assert_eq!(data, [128, 64, 0, 128, 10, 20, 30, 0, 1, 2, 3, 255]);
+    ColorEncoding::PremultipliedSrgb.convert_rgba8(&mut data);
+    assert_eq!(data, [255, 128, 0, 128, 0, 0, 0, 0, 1, 2, 3, 255]);
+}
+
 /// A resource that can be checked for surface compatibility.
 pub trait CanRenderTo {
     /// Returns `true` if this resource can be rendered into a graphics context
@@ -1900,15 +4631,37 @@ pub trait TextureSource: CanRenderTo + sealed::TextureSource {}
 
 impl CanRenderTo for Texture {
     fn can_render_to(&self, kludgine: &Kludgine) -> bool {
-        self.kludgine == kludgine.id
+        self.kludgine == kludgine.id || self.data.instance_bind_groups.is_some()
     }
 }
 
 impl TextureSource for Texture {}
 
 impl sealed::TextureSource for Texture {
-    fn bind_group(&self, _graphics: &impl sealed::KludgineGraphics) -> Arc<wgpu::BindGroup> {
-        self.data.bind_group.clone()
+    fn bind_group(&self, graphics: &impl sealed::KludgineGraphics) -> Arc<wgpu::BindGroup> {
+        match &self.data.instance_bind_groups {
+            Some(bind_groups) if graphics.id() != self.kludgine => bind_groups
+                .lock()
+                .assert("texture lock poisoned")
+                .entry(graphics.id())
+                .or_insert_with(|| {
+                    Arc::new(TextureInstance::create_bind_group(
+                        &self.data.view,
+                        self.data.multisampled,
+                        self.data.linear,
+                        graphics,
+                    ))
+                })
+                .clone(),
+            _ => self.data.bind_group.clone(),
+        }
+    }
+
+    fn array_texture(&self, _graphics: &impl sealed::KludgineGraphics) -> Option<ArrayTexture> {
+        (!self.data.multisampled).then(|| ArrayTexture {
+            view: self.data.view.clone(),
+            linear: self.data.linear,
+        })
     }
 
     fn id(&self) -> sealed::TextureId {
@@ -2019,6 +4772,13 @@ impl sealed::TextureSource for ShareableTexture {
         }
     }
 
+    fn array_texture(&self, graphics: &impl sealed::KludgineGraphics) -> Option<ArrayTexture> {
+        match self {
+            ShareableTexture::Shared(texture) => texture.array_texture(graphics),
+            ShareableTexture::Lazy(texture) => texture.array_texture(graphics),
+        }
+    }
+
     fn default_rect(&self) -> Rect<UPx> {
         match self {
             ShareableTexture::Shared(texture) => texture.default_rect(),
@@ -2054,6 +4814,119 @@ where
     }
 }
 
+/// Controls how texture coordinates are computed for a region of a texture.
+#[derive(Default, Clone, Copy, Eq, PartialEq, Debug, Hash)]
+pub enum TexelSampling {
+    /// The texture coordinates are placed on the outer edges of the region.
+    ///
+    /// When the region is rendered at a fractional scale or with linear
+    /// filtering, texels neighboring the region may be sampled. This can cause
+    /// visible seams when rendering adjacent regions from a shared sheet.
+    #[default]
+    Edges,
+    /// The texture coordinates are inset by half of a texel on each side,
+    /// placing them on the centers of the outermost texels of the region.
+    ///
+    /// This prevents texels outside of the region from being sampled at the
+    /// cost of half a texel of the region's border.
+    Centers,
+}
+
+impl TexelSampling {
+    /// Returns the texture coordinates to use when sampling `region`.
+    ///
+    /// Regions smaller than one texel in either dimension are returned
+    /// unchanged.
+    #[must_use]
+    pub fn uv_rect(self, region: Rect<UPx>) -> Rect<UPx> {
+        match self {
+            TexelSampling::Edges => region,
+            TexelSampling::Centers => {
+                let texel = UPx::new(1);
+                if region.size.width < texel || region.size.height < texel {
+                    return region;
+                }
+                let half_texel = UPx::from_unscaled(2);
+                Rect::new(
+                    region.origin + Point::squared(half_texel),
+                    region.size - Size::squared(texel),
+                )
+            }
+        }
+    }
+}
+
+#[test]
+fn texel_centers_inset() {
+    let region = Rect::new(Point::upx(32, 64), Size::upx(32, 32));
+    assert_eq!(TexelSampling::Edges.uv_rect(region), region);
+    let inset = TexelSampling::Centers.uv_rect(region);
+    assert_eq!(
+        inset.origin,
+        Point::upx(32, 64) + Point::squared(UPx::from_unscaled(2))
+    );
+    assert_eq!(inset.size, Size::upx(31, 31));
+    let tiny = Rect::new(Point::upx(1, 1), Size::upx(0, 1));
+    assert_eq!(TexelSampling::Centers.uv_rect(tiny), tiny);
+}
+
+/// Mirrors a texture when it is drawn.
+///
+/// Flipping is performed by swapping texture coordinates rather than by
+/// scaling, which keeps the drawn geometry and its batching unchanged.
+#[derive(Default, Clone, Copy, Eq, PartialEq, Debug, Hash)]
+pub struct TextureFlip {
+    /// Mirrors the texture across its vertical axis, swapping its left and
+    /// right edges.
+    pub horizontal: bool,
+    /// Mirrors the texture across its horizontal axis, swapping its top and
+    /// bottom edges.
+    pub vertical: bool,
+}
+
+impl TextureFlip {
+    /// Rotates the texture 180 degrees by flipping it in both directions.
+    pub const BOTH: Self = Self {
+        horizontal: true,
+        vertical: true,
+    };
+    /// Swaps the left and right edges of the texture.
+    pub const HORIZONTAL: Self = Self {
+        horizontal: true,
+        vertical: false,
+    };
+    /// Draws the texture unchanged.
+    pub const NONE: Self = Self {
+        horizontal: false,
+        vertical: false,
+    };
+    /// Swaps the top and bottom edges of the texture.
+    pub const VERTICAL: Self = Self {
+        horizontal: false,
+        vertical: true,
+    };
+}
+
+#[test]
+fn texture_flipping() {
+    let blit = TextureBlit::new(
+        Rect::new(Point::upx(0, 0), Size::upx(4, 2)),
+        Rect::new(Point::new(10, 10), Size::new(8, 4)),
+        Color::WHITE,
+    );
+    let flipped = blit.flipped(TextureFlip::HORIZONTAL);
+    assert_eq!(flipped.top_left().texture, Point::upx(4, 0));
+    assert_eq!(flipped.bottom_right().texture, Point::upx(0, 2));
+    assert_eq!(flipped.top_left().location, blit.top_left().location);
+    let flipped = blit.flipped(TextureFlip::BOTH);
+    assert_eq!(flipped.top_left().texture, Point::upx(4, 2));
+    assert_eq!(flipped.bottom_right().texture, Point::upx(0, 0));
+    assert_eq!(
+        blit.flipped(TextureFlip::NONE).top_left().texture,
+        Point::upx(0, 0)
+    );
+}
+
 /// A region of a [`SharedTexture`].
 ///
 /// When this type is drawn, only a region of the source texture will be drawn.
@@ -2061,6 +4934,7 @@ where
 pub struct TextureRegion {
     texture: ShareableTexture,
     region: Rect<UPx>,
+    sampling: TexelSampling,
 }
 
 impl TextureRegion {
@@ -2071,9 +4945,27 @@ impl TextureRegion {
         Self {
             texture: texture.into(),
             region,
+            sampling: TexelSampling::default(),
         }
     }
 
+    /// Returns this region configured to compute its texture coordinates using
+    /// `sampling`.
+    ///
+    /// Using [`TexelSampling::Centers`] eliminates seams when rendering
+    /// adjacent tiles from a shared sheet.
+    #[must_use]
+    pub fn with_sampling(mut self, sampling: TexelSampling) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    /// Returns the texel sampling mode of this region.
+    #[must_use]
+    pub const fn sampling(&self) -> TexelSampling {
+        self.sampling
+    }
+
     /// Returns the size of the region being drawn.
     #[must_use]
     pub const fn size(&self) -> Size<UPx> {
@@ -2087,9 +4979,12 @@ impl TextureRegion {
         Unit: figures::Unit,
         Vertex<Unit>: bytemuck::Pod,
     {
-        self.texture
-            .texture(graphics)
-            .prepare_partial(self.region, dest, graphics)
+        self.texture.texture(graphics).prepare_partial_sampled(
+            self.region,
+            self.sampling,
+            dest,
+            graphics,
+        )
     }
 }
 
@@ -2114,9 +5009,17 @@ impl sealed::TextureSource for TextureRegion {
         self.texture.bind_group(graphics)
     }
 
+    fn array_texture(&self, graphics: &impl sealed::KludgineGraphics) -> Option<ArrayTexture> {
+        self.texture.array_texture(graphics)
+    }
+
     fn default_rect(&self) -> Rect<UPx> {
         self.region
     }
+
+    fn uv_rect(&self) -> Rect<UPx> {
+        self.sampling.uv_rect(self.region)
+    }
 }
 
 impl From<SharedTexture> for TextureRegion {
@@ -2136,6 +5039,7 @@ impl From<ShareableTexture> for TextureRegion {
         Self {
             region: texture.default_rect(),
             texture,
+            sampling: TexelSampling::default(),
         }
     }
 }
@@ -2251,6 +5155,16 @@ impl sealed::TextureSource for AnyTexture {
         }
     }
 
+    fn array_texture(&self, graphics: &impl sealed::KludgineGraphics) -> Option<ArrayTexture> {
+        match self {
+            AnyTexture::Texture(texture) => texture.array_texture(graphics),
+            AnyTexture::Lazy(texture) => texture.array_texture(graphics),
+            AnyTexture::Collected(texture) => texture.array_texture(graphics),
+            AnyTexture::Shared(texture) => texture.array_texture(graphics),
+            AnyTexture::Region(texture) => texture.array_texture(graphics),
+        }
+    }
+
     fn default_rect(&self) -> Rect<UPx> {
         match self {
             AnyTexture::Texture(texture) => texture.default_rect(),
@@ -2260,6 +5174,16 @@ impl sealed::TextureSource for AnyTexture {
             AnyTexture::Region(texture) => texture.default_rect(),
         }
     }
+
+    fn uv_rect(&self) -> Rect<UPx> {
+        match self {
+            AnyTexture::Texture(texture) => texture.uv_rect(),
+            AnyTexture::Lazy(texture) => texture.uv_rect(),
+            AnyTexture::Collected(texture) => texture.uv_rect(),
+            AnyTexture::Shared(texture) => texture.uv_rect(),
+            AnyTexture::Region(texture) => texture.uv_rect(),
+        }
+    }
 }
 
 #[derive(Default)]
@@ -2329,6 +5253,10 @@ where
     fn indices(&self) -> &[u32] {
         &[1, 0, 2, 1, 2, 3]
     }
+
+    fn quad(&self) -> Option<[Vertex<Unit>; 4]> {
+        Some(self.verticies)
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -2380,6 +5308,23 @@ impl<Unit> TextureBlit<Unit> {
         &self.verticies[0]
     }
 
+    pub fn flipped(mut self, flip: TextureFlip) -> Self {
+        let mut swap = |a: usize, b: usize| {
+            let texture = self.verticies[a].texture;
+            self.verticies[a].texture = self.verticies[b].texture;
+            self.verticies[b].texture = texture;
+        };
+        if flip.horizontal {
+            swap(0, 1);
+            swap(2, 3);
+        }
+        if flip.vertical {
+            swap(0, 2);
+            swap(1, 3);
+        }
+        self
+    }
+
     // pub const fn top_right(&self) -> &Vertex<Unit> {
     //     &self.verticies[1]
     // }
@@ -2415,8 +5360,26 @@ pub struct Drawable<T, Unit> {
     pub rotation: Option<Angle>,
     /// Scale the source before rendering.
     pub scale: Option<Point<f32>>,
+    /// The point of the source that it is rotated and scaled around.
+    ///
+    /// [`Origin::TopLeft`] and [`Origin::Center`] refer to the bounds of the
+    /// drawn source, and [`Origin::Custom`] refers to a location relative to
+    /// the source's coordinate origin. The bounds of text are the bounds of
+    /// its visible glyphs. When not provided, the source is rotated and scaled
+    /// around its coordinate origin. The pivot does not affect where the
+    /// source is translated to.
+    pub pivot: Option<Origin<Unit>>,
     /// An opacity multiplier to apply to this drawable.
     pub opacity: Option<f32>,
+    /// The depth of this drawable, ranged from 0.0 to 1.0.
+    ///
+    /// This value is only used when rendering into a pass with a depth
+    /// attachment. Drawables with a greater z are drawn above drawables with a
+    /// lesser z. When not provided, 0.0 is used. Fully transparent pixels of
+    /// drawables with a z do not affect the depth buffer.
+    pub z: Option<f32>,
+    /// A table that this drawable's colors are replaced with.
+    pub lookup: Option<ColorLookup>,
 }
 
 impl<'a, Unit> From<Text<'a, Unit>> for Drawable<Text<'a, Unit>, Unit>
@@ -2429,7 +5392,10 @@ where
             translation: Point::default(),
             rotation: None,
             scale: None,
+            pivot: None,
             opacity: None,
+            z: None,
+            lookup: None,
         }
     }
 }
@@ -2445,7 +5411,10 @@ where
             translation: Point::default(),
             rotation: None,
             scale: None,
+            pivot: None,
             opacity: None,
+            z: None,
+            lookup: None,
         }
     }
 }
@@ -2458,8 +5427,19 @@ pub trait DrawableExt<Source, Unit> {
     fn rotate_by(self, angle: Angle) -> Drawable<Source, Unit>;
     /// Scales `self` by `factor`.
     fn scale(self, factor: impl ScaleFactor) -> Drawable<Source, Unit>;
+    /// Rotates and scales `self` around `point`, relative to the source's
+    /// coordinate origin.
+    fn pivot(self, point: Point<Unit>) -> Drawable<Source, Unit>;
+    /// Rotates and scales `self` around `anchor`.
+    ///
+    /// See [`Drawable::pivot`] for more information.
+    fn anchor(self, anchor: Origin<Unit>) -> Drawable<Source, Unit>;
     /// Renders this drawable with `opacity`, ranged from 0.- to 1.0.
     fn opacity(self, opacity: f32) -> Drawable<Source, Unit>;
+    /// Renders this drawable at depth `z`, ranged from 0.0 to 1.0.
+    fn at_z(self, z: f32) -> Drawable<Source, Unit>;
+    /// Replaces the colors of this drawable using `lookup`.
+    fn color_lookup(self, lookup: &ColorLookup) -> Drawable<Source, Unit>;
 }
 
 impl<T, Unit> DrawableExt<T, Unit> for Drawable<T, Unit> {
@@ -2478,10 +5458,29 @@ impl<T, Unit> DrawableExt<T, Unit> for Drawable<T, Unit> {
         self
     }
 
+    fn pivot(self, point: Point<Unit>) -> Drawable<T, Unit> {
+        self.anchor(Origin::Custom(point))
+    }
+
+    fn anchor(mut self, anchor: Origin<Unit>) -> Drawable<T, Unit> {
+        self.pivot = Some(anchor);
+        self
+    }
+
     fn opacity(mut self, opacity: f32) -> Drawable<T, Unit> {
         self.opacity = Some(opacity.clamp(0., 1.));
         self
     }
+
+    fn at_z(mut self, z: f32) -> Drawable<T, Unit> {
+        self.z = Some(z.clamp(0., 1.));
+        self
+    }
+
+    fn color_lookup(mut self, lookup: &ColorLookup) -> Drawable<T, Unit> {
+        self.lookup = Some(lookup.clone());
+        self
+    }
 }
 
 /// A type representing an x and y scaling factor.
@@ -2525,7 +5524,23 @@ where
         Drawable::from(self).scale(factor)
     }
 
+    fn pivot(self, point: Point<Unit>) -> Drawable<T, Unit> {
+        Drawable::from(self).pivot(point)
+    }
+
+    fn anchor(self, anchor: Origin<Unit>) -> Drawable<T, Unit> {
+        Drawable::from(self).anchor(anchor)
+    }
+
     fn opacity(self, opacity: f32) -> Drawable<T, Unit> {
         Drawable::from(self).opacity(opacity)
     }
+
+    fn at_z(self, z: f32) -> Drawable<T, Unit> {
+        Drawable::from(self).at_z(z)
+    }
+
+    fn color_lookup(self, lookup: &ColorLookup) -> Drawable<T, Unit> {
+        Drawable::from(self).color_lookup(lookup)
+    }
 }
@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use figures::units::UPx;
+use figures::Size;
+
+use crate::pipeline::{self, FLAG_LOOKUP_GRADING, FLAG_LOOKUP_PALETTE};
+use crate::{sealed, Color, Error, Graphics, Texture};
+
+/// How colors are looked up in a [`ColorLookup`] table.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ColorLookupKind {
+    /// The table is a single row of colors, such as a 256x1 palette.
+    ///
+    /// The red channel of each drawn color selects the entry that replaces
+    /// it, with `0.0` selecting the first entry and `1.0` selecting the last.
+    /// The entry's alpha is multiplied by the drawn color's alpha. This is
+    /// useful for swapping the palettes of sprites whose red channel stores
+    /// palette indices. These textures should be uploaded using
+    /// [`ColorEncoding::Linear`](crate::ColorEncoding::Linear) to ensure
+    /// their indices are not altered.
+    Palette,
+    /// The table is a three dimensional grid containing `n` entries for each
+    /// color channel, stored as a strip of `n` slices that are each `n` by
+    /// `n` texels. The texture must be `n * n` texels wide and `n` texels
+    /// tall.
+    ///
+    /// Within each slice, red increases to the right and green increases
+    /// downwards. Blue increases with each slice. Drawn colors are replaced by
+    /// interpolating between the nearest entries, and their alpha is left
+    /// unchanged. This is useful for color grading.
+    Grading,
+}
+
+/// A table that drawn colors are replaced with.
+///
+/// Color lookups are attached to drawables using
+/// [`DrawableExt::color_lookup`](crate::DrawableExt::color_lookup). Each
+/// fragment's color, after being tinted and textured, is replaced with the
+/// color looked up in the table. Text ignores color lookups.
+///
+/// Cloning a color lookup is cheap and shares the table between clones.
+#[derive(Debug, Clone)]
+pub struct ColorLookup {
+    pub(crate) id: sealed::TextureId,
+    kind: ColorLookupKind,
+    size: Size<UPx>,
+    pub(crate) bind_group: Arc<wgpu::BindGroup>,
+}
+
+impl ColorLookup {
+    /// Returns a lookup that uses `texture` as a table of `kind`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLookupTable`] if `texture`'s size is not valid
+    /// for `kind`, or [`Error::UnsupportedFormat`] if `texture` is
+    /// multisampled or cannot be filtered.
+    pub fn new(
+        texture: &Texture,
+        kind: ColorLookupKind,
+        graphics: &Graphics<'_>,
+    ) -> Result<Self, Error> {
+        let size = texture.size();
+        if !is_valid_size(kind, size) {
+            return Err(Error::InvalidLookupTable { kind, size });
+        }
+        if texture.data.multisampled
+            || texture.format().sample_type(None, None)
+                != Some(wgpu::TextureSampleType::Float { filterable: true })
+        {
+            return Err(Error::UnsupportedFormat(texture.format()));
+        }
+
+        let kludgine = &*graphics.kludgine;
+        Ok(Self {
+            id: sealed::TextureId::new_unique_id(),
+            kind,
+            size,
+            bind_group: Arc::new(pipeline::lookup_bind_group(
+                graphics.device,
+                &kludgine.lookup_layout,
+                &texture.data.view,
+                &kludgine.linear_sampler,
+            )),
+        })
+    }
+
+    /// Returns a palette lookup containing `colors`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLookupTable`] if `colors` is empty, or
+    /// [`Error::TextureTooLarge`] if `colors` contains more entries than the
+    /// graphics device supports.
+    pub fn palette(colors: &[Color], graphics: &Graphics<'_>) -> Result<Self, Error> {
+        let size = Size::new(
+            UPx::new(u32::try_from(colors.len()).unwrap_or(u32::MAX)),
+            UPx::new(1),
+        );
+        if colors.is_empty() {
+            return Err(Error::InvalidLookupTable {
+                kind: ColorLookupKind::Palette,
+                size,
+            });
+        }
+        Error::check_texture_size(size, graphics.device)?;
+
+        let data = colors
+            .iter()
+            .flat_map(|color| [color.red(), color.green(), color.blue(), color.alpha()])
+            .collect::<Vec<_>>();
+        let texture = Texture::new_with_data(
+            graphics,
+            size,
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+            wgpu::TextureUsages::TEXTURE_BINDING,
+            wgpu::FilterMode::Nearest,
+            &data,
+        );
+        Self::new(&texture, ColorLookupKind::Palette, graphics)
+    }
+
+    /// Returns how colors are looked up in this table.
+    #[must_use]
+    pub const fn kind(&self) -> ColorLookupKind {
+        self.kind
+    }
+
+    /// Returns the size of this table's texture.
+    #[must_use]
+    pub const fn size(&self) -> Size<UPx> {
+        self.size
+    }
+
+    pub(crate) const fn flag(&self) -> u32 {
+        match self.kind {
+            ColorLookupKind::Palette => FLAG_LOOKUP_PALETTE,
+            ColorLookupKind::Grading => FLAG_LOOKUP_GRADING,
+        }
+    }
+}
+
+impl Eq for ColorLookup {}
+
+impl PartialEq for ColorLookup {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+fn is_valid_size(kind: ColorLookupKind, size: Size<UPx>) -> bool {
+    match kind {
+        ColorLookupKind::Palette => size.width > 0 && size.height == 1,
+        ColorLookupKind::Grading => {
+            size.height > 1
+                && size.height.get().checked_mul(size.height.get()) == Some(size.width.get())
+        }
+    }
+}
+
+#[test]
+fn lookup_sizes() {
+    use figures::UPx2D;
+
+    assert!(is_valid_size(ColorLookupKind::Palette, Size::upx(256, 1)));
+    assert!(!is_valid_size(ColorLookupKind::Palette, Size::upx(16, 16)));
+    assert!(is_valid_size(ColorLookupKind::Grading, Size::upx(256, 16)));
+    assert!(!is_valid_size(ColorLookupKind::Grading, Size::upx(256, 1)));
+    assert!(!is_valid_size(ColorLookupKind::Grading, Size::upx(255, 16)));
+}
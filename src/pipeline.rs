@@ -1,26 +1,47 @@
 use std::any::TypeId;
+use std::borrow::Cow;
+use std::hash::{Hash, Hasher};
 use std::mem::size_of;
-use std::ops::Range;
+use std::num::NonZeroU32;
+use std::ops::{Range, Sub};
 use std::sync::Arc;
 
 use bytemuck::{Pod, Zeroable};
 use figures::units::{Lp, Px, UPx};
-use figures::{Fraction, IntoSigned, Point, ScreenScale, ScreenUnit, Size, UnscaledUnit, Zero};
+use figures::{
+    Angle, FloatConversion, Fraction, IntoSigned, Point, Rect, ScreenScale, ScreenUnit, Size,
+    UnscaledUnit, Zero,
+};
+use intentional::Cast;
 use smallvec::SmallVec;
 
 use crate::buffer::Buffer;
-use crate::{sealed, Color, Drawable, DrawableSource, RenderingGraphics};
+use crate::shapes::PatternWrap;
+use crate::{
+    sealed, srgb_to_linear, Assert, Color, Drawable, DrawableSource, KludgineGraphics, Origin,
+    RenderingGraphics,
+};
 
 #[derive(Pod, Zeroable, Copy, Clone, Debug)]
 #[repr(C)]
 pub(crate) struct Uniforms {
     ortho: [f32; 16],
     scale: u32,
-    _padding: [u32; 3],
+    /// 0 when dithering is disabled, 1 when dithering a linear target, and 2
+    /// when dithering an sRGB target.
+    dither: u32,
+    _padding: [u32; 2],
 }
 
 impl Uniforms {
-    pub fn new(size: Size<UPx>, scale: Fraction) -> Self {
+    /// Returns the uniforms for rendering into a target of `size`. When
+    /// `dither_format` is provided, colors are dithered for a target of that
+    /// format.
+    pub fn new(
+        size: Size<UPx>,
+        scale: Fraction,
+        dither_format: Option<wgpu::TextureFormat>,
+    ) -> Self {
         let scale = u32::from(scale.denominator().unsigned_abs()) << 16
             | u32::try_from(scale.numerator()).expect("negative scaling ratio");
         Self {
@@ -34,7 +55,12 @@ impl Uniforms {
             )
             .into_array(),
             scale,
-            _padding: [0; 3],
+            dither: match dither_format {
+                None => 0,
+                Some(format) if format.is_srgb() => 2,
+                Some(_) => 1,
+            },
+            _padding: [0; 2],
         }
     }
 }
@@ -68,6 +94,23 @@ pub(crate) const FLAG_ROTATE: u32 = 1 << 2;
 pub(crate) const FLAG_TRANSLATE: u32 = 1 << 3;
 pub(crate) const FLAG_TEXTURED: u32 = 1 << 4;
 pub(crate) const FLAG_MASKED: u32 = 1 << 5;
+pub(crate) const FLAG_DEPTH: u32 = 1 << 6;
+pub(crate) const FLAG_PATTERN_REPEAT: u32 = 1 << 7;
+pub(crate) const FLAG_PATTERN_MIRROR: u32 = 1 << 8;
+pub(crate) const FLAG_CLIP_ROTATED: u32 = 1 << 9;
+pub(crate) const FLAG_LOOKUP_PALETTE: u32 = 1 << 11;
+pub(crate) const FLAG_LOOKUP_GRADING: u32 = 1 << 12;
+
+/// The number of textures bound by each texture array bind group.
+///
+/// This must match the length of `r_textures` in `texture_array.wgsl`.
+pub(crate) const TEXTURE_ARRAY_SIZE: usize = 8;
+
+/// Set in a vertex's texture array slot when its texture is sampled using
+/// linear filtering. The lower bits contain the texture's index in the array.
+///
+/// This must match `slot_linear` in `texture_array.wgsl`.
+pub(crate) const TEXTURE_SLOT_LINEAR: u32 = 1 << 8;
 
 #[derive(Debug, Clone, Copy, Pod, Zeroable, PartialEq)]
 #[repr(C)]
@@ -77,14 +120,277 @@ pub(crate) struct PushConstants {
     pub rotation: f32,
     pub opacity: f32,
     pub translation: Point<i32>,
+    pub z: f32,
+    pub target_scale: Point<f32>,
+    /// The origin and size of the texture region repeated by a pattern fill,
+    /// in texels.
+    pub pattern: [f32; 4],
+    /// The rotated rectangle fragments are clipped to when
+    /// [`FLAG_CLIP_ROTATED`] is set.
+    pub clip: RotatedClip,
+}
+
+/// A rotated rectangle that fragments are clipped to.
+///
+/// All measurements are in pixels relative to the render target.
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub(crate) struct RotatedClip {
+    pub center: Point<f32>,
+    pub half_size: Point<f32>,
+    /// The cosine and sine of the rectangle's rotation.
+    pub rotation: Point<f32>,
+}
+
+impl RotatedClip {
+    /// Returns `rect` rotated by `angle` around its center, after offsetting
+    /// it by `origin`.
+    pub fn new(origin: Point<UPx>, rect: Rect<UPx>, angle: Angle) -> Self {
+        let (sin, cos) = angle.into_raidans_f().sin_cos();
+        let half_size = Point::new(
+            rect.size.width.into_float() / 2.,
+            rect.size.height.into_float() / 2.,
+        );
+        Self {
+            center: Point::new(
+                (origin.x + rect.origin.x).into_float() + half_size.x,
+                (origin.y + rect.origin.y).into_float() + half_size.y,
+            ),
+            half_size,
+            rotation: Point::new(cos, sin),
+        }
+    }
+
+    /// Returns this clip moved by `offset`.
+    pub fn offset(mut self, offset: Point<Px>) -> Self {
+        self.center.x += offset.x.into_float();
+        self.center.y += offset.y.into_float();
+        self
+    }
+
+    /// Returns the top-left and bottom-right corners of the smallest
+    /// axis-aligned rectangle that contains this clip.
+    pub fn extents(&self) -> (Point<f32>, Point<f32>) {
+        let (cos, sin) = (self.rotation.x.abs(), self.rotation.y.abs());
+        let extent = Point::new(
+            cos * self.half_size.x + sin * self.half_size.y,
+            sin * self.half_size.x + cos * self.half_size.y,
+        );
+        (self.center - extent, self.center + extent)
+    }
+
+    fn bits(&self) -> [u32; 6] {
+        [
+            self.center.x.to_bits(),
+            self.center.y.to_bits(),
+            self.half_size.x.to_bits(),
+            self.half_size.y.to_bits(),
+            self.rotation.x.to_bits(),
+            self.rotation.y.to_bits(),
+        ]
+    }
+}
+
+impl PartialEq for RotatedClip {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits() == other.bits()
+    }
+}
+
+impl Eq for RotatedClip {}
+
+impl Hash for RotatedClip {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits().hash(state);
+    }
+}
+
+/// A region of a texture that is repeated across a shape.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) struct TexturePattern {
+    pub region: Rect<UPx>,
+    pub wrap: PatternWrap,
+}
+
+impl TexturePattern {
+    /// Returns the shader flags and region push constant for `pattern`.
+    pub fn constants(pattern: Option<Self>) -> (u32, [f32; 4]) {
+        let Some(pattern) = pattern else {
+            return (0, [0.; 4]);
+        };
+        let flags = match pattern.wrap {
+            PatternWrap::Repeat => FLAG_PATTERN_REPEAT,
+            PatternWrap::Mirror => FLAG_PATTERN_MIRROR,
+        };
+        (
+            flags,
+            [
+                pattern.region.origin.x.into_float(),
+                pattern.region.origin.y.into_float(),
+                pattern.region.size.width.into_float(),
+                pattern.region.size.height.into_float(),
+            ],
+        )
+    }
 }
 
 /// A graphic that is on the GPU and ready to render.
 #[derive(Debug)]
 pub struct PreparedGraphic<Unit> {
     pub(crate) vertices: Buffer<Vertex<Unit>>,
+    /// The vertices of a graphic prepared from a single texture region, which
+    /// are kept to allow the graphic to be updated. Other graphics do not keep
+    /// a copy of their vertices.
+    pub(crate) quad: Option<[Vertex<Unit>; 4]>,
     pub(crate) indices: Buffer<u32>,
     pub(crate) commands: SmallVec<[PreparedCommand; 2]>,
+    pub(crate) bounds: Rect<Unit>,
+}
+
+impl<Unit> PreparedGraphic<Unit>
+where
+    Unit: Copy,
+{
+    /// Returns the smallest rectangle that contains all of this graphic's
+    /// vertices, before any translation, rotation, or scaling is applied.
+    #[must_use]
+    pub const fn bounds(&self) -> Rect<Unit> {
+        self.bounds
+    }
+}
+
+impl<Unit> PreparedGraphic<Unit>
+where
+    Unit: Copy,
+    Vertex<Unit>: Pod,
+{
+    /// Replaces the color of every vertex in this graphic with `color`,
+    /// updating the existing GPU buffer.
+    ///
+    /// `color` is blended with the texture.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if this graphic was not prepared from a single
+    /// texture region, such as graphics returned from
+    /// [`Texture::prepare`](crate::Texture::prepare). Shapes and text do not
+    /// keep a copy of their vertices and cannot be updated.
+    pub fn set_color(&mut self, color: Color, graphics: &impl KludgineGraphics) {
+        let color = srgb_to_linear(
+            color.red_f32(),
+            color.green_f32(),
+            color.blue_f32(),
+            color.alpha_f32(),
+        );
+        let quad = self.quad_mut();
+        for vertex in &mut *quad {
+            vertex.color = color;
+        }
+        self.upload_quad(graphics);
+    }
+
+    /// Updates the region of the texture this graphic displays, updating the
+    /// existing GPU buffer.
+    ///
+    /// The texture coordinates are remapped from the region currently
+    /// displayed to `region`, preserving any flipping applied when this
+    /// graphic was prepared.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if this graphic was not prepared from a single
+    /// texture region. See [`set_color()`](Self::set_color) for more
+    /// information.
+    pub fn set_source_region(&mut self, region: Rect<UPx>, graphics: &impl KludgineGraphics) {
+        let quad = self.quad_mut();
+        let (min, max) = texture_extents(&quad[..]);
+        let from = float_rect(Rect::new(min, Size::new(max.x - min.x, max.y - min.y)));
+        let to = float_rect(region);
+        for vertex in &mut *quad {
+            vertex.texture = remap(vertex.texture.into_float(), from, to).map(UPx::from_float);
+        }
+        self.upload_quad(graphics);
+    }
+
+    /// Moves and resizes this graphic to fill `rect`, updating the existing
+    /// GPU buffer.
+    ///
+    /// The vertices are remapped from this graphic's current
+    /// [`bounds()`](Self::bounds) to `rect`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if this graphic was not prepared from a single
+    /// texture region. See [`set_color()`](Self::set_color) for more
+    /// information.
+    pub fn set_dest_rect(&mut self, rect: Rect<Unit>, graphics: &impl KludgineGraphics)
+    where
+        Unit: FloatConversion<Float = f32> + Ord + Sub<Output = Unit> + Zero,
+    {
+        let from = float_rect(self.bounds);
+        let to = float_rect(rect);
+        let quad = self.quad_mut();
+        for vertex in &mut *quad {
+            vertex.location = remap(vertex.location.into_float(), from, to).map(Unit::from_float);
+        }
+        let bounds = vertex_bounds(&quad[..]);
+        self.bounds = bounds;
+        self.upload_quad(graphics);
+    }
+
+    fn quad_mut(&mut self) -> &mut [Vertex<Unit>; 4] {
+        self.quad
+            .as_mut()
+            .assert("only graphics prepared from a texture region can be updated")
+    }
+
+    fn upload_quad(&self, graphics: &impl KludgineGraphics) {
+        if let Some(quad) = &self.quad {
+            self.vertices.update(0, quad, graphics.queue());
+        }
+    }
+}
+
+/// Returns the smallest and largest texture coordinates of `vertices`.
+fn texture_extents<Unit>(vertices: &[Vertex<Unit>]) -> (Point<UPx>, Point<UPx>) {
+    let mut coordinates = vertices.iter().map(|vertex| vertex.texture);
+    let first = coordinates.next().unwrap_or_default();
+    coordinates.fold((first, first), |(min, max), texture| {
+        (
+            Point::new(min.x.min(texture.x), min.y.min(texture.y)),
+            Point::new(max.x.max(texture.x), max.y.max(texture.y)),
+        )
+    })
+}
+
+fn float_rect<Unit>(rect: Rect<Unit>) -> Rect<f32>
+where
+    Unit: FloatConversion<Float = f32>,
+{
+    Rect::new(
+        rect.origin.into_float(),
+        Size::new(rect.size.width.into_float(), rect.size.height.into_float()),
+    )
+}
+
+/// Maps `point` from its position relative to `from` to the same relative
+/// position in `to`. Points in an empty dimension of `from` are mapped to the
+/// origin of `to`.
+fn remap(point: Point<f32>, from: Rect<f32>, to: Rect<f32>) -> Point<f32> {
+    let x = if from.size.width > 0. {
+        (point.x - from.origin.x) / from.size.width
+    } else {
+        0.
+    };
+    let y = if from.size.height > 0. {
+        (point.y - from.origin.y) / from.size.height
+    } else {
+        0.
+    };
+    Point::new(
+        to.origin.x + x * to.size.width,
+        to.origin.y + y * to.size.height,
+    )
 }
 
 #[derive(Debug)]
@@ -92,6 +398,7 @@ pub struct PreparedCommand {
     pub indices: Range<u32>,
     pub is_mask: bool,
     pub binding: Option<Arc<wgpu::BindGroup>>,
+    pub pattern: Option<TexturePattern>,
 }
 
 impl<Unit> PreparedGraphic<Unit>
@@ -120,12 +427,40 @@ where
     pub fn render(&self, graphics: &mut RenderingGraphics<'_, 'pass>) {
         graphics.active_pipeline_if_needed();
 
+        let (min, max) = self.source.bounds.extents();
+        let (min, max) = (min.into_px(graphics.scale()), max.into_px(graphics.scale()));
+        let offset = graphics.clip.current.origin.into_signed()
+            + graphics.translation
+            + self.translation.into_px(graphics.scale())
+            + pivot_offset(
+                self.pivot,
+                min,
+                max,
+                self.rotation,
+                self.scale,
+                graphics.scale(),
+            );
+        if !is_visible(
+            min,
+            max,
+            self.rotation,
+            self.scale,
+            offset,
+            *graphics.clip.current,
+        ) {
+            return;
+        }
+
         graphics
             .pass
             .set_vertex_buffer(0, self.source.vertices.as_slice());
         graphics
             .pass
             .set_index_buffer(self.source.indices.as_slice(), wgpu::IndexFormat::Uint32);
+        let lookup_flag = self.lookup.as_ref().map_or(0, |lookup| {
+            graphics.pass.set_bind_group(1, &*lookup.bind_group, &[]);
+            lookup.flag()
+        });
 
         for command in &self.source.commands {
             if graphics.clip.current.size.is_zero() {
@@ -139,7 +474,8 @@ where
                     .unwrap_or(&graphics.kludgine.default_bindings),
                 &[],
             );
-            let mut flags = Unit::flags();
+            let (mut flags, pattern) = TexturePattern::constants(command.pattern);
+            flags |= Unit::flags() | lookup_flag;
             if command.binding.is_some() {
                 flags |= FLAG_TEXTURED;
                 if command.is_mask {
@@ -154,12 +490,21 @@ where
                 flags |= FLAG_ROTATE;
                 scale.into_raidans_f()
             });
-            let translation = (graphics.clip.current.origin.into_signed()
-                + self.translation.into_px(graphics.scale()))
-            .map(Px::into_unscaled);
+            let translation = offset.map(Px::into_unscaled);
             if !translation.is_zero() {
                 flags |= FLAG_TRANSLATE;
             }
+            let z = self.z.map_or(0., |z| {
+                flags |= FLAG_DEPTH;
+                z
+            });
+            let clip = graphics
+                .clip
+                .rotated
+                .map_or_else(RotatedClip::zeroed, |clip| {
+                    flags |= FLAG_CLIP_ROTATED;
+                    clip
+                });
 
             graphics.pass.set_push_constants(
                 wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
@@ -170,6 +515,10 @@ where
                     rotation,
                     translation,
                     opacity: self.opacity.unwrap_or(1.),
+                    z,
+                    target_scale: graphics.target_scale,
+                    pattern,
+                    clip,
                 }),
             );
             graphics.pass.draw_indexed(command.indices.clone(), 0, 0..1);
@@ -177,6 +526,270 @@ where
     }
 }
 
+/// Returns the smallest rectangle containing all of `vertices`.
+pub(crate) fn vertex_bounds<Unit>(vertices: &[Vertex<Unit>]) -> Rect<Unit>
+where
+    Unit: Copy + Ord + Sub<Output = Unit> + Zero,
+{
+    let mut locations = vertices.iter().map(|vertex| vertex.location);
+    let Some(first) = locations.next() else {
+        return Rect::new(
+            Point::new(Unit::ZERO, Unit::ZERO),
+            Size::new(Unit::ZERO, Unit::ZERO),
+        );
+    };
+    let (min, max) = locations.fold((first, first), |(min, max), location| {
+        (
+            Point::new(min.x.min(location.x), min.y.min(location.y)),
+            Point::new(max.x.max(location.x), max.y.max(location.y)),
+        )
+    });
+    Rect::new(min, Size::new(max.x - min.x, max.y - min.y))
+}
+
+/// Returns the offset that causes a drawable whose vertices are between `min`
+/// and `max` to be rotated and scaled by the shader around `pivot` instead of
+/// its coordinate origin.
+pub(crate) fn pivot_offset<Unit>(
+    pivot: Option<Origin<Unit>>,
+    min: Point<Px>,
+    max: Point<Px>,
+    rotation: Option<Angle>,
+    scale: Option<Point<f32>>,
+    effective_scale: Fraction,
+) -> Point<Px>
+where
+    Unit: ScreenUnit,
+{
+    let Some(pivot) = pivot.filter(|_| rotation.is_some() || scale.is_some()) else {
+        return Point::ZERO;
+    };
+    let pivot = match pivot {
+        Origin::TopLeft => min,
+        Origin::Center => min + (max - min) / 2,
+        Origin::Custom(point) => point.into_px(effective_scale),
+    };
+    let mut transformed = pivot.into_float();
+    if let Some(rotation) = rotation {
+        let (sin, cos) = rotation.into_raidans_f().sin_cos();
+        transformed = Point::new(
+            transformed.x * cos - transformed.y * sin,
+            transformed.x * sin + transformed.y * cos,
+        );
+    }
+    if let Some(scale) = scale {
+        transformed = Point::new(transformed.x * scale.x, transformed.y * scale.y);
+    }
+    pivot - transformed.map(Px::from_float)
+}
+
+/// Returns the area covered by the rectangle from `min` to `max` after being
+/// rotated, scaled, and offset in the same order as the shader.
+pub(crate) fn transformed_bounds(
+    min: Point<Px>,
+    max: Point<Px>,
+    rotation: Option<Angle>,
+    scale: Option<Point<f32>>,
+    offset: Point<Px>,
+) -> Rect<Px> {
+    if rotation.is_none() && scale.is_none() {
+        return Rect::from_extents(min + offset, max + offset);
+    }
+
+    let mut corners = [min, Point::new(max.x, min.y), Point::new(min.x, max.y), max]
+        .map(FloatConversion::into_float);
+    if let Some(rotation) = rotation {
+        let (sin, cos) = rotation.into_raidans_f().sin_cos();
+        for corner in &mut corners {
+            *corner = Point::new(
+                corner.x * cos - corner.y * sin,
+                corner.x * sin + corner.y * cos,
+            );
+        }
+    }
+    if let Some(scale) = scale {
+        for corner in &mut corners {
+            *corner = Point::new(corner.x * scale.x, corner.y * scale.y);
+        }
+    }
+
+    let (left, right) = corners.iter().fold((f32::MAX, f32::MIN), |(min, max), c| {
+        (min.min(c.x), max.max(c.x))
+    });
+    let (top, bottom) = corners.iter().fold((f32::MAX, f32::MIN), |(min, max), c| {
+        (min.min(c.y), max.max(c.y))
+    });
+    Rect::from_extents(
+        Point::new(Px::from_float(left.floor()), Px::from_float(top.floor())) + offset,
+        Point::new(Px::from_float(right.ceil()), Px::from_float(bottom.ceil())) + offset,
+    )
+}
+
+/// Returns true if the rectangle from `min` to `max` intersects `clip` after
+/// being rotated, scaled, and offset in the same order as the shader.
+pub(crate) fn is_visible(
+    min: Point<Px>,
+    max: Point<Px>,
+    rotation: Option<Angle>,
+    scale: Option<Point<f32>>,
+    offset: Point<Px>,
+    clip: Rect<UPx>,
+) -> bool {
+    let (min, max) = transformed_bounds(min, max, rotation, scale, offset).extents();
+    let (clip_min, clip_max) = clip.extents();
+    let (clip_min, clip_max) = (clip_min.into_signed(), clip_max.into_signed());
+    min.x <= clip_max.x && max.x >= clip_min.x && min.y <= clip_max.y && max.y >= clip_min.y
+}
+
+#[test]
+fn remapping() {
+    let from = Rect::new(Point::new(10., 10.), Size::new(20., 40.));
+    let to = Rect::new(Point::new(0., 100.), Size::new(10., 10.));
+    assert_eq!(remap(Point::new(10., 10.), from, to), Point::new(0., 100.));
+    assert_eq!(remap(Point::new(20., 50.), from, to), Point::new(5., 110.));
+    let empty = Rect::new(Point::new(10., 10.), Size::new(0., 0.));
+    assert_eq!(remap(Point::new(10., 10.), empty, to), to.origin);
+}
+
+#[test]
+#[cfg(feature = "app")]
+fn only_texture_regions_keep_vertices() {
+    use figures::{Px2D, UPx2D};
+
+    use crate::shapes::Shape;
+    use crate::{Graphics, Kludgine, Texture};
+
+    let Ok(window) = crate::app::headless_window(Size::upx(16, 16), |_renderer, _window| true)
+    else {
+        // No graphics adapter is available.
+        return;
+    };
+    let mut kludgine = Kludgine::new(
+        window.device(),
+        window.queue(),
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        wgpu::MultisampleState::default(),
+        Size::upx(16, 16),
+        1.,
+    );
+    let graphics = Graphics::new(&mut kludgine, window.device(), window.queue());
+    let texture = Texture::new(
+        &graphics,
+        Size::upx(4, 4),
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        wgpu::TextureUsages::TEXTURE_BINDING,
+        wgpu::FilterMode::Nearest,
+    );
+
+    let mut graphic = texture.prepare(Rect::new(Point::px(0, 0), Size::px(4, 4)), &graphics);
+    graphic.set_dest_rect(Rect::new(Point::px(2, 2), Size::px(8, 8)), &graphics);
+    assert_eq!(graphic.bounds(), Rect::new(Point::px(2, 2), Size::px(8, 8)));
+    graphic.set_source_region(Rect::new(Point::upx(1, 1), Size::upx(2, 2)), &graphics);
+    graphic.set_color(Color::WHITE, &graphics);
+    let quad = graphic.quad.expect("texture regions keep their vertices");
+    assert_eq!(quad[0].location, Point::px(2, 2));
+    assert_eq!(quad[3].location, Point::px(10, 10));
+    assert_eq!(quad[0].texture, Point::upx(1, 1));
+    assert_eq!(quad[3].texture, Point::upx(3, 3));
+    assert_eq!(quad[0].color, Color::WHITE);
+
+    let shape = Shape::filled_rect(Rect::new(Point::px(0, 0), Size::px(4, 4)), Color::RED)
+        .prepare(&graphics);
+    assert!(shape.quad.is_none());
+}
+
+#[test]
+fn culling() {
+    let clip = Rect::new(
+        Point::new(UPx::new(0), UPx::new(0)),
+        Size::squared(UPx::new(100)),
+    );
+    let min = Point::squared(Px::ZERO);
+    let max = Point::squared(Px::new(10));
+    assert!(is_visible(
+        min,
+        max,
+        None,
+        None,
+        Point::squared(Px::new(50)),
+        clip
+    ));
+    assert!(!is_visible(
+        min,
+        max,
+        None,
+        None,
+        Point::squared(Px::new(150)),
+        clip
+    ));
+    assert!(!is_visible(
+        min,
+        max,
+        None,
+        None,
+        Point::new(Px::new(-20), Px::ZERO),
+        clip
+    ));
+    // Scaling and rotation can bring the graphic back into view.
+    assert!(is_visible(
+        min,
+        max,
+        None,
+        Some(Point::squared(3.)),
+        Point::new(Px::new(-20), Px::ZERO),
+        clip
+    ));
+    assert!(is_visible(
+        min,
+        max,
+        Some(Angle::degrees(180)),
+        None,
+        Point::new(Px::new(105), Px::ZERO),
+        clip
+    ));
+}
+
+#[test]
+fn pivots() {
+    let min = Point::squared(Px::ZERO);
+    let max = Point::squared(Px::new(10));
+    // Without rotation or scaling, the pivot has no effect.
+    assert_eq!(
+        pivot_offset(
+            Some(Origin::<Px>::Center),
+            min,
+            max,
+            None,
+            None,
+            Fraction::ONE
+        ),
+        Point::ZERO
+    );
+    // Rotating half a turn around the center must keep the center in place.
+    assert_eq!(
+        pivot_offset(
+            Some(Origin::<Px>::Center),
+            min,
+            max,
+            Some(Angle::degrees(180)),
+            None,
+            Fraction::ONE
+        ),
+        Point::squared(Px::new(10))
+    );
+    assert_eq!(
+        pivot_offset(
+            Some(Origin::Custom(Point::squared(Px::new(2)))),
+            min,
+            max,
+            None,
+            Some(Point::squared(2.)),
+            Fraction::ONE
+        ),
+        Point::squared(Px::new(-2))
+    );
+}
+
 /// A unit that is able to be scaled by the GPU shader.
 pub trait ShaderScalable: sealed::ShaderScalableSealed {}
 
@@ -238,11 +851,15 @@ impl ScreenTransformation {
 
 pub fn bind_group_layout(device: &wgpu::Device, multisampled: bool) -> wgpu::BindGroupLayout {
     device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-        label: None,
+        label: Some(if multisampled {
+            "kludgine::multisampled bind group layout"
+        } else {
+            "kludgine::bind group layout"
+        }),
         entries: &[
             wgpu::BindGroupLayoutEntry {
                 binding: 0,
-                visibility: wgpu::ShaderStages::VERTEX,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
                 ty: wgpu::BindingType::Buffer {
                     ty: wgpu::BufferBindingType::Uniform,
                     has_dynamic_offset: false,
@@ -272,13 +889,63 @@ pub fn bind_group_layout(device: &wgpu::Device, multisampled: bool) -> wgpu::Bin
     })
 }
 
+/// Returns the layout of the bind group containing the
+/// [`ColorLookup`](crate::ColorLookup) table, which is bound as the second
+/// bind group.
+pub fn lookup_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("kludgine::color lookup bind group layout"),
+        entries: &[
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                count: None,
+            },
+        ],
+    })
+}
+
+pub(crate) fn lookup_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    table: &wgpu::TextureView,
+    sampler: &wgpu::Sampler,
+) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("kludgine::color lookup bindings"),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(table),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            },
+        ],
+    })
+}
+
 pub fn layout(
     device: &wgpu::Device,
     binding_layout: &wgpu::BindGroupLayout,
+    lookup_layout: &wgpu::BindGroupLayout,
 ) -> wgpu::PipelineLayout {
     device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-        label: None,
-        bind_group_layouts: &[binding_layout],
+        label: Some("kludgine::pipeline layout"),
+        bind_group_layouts: &[binding_layout, lookup_layout],
         push_constant_ranges: &[wgpu::PushConstantRange {
             stages: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
             range: 0..size_of::<PushConstants>()
@@ -296,7 +963,7 @@ pub(crate) fn bind_group(
     sampler: &wgpu::Sampler,
 ) -> wgpu::BindGroup {
     device.create_bind_group(&wgpu::BindGroupDescriptor {
-        label: None,
+        label: Some("kludgine::texture bindings"),
         layout,
         entries: &[
             wgpu::BindGroupEntry {
@@ -319,58 +986,121 @@ pub(crate) fn bind_group(
     })
 }
 
+/// How Kludgine's pipelines shade graphics.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) struct Shading {
+    /// Whether triangles are filled or only their edges are drawn.
+    pub polygon_mode: wgpu::PolygonMode,
+    /// Whether each fragment counts towards the overdraw of its pixel rather
+    /// than writing its color.
+    pub overdraw: bool,
+}
+
+impl Default for Shading {
+    fn default() -> Self {
+        Self {
+            polygon_mode: wgpu::PolygonMode::Fill,
+            overdraw: false,
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn new(
     device: &wgpu::Device,
     pipeline_layout: &wgpu::PipelineLayout,
     shader: &wgpu::ShaderModule,
     format: wgpu::TextureFormat,
     multisample: wgpu::MultisampleState,
+    shading: Shading,
+    depth_tested: bool,
+    texture_slots: bool,
+    label: &str,
 ) -> wgpu::RenderPipeline {
+    let buffers = [
+        wgpu::VertexBufferLayout {
+            array_stride: size_of::<Vertex<Lp>>() as u64,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    format: wgpu::VertexFormat::Sint32x2,
+                    offset: 0,
+                    shader_location: 0,
+                },
+                wgpu::VertexAttribute {
+                    format: wgpu::VertexFormat::Uint32x2,
+                    offset: 8,
+                    shader_location: 1,
+                },
+                wgpu::VertexAttribute {
+                    format: wgpu::VertexFormat::Uint32,
+                    offset: 16,
+                    shader_location: 2,
+                },
+            ],
+        },
+        // The texture array slot of each vertex, combined with
+        // `TEXTURE_SLOT_LINEAR`.
+        wgpu::VertexBufferLayout {
+            array_stride: size_of::<u32>() as u64,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[wgpu::VertexAttribute {
+                format: wgpu::VertexFormat::Uint32,
+                offset: 0,
+                shader_location: 3,
+            }],
+        },
+    ];
     device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-        label: None,
+        label: Some(label),
         layout: Some(pipeline_layout),
         vertex: wgpu::VertexState {
             module: shader,
             entry_point: Some("vertex"),
-            buffers: &[wgpu::VertexBufferLayout {
-                array_stride: size_of::<Vertex<Lp>>() as u64,
-                step_mode: wgpu::VertexStepMode::Vertex,
-                attributes: &[
-                    wgpu::VertexAttribute {
-                        format: wgpu::VertexFormat::Sint32x2,
-                        offset: 0,
-                        shader_location: 0,
-                    },
-                    wgpu::VertexAttribute {
-                        format: wgpu::VertexFormat::Uint32x2,
-                        offset: 8,
-                        shader_location: 1,
-                    },
-                    wgpu::VertexAttribute {
-                        format: wgpu::VertexFormat::Uint32,
-                        offset: 16,
-                        shader_location: 2,
-                    },
-                ],
-            }],
+            buffers: if texture_slots {
+                &buffers
+            } else {
+                &buffers[..1]
+            },
             compilation_options: wgpu::PipelineCompilationOptions::default(),
         },
         fragment: Some(wgpu::FragmentState {
             module: shader,
-            entry_point: Some("fragment"),
+            entry_point: Some(if shading.overdraw {
+                "overdraw"
+            } else {
+                "fragment"
+            }),
             targets: &[Some(wgpu::ColorTargetState {
                 format,
-                blend: Some(wgpu::BlendState {
-                    color: wgpu::BlendComponent {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha: wgpu::BlendComponent {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
+                blend: Some(if shading.overdraw {
+                    // The overdraw shader's output is added to the alpha
+                    // channel, and the color channels are cleared.
+                    wgpu::BlendState {
+                        color: wgpu::BlendComponent {
+                            src_factor: wgpu::BlendFactor::Zero,
+                            dst_factor: wgpu::BlendFactor::Zero,
+                            operation: wgpu::BlendOperation::Add,
+                        },
+                        alpha: wgpu::BlendComponent {
+                            src_factor: wgpu::BlendFactor::One,
+                            dst_factor: wgpu::BlendFactor::One,
+                            operation: wgpu::BlendOperation::Add,
+                        },
+                    }
+                } else {
+                    wgpu::BlendState {
+                        color: wgpu::BlendComponent {
+                            src_factor: wgpu::BlendFactor::SrcAlpha,
+                            dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                            operation: wgpu::BlendOperation::Add,
+                        },
+                        alpha: wgpu::BlendComponent {
+                            src_factor: wgpu::BlendFactor::SrcAlpha,
+                            dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                            operation: wgpu::BlendOperation::Add,
+                        },
+                    }
                 }),
 
                 write_mask: wgpu::ColorWrites::ALL,
@@ -382,13 +1112,194 @@ pub fn new(
             strip_index_format: None,
             front_face: wgpu::FrontFace::Ccw,
             cull_mode: None,
-            polygon_mode: wgpu::PolygonMode::Fill,
+            polygon_mode: shading.polygon_mode,
             unclipped_depth: false,
             conservative: false,
         },
-        depth_stencil: None,
+        // Drawables with a greater z are drawn above those with a lesser z.
+        // Equal values pass so that painter's order is preserved when z is
+        // unused.
+        depth_stencil: depth_tested.then_some(wgpu::DepthStencilState {
+            format: crate::Kludgine::DEPTH_FORMAT,
+            depth_write_enabled: true,
+            depth_compare: wgpu::CompareFunction::GreaterEqual,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
         multisample,
         multiview: None,
         cache: None,
     })
 }
+
+/// Returns Kludgine's shader module, sampling from either a single texture or
+/// an array of textures.
+pub(crate) fn shader(device: &wgpu::Device, texture_arrays: bool) -> wgpu::ShaderModule {
+    let (label, textures) = if texture_arrays {
+        (
+            "kludgine::texture array shader",
+            include_str!("texture_array.wgsl"),
+        )
+    } else {
+        ("kludgine::shader", include_str!("texture.wgsl"))
+    };
+    device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some(label),
+        source: wgpu::ShaderSource::Wgsl(Cow::Owned(format!(
+            "{}{textures}",
+            include_str!("shader.wgsl")
+        ))),
+    })
+}
+
+/// A texture that can be bound in a texture array.
+#[derive(Debug, Clone)]
+pub struct ArrayTexture {
+    pub view: Arc<wgpu::TextureView>,
+    pub linear: bool,
+}
+
+/// The pipelines used to render with texture binding arrays.
+#[derive(Debug)]
+pub(crate) struct TextureArrays {
+    layout: wgpu::BindGroupLayout,
+    pub pipeline: wgpu::RenderPipeline,
+    pub depth_pipeline: wgpu::RenderPipeline,
+    _shader: wgpu::ShaderModule,
+}
+
+impl TextureArrays {
+    /// The features a device must support to render with texture arrays.
+    ///
+    /// Each vertex selects its texture, so the array must support being
+    /// indexed by non-uniform values.
+    pub const FEATURES: wgpu::Features = wgpu::Features::TEXTURE_BINDING_ARRAY
+        .union(wgpu::Features::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING);
+
+    /// Returns true if `device` supports rendering with texture arrays.
+    pub fn supported_by(device: &wgpu::Device) -> bool {
+        device.features().contains(Self::FEATURES)
+            && device
+                .limits()
+                .max_sampled_textures_per_shader_stage
+                .cast::<usize>()
+                >= TEXTURE_ARRAY_SIZE
+    }
+
+    pub fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        multisample: wgpu::MultisampleState,
+        shading: Shading,
+        lookup_layout: &wgpu::BindGroupLayout,
+    ) -> Self {
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("kludgine::texture array bind group layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: NonZeroU32::new(TEXTURE_ARRAY_SIZE.cast::<u32>()),
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        let pipeline_layout = self::layout(device, &layout, lookup_layout);
+        let shader = shader(device, true);
+        let pipeline = new(
+            device,
+            &pipeline_layout,
+            &shader,
+            format,
+            multisample,
+            shading,
+            false,
+            true,
+            "kludgine::texture array pipeline",
+        );
+        let depth_pipeline = new(
+            device,
+            &pipeline_layout,
+            &shader,
+            format,
+            multisample,
+            shading,
+            true,
+            true,
+            "kludgine::depth tested texture array pipeline",
+        );
+        Self {
+            layout,
+            pipeline,
+            depth_pipeline,
+            _shader: shader,
+        }
+    }
+
+    /// Returns a bind group containing `textures`.
+    ///
+    /// `textures` must contain between 1 and [`TEXTURE_ARRAY_SIZE`] entries.
+    /// Unused slots are filled with the first texture.
+    pub fn bind_group(
+        &self,
+        graphics: &impl KludgineGraphics,
+        textures: &[&wgpu::TextureView],
+    ) -> wgpu::BindGroup {
+        let mut views = [textures[0]; TEXTURE_ARRAY_SIZE];
+        views[..textures.len()].copy_from_slice(textures);
+        graphics
+            .device()
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("kludgine::texture array bindings"),
+                layout: &self.layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                            buffer: graphics.uniforms(),
+                            offset: 0,
+                            size: None,
+                        }),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureViewArray(&views),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::Sampler(graphics.nearest_sampler()),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: wgpu::BindingResource::Sampler(graphics.linear_sampler()),
+                    },
+                ],
+            })
+    }
+}
@@ -0,0 +1,375 @@
+use std::mem::size_of;
+
+use bytemuck::{Pod, Zeroable};
+use figures::units::UPx;
+use intentional::{Assert, Cast};
+
+use crate::{Error, Texture};
+
+/// A full-frame effect applied to a rendered texture.
+///
+/// Post effects are applied using [`Frame::apply_post_effects()`] or, within
+/// a [`RenderGraph`](crate::graph::RenderGraph) pass, using
+/// [`PassContext::apply_post_effects()`](crate::graph::PassContext::apply_post_effects).
+/// Effects are applied in order, with each effect processing the result of
+/// the previous effect, which allows combining effects such as
+/// [`PostEffect::crt()`].
+///
+/// Measurements in pixels refer to pixels of the texture being written.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PostEffect {
+    /// Replaces each square block of pixels with the color at the block's
+    /// center. The block size is measured in pixels.
+    Pixelate(UPx),
+    /// Darkens alternating bands of rows, resembling the scanlines of a CRT
+    /// display.
+    Scanlines {
+        /// The number of rows between the start of each scanline. The lower
+        /// half of each scanline is darkened.
+        spacing: UPx,
+        /// How much the darkened rows are darkened, from `0.0` to `1.0`.
+        intensity: f32,
+    },
+    /// Bends the image outwards as if it were displayed on the curved glass
+    /// of a CRT display. Areas outside of the curved image are black.
+    ///
+    /// `0.0` leaves the image unchanged, and values around `0.1` resemble a
+    /// typical CRT display.
+    Curvature(f32),
+    /// Darkens the image towards its edges.
+    Vignette {
+        /// The distance from the center at which darkening begins, where
+        /// `1.0` is the distance to the corners.
+        radius: f32,
+        /// The distance past `radius` over which the image fades to black.
+        softness: f32,
+    },
+    /// Maps the overdraw counts rendered while
+    /// [`Kludgine::set_overdraw()`](crate::Kludgine::set_overdraw) is enabled
+    /// through a heat gradient.
+    ///
+    /// Pixels that were never drawn are black. Pixels drawn once are blue,
+    /// and pixels drawn more often progress through green and yellow until
+    /// they are red at the specified count. Pixels drawn more times than the
+    /// specified count are white.
+    OverdrawHeatmap(u8),
+}
+
+impl PostEffect {
+    /// Returns a combination of effects that resembles a CRT display.
+    #[must_use]
+    pub fn crt() -> [Self; 3] {
+        [
+            Self::Scanlines {
+                spacing: UPx::new(3),
+                intensity: 0.35,
+            },
+            Self::Curvature(0.1),
+            Self::Vignette {
+                radius: 0.6,
+                softness: 0.5,
+            },
+        ]
+    }
+
+    fn constants(self, target: wgpu::Extent3d) -> PostConstants {
+        let (kind, size, amount, softness) = match self {
+            PostEffect::Pixelate(size) => (KIND_PIXELATE, size.get().cast::<f32>(), 0., 0.),
+            PostEffect::Scanlines { spacing, intensity } => (
+                KIND_SCANLINES,
+                spacing.get().cast::<f32>(),
+                intensity.clamp(0., 1.),
+                0.,
+            ),
+            PostEffect::Curvature(amount) => (KIND_CURVATURE, 0., amount.max(0.), 0.),
+            PostEffect::Vignette { radius, softness } => {
+                (KIND_VIGNETTE, 0., radius.max(0.), softness.max(0.))
+            }
+            PostEffect::OverdrawHeatmap(count) => (KIND_OVERDRAW, f32::from(count.max(1)), 0., 0.),
+        };
+        PostConstants {
+            kind,
+            size,
+            target: [target.width.cast::<f32>(), target.height.cast::<f32>()],
+            amount,
+            softness,
+        }
+    }
+}
+
+const KIND_COPY: u32 = 0;
+const KIND_PIXELATE: u32 = 1;
+const KIND_SCANLINES: u32 = 2;
+const KIND_CURVATURE: u32 = 3;
+const KIND_VIGNETTE: u32 = 4;
+const KIND_OVERDRAW: u32 = 5;
+
+/// The push constants of `post.wgsl`.
+#[derive(Debug, Clone, Copy, Pod, Zeroable, PartialEq)]
+#[repr(C)]
+struct PostConstants {
+    kind: u32,
+    size: f32,
+    target: [f32; 2],
+    amount: f32,
+    softness: f32,
+}
+
+/// The pipelines and scratch texture used to apply [`PostEffect`]s, which
+/// are created the first time effects are applied.
+#[derive(Debug, Default)]
+pub(crate) struct PostProcessor {
+    resources: Option<Resources>,
+    pipelines: Vec<(wgpu::TextureFormat, wgpu::RenderPipeline)>,
+    scratch: Option<(wgpu::Texture, wgpu::TextureView)>,
+}
+
+#[derive(Debug)]
+struct Resources {
+    layout: wgpu::BindGroupLayout,
+    pipeline_layout: wgpu::PipelineLayout,
+    shader: wgpu::ShaderModule,
+}
+
+impl PostProcessor {
+    /// Records passes that apply `effects` to `source`, writing the result
+    /// into `destination`.
+    pub(crate) fn apply(
+        &mut self,
+        source: &Texture,
+        destination: &wgpu::Texture,
+        effects: &[PostEffect],
+        sampler: &wgpu::Sampler,
+        encoder: &mut wgpu::CommandEncoder,
+        device: &wgpu::Device,
+    ) -> Result<(), Error> {
+        assert!(
+            source.wgpu() != destination,
+            "post effects cannot be applied in place"
+        );
+        if source.data.multisampled
+            || source.format().sample_type(None, None)
+                != Some(wgpu::TextureSampleType::Float { filterable: true })
+        {
+            return Err(Error::UnsupportedFormat(source.format()));
+        }
+        if !source
+            .wgpu()
+            .usage()
+            .contains(wgpu::TextureUsages::TEXTURE_BINDING)
+        {
+            return Err(Error::MissingUsage(wgpu::TextureUsages::TEXTURE_BINDING));
+        }
+        let format = destination.format();
+        if !matches!(
+            format.sample_type(None, None),
+            Some(wgpu::TextureSampleType::Float { .. })
+        ) || destination.sample_count() > 1
+        {
+            return Err(Error::UnsupportedFormat(format));
+        }
+        if !destination
+            .usage()
+            .contains(wgpu::TextureUsages::RENDER_ATTACHMENT)
+        {
+            return Err(Error::MissingUsage(wgpu::TextureUsages::RENDER_ATTACHMENT));
+        }
+
+        let size = destination.size();
+        if effects.len() > 1
+            && self.scratch.as_ref().map_or(true, |(scratch, _)| {
+                scratch.size() != size || scratch.format() != format
+            })
+        {
+            let scratch = device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("kludgine::post effect scratch"),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            });
+            let view = scratch.create_view(&wgpu::TextureViewDescriptor::default());
+            self.scratch = Some((scratch, view));
+        }
+
+        let resources = self.resources.get_or_insert_with(|| Resources::new(device));
+        let pipeline = if let Some(index) = self
+            .pipelines
+            .iter()
+            .position(|(existing, _)| *existing == format)
+        {
+            index
+        } else {
+            self.pipelines
+                .push((format, resources.pipeline(format, device)));
+            self.pipelines.len() - 1
+        };
+        let pipeline = &self.pipelines[pipeline].1;
+
+        // Without any effects, the source is copied into the destination.
+        let passes = if effects.is_empty() {
+            vec![PostConstants {
+                kind: KIND_COPY,
+                ..PostEffect::Curvature(0.).constants(size)
+            }]
+        } else {
+            effects
+                .iter()
+                .map(|effect| effect.constants(size))
+                .collect::<Vec<_>>()
+        };
+        let destination_view = destination.create_view(&wgpu::TextureViewDescriptor::default());
+        // Passes alternate between the destination and the scratch texture,
+        // beginning with whichever ensures the last pass writes the
+        // destination.
+        let mut input = source.view();
+        for (index, constants) in passes.iter().enumerate() {
+            let output = if (passes.len() - index) % 2 == 1 {
+                &destination_view
+            } else {
+                &self.scratch.as_ref().assert("allocated above").1
+            };
+            let bindings = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("kludgine::post effect bindings"),
+                layout: &resources.layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(input),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(sampler),
+                    },
+                ],
+            });
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("kludgine::post effect pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: output,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, &bindings, &[]);
+            pass.set_push_constants(
+                wgpu::ShaderStages::FRAGMENT,
+                0,
+                bytemuck::bytes_of(constants),
+            );
+            pass.draw(0..3, 0..1);
+            drop(pass);
+            input = output;
+        }
+
+        Ok(())
+    }
+}
+
+impl Resources {
+    fn new(device: &wgpu::Device) -> Self {
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("kludgine::post effect bind group layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("kludgine::post effect pipeline layout"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[wgpu::PushConstantRange {
+                stages: wgpu::ShaderStages::FRAGMENT,
+                range: 0..size_of::<PostConstants>().cast::<u32>(),
+            }],
+        });
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("kludgine::post effect shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("post.wgsl").into()),
+        });
+        Self {
+            layout,
+            pipeline_layout,
+            shader,
+        }
+    }
+
+    fn pipeline(&self, format: wgpu::TextureFormat, device: &wgpu::Device) -> wgpu::RenderPipeline {
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("kludgine::post effect pipeline"),
+            layout: Some(&self.pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &self.shader,
+                entry_point: Some("vertex"),
+                buffers: &[],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &self.shader,
+                entry_point: Some("fragment"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        })
+    }
+}
+
+#[test]
+fn post_constants() {
+    let target = wgpu::Extent3d {
+        width: 320,
+        height: 240,
+        depth_or_array_layers: 1,
+    };
+    let constants = PostEffect::Scanlines {
+        spacing: UPx::new(4),
+        intensity: 2.,
+    }
+    .constants(target);
+    // Intensity is clamped to 1.0.
+    assert_eq!(
+        constants,
+        PostConstants {
+            kind: KIND_SCANLINES,
+            size: 4.,
+            target: [320., 240.],
+            amount: 1.,
+            softness: 0.,
+        }
+    );
+    assert_eq!(size_of::<PostConstants>(), 24);
+}
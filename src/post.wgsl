@@ -0,0 +1,95 @@
+struct Effect {
+    kind: u32,
+    size: f32,
+    target_size: vec2<f32>,
+    amount: f32,
+    softness: f32,
+}
+
+var<push_constant> effect: Effect;
+
+@group(0) @binding(0)
+var r_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var r_sampler: sampler;
+
+const KIND_PIXELATE: u32 = 1u;
+const KIND_SCANLINES: u32 = 2u;
+const KIND_CURVATURE: u32 = 3u;
+const KIND_VIGNETTE: u32 = 4u;
+const KIND_OVERDRAW: u32 = 5u;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// Emits a single triangle that covers the entire target.
+@vertex
+fn vertex(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    var out: VertexOutput;
+    out.position = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+// Maps `amount` through a gradient from blue at 0.0, through green and yellow,
+// to red at 1.0.
+fn heat(amount: f32) -> vec3<f32> {
+    var stops = array<vec3<f32>, 4>(
+        vec3<f32>(0.0, 0.0, 1.0),
+        vec3<f32>(0.0, 1.0, 0.0),
+        vec3<f32>(1.0, 1.0, 0.0),
+        vec3<f32>(1.0, 0.0, 0.0),
+    );
+    let scaled = clamp(amount, 0.0, 1.0) * 3.0;
+    let index = min(u32(scaled), 2u);
+    return mix(stops[index], stops[index + 1u], scaled - f32(index));
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    var uv = in.uv;
+    if effect.kind == KIND_PIXELATE {
+        let block = max(effect.size, 1.0);
+        let center = (floor(in.position.xy / block) + 0.5) * block;
+        let dimensions = vec2<f32>(textureDimensions(r_texture));
+        let texel = clamp(floor(center / effect.target_size * dimensions), vec2<f32>(0.0), dimensions - 1.0);
+        return textureLoad(r_texture, vec2<i32>(texel), 0);
+    } else if effect.kind == KIND_OVERDRAW {
+        // The overdraw count is stored in the alpha channel in 8-bit levels.
+        let dimensions = vec2<f32>(textureDimensions(r_texture));
+        let texel = clamp(floor(uv * dimensions), vec2<f32>(0.0), dimensions - 1.0);
+        let count = round(textureLoad(r_texture, vec2<i32>(texel), 0).w * 255.0);
+        if count < 1.0 {
+            return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+        } else if count > effect.size {
+            return vec4<f32>(1.0);
+        }
+        let amount = select((count - 1.0) / (effect.size - 1.0), 1.0, effect.size <= 1.0);
+        return vec4<f32>(heat(amount), 1.0);
+    } else if effect.kind == KIND_CURVATURE {
+        let centered = uv * 2.0 - 1.0;
+        let curved = centered * (1.0 + effect.amount * centered.yx * centered.yx);
+        if any(abs(curved) > vec2<f32>(1.0)) {
+            return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+        }
+        uv = curved * 0.5 + 0.5;
+    }
+
+    let color = textureSampleLevel(r_texture, r_sampler, uv, 0.0);
+    var shade = 1.0;
+    if effect.kind == KIND_SCANLINES {
+        let spacing = max(effect.size, 1.0);
+        if floor(in.position.y) % spacing >= spacing * 0.5 {
+            shade = 1.0 - effect.amount;
+        }
+    } else if effect.kind == KIND_VIGNETTE {
+        // The distance from the center, where 1.0 is the distance to the
+        // corners.
+        let distance = length(in.uv - 0.5) * sqrt(2.0);
+        shade = 1.0 - smoothstep(effect.amount, effect.amount + max(effect.softness, 0.0001), distance);
+    }
+    return vec4<f32>(color.rgb * shade, color.a);
+}
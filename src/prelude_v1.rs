@@ -0,0 +1,49 @@
+//! A curated set of Kludgine's most commonly used types with a stronger
+//! stability guarantee than the rest of the crate.
+//!
+//! Kludgine is still evolving, and many of its modules change between
+//! releases. The items exported from this module are covered by the
+//! following guarantees:
+//!
+//! - No item will be removed or renamed without first being deprecated for at
+//!   least one release.
+//! - The module will not be changed to export different items with the same
+//!   names.
+//!
+//! These guarantees cover the items' names and locations. Methods on these
+//! types may still change, and are listed in the changelog when they do.
+//! Modules such as [`tilemap`](crate::tilemap) and `gui` should be considered
+//! experimental.
+//!
+//! When breaking changes to this set of items are necessary, a new
+//! `prelude_v2` module will be introduced alongside this one.
+//!
+//! ```rust
+//! use kludgine::prelude_v1::*;
+//! ```
+
+pub use crate::drawing::{Drawing, Renderer};
+pub use crate::shapes::{CornerRadii, Path, PathBuilder, Shape, StrokeOptions};
+#[cfg(feature = "cosmic-text")]
+pub use crate::text::{Text, TextOrigin};
+pub use crate::{
+    AnyTexture, Clipped, CollectedTexture, Color, Drawable, DrawableExt, Frame, Graphics, Kludgine,
+    LazyTexture, Origin, PreparedGraphic, RenderingGraphics, ShareableTexture, SharedTexture,
+    Texture, TextureCollection, TextureRegion,
+};
+
+// Removing or renaming any item covered by this module's guarantees fails to
+// compile these imports. Items are listed individually, rather than imported
+// using a glob, so that each one is checked.
+#[cfg(test)]
+#[allow(unused_imports)]
+mod stable_api {
+    use super::{
+        AnyTexture, Clipped, CollectedTexture, Color, CornerRadii, Drawable, DrawableExt, Drawing,
+        Frame, Graphics, Kludgine, LazyTexture, Origin, Path, PathBuilder, PreparedGraphic,
+        Renderer, RenderingGraphics, Shape, ShareableTexture, SharedTexture, StrokeOptions,
+        Texture, TextureCollection, TextureRegion,
+    };
+    #[cfg(feature = "cosmic-text")]
+    use super::{Text, TextOrigin};
+}
@@ -1,13 +1,14 @@
-use std::ops::Deref;
+use std::ops::{Deref, Sub};
 use std::sync::atomic::{self, AtomicUsize};
 use std::sync::{Arc, OnceLock};
 
 use figures::units::UPx;
-use figures::{Rect, Size};
+use figures::{Rect, Size, Zero};
 use smallvec::smallvec;
 
 use crate::buffer::Buffer;
-use crate::pipeline::{PreparedCommand, Vertex};
+use crate::pipeline::{vertex_bounds, ArrayTexture, PreparedCommand, TexturePattern, Vertex};
+use crate::upload::TextureUploads;
 use crate::{Graphics, KludgineId, PreparedGraphic};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
@@ -31,32 +32,48 @@ pub trait TextureSource {
     fn id(&self) -> TextureId;
     fn is_mask(&self) -> bool;
     fn bind_group(&self, graphics: &impl KludgineGraphics) -> Arc<wgpu::BindGroup>;
+    fn array_texture(&self, graphics: &impl KludgineGraphics) -> Option<ArrayTexture>;
     fn default_rect(&self) -> Rect<UPx>;
+    fn uv_rect(&self) -> Rect<UPx> {
+        self.default_rect()
+    }
 }
 
 pub trait ShapeSource<Unit> {
     fn vertices(&self) -> &[Vertex<Unit>];
     fn indices(&self) -> &[u32];
+    fn pattern(&self) -> Option<TexturePattern> {
+        None
+    }
+    /// Returns the vertices to keep for graphics that can be updated after
+    /// being prepared.
+    fn quad(&self) -> Option<[Vertex<Unit>; 4]> {
+        None
+    }
     fn prepare(
         &self,
         texture: Option<&impl TextureSource>,
         graphics: &Graphics<'_>,
     ) -> PreparedGraphic<Unit>
     where
+        Unit: Copy + Ord + Sub<Output = Unit> + Zero,
         Vertex<Unit>: bytemuck::Pod,
     {
         let vertices = Buffer::new(
             self.vertices(),
             wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            Some("kludgine::prepared vertices"),
             graphics.device,
         );
         let indices = Buffer::new(
             self.indices(),
             wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
+            Some("kludgine::prepared indices"),
             graphics.device,
         );
         PreparedGraphic {
             vertices,
+            quad: self.quad(),
             indices,
             commands: smallvec![PreparedCommand {
                 indices: 0..self
@@ -66,7 +83,9 @@ pub trait ShapeSource<Unit> {
                     .expect("too many drawn indices"),
                 is_mask: false,
                 binding: texture.map(|source| source.bind_group(graphics)),
+                pattern: self.pattern(),
             }],
+            bounds: vertex_bounds(self.vertices()),
         }
     }
 }
@@ -105,4 +124,5 @@ pub trait KludgineGraphics {
     fn nearest_sampler(&self) -> &wgpu::Sampler;
     fn linear_sampler(&self) -> &wgpu::Sampler;
     fn multisample_state(&self) -> wgpu::MultisampleState;
+    fn texture_uploads(&self) -> Option<&TextureUploads>;
 }
@@ -6,6 +6,19 @@ struct PushConstants {
     opacity: f32,
     translation_x: i32,
     translation_y: i32,
+    z: f32,
+    target_scale_x: f32,
+    target_scale_y: f32,
+    pattern_x: f32,
+    pattern_y: f32,
+    pattern_width: f32,
+    pattern_height: f32,
+    clip_center_x: f32,
+    clip_center_y: f32,
+    clip_half_width: f32,
+    clip_half_height: f32,
+    clip_cos: f32,
+    clip_sin: f32,
 }
 var<push_constant> pc: PushConstants;
 
@@ -19,11 +32,16 @@ struct VertexOutput {
     @builtin(position) position: vec4<f32>,
     @location(0) color: vec4<f32>,
     @location(1) uv: vec2<f32>,
+    // The texture array slot to sample, which is unused when sampling a single
+    // texture.
+    @location(2) @interpolate(flat) texture: u32,
 }
 
 struct Uniforms {
     ortho: mat4x4<f32>,
     dips_scale: u32,
+    // 0 when disabled, 1 for linear targets, and 2 for sRGB targets.
+    dither: u32,
 }
 
 @group(0) @binding(0)
@@ -62,8 +80,9 @@ fn int_to_rgba(color: u32) -> vec4<f32> {
     return vec4<f32>(f32(r) / 255.0, f32(g) / 255.0, f32(b) / 255.0, f32(a) / 255.0);
 }
 
-@vertex
-fn vertex(input: VertexInput) -> VertexOutput {
+// Transforms `input` into a vertex for `fragment()`. The `vertex()` entry point
+// is appended from either `texture.wgsl` or `texture_array.wgsl`.
+fn transform(input: VertexInput) -> VertexOutput {
     let flag_dips = u32(1);
     let flag_scale = flag_dips << u32(1);
     let flag_rotation = flag_dips << u32(2);
@@ -100,40 +119,175 @@ fn vertex(input: VertexInput) -> VertexOutput {
         );
     }
     outval.position = uniforms.ortho * vec4<f32>(position / 4., 0., 1.0);
+    // When rendering into a target that is a different size than the
+    // projection, keep the top-left origin and one-pixel-per-pixel scale.
+    outval.position.x = (outval.position.x + 1.0) * pc.target_scale_x - 1.0;
+    outval.position.y = (outval.position.y - 1.0) * pc.target_scale_y + 1.0;
+    outval.position.z = pc.z;
     outval.color = int_to_rgba(input.color);
     outval.color.a = pc.opacity * outval.color.a;
-    outval.uv = vec2<f32>(input.uv) / vec2<f32>(textureDimensions(r_texture));
+    outval.uv = vec2<f32>(input.uv) / 4.;
     return outval;
 }
 
+fn linear_to_srgb(color: vec3<f32>) -> vec3<f32> {
+    let low = color * 12.92;
+    let high = 1.055 * pow(color, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(high, low, color <= vec3<f32>(0.0031308));
+}
+
+fn srgb_to_linear(color: vec3<f32>) -> vec3<f32> {
+    let low = color / 12.92;
+    let high = pow((color + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(high, low, color <= vec3<f32>(0.04045));
+}
+
+// Offsets `color` by up to half of one 8-bit level using an 8x8 ordered
+// dithering matrix.
+fn dither(color: vec4<f32>, position: vec2<f32>) -> vec4<f32> {
+    let x = u32(position.x) & u32(7);
+    let y = u32(position.y) & u32(7);
+    let xy = x ^ y;
+    // Interleaving and reversing the bits of `x ^ y` and `y` produces the
+    // Bayer matrix's value for this position.
+    let index = ((xy & u32(1)) << u32(5)) | ((y & u32(1)) << u32(4))
+        | ((xy & u32(2)) << u32(2)) | ((y & u32(2)) << u32(1))
+        | ((xy & u32(4)) >> u32(1)) | ((y & u32(4)) >> u32(2));
+    let offset = ((f32(index) + 0.5) / 64.0 - 0.5) / 255.0;
+
+    var rgb = clamp(color.xyz, vec3<f32>(0.0), vec3<f32>(1.0));
+    if uniforms.dither == u32(2) {
+        rgb = srgb_to_linear(clamp(linear_to_srgb(rgb) + offset, vec3<f32>(0.0), vec3<f32>(1.0)));
+    } else {
+        rgb = clamp(rgb + offset, vec3<f32>(0.0), vec3<f32>(1.0));
+    }
+    // Fully transparent and fully opaque fragments must remain so.
+    var alpha = color.w;
+    if alpha > 0.0 && alpha < 1.0 {
+        alpha = clamp(alpha + offset, 0.0, 1.0);
+    }
+    return vec4<f32>(rgb, alpha);
+}
+
 struct FragmentInput {
+    @builtin(position) position: vec4<f32>,
     @location(0) color: vec4<f32>,
     @location(1) uv: vec2<f32>,
+    @location(2) @interpolate(flat) texture: u32,
 }
 
-@group(0)
+// The texture bindings, `vertex()`, and `sample_texture()` are appended from
+// either `texture.wgsl` or `texture_array.wgsl`.
+
+@group(1)
+@binding(0)
+var r_lookup: texture_2d<f32>;
+@group(1)
 @binding(1)
-var r_texture: texture_2d<f32>;
-@group(0)
-@binding(2)
-var r_sampler: sampler;
+var r_lookup_sampler: sampler;
+
+// Replaces `color` with the palette entry selected by its red channel.
+fn lookup_palette(color: vec4<f32>) -> vec4<f32> {
+    let entries = textureDimensions(r_lookup).x;
+    let index = u32(round(clamp(color.x, 0.0, 1.0) * f32(entries - u32(1))));
+    let entry = textureLoad(r_lookup, vec2<u32>(index, u32(0)), 0);
+    return vec4<f32>(entry.xyz, entry.w * color.w);
+}
+
+// Replaces `color` by interpolating a grading table stored as a strip of
+// `size` slices that are each `size` by `size` texels, ordered by blue.
+fn lookup_grading(color: vec4<f32>) -> vec4<f32> {
+    let size = f32(textureDimensions(r_lookup).y);
+    let scaled = clamp(color.xyz, vec3<f32>(0.0), vec3<f32>(1.0)) * (size - 1.0);
+    let slice = floor(scaled.z);
+    let next_slice = min(slice + 1.0, size - 1.0);
+    let texel = scaled.xy + vec2<f32>(0.5);
+    let dimensions = vec2<f32>(size * size, size);
+    let low = textureSampleLevel(
+        r_lookup,
+        r_lookup_sampler,
+        (texel + vec2<f32>(slice * size, 0.0)) / dimensions,
+        0.0,
+    );
+    let high = textureSampleLevel(
+        r_lookup,
+        r_lookup_sampler,
+        (texel + vec2<f32>(next_slice * size, 0.0)) / dimensions,
+        0.0,
+    );
+    return vec4<f32>(mix(low.xyz, high.xyz, scaled.z - slice), color.w);
+}
 
 @fragment
 fn fragment(fragment: FragmentInput) -> @location(0) vec4<f32> {
     let flag_textured = u32(1) << u32(4);
     let flag_masked = u32(1) << u32(5);
+    let flag_depth = u32(1) << u32(6);
+    let flag_pattern_repeat = u32(1) << u32(7);
+    let flag_pattern_mirror = u32(1) << u32(8);
+    let flag_clip_rotated = u32(1) << u32(9);
+    let flag_lookup_palette = u32(1) << u32(11);
+    let flag_lookup_grading = u32(1) << u32(12);
 
     var color = fragment.color;
 
     let flags = u32(pc.flags);
     if (flags & flag_textured) != u32(0) {
-        let sample = textureSample(r_texture, r_sampler, fragment.uv / 4.);
+        var uv = fragment.uv;
+        if (flags & (flag_pattern_repeat | flag_pattern_mirror)) != u32(0) {
+            // Wrap the texture coordinate into the pattern's region.
+            let origin = vec2<f32>(pc.pattern_x, pc.pattern_y);
+            let size = vec2<f32>(pc.pattern_width, pc.pattern_height);
+            let local = uv - origin;
+            if (flags & flag_pattern_mirror) != u32(0) {
+                let period = size * 2.;
+                uv = origin + size - abs(local - floor(local / period) * period - size);
+            } else {
+                uv = origin + local - floor(local / size) * size;
+            }
+        }
+        let sample = sample_texture(uv, fragment.texture);
         if (flags & flag_masked) != u32(0) {
-            return vec4<f32>(color.x, color.y, color.z, sample.x * color.w);
+            color = vec4<f32>(color.x, color.y, color.z, sample.x * color.w);
+        } else {
+            color = sample * color;
         }
+    }
+
+    if (flags & flag_lookup_palette) != u32(0) {
+        color = lookup_palette(color);
+    } else if (flags & flag_lookup_grading) != u32(0) {
+        color = lookup_grading(color);
+    }
+
+    if (flags & flag_clip_rotated) != u32(0) {
+        // Undo the clip's rotation and fade out fragments as they cross the
+        // clip's edges, which anti-aliases the rotated edges.
+        let offset = fragment.position.xy - vec2<f32>(pc.clip_center_x, pc.clip_center_y);
+        let local = vec2<f32>(
+            offset.x * pc.clip_cos + offset.y * pc.clip_sin,
+            offset.y * pc.clip_cos - offset.x * pc.clip_sin,
+        );
+        let inside = vec2<f32>(pc.clip_half_width, pc.clip_half_height) - abs(local);
+        color.w = color.w * clamp(min(inside.x, inside.y) + 0.5, 0.0, 1.0);
+    }
+
+    // Fully transparent fragments must not write to the depth buffer, or
+    // they would hide graphics with a lower z drawn afterwards.
+    if (flags & flag_depth) != u32(0) && color.w <= 0.0 {
+        discard;
+    }
 
-        return sample * color;
+    if uniforms.dither != u32(0) {
+        color = dither(color, fragment.position.xy);
     }
 
     return color;
 }
+
+// Used instead of `fragment()` while rendering overdraw. Each fragment adds one
+// 8-bit level to the alpha channel, counting how many times a pixel is drawn.
+@fragment
+fn overdraw(fragment: FragmentInput) -> @location(0) vec4<f32> {
+    return vec4<f32>(0.0, 0.0, 0.0, 1.0 / 255.0);
+}
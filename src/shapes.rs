@@ -7,20 +7,38 @@ use figures::{
     Size, Zero,
 };
 use lyon_tessellation::geom::Arc;
+use lyon_tessellation::path::iterator::PathIterator;
 use lyon_tessellation::{
     FillGeometryBuilder, FillTessellator, FillVertex, FillVertexConstructor, GeometryBuilder,
     GeometryBuilderError, StrokeGeometryBuilder, StrokeTessellator, StrokeVertex,
     StrokeVertexConstructor, VertexId,
 };
-pub use lyon_tessellation::{FillOptions, LineCap, LineJoin, Orientation};
+pub use lyon_tessellation::{FillOptions, FillRule, LineCap, LineJoin, Orientation};
 use smallvec::SmallVec;
 
-use crate::pipeline::Vertex;
+use self::boolean::Contour;
+use crate::pipeline::{TexturePattern, Vertex};
 use crate::{
     sealed, srgb_to_linear, Assert, Color, DrawableSource, Graphics, Origin, PreparedGraphic,
     ShapeSource, Texture, TextureSource,
 };
 
+mod boolean;
+mod cache;
+mod feather;
+mod gradient;
+mod morph;
+mod polyline;
+mod spline;
+#[cfg(feature = "svg")]
+mod svg;
+
+pub use self::cache::{CacheStatistics, CachedPath};
+pub use self::gradient::{ColorStop, Gradient, GradientInterpolation, GradientKind};
+pub use self::polyline::Polyline;
+#[cfg(feature = "svg")]
+pub use self::svg::{Svg, SvgElement, SvgError, SvgFill, SvgPaint, SvgStroke};
+
 /// A tesselated shape.
 ///
 /// This structure contains geometry that has been divided into triangles, ready
@@ -29,7 +47,11 @@ use crate::{
 #[derive(Debug, Clone, PartialEq)]
 pub struct Shape<Unit, const TEXTURED: bool> {
     pub(crate) vertices: SmallVec<[Vertex<Unit>; 6]>,
-    pub(crate) indices: SmallVec<[u32; 20]>,
+    // Two fewer inline indices make room for `pattern` without growing
+    // `Shape`.
+    pub(crate) indices: SmallVec<[u32; 18]>,
+    // Patterns are rare, so they are stored out of line to keep shapes small.
+    pub(crate) pattern: Option<Box<TexturePattern>>,
 }
 
 #[test]
@@ -42,6 +64,7 @@ impl<Unit, const TEXTURED: bool> Default for Shape<Unit, TEXTURED> {
         Self {
             vertices: SmallVec::new(),
             indices: SmallVec::new(),
+            pattern: None,
         }
     }
 }
@@ -108,6 +131,123 @@ impl<Unit: PixelScaling> Shape<Unit, false> {
         shape_builder.shape
     }
 
+    /// Returns an oval sized `radii` oriented around `center` that is filled
+    /// solid with `color`.
+    pub fn filled_ellipse(center: Point<Unit>, radii: Size<Unit>, color: Color) -> Self
+    where
+        Unit: FloatConversion<Float = f32> + ScreenScale<Px = Px> + Copy,
+    {
+        Path::ellipse(center, radii).fill_opt(
+            color,
+            &FillOptions::tolerance(pixel_tolerance::<Unit>(FillOptions::DEFAULT_TOLERANCE)),
+        )
+    }
+
+    /// Returns an oval sized `radii` oriented around `center` that is stroked
+    /// with `options`. [`StrokeOptions::tolerance`] is measured in pixels,
+    /// regardless of `Unit`.
+    pub fn stroked_ellipse(
+        center: Point<Unit>,
+        radii: Size<Unit>,
+        options: impl Into<StrokeOptions<Unit>>,
+    ) -> Self
+    where
+        Unit: FloatConversion<Float = f32> + ScreenScale<Px = Px> + Copy,
+    {
+        let mut options = options.into();
+        options.tolerance = pixel_tolerance::<Unit>(options.tolerance);
+        Path::ellipse(center, radii).stroke(options)
+    }
+
+    /// Returns the region between an arc and the straight line connecting its
+    /// ends, filled solid with `color`.
+    ///
+    /// The arc is part of an oval sized `radii` oriented around `center`. It
+    /// starts at `start` angle and sweeps in the direction of `winding` a
+    /// rotation of `sweep` angle.
+    pub fn filled_arc(
+        center: Point<Unit>,
+        radii: Size<Unit>,
+        start: Angle,
+        sweep: Angle,
+        winding: Winding,
+        color: Color,
+    ) -> Self
+    where
+        Unit: FloatConversion<Float = f32> + ScreenScale<Px = Px> + Copy,
+    {
+        Path::directed_arc(center, radii, start, sweep, winding).fill_opt(
+            color,
+            &FillOptions::tolerance(pixel_tolerance::<Unit>(FillOptions::DEFAULT_TOLERANCE)),
+        )
+    }
+
+    /// Returns an arc that is stroked with `options`.
+    ///
+    /// The arc is part of an oval sized `radii` oriented around `center`. It
+    /// starts at `start` angle and sweeps in the direction of `winding` a
+    /// rotation of `sweep` angle. [`StrokeOptions::tolerance`] is measured in
+    /// pixels, regardless of `Unit`.
+    pub fn stroked_arc(
+        center: Point<Unit>,
+        radii: Size<Unit>,
+        start: Angle,
+        sweep: Angle,
+        winding: Winding,
+        options: impl Into<StrokeOptions<Unit>>,
+    ) -> Self
+    where
+        Unit: FloatConversion<Float = f32> + ScreenScale<Px = Px> + Copy,
+    {
+        let mut options = options.into();
+        options.tolerance = pixel_tolerance::<Unit>(options.tolerance);
+        Path::directed_arc(center, radii, start, sweep, winding).stroke(options)
+    }
+
+    /// Returns a wedge of an oval that is filled solid with `color`.
+    ///
+    /// The oval is sized `radii` and oriented around `center`. The wedge's
+    /// curved edge starts at `start` angle and sweeps in the direction of
+    /// `winding` a rotation of `sweep` angle.
+    pub fn filled_pie(
+        center: Point<Unit>,
+        radii: Size<Unit>,
+        start: Angle,
+        sweep: Angle,
+        winding: Winding,
+        color: Color,
+    ) -> Self
+    where
+        Unit: FloatConversion<Float = f32> + ScreenScale<Px = Px> + Copy,
+    {
+        Path::pie(center, radii, start, sweep, winding).fill_opt(
+            color,
+            &FillOptions::tolerance(pixel_tolerance::<Unit>(FillOptions::DEFAULT_TOLERANCE)),
+        )
+    }
+
+    /// Returns a wedge of an oval that has its outline stroked with `options`.
+    ///
+    /// The oval is sized `radii` and oriented around `center`. The wedge's
+    /// curved edge starts at `start` angle and sweeps in the direction of
+    /// `winding` a rotation of `sweep` angle. [`StrokeOptions::tolerance`] is
+    /// measured in pixels, regardless of `Unit`.
+    pub fn stroked_pie(
+        center: Point<Unit>,
+        radii: Size<Unit>,
+        start: Angle,
+        sweep: Angle,
+        winding: Winding,
+        options: impl Into<StrokeOptions<Unit>>,
+    ) -> Self
+    where
+        Unit: FloatConversion<Float = f32> + ScreenScale<Px = Px> + Copy,
+    {
+        let mut options = options.into();
+        options.tolerance = pixel_tolerance::<Unit>(options.tolerance);
+        Path::pie(center, radii, start, sweep, winding).stroke(options)
+    }
+
     /// Returns a rectangle that is filled solid with `color`.
     pub fn filled_rect(rect: Rect<Unit>, color: Color) -> Self
     where
@@ -139,6 +279,11 @@ impl<Unit: PixelScaling> Shape<Unit, false> {
 
     /// Returns a rounded rectangle with the specified corner radii that is
     /// filled solid with `color`.
+    ///
+    /// Each corner's radius is configured independently, and a radius of zero
+    /// produces a square corner. The corners are tessellated so that they
+    /// deviate from a true curve by at most a tenth of a pixel, which keeps
+    /// large corners smooth without adding vertices to small ones.
     pub fn filled_round_rect(
         rect: Rect<Unit>,
         corner_radius: impl Into<CornerRadii<Unit>>,
@@ -152,15 +297,23 @@ impl<Unit: PixelScaling> Shape<Unit, false> {
             + TryFrom<i32>
             + Ord
             + FloatConversion<Float = f32>
+            + ScreenScale<Px = Px>
             + Copy,
         Unit::Error: Debug,
     {
         let path = Path::round_rect(rect, corner_radius);
-        path.fill(color)
+        path.fill_opt(
+            color,
+            &FillOptions::tolerance(pixel_tolerance::<Unit>(FillOptions::DEFAULT_TOLERANCE)),
+        )
     }
 
     /// Returns a rounded rectangle with the specified corner radii that has its
     /// outline stroked with `color` and `options`.
+    ///
+    /// Each corner's radius is configured independently, and a radius of zero
+    /// produces a square corner. [`StrokeOptions::tolerance`] is measured in
+    /// pixels, regardless of `Unit`.
     pub fn stroked_round_rect(
         rect: Rect<Unit>,
         corner_radius: impl Into<CornerRadii<Unit>>,
@@ -174,9 +327,12 @@ impl<Unit: PixelScaling> Shape<Unit, false> {
             + TryFrom<i32>
             + Ord
             + FloatConversion<Float = f32>
+            + ScreenScale<Px = Px>
             + Copy,
         Unit::Error: Debug,
     {
+        let mut options = options.into();
+        options.tolerance = pixel_tolerance::<Unit>(options.tolerance);
         let path = Path::round_rect(rect, corner_radius);
         path.stroke(options)
     }
@@ -185,7 +341,7 @@ impl<Unit: PixelScaling> Shape<Unit, false> {
     #[must_use]
     pub fn prepare(&self, graphics: &Graphics<'_>) -> PreparedGraphic<Unit>
     where
-        Unit: Copy,
+        Unit: Copy + Ord + Sub<Output = Unit> + Zero,
         Vertex<Unit>: bytemuck::Pod,
     {
         sealed::ShapeSource::prepare(self, Option::<&Texture>::None, graphics)
@@ -200,7 +356,7 @@ impl<Unit> Shape<Unit, true> {
         graphics: &Graphics<'_>,
     ) -> PreparedGraphic<Unit>
     where
-        Unit: Copy,
+        Unit: Copy + Ord + Sub<Output = Unit> + Zero,
         Vertex<Unit>: bytemuck::Pod,
     {
         sealed::ShapeSource::prepare(self, Some(texture), graphics)
@@ -208,6 +364,9 @@ impl<Unit> Shape<Unit, true> {
 
     /// Returns a rounded rectangle with the specified corner radii that is
     /// textured using the texture region and blending color.
+    ///
+    /// The corners are tessellated with the same quality as
+    /// [`Shape::filled_round_rect`].
     pub fn textured_round_rect(
         rect: Rect<Unit>,
         corner_radius: impl Into<CornerRadii<Unit>>,
@@ -222,12 +381,92 @@ impl<Unit> Shape<Unit, true> {
             + TryFrom<i32>
             + Ord
             + FloatConversion<Float = f32>
+            + ScreenScale<Px = Px>
             + Copy
             + PixelScaling,
         Unit::Error: Debug,
     {
         let path = Path::textured_round_rect(rect, corner_radius, texture_region);
-        path.fill(color)
+        path.fill_opt(
+            color,
+            &FillOptions::tolerance(pixel_tolerance::<Unit>(FillOptions::DEFAULT_TOLERANCE)),
+        )
+    }
+
+    /// Returns `path` filled with `color` and `texture` repeated across it
+    /// according to `pattern`.
+    ///
+    /// The sampled texture colors are multiplied with `color` and the colors
+    /// of the path's endpoints. The returned shape must be drawn using
+    /// `texture`. When `texture` is part of a
+    /// [`TextureCollection`](crate::TextureCollection) and is sampled using
+    /// linear filtering, neighboring texels from outside of its region may
+    /// blend into the edges of each repetition.
+    pub fn textured_fill(
+        path: &Path<Unit, false>,
+        texture: &impl TextureSource,
+        pattern: FillPattern<Unit>,
+        color: Color,
+    ) -> Self
+    where
+        Unit: FloatConversion<Float = f32> + ScreenScale<Px = Px> + Copy + PixelScaling,
+    {
+        let filled = path.fill_opt(
+            color,
+            &FillOptions::tolerance(pixel_tolerance::<Unit>(FillOptions::DEFAULT_TOLERANCE)),
+        );
+        let region = texture.uv_rect();
+        let texel = pixel_tolerance::<Unit>(pattern.scale);
+        let (sin, cos) = pattern.rotation.into_raidans_f().sin_cos();
+        let offset = pattern.offset.map(Unit::into_float);
+        let uvs = filled
+            .vertices
+            .iter()
+            .map(|vertex| {
+                let location = vertex.location.map(Unit::into_float);
+                let (x, y) = (location.x - offset.x, location.y - offset.y);
+                Point::new((x * cos + y * sin) / texel, (y * cos - x * sin) / texel)
+            })
+            .collect::<Vec<_>>();
+
+        // Texture coordinates can't be negative. Shifting them by a whole
+        // number of repetitions leaves the pattern unchanged.
+        let period = match pattern.wrap {
+            PatternWrap::Repeat => 1.,
+            PatternWrap::Mirror => 2.,
+        };
+        let shift = |minimum: f32, size: UPx| {
+            let period = size.into_float() * period;
+            if minimum < 0. && period > 0. {
+                (-minimum / period).ceil() * period
+            } else {
+                0.
+            }
+        };
+        let minimum = uvs.iter().fold(Point::new(0., 0.), |minimum, uv| {
+            Point::new(minimum.x.min(uv.x), minimum.y.min(uv.y))
+        });
+        let origin = Point::new(
+            region.origin.x.into_float() + shift(minimum.x, region.size.width),
+            region.origin.y.into_float() + shift(minimum.y, region.size.height),
+        );
+
+        Self {
+            vertices: filled
+                .vertices
+                .into_iter()
+                .zip(uvs)
+                .map(|(vertex, uv)| Vertex {
+                    texture: Point::new(origin.x + uv.x, origin.y + uv.y).map(UPx::from_float),
+                    ..vertex
+                })
+                .collect(),
+            indices: filled.indices,
+            pattern: Some(Box::new(TexturePattern {
+                region,
+                wrap: pattern.wrap,
+            })),
+        }
     }
 
     /// Returns a rectangle that is textured using the texture region and
@@ -289,6 +528,117 @@ impl<Unit> Shape<Unit, true> {
     }
 }
 
+/// Options for filling a shape with a repeating texture.
+///
+/// See [`Shape::textured_fill()`] for more information.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FillPattern<Unit> {
+    /// The size of each texel, in pixels at a 1x scale. Defaults to `1.0`.
+    pub scale: f32,
+    /// The location within the shape where the texture's top-left corner is
+    /// placed. Defaults to the shape's origin.
+    pub offset: Point<Unit>,
+    /// The rotation of the texture around [`offset`](Self::offset). Defaults
+    /// to no rotation.
+    pub rotation: Angle,
+    /// How the texture repeats. Defaults to [`PatternWrap::Repeat`].
+    pub wrap: PatternWrap,
+}
+
+impl<Unit> FillPattern<Unit> {
+    /// Sets the size of each texel, in pixels at a 1x scale, and returns
+    /// self.
+    #[must_use]
+    pub fn scaled(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Sets the location of the texture's top-left corner and returns self.
+    #[must_use]
+    pub fn offset(mut self, offset: Point<Unit>) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets the rotation of the texture and returns self.
+    #[must_use]
+    pub fn rotated(mut self, rotation: Angle) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Mirrors every other repetition of the texture and returns self.
+    #[must_use]
+    pub fn mirrored(mut self) -> Self {
+        self.wrap = PatternWrap::Mirror;
+        self
+    }
+}
+
+impl<Unit> Default for FillPattern<Unit>
+where
+    Unit: Zero,
+{
+    fn default() -> Self {
+        Self {
+            scale: 1.,
+            offset: Point::new(Unit::ZERO, Unit::ZERO),
+            rotation: Angle::ZERO,
+            wrap: PatternWrap::Repeat,
+        }
+    }
+}
+
+/// How a texture repeats across a shape filled using a [`FillPattern`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum PatternWrap {
+    /// Each repetition is identical.
+    #[default]
+    Repeat,
+    /// Every other repetition is mirrored, causing the edges of neighboring
+    /// repetitions to match.
+    Mirror,
+}
+
+/// Converts `tolerance`, measured in pixels, into `Unit`s at a 1x scale.
+fn pixel_tolerance<Unit>(tolerance: f32) -> f32
+where
+    Unit: ScreenScale<Px = Px> + FloatConversion<Float = f32>,
+{
+    scaled_pixel_tolerance::<Unit>(tolerance, figures::Fraction::ONE)
+}
+
+/// Returns `tolerance` pixels converted to `Unit` when rendered at `scale`.
+pub(crate) fn scaled_pixel_tolerance<Unit>(tolerance: f32, scale: figures::Fraction) -> f32
+where
+    Unit: ScreenScale<Px = Px> + FloatConversion<Float = f32>,
+{
+    Unit::from_px(Px::new(1), scale).into_float() * tolerance
+}
+
+#[test]
+fn round_rect_tessellation() {
+    let small = Shape::filled_round_rect(
+        Rect::new(Point::ZERO, Size::squared(Px::new(10))),
+        Px::new(4),
+        Color::WHITE,
+    );
+    let large = Shape::filled_round_rect(
+        Rect::new(Point::ZERO, Size::squared(Px::new(1000))),
+        Px::new(400),
+        Color::WHITE,
+    );
+    assert!(large.vertices.len() > small.vertices.len());
+
+    let square_top = Shape::filled_round_rect(
+        Rect::new(Point::ZERO, Size::squared(Px::new(1000))),
+        CornerRadii::new(Px::ZERO, Px::ZERO, Px::new(400), Px::new(400)),
+        Color::WHITE,
+    );
+    assert!(square_top.vertices.len() < large.vertices.len());
+}
+
 impl<Unit, const TEXTURED: bool> ShapeSource<Unit, TEXTURED> for Shape<Unit, TEXTURED> where
     Unit: Copy
 {
@@ -307,12 +657,17 @@ where
     fn indices(&self) -> &[u32] {
         &self.indices
     }
+
+    fn pattern(&self) -> Option<TexturePattern> {
+        self.pattern.as_deref().copied()
+    }
 }
 
 struct ShapeBuilder<Unit, const TEXTURED: bool> {
     shape: Shape<Unit, TEXTURED>,
     default_color: Color,
     texture_region: Option<TextureRegions>,
+    gradient: Option<(Gradient, GradientKind<f32>)>,
 }
 
 struct TextureRegions {
@@ -351,31 +706,43 @@ where
             shape: Shape::default(),
             default_color,
             texture_region: None,
+            gradient: None,
         }
     }
 
+    fn with_gradient(mut self, gradient: Gradient, kind: GradientKind<f32>) -> Self {
+        self.gradient = Some((gradient, kind));
+        self
+    }
+
     fn new_vertex(
         &mut self,
         position: lyon_tessellation::math::Point,
         attributes: &[f32],
     ) -> Vertex<Unit> {
+        let default_color =
+            self.gradient
+                .as_ref()
+                .map_or(self.default_color, |(gradient, kind)| {
+                    gradient.sample(kind.position_of(Point::new(position.x, position.y)))
+                });
         let (texture, red, green, blue, alpha) = match attributes.len() {
             0 => (
                 self.texture_region
                     .as_ref()
                     .map(|region| region.uv_coordinate(position))
                     .unwrap_or_default(),
-                self.default_color.red_f32(),
-                self.default_color.green_f32(),
-                self.default_color.blue_f32(),
-                self.default_color.alpha_f32(),
+                default_color.red_f32(),
+                default_color.green_f32(),
+                default_color.blue_f32(),
+                default_color.alpha_f32(),
             ),
             6 => (
                 Point::new(attributes[0], attributes[1]).cast(),
-                self.default_color.red_f32() * attributes[2],
-                self.default_color.green_f32() * attributes[3],
-                self.default_color.blue_f32() * attributes[4],
-                self.default_color.alpha_f32() * attributes[5],
+                default_color.red_f32() * attributes[2],
+                default_color.green_f32() * attributes[3],
+                default_color.blue_f32() * attributes[4],
+                default_color.alpha_f32() * attributes[5],
             ),
             _ => unreachable!("Attributes should be empty or 2"),
         };
@@ -481,6 +848,7 @@ where
 
 /// A point on a [`Path`].
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Endpoint<Unit> {
     /// The location of the endpoint on a path.
     pub location: Point<Unit>,
@@ -521,6 +889,7 @@ pub type ControlPoint<Unit> = Point<Unit>;
 
 /// An entry in a [`Path`].
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PathEvent<Unit> {
     /// Begins a path. Must be at the start.
     Begin {
@@ -565,6 +934,7 @@ pub enum PathEvent<Unit> {
 
 /// A geometric shape defined by a path.
 #[derive(Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Path<Unit, const TEXTURED: bool> {
     /// A small-vec of path events. Contains enough stack space to contain the
     /// path of a hexagon, because it's the bestagon.
@@ -731,46 +1101,399 @@ impl<Unit> Path<Unit, false> {
     /// direction a rotation of `sweep` angle.
     #[must_use]
     pub fn arc(center: Point<Unit>, radii: Size<Unit>, start: Angle, sweep: Angle) -> Self
+    where
+        Unit: FloatConversion<Float = f32>,
+    {
+        Self::directed_arc(center, radii, start, sweep, Winding::Clockwise)
+    }
+
+    /// Returns a path forming an arc starting at `start` angle of an oval sized
+    /// `radii` oriented around `center`. The arc will sweep in the direction of
+    /// `winding` a rotation of `sweep` angle.
+    #[must_use]
+    pub fn directed_arc(
+        center: Point<Unit>,
+        radii: Size<Unit>,
+        start: Angle,
+        sweep: Angle,
+        winding: Winding,
+    ) -> Self
     where
         Unit: FloatConversion<Float = f32>,
     {
         let mut events = SmallVec::new();
-        Arc {
-            center: lyon_tessellation::geom::point(center.x.into_float(), center.y.into_float()),
-            radii: lyon_tessellation::geom::vector(
-                radii.width.into_float(),
-                radii.height.into_float(),
-            ),
-            start_angle: lyon_tessellation::geom::Angle::degrees(start.into_degrees()),
-            sweep_angle: lyon_tessellation::geom::Angle::degrees(sweep.into_degrees()),
-            x_rotation: lyon_tessellation::geom::Angle::degrees(0.),
+        push_arc(&mut events, center, radii, start, sweep, winding);
+        events.push(PathEvent::End {
+            close: sweep == Angle::MAX,
+        });
+        Self { events }
+    }
+
+    /// Returns a closed path forming an oval sized `radii` oriented around
+    /// `center`.
+    #[must_use]
+    pub fn ellipse(center: Point<Unit>, radii: Size<Unit>) -> Self
+    where
+        Unit: FloatConversion<Float = f32>,
+    {
+        Self::arc(center, radii, Angle::ZERO, Angle::MAX)
+    }
+
+    /// Returns a closed path forming a wedge of an oval sized `radii` oriented
+    /// around `center`. The wedge's curved edge starts at `start` angle and
+    /// sweeps in the direction of `winding` a rotation of `sweep` angle.
+    #[must_use]
+    pub fn pie(
+        center: Point<Unit>,
+        radii: Size<Unit>,
+        start: Angle,
+        sweep: Angle,
+        winding: Winding,
+    ) -> Self
+    where
+        Unit: FloatConversion<Float = f32> + Copy,
+    {
+        let mut events = SmallVec::new();
+        events.push(PathEvent::Begin {
+            at: center.into(),
+            texture: Point::ZERO,
+        });
+        push_arc(&mut events, center, radii, start, sweep, winding);
+        events.push(PathEvent::End { close: true });
+        Self { events }
+    }
+}
+
+/// Pushes the curves of an arc onto `events`. If `events` is empty, the path
+/// begins at the start of the arc. Otherwise, a line connects the current
+/// location to the start of the arc.
+fn push_arc<Unit>(
+    events: &mut SmallVec<[PathEvent<Unit>; 7]>,
+    center: Point<Unit>,
+    radii: Size<Unit>,
+    start: Angle,
+    sweep: Angle,
+    winding: Winding,
+) where
+    Unit: FloatConversion<Float = f32>,
+{
+    let sweep = match winding {
+        Winding::Clockwise => sweep.into_degrees(),
+        Winding::CounterClockwise => -sweep.into_degrees::<f32>(),
+    };
+    let mut started = false;
+    Arc {
+        center: lyon_tessellation::geom::point(center.x.into_float(), center.y.into_float()),
+        radii: lyon_tessellation::geom::vector(radii.width.into_float(), radii.height.into_float()),
+        start_angle: lyon_tessellation::geom::Angle::degrees(start.into_degrees()),
+        sweep_angle: lyon_tessellation::geom::Angle::degrees(sweep),
+        x_rotation: lyon_tessellation::geom::Angle::degrees(0.),
+    }
+    .for_each_cubic_bezier(&mut |segment| {
+        if !started {
+            started = true;
+            let from = Point::new(segment.from.x, segment.from.y)
+                .map(Unit::from_float)
+                .into();
+            events.push(if events.is_empty() {
+                PathEvent::Begin {
+                    at: from,
+                    texture: Point::ZERO,
+                }
+            } else {
+                PathEvent::Line {
+                    to: from,
+                    texture: Point::ZERO,
+                }
+            });
         }
-        .for_each_cubic_bezier(&mut |segment| {
-            if events.is_empty() {
-                events.push(PathEvent::Begin {
-                    at: Point::new(segment.from.x, segment.from.y)
-                        .map(Unit::from_float)
-                        .into(),
+        events.push(PathEvent::Cubic {
+            ctrl1: Point::new(segment.ctrl1.x, segment.ctrl1.y).map(Unit::from_float),
+            ctrl2: Point::new(segment.ctrl2.x, segment.ctrl2.y).map(Unit::from_float),
+            to: Point::new(segment.to.x, segment.to.y)
+                .map(Unit::from_float)
+                .into(),
+            texture: Point::ZERO,
+        });
+    });
+}
+
+impl<Unit> Path<Unit, false>
+where
+    Unit: FloatConversion<Float = f32> + ScreenScale<Px = Px> + Copy + PixelScaling,
+{
+    /// Returns a path enclosing the region produced by combining the regions
+    /// enclosed by this path and `other` using `operation`.
+    ///
+    /// Both paths are interpreted using the even-odd fill rule. Curves are
+    /// flattened into line segments that deviate from the original curves by
+    /// at most a tenth of a pixel. The returned path is made of straight
+    /// lines, and the colors of the original endpoints are not preserved.
+    #[must_use]
+    pub fn combine(&self, other: &Self, operation: PathOperation) -> Self {
+        let tolerance = pixel_tolerance::<Unit>(FillOptions::DEFAULT_TOLERANCE);
+        Self::from_contours(boolean::combine(
+            &self.contours(tolerance),
+            &other.contours(tolerance),
+            operation,
+        ))
+    }
+
+    /// Returns a path enclosing the area covered when this path is stroked
+    /// using `options`.
+    ///
+    /// This converts a stroke into a shape that can be cached, hit-tested, or
+    /// filled using a gradient or [pattern](Shape::textured_fill). Where the
+    /// stroke overlaps itself, such as on the inside of sharp corners, the
+    /// returned path overlaps itself as well. To cover the same area as the
+    /// stroke, it must be filled using the non-zero fill rule:
+    ///
+    /// ```rust
+    /// use kludgine::figures::units::Px;
+    /// use kludgine::figures::Point;
+    /// use kludgine::shapes::{FillRule, PathBuilder, StrokeOptions};
+    /// use kludgine::Color;
+    ///
+    /// let path = PathBuilder::new(Point::new(Px::new(0), Px::new(0)))
+    ///     .line_to(Point::new(Px::new(50), Px::new(10)))
+    ///     .line_to(Point::new(Px::new(0), Px::new(20)))
+    ///     .build();
+    /// let outline = path.outline(StrokeOptions::px_wide(Px::new(4)));
+    /// let shape = outline.fill_with_rule(Color::RED, FillRule::NonZero);
+    /// ```
+    ///
+    /// The returned path is made of straight lines, and the colors of the
+    /// original endpoints are not preserved.
+    #[must_use]
+    pub fn outline(&self, options: impl Into<StrokeOptions<Unit>>) -> Self {
+        let stroke = self.stroke(options);
+        let location = |index: u32| {
+            stroke.vertices[usize::try_from(index).assert("too many vertices")]
+                .location
+                .map(Unit::into_float)
+        };
+        Self::from_contours(boolean::outline(
+            stroke
+                .indices
+                .chunks_exact(3)
+                .map(|triangle| [triangle[0], triangle[1], triangle[2]].map(location)),
+        ))
+    }
+
+    fn from_contours(contours: Vec<Contour>) -> Self {
+        contours
+            .into_iter()
+            .flat_map(|contour| {
+                let mut points = contour
+                    .into_iter()
+                    .map(|point| Endpoint::from(point.map(Unit::from_float)));
+                let begin = points.next().map(|at| PathEvent::Begin {
+                    at,
                     texture: Point::ZERO,
                 });
+                begin
+                    .into_iter()
+                    .chain(points.map(|to| PathEvent::Line {
+                        to,
+                        texture: Point::ZERO,
+                    }))
+                    .chain([PathEvent::End { close: true }])
+            })
+            .collect()
+    }
+
+    /// Returns a path enclosing the regions enclosed by either this path or
+    /// `other`.
+    ///
+    /// See [`combine()`](Self::combine) for more information.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        self.combine(other, PathOperation::Union)
+    }
+
+    /// Returns a path enclosing the region enclosed by both this path and
+    /// `other`.
+    ///
+    /// See [`combine()`](Self::combine) for more information.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.combine(other, PathOperation::Intersection)
+    }
+
+    /// Returns a path enclosing the region enclosed by this path that is not
+    /// enclosed by `other`. This can be used to cut holes into a shape.
+    ///
+    /// See [`combine()`](Self::combine) for more information.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        self.combine(other, PathOperation::Difference)
+    }
+
+    /// Returns the closed polygons approximating this path.
+    fn contours(&self, tolerance: f32) -> Vec<Contour> {
+        let mut contours = Vec::new();
+        let mut current = Vec::new();
+        for event in self.as_lyon().iter().flattened(tolerance) {
+            match event {
+                lyon_tessellation::path::Event::Begin { at } => {
+                    current.push(Point::new(at.x, at.y));
+                }
+                lyon_tessellation::path::Event::Line { to, .. }
+                | lyon_tessellation::path::Event::Quadratic { to, .. }
+                | lyon_tessellation::path::Event::Cubic { to, .. } => {
+                    current.push(Point::new(to.x, to.y));
+                }
+                lyon_tessellation::path::Event::End { .. } => {
+                    // Open subpaths are closed when filled.
+                    if current.len() >= 3 {
+                        contours.push(std::mem::take(&mut current));
+                    } else {
+                        current.clear();
+                    }
+                }
             }
-            events.push(PathEvent::Cubic {
-                ctrl1: Point::new(segment.ctrl1.x, segment.ctrl1.y).map(Unit::from_float),
-                ctrl2: Point::new(segment.ctrl2.x, segment.ctrl2.y).map(Unit::from_float),
-                to: Point::new(segment.to.x, segment.to.y)
-                    .map(Unit::from_float)
-                    .into(),
-                texture: Point::ZERO,
-            });
-        });
+        }
+        contours
+    }
+}
 
-        events.push(PathEvent::End {
-            close: sweep == Angle::MAX,
-        });
-        Self { events }
+impl<Unit> Path<Unit, false>
+where
+    Unit: FloatConversion<Float = f32> + Copy,
+{
+    /// Returns a smooth curve passing through each of `points`.
+    ///
+    /// The curve is a centripetal Catmull-Rom spline, which avoids the loops
+    /// and cusps that can form when points are unevenly spaced. Repeated
+    /// points are ignored. When `closed` is true, the curve continues from the
+    /// last point back to the first.
+    ///
+    /// The spline is converted into cubic Bézier curves, which are flattened
+    /// during tessellation using the fill or stroke tolerance.
+    #[must_use]
+    pub fn catmull_rom(points: &[Point<Unit>], closed: bool) -> Self {
+        Self::from_segments(
+            &spline::catmull_rom(&Self::float_points(points), closed),
+            closed,
+        )
+    }
+
+    /// Returns a smooth curve shaped by the control points `points`.
+    ///
+    /// The curve is a uniform cubic B-spline. Unlike
+    /// [`catmull_rom()`](Self::catmull_rom), the curve only passes near its
+    /// control points, but it has continuous curvature. Open curves start at
+    /// the first point and end at the last point. When `closed` is true, the
+    /// curve forms a loop around all of the points.
+    ///
+    /// The spline is converted into cubic Bézier curves, which are flattened
+    /// during tessellation using the fill or stroke tolerance.
+    #[must_use]
+    pub fn b_spline(points: &[Point<Unit>], closed: bool) -> Self {
+        Self::from_segments(
+            &spline::b_spline(&Self::float_points(points), closed),
+            closed,
+        )
+    }
+
+    fn float_points(points: &[Point<Unit>]) -> Vec<Point<f32>> {
+        points
+            .iter()
+            .map(|point| point.map(Unit::into_float))
+            .collect()
+    }
+
+    fn from_segments(segments: &[spline::Segment], close: bool) -> Self {
+        let Some(first) = segments.first() else {
+            return Self {
+                events: SmallVec::new(),
+            };
+        };
+        let point = |point: Point<f32>| point.map(Unit::from_float);
+        [PathEvent::Begin {
+            at: Endpoint::from(point(first[0])),
+            texture: Point::ZERO,
+        }]
+        .into_iter()
+        .chain(segments.iter().map(|segment| PathEvent::Cubic {
+            ctrl1: point(segment[1]),
+            ctrl2: point(segment[2]),
+            to: Endpoint::from(point(segment[3])),
+            texture: Point::ZERO,
+        }))
+        .chain([PathEvent::End { close }])
+        .collect()
     }
 }
 
+/// An operation that combines the regions enclosed by two paths.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PathOperation {
+    /// The region enclosed by either path.
+    Union,
+    /// The region enclosed by both paths.
+    Intersection,
+    /// The region enclosed by the first path but not the second.
+    Difference,
+    /// The region enclosed by exactly one of the paths.
+    Xor,
+}
+
+impl PathOperation {
+    const fn includes(self, in_first: bool, in_second: bool) -> bool {
+        match self {
+            Self::Union => in_first || in_second,
+            Self::Intersection => in_first && in_second,
+            Self::Difference => in_first && !in_second,
+            Self::Xor => in_first != in_second,
+        }
+    }
+}
+
+/// The direction an arc sweeps in.
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Winding {
+    /// The arc sweeps clockwise on screen.
+    #[default]
+    Clockwise,
+    /// The arc sweeps counter-clockwise on screen.
+    CounterClockwise,
+}
+
+#[test]
+fn pie_path() {
+    let pie = Path::<Px, false>::pie(
+        Point::ZERO,
+        Size::squared(Px::new(10)),
+        Angle::ZERO,
+        Angle::degrees(90),
+        Winding::Clockwise,
+    );
+    assert!(matches!(pie.events[0], PathEvent::Begin { at, .. } if at.location == Point::ZERO));
+    assert!(matches!(
+        pie.events[1],
+        PathEvent::Line { to, .. } if to.location == Point::new(Px::new(10), Px::ZERO)
+    ));
+    assert!(matches!(
+        pie.events.last(),
+        Some(PathEvent::End { close: true })
+    ));
+
+    let counter = Path::<Px, false>::directed_arc(
+        Point::ZERO,
+        Size::squared(Px::new(10)),
+        Angle::ZERO,
+        Angle::degrees(90),
+        Winding::CounterClockwise,
+    );
+    assert!(matches!(
+        counter.events[counter.events.len() - 2],
+        PathEvent::Cubic { to, .. } if to.location == Point::new(Px::ZERO, Px::new(-10))
+    ));
+}
+
 #[test]
 fn path_size() {
     assert_eq!(std::mem::size_of::<PathEvent<i32>>(), 40);
@@ -787,6 +1510,82 @@ impl<Unit, const TEXTURED: bool> FromIterator<PathEvent<Unit>> for Path<Unit, TE
     }
 }
 
+impl<Unit, const TEXTURED: bool> Path<Unit, TEXTURED>
+where
+    Unit: FloatConversion<Float = f32> + Copy,
+{
+    /// Returns true if this path can be interpolated with `other` using
+    /// [`lerp()`](Self::lerp).
+    ///
+    /// Paths are compatible when they are made of the same sequence of
+    /// events, such as two paths that each contain a line followed by a
+    /// cubic curve.
+    #[must_use]
+    pub fn is_compatible_with(&self, other: &Self) -> bool {
+        self.events.len() == other.events.len()
+            && self.events.iter().zip(&other.events).all(|(a, b)| {
+                std::mem::discriminant(a) == std::mem::discriminant(b)
+                    && !matches!(
+                        (a, b),
+                        (PathEvent::End { close: a }, PathEvent::End { close: b }) if a != b
+                    )
+            })
+    }
+
+    /// Returns the path between this path and `other` at `fraction`, or `None`
+    /// if the paths are not [compatible](Self::is_compatible_with).
+    ///
+    /// A `fraction` of 0.0 returns this path and 1.0 returns `other`. Every
+    /// location, control point, and texture coordinate is interpolated
+    /// linearly. Values outside of `0.0..=1.0` extrapolate locations, which
+    /// allows easing functions that overshoot. Colors are interpolated using
+    /// [`Color::lerp()`].
+    ///
+    /// Paths with different structures can be made compatible using
+    /// [`resampled()`](Self::resampled).
+    #[must_use]
+    pub fn lerp(&self, other: &Self, fraction: f32) -> Option<Self> {
+        if self.events.len() != other.events.len() {
+            return None;
+        }
+        self.events
+            .iter()
+            .zip(&other.events)
+            .map(|(a, b)| morph::lerp_event(a, b, fraction))
+            .collect::<Option<_>>()
+            .map(|events| Self { events })
+    }
+
+    /// Returns a path with each subpath of this path replaced by `points`
+    /// locations connected by straight lines.
+    ///
+    /// The locations are evenly spaced along the length of each subpath, and
+    /// the first location is always the subpath's starting location. Colors
+    /// and texture coordinates are interpolated between the original
+    /// endpoints. Curves are flattened into line segments that deviate from
+    /// the original curves by at most a tenth of a pixel.
+    ///
+    /// Two paths with the same number of subpaths resampled using the same
+    /// number of points are always [compatible](Self::is_compatible_with).
+    /// This allows morphing between unrelated shapes, such as a star and a
+    /// circle, using [`lerp()`](Self::lerp).
+    #[must_use]
+    pub fn resampled(&self, points: usize) -> Self
+    where
+        Unit: ScreenScale<Px = Px>,
+    {
+        Self {
+            events: morph::resample(
+                &self.events,
+                points,
+                pixel_tolerance::<Unit>(FillOptions::DEFAULT_TOLERANCE),
+            )
+            .into_iter()
+            .collect(),
+        }
+    }
+}
+
 impl<Unit, const TEXTURED: bool> Path<Unit, TEXTURED>
 where
     Unit: FloatConversion<Float = f32> + Copy + PixelScaling,
@@ -862,8 +1661,62 @@ where
         builder.build()
     }
 
+    /// Colors each endpoint of this path based on how far along the path it
+    /// is.
+    ///
+    /// `color_at` is invoked for each endpoint with its distance along the
+    /// path divided by the path's total length, ranging from `0.0` at the
+    /// start to `1.0` at the end. Curves are measured using the straight line
+    /// between their endpoints. Colors are interpolated between endpoints when
+    /// the path is filled or stroked.
+    pub fn color_along(&mut self, mut color_at: impl FnMut(f32) -> Color) {
+        let mut distances = Vec::with_capacity(self.events.len());
+        let mut total = 0.;
+        let mut previous = None::<Point<f32>>;
+        for event in &self.events {
+            let location = match event {
+                PathEvent::Begin { at: endpoint, .. }
+                | PathEvent::Line { to: endpoint, .. }
+                | PathEvent::Quadratic { to: endpoint, .. }
+                | PathEvent::Cubic { to: endpoint, .. } => endpoint.location.map(Unit::into_float),
+                PathEvent::End { .. } => continue,
+            };
+            if let Some(previous) = previous {
+                total += (location.x - previous.x).hypot(location.y - previous.y);
+            }
+            previous = Some(location);
+            distances.push(total);
+        }
+
+        let mut distances = distances.into_iter();
+        for event in &mut self.events {
+            if let PathEvent::Begin { at: endpoint, .. }
+            | PathEvent::Line { to: endpoint, .. }
+            | PathEvent::Quadratic { to: endpoint, .. }
+            | PathEvent::Cubic { to: endpoint, .. } = event
+            {
+                let distance = distances.next().assert("one distance per endpoint");
+                endpoint.color = color_at(if total > 0. { distance / total } else { 0. });
+            }
+        }
+    }
+
+    /// Colors this path with a gradient from `start` to `end` along its
+    /// length.
+    ///
+    /// See [`color_along()`](Self::color_along) for more information.
+    #[must_use]
+    pub fn gradient(mut self, start: Color, end: Color) -> Self {
+        self.color_along(|fraction| start.lerp(end, fraction));
+        self
+    }
+
     /// Fills this path with `color`.
     ///
+    /// Overlapping regions of the path are filled using the
+    /// [even-odd](FillRule::EvenOdd) fill rule. To use a different fill rule,
+    /// use [`fill_with_rule()`](Self::fill_with_rule).
+    ///
     /// If this is a textured image or the path endpoints were constructed with
     /// colors, the sampled texture colors will be multiplied with this color.
     /// To render the image unchanged, use [`Color::WHITE`].
@@ -872,6 +1725,26 @@ where
         self.fill_opt(color, &FillOptions::DEFAULT)
     }
 
+    /// Fills this path with `color`, using `rule` to determine which regions
+    /// are inside of the path.
+    ///
+    /// - [`FillRule::EvenOdd`]: A region is inside of the path when it is
+    ///   enclosed by an odd number of subpaths. Nested subpaths create holes,
+    ///   regardless of their direction.
+    /// - [`FillRule::NonZero`]: A region is inside of the path when the
+    ///   subpaths enclosing it do not wind around it an equal number of times
+    ///   in each direction. Nested subpaths only create holes when they wind
+    ///   in the opposite direction of the subpath enclosing them, which is how
+    ///   font outlines and most SVG data are authored.
+    ///
+    /// If this is a textured image or the path endpoints were constructed with
+    /// colors, the sampled texture colors will be multiplied with this color.
+    /// To render the image unchanged, use [`Color::WHITE`].
+    #[must_use]
+    pub fn fill_with_rule(&self, color: Color, rule: FillRule) -> Shape<Unit, TEXTURED> {
+        self.fill_opt(color, &FillOptions::DEFAULT.with_fill_rule(rule))
+    }
+
     /// Fills this path with `color` using the provided options.
     ///
     /// If this is a textured image or the path endpoints were constructed with
@@ -894,6 +1767,39 @@ where
         shape_builder.shape
     }
 
+    /// Fills this path with `gradient`, using `kind` to determine the gradient
+    /// position of each location within the path.
+    ///
+    /// The gradient is sampled at each vertex of the tessellated shape, and
+    /// colors are interpolated between vertices when the shape is rendered.
+    /// Gradients with stops closer together than the shape's vertices will
+    /// not show every stop.
+    ///
+    /// If this is a textured image or the path endpoints were constructed with
+    /// colors, the sampled texture colors will be multiplied with the
+    /// gradient.
+    #[must_use]
+    pub fn fill_gradient(
+        &self,
+        gradient: &Gradient,
+        kind: GradientKind<Unit>,
+    ) -> Shape<Unit, TEXTURED> {
+        let lyon_path = self.as_lyon();
+        let mut shape_builder =
+            ShapeBuilder::new(Color::WHITE).with_gradient(gradient.clone(), kind.to_float());
+        let mut tesselator = FillTessellator::new();
+        tesselator
+            .tessellate_with_ids(
+                lyon_path.id_iter(),
+                &lyon_path,
+                Some(&lyon_path),
+                &FillOptions::DEFAULT,
+                &mut shape_builder,
+            )
+            .assert("should not fail to tesselat4e a rect");
+        shape_builder.shape
+    }
+
     /// Fills this path with solid white.
     ///
     /// If this is a textured image or the path endpoints were constructed with
@@ -905,9 +1811,9 @@ where
 
     /// Strokes this path with `color` and `options`.
     ///
-    /// If this is a textured image, the sampled texture colors will be
-    /// multiplied with this color. To render the image unchanged, use
-    /// [`Color::WHITE`].
+    /// If this is a textured image or the path endpoints were constructed with
+    /// colors, the sampled texture colors will be multiplied with this color.
+    /// To render the image unchanged, use [`Color::WHITE`].
     #[must_use]
     pub fn stroke(&self, options: impl Into<StrokeOptions<Unit>>) -> Shape<Unit, TEXTURED> {
         let options = options.into();
@@ -1301,6 +2207,25 @@ where
         self
     }
 
+    /// Sets the color of the current location and returns self.
+    ///
+    /// Colors are interpolated between endpoints when the path is filled or
+    /// stroked, and are multiplied with the fill or stroke color.
+    #[must_use]
+    pub fn color(mut self, color: Color) -> Self {
+        self.current_location.color = color;
+        if let Some(
+            PathEvent::Begin { at: endpoint, .. }
+            | PathEvent::Line { to: endpoint, .. }
+            | PathEvent::Quadratic { to: endpoint, .. }
+            | PathEvent::Cubic { to: endpoint, .. },
+        ) = self.path.events.last_mut()
+        {
+            endpoint.color = color;
+        }
+        self
+    }
+
     /// Returns the built path.
     #[must_use]
     pub fn build(mut self) -> Path<Unit, TEXTURED> {
@@ -1319,6 +2244,7 @@ where
 
 /// Options for stroking lines on a path.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StrokeOptions<Unit> {
     /// The color to apply to the stroke.
     pub color: Color,
@@ -1326,7 +2252,15 @@ pub struct StrokeOptions<Unit> {
     /// The width of the line.
     pub line_width: Unit,
 
-    /// See the SVG specification.
+    /// How corners between connected segments are drawn.
+    ///
+    /// - [`LineJoin::Miter`] extends the outer edges until they meet, falling
+    ///   back to [`LineJoin::Bevel`] when the corner exceeds
+    ///   [`miter_limit`](Self::miter_limit).
+    /// - [`LineJoin::MiterClip`] extends the outer edges, clipping them at
+    ///   the miter limit.
+    /// - [`LineJoin::Round`] rounds the outer edge of the corner.
+    /// - [`LineJoin::Bevel`] connects the outer edges with a straight line.
     ///
     /// Default value: `LineJoin::Miter`.
     pub line_join: LineJoin,
@@ -1341,16 +2275,16 @@ pub struct StrokeOptions<Unit> {
     /// Default value: `LineCap::Butt`.
     pub end_cap: LineCap,
 
-    /// See the SVG specification.
+    /// The maximum ratio of a miter's length to the line width before a
+    /// mitered corner is beveled or clipped.
     ///
-    /// Must be greater than or equal to 1.0.
-    /// Default value: `StrokeOptions::DEFAULT_MITER_LIMIT`.
+    /// Values less than [`StrokeOptions::MINIMUM_MITER_LIMIT`] are treated as
+    /// the minimum. Default value: [`StrokeOptions::DEFAULT_MITER_LIMIT`].
     pub miter_limit: f32,
 
     /// Maximum allowed distance to the path when building an approximation.
     ///
-    /// See [Flattening and tolerance](index.html#flattening-and-tolerance).
-    /// Default value: `StrokeOptions::DEFAULT_TOLERANCE`.
+    /// Default value: [`StrokeOptions::DEFAULT_TOLERANCE`].
     pub tolerance: f32,
 }
 
@@ -1365,8 +2299,8 @@ where
             line_join: lyon_tessellation::StrokeOptions::DEFAULT_LINE_JOIN,
             start_cap: lyon_tessellation::StrokeOptions::DEFAULT_LINE_CAP,
             end_cap: lyon_tessellation::StrokeOptions::DEFAULT_LINE_CAP,
-            miter_limit: lyon_tessellation::StrokeOptions::DEFAULT_MITER_LIMIT,
-            tolerance: lyon_tessellation::StrokeOptions::DEFAULT_TOLERANCE,
+            miter_limit: Self::DEFAULT_MITER_LIMIT,
+            tolerance: Self::DEFAULT_TOLERANCE,
         }
     }
 }
@@ -1384,6 +2318,13 @@ where
 }
 
 impl<Unit> StrokeOptions<Unit> {
+    /// The default miter limit.
+    pub const DEFAULT_MITER_LIMIT: f32 = lyon_tessellation::StrokeOptions::DEFAULT_MITER_LIMIT;
+    /// The default tolerance.
+    pub const DEFAULT_TOLERANCE: f32 = lyon_tessellation::StrokeOptions::DEFAULT_TOLERANCE;
+    /// The smallest supported miter limit.
+    pub const MINIMUM_MITER_LIMIT: f32 = lyon_tessellation::StrokeOptions::MINIMUM_MITER_LIMIT;
+
     /// Sets the color of this stroke and returns self.
     #[must_use]
     pub fn colored(mut self, color: Color) -> Self {
@@ -1412,10 +2353,30 @@ impl<Unit> StrokeOptions<Unit> {
         self
     }
 
+    /// Sets the line cap style for both the start and end of line segments
+    /// and returns self.
+    #[must_use]
+    pub fn caps(mut self, cap: LineCap) -> Self {
+        self.start_cap = cap;
+        self.end_cap = cap;
+        self
+    }
+
     /// Sets the miter limit and returns self.
+    ///
+    /// Limits less than [`Self::MINIMUM_MITER_LIMIT`] are clamped to the
+    /// minimum.
     #[must_use]
     pub fn miter_limit(mut self, limit: f32) -> Self {
-        self.miter_limit = limit;
+        self.miter_limit = limit.max(Self::MINIMUM_MITER_LIMIT);
+        self
+    }
+
+    /// Sets the maximum distance the tessellated stroke may deviate from the
+    /// path's curves and returns self.
+    #[must_use]
+    pub fn tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
         self
     }
 }
@@ -1603,14 +2564,288 @@ where
             .with_line_join(line_join)
             .with_start_cap(start_cap)
             .with_end_cap(end_cap)
-            .with_miter_limit(miter_limit)
+            .with_miter_limit(miter_limit.max(Self::MINIMUM_MITER_LIMIT))
             .with_tolerance(tolerance)
     }
 }
 
+#[test]
+fn path_colors() {
+    let path = PathBuilder::new(Point::new(Px::ZERO, Px::ZERO))
+        .color(Color::RED)
+        .line_to(Point::new(Px::new(30), Px::ZERO))
+        .line_to(Point::new(Px::new(30), Px::new(10)))
+        .color(Color::BLUE)
+        .build();
+    let colors = |path: &Path<Px, false>| {
+        path.events
+            .iter()
+            .filter_map(|event| match event {
+                PathEvent::Begin { at: endpoint, .. } | PathEvent::Line { to: endpoint, .. } => {
+                    Some(endpoint.color)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(colors(&path), [Color::RED, Color::WHITE, Color::BLUE]);
+
+    let path = path.gradient(Color::BLACK, Color::WHITE);
+    assert_eq!(
+        colors(&path),
+        [
+            Color::BLACK,
+            Color::BLACK.lerp(Color::WHITE, 0.75),
+            Color::WHITE
+        ]
+    );
+}
+
+#[test]
+fn gradient_fill() {
+    let point = |x: i32, y: i32| Point::new(Px::new(x), Px::new(y));
+    let path = PathBuilder::new(point(0, 0))
+        .line_to(point(10, 0))
+        .line_to(point(10, 10))
+        .line_to(point(0, 10))
+        .close();
+    let shape = path.fill_gradient(
+        &Gradient::new(Color::BLACK, Color::WHITE),
+        GradientKind::Linear {
+            start: point(0, 0),
+            end: point(10, 0),
+        },
+    );
+    assert_eq!(shape.vertices.len(), 4);
+    for vertex in &shape.vertices {
+        let expected = if vertex.location.x == Px::ZERO {
+            Color::BLACK
+        } else {
+            Color::WHITE
+        };
+        assert_eq!(vertex.color, expected);
+    }
+}
+
+#[test]
+fn pattern_fill() {
+    let point = |x: i32, y: i32| Point::new(Px::new(x), Px::new(y));
+    let texture = crate::LazyTexture::from_data(
+        Size::squared(UPx::new(8)),
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        wgpu::TextureUsages::TEXTURE_BINDING,
+        wgpu::FilterMode::Nearest,
+        vec![0; 8 * 8 * 4],
+    );
+    let path = PathBuilder::new(point(-4, 0))
+        .line_to(point(12, 0))
+        .line_to(point(12, 8))
+        .line_to(point(-4, 8))
+        .close();
+    let texture_at = |shape: &Shape<Px, true>, location: Point<Px>| {
+        shape
+            .vertices
+            .iter()
+            .find(|vertex| vertex.location == location)
+            .map(|vertex| vertex.texture)
+    };
+
+    let repeated = Shape::textured_fill(&path, &texture, FillPattern::default(), Color::WHITE);
+    assert_eq!(
+        repeated.pattern.as_deref().copied(),
+        Some(TexturePattern {
+            region: Rect::new(Point::ZERO, Size::squared(UPx::new(8))),
+            wrap: PatternWrap::Repeat,
+        })
+    );
+    // Negative coordinates are shifted by a whole number of repetitions.
+    assert_eq!(
+        texture_at(&repeated, point(-4, 0)),
+        Some(Point::new(UPx::new(4), UPx::ZERO))
+    );
+    assert_eq!(
+        texture_at(&repeated, point(12, 8)),
+        Some(Point::new(UPx::new(20), UPx::new(8)))
+    );
+
+    let mirrored = Shape::textured_fill(
+        &path,
+        &texture,
+        FillPattern::default().mirrored().scaled(2.),
+        Color::WHITE,
+    );
+    assert_eq!(
+        texture_at(&mirrored, point(-4, 0)),
+        Some(Point::new(UPx::new(14), UPx::ZERO))
+    );
+}
+
+#[test]
+fn fill_rules() {
+    let point = |x: i32, y: i32| Point::new(Px::new(x), Px::new(y));
+    let square = |origin: i32, size: i32| {
+        [
+            PathEvent::Begin {
+                at: point(origin, origin).into(),
+                texture: Point::ZERO,
+            },
+            PathEvent::Line {
+                to: point(origin + size, origin).into(),
+                texture: Point::ZERO,
+            },
+            PathEvent::Line {
+                to: point(origin + size, origin + size).into(),
+                texture: Point::ZERO,
+            },
+            PathEvent::Line {
+                to: point(origin, origin + size).into(),
+                texture: Point::ZERO,
+            },
+            PathEvent::End { close: true },
+        ]
+    };
+    let area = |shape: &Shape<Px, false>| {
+        shape
+            .indices
+            .chunks_exact(3)
+            .map(|triangle| {
+                let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|index| {
+                    shape.vertices[usize::try_from(index).unwrap()]
+                        .location
+                        .map(Px::into_float)
+                });
+                ((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)).abs() / 2.
+            })
+            .sum::<f32>()
+    };
+    // Two nested squares wound in the same direction.
+    let path = square(0, 10)
+        .into_iter()
+        .chain(square(2, 6))
+        .collect::<Path<Px, false>>();
+    assert_eq!(area(&path.fill(Color::WHITE)), 64.);
+    assert_eq!(
+        area(&path.fill_with_rule(Color::WHITE, FillRule::EvenOdd)),
+        64.
+    );
+    assert_eq!(
+        area(&path.fill_with_rule(Color::WHITE, FillRule::NonZero)),
+        100.
+    );
+}
+
+#[test]
+fn stroke_outline() {
+    let point = |x: i32, y: i32| Point::new(Px::new(x), Px::new(y));
+    let outline = PathBuilder::new(point(0, 0))
+        .line_to(point(20, 0))
+        .build()
+        .outline(StrokeOptions::px_wide(Px::new(4)));
+    let corners = [point(0, -2), point(20, -2), point(20, 2), point(0, 2)];
+    let mut begins = 0;
+    for event in &outline.events {
+        match event {
+            PathEvent::Begin { at, .. } => {
+                begins += 1;
+                assert!(corners.contains(&at.location));
+            }
+            PathEvent::Line { to, .. } => assert!(corners.contains(&to.location)),
+            PathEvent::End { close } => assert!(*close),
+            _ => unreachable!("outlines are made of lines"),
+        }
+    }
+    assert_eq!(begins, 1);
+}
+
+#[test]
+fn path_morphing() {
+    let point = |x: i32, y: i32| Point::new(Px::new(x), Px::new(y));
+    let locations = |path: &Path<Px, false>| {
+        path.events
+            .iter()
+            .filter_map(|event| match event {
+                PathEvent::Begin { at: endpoint, .. } | PathEvent::Line { to: endpoint, .. } => {
+                    Some(endpoint.location)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+    let square = PathBuilder::new(point(0, 0))
+        .line_to(point(40, 0))
+        .line_to(point(40, 40))
+        .line_to(point(0, 40))
+        .close();
+    let arch = PathBuilder::new(point(0, 0))
+        .quadratic_curve_to(point(20, 40), point(40, 0))
+        .close();
+    assert!(!square.is_compatible_with(&arch));
+    assert!(square.lerp(&arch, 0.5).is_none());
+
+    let square = square.resampled(8);
+    assert_eq!(
+        locations(&square),
+        [
+            point(0, 0),
+            point(20, 0),
+            point(40, 0),
+            point(40, 20),
+            point(40, 40),
+            point(20, 40),
+            point(0, 40),
+            point(0, 20),
+        ]
+    );
+    let arch = arch.resampled(8);
+    assert!(square.is_compatible_with(&arch));
+    let halfway = square.lerp(&arch, 0.5).expect("compatible");
+    assert_eq!(locations(&halfway)[0], point(0, 0));
+    assert_eq!(
+        locations(&square.lerp(&arch, 0.).expect("compatible")),
+        locations(&square)
+    );
+}
+
+#[test]
+fn stroke_joins_and_caps() {
+    fn extents(shape: &Shape<Px, false>) -> (Px, Px) {
+        shape
+            .vertices
+            .iter()
+            .fold((Px::MAX, Px::MIN), |(min, max), vertex| {
+                (min.min(vertex.location.x), max.max(vertex.location.x))
+            })
+    }
+
+    // A sharp corner pointing right at x = 100.
+    let path = PathBuilder::new(Point::new(Px::ZERO, Px::ZERO))
+        .line_to(Point::new(Px::new(100), Px::new(10)))
+        .line_to(Point::new(Px::ZERO, Px::new(20)))
+        .build();
+    let options = StrokeOptions::px_wide(10);
+
+    let (_, mitered) = extents(&path.stroke(options.miter_limit(100.)));
+    let (_, limited) = extents(&path.stroke(options));
+    let (_, beveled) = extents(&path.stroke(options.line_join(LineJoin::Bevel)));
+    assert!(mitered > Px::new(140));
+    assert!(limited <= Px::new(110));
+    assert!(beveled <= Px::new(110));
+
+    let (butt, _) = extents(&path.stroke(options));
+    let (square, _) = extents(&path.stroke(options.caps(LineCap::Square)));
+    assert!(square < butt);
+
+    // Limits below the minimum must not cause tessellation to panic.
+    let _ = path.stroke(StrokeOptions {
+        miter_limit: 0.,
+        ..options
+    });
+}
+
 /// A description of the size to use for each corner radius measurement when
 /// rendering a rounded rectangle.
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CornerRadii<Unit> {
     /// The radius of the top left rounded corner.
     pub top_left: Unit,
@@ -1623,6 +2858,22 @@ pub struct CornerRadii<Unit> {
 }
 
 impl<Unit> CornerRadii<Unit> {
+    /// Returns a set of radii with each corner specified independently.
+    #[must_use]
+    pub const fn new(
+        top_left: Unit,
+        top_right: Unit,
+        bottom_right: Unit,
+        bottom_left: Unit,
+    ) -> Self {
+        Self {
+            top_left,
+            top_right,
+            bottom_right,
+            bottom_left,
+        }
+    }
+
     /// Passes each radius definition to `map` and returns a new set of radii
     /// with the results.
     #[must_use]
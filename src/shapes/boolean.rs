@@ -0,0 +1,304 @@
+use std::collections::{HashMap, HashSet};
+
+use figures::Point;
+
+use super::PathOperation;
+
+/// A closed polygon. The last point connects back to the first.
+pub(super) type Contour = Vec<Point<f32>>;
+
+/// The grid that computed points are snapped to, ensuring that segments
+/// meeting at an intersection share identical endpoints.
+const SNAP: f32 = 1. / 1024.;
+
+type Segment = (Point<f32>, Point<f32>);
+type PointKey = (u32, u32);
+
+/// Combines the regions enclosed by `a` and `b`, each interpreted using the
+/// even-odd fill rule, returning the contours enclosing the result.
+///
+/// Every segment of both inputs is split where it crosses another segment.
+/// Each piece is then kept only if the result region lies on exactly one side
+/// of it, and the kept pieces are linked back into closed contours.
+pub(super) fn combine(a: &[Contour], b: &[Contour], operation: PathOperation) -> Vec<Contour> {
+    let a = segments(a);
+    let b = segments(b);
+    let all = a.iter().chain(&b).copied().collect::<Vec<_>>();
+
+    let mut seen = HashSet::new();
+    let mut kept = Vec::new();
+    for (from, to) in split_at_intersections(&all) {
+        let (start, end) = (key(from), key(to));
+        if !seen.insert((start.min(end), start.max(end))) {
+            // Edges shared by both paths are only classified once.
+            continue;
+        }
+
+        let dx = to.x - from.x;
+        let dy = to.y - from.y;
+        let length = dx.hypot(dy);
+        let offset = (length / 4.).min(SNAP * 4.) / length;
+        let middle = Point::new((from.x + to.x) / 2., (from.y + to.y) / 2.);
+        let left = Point::new(middle.x - dy * offset, middle.y + dx * offset);
+        let right = Point::new(middle.x + dy * offset, middle.y - dx * offset);
+        let inside = |point| operation.includes(contains(&a, point), contains(&b, point));
+        match (inside(left), inside(right)) {
+            (true, false) => kept.push((from, to)),
+            (false, true) => kept.push((to, from)),
+            _ => {}
+        }
+    }
+
+    link(&kept)
+}
+
+/// Returns the contours tracing the boundary of the area covered by
+/// `triangles`.
+///
+/// Each triangle is wound in the same direction, allowing edges shared by two
+/// triangles to cancel each other out. Where triangles overlap, the remaining
+/// contours overlap as well, so the area is only enclosed when the contours
+/// are filled using the non-zero fill rule.
+pub(super) fn outline(triangles: impl IntoIterator<Item = [Point<f32>; 3]>) -> Vec<Contour> {
+    let mut edges = HashMap::<(PointKey, PointKey), (Segment, usize)>::new();
+    for triangle in triangles {
+        let [a, mut b, mut c] = triangle.map(snap);
+        let area = cross(
+            Point::new(b.x - a.x, b.y - a.y),
+            Point::new(c.x - a.x, c.y - a.y),
+        );
+        if area == 0. {
+            continue;
+        } else if area < 0. {
+            std::mem::swap(&mut b, &mut c);
+        }
+
+        for (from, to) in [(a, b), (b, c), (c, a)] {
+            let (start, end) = (key(from), key(to));
+            if start == end {
+                continue;
+            }
+            match edges.get_mut(&(end, start)) {
+                Some((_, count)) if *count > 0 => *count -= 1,
+                _ => edges.entry((start, end)).or_insert(((from, to), 0)).1 += 1,
+            }
+        }
+    }
+
+    let boundary = edges
+        .into_values()
+        .flat_map(|(segment, count)| std::iter::repeat(segment).take(count))
+        .collect::<Vec<_>>();
+    link(&boundary)
+}
+
+fn segments(contours: &[Contour]) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    for contour in contours {
+        for (index, &from) in contour.iter().enumerate() {
+            let to = contour[(index + 1) % contour.len()];
+            if key(snap(from)) != key(snap(to)) {
+                segments.push((from, to));
+            }
+        }
+    }
+    segments
+}
+
+/// Returns true if `point` is inside the region bounded by `segments` using
+/// the even-odd fill rule.
+fn contains(segments: &[Segment], point: Point<f32>) -> bool {
+    let mut inside = false;
+    for &(from, to) in segments {
+        if (from.y > point.y) != (to.y > point.y)
+            && point.x < (to.x - from.x) * (point.y - from.y) / (to.y - from.y) + from.x
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+fn split_at_intersections(segments: &[Segment]) -> Vec<Segment> {
+    const EPSILON: f32 = 1e-5;
+
+    let mut splits = segments
+        .iter()
+        .map(|&(from, to)| vec![(0., from), (1., to)])
+        .collect::<Vec<_>>();
+    for (i, &(a_from, a_to)) in segments.iter().enumerate() {
+        let a_delta = Point::new(a_to.x - a_from.x, a_to.y - a_from.y);
+        for (j, &(b_from, b_to)) in segments.iter().enumerate().skip(i + 1) {
+            let b_delta = Point::new(b_to.x - b_from.x, b_to.y - b_from.y);
+            let between = Point::new(b_from.x - a_from.x, b_from.y - a_from.y);
+            let denominator = cross(a_delta, b_delta);
+            if denominator.abs() > EPSILON * length(a_delta) * length(b_delta) {
+                let t = cross(between, b_delta) / denominator;
+                let u = cross(between, a_delta) / denominator;
+                if !(-EPSILON..=1. + EPSILON).contains(&t)
+                    || !(-EPSILON..=1. + EPSILON).contains(&u)
+                {
+                    continue;
+                }
+                // Prefer existing endpoints to keep T-junctions exact.
+                let point = if u < EPSILON {
+                    b_from
+                } else if u > 1. - EPSILON {
+                    b_to
+                } else if t < EPSILON {
+                    a_from
+                } else if t > 1. - EPSILON {
+                    a_to
+                } else {
+                    Point::new(a_from.x + a_delta.x * t, a_from.y + a_delta.y * t)
+                };
+                splits[i].push((t, point));
+                splits[j].push((u, point));
+            } else if cross(between, a_delta).abs() <= EPSILON * length(a_delta) * length(between) {
+                // The segments are collinear. Split each at the other's
+                // endpoints that lie within it.
+                for point in [b_from, b_to] {
+                    let t = projection(a_from, a_delta, point);
+                    if t > 0. && t < 1. {
+                        splits[i].push((t, point));
+                    }
+                }
+                for point in [a_from, a_to] {
+                    let u = projection(b_from, b_delta, point);
+                    if u > 0. && u < 1. {
+                        splits[j].push((u, point));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut split = Vec::new();
+    for mut points in splits {
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        for pair in points.windows(2) {
+            let (from, to) = (snap(pair[0].1), snap(pair[1].1));
+            if key(from) != key(to) {
+                split.push((from, to));
+            }
+        }
+    }
+    split
+}
+
+/// Links directed segments that share endpoints into closed contours.
+fn link(segments: &[Segment]) -> Vec<Contour> {
+    let mut outgoing = HashMap::<PointKey, Vec<usize>>::new();
+    for (index, (from, _)) in segments.iter().enumerate() {
+        outgoing.entry(key(*from)).or_default().push(index);
+    }
+
+    let mut visited = vec![false; segments.len()];
+    let mut contours = Vec::new();
+    while let Some(start) = visited.iter().position(|visited| !visited) {
+        let mut contour = Vec::new();
+        let mut current = start;
+        loop {
+            visited[current] = true;
+            let (from, to) = segments[current];
+            contour.push(from);
+            if key(to) == key(segments[start].0) {
+                break;
+            }
+            let Some(next) = outgoing
+                .get(&key(to))
+                .and_then(|candidates| candidates.iter().copied().find(|&i| !visited[i]))
+            else {
+                break;
+            };
+            current = next;
+        }
+        if contour.len() >= 3 {
+            contours.push(contour);
+        }
+    }
+    contours
+}
+
+fn cross(a: Point<f32>, b: Point<f32>) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+fn length(a: Point<f32>) -> f32 {
+    a.x.hypot(a.y)
+}
+
+fn projection(origin: Point<f32>, delta: Point<f32>, point: Point<f32>) -> f32 {
+    ((point.x - origin.x) * delta.x + (point.y - origin.y) * delta.y)
+        / (delta.x * delta.x + delta.y * delta.y)
+}
+
+fn snap(point: Point<f32>) -> Point<f32> {
+    Point::new(
+        (point.x / SNAP).round() * SNAP,
+        (point.y / SNAP).round() * SNAP,
+    )
+}
+
+fn key(point: Point<f32>) -> PointKey {
+    // Adding zero normalizes negative zero.
+    ((point.x + 0.).to_bits(), (point.y + 0.).to_bits())
+}
+
+#[test]
+fn triangle_outlines() {
+    // Two triangles forming a square share their diagonal.
+    let square = outline([
+        [Point::new(0., 0.), Point::new(1., 0.), Point::new(1., 1.)],
+        [Point::new(0., 0.), Point::new(0., 1.), Point::new(1., 1.)],
+    ]);
+    assert_eq!(square.len(), 1);
+    assert_eq!(square[0].len(), 4);
+}
+
+#[test]
+fn boolean_operations() {
+    fn square(x: f32, y: f32, size: f32) -> Contour {
+        vec![
+            Point::new(x, y),
+            Point::new(x + size, y),
+            Point::new(x + size, y + size),
+            Point::new(x, y + size),
+        ]
+    }
+    fn area(contours: &[Contour]) -> f32 {
+        contours
+            .iter()
+            .flat_map(|contour| {
+                contour
+                    .iter()
+                    .zip(contour.iter().cycle().skip(1))
+                    .map(|(a, b)| cross(*a, *b) / 2.)
+            })
+            .sum::<f32>()
+            .abs()
+    }
+
+    let a = [square(0., 0., 2.)];
+    let b = [square(1., 1., 2.)];
+    let union = combine(&a, &b, PathOperation::Union);
+    assert_eq!(union.len(), 1);
+    assert_eq!(union[0].len(), 8);
+    assert_eq!(area(&union), 7.);
+    assert_eq!(area(&combine(&a, &b, PathOperation::Intersection)), 1.);
+    assert_eq!(area(&combine(&a, &b, PathOperation::Difference)), 3.);
+    assert_eq!(area(&combine(&a, &b, PathOperation::Xor)), 6.);
+
+    // Cutting a hole leaves the outer contour and the hole.
+    let overlay = [square(0., 0., 10.)];
+    let cutout = [square(2., 2., 2.)];
+    let punched = combine(&overlay, &cutout, PathOperation::Difference);
+    assert_eq!(punched.len(), 2);
+    assert_eq!(area(&punched), 96.);
+
+    // Shared edges are not duplicated.
+    let adjacent = [square(2., 0., 2.)];
+    let joined = combine(&a, &adjacent, PathOperation::Union);
+    assert_eq!(joined.len(), 1);
+    assert_eq!(area(&joined), 8.);
+}
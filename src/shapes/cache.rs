@@ -0,0 +1,274 @@
+use figures::units::Px;
+use figures::{FloatConversion, Fraction, PixelScaling, ScreenScale};
+use intentional::Cast;
+use lyon_tessellation::{FillOptions, FillRule};
+
+use super::{scaled_pixel_tolerance, Path, Shape, StrokeOptions};
+use crate::{Assert, Color};
+
+/// A [`Path`] that keeps the shapes it has been tessellated into.
+///
+/// Tessellating a path with curves can be expensive, and most paths are drawn
+/// with the same options every frame. Each shape is cached alongside the
+/// options and the scale it was tessellated for. Requesting the same shape
+/// again returns the cached shape without tessellating the path.
+///
+/// Tolerances are measured in pixels at the requested scale, which allows a
+/// path measured in [`Lp`](figures::units::Lp) to be tessellated more finely
+/// on high-DPI displays. Up to [`CachedPath::CAPACITY`] shapes are kept, with
+/// the least recently used shape being discarded first.
+///
+/// Modifying the path using [`path_mut()`](Self::path_mut) or
+/// [`set_path()`](Self::set_path) discards all cached shapes.
+///
+/// ```rust
+/// use kludgine::figures::units::Lp;
+/// use kludgine::figures::{Point, Size};
+/// use kludgine::shapes::{CachedPath, Path};
+/// use kludgine::Color;
+///
+/// # fn draw(renderer: &mut kludgine::drawing::Renderer<'_, '_>) {
+/// let mut circle = CachedPath::new(Path::ellipse(Point::ZERO, Size::squared(Lp::inches(1))));
+/// // Each frame:
+/// let scale = renderer.scale();
+/// renderer.draw_shape(circle.fill(Color::RED, scale));
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CachedPath<Unit, const TEXTURED: bool> {
+    path: Path<Unit, TEXTURED>,
+    entries: Vec<Entry<Unit, TEXTURED>>,
+    statistics: CacheStatistics,
+}
+
+#[derive(Debug, Clone)]
+struct Entry<Unit, const TEXTURED: bool> {
+    key: Key<Unit>,
+    shape: Shape<Unit, TEXTURED>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Key<Unit> {
+    Fill {
+        color: Color,
+        options: FillOptions,
+        scale: Fraction,
+    },
+    Stroke {
+        options: StrokeOptions<Unit>,
+        scale: Fraction,
+    },
+}
+
+impl<Unit, const TEXTURED: bool> CachedPath<Unit, TEXTURED> {
+    /// The maximum number of shapes kept for a single path.
+    pub const CAPACITY: usize = 4;
+
+    /// Returns a new cache for `path`.
+    #[must_use]
+    pub const fn new(path: Path<Unit, TEXTURED>) -> Self {
+        Self {
+            path,
+            entries: Vec::new(),
+            statistics: CacheStatistics { hits: 0, misses: 0 },
+        }
+    }
+
+    /// Returns the path being tessellated.
+    #[must_use]
+    pub const fn path(&self) -> &Path<Unit, TEXTURED> {
+        &self.path
+    }
+
+    /// Returns an exclusive reference to the path being tessellated.
+    ///
+    /// All cached shapes are discarded.
+    pub fn path_mut(&mut self) -> &mut Path<Unit, TEXTURED> {
+        self.invalidate();
+        &mut self.path
+    }
+
+    /// Replaces the path being tessellated with `path`.
+    ///
+    /// All cached shapes are discarded.
+    pub fn set_path(&mut self, path: Path<Unit, TEXTURED>) {
+        self.invalidate();
+        self.path = path;
+    }
+
+    /// Returns the path, discarding all cached shapes.
+    #[must_use]
+    pub fn into_path(self) -> Path<Unit, TEXTURED> {
+        self.path
+    }
+
+    /// Discards all cached shapes.
+    ///
+    /// This does not reset the [statistics](Self::statistics).
+    pub fn invalidate(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Returns the number of shapes currently cached.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if no shapes are currently cached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns how often shapes have been found in this cache.
+    #[must_use]
+    pub const fn statistics(&self) -> CacheStatistics {
+        self.statistics
+    }
+
+    /// Resets the [statistics](Self::statistics) of this cache.
+    pub fn reset_statistics(&mut self) {
+        self.statistics = CacheStatistics::default();
+    }
+
+    fn cached(
+        &mut self,
+        key: Key<Unit>,
+        tessellate: impl FnOnce(&Path<Unit, TEXTURED>) -> Shape<Unit, TEXTURED>,
+    ) -> &Shape<Unit, TEXTURED>
+    where
+        Unit: PartialEq,
+    {
+        if let Some(index) = self.entries.iter().position(|entry| entry.key == key) {
+            self.statistics.hits += 1;
+            // Keep the most recently used shape at the end.
+            let entry = self.entries.remove(index);
+            self.entries.push(entry);
+        } else {
+            self.statistics.misses += 1;
+            if self.entries.len() >= Self::CAPACITY {
+                self.entries.remove(0);
+            }
+            let shape = tessellate(&self.path);
+            self.entries.push(Entry { key, shape });
+        }
+        &self.entries.last().assert("just pushed").shape
+    }
+}
+
+impl<Unit, const TEXTURED: bool> CachedPath<Unit, TEXTURED>
+where
+    Unit: FloatConversion<Float = f32> + ScreenScale<Px = Px> + PixelScaling + PartialEq + Copy,
+{
+    /// Returns this path filled with `color`, tessellated for rendering at
+    /// `scale`.
+    ///
+    /// See [`Path::fill()`] for more information.
+    pub fn fill(&mut self, color: Color, scale: Fraction) -> &Shape<Unit, TEXTURED> {
+        self.fill_opt(color, &FillOptions::DEFAULT, scale)
+    }
+
+    /// Returns this path filled with `color` using `rule`, tessellated for
+    /// rendering at `scale`.
+    ///
+    /// See [`Path::fill_with_rule()`] for more information.
+    pub fn fill_with_rule(
+        &mut self,
+        color: Color,
+        rule: FillRule,
+        scale: Fraction,
+    ) -> &Shape<Unit, TEXTURED> {
+        self.fill_opt(color, &FillOptions::DEFAULT.with_fill_rule(rule), scale)
+    }
+
+    /// Returns this path filled with `color` using `options`, tessellated for
+    /// rendering at `scale`. [`FillOptions::tolerance`] is measured in
+    /// pixels, regardless of `Unit`.
+    pub fn fill_opt(
+        &mut self,
+        color: Color,
+        options: &FillOptions,
+        scale: Fraction,
+    ) -> &Shape<Unit, TEXTURED> {
+        let mut options = *options;
+        options.tolerance = scaled_pixel_tolerance::<Unit>(options.tolerance, scale);
+        self.cached(
+            Key::Fill {
+                color,
+                options,
+                scale,
+            },
+            |path| path.fill_opt(color, &options),
+        )
+    }
+
+    /// Returns this path stroked with `options`, tessellated for rendering at
+    /// `scale`. [`StrokeOptions::tolerance`] is measured in pixels, regardless
+    /// of `Unit`.
+    pub fn stroke(
+        &mut self,
+        options: impl Into<StrokeOptions<Unit>>,
+        scale: Fraction,
+    ) -> &Shape<Unit, TEXTURED> {
+        let mut options = options.into();
+        options.tolerance = scaled_pixel_tolerance::<Unit>(options.tolerance, scale);
+        self.cached(Key::Stroke { options, scale }, |path| path.stroke(options))
+    }
+}
+
+impl<Unit, const TEXTURED: bool> From<Path<Unit, TEXTURED>> for CachedPath<Unit, TEXTURED> {
+    fn from(path: Path<Unit, TEXTURED>) -> Self {
+        Self::new(path)
+    }
+}
+
+/// Statistics about how effective a [`CachedPath`] has been.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct CacheStatistics {
+    /// The number of shapes returned from the cache.
+    pub hits: u64,
+    /// The number of shapes that needed to be tessellated.
+    pub misses: u64,
+}
+
+impl CacheStatistics {
+    /// Returns the fraction of requests that were returned from the cache,
+    /// ranging from `0.0` to `1.0`.
+    #[must_use]
+    pub fn hit_ratio(&self) -> f32 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.
+        } else {
+            self.hits.cast::<f32>() / total.cast::<f32>()
+        }
+    }
+}
+
+#[test]
+fn cached_tessellation() {
+    use figures::units::Lp;
+    use figures::{Point, Size};
+
+    let mut circle = CachedPath::new(Path::ellipse(Point::ZERO, Size::squared(Lp::inches(1))));
+    let standard = circle.fill(Color::RED, Fraction::ONE).vertices.len();
+    assert_eq!(
+        circle.fill(Color::RED, Fraction::ONE).vertices.len(),
+        standard
+    );
+    assert_eq!(circle.statistics(), CacheStatistics { hits: 1, misses: 1 });
+
+    // Higher scales use a tighter tolerance, producing more vertices.
+    let doubled = circle.fill(Color::RED, Fraction::from(2.)).vertices.len();
+    assert!(doubled > standard);
+    circle.stroke(Color::RED, Fraction::ONE);
+    assert_eq!(circle.len(), 3);
+    assert_eq!(circle.statistics().misses, 3);
+
+    circle.invalidate();
+    assert!(circle.is_empty());
+    circle.fill(Color::RED, Fraction::ONE);
+    assert_eq!(circle.statistics().misses, 4);
+    assert!((circle.statistics().hit_ratio() - 0.2).abs() < f32::EPSILON);
+}
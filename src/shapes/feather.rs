@@ -0,0 +1,177 @@
+use std::collections::hash_map;
+
+use ahash::AHashMap;
+use figures::units::Px;
+use figures::{FloatConversion, Point, ScreenScale};
+use intentional::Cast;
+
+use super::{pixel_tolerance, Shape};
+use crate::pipeline::Vertex;
+
+/// The longest a feathered corner can extend, as a multiple of the feather's
+/// width.
+const MITER_LIMIT: f32 = 4.;
+
+impl<Unit, const TEXTURED: bool> Shape<Unit, TEXTURED>
+where
+    Unit: FloatConversion<Float = f32> + Copy,
+{
+    /// Returns this shape with a feathered edge `width` wide surrounding its
+    /// outline.
+    ///
+    /// The feathered edge fades from each outline vertex's color to fully
+    /// transparent, which smooths jagged edges when rendering without
+    /// multisampling. Because the edge extends outward, the shape appears
+    /// approximately `width / 2` larger.
+    ///
+    /// The outline is made of the edges of the tessellated shape that belong
+    /// to a single triangle. Shapes whose triangles overlap, such as strokes
+    /// with sharp corners, may be feathered in places that are covered by
+    /// other triangles of the same shape.
+    #[must_use]
+    pub fn feathered(mut self, width: Unit) -> Self {
+        self.feather(width.into_float());
+        self
+    }
+
+    /// Returns this shape with its edges anti-aliased using a feathered edge
+    /// one pixel wide.
+    ///
+    /// The edge is one pixel wide when rendered at a scale of 1.0. For scaled
+    /// units, [`Renderer::fill_path()`](crate::drawing::Renderer::fill_path)
+    /// and [`Renderer::stroke_path()`](crate::drawing::Renderer::stroke_path)
+    /// feather shapes using the current scale when
+    /// [shape anti-aliasing](crate::Kludgine::set_shape_anti_aliasing) is
+    /// enabled.
+    ///
+    /// See [`feathered()`](Self::feathered) for more information.
+    #[must_use]
+    pub fn anti_aliased(mut self) -> Self
+    where
+        Unit: ScreenScale<Px = Px>,
+    {
+        self.feather(pixel_tolerance::<Unit>(1.));
+        self
+    }
+
+    pub(crate) fn feather(&mut self, width: f32) {
+        if width <= 0. || self.indices.is_empty() {
+            return;
+        }
+        let locations = self
+            .vertices
+            .iter()
+            .map(|vertex| {
+                Point::new(
+                    vertex.location.x.into_float(),
+                    vertex.location.y.into_float(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // Edges that belong to a single triangle are on the outline.
+        let mut edges = Vec::<(u32, u32, u32, bool)>::new();
+        let mut edge_lookup = AHashMap::<(u32, u32), usize>::new();
+        for triangle in self.indices.chunks_exact(3) {
+            for (a, b, opposite) in [
+                (triangle[0], triangle[1], triangle[2]),
+                (triangle[1], triangle[2], triangle[0]),
+                (triangle[2], triangle[0], triangle[1]),
+            ] {
+                match edge_lookup.entry((a.min(b), a.max(b))) {
+                    hash_map::Entry::Occupied(entry) => edges[*entry.get()].3 = true,
+                    hash_map::Entry::Vacant(entry) => {
+                        entry.insert(edges.len());
+                        edges.push((a, b, opposite, false));
+                    }
+                }
+            }
+        }
+
+        // Sum the outward normals of each outline vertex's edges.
+        let mut normals = vec![None::<(Point<f32>, f32)>; self.vertices.len()];
+        let mut outline = Vec::new();
+        for &(a, b, opposite, shared) in &edges {
+            if shared {
+                continue;
+            }
+            let start = locations[a.cast::<usize>()];
+            let direction = locations[b.cast::<usize>()] - start;
+            let length = direction.x.hypot(direction.y);
+            if length <= f32::EPSILON {
+                continue;
+            }
+            let mut normal = Point::new(direction.y / length, -direction.x / length);
+            let inward = locations[opposite.cast::<usize>()] - start;
+            if normal.x * inward.x + normal.y * inward.y > 0. {
+                normal = Point::new(-normal.x, -normal.y);
+            }
+            for vertex in [a, b] {
+                let (sum, count) =
+                    normals[vertex.cast::<usize>()].get_or_insert((Point::new(0., 0.), 0.));
+                *sum = *sum + normal;
+                *count += 1.;
+            }
+            outline.push((a, b));
+        }
+
+        // Add a transparent copy of each outline vertex, offset such that the
+        // feathered edge is `width` wide along each of the vertex's edges.
+        let mut outer = vec![0; self.vertices.len()];
+        for (index, normal) in normals.into_iter().enumerate() {
+            let Some((sum, count)) = normal else {
+                continue;
+            };
+            let length_squared = sum.x * sum.x + sum.y * sum.y;
+            let offset = if length_squared > f32::EPSILON {
+                let length = length_squared.sqrt();
+                let miter = (count / length).min(MITER_LIMIT) * width / length;
+                Point::new(sum.x * miter, sum.y * miter)
+            } else {
+                Point::new(0., 0.)
+            };
+            let inner = self.vertices[index];
+            let position = locations[index] + offset;
+            outer[index] = u32::try_from(self.vertices.len()).unwrap_or(u32::MAX);
+            self.vertices.push(Vertex {
+                location: Point::new(Unit::from_float(position.x), Unit::from_float(position.y)),
+                texture: inner.texture,
+                color: inner.color.with_alpha(0),
+            });
+        }
+
+        for (a, b) in outline {
+            let (outer_a, outer_b) = (outer[a.cast::<usize>()], outer[b.cast::<usize>()]);
+            self.indices
+                .extend_from_slice(&[a, b, outer_b, a, outer_b, outer_a]);
+        }
+    }
+}
+
+#[test]
+fn feathered_rect() {
+    use figures::{Rect, Size};
+
+    use crate::Color;
+
+    let shape = Shape::filled_rect(
+        Rect::new(
+            Point::new(Px::new(0), Px::new(0)),
+            Size::new(Px::new(10), Px::new(10)),
+        ),
+        Color::RED,
+    )
+    .feathered(Px::new(2));
+    assert_eq!(shape.vertices.len(), 8);
+    // Two triangles for the rect and two for each of its four edges.
+    assert_eq!(shape.indices.len(), 30);
+    let outer = shape
+        .vertices
+        .iter()
+        .filter(|vertex| vertex.color.alpha() == 0)
+        .map(|vertex| vertex.location)
+        .collect::<Vec<_>>();
+    assert_eq!(outer.len(), 4);
+    assert!(outer.contains(&Point::new(Px::new(-2), Px::new(-2))));
+    assert!(outer.contains(&Point::new(Px::new(12), Px::new(12))));
+}
@@ -0,0 +1,262 @@
+use figures::{FloatConversion, Point};
+
+use crate::Color;
+
+/// A series of colors that smoothly transition between each other.
+///
+/// A gradient can be sampled on the CPU using [`sample()`](Self::sample),
+/// which is useful for coloring particles, charts, and heatmaps. Paths can be
+/// filled with a gradient using
+/// [`Path::fill_gradient()`](super::Path::fill_gradient).
+///
+/// ```rust
+/// use kludgine::shapes::{Gradient, GradientInterpolation};
+/// use kludgine::Color;
+///
+/// let heat = Gradient::new(Color::BLUE, Color::RED)
+///     .with_stop(0.5, Color::YELLOW)
+///     .with_interpolation(GradientInterpolation::Oklab);
+/// assert_eq!(heat.sample(0.), Color::BLUE);
+/// assert_eq!(heat.sample(0.5), Color::YELLOW);
+/// assert_eq!(heat.sample(2.), Color::RED);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gradient {
+    stops: Vec<ColorStop>,
+    interpolation: GradientInterpolation,
+}
+
+impl Gradient {
+    /// Returns a gradient from `start` at position `0.0` to `end` at position
+    /// `1.0`.
+    #[must_use]
+    pub fn new(start: Color, end: Color) -> Self {
+        Self::from_stops([ColorStop::new(0., start), ColorStop::new(1., end)])
+    }
+
+    /// Returns a gradient containing `stops`.
+    ///
+    /// The stops are sorted by their positions. Stops that share a position
+    /// keep their relative order, which creates a hard transition between
+    /// their colors.
+    #[must_use]
+    pub fn from_stops(stops: impl IntoIterator<Item = ColorStop>) -> Self {
+        let mut stops = stops.into_iter().collect::<Vec<_>>();
+        stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+        Self {
+            stops,
+            interpolation: GradientInterpolation::default(),
+        }
+    }
+
+    /// Adds a stop of `color` at `position` and returns self.
+    ///
+    /// If other stops already exist at `position`, the new stop is placed
+    /// after them.
+    #[must_use]
+    pub fn with_stop(mut self, position: f32, color: Color) -> Self {
+        let index = self.stops.partition_point(|stop| stop.position <= position);
+        self.stops.insert(index, ColorStop::new(position, color));
+        self
+    }
+
+    /// Sets the color space colors are interpolated in and returns self.
+    #[must_use]
+    pub const fn with_interpolation(mut self, interpolation: GradientInterpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Returns the color stops of this gradient, sorted by their positions.
+    #[must_use]
+    pub fn stops(&self) -> &[ColorStop] {
+        &self.stops
+    }
+
+    /// Returns the color space colors are interpolated in.
+    #[must_use]
+    pub const fn interpolation(&self) -> GradientInterpolation {
+        self.interpolation
+    }
+
+    /// Returns the color of this gradient at `position`.
+    ///
+    /// Positions before the first stop return the first stop's color, and
+    /// positions after the last stop return the last stop's color. A gradient
+    /// without any stops returns [`Color::CLEAR_BLACK`].
+    #[must_use]
+    pub fn sample(&self, position: f32) -> Color {
+        let (Some(first), Some(last)) = (self.stops.first(), self.stops.last()) else {
+            return Color::CLEAR_BLACK;
+        };
+        let upper = self.stops.partition_point(|stop| stop.position < position);
+        if upper == 0 || position.is_nan() {
+            return first.color;
+        } else if upper == self.stops.len() {
+            return last.color;
+        }
+
+        let start = self.stops[upper - 1];
+        let end = self.stops[upper];
+        let span = end.position - start.position;
+        if span <= 0. || position >= end.position {
+            return end.color;
+        }
+        self.interpolation
+            .mix(start.color, end.color, (position - start.position) / span)
+    }
+}
+
+/// A color at a position within a [`Gradient`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColorStop {
+    /// The position of this stop. Positions typically range from `0.0` to
+    /// `1.0`.
+    pub position: f32,
+    /// The color at this stop.
+    pub color: Color,
+}
+
+impl ColorStop {
+    /// Returns a new stop of `color` at `position`.
+    #[must_use]
+    pub const fn new(position: f32, color: Color) -> Self {
+        Self { position, color }
+    }
+}
+
+impl From<(f32, Color)> for ColorStop {
+    fn from((position, color): (f32, Color)) -> Self {
+        Self::new(position, color)
+    }
+}
+
+/// The color space a [`Gradient`] interpolates colors in.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GradientInterpolation {
+    /// Colors are interpolated using [`Color::lerp()`].
+    #[default]
+    Srgb,
+    /// Colors are interpolated using [`Color::mix()`].
+    LinearRgb,
+    /// Colors are interpolated using [`Color::mix_oklab()`].
+    Oklab,
+}
+
+impl GradientInterpolation {
+    /// Returns the mix of `start` and `end` by `fraction` in this color space.
+    #[must_use]
+    pub fn mix(self, start: Color, end: Color, fraction: f32) -> Color {
+        match self {
+            GradientInterpolation::Srgb => start.lerp(end, fraction),
+            GradientInterpolation::LinearRgb => start.mix(end, fraction),
+            GradientInterpolation::Oklab => start.mix_oklab(end, fraction),
+        }
+    }
+}
+
+/// The geometry used to map locations to positions within a [`Gradient`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GradientKind<Unit> {
+    /// The gradient's positions range from `0.0` at `start` to `1.0` at
+    /// `end`. Positions are constant along lines perpendicular to the line
+    /// between `start` and `end`.
+    Linear {
+        /// The location of position `0.0`.
+        start: Point<Unit>,
+        /// The location of position `1.0`.
+        end: Point<Unit>,
+    },
+    /// The gradient's positions range from `0.0` at `center` to `1.0` at
+    /// `radius` away from `center`.
+    Radial {
+        /// The location of position `0.0`.
+        center: Point<Unit>,
+        /// The distance from `center` of position `1.0`.
+        radius: Unit,
+    },
+}
+
+impl<Unit> GradientKind<Unit>
+where
+    Unit: FloatConversion<Float = f32> + Copy,
+{
+    /// Returns the gradient position of `location`.
+    #[must_use]
+    pub fn position_at(&self, location: Point<Unit>) -> f32 {
+        self.to_float().position_of(location.map(Unit::into_float))
+    }
+
+    pub(crate) fn to_float(self) -> GradientKind<f32> {
+        match self {
+            GradientKind::Linear { start, end } => GradientKind::Linear {
+                start: start.map(Unit::into_float),
+                end: end.map(Unit::into_float),
+            },
+            GradientKind::Radial { center, radius } => GradientKind::Radial {
+                center: center.map(Unit::into_float),
+                radius: radius.into_float(),
+            },
+        }
+    }
+}
+
+impl GradientKind<f32> {
+    pub(crate) fn position_of(&self, location: Point<f32>) -> f32 {
+        match *self {
+            GradientKind::Linear { start, end } => {
+                let direction = end - start;
+                let length_squared = direction.x * direction.x + direction.y * direction.y;
+                if length_squared > 0. {
+                    let offset = location - start;
+                    (offset.x * direction.x + offset.y * direction.y) / length_squared
+                } else {
+                    0.
+                }
+            }
+            GradientKind::Radial { center, radius } => {
+                let offset = location - center;
+                let distance = offset.x.hypot(offset.y);
+                if radius > 0. {
+                    distance / radius
+                } else {
+                    1.
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn gradient_sampling() {
+    let gradient = Gradient::new(Color::BLACK, Color::WHITE).with_stop(0.5, Color::RED);
+    assert_eq!(gradient.sample(-1.), Color::BLACK);
+    assert_eq!(gradient.sample(0.25), Color::BLACK.lerp(Color::RED, 0.5));
+    assert_eq!(gradient.sample(0.5), Color::RED);
+    assert_eq!(gradient.sample(0.75), Color::RED.lerp(Color::WHITE, 0.5));
+    assert_eq!(gradient.sample(f32::NAN), Color::BLACK);
+    assert_eq!(Gradient::default().sample(0.5), Color::CLEAR_BLACK);
+
+    // Stops at the same position create a hard edge.
+    let hard = Gradient::new(Color::RED, Color::BLUE)
+        .with_stop(0.5, Color::RED)
+        .with_stop(0.5, Color::BLUE);
+    assert_eq!(hard.sample(0.49), Color::RED);
+    assert_eq!(hard.sample(0.5), Color::RED);
+    assert_eq!(hard.sample(0.51), Color::BLUE);
+
+    let linear = GradientKind::Linear {
+        start: Point::new(0., 0.),
+        end: Point::new(10., 0.),
+    };
+    assert_eq!(linear.position_of(Point::new(5., 100.)), 0.5);
+    let radial = GradientKind::Radial {
+        center: Point::new(0., 0.),
+        radius: 10.,
+    };
+    assert_eq!(radial.position_of(Point::new(6., 8.)), 1.);
+}
@@ -0,0 +1,307 @@
+use figures::units::UPx;
+use figures::{FloatConversion, Point};
+use intentional::Cast;
+
+use super::{Endpoint, PathEvent};
+use crate::{Assert, Color};
+
+/// Returns the event between `a` and `b` at `fraction`, or `None` if the
+/// events are not the same kind of event.
+pub(super) fn lerp_event<Unit>(
+    a: &PathEvent<Unit>,
+    b: &PathEvent<Unit>,
+    fraction: f32,
+) -> Option<PathEvent<Unit>>
+where
+    Unit: FloatConversion<Float = f32> + Copy,
+{
+    let point = |a: Point<Unit>, b: Point<Unit>| {
+        lerp_point(a.map(Unit::into_float), b.map(Unit::into_float), fraction).map(Unit::from_float)
+    };
+    let endpoint = |a: Endpoint<Unit>, b: Endpoint<Unit>| Endpoint {
+        location: point(a.location, b.location),
+        color: a.color.lerp(b.color, fraction),
+    };
+    let texture = |a: Point<UPx>, b: Point<UPx>| {
+        lerp_point(a.map(UPx::into_float), b.map(UPx::into_float), fraction).map(UPx::from_float)
+    };
+    match (*a, *b) {
+        (
+            PathEvent::Begin {
+                at: a_at,
+                texture: a_texture,
+            },
+            PathEvent::Begin {
+                at: b_at,
+                texture: b_texture,
+            },
+        ) => Some(PathEvent::Begin {
+            at: endpoint(a_at, b_at),
+            texture: texture(a_texture, b_texture),
+        }),
+        (
+            PathEvent::Line {
+                to: a_to,
+                texture: a_texture,
+            },
+            PathEvent::Line {
+                to: b_to,
+                texture: b_texture,
+            },
+        ) => Some(PathEvent::Line {
+            to: endpoint(a_to, b_to),
+            texture: texture(a_texture, b_texture),
+        }),
+        (
+            PathEvent::Quadratic {
+                ctrl: a_ctrl,
+                to: a_to,
+                texture: a_texture,
+            },
+            PathEvent::Quadratic {
+                ctrl: b_ctrl,
+                to: b_to,
+                texture: b_texture,
+            },
+        ) => Some(PathEvent::Quadratic {
+            ctrl: point(a_ctrl, b_ctrl),
+            to: endpoint(a_to, b_to),
+            texture: texture(a_texture, b_texture),
+        }),
+        (
+            PathEvent::Cubic {
+                ctrl1: a_ctrl1,
+                ctrl2: a_ctrl2,
+                to: a_to,
+                texture: a_texture,
+            },
+            PathEvent::Cubic {
+                ctrl1: b_ctrl1,
+                ctrl2: b_ctrl2,
+                to: b_to,
+                texture: b_texture,
+            },
+        ) => Some(PathEvent::Cubic {
+            ctrl1: point(a_ctrl1, b_ctrl1),
+            ctrl2: point(a_ctrl2, b_ctrl2),
+            to: endpoint(a_to, b_to),
+            texture: texture(a_texture, b_texture),
+        }),
+        (PathEvent::End { close: a_close }, PathEvent::End { close: b_close })
+            if a_close == b_close =>
+        {
+            Some(PathEvent::End { close: a_close })
+        }
+        _ => None,
+    }
+}
+
+/// A location on a flattened path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Sample {
+    location: Point<f32>,
+    texture: Point<f32>,
+    color: Color,
+}
+
+impl Sample {
+    fn new<Unit>(endpoint: Endpoint<Unit>, texture: Point<UPx>) -> Self
+    where
+        Unit: FloatConversion<Float = f32>,
+    {
+        Self {
+            location: endpoint.location.map(Unit::into_float),
+            texture: texture.map(UPx::into_float),
+            color: endpoint.color,
+        }
+    }
+
+    fn lerp(self, other: Self, fraction: f32) -> Self {
+        Self {
+            location: lerp_point(self.location, other.location, fraction),
+            texture: lerp_point(self.texture, other.texture, fraction),
+            color: self.color.lerp(other.color, fraction),
+        }
+    }
+}
+
+/// Returns `events` with each subpath replaced by `count` points joined by
+/// straight lines, evenly spaced along the subpath's length.
+///
+/// Curves are flattened such that they deviate from the original curve by at
+/// most `tolerance`.
+pub(super) fn resample<Unit>(
+    events: &[PathEvent<Unit>],
+    count: usize,
+    tolerance: f32,
+) -> Vec<PathEvent<Unit>>
+where
+    Unit: FloatConversion<Float = f32> + Copy,
+{
+    let mut resampled = Vec::new();
+    let mut subpath = Vec::<Sample>::new();
+    for event in events {
+        match *event {
+            PathEvent::Begin { at, texture } => {
+                subpath.clear();
+                subpath.push(Sample::new(at, texture));
+            }
+            PathEvent::Line { to, texture } => {
+                subpath.push(Sample::new(to, texture));
+            }
+            PathEvent::Quadratic { ctrl, to, texture } => {
+                let Some(&from) = subpath.last() else {
+                    continue;
+                };
+                let to = Sample::new(to, texture);
+                let points = [from.location, ctrl.map(Unit::into_float), to.location];
+                let segments = segment_count(0.25 * second_difference(points), tolerance);
+                flatten(&mut subpath, from, to, segments, |t| {
+                    let u = 1. - t;
+                    weighted(&points, [u * u, 2. * u * t, t * t])
+                });
+            }
+            PathEvent::Cubic {
+                ctrl1,
+                ctrl2,
+                to,
+                texture,
+            } => {
+                let Some(&from) = subpath.last() else {
+                    continue;
+                };
+                let to = Sample::new(to, texture);
+                let points = [
+                    from.location,
+                    ctrl1.map(Unit::into_float),
+                    ctrl2.map(Unit::into_float),
+                    to.location,
+                ];
+                let largest = second_difference([points[0], points[1], points[2]])
+                    .max(second_difference([points[1], points[2], points[3]]));
+                let segments = segment_count(0.75 * largest, tolerance);
+                flatten(&mut subpath, from, to, segments, |t| {
+                    let u = 1. - t;
+                    weighted(
+                        &points,
+                        [u * u * u, 3. * u * u * t, 3. * u * t * t, t * t * t],
+                    )
+                });
+            }
+            PathEvent::End { close } => {
+                if subpath.is_empty() {
+                    continue;
+                }
+                let mut samples = resample_subpath(&subpath, close, count).into_iter();
+                let first = samples.next().assert("at least one sample");
+                resampled.push(PathEvent::Begin {
+                    at: endpoint(first),
+                    texture: first.texture.map(UPx::from_float),
+                });
+                resampled.extend(samples.map(|sample| PathEvent::Line {
+                    to: endpoint(sample),
+                    texture: sample.texture.map(UPx::from_float),
+                }));
+                resampled.push(PathEvent::End { close });
+                subpath.clear();
+            }
+        }
+    }
+    resampled
+}
+
+/// Appends `segments` line segments approximating the curve from `from` to
+/// `to` to `subpath`. `location` returns the location on the curve at a given
+/// `t`.
+fn flatten(
+    subpath: &mut Vec<Sample>,
+    from: Sample,
+    to: Sample,
+    segments: u32,
+    location: impl Fn(f32) -> Point<f32>,
+) {
+    for index in 1..segments {
+        let t = index.cast::<f32>() / segments.cast::<f32>();
+        subpath.push(Sample {
+            location: location(t),
+            ..from.lerp(to, t)
+        });
+    }
+    subpath.push(to);
+}
+
+/// Returns the number of segments needed to flatten a curve within
+/// `tolerance`, using Wang's formula. `bound` is the largest second
+/// difference of the curve's points, multiplied by `n(n - 1) / 8`, where `n`
+/// is the curve's degree.
+fn segment_count(bound: f32, tolerance: f32) -> u32 {
+    (bound / tolerance.max(f32::EPSILON))
+        .sqrt()
+        .ceil()
+        .clamp(1., 1024.)
+        .cast::<u32>()
+}
+
+fn second_difference([a, b, c]: [Point<f32>; 3]) -> f32 {
+    (a.x - 2. * b.x + c.x).hypot(a.y - 2. * b.y + c.y)
+}
+
+fn weighted<const N: usize>(points: &[Point<f32>; N], weights: [f32; N]) -> Point<f32> {
+    points
+        .iter()
+        .zip(weights)
+        .fold(Point::new(0., 0.), |sum, (point, weight)| {
+            Point::new(sum.x + point.x * weight, sum.y + point.y * weight)
+        })
+}
+
+/// Returns `count` samples evenly spaced along `subpath`.
+fn resample_subpath(subpath: &[Sample], close: bool, count: usize) -> Vec<Sample> {
+    let mut points = subpath.to_vec();
+    if close || points.len() == 1 {
+        points.push(points[0]);
+    }
+    let mut distances = Vec::with_capacity(points.len());
+    let mut total = 0.;
+    for (index, point) in points.iter().enumerate() {
+        if let Some(previous) = index.checked_sub(1).map(|index| points[index]) {
+            total += (point.location.x - previous.location.x)
+                .hypot(point.location.y - previous.location.y);
+        }
+        distances.push(total);
+    }
+
+    let count = count.max(2);
+    // Closed subpaths do not repeat their starting point.
+    let steps = if close { count } else { count - 1 };
+    let mut segment = 0;
+    (0..count)
+        .map(|index| {
+            let target = total * index.cast::<f32>() / steps.cast::<f32>();
+            while segment + 2 < points.len() && distances[segment + 1] < target {
+                segment += 1;
+            }
+            let length = distances[segment + 1] - distances[segment];
+            let fraction = if length > 0. {
+                (target - distances[segment]) / length
+            } else {
+                0.
+            };
+            points[segment].lerp(points[segment + 1], fraction)
+        })
+        .collect()
+}
+
+fn endpoint<Unit>(sample: Sample) -> Endpoint<Unit>
+where
+    Unit: FloatConversion<Float = f32>,
+{
+    Endpoint {
+        location: sample.location.map(Unit::from_float),
+        color: sample.color,
+    }
+}
+
+fn lerp_point(a: Point<f32>, b: Point<f32>, fraction: f32) -> Point<f32> {
+    Point::new(a.x + (b.x - a.x) * fraction, a.y + (b.y - a.y) * fraction)
+}
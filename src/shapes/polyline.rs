@@ -0,0 +1,246 @@
+use figures::units::Px;
+use figures::{FloatConversion, Point, ScreenScale};
+
+use super::{pixel_tolerance, Shape};
+use crate::pipeline::Vertex;
+use crate::{srgb_to_linear, Color};
+
+/// The longest a mitered corner can extend, as a multiple of half of the
+/// line's width.
+const MITER_LIMIT: f32 = 4.;
+
+/// A series of connected line segments that can be efficiently updated.
+///
+/// Unlike stroking a [`Path`](super::Path), a polyline is tessellated directly
+/// into a triangle strip with mitered corners. The points and the tessellated
+/// [`Shape`] are kept between updates, allowing a polyline to be rebuilt
+/// every frame without allocating once its buffers have grown large enough.
+/// This makes it well suited for plots and charts.
+///
+/// Lines one pixel wide or thinner are drawn exactly one pixel wide, without
+/// computing corner joins.
+///
+/// ```rust
+/// use kludgine::figures::units::Px;
+/// use kludgine::figures::Point;
+/// use kludgine::shapes::Polyline;
+/// use kludgine::Color;
+///
+/// # fn draw(renderer: &mut kludgine::drawing::Renderer<'_, '_>) {
+/// let mut line = Polyline::new(Px::new(2), Color::RED);
+/// // Each frame:
+/// line.set_points((0..1000).map(|x| Point::new(Px::new(x), Px::new(x % 7))));
+/// renderer.draw_shape(line.shape());
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Polyline<Unit> {
+    points: Vec<Point<Unit>>,
+    width: Unit,
+    color: Color,
+    shape: Shape<Unit, false>,
+    tessellated: bool,
+}
+
+impl<Unit> Polyline<Unit> {
+    /// Returns an empty polyline with the given line `width` and `color`.
+    #[must_use]
+    pub fn new(width: Unit, color: Color) -> Self {
+        Self {
+            points: Vec::new(),
+            width,
+            color,
+            shape: Shape::default(),
+            tessellated: false,
+        }
+    }
+
+    /// Returns the points of this polyline.
+    #[must_use]
+    pub fn points(&self) -> &[Point<Unit>] {
+        &self.points
+    }
+
+    /// Removes all points from this polyline.
+    pub fn clear(&mut self) {
+        self.points.clear();
+        self.tessellated = false;
+    }
+
+    /// Adds `point` to the end of this polyline.
+    pub fn push(&mut self, point: Point<Unit>) {
+        self.points.push(point);
+        self.tessellated = false;
+    }
+
+    /// Replaces the points of this polyline with `points`.
+    pub fn set_points(&mut self, points: impl IntoIterator<Item = Point<Unit>>) {
+        self.points.clear();
+        self.points.extend(points);
+        self.tessellated = false;
+    }
+
+    /// Returns the width of the line.
+    #[must_use]
+    pub const fn width(&self) -> &Unit {
+        &self.width
+    }
+
+    /// Sets the width of the line.
+    pub fn set_width(&mut self, width: Unit) {
+        self.width = width;
+        self.tessellated = false;
+    }
+
+    /// Returns the color of the line.
+    #[must_use]
+    pub const fn color(&self) -> Color {
+        self.color
+    }
+
+    /// Sets the color of the line.
+    pub fn set_color(&mut self, color: Color) {
+        self.color = color;
+        self.tessellated = false;
+    }
+}
+
+impl<Unit> Polyline<Unit>
+where
+    Unit: FloatConversion<Float = f32> + ScreenScale<Px = Px> + Copy,
+{
+    /// Returns the tessellated line, tessellating it if it has changed since
+    /// the last call.
+    pub fn shape(&mut self) -> &Shape<Unit, false> {
+        if !self.tessellated {
+            self.tessellate();
+            self.tessellated = true;
+        }
+        &self.shape
+    }
+
+    fn tessellate(&mut self) {
+        self.shape.vertices.clear();
+        self.shape.indices.clear();
+
+        let one_pixel = pixel_tolerance::<Unit>(1.);
+        let width = self.width.into_float();
+        let hairline = width <= one_pixel;
+        let half_width = if hairline { one_pixel } else { width } / 2.;
+        let color = srgb_to_linear(
+            self.color.red_f32(),
+            self.color.green_f32(),
+            self.color.blue_f32(),
+            self.color.alpha_f32(),
+        );
+
+        let mut points = self
+            .points
+            .iter()
+            .map(|point| point.map(Unit::into_float))
+            .peekable();
+        let Some(mut current) = points.next() else {
+            return;
+        };
+        // Repeated points are skipped, as they have no direction.
+        while points.next_if_eq(&current).is_some() {}
+        let mut incoming = None::<Point<f32>>;
+        loop {
+            let next = points.next();
+            let outgoing = next.map(|next| direction(current, next));
+            let offset = match (incoming, outgoing) {
+                (None, None) => return,
+                (Some(normal), None) | (None, Some(normal)) => scaled(normal, half_width),
+                (Some(_), Some(normal)) if hairline => scaled(normal, half_width),
+                (Some(incoming), Some(outgoing)) => miter(incoming, outgoing, half_width),
+            };
+
+            let index = u32::try_from(self.shape.vertices.len()).expect("too many vertices");
+            for location in [
+                Point::new(current.x + offset.x, current.y + offset.y),
+                Point::new(current.x - offset.x, current.y - offset.y),
+            ] {
+                self.shape.vertices.push(Vertex {
+                    location: location.map(Unit::from_float),
+                    texture: Point::default(),
+                    color,
+                });
+            }
+            if index > 0 {
+                self.shape.indices.extend([
+                    index - 2,
+                    index - 1,
+                    index,
+                    index - 1,
+                    index + 1,
+                    index,
+                ]);
+            }
+
+            let Some(next) = next else {
+                break;
+            };
+            incoming = outgoing;
+            current = next;
+            while points.next_if_eq(&current).is_some() {}
+        }
+    }
+}
+
+/// Returns the unit normal of the segment from `from` to `to`.
+fn direction(from: Point<f32>, to: Point<f32>) -> Point<f32> {
+    let (dx, dy) = (to.x - from.x, to.y - from.y);
+    let length = dx.hypot(dy);
+    Point::new(-dy / length, dx / length)
+}
+
+fn scaled(point: Point<f32>, scale: f32) -> Point<f32> {
+    Point::new(point.x * scale, point.y * scale)
+}
+
+/// Returns the offset from a corner to the edges of a line of `half_width`,
+/// given the normals of the incoming and outgoing segments.
+fn miter(incoming: Point<f32>, outgoing: Point<f32>, half_width: f32) -> Point<f32> {
+    let sum = Point::new(incoming.x + outgoing.x, incoming.y + outgoing.y);
+    let length = sum.x.hypot(sum.y);
+    if length <= f32::EPSILON {
+        // The line doubles back on itself.
+        return scaled(outgoing, half_width);
+    }
+    let miter = scaled(sum, 1. / length);
+    let cosine = miter.x * outgoing.x + miter.y * outgoing.y;
+    scaled(miter, (half_width / cosine).min(half_width * MITER_LIMIT))
+}
+
+#[test]
+fn polyline_tessellation() {
+    let mut line = Polyline::new(Px::new(4), Color::WHITE);
+    line.set_points([
+        Point::new(Px::new(0), Px::new(0)),
+        Point::new(Px::new(10), Px::new(0)),
+        Point::new(Px::new(10), Px::new(0)),
+        Point::new(Px::new(10), Px::new(10)),
+    ]);
+    let shape = line.shape();
+    // The repeated point is skipped.
+    assert_eq!(shape.vertices.len(), 6);
+    assert_eq!(shape.indices.len(), 12);
+    // The corner is mitered, extending to the outside of both segments.
+    assert_eq!(
+        shape.vertices[2].location,
+        Point::new(Px::new(8), Px::new(2))
+    );
+    assert_eq!(
+        shape.vertices[3].location,
+        Point::new(Px::new(12), Px::new(-2))
+    );
+
+    // Hairlines are widened to one pixel.
+    line.set_width(Px::ZERO);
+    let shape = line.shape();
+    assert_eq!(shape.vertices[0].location.y, Px::from(0.5));
+    assert_eq!(shape.vertices[1].location.y, Px::from(-0.5));
+
+    line.clear();
+    assert!(line.shape().vertices.is_empty());
+}
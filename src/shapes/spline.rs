@@ -0,0 +1,171 @@
+use figures::Point;
+use intentional::Cast;
+
+/// A cubic Bézier segment: the start, two control points, and the end.
+pub(super) type Segment = [Point<f32>; 4];
+
+/// Returns the cubic Bézier segments of a centripetal Catmull-Rom spline
+/// passing through each of `points`.
+///
+/// Centripetal parameterization avoids the loops and cusps that uniform
+/// Catmull-Rom splines produce when points are unevenly spaced.
+pub(super) fn catmull_rom(points: &[Point<f32>], closed: bool) -> Vec<Segment> {
+    let points = without_repeats(points, closed);
+    let count = points.len();
+    if count < 2 {
+        return Vec::new();
+    }
+
+    let point = |index: isize| -> Point<f32> {
+        if closed {
+            points[index.rem_euclid(count.cast::<isize>()).cast::<usize>()]
+        } else if index < 0 {
+            // Reflect the neighboring point to extend the ends.
+            reflect(points[1], points[0])
+        } else if index.cast::<usize>() >= count {
+            reflect(points[count - 2], points[count - 1])
+        } else {
+            points[index.cast::<usize>()]
+        }
+    };
+
+    let segments = if closed { count } else { count - 1 };
+    (0..segments.cast::<isize>())
+        .map(|index| {
+            let (p0, p1, p2, p3) = (
+                point(index - 1),
+                point(index),
+                point(index + 1),
+                point(index + 2),
+            );
+            let d1 = distance(p0, p1).sqrt();
+            let d2 = distance(p1, p2).sqrt();
+            let d3 = distance(p2, p3).sqrt();
+            [
+                p1,
+                control(p0, p1, p2, d1, d2),
+                control(p3, p2, p1, d3, d2),
+                p2,
+            ]
+        })
+        .collect()
+}
+
+/// Returns the cubic Bézier segments of a uniform cubic B-spline with
+/// `points` as its control points.
+///
+/// Open splines start and end at the first and last points.
+pub(super) fn b_spline(points: &[Point<f32>], closed: bool) -> Vec<Segment> {
+    let count = points.len();
+    if count < 2 {
+        return Vec::new();
+    }
+
+    let point = |index: isize| -> Point<f32> {
+        if closed {
+            points[index.rem_euclid(count.cast::<isize>()).cast::<usize>()]
+        } else {
+            // Repeating the end points pulls the curve to them.
+            points[index.clamp(0, count.cast::<isize>() - 1).cast::<usize>()]
+        }
+    };
+
+    let (first, last) = if closed {
+        (0, count.cast::<isize>() - 1)
+    } else {
+        (-2, count.cast::<isize>() - 2)
+    };
+    (first..=last)
+        .map(|index| {
+            let (b0, b1, b2, b3) = (
+                point(index),
+                point(index + 1),
+                point(index + 2),
+                point(index + 3),
+            );
+            [
+                weighted(&[(b0, 1.), (b1, 4.), (b2, 1.)], 6.),
+                weighted(&[(b1, 2.), (b2, 1.)], 3.),
+                weighted(&[(b1, 1.), (b2, 2.)], 3.),
+                weighted(&[(b1, 1.), (b2, 4.), (b3, 1.)], 6.),
+            ]
+        })
+        .collect()
+}
+
+/// Returns the Bézier control point leaving `p1` towards `p2`, where `p0` is
+/// the point before `p1`, and `d1` and `d2` are the parameterized lengths of
+/// the segments before and after `p1`.
+fn control(p0: Point<f32>, p1: Point<f32>, p2: Point<f32>, d1: f32, d2: f32) -> Point<f32> {
+    weighted(
+        &[
+            (p2, d1 * d1),
+            (p0, -d2 * d2),
+            (p1, 2. * d1 * d1 + 3. * d1 * d2 + d2 * d2),
+        ],
+        3. * d1 * (d1 + d2),
+    )
+}
+
+fn weighted(points: &[(Point<f32>, f32)], divisor: f32) -> Point<f32> {
+    let (x, y) = points.iter().fold((0., 0.), |(x, y), (point, weight)| {
+        (x + point.x * weight, y + point.y * weight)
+    });
+    Point::new(x / divisor, y / divisor)
+}
+
+fn reflect(point: Point<f32>, around: Point<f32>) -> Point<f32> {
+    Point::new(around.x * 2. - point.x, around.y * 2. - point.y)
+}
+
+fn distance(a: Point<f32>, b: Point<f32>) -> f32 {
+    (b.x - a.x).hypot(b.y - a.y)
+}
+
+fn without_repeats(points: &[Point<f32>], closed: bool) -> Vec<Point<f32>> {
+    let mut unique: Vec<Point<f32>> = Vec::with_capacity(points.len());
+    for &point in points {
+        if unique.last() != Some(&point) {
+            unique.push(point);
+        }
+    }
+    if closed && unique.len() > 1 && unique.first() == unique.last() {
+        unique.pop();
+    }
+    unique
+}
+
+#[test]
+fn splines() {
+    let points = [
+        Point::new(0., 0.),
+        Point::new(10., 0.),
+        Point::new(10., 0.),
+        Point::new(20., 10.),
+        Point::new(40., 10.),
+    ];
+    let segments = catmull_rom(&points, false);
+    assert_eq!(segments.len(), 3);
+    // The spline passes through each point.
+    assert_eq!(segments[0][0], points[0]);
+    assert_eq!(segments[1][0], points[1]);
+    assert_eq!(segments[2][3], points[4]);
+    // Adjacent segments share a tangent at the points between them.
+    let incoming = Point::new(
+        segments[0][3].x - segments[0][2].x,
+        segments[0][3].y - segments[0][2].y,
+    );
+    let outgoing = Point::new(
+        segments[1][1].x - segments[1][0].x,
+        segments[1][1].y - segments[1][0].y,
+    );
+    assert!((incoming.x * outgoing.y - incoming.y * outgoing.x).abs() < 1e-4);
+
+    assert_eq!(catmull_rom(&points, true).len(), 4);
+
+    let segments = b_spline(&points, false);
+    assert_eq!(segments.len(), points.len() + 1);
+    assert_eq!(segments[0][0], points[0]);
+    assert_eq!(segments.last().unwrap()[3], points[4]);
+    assert_eq!(b_spline(&points, true).len(), points.len());
+}
@@ -0,0 +1,1222 @@
+use std::fmt::{self, Display};
+
+use figures::{FloatConversion, PixelScaling, Point, Size};
+use lyon_tessellation::geom::{self, ArcFlags, SvgArc};
+
+use super::{Endpoint, FillRule, LineCap, LineJoin, Path, PathEvent, Shape, StrokeOptions};
+use crate::Color;
+
+/// A vector image loaded from an SVG document.
+///
+/// Rather than rasterizing the document, each element is converted into a
+/// [`Path`], allowing the image to be tessellated at any resolution. The
+/// subset of SVG commonly used by icons is supported:
+///
+/// - `path`, `rect`, `circle`, `ellipse`, `line`, `polyline`, and `polygon`
+///   elements, optionally grouped within `g` elements.
+/// - Solid fills and strokes, specified using presentation attributes or the
+///   `style` attribute. `currentColor` is resolved when creating
+///   [shapes](Self::shapes).
+/// - The `fill-rule`, `stroke-width`, `stroke-linecap`, `stroke-linejoin`,
+///   `stroke-miterlimit`, `opacity`, `fill-opacity`, and `stroke-opacity`
+///   properties.
+///
+/// Text, images, and the contents of `defs` are ignored. Paints referring to
+/// gradients or patterns use their fallback color if one is provided, and are
+/// otherwise not drawn. Documents that use the `transform` attribute are
+/// rejected with [`SvgError::Unsupported`].
+///
+/// ```rust
+/// use kludgine::figures::units::Lp;
+/// use kludgine::figures::Size;
+/// use kludgine::shapes::Svg;
+/// use kludgine::Color;
+///
+/// let icon = Svg::<Lp>::parse_with_size(
+///     r#"<svg viewBox="0 0 24 24"><path d="M12 2 L22 22 H2 Z" fill="currentColor"/></svg>"#,
+///     Size::squared(Lp::inches(1)),
+/// )
+/// .unwrap();
+/// let shapes = icon.shapes(Color::WHITE);
+/// assert_eq!(shapes.len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Svg<Unit> {
+    size: Size<Unit>,
+    elements: Vec<SvgElement<Unit>>,
+}
+
+impl<Unit> Svg<Unit>
+where
+    Unit: FloatConversion<Float = f32> + Copy,
+{
+    /// Parses `source` as an SVG document, with one unit in the document's
+    /// coordinate system equal to one `Unit`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document cannot be parsed or uses an
+    /// unsupported feature.
+    pub fn parse(source: &str) -> Result<Self, SvgError> {
+        Self::parse_into(source, None)
+    }
+
+    /// Parses `source` as an SVG document, scaling it to fit within `size`.
+    ///
+    /// The document's aspect ratio is preserved, and it is centered within
+    /// `size`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document cannot be parsed or uses an
+    /// unsupported feature.
+    pub fn parse_with_size(source: &str, size: Size<Unit>) -> Result<Self, SvgError> {
+        Self::parse_into(
+            source,
+            Some(Size::new(size.width.into_float(), size.height.into_float())),
+        )
+    }
+
+    fn parse_into(source: &str, size: Option<Size<f32>>) -> Result<Self, SvgError> {
+        let mut tags = Tags { source, offset: 0 };
+        let root = tags.next_tag()?.ok_or(SvgError::InvalidDocument)?;
+        if root.name != "svg" || root.closing {
+            return Err(SvgError::InvalidDocument);
+        }
+        let (origin, view_size) = view_box(&root)?;
+        let mapping = match size {
+            Some(size) => {
+                let scale = (size.width / view_size.width).min(size.height / view_size.height);
+                Mapping {
+                    origin,
+                    scale,
+                    offset: Point::new(
+                        (size.width - view_size.width * scale) / 2.,
+                        (size.height - view_size.height * scale) / 2.,
+                    ),
+                }
+            }
+            None => Mapping {
+                origin,
+                scale: 1.,
+                offset: Point::new(0., 0.),
+            },
+        };
+
+        let mut svg = Self {
+            size: {
+                let size = size.unwrap_or(view_size);
+                Size::new(Unit::from_float(size.width), Unit::from_float(size.height))
+            },
+            elements: Vec::new(),
+        };
+        let mut styles = vec![Style::default().apply(&root)?];
+        if root.self_closing {
+            return Ok(svg);
+        }
+        while let Some(tag) = tags.next_tag()? {
+            if tag.closing {
+                styles.pop();
+                if styles.is_empty() {
+                    break;
+                }
+                continue;
+            }
+
+            let parent = styles.last().ok_or(SvgError::InvalidDocument)?;
+            let style = parent.apply(&tag)?;
+            if !style.hidden {
+                if let Some(data) = shape_path_data(&tag)? {
+                    svg.push_element(&data, &style, &mapping)?;
+                }
+            }
+            if !tag.self_closing {
+                styles.push(style);
+            }
+        }
+
+        Ok(svg)
+    }
+
+    fn push_element(
+        &mut self,
+        data: &str,
+        style: &Style,
+        mapping: &Mapping,
+    ) -> Result<(), SvgError> {
+        let events = path_events(data)?;
+        if events.is_empty() {
+            return Ok(());
+        }
+        let path = map_events(events, |point| mapping.map(point).map(Unit::from_float));
+        let fill = style.fill.map(|paint| SvgFill {
+            paint,
+            opacity: style.opacity * style.fill_opacity,
+            rule: style.fill_rule,
+        });
+        let stroke = style
+            .stroke
+            .filter(|_| style.stroke_width > 0.)
+            .map(|paint| SvgStroke {
+                paint,
+                opacity: style.opacity * style.stroke_opacity,
+                options: StrokeOptions {
+                    color: Color::WHITE,
+                    line_width: Unit::from_float(style.stroke_width * mapping.scale),
+                    line_join: style.line_join,
+                    start_cap: style.line_cap,
+                    end_cap: style.line_cap,
+                    miter_limit: style.miter_limit,
+                    tolerance: StrokeOptions::<Unit>::DEFAULT_TOLERANCE,
+                },
+            });
+        if fill.is_some() || stroke.is_some() {
+            self.elements.push(SvgElement { path, fill, stroke });
+        }
+        Ok(())
+    }
+}
+
+impl<Unit> Svg<Unit> {
+    /// Returns the size of this image.
+    #[must_use]
+    pub const fn size(&self) -> Size<Unit>
+    where
+        Unit: Copy,
+    {
+        self.size
+    }
+
+    /// Returns the drawable elements of this image, in the order they should
+    /// be drawn.
+    #[must_use]
+    pub fn elements(&self) -> &[SvgElement<Unit>] {
+        &self.elements
+    }
+
+    /// Returns the tessellated shapes of this image, in the order they should
+    /// be drawn. Paints using `currentColor` are drawn using `current_color`.
+    #[must_use]
+    pub fn shapes(&self, current_color: Color) -> Vec<Shape<Unit, false>>
+    where
+        Unit: FloatConversion<Float = f32> + PixelScaling + Copy,
+    {
+        let mut shapes = Vec::new();
+        for element in &self.elements {
+            if let Some(fill) = &element.fill {
+                shapes.push(
+                    element
+                        .path
+                        .fill_with_rule(fill.color(current_color), fill.rule),
+                );
+            }
+            if let Some(stroke) = &element.stroke {
+                shapes.push(
+                    element
+                        .path
+                        .stroke(stroke.options.colored(stroke.color(current_color))),
+                );
+            }
+        }
+        shapes
+    }
+}
+
+/// A drawable element of an [`Svg`].
+#[derive(Debug, Clone)]
+pub struct SvgElement<Unit> {
+    /// The outline of this element.
+    pub path: Path<Unit, false>,
+    /// How this element is filled, if it is filled.
+    pub fill: Option<SvgFill>,
+    /// How this element is stroked, if it is stroked.
+    pub stroke: Option<SvgStroke<Unit>>,
+}
+
+/// The color of a fill or stroke in an [`Svg`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SvgPaint {
+    /// A specific color.
+    Color(Color),
+    /// The color provided when the image is drawn, specified using
+    /// `currentColor`.
+    CurrentColor,
+}
+
+impl SvgPaint {
+    /// Returns the color of this paint with `opacity` applied, using
+    /// `current_color` for [`SvgPaint::CurrentColor`].
+    #[must_use]
+    pub fn resolve(self, current_color: Color, opacity: f32) -> Color {
+        let color = match self {
+            SvgPaint::Color(color) => color,
+            SvgPaint::CurrentColor => current_color,
+        };
+        if opacity < 1. {
+            color.with_alpha_f32(color.alpha_f32() * opacity.max(0.))
+        } else {
+            color
+        }
+    }
+}
+
+/// How an [`SvgElement`] is filled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SvgFill {
+    /// The color of the fill.
+    pub paint: SvgPaint,
+    /// The opacity of the fill, ranging from `0.0` to `1.0`.
+    pub opacity: f32,
+    /// The rule used to determine which regions are inside of the path.
+    pub rule: FillRule,
+}
+
+impl SvgFill {
+    /// Returns the color of this fill, using `current_color` for
+    /// [`SvgPaint::CurrentColor`].
+    #[must_use]
+    pub fn color(&self, current_color: Color) -> Color {
+        self.paint.resolve(current_color, self.opacity)
+    }
+}
+
+/// How an [`SvgElement`] is stroked.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SvgStroke<Unit> {
+    /// The color of the stroke.
+    pub paint: SvgPaint,
+    /// The opacity of the stroke, ranging from `0.0` to `1.0`.
+    pub opacity: f32,
+    /// The options for stroking. The color of these options is not used.
+    pub options: StrokeOptions<Unit>,
+}
+
+impl<Unit> SvgStroke<Unit> {
+    /// Returns the color of this stroke, using `current_color` for
+    /// [`SvgPaint::CurrentColor`].
+    #[must_use]
+    pub fn color(&self, current_color: Color) -> Color {
+        self.paint.resolve(current_color, self.opacity)
+    }
+}
+
+/// An error parsing SVG data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SvgError {
+    /// The document is not well-formed or is not an SVG document.
+    InvalidDocument,
+    /// The document does not specify its size using either `viewBox` or
+    /// `width` and `height`.
+    MissingSize,
+    /// An attribute's value could not be parsed.
+    InvalidAttribute {
+        /// The name of the attribute.
+        name: String,
+        /// The attribute's value.
+        value: String,
+    },
+    /// Path data could not be parsed.
+    InvalidPathData {
+        /// The byte offset of the error within the path data.
+        offset: usize,
+    },
+    /// The document uses an unsupported feature.
+    Unsupported(&'static str),
+}
+
+impl Display for SvgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SvgError::InvalidDocument => f.write_str("invalid svg document"),
+            SvgError::MissingSize => f.write_str("svg document does not specify its size"),
+            SvgError::InvalidAttribute { name, value } => {
+                write!(f, "invalid value for attribute {name}: {value:?}")
+            }
+            SvgError::InvalidPathData { offset } => {
+                write!(f, "invalid path data at offset {offset}")
+            }
+            SvgError::Unsupported(feature) => write!(f, "unsupported svg feature: {feature}"),
+        }
+    }
+}
+
+impl std::error::Error for SvgError {}
+
+impl<Unit> Path<Unit, false>
+where
+    Unit: FloatConversion<Float = f32>,
+{
+    /// Returns a path parsed from SVG path data, such as the `d` attribute of
+    /// a `path` element.
+    ///
+    /// One unit in the path data is equal to one `Unit`. Elliptical arcs are
+    /// converted into cubic Bézier curves.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SvgError::InvalidPathData`] if `data` cannot be parsed.
+    pub fn from_svg_path(data: &str) -> Result<Self, SvgError> {
+        Ok(map_events(path_events(data)?, |point| {
+            point.map(Unit::from_float)
+        }))
+    }
+}
+
+struct Mapping {
+    origin: Point<f32>,
+    scale: f32,
+    offset: Point<f32>,
+}
+
+impl Mapping {
+    fn map(&self, point: Point<f32>) -> Point<f32> {
+        Point::new(
+            (point.x - self.origin.x) * self.scale + self.offset.x,
+            (point.y - self.origin.y) * self.scale + self.offset.y,
+        )
+    }
+}
+
+fn map_events<Unit>(
+    events: Vec<PathEvent<f32>>,
+    map: impl Fn(Point<f32>) -> Point<Unit>,
+) -> Path<Unit, false> {
+    let endpoint = |endpoint: Endpoint<f32>| Endpoint::new(map(endpoint.location), endpoint.color);
+    events
+        .into_iter()
+        .map(|event| match event {
+            PathEvent::Begin { at, texture } => PathEvent::Begin {
+                at: endpoint(at),
+                texture,
+            },
+            PathEvent::Line { to, texture } => PathEvent::Line {
+                to: endpoint(to),
+                texture,
+            },
+            PathEvent::Quadratic { ctrl, to, texture } => PathEvent::Quadratic {
+                ctrl: map(ctrl),
+                to: endpoint(to),
+                texture,
+            },
+            PathEvent::Cubic {
+                ctrl1,
+                ctrl2,
+                to,
+                texture,
+            } => PathEvent::Cubic {
+                ctrl1: map(ctrl1),
+                ctrl2: map(ctrl2),
+                to: endpoint(to),
+                texture,
+            },
+            PathEvent::End { close } => PathEvent::End { close },
+        })
+        .collect()
+}
+
+fn path_events(data: &str) -> Result<Vec<PathEvent<f32>>, SvgError> {
+    let mut parser = PathData {
+        data: data.as_bytes(),
+        offset: 0,
+    };
+    let mut events = EventBuilder {
+        events: Vec::new(),
+        current: Point::new(0., 0.),
+        start: Point::new(0., 0.),
+        open: false,
+        previous_control: None,
+    };
+    let mut command = None::<u8>;
+
+    loop {
+        parser.skip_separators();
+        let Some(&next) = parser.data.get(parser.offset) else {
+            break;
+        };
+        let next_command = if next.is_ascii_alphabetic() {
+            parser.offset += 1;
+            next
+        } else {
+            // Coordinates without a command repeat the previous command, with
+            // moves becoming lines.
+            match command {
+                Some(b'M') => b'L',
+                Some(b'm') => b'l',
+                Some(command) if !command.eq_ignore_ascii_case(&b'z') => command,
+                _ => return Err(parser.error()),
+            }
+        };
+        if command.is_none() && !next_command.eq_ignore_ascii_case(&b'm') {
+            return Err(parser.error());
+        }
+        command = Some(next_command);
+        events.command(next_command, &mut parser)?;
+    }
+
+    if events.open {
+        events.events.push(PathEvent::End { close: false });
+    }
+    Ok(events.events)
+}
+
+struct EventBuilder {
+    events: Vec<PathEvent<f32>>,
+    current: Point<f32>,
+    start: Point<f32>,
+    open: bool,
+    // The kind of curve and the last control point of the previous command,
+    // used by the smooth curve commands.
+    previous_control: Option<(u8, Point<f32>)>,
+}
+
+impl EventBuilder {
+    fn command(&mut self, command: u8, parser: &mut PathData<'_>) -> Result<(), SvgError> {
+        let origin = if command.is_ascii_lowercase() {
+            self.current
+        } else {
+            Point::new(0., 0.)
+        };
+        let command = command.to_ascii_uppercase();
+        if !self.open && !matches!(command, b'M' | b'Z') {
+            // Drawing after closing a subpath begins a new subpath at the
+            // current location.
+            self.events.push(PathEvent::Begin {
+                at: Endpoint::from(self.current),
+                texture: Point::ZERO,
+            });
+            self.open = true;
+        }
+
+        let mut control = None;
+        match command {
+            b'M' => {
+                let to = parser.point(origin)?;
+                if self.open {
+                    self.events.push(PathEvent::End { close: false });
+                }
+                self.events.push(PathEvent::Begin {
+                    at: Endpoint::from(to),
+                    texture: Point::ZERO,
+                });
+                self.open = true;
+                self.start = to;
+                self.current = to;
+            }
+            b'Z' => {
+                if self.open {
+                    self.events.push(PathEvent::End { close: true });
+                    self.open = false;
+                }
+                self.current = self.start;
+            }
+            b'L' => {
+                let to = parser.point(origin)?;
+                self.line_to(to);
+            }
+            b'H' => {
+                let to = Point::new(parser.number()? + origin.x, self.current.y);
+                self.line_to(to);
+            }
+            b'V' => {
+                let to = Point::new(self.current.x, parser.number()? + origin.y);
+                self.line_to(to);
+            }
+            b'C' | b'S' => {
+                let ctrl1 = if command == b'C' {
+                    parser.point(origin)?
+                } else {
+                    self.reflected_control(b'C')
+                };
+                let ctrl2 = parser.point(origin)?;
+                let to = parser.point(origin)?;
+                self.events.push(PathEvent::Cubic {
+                    ctrl1,
+                    ctrl2,
+                    to: Endpoint::from(to),
+                    texture: Point::ZERO,
+                });
+                control = Some((b'C', ctrl2));
+                self.current = to;
+            }
+            b'Q' | b'T' => {
+                let ctrl = if command == b'Q' {
+                    parser.point(origin)?
+                } else {
+                    self.reflected_control(b'Q')
+                };
+                let to = parser.point(origin)?;
+                self.events.push(PathEvent::Quadratic {
+                    ctrl,
+                    to: Endpoint::from(to),
+                    texture: Point::ZERO,
+                });
+                control = Some((b'Q', ctrl));
+                self.current = to;
+            }
+            b'A' => self.arc(parser, origin)?,
+            _ => return Err(parser.error()),
+        }
+        self.previous_control = control;
+        Ok(())
+    }
+
+    fn line_to(&mut self, to: Point<f32>) {
+        self.events.push(PathEvent::Line {
+            to: Endpoint::from(to),
+            texture: Point::ZERO,
+        });
+        self.current = to;
+    }
+
+    fn arc(&mut self, parser: &mut PathData<'_>, origin: Point<f32>) -> Result<(), SvgError> {
+        let radii = geom::vector(parser.number()?.abs(), parser.number()?.abs());
+        let x_rotation = geom::Angle::degrees(parser.number()?);
+        let flags = ArcFlags {
+            large_arc: parser.flag()?,
+            sweep: parser.flag()?,
+        };
+        let to = parser.point(origin)?;
+        let arc = SvgArc {
+            from: geom::point(self.current.x, self.current.y),
+            to: geom::point(to.x, to.y),
+            radii,
+            x_rotation,
+            flags,
+        };
+        if arc.is_straight_line() {
+            self.line_to(to);
+        } else {
+            arc.to_arc().for_each_cubic_bezier(&mut |segment| {
+                self.events.push(PathEvent::Cubic {
+                    ctrl1: Point::new(segment.ctrl1.x, segment.ctrl1.y),
+                    ctrl2: Point::new(segment.ctrl2.x, segment.ctrl2.y),
+                    to: Endpoint::from(Point::new(segment.to.x, segment.to.y)),
+                    texture: Point::ZERO,
+                });
+            });
+            self.current = to;
+        }
+        Ok(())
+    }
+
+    /// Returns the first control point of a smooth curve, which is the
+    /// reflection of the previous curve's last control point if it was the
+    /// same `kind` of curve.
+    fn reflected_control(&self, kind: u8) -> Point<f32> {
+        match self.previous_control {
+            Some((previous_kind, control)) if previous_kind == kind => Point::new(
+                self.current.x * 2. - control.x,
+                self.current.y * 2. - control.y,
+            ),
+            _ => self.current,
+        }
+    }
+}
+
+struct PathData<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl PathData<'_> {
+    fn error(&self) -> SvgError {
+        SvgError::InvalidPathData {
+            offset: self.offset,
+        }
+    }
+
+    fn skip_separators(&mut self) {
+        while self
+            .data
+            .get(self.offset)
+            .is_some_and(|ch| ch.is_ascii_whitespace() || *ch == b',')
+        {
+            self.offset += 1;
+        }
+    }
+
+    fn number(&mut self) -> Result<f32, SvgError> {
+        self.skip_separators();
+        let start = self.offset;
+        let digits = |parser: &mut Self| {
+            let start = parser.offset;
+            while parser
+                .data
+                .get(parser.offset)
+                .is_some_and(u8::is_ascii_digit)
+            {
+                parser.offset += 1;
+            }
+            parser.offset - start
+        };
+
+        if matches!(self.data.get(self.offset), Some(b'+' | b'-')) {
+            self.offset += 1;
+        }
+        let mut length = digits(self);
+        if self.data.get(self.offset) == Some(&b'.') {
+            self.offset += 1;
+            length += digits(self);
+        }
+        if length == 0 {
+            self.offset = start;
+            return Err(self.error());
+        }
+        if matches!(self.data.get(self.offset), Some(b'e' | b'E')) {
+            let mantissa_end = self.offset;
+            self.offset += 1;
+            if matches!(self.data.get(self.offset), Some(b'+' | b'-')) {
+                self.offset += 1;
+            }
+            if digits(self) == 0 {
+                // The `e` isn't an exponent.
+                self.offset = mantissa_end;
+            }
+        }
+
+        std::str::from_utf8(&self.data[start..self.offset])
+            .ok()
+            .and_then(|number| number.parse().ok())
+            .ok_or(SvgError::InvalidPathData { offset: start })
+    }
+
+    fn point(&mut self, origin: Point<f32>) -> Result<Point<f32>, SvgError> {
+        Ok(Point::new(
+            self.number()? + origin.x,
+            self.number()? + origin.y,
+        ))
+    }
+
+    fn flag(&mut self) -> Result<bool, SvgError> {
+        self.skip_separators();
+        let flag = match self.data.get(self.offset) {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return Err(self.error()),
+        };
+        self.offset += 1;
+        Ok(flag)
+    }
+}
+
+/// Returns path data describing the outline of `tag`, if it is a supported
+/// shape element.
+fn shape_path_data(tag: &Tag<'_>) -> Result<Option<String>, SvgError> {
+    let data = match tag.name {
+        "path" => tag.attribute("d").map(ToString::to_string),
+        "rect" => {
+            let (x, y) = (tag.length("x", 0.)?, tag.length("y", 0.)?);
+            let (width, height) = (tag.length("width", 0.)?, tag.length("height", 0.)?);
+            if width <= 0. || height <= 0. {
+                return Ok(None);
+            }
+            let rx = tag
+                .attribute("rx")
+                .map(|_| tag.length("rx", 0.))
+                .transpose()?;
+            let ry = tag
+                .attribute("ry")
+                .map(|_| tag.length("ry", 0.))
+                .transpose()?;
+            let rx = rx.or(ry).unwrap_or(0.).clamp(0., width / 2.);
+            let ry = ry.or(Some(rx)).unwrap_or(0.).clamp(0., height / 2.);
+            let (right, bottom) = (x + width, y + height);
+            Some(if rx > 0. && ry > 0. {
+                format!(
+                    "M{} {y}H{}A{rx} {ry} 0 0 1 {right} {}V{}A{rx} {ry} 0 0 1 {} {bottom}\
+                     H{}A{rx} {ry} 0 0 1 {x} {}V{}A{rx} {ry} 0 0 1 {} {y}Z",
+                    x + rx,
+                    right - rx,
+                    y + ry,
+                    bottom - ry,
+                    right - rx,
+                    x + rx,
+                    bottom - ry,
+                    y + ry,
+                    x + rx,
+                )
+            } else {
+                format!("M{x} {y}H{right}V{bottom}H{x}Z")
+            })
+        }
+        "circle" | "ellipse" => {
+            let (cx, cy) = (tag.length("cx", 0.)?, tag.length("cy", 0.)?);
+            let (rx, ry) = if tag.name == "circle" {
+                let r = tag.length("r", 0.)?;
+                (r, r)
+            } else {
+                (tag.length("rx", 0.)?, tag.length("ry", 0.)?)
+            };
+            if rx <= 0. || ry <= 0. {
+                return Ok(None);
+            }
+            Some(format!(
+                "M{} {cy}A{rx} {ry} 0 1 0 {} {cy}A{rx} {ry} 0 1 0 {} {cy}Z",
+                cx - rx,
+                cx + rx,
+                cx - rx,
+            ))
+        }
+        "line" => Some(format!(
+            "M{} {}L{} {}",
+            tag.length("x1", 0.)?,
+            tag.length("y1", 0.)?,
+            tag.length("x2", 0.)?,
+            tag.length("y2", 0.)?,
+        )),
+        "polyline" => tag.attribute("points").map(|points| format!("M{points}")),
+        "polygon" => tag.attribute("points").map(|points| format!("M{points}Z")),
+        _ => None,
+    };
+    Ok(data.filter(|data| !data.trim().is_empty()))
+}
+
+fn view_box(root: &Tag<'_>) -> Result<(Point<f32>, Size<f32>), SvgError> {
+    if let Some(value) = root.attribute("viewBox") {
+        let numbers = value
+            .split(|ch: char| ch.is_ascii_whitespace() || ch == ',')
+            .filter(|part| !part.is_empty())
+            .map(str::parse::<f32>)
+            .collect::<Result<Vec<_>, _>>();
+        match numbers.as_deref() {
+            Ok(&[x, y, width, height]) if width > 0. && height > 0. => {
+                Ok((Point::new(x, y), Size::new(width, height)))
+            }
+            _ => Err(invalid_attribute("viewBox", value)),
+        }
+    } else {
+        let width = root.length("width", 0.)?;
+        let height = root.length("height", 0.)?;
+        if width > 0. && height > 0. {
+            Ok((Point::new(0., 0.), Size::new(width, height)))
+        } else {
+            Err(SvgError::MissingSize)
+        }
+    }
+}
+
+fn invalid_attribute(name: &str, value: &str) -> SvgError {
+    SvgError::InvalidAttribute {
+        name: name.to_string(),
+        value: value.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Style {
+    hidden: bool,
+    fill: Option<SvgPaint>,
+    fill_rule: FillRule,
+    fill_opacity: f32,
+    stroke: Option<SvgPaint>,
+    stroke_width: f32,
+    stroke_opacity: f32,
+    line_cap: LineCap,
+    line_join: LineJoin,
+    miter_limit: f32,
+    opacity: f32,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            hidden: false,
+            fill: Some(SvgPaint::Color(Color::BLACK)),
+            fill_rule: FillRule::NonZero,
+            fill_opacity: 1.,
+            stroke: None,
+            stroke_width: 1.,
+            stroke_opacity: 1.,
+            line_cap: LineCap::Butt,
+            line_join: LineJoin::Miter,
+            miter_limit: 4.,
+            opacity: 1.,
+        }
+    }
+}
+
+impl Style {
+    /// Returns the style of `tag`, inheriting from this style.
+    fn apply(&self, tag: &Tag<'_>) -> Result<Self, SvgError> {
+        let mut style = *self;
+        if matches!(
+            tag.name,
+            "defs"
+                | "clipPath"
+                | "mask"
+                | "symbol"
+                | "pattern"
+                | "marker"
+                | "linearGradient"
+                | "radialGradient"
+                | "title"
+                | "desc"
+                | "metadata"
+                | "style"
+                | "script"
+                | "text"
+        ) {
+            style.hidden = true;
+        }
+        if tag.attribute("transform").is_some() {
+            return Err(SvgError::Unsupported("transform"));
+        }
+        for &(name, value) in &tag.attributes {
+            style.set(name, value)?;
+        }
+        // Properties in the style attribute take precedence over presentation
+        // attributes.
+        if let Some(declarations) = tag.attribute("style") {
+            for declaration in declarations.split(';') {
+                if let Some((name, value)) = declaration.split_once(':') {
+                    style.set(name.trim(), value.trim())?;
+                }
+            }
+        }
+        Ok(style)
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<(), SvgError> {
+        let number = || {
+            value
+                .trim()
+                .parse::<f32>()
+                .map_err(|_| invalid_attribute(name, value))
+        };
+        match name {
+            "fill" => self.fill = paint(name, value)?,
+            "stroke" => self.stroke = paint(name, value)?,
+            "fill-rule" => {
+                self.fill_rule = match value.trim() {
+                    "evenodd" => FillRule::EvenOdd,
+                    "nonzero" => FillRule::NonZero,
+                    _ => return Err(invalid_attribute(name, value)),
+                };
+            }
+            "fill-opacity" => self.fill_opacity = number()?,
+            "stroke-opacity" => self.stroke_opacity = number()?,
+            // Group opacity is approximated by applying it to each element.
+            "opacity" => self.opacity *= number()?,
+            "stroke-width" => {
+                self.stroke_width =
+                    parse_length(value).ok_or_else(|| invalid_attribute(name, value))?;
+            }
+            "stroke-miterlimit" => self.miter_limit = number()?,
+            "stroke-linecap" => {
+                self.line_cap = match value.trim() {
+                    "butt" => LineCap::Butt,
+                    "round" => LineCap::Round,
+                    "square" => LineCap::Square,
+                    _ => return Err(invalid_attribute(name, value)),
+                };
+            }
+            "stroke-linejoin" => {
+                self.line_join = match value.trim() {
+                    "miter" => LineJoin::Miter,
+                    "miter-clip" => LineJoin::MiterClip,
+                    "round" => LineJoin::Round,
+                    "bevel" => LineJoin::Bevel,
+                    _ => return Err(invalid_attribute(name, value)),
+                };
+            }
+            "display" if value.trim() == "none" => self.hidden = true,
+            "visibility" if value.trim() == "hidden" => self.hidden = true,
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+fn paint(name: &str, value: &str) -> Result<Option<SvgPaint>, SvgError> {
+    let value = value.trim();
+    if let Some(reference) = value.strip_prefix("url(") {
+        // Gradients and patterns aren't supported, but a fallback color may
+        // follow the reference.
+        let (_, fallback) = reference
+            .split_once(')')
+            .ok_or_else(|| invalid_attribute(name, value))?;
+        return if fallback.trim().is_empty() {
+            Ok(None)
+        } else {
+            paint(name, fallback)
+        };
+    }
+    match value {
+        "none" => Ok(None),
+        "currentColor" => Ok(Some(SvgPaint::CurrentColor)),
+        _ => parse_color(value)
+            .map(|color| Some(SvgPaint::Color(color)))
+            .ok_or_else(|| invalid_attribute(name, value)),
+    }
+}
+
+fn parse_color(value: &str) -> Option<Color> {
+    if let Some(hex) = value.strip_prefix('#') {
+        let digit = |index: usize| {
+            hex.get(index..=index)
+                .and_then(|digit| u8::from_str_radix(digit, 16).ok())
+        };
+        let channel = |index: usize| {
+            hex.get(index..index + 2)
+                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+        };
+        return match hex.len() {
+            3 | 4 => Some(Color::new(
+                digit(0)? * 17,
+                digit(1)? * 17,
+                digit(2)? * 17,
+                if hex.len() == 4 { digit(3)? * 17 } else { 255 },
+            )),
+            6 | 8 => Some(Color::new(
+                channel(0)?,
+                channel(2)?,
+                channel(4)?,
+                if hex.len() == 8 { channel(6)? } else { 255 },
+            )),
+            _ => None,
+        };
+    }
+    if let Some(arguments) = value
+        .strip_prefix("rgba(")
+        .or_else(|| value.strip_prefix("rgb("))
+    {
+        let arguments = arguments.strip_suffix(')')?;
+        let mut parts = arguments
+            .split(|ch: char| ch == ',' || ch.is_ascii_whitespace() || ch == '/')
+            .filter(|part| !part.is_empty());
+        let mut channel = || {
+            let part = parts.next()?;
+            let value = if let Some(percent) = part.strip_suffix('%') {
+                percent.parse::<f32>().ok()? / 100. * 255.
+            } else {
+                part.parse::<f32>().ok()?
+            };
+            Some(value.round().clamp(0., 255.))
+        };
+        let (red, green, blue) = (channel()?, channel()?, channel()?);
+        let alpha = parts
+            .next()
+            .map_or(Some(1.), |alpha| alpha.parse::<f32>().ok())?;
+        return Some(Color::new_f32(
+            red / 255.,
+            green / 255.,
+            blue / 255.,
+            alpha.clamp(0., 1.),
+        ));
+    }
+    match value.to_ascii_lowercase().as_str() {
+        "black" => Some(Color::BLACK),
+        "white" => Some(Color::WHITE),
+        "red" => Some(Color::RED),
+        "green" => Some(Color::GREEN),
+        "blue" => Some(Color::BLUE),
+        "yellow" => Some(Color::YELLOW),
+        "gray" | "grey" => Some(Color::GRAY),
+        "transparent" => Some(Color::CLEAR_WHITE),
+        _ => None,
+    }
+}
+
+/// Parses a length in user units, accepting an optional `px` suffix.
+fn parse_length(value: &str) -> Option<f32> {
+    let value = value.trim();
+    value
+        .strip_suffix("px")
+        .unwrap_or(value)
+        .trim()
+        .parse()
+        .ok()
+}
+
+struct Tag<'a> {
+    name: &'a str,
+    attributes: Vec<(&'a str, &'a str)>,
+    closing: bool,
+    self_closing: bool,
+}
+
+impl<'a> Tag<'a> {
+    fn attribute(&self, name: &str) -> Option<&'a str> {
+        self.attributes
+            .iter()
+            .find_map(|&(attribute, value)| (attribute == name).then_some(value))
+    }
+
+    fn length(&self, name: &str, default: f32) -> Result<f32, SvgError> {
+        self.attribute(name).map_or(Ok(default), |value| {
+            parse_length(value).ok_or_else(|| invalid_attribute(name, value))
+        })
+    }
+}
+
+/// A minimal scanner of the tags in an XML document.
+struct Tags<'a> {
+    source: &'a str,
+    offset: usize,
+}
+
+impl<'a> Tags<'a> {
+    fn skip_past(&mut self, terminator: &str) -> Result<(), SvgError> {
+        let end = self.source[self.offset..]
+            .find(terminator)
+            .ok_or(SvgError::InvalidDocument)?;
+        self.offset += end + terminator.len();
+        Ok(())
+    }
+
+    fn next_tag(&mut self) -> Result<Option<Tag<'a>>, SvgError> {
+        loop {
+            let Some(start) = self.source[self.offset..].find('<') else {
+                return Ok(None);
+            };
+            self.offset += start;
+            let rest = &self.source[self.offset..];
+            if rest.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if rest.starts_with("<![CDATA[") {
+                self.skip_past("]]>")?;
+            } else if rest.starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if rest.starts_with("<!") {
+                self.skip_past(">")?;
+            } else {
+                let mut quote = None;
+                let end = rest
+                    .char_indices()
+                    .find(|&(_, ch)| match quote {
+                        Some(open) => {
+                            if ch == open {
+                                quote = None;
+                            }
+                            false
+                        }
+                        None if ch == '"' || ch == '\'' => {
+                            quote = Some(ch);
+                            false
+                        }
+                        None => ch == '>',
+                    })
+                    .map(|(index, _)| index)
+                    .ok_or(SvgError::InvalidDocument)?;
+                self.offset += end + 1;
+                return parse_tag(&rest[1..end]).map(Some);
+            }
+        }
+    }
+}
+
+fn parse_tag(contents: &str) -> Result<Tag<'_>, SvgError> {
+    let (closing, contents) = match contents.strip_prefix('/') {
+        Some(contents) => (true, contents),
+        None => (false, contents),
+    };
+    let (self_closing, contents) = match contents.strip_suffix('/') {
+        Some(contents) => (true, contents),
+        None => (false, contents),
+    };
+    let contents = contents.trim();
+    let (name, mut rest) = contents
+        .split_once(|ch: char| ch.is_ascii_whitespace())
+        .unwrap_or((contents, ""));
+    if name.is_empty() {
+        return Err(SvgError::InvalidDocument);
+    }
+    // Namespace prefixes, such as `svg:path`, are ignored.
+    let name = name.rsplit(':').next().unwrap_or(name);
+
+    let mut attributes = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+        let (attribute, value) = rest.split_once('=').ok_or(SvgError::InvalidDocument)?;
+        let value = value.trim_start();
+        let quote = value.chars().next().ok_or(SvgError::InvalidDocument)?;
+        if quote != '"' && quote != '\'' {
+            return Err(SvgError::InvalidDocument);
+        }
+        let (value, remaining) = value[1..]
+            .split_once(quote)
+            .ok_or(SvgError::InvalidDocument)?;
+        attributes.push((attribute.trim(), value));
+        rest = remaining;
+    }
+
+    Ok(Tag {
+        name,
+        attributes,
+        closing,
+        self_closing,
+    })
+}
+
+#[test]
+fn svg_parsing() {
+    use figures::units::Px;
+
+    let path = Path::<Px, false>::from_svg_path("M10 10 h10 v10 l-10 0 z m5 5 L7 7").unwrap();
+    assert_eq!(path.events.len(), 8);
+    assert!(matches!(
+        path.events[3],
+        PathEvent::Line { to, .. } if to.location == Point::new(Px::new(10), Px::new(20))
+    ));
+    assert!(matches!(
+        path.events[6],
+        PathEvent::Line { to, .. } if to.location == Point::new(Px::new(7), Px::new(7))
+    ));
+    assert_eq!(
+        Path::<Px, false>::from_svg_path("M1 1 L2").unwrap_err(),
+        SvgError::InvalidPathData { offset: 7 }
+    );
+    assert_eq!(
+        Path::<Px, false>::from_svg_path("L1 1").unwrap_err(),
+        SvgError::InvalidPathData { offset: 1 }
+    );
+
+    let icon = Svg::<Px>::parse_with_size(
+        r##"<?xml version="1.0"?>
+        <!-- An icon -->
+        <svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 12 12" fill="none"
+            stroke="currentColor" stroke-width="2">
+            <defs><path id="unused" d="M0 0 L1 1"/></defs>
+            <circle cx="6" cy="6" r="5"/>
+            <rect x="2" y="2" width="8" height="8" rx="1" fill="#f00" stroke="none"/>
+            <g style="stroke: rgb(0, 0, 255); opacity: 0.5">
+                <line x1="0" y1="0" x2="12" y2="12"/>
+            </g>
+        </svg>"##,
+        Size::squared(Px::new(24)),
+    )
+    .unwrap();
+    assert_eq!(icon.size(), Size::squared(Px::new(24)));
+    let elements = icon.elements();
+    assert_eq!(elements.len(), 3);
+
+    let circle = &elements[0];
+    assert!(circle.fill.is_none());
+    let stroke = circle.stroke.unwrap();
+    assert_eq!(stroke.color(Color::WHITE), Color::WHITE);
+    assert_eq!(stroke.options.line_width, Px::new(4));
+
+    let rect = &elements[1];
+    assert_eq!(rect.fill.unwrap().color(Color::WHITE), Color::RED);
+    assert!(rect.stroke.is_none());
+
+    let line = &elements[2];
+    assert_eq!(
+        line.stroke.unwrap().color(Color::WHITE),
+        Color::new(0, 0, 255, 128)
+    );
+    assert!(matches!(
+        line.path.events[1],
+        PathEvent::Line { to, .. } if to.location == Point::new(Px::new(24), Px::new(24))
+    ));
+
+    assert_eq!(icon.shapes(Color::WHITE).len(), 3);
+    assert_eq!(
+        Svg::<Px>::parse(r#"<svg width="10" height="10"><g transform="scale(2)"/></svg>"#)
+            .unwrap_err(),
+        SvgError::Unsupported("transform")
+    );
+}
@@ -11,8 +11,10 @@ use figures::{Point, Rect, Size};
 use intentional::{Assert, Cast};
 use justjson::Value;
 
-use crate::pipeline::Vertex;
+use crate::pipeline::{ArrayTexture, Vertex};
 use crate::sealed::{self, TextureSource as _};
+#[cfg(feature = "image")]
+use crate::TextureCollection;
 use crate::{
     CanRenderTo, CollectedTexture, Graphics, Kludgine, PreparedGraphic, ShareableTexture,
     SharedTexture, TextureRegion, TextureSource,
@@ -38,6 +40,7 @@ macro_rules! include_aseprite_sprite {
 
 /// The animation mode of the sprite.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AnimationMode {
     /// Iterate frames in order. When at the end, reset to the start.
     Forward,
@@ -92,6 +95,8 @@ pub enum SpriteParseError {
     /// An image parsing error.
     #[cfg(feature = "image")]
     Image(image::ImageError),
+    /// An error loading the sprite's texture.
+    Texture(crate::Error),
 }
 
 impl SpriteParseError {
@@ -117,6 +122,16 @@ impl From<image::ImageError> for SpriteParseError {
     }
 }
 
+impl From<crate::Error> for SpriteParseError {
+    fn from(value: crate::Error) -> Self {
+        match value {
+            #[cfg(feature = "image")]
+            crate::Error::Image(err) => Self::Image(err),
+            other => Self::Texture(other),
+        }
+    }
+}
+
 /// An error parsing a single frame in a sprite animation.
 #[derive(Clone, Copy, Eq, PartialEq, Debug)]
 pub enum FrameParseError {
@@ -175,6 +190,15 @@ pub struct Sprite {
     current_tag: Option<String>,
     current_frame: usize,
     current_animation_direction: AnimationDirection,
+    queued_tag: Option<(Option<String>, Option<Duration>)>,
+    crossfade: Option<Crossfade>,
+}
+
+#[derive(Debug, Clone)]
+struct Crossfade {
+    outgoing: SpriteSource,
+    duration: Duration,
+    elapsed: Duration,
 }
 
 impl From<SpriteAnimations> for Sprite {
@@ -193,6 +217,8 @@ impl Sprite {
             current_tag: None,
             elapsed_since_frame_change: Duration::from_millis(0),
             current_animation_direction: AnimationDirection::Forward,
+            queued_tag: None,
+            crossfade: None,
         }
     }
 
@@ -399,10 +425,95 @@ impl Sprite {
         Ok(Self::new(SpriteAnimations::new(animations)))
     }
 
+    /// Loads an animated image's `frames` into `collection`, returning a
+    /// sprite with an untagged animation containing each frame.
+    ///
+    /// Frames are displayed for the delay specified by the image. Like web
+    /// browsers, delays shorter than 20 milliseconds are treated as 100
+    /// milliseconds, as many images were authored expecting this behavior.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a frame cannot be decoded or added to
+    /// `collection`.
+    #[cfg(feature = "image")]
+    pub fn load_animation(
+        frames: impl IntoIterator<Item = image::ImageResult<image::Frame>>,
+        collection: &mut TextureCollection,
+        graphics: &Graphics<'_>,
+    ) -> Result<Self, SpriteParseError> {
+        const MINIMUM_DELAY: Duration = Duration::from_millis(20);
+        const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+
+        let mut sprite_frames = Vec::new();
+        for frame in frames {
+            let frame = frame?;
+            let delay = Duration::from(frame.delay());
+            let delay = if delay < MINIMUM_DELAY {
+                DEFAULT_DELAY
+            } else {
+                delay
+            };
+            let texture = collection
+                .try_push_image(&image::DynamicImage::from(frame.into_buffer()), graphics)?;
+            sprite_frames.push(SpriteFrame::new(texture).with_duration(delay));
+        }
+
+        let mut animations = HashMap::new();
+        animations.insert(None, SpriteAnimation::new(sprite_frames));
+        Ok(Self::new(SpriteAnimations::new(animations)))
+    }
+
+    /// Loads an animated GIF from `data` into `collection`.
+    ///
+    /// See [`Sprite::load_animation()`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is not a valid GIF or a frame cannot be
+    /// added to `collection`.
+    #[cfg(feature = "gif")]
+    pub fn load_gif(
+        data: &[u8],
+        collection: &mut TextureCollection,
+        graphics: &Graphics<'_>,
+    ) -> Result<Self, SpriteParseError> {
+        use image::AnimationDecoder;
+
+        let decoder = image::codecs::gif::GifDecoder::new(std::io::Cursor::new(data))?;
+        Self::load_animation(decoder.into_frames(), collection, graphics)
+    }
+
+    /// Loads an animated PNG from `data` into `collection`.
+    ///
+    /// See [`Sprite::load_animation()`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is not a valid PNG or a frame cannot be
+    /// added to `collection`.
+    #[cfg(feature = "apng")]
+    pub fn load_apng(
+        data: &[u8],
+        collection: &mut TextureCollection,
+        graphics: &Graphics<'_>,
+    ) -> Result<Self, SpriteParseError> {
+        use image::AnimationDecoder;
+
+        let decoder = image::codecs::png::PngDecoder::new(std::io::Cursor::new(data))?;
+        Self::load_animation(decoder.apng()?.into_frames(), collection, graphics)
+    }
+
     /// Sets the current tag for the animation. If the tag currently matches,
     /// nothing will happen. If it is a new tag, the current frame and animation
     /// direction will be switched to the values from the new tag.
     ///
+    /// The switch honors the [`SpriteTransition`] configured in
+    /// [`SpriteAnimations`] for the current and new tags. When the transition
+    /// uses [`TransitionTiming::AfterLoop`], the new tag is
+    /// [queued](Self::queued_tag) until the current animation completes its
+    /// loop. Setting the current tag again cancels a queued tag.
+    ///
     /// # Errors
     ///
     /// Returns an error if `tag` is not a valid animation tag.
@@ -411,17 +522,23 @@ impl Sprite {
         tag: Option<S>,
     ) -> Result<(), InvalidSpriteTag> {
         let new_tag = tag.map(Into::into);
-        if self.current_tag != new_tag {
-            self.current_animation_direction = {
-                let animation = self
-                    .animations
-                    .animations
-                    .get(&new_tag)
-                    .ok_or(InvalidSpriteTag)?;
-                animation.mode.default_direction()
-            };
-            self.current_frame = 0;
-            self.current_tag = new_tag;
+        if self.current_tag == new_tag {
+            self.queued_tag = None;
+            return Ok(());
+        }
+        if !self.animations.animations.contains_key(&new_tag) {
+            return Err(InvalidSpriteTag);
+        }
+
+        let transition = self.animations.transition(&self.current_tag, &new_tag);
+        // Animations that are stuck on a frame without a duration never
+        // complete their loop.
+        let looping = self.remaining_frame_duration().ok().flatten().is_some();
+        if transition.timing == TransitionTiming::AfterLoop && looping {
+            self.queued_tag = Some((new_tag, transition.crossfade));
+        } else {
+            self.queued_tag = None;
+            self.switch_to(new_tag, transition.crossfade)?;
         }
 
         Ok(())
@@ -433,6 +550,51 @@ impl Sprite {
         self.current_tag.as_deref()
     }
 
+    /// Returns the tag that will become the current tag once the current
+    /// animation completes its loop, if one has been queued by
+    /// [`set_current_tag()`](Self::set_current_tag).
+    #[must_use]
+    #[allow(clippy::option_option)]
+    pub fn queued_tag(&self) -> Option<Option<&'_ str>> {
+        self.queued_tag.as_ref().map(|(tag, _)| tag.as_deref())
+    }
+
+    /// Returns the crossfade from the previous animation, if a transition
+    /// with a [crossfade](SpriteTransition::crossfade) is in progress.
+    #[must_use]
+    pub fn crossfade(&self) -> Option<SpriteCrossfade> {
+        self.crossfade.as_ref().map(|crossfade| SpriteCrossfade {
+            outgoing: crossfade.outgoing.clone(),
+            progress: (crossfade.elapsed.as_secs_f32() / crossfade.duration.as_secs_f32()).min(1.),
+        })
+    }
+
+    fn switch_to(
+        &mut self,
+        tag: Option<String>,
+        crossfade: Option<Duration>,
+    ) -> Result<(), InvalidSpriteTag> {
+        self.current_animation_direction = self
+            .animations
+            .animations
+            .get(&tag)
+            .ok_or(InvalidSpriteTag)?
+            .mode
+            .default_direction();
+        self.crossfade = crossfade
+            .filter(|duration| !duration.is_zero())
+            .and_then(|duration| {
+                Some(Crossfade {
+                    outgoing: self.current_frame().ok()?,
+                    duration,
+                    elapsed: Duration::ZERO,
+                })
+            });
+        self.current_frame = 0;
+        self.current_tag = tag;
+        Ok(())
+    }
+
     /// Gets the current frame after advancing the animation for `elapsed`
     /// duration. If you need to invoke this multiple times in a single frame,
     /// pass `None` on subsequent calls. In general, you should clone sprites
@@ -448,6 +610,12 @@ impl Sprite {
         elapsed: Option<Duration>,
     ) -> Result<SpriteSource, InvalidSpriteTag> {
         if let Some(elapsed) = elapsed {
+            if let Some(crossfade) = &mut self.crossfade {
+                crossfade.elapsed += elapsed;
+                if crossfade.elapsed >= crossfade.duration {
+                    self.crossfade = None;
+                }
+            }
             self.elapsed_since_frame_change += elapsed;
 
             let current_frame_duration = self.with_current_frame(|frame| frame.duration)?;
@@ -497,12 +665,19 @@ impl Sprite {
     }
 
     fn advance_frame(&mut self) -> Result<(), InvalidSpriteTag> {
-        self.current_frame = self.next_frame()?;
+        let (next_frame, looped) = self.next_frame()?;
+        if looped {
+            if let Some((tag, crossfade)) = self.queued_tag.take() {
+                return self.switch_to(tag, crossfade);
+            }
+        }
+        self.current_frame = next_frame;
         Ok(())
     }
 
+    /// Returns the next frame, and whether the animation completed its loop.
     #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
-    fn next_frame(&mut self) -> Result<usize, InvalidSpriteTag> {
+    fn next_frame(&mut self) -> Result<(usize, bool), InvalidSpriteTag> {
         let starting_frame = self.current_frame.cast::<i32>();
         let animation = self
             .animations
@@ -520,24 +695,24 @@ impl Sprite {
                 AnimationMode::Forward => unreachable!(),
                 AnimationMode::Reverse => {
                     // Cycle back to the last frame
-                    animation.frames.len() - 1
+                    (animation.frames.len() - 1, true)
                 }
                 AnimationMode::PingPong => {
                     self.current_animation_direction = AnimationDirection::Forward;
-                    1
+                    (1, true)
                 }
             }
         } else if next_frame as usize >= animation.frames.len() {
             match animation.mode {
                 AnimationMode::Reverse => unreachable!(),
-                AnimationMode::Forward => 0,
+                AnimationMode::Forward => (0, true),
                 AnimationMode::PingPong => {
                     self.current_animation_direction = AnimationDirection::Reverse;
-                    (animation.frames.len() - 2).max(0)
+                    ((animation.frames.len() - 2).max(0), false)
                 }
             }
         } else {
-            next_frame as usize
+            (next_frame as usize, false)
         })
     }
 
@@ -561,6 +736,7 @@ impl Sprite {
 #[derive(Debug, Clone)]
 pub struct SpriteAnimations {
     animations: Arc<HashMap<Option<String>, SpriteAnimation>>,
+    transitions: Arc<Vec<(TransitionTag, TransitionTag, SpriteTransition)>>,
 }
 
 impl SpriteAnimations {
@@ -569,6 +745,7 @@ impl SpriteAnimations {
     pub fn new(animations: HashMap<Option<String>, SpriteAnimation>) -> Self {
         Self {
             animations: Arc::new(animations),
+            transitions: Arc::default(),
         }
     }
 
@@ -577,6 +754,169 @@ impl SpriteAnimations {
     pub fn animation_for(&self, tag: &Option<impl ToString>) -> Option<&'_ SpriteAnimation> {
         self.animations.get(&tag.as_ref().map(ToString::to_string))
     }
+
+    /// Builder-style function. Uses `transition` when a [`Sprite`] switches
+    /// from an animation matching `from` to an animation matching `to`, and
+    /// returns self.
+    ///
+    /// When multiple transitions match, the transition that matches the most
+    /// specific tags is used, preferring transitions added later. Switches
+    /// that match no transitions happen immediately without a crossfade.
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use kludgine::sprite::{SpriteAnimations, SpriteTransition, TransitionTag};
+    ///
+    /// fn with_rules(animations: SpriteAnimations) -> SpriteAnimations {
+    ///     animations
+    ///         .with_transition("Walk", "Idle", SpriteTransition::after_loop())
+    ///         .with_transition(
+    ///             TransitionTag::Any,
+    ///             "Hurt",
+    ///             SpriteTransition::immediate().crossfade(Duration::from_millis(100)),
+    ///         )
+    /// }
+    /// ```
+    #[must_use]
+    pub fn with_transition(
+        mut self,
+        from: impl Into<TransitionTag>,
+        to: impl Into<TransitionTag>,
+        transition: SpriteTransition,
+    ) -> Self {
+        Arc::make_mut(&mut self.transitions).push((from.into(), to.into(), transition));
+        self
+    }
+
+    /// Returns the transition used when switching from the animation tagged
+    /// `from` to the animation tagged `to`.
+    #[must_use]
+    pub fn transition(&self, from: &Option<String>, to: &Option<String>) -> SpriteTransition {
+        self.transitions
+            .iter()
+            .filter_map(|(rule_from, rule_to, transition)| {
+                Some((
+                    rule_from.specificity(from)? + rule_to.specificity(to)?,
+                    *transition,
+                ))
+            })
+            .max_by_key(|(specificity, _)| *specificity)
+            .map(|(_, transition)| transition)
+            .unwrap_or_default()
+    }
+}
+
+/// A tag pattern used to match animations when defining a
+/// [`SpriteTransition`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransitionTag {
+    /// Matches any animation.
+    Any,
+    /// Matches the animation without a tag.
+    Untagged,
+    /// Matches the animation with this tag.
+    Tagged(String),
+}
+
+impl TransitionTag {
+    fn specificity(&self, tag: &Option<String>) -> Option<u8> {
+        match (self, tag) {
+            (TransitionTag::Any, _) => Some(0),
+            (TransitionTag::Untagged, None) => Some(1),
+            (TransitionTag::Tagged(expected), Some(tag)) if expected == tag => Some(1),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for TransitionTag {
+    fn from(tag: &str) -> Self {
+        Self::Tagged(tag.to_string())
+    }
+}
+
+impl From<String> for TransitionTag {
+    fn from(tag: String) -> Self {
+        Self::Tagged(tag)
+    }
+}
+
+impl From<Option<String>> for TransitionTag {
+    fn from(tag: Option<String>) -> Self {
+        tag.map_or(Self::Untagged, Self::Tagged)
+    }
+}
+
+/// Controls how a [`Sprite`] switches between two animations.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpriteTransition {
+    /// When the switch happens.
+    pub timing: TransitionTiming,
+    /// The duration to crossfade from the previous animation's frame, if any.
+    ///
+    /// During the crossfade, [`Sprite::crossfade()`] returns the previous
+    /// animation's last frame and the progress of the crossfade.
+    pub crossfade: Option<Duration>,
+}
+
+impl SpriteTransition {
+    /// Returns a transition that switches animations immediately.
+    #[must_use]
+    pub const fn immediate() -> Self {
+        Self {
+            timing: TransitionTiming::Immediate,
+            crossfade: None,
+        }
+    }
+
+    /// Returns a transition that switches animations after the current
+    /// animation completes its loop.
+    #[must_use]
+    pub const fn after_loop() -> Self {
+        Self {
+            timing: TransitionTiming::AfterLoop,
+            crossfade: None,
+        }
+    }
+
+    /// Builder-style function. Crossfades from the previous animation over
+    /// `duration` and returns self.
+    #[must_use]
+    pub const fn crossfade(mut self, duration: Duration) -> Self {
+        self.crossfade = Some(duration);
+        self
+    }
+}
+
+/// When a [`SpriteTransition`] switches animations.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransitionTiming {
+    /// The animation switches as soon as the tag is set.
+    #[default]
+    Immediate,
+    /// The animation switches once the current animation returns to its
+    /// starting frame. [`AnimationMode::PingPong`] animations complete their
+    /// loop after playing forwards and backwards.
+    ///
+    /// If the current frame has no duration, the animation switches
+    /// immediately.
+    AfterLoop,
+}
+
+/// A crossfade in progress between two of a [`Sprite`]'s animations.
+///
+/// To render the crossfade, draw [`outgoing`](Self::outgoing) with an opacity
+/// of `1.0 - progress` and the current frame with an opacity of `progress`.
+#[derive(Debug, Clone)]
+pub struct SpriteCrossfade {
+    /// The last frame shown of the previous animation.
+    pub outgoing: SpriteSource,
+    /// The progress of the crossfade, ranging from `0.0` to `1.0`.
+    pub progress: f32,
 }
 
 /// An animation of one or more [`SpriteFrame`]s.
@@ -930,12 +1270,29 @@ impl sealed::TextureSource for SpriteSource {
         }
     }
 
+    fn array_texture(
+        &self,
+        graphics: &impl crate::sealed::KludgineGraphics,
+    ) -> Option<ArrayTexture> {
+        match self {
+            SpriteSource::Region(texture) => texture.array_texture(graphics),
+            SpriteSource::Collected(texture) => texture.array_texture(graphics),
+        }
+    }
+
     fn default_rect(&self) -> Rect<UPx> {
         match self {
             SpriteSource::Region(texture) => texture.default_rect(),
             SpriteSource::Collected(texture) => texture.default_rect(),
         }
     }
+
+    fn uv_rect(&self) -> Rect<UPx> {
+        match self {
+            SpriteSource::Region(texture) => texture.uv_rect(),
+            SpriteSource::Collected(texture) => texture.uv_rect(),
+        }
+    }
 }
 
 impl From<TextureRegion> for SpriteSource {
@@ -981,3 +1338,57 @@ where
         self.sprites.get(tile).cloned()
     }
 }
+
+#[test]
+fn sprite_transitions() {
+    let texture = TextureRegion::from(crate::LazyTexture::from_data(
+        Size::squared(UPx::new(1)),
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        wgpu::TextureUsages::TEXTURE_BINDING,
+        wgpu::FilterMode::Nearest,
+        vec![0; 4],
+    ));
+    let frame = SpriteFrame::new(texture).with_duration(Duration::from_millis(100));
+    let animation = SpriteAnimation::new(vec![frame.clone(), frame]);
+    let animations = SpriteAnimations::new(
+        ["Walk", "Idle", "Hurt"]
+            .into_iter()
+            .map(|tag| (Some(tag.to_string()), animation.clone()))
+            .collect(),
+    )
+    .with_transition("Walk", "Idle", SpriteTransition::after_loop())
+    .with_transition(
+        TransitionTag::Any,
+        "Hurt",
+        SpriteTransition::immediate().crossfade(Duration::from_millis(100)),
+    );
+    assert_eq!(
+        animations.transition(&Some(String::from("Idle")), &Some(String::from("Walk"))),
+        SpriteTransition::default()
+    );
+
+    let mut sprite = Sprite::new(animations);
+    sprite.set_current_tag(Some("Walk")).unwrap();
+    sprite.set_current_tag(Some("Idle")).unwrap();
+    assert_eq!(sprite.current_tag(), Some("Walk"));
+    assert_eq!(sprite.queued_tag(), Some(Some("Idle")));
+
+    // The switch happens once the walk animation wraps around.
+    sprite.get_frame(Some(Duration::from_millis(150))).unwrap();
+    assert_eq!(sprite.current_tag(), Some("Walk"));
+    sprite.get_frame(Some(Duration::from_millis(100))).unwrap();
+    assert_eq!(sprite.current_tag(), Some("Idle"));
+    assert_eq!(sprite.queued_tag(), None);
+    assert!(sprite.crossfade().is_none());
+
+    // Any animation switches to "Hurt" immediately, crossfading.
+    sprite.set_current_tag(Some("Hurt")).unwrap();
+    assert_eq!(sprite.current_tag(), Some("Hurt"));
+    sprite.get_frame(Some(Duration::from_millis(50))).unwrap();
+    let crossfade = sprite.crossfade().unwrap();
+    assert!((crossfade.progress - 0.5).abs() < f32::EPSILON);
+    sprite.get_frame(Some(Duration::from_millis(50))).unwrap();
+    assert!(sprite.crossfade().is_none());
+
+    assert_eq!(sprite.set_current_tag(Some("Run")), Err(InvalidSpriteTag));
+}
@@ -1,7 +1,9 @@
 use std::array;
+use std::borrow::Borrow;
 use std::collections::{hash_map, HashMap};
 use std::fmt::{self, Debug};
-use std::sync::{Arc, Mutex, PoisonError, Weak};
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, Weak};
 
 use cosmic_text::{Align, Attrs, AttrsOwned, LayoutGlyph, SwashContent};
 use figures::units::{Lp, Px, UPx};
@@ -12,11 +14,14 @@ use intentional::Cast;
 use smallvec::SmallVec;
 
 use crate::buffer::Buffer;
-use crate::pipeline::PreparedCommand;
-use crate::sealed::{ShapeSource, TextureSource};
+use crate::drawing::RenderStats;
+use crate::pipeline::{vertex_bounds, PreparedCommand};
+use crate::sealed::{ShapeSource, TextureId, TextureSource};
+use crate::shapes::{self, Endpoint, Path, PathEvent};
 use crate::{
-    Assert, CanRenderTo, CollectedTexture, Color, DefaultHasher, DrawableSource, Graphics,
-    Kludgine, PreparedGraphic, ProtoGraphics, TextureBlit, TextureCollection, VertexCollection,
+    Assert, AtlasOptions, CanRenderTo, CollectedTexture, Color, DefaultHasher, DrawableSource,
+    Error, Graphics, Kludgine, PreparedGraphic, ProtoGraphics, Texture, TextureBlit,
+    TextureCollection, VertexCollection,
 };
 
 impl Kludgine {
@@ -40,14 +45,27 @@ impl Kludgine {
         self.text.fonts = cosmic_text::FontSystem::new_with_locale_and_db(locale, db);
     }
 
-    pub(crate) fn update_scratch_buffer(
-        &mut self,
-        text: &str,
-        width: Option<Px>,
-        align: Option<Align>,
-    ) {
-        self.text
-            .update_scratch_buffer(text, self.effective_scale, width, align);
+    pub(crate) fn update_scratch_buffer<Unit>(&mut self, text: &Text<'_, Unit>)
+    where
+        Unit: figures::ScreenUnit,
+    {
+        self.text.update_scratch_buffer(text, self.effective_scale);
+    }
+
+    /// Measures `text` using the current text settings.
+    ///
+    /// Unlike [`Renderer::measure_text()`](crate::drawing::Renderer::measure_text),
+    /// this function does not require a graphics device. Each glyph is
+    /// uploaded to the GPU the first time the measured text is drawn using
+    /// [`Renderer::draw_measured_text()`](crate::drawing::Renderer::draw_measured_text).
+    ///
+    /// The measured text keeps the shaped text, which allows it to be
+    /// [rewrapped](MeasuredText::rewrap) without shaping it again.
+    pub fn measure_text<'a, Unit>(&mut self, text: impl Into<Text<'a, Unit>>) -> MeasuredText<Unit>
+    where
+        Unit: figures::ScreenUnit,
+    {
+        self.text.measure(&text.into(), self.effective_scale)
     }
 
     /// Sets the font size.
@@ -116,6 +134,92 @@ impl Kludgine {
         self.text.attrs.stretch
     }
 
+    /// Sets the distance between tab stops.
+    ///
+    /// Tab characters advance text to the next tab stop, allowing columns of
+    /// text to be aligned.
+    pub fn set_tab_width(&mut self, width: TabWidth) {
+        self.text.tab_width = width;
+    }
+
+    /// Returns the current distance between tab stops.
+    pub const fn tab_width(&self) -> TabWidth {
+        self.text.tab_width
+    }
+
+    /// Sets the options used when rasterizing glyphs.
+    ///
+    /// Changing these options causes all glyphs to be rasterized again the
+    /// next time they are drawn.
+    pub fn set_glyph_rasterization(&mut self, rasterization: GlyphRasterization) {
+        if self.text.rasterization != rasterization {
+            self.text.rasterization = rasterization;
+            self.text.swash_cache.image_cache.clear();
+            self.glyph_atlas.rasterization_changed();
+        }
+    }
+
+    /// Returns the options used when rasterizing glyphs.
+    pub const fn glyph_rasterization(&self) -> GlyphRasterization {
+        self.text.rasterization
+    }
+
+    /// Sets the number of pixels that cached glyphs may occupy in the text
+    /// atlases before unused glyphs are evicted.
+    ///
+    /// Rasterized glyphs are cached so that they can be drawn again without
+    /// rasterizing them. When a new frame begins and the cached glyphs occupy
+    /// more than `pixels`, the least recently used glyphs that are not being
+    /// drawn are evicted until the cache fits within the budget. Evicted
+    /// glyphs are rasterized again when they are next drawn.
+    ///
+    /// Glyphs that are in use are never evicted, which allows the cache to
+    /// temporarily exceed its budget. A budget of 0 evicts every unused glyph
+    /// each frame, and [`u64::MAX`] never evicts glyphs. The default budget is
+    /// 4,194,304 pixels, the area of a 2,048 by 2,048 texture.
+    ///
+    /// The number of glyphs rasterized and evicted each frame are reported in
+    /// [`Kludgine::last_frame_stats()`].
+    pub fn set_glyph_atlas_budget(&mut self, pixels: u64) {
+        self.glyph_atlas.budget = pixels;
+    }
+
+    /// Returns the number of pixels that cached glyphs may occupy in the text
+    /// atlases before unused glyphs are evicted.
+    ///
+    /// See [`set_glyph_atlas_budget()`](Self::set_glyph_atlas_budget) for more
+    /// information.
+    pub const fn glyph_atlas_budget(&self) -> u64 {
+        self.glyph_atlas.budget
+    }
+
+    /// Returns this instance's glyph atlas, allowing other instances to share
+    /// it using [`set_glyph_atlas()`](Self::set_glyph_atlas).
+    ///
+    /// The first time this is called, this instance's cached glyphs are
+    /// discarded, as shared atlases identify fonts in a way that does not
+    /// depend on this instance's font system.
+    pub fn shared_glyph_atlas(&mut self) -> SharedGlyphAtlas {
+        self.glyph_atlas.share()
+    }
+
+    /// Caches glyphs drawn by this instance in `atlas`.
+    ///
+    /// Instances sharing an atlas rasterize and upload each glyph only once,
+    /// and glyphs drawn by any of the instances can be drawn by the others.
+    /// Each instance evicts glyphs from the atlas according to its own
+    /// [budget](Self::set_glyph_atlas_budget) as it begins each frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::DeviceMismatch`] if `atlas` was created using a
+    /// different [`wgpu::Device`] than this instance renders with. Each
+    /// window of a running [`App`](crate::app::App) creates its own device,
+    /// so their atlases cannot be shared.
+    pub fn set_glyph_atlas(&mut self, atlas: &SharedGlyphAtlas) -> Result<(), Error> {
+        self.glyph_atlas.use_shared(atlas)
+    }
+
     /// Returns the current text attributes.
     pub fn text_attrs(&self) -> cosmic_text::Attrs<'_> {
         self.text.attrs.as_attrs()
@@ -131,19 +235,111 @@ impl Kludgine {
         self.set_text_attributes(Attrs::new());
         self.text.font_size = DEFAULT_FONT_SIZE;
         self.text.line_height = DEFAULT_LINE_SIZE;
+        self.text.tab_width = TabWidth::default();
+    }
+}
+
+/// The distance between tab stops when laying out text.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TabWidth {
+    /// Tab stops are placed every this many spaces of the current font.
+    Spaces(u16),
+    /// Tab stops are placed every this distance.
+    ///
+    /// Tab stops are always a whole number of spaces apart, so this width is
+    /// rounded to the nearest multiple of the current font's space width.
+    Width(Lp),
+}
+
+impl Default for TabWidth {
+    fn default() -> Self {
+        Self::Spaces(8)
+    }
+}
+
+/// Options that control how glyphs are rasterized.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GlyphRasterization {
+    /// The horizontal positions within a pixel that glyphs are rasterized at.
+    pub subpixel_positioning: SubpixelPositioning,
+    /// If true, glyph outlines are adjusted to align with the pixel grid.
+    pub hinting: bool,
+    /// The gamma applied to glyph coverage when blending.
+    ///
+    /// Each glyph's coverage is raised to the power of `1 / gamma`. Values
+    /// greater than 1.0 produce heavier text, and values less than 1.0 produce
+    /// lighter text.
+    pub gamma: f32,
+}
+
+impl GlyphRasterization {
+    fn apply_gamma(&self, coverage: &[u8]) -> Option<Vec<u8>> {
+        if (self.gamma - 1.).abs() < f32::EPSILON || self.gamma <= 0. {
+            return None;
+        }
+        let exponent = self.gamma.recip();
+        let table: [u8; 256] = array::from_fn(|value| {
+            (value.cast::<f32>() / 255.)
+                .powf(exponent)
+                .mul_add(255., 0.5)
+                .cast::<u8>()
+        });
+        Some(
+            coverage
+                .iter()
+                .map(|value| table[usize::from(*value)])
+                .collect(),
+        )
+    }
+}
+
+impl Default for GlyphRasterization {
+    fn default() -> Self {
+        Self {
+            subpixel_positioning: SubpixelPositioning::default(),
+            hinting: true,
+            gamma: 1.,
+        }
+    }
+}
+
+/// The horizontal positions within a pixel that glyphs are rasterized at.
+///
+/// Each position a glyph is drawn at is cached separately. Fewer positions
+/// allow more glyphs to share cached rasterizations at the expense of less
+/// accurate glyph placement.
+#[derive(Default, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SubpixelPositioning {
+    /// Glyphs are always placed on whole pixels.
+    Disabled,
+    /// Glyphs are placed on whole or half pixels.
+    Halves,
+    /// Glyphs are placed on quarter pixels.
+    #[default]
+    Quarters,
+}
+
+impl SubpixelPositioning {
+    fn quantize(self, x: f32) -> f32 {
+        match self {
+            Self::Disabled => x.round(),
+            Self::Halves => (x * 2.).round() / 2.,
+            Self::Quarters => x,
+        }
     }
 }
 
 pub(crate) struct TextSystem {
     pub fonts: cosmic_text::FontSystem,
     pub swash_cache: cosmic_text::SwashCache,
-    pub alpha_text_atlas: TextureCollection,
-    pub color_text_atlas: TextureCollection,
     pub scratch: Option<cosmic_text::Buffer>,
+    pub scratch_truncated: bool,
     pub font_size: Lp,
     pub line_height: Lp,
+    pub tab_width: TabWidth,
+    pub rasterization: GlyphRasterization,
     pub attrs: AttrsOwned,
-    glyphs: GlyphCache,
+    scale_context: swash::scale::ScaleContext,
 }
 
 impl Debug for TextSystem {
@@ -151,46 +347,312 @@ impl Debug for TextSystem {
         f.debug_struct("TextSystem")
             .field("font_size", &self.font_size)
             .field("line_height", &self.line_height)
+            .field("tab_width", &self.tab_width)
+            .field("rasterization", &self.rasterization)
             .field("attrs", &self.attrs)
-            .field("glyphs", &self.glyphs)
             .finish_non_exhaustive()
     }
 }
 
+/// The textures that rasterized glyphs are cached in on the GPU.
+pub(crate) struct GlyphAtlas {
+    data: SharedGlyphAtlas,
+    /// The device this instance renders with.
+    device: wgpu::Id<wgpu::Device>,
+    /// If true, `data` may be used by other [`Kludgine`] instances.
+    shared: bool,
+    budget: u64,
+    rasterized: usize,
+    /// The number of glyphs that had been evicted from `data` when this
+    /// instance last removed unused images from its swash cache.
+    evictions_seen: usize,
+    /// Identifies the fonts in this instance's font system across font
+    /// systems, allowing glyphs to be shared with other instances.
+    font_fingerprints: HashMap<cosmic_text::fontdb::ID, u64, DefaultHasher>,
+}
+
+impl Debug for GlyphAtlas {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlyphAtlas")
+            .field("data", &self.data)
+            .field("shared", &self.shared)
+            .field("budget", &self.budget)
+            .finish_non_exhaustive()
+    }
+}
+
+impl GlyphAtlas {
+    pub(crate) fn new(graphics: &ProtoGraphics<'_>) -> Self {
+        let device = graphics.device.global_id();
+        Self {
+            data: SharedGlyphAtlas(Arc::new(Mutex::new(AtlasData {
+                device,
+                alpha_text_atlas: TextureCollection::new_generic(
+                    AtlasOptions::new(Size::new(512, 512).cast())
+                        .label("kludgine::text atlas (alpha)"),
+                    wgpu::TextureFormat::R8Unorm,
+                    wgpu::FilterMode::Linear,
+                    graphics,
+                ),
+                color_text_atlas: TextureCollection::new_generic(
+                    AtlasOptions::new(Size::new(512, 512).cast())
+                        .label("kludgine::text atlas (color)"),
+                    wgpu::TextureFormat::Rgba8UnormSrgb,
+                    wgpu::FilterMode::Linear,
+                    graphics,
+                ),
+                glyphs: GlyphCache::default(),
+            }))),
+            device,
+            shared: false,
+            budget: DEFAULT_GLYPH_ATLAS_BUDGET,
+            rasterized: 0,
+            evictions_seen: 0,
+            font_fingerprints: HashMap::default(),
+        }
+    }
+
+    /// Returns `fresh`, an atlas created using a new graphics device, after
+    /// applying this atlas's settings to it.
+    pub(crate) fn recreated(&self, fresh: Self) -> Self {
+        Self {
+            budget: self.budget,
+            ..fresh
+        }
+    }
+
+    /// Returns this atlas after allowing it to be used by other instances.
+    fn share(&mut self) -> SharedGlyphAtlas {
+        if !self.shared {
+            self.shared = true;
+            let mut data = self.data.lock();
+            // The cached glyphs identify their fonts using this instance's
+            // font system, which other instances do not share.
+            data.glyphs = GlyphCache::default();
+            data.alpha_text_atlas.share_between_instances();
+            data.color_text_atlas.share_between_instances();
+            self.evictions_seen = 0;
+        }
+        self.data.clone()
+    }
+
+    /// Replaces this atlas with `atlas`.
+    fn use_shared(&mut self, atlas: &SharedGlyphAtlas) -> Result<(), Error> {
+        let data = atlas.lock();
+        if data.device != self.device {
+            return Err(Error::DeviceMismatch);
+        }
+        self.evictions_seen = data.glyphs.evictions;
+        drop(data);
+        self.data = atlas.clone();
+        self.shared = true;
+        Ok(())
+    }
+
+    /// Discards the cached glyphs after the rasterization options have
+    /// changed.
+    ///
+    /// Glyphs rasterized with different options are cached separately, so
+    /// this only frees space in the atlas sooner. Shared atlases are left
+    /// untouched, as other instances may still be drawing their glyphs.
+    fn rasterization_changed(&mut self) {
+        if !self.shared {
+            self.data.lock().glyphs = GlyphCache::default();
+            self.evictions_seen = 0;
+        }
+    }
+
+    pub fn new_frame(&mut self, text: &mut TextSystem, stats: &mut RenderStats) {
+        let mut data = self.data.lock();
+        stats.glyphs_evicted = data.glyphs.evict_unused(self.budget);
+        if data.glyphs.evictions != self.evictions_seen {
+            self.evictions_seen = data.glyphs.evictions;
+            // Evicted glyphs are rasterized again when they are next drawn, so
+            // their images no longer need to be kept.
+            let rasterization = text.rasterization;
+            text.swash_cache.image_cache.retain(|key, image| {
+                image.as_ref().map_or(true, is_invisible)
+                    || self
+                        .cached_key(*key, rasterization)
+                        .map_or(true, |key| data.glyphs.contains(&key))
+            });
+        }
+        stats.glyphs_rasterized = std::mem::take(&mut self.rasterized);
+        stats.glyph_atlas_used =
+            data.alpha_text_atlas.used_area() + data.color_text_atlas.used_area();
+        stats.glyph_atlas_total =
+            data.alpha_text_atlas.total_area() + data.color_text_atlas.total_area();
+    }
+
+    /// Returns the key the glyph identified by `cache_key` is cached using,
+    /// or `None` if its font has not been identified.
+    fn cached_key(
+        &self,
+        cache_key: cosmic_text::CacheKey,
+        rasterization: GlyphRasterization,
+    ) -> Option<GlyphKey> {
+        let font = if self.shared {
+            FontKey::Shared(*self.font_fingerprints.get(&cache_key.font_id)?)
+        } else {
+            FontKey::Local(cache_key.font_id)
+        };
+        Some(GlyphKey::new(font, cache_key, rasterization))
+    }
+
+    /// Returns the key the glyph identified by `cache_key` is cached using,
+    /// identifying its font if needed.
+    fn glyph_key(
+        &mut self,
+        cache_key: cosmic_text::CacheKey,
+        rasterization: GlyphRasterization,
+        fonts: &mut cosmic_text::FontSystem,
+    ) -> GlyphKey {
+        if self.shared {
+            self.font_fingerprints
+                .entry(cache_key.font_id)
+                .or_insert_with(|| font_fingerprint(fonts, cache_key.font_id));
+        }
+        self.cached_key(cache_key, rasterization)
+            .assert("font identified")
+    }
+
+    /// Returns the cached glyph for `cache_key`, uploading `image` to the
+    /// appropriate atlas if the glyph is not already cached.
+    fn cache(
+        &mut self,
+        cache_key: cosmic_text::CacheKey,
+        image: &cosmic_text::SwashImage,
+        rasterization: GlyphRasterization,
+        fonts: &mut cosmic_text::FontSystem,
+        graphics: &ProtoGraphics<'_>,
+    ) -> Option<CachedGlyphHandle> {
+        let key = self.glyph_key(cache_key, rasterization, fonts);
+        let mut data = self.data.lock();
+        let data = &mut *data;
+        let mut rasterized = false;
+        let glyph = data.glyphs.get_or_insert(key, || {
+            let uploaded = match image.content {
+                // Glyphs that cannot fit in the atlas are skipped.
+                SwashContent::Mask => (
+                    data.alpha_text_atlas
+                        .push_texture_generic(
+                            rasterization
+                                .apply_gamma(&image.data)
+                                .as_deref()
+                                .unwrap_or(&image.data),
+                            wgpu::ImageDataLayout {
+                                offset: 0,
+                                bytes_per_row: Some(image.placement.width),
+                                rows_per_image: None,
+                            },
+                            Size::upx(image.placement.width, image.placement.height),
+                            graphics,
+                        )
+                        .ok()?,
+                    true,
+                ),
+                SwashContent::Color => (
+                    data.color_text_atlas
+                        .push_texture_generic(
+                            &image.data,
+                            wgpu::ImageDataLayout {
+                                offset: 0,
+                                bytes_per_row: Some(image.placement.width * 4),
+                                rows_per_image: None,
+                            },
+                            Size::upx(image.placement.width, image.placement.height),
+                            graphics,
+                        )
+                        .ok()?,
+                    false,
+                ),
+                SwashContent::SubpixelMask => return None,
+            };
+            rasterized = true;
+            Some(uploaded)
+        });
+        if rasterized {
+            self.rasterized += 1;
+        }
+        glyph
+    }
+}
+
+/// Glyph atlases that can be shared between [`Kludgine`] instances.
+///
+/// Each [`Kludgine`] instance rasterizes glyphs into its own atlases by
+/// default. When multiple instances render using the same graphics device,
+/// such as several instances rendering into textures, sharing one atlas
+/// between the instances rasterizes, uploads, and stores each glyph only
+/// once. Atlases can only be shared between instances using the same device.
+///
+/// An instance's atlas is shared using [`Kludgine::shared_glyph_atlas()`],
+/// and other instances begin using it with [`Kludgine::set_glyph_atlas()`].
+/// Cloning this type is cheap and refers to the same atlas.
+#[derive(Clone)]
+pub struct SharedGlyphAtlas(Arc<Mutex<AtlasData>>);
+
+impl SharedGlyphAtlas {
+    fn lock(&self) -> MutexGuard<'_, AtlasData> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Debug for SharedGlyphAtlas {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedGlyphAtlas")
+            .field("glyphs", &self.lock().glyphs)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Eq for SharedGlyphAtlas {}
+
+impl PartialEq for SharedGlyphAtlas {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+struct AtlasData {
+    /// The device the atlas textures were created with.
+    device: wgpu::Id<wgpu::Device>,
+    alpha_text_atlas: TextureCollection,
+    color_text_atlas: TextureCollection,
+    glyphs: GlyphCache,
+}
+
+/// Returns a hash of the font `id`'s contents, which identifies the font
+/// regardless of which font system it was loaded into.
+fn font_fingerprint(fonts: &mut cosmic_text::FontSystem, id: cosmic_text::fontdb::ID) -> u64 {
+    let mut hasher = hash_map::DefaultHasher::new();
+    fonts.db().face(id).map(|face| face.index).hash(&mut hasher);
+    if let Some(font) = fonts.get_font(id) {
+        font.data().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
 const DEFAULT_FONT_SIZE: Lp = Lp::points(12);
 const DEFAULT_LINE_SIZE: Lp = Lp::points(16);
+const DEFAULT_GLYPH_ATLAS_BUDGET: u64 = 2048 * 2048;
 
 impl TextSystem {
-    pub(crate) fn new(graphics: &ProtoGraphics<'_>) -> Self {
-        let fonts = cosmic_text::FontSystem::new();
-
+    pub(crate) fn new(fonts: cosmic_text::FontSystem) -> Self {
         Self {
-            alpha_text_atlas: TextureCollection::new_generic(
-                Size::new(512, 512).cast(),
-                wgpu::TextureFormat::R8Unorm,
-                wgpu::FilterMode::Linear,
-                graphics,
-            ),
-            color_text_atlas: TextureCollection::new_generic(
-                Size::new(512, 512).cast(),
-                wgpu::TextureFormat::Rgba8UnormSrgb,
-                wgpu::FilterMode::Linear,
-                graphics,
-            ),
             swash_cache: cosmic_text::SwashCache::new(),
             scratch: None,
+            scratch_truncated: false,
             fonts,
             font_size: DEFAULT_FONT_SIZE,
             line_height: DEFAULT_LINE_SIZE,
-            glyphs: GlyphCache::default(),
+            tab_width: TabWidth::default(),
+            rasterization: GlyphRasterization::default(),
+            scale_context: swash::scale::ScaleContext::new(),
             attrs: AttrsOwned::new(Attrs::new()),
         }
     }
 
-    pub fn new_frame(&mut self) {
-        self.glyphs.clear_unused();
-    }
-
     fn metrics(&self, scale: Fraction) -> cosmic_text::Metrics {
         let font_size = self.font_size.into_px(scale);
         let line_height = self.line_height.into_px(scale);
@@ -219,19 +681,110 @@ impl TextSystem {
         }
     }
 
-    pub fn update_scratch_buffer(
-        &mut self,
-        text: &str,
-        scale: Fraction,
-        width: Option<Px>,
-        align: Option<Align>,
-    ) {
+    pub fn update_scratch_buffer<Unit>(&mut self, text: &Text<'_, Unit>, scale: Fraction)
+    where
+        Unit: figures::ScreenUnit,
+    {
+        let wrap_at = text.wrap_at.map(|width| width.into_px(scale));
         if self.scratch.is_none() {
             let metrics = self.metrics(scale);
             let buffer = cosmic_text::Buffer::new(&mut self.fonts, metrics);
             self.scratch = Some(buffer);
         }
 
+        let tab_width = self.tab_width_in_spaces(scale);
+        let scratch = self.scratch.as_mut().expect("initialized above");
+        scratch.set_tab_width(&mut self.fonts, tab_width);
+        scratch.set_wrap(&mut self.fonts, text.wrap.into());
+        scratch.set_size(&mut self.fonts, wrap_at.map(Cast::cast), None);
+        self.shape_scratch(text.text, text.align);
+        self.scratch_truncated = false;
+
+        let Some(max_lines) = text.max_lines else {
+            return;
+        };
+        if self.scratch_fits(max_lines, wrap_at) {
+            return;
+        }
+
+        // Find the longest prefix of the text that still fits once the
+        // ellipsis has been appended to it. Every prefix ends on a character
+        // boundary, and the full text is already known to not fit.
+        let boundaries = text
+            .text
+            .char_indices()
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        let mut fits = 0;
+        let mut overflows = boundaries.len();
+        let mut truncated = String::with_capacity(text.text.len() + text.ellipsis.len());
+        while overflows - fits > 1 {
+            let middle = (fits + overflows) / 2;
+            truncate_into(
+                &mut truncated,
+                &text.text[..boundaries[middle]],
+                text.ellipsis,
+            );
+            self.shape_scratch(&truncated, text.align);
+            if self.scratch_fits(max_lines, wrap_at) {
+                fits = middle;
+            } else {
+                overflows = middle;
+            }
+        }
+
+        let prefix = boundaries.get(fits).copied().unwrap_or_default();
+        truncate_into(&mut truncated, &text.text[..prefix], text.ellipsis);
+        self.shape_scratch(&truncated, text.align);
+        self.scratch_truncated = true;
+    }
+
+    /// Measures `text`, retaining its shaped layout when possible.
+    pub fn measure<Unit>(&mut self, text: &Text<'_, Unit>, scale: Fraction) -> MeasuredText<Unit>
+    where
+        Unit: figures::ScreenUnit,
+    {
+        self.update_scratch_buffer(text, scale);
+        let mut measured = measure_text::<Unit, true>(None, text.color, self, scale);
+        if !measured.truncated {
+            // The scratch buffer is recreated the next time text is laid out,
+            // so it can be given to the measured text.
+            measured.layout = self.scratch.take().map(|buffer| {
+                Arc::new(RetainedLayout {
+                    buffer,
+                    color: text.color,
+                    scale,
+                })
+            });
+        }
+        measured
+    }
+
+    fn tab_width_in_spaces(&mut self, scale: Fraction) -> u16 {
+        match self.tab_width {
+            TabWidth::Spaces(spaces) => spaces.max(1),
+            TabWidth::Width(width) => {
+                self.shape_scratch(" ", None);
+                let space = self
+                    .scratch
+                    .as_ref()
+                    .expect("initialized above")
+                    .layout_runs()
+                    .next()
+                    .map_or(0., |run| run.line_w);
+                if space > 0. {
+                    (width.into_px(scale).into_float() / space)
+                        .round()
+                        .max(1.)
+                        .cast::<u16>()
+                } else {
+                    1
+                }
+            }
+        }
+    }
+
+    fn shape_scratch(&mut self, text: &str, align: Option<Align>) {
         let scratch = self.scratch.as_mut().expect("initialized above");
         scratch.set_text(
             &mut self.fonts,
@@ -239,23 +792,93 @@ impl TextSystem {
             self.attrs.as_attrs(),
             cosmic_text::Shaping::Advanced, // TODO maybe this should be configurable?
         );
-        scratch.set_size(&mut self.fonts, width.map(Cast::cast), None);
         for line in &mut scratch.lines {
             line.set_align(align);
         }
         scratch.shape_until_scroll(&mut self.fonts, false);
     }
+
+    fn scratch_fits(&self, max_lines: usize, width: Option<Px>) -> bool {
+        let scratch = self.scratch.as_ref().expect("initialized above");
+        let width = width.map(Cast::<f32>::cast);
+        let mut lines = 0;
+        for run in scratch.layout_runs() {
+            lines += 1;
+            if lines > max_lines || width.is_some_and(|width| run.line_w > width) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+fn truncate_into(truncated: &mut String, prefix: &str, ellipsis: &str) {
+    truncated.clear();
+    truncated.push_str(prefix.trim_end());
+    truncated.push_str(ellipsis);
 }
 
-#[derive(Debug, Default, Clone)]
+/// Identifies a font within a glyph atlas.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+enum FontKey {
+    /// A font in the font system of the only instance using the atlas.
+    Local(cosmic_text::fontdb::ID),
+    /// The [fingerprint](font_fingerprint) of a font.
+    Shared(u64),
+}
+
+/// Identifies a rasterized glyph within a glyph atlas.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+struct GlyphKey {
+    font: FontKey,
+    glyph_id: u16,
+    font_size_bits: u32,
+    x_bin: cosmic_text::SubpixelBin,
+    y_bin: cosmic_text::SubpixelBin,
+    flags: cosmic_text::CacheKeyFlags,
+    hinting: bool,
+    gamma_bits: u32,
+}
+
+impl GlyphKey {
+    fn new(
+        font: FontKey,
+        cache_key: cosmic_text::CacheKey,
+        rasterization: GlyphRasterization,
+    ) -> Self {
+        Self {
+            font,
+            glyph_id: cache_key.glyph_id,
+            font_size_bits: cache_key.font_size_bits,
+            x_bin: cache_key.x_bin,
+            y_bin: cache_key.y_bin,
+            flags: cache_key.flags,
+            hinting: rasterization.hinting,
+            gamma_bits: rasterization.gamma.to_bits(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
 struct GlyphCache {
-    glyphs: Arc<Mutex<HashMap<cosmic_text::CacheKey, CachedGlyph, DefaultHasher>>>,
+    glyphs: Arc<Mutex<HashMap<GlyphKey, CachedGlyph, DefaultHasher>>>,
+    frame: u64,
+    area: u64,
+    /// The total number of glyphs evicted from this cache.
+    evictions: usize,
 }
 
 impl GlyphCache {
+    fn contains(&self, key: &GlyphKey) -> bool {
+        self.glyphs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains_key(key)
+    }
+
     fn get_or_insert(
-        &self,
-        key: cosmic_text::CacheKey,
+        &mut self,
+        key: GlyphKey,
         insert_fn: impl FnOnce() -> Option<(CollectedTexture, bool)>,
     ) -> Option<CachedGlyphHandle> {
         let mut data = self.glyphs.lock().unwrap_or_else(PoisonError::into_inner);
@@ -263,14 +886,20 @@ impl GlyphCache {
             hash_map::Entry::Occupied(cached) => {
                 let cached = cached.into_mut();
                 cached.ref_count += 1;
+                cached.last_used = self.frame;
                 cached
             }
             hash_map::Entry::Vacant(vacant) => {
                 let (texture, is_mask) = insert_fn()?;
+                let area = u64::from(texture.region.size.width.get())
+                    * u64::from(texture.region.size.height.get());
+                self.area += area;
                 vacant.insert(CachedGlyph {
                     texture,
                     is_mask,
                     ref_count: 1,
+                    area,
+                    last_used: self.frame,
                 })
             }
         };
@@ -282,9 +911,34 @@ impl GlyphCache {
         })
     }
 
-    fn clear_unused(&mut self) {
+    /// Evicts the least recently used glyphs that are not in use until the
+    /// cached glyphs occupy no more than `budget` pixels, returning the number
+    /// of evicted glyphs.
+    fn evict_unused(&mut self, budget: u64) -> usize {
+        self.frame += 1;
+        if self.area <= budget {
+            return 0;
+        }
+
         let mut data = self.glyphs.lock().unwrap_or_else(PoisonError::into_inner);
-        data.retain(|_, glyph| glyph.ref_count > 0);
+        let mut unused = data
+            .iter()
+            .filter(|(_, glyph)| glyph.ref_count == 0)
+            .map(|(key, glyph)| (glyph.last_used, *key))
+            .collect::<Vec<_>>();
+        unused.sort_unstable_by_key(|(last_used, _)| *last_used);
+
+        let mut evicted = 0;
+        for (_, key) in unused {
+            if self.area <= budget {
+                break;
+            }
+            let glyph = data.remove(&key).assert("unused glyph missing");
+            self.area -= glyph.area;
+            evicted += 1;
+        }
+        self.evictions += evicted;
+        evicted
     }
 }
 
@@ -293,12 +947,16 @@ struct CachedGlyph {
     texture: CollectedTexture,
     is_mask: bool,
     ref_count: usize,
+    /// The number of pixels this glyph occupies in its atlas.
+    area: u64,
+    /// The frame this glyph was last drawn during.
+    last_used: u64,
 }
 
 pub(crate) struct CachedGlyphHandle {
-    key: cosmic_text::CacheKey,
+    key: GlyphKey,
     pub is_mask: bool,
-    cache: Weak<Mutex<HashMap<cosmic_text::CacheKey, CachedGlyph, DefaultHasher>>>,
+    cache: Weak<Mutex<HashMap<GlyphKey, CachedGlyph, DefaultHasher>>>,
     pub texture: CollectedTexture,
 }
 
@@ -352,59 +1010,90 @@ impl Graphics<'_> {
         buffer: &cosmic_text::Buffer,
         default_color: Color,
         origin: TextOrigin<Px>,
+    ) -> PreparedText {
+        self.prepare_text_inner(Some(buffer), default_color, origin)
+    }
+
+    fn prepare_text_inner(
+        &mut self,
+        buffer: Option<&cosmic_text::Buffer>,
+        default_color: Color,
+        origin: TextOrigin<Px>,
     ) -> PreparedText {
         let mut glyphs = HashMap::default();
         let mut vertices = VertexCollection::default();
         let mut indices = Vec::new();
         let mut commands = SmallVec::<[PreparedCommand; 2]>::new();
 
+        let mut visible = Vec::new();
         map_each_glyph(
-            Some(buffer),
+            buffer,
             default_color,
             origin,
             self.kludgine,
             self.device,
             self.queue,
             &mut glyphs,
-            |blit, _glyph, _is_first_line, _baseline, _line_w, kludgine| {
+            |blit, _glyph, _is_first_line, _baseline, _line_w, _kludgine| {
                 if let GlyphBlit::Visible {
                     blit,
                     glyph: cached,
                 } = blit
                 {
-                    let corners: [u32; 4] =
-                        array::from_fn(|index| vertices.get_or_insert(blit.verticies[index]));
-                    let start_index = u32::try_from(indices.len()).assert("too many drawn indices");
-                    for &index in blit.indices() {
-                        indices
-                            .push(corners[usize::try_from(index).assert("too many drawn indices")]);
-                    }
-                    let end_index = u32::try_from(indices.len()).assert("too many drawn indices");
-                    match commands.last_mut() {
-                        Some(last_command) if last_command.is_mask == cached.is_mask => {
-                            // The last command was from the same texture source, we can stend the previous range to the new end.
-                            last_command.indices.end = end_index;
-                        }
-                        _ => {
-                            commands.push(PreparedCommand {
-                                indices: start_index..end_index,
-                                is_mask: cached.is_mask,
-                                binding: Some(cached.texture.bind_group(&ProtoGraphics::new(
-                                    self.device,
-                                    self.queue,
-                                    kludgine,
-                                ))),
-                            });
-                        }
-                    }
+                    visible.push((blit, cached));
                 }
             },
         );
+        group_glyphs_by_atlas(&mut visible);
+
+        let graphics = ProtoGraphics::new(self.device, self.queue, self.kludgine);
+        for (blit, cached) in &visible {
+            let corners: [u32; 4] =
+                array::from_fn(|index| vertices.get_or_insert(blit.verticies[index]));
+            let start_index = u32::try_from(indices.len()).assert("too many drawn indices");
+            for &index in blit.indices() {
+                indices.push(corners[usize::try_from(index).assert("too many drawn indices")]);
+            }
+            let end_index = u32::try_from(indices.len()).assert("too many drawn indices");
+            let binding = cached.texture.bind_group(&graphics);
+            match commands.last_mut() {
+                Some(last_command)
+                    if last_command.is_mask == cached.is_mask
+                        && last_command
+                            .binding
+                            .as_ref()
+                            .is_some_and(|last| Arc::ptr_eq(last, &binding)) =>
+                {
+                    // The last command was from the same texture source, we can stend the previous range to the new end.
+                    last_command.indices.end = end_index;
+                }
+                _ => {
+                    commands.push(PreparedCommand {
+                        indices: start_index..end_index,
+                        is_mask: cached.is_mask,
+                        pattern: None,
+                        binding: Some(binding),
+                    });
+                }
+            }
+        }
 
         PreparedText {
             graphic: PreparedGraphic {
-                vertices: Buffer::new(&vertices.vertices, wgpu::BufferUsages::VERTEX, self.device),
-                indices: Buffer::new(&indices, wgpu::BufferUsages::INDEX, self.device),
+                vertices: Buffer::new(
+                    &vertices.vertices,
+                    wgpu::BufferUsages::VERTEX,
+                    Some("kludgine::prepared text vertices"),
+                    self.device,
+                ),
+                indices: Buffer::new(
+                    &indices,
+                    wgpu::BufferUsages::INDEX,
+                    Some("kludgine::prepared text indices"),
+                    self.device,
+                ),
+                bounds: vertex_bounds(&vertices.vertices),
+                quad: None,
                 commands,
             },
             _glyphs: glyphs,
@@ -412,7 +1101,178 @@ impl Graphics<'_> {
     }
 }
 
-#[allow(clippy::too_many_lines)]
+/// Rasterizes a glyph the same way [`cosmic_text::SwashCache`] does, except
+/// without hinting.
+fn rasterize_unhinted(
+    fonts: &mut cosmic_text::FontSystem,
+    context: &mut swash::scale::ScaleContext,
+    cache_key: cosmic_text::CacheKey,
+) -> Option<cosmic_text::SwashImage> {
+    let font = fonts.get_font(cache_key.font_id)?;
+    let mut scaler = context
+        .builder(font.as_swash())
+        .size(f32::from_bits(cache_key.font_size_bits))
+        .hint(false)
+        .build();
+
+    swash::scale::Render::new(&[
+        swash::scale::Source::ColorOutline(0),
+        swash::scale::Source::ColorBitmap(swash::scale::StrikeWith::BestFit),
+        swash::scale::Source::Outline,
+    ])
+    .format(swash::zeno::Format::Alpha)
+    .offset(swash::zeno::Vector::new(
+        cache_key.x_bin.as_float(),
+        cache_key.y_bin.as_float(),
+    ))
+    .transform(
+        cache_key
+            .flags
+            .contains(cosmic_text::CacheKeyFlags::FAKE_ITALIC)
+            .then(|| {
+                swash::zeno::Transform::skew(
+                    swash::zeno::Angle::from_degrees(14.),
+                    swash::zeno::Angle::from_degrees(0.),
+                )
+            }),
+    )
+    .render(&mut scaler, cache_key.glyph_id)
+}
+
+/// Returns the rasterized image of the glyph identified by `cache_key`.
+fn glyph_image<'a>(
+    fonts: &mut cosmic_text::FontSystem,
+    swash_cache: &'a mut cosmic_text::SwashCache,
+    scale_context: &mut swash::scale::ScaleContext,
+    hinting: bool,
+    cache_key: cosmic_text::CacheKey,
+) -> Option<&'a cosmic_text::SwashImage> {
+    if !hinting {
+        swash_cache
+            .image_cache
+            .entry(cache_key)
+            .or_insert_with(|| rasterize_unhinted(fonts, scale_context, cache_key));
+    }
+    swash_cache.get_image(fonts, cache_key).as_ref()
+}
+
+/// Returns the position `glyph` is rasterized at when its line begins at
+/// `run_origin`.
+fn physical_glyph(
+    glyph: &LayoutGlyph,
+    run_origin: Point<Px>,
+    subpixel_positioning: SubpixelPositioning,
+) -> cosmic_text::PhysicalGlyph {
+    let x = glyph.font_size.mul_add(glyph.x_offset, glyph.x) + run_origin.x.into_float();
+    glyph.physical(
+        (
+            run_origin.x.into_float() + subpixel_positioning.quantize(x) - x,
+            run_origin.y.into_float(),
+        ),
+        1.,
+    )
+}
+
+/// Returns the area covered by `image` when drawn for `physical`.
+fn glyph_rect(
+    physical: &cosmic_text::PhysicalGlyph,
+    image: &cosmic_text::SwashImage,
+    line_height_offset: Point<Px>,
+) -> Rect<Px> {
+    Rect::new(
+        (Point::new(physical.x, physical.y)).cast::<Px>()
+            + Point::new(
+                Px::new(image.placement.left),
+                line_height_offset.y - image.placement.top,
+            ),
+        Size::new(
+            UPx::new(image.placement.width),
+            UPx::new(image.placement.height),
+        )
+        .into_signed(),
+    )
+}
+
+const fn is_invisible(image: &cosmic_text::SwashImage) -> bool {
+    image.placement.width == 0 || image.placement.height == 0
+}
+
+/// Returns the cached glyph identified by `cache_key`, rasterizing it and
+/// uploading it to the glyph atlases if it is not already cached.
+///
+/// Returns `None` if the glyph is invisible or cannot be stored in the glyph
+/// atlases.
+pub(crate) fn cache_glyph(
+    kludgine: &mut Kludgine,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    cache_key: cosmic_text::CacheKey,
+) -> Option<CachedGlyphHandle> {
+    let rasterization = kludgine.text.rasterization;
+    let image = glyph_image(
+        &mut kludgine.text.fonts,
+        &mut kludgine.text.swash_cache,
+        &mut kludgine.text.scale_context,
+        rasterization.hinting,
+        cache_key,
+    )?;
+    if is_invisible(image) {
+        return None;
+    }
+    kludgine.glyph_atlas.cache(
+        cache_key,
+        image,
+        rasterization,
+        &mut kludgine.text.fonts,
+        &ProtoGraphics {
+            id: kludgine.id,
+            device,
+            queue,
+            binding_layout: &kludgine.binding_layout,
+            linear_sampler: &kludgine.linear_sampler,
+            nearest_sampler: &kludgine.nearest_sampler,
+            uniforms: &kludgine.uniforms.wgpu,
+            multisample: kludgine.multisample,
+        },
+    )
+}
+
+/// Reorders `glyphs` so that glyphs stored in the same atlas are adjacent,
+/// keeping the atlases in the order they are first drawn.
+///
+/// Consecutive glyphs from the same atlas are drawn using a single draw call.
+/// Because glyph colors are stored in their vertices, this allows text mixing
+/// many colored spans with color glyphs, such as emoji, to be drawn using one
+/// draw call per atlas.
+pub(crate) fn group_glyphs_by_atlas<Glyph>(glyphs: &mut [(TextureBlit<Px>, Glyph)])
+where
+    Glyph: Borrow<CachedGlyphHandle>,
+{
+    let mut atlases = SmallVec::<[TextureId; 2]>::new();
+    for (_, glyph) in &*glyphs {
+        let atlas = glyph.borrow().texture.id();
+        if !atlases.contains(&atlas) {
+            atlases.push(atlas);
+        }
+    }
+    if atlases.len() > 1 {
+        glyphs.sort_by_key(|(_, glyph)| {
+            let atlas = glyph.borrow().texture.id();
+            atlases.iter().position(|id| *id == atlas)
+        });
+    }
+}
+
+/// Returns the color `image` should be drawn with.
+fn glyph_color(glyph: &LayoutGlyph, image: &cosmic_text::SwashImage, default: Color) -> Color {
+    if image.content == SwashContent::Color {
+        // Set the color to full white to avoid mixing.
+        Color::WHITE
+    } else {
+        glyph.color_opt.map_or(default, Color::from)
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn map_each_glyph(
     buffer: Option<&cosmic_text::Buffer>,
@@ -433,87 +1293,52 @@ pub(crate) fn map_each_glyph(
         TextOrigin::Custom(point) => point,
         TextOrigin::TopLeft => Point::default(),
         TextOrigin::Center => {
-            let measured =
-                measure_text::<Px, false>(buffer, default_color, kludgine, device, queue, glyphs);
+            let measured = measure_text::<Px, false>(
+                buffer,
+                default_color,
+                &mut kludgine.text,
+                kludgine.effective_scale,
+            );
             (Point::from(measured.size) / 2).round()
         }
         TextOrigin::FirstBaseline => line_height_offset.cast(),
     } + line_height_offset;
 
     let buffer = buffer.unwrap_or_else(|| kludgine.text.scratch.as_ref().expect("no buffer"));
+    let rasterization = kludgine.text.rasterization;
+    let graphics = ProtoGraphics {
+        id: kludgine.id,
+        device,
+        queue,
+        binding_layout: &kludgine.binding_layout,
+        linear_sampler: &kludgine.linear_sampler,
+        nearest_sampler: &kludgine.nearest_sampler,
+        uniforms: &kludgine.uniforms.wgpu,
+        multisample: kludgine.multisample,
+    };
     for run in buffer.layout_runs() {
         let run_origin = Point::new(Px::ZERO, Px::from(run.line_y)) - relative_to;
         for glyph in run.glyphs {
-            let physical =
-                glyph.physical((run_origin.x.into_float(), run_origin.y.into_float()), 1.);
-            let Some(image) = kludgine
-                .text
-                .swash_cache
-                .get_image(&mut kludgine.text.fonts, physical.cache_key)
-            else {
+            let physical = physical_glyph(glyph, run_origin, rasterization.subpixel_positioning);
+            let Some(image) = glyph_image(
+                &mut kludgine.text.fonts,
+                &mut kludgine.text.swash_cache,
+                &mut kludgine.text.scale_context,
+                rasterization.hinting,
+                physical.cache_key,
+            ) else {
                 continue;
             };
-            let invisible = image.placement.width == 0 || image.placement.height == 0;
-
-            let mut color = glyph.color_opt.map_or(default_color, Color::from);
-
-            let cached = if invisible {
+            let cached = if is_invisible(image) {
                 None
             } else {
-                kludgine
-                    .text
-                    .glyphs
-                    .get_or_insert(physical.cache_key, || match image.content {
-                        SwashContent::Mask => Some((
-                            kludgine.text.alpha_text_atlas.push_texture_generic(
-                                &image.data,
-                                wgpu::ImageDataLayout {
-                                    offset: 0,
-                                    bytes_per_row: Some(image.placement.width),
-                                    rows_per_image: None,
-                                },
-                                Size::upx(image.placement.width, image.placement.height),
-                                &ProtoGraphics {
-                                    id: kludgine.id,
-                                    device,
-                                    queue,
-                                    binding_layout: &kludgine.binding_layout,
-                                    linear_sampler: &kludgine.linear_sampler,
-                                    nearest_sampler: &kludgine.nearest_sampler,
-                                    uniforms: &kludgine.uniforms.wgpu,
-                                    multisample: kludgine.multisample,
-                                },
-                            ),
-                            true,
-                        )),
-                        SwashContent::Color => {
-                            // Set the color to full white to avoid mixing.
-                            color = Color::WHITE;
-                            Some((
-                                kludgine.text.color_text_atlas.push_texture_generic(
-                                    &image.data,
-                                    wgpu::ImageDataLayout {
-                                        offset: 0,
-                                        bytes_per_row: Some(image.placement.width * 4),
-                                        rows_per_image: None,
-                                    },
-                                    Size::upx(image.placement.width, image.placement.height),
-                                    &ProtoGraphics {
-                                        id: kludgine.id,
-                                        device,
-                                        queue,
-                                        binding_layout: &kludgine.binding_layout,
-                                        linear_sampler: &kludgine.linear_sampler,
-                                        nearest_sampler: &kludgine.nearest_sampler,
-                                        uniforms: &kludgine.uniforms.wgpu,
-                                        multisample: kludgine.multisample,
-                                    },
-                                ),
-                                false,
-                            ))
-                        }
-                        SwashContent::SubpixelMask => None,
-                    })
+                kludgine.glyph_atlas.cache(
+                    physical.cache_key,
+                    image,
+                    rasterization,
+                    &mut kludgine.text.fonts,
+                    &graphics,
+                )
             };
 
             let blit = if let Some(cached) = cached {
@@ -524,21 +1349,10 @@ pub(crate) fn map_each_glyph(
                 GlyphBlit::Visible {
                     blit: TextureBlit::new(
                         cached.texture.region,
-                        Rect::new(
-                            (Point::new(physical.x, physical.y)).cast::<Px>()
-                                + Point::new(
-                                    Px::new(image.placement.left),
-                                    line_height_offset.y - image.placement.top,
-                                ),
-                            Size::new(
-                                UPx::new(image.placement.width),
-                                UPx::new(image.placement.height),
-                            )
-                            .into_signed(),
-                        ),
-                        color,
+                        glyph_rect(&physical, image, line_height_offset),
+                        glyph_color(glyph, image, default_color),
                     ),
-                    glyph: cached.clone(),
+                    glyph: cached,
                 }
             } else {
                 GlyphBlit::Invisible {
@@ -568,6 +1382,13 @@ pub(crate) enum GlyphBlit {
         blit: TextureBlit<Px>,
         glyph: CachedGlyphHandle,
     },
+    /// A visible glyph that has been measured but has not been uploaded to
+    /// the glyph atlases.
+    Pending {
+        rect: Rect<Px>,
+        cache_key: cosmic_text::CacheKey,
+        color: Color,
+    },
 }
 
 impl GlyphBlit {
@@ -575,6 +1396,7 @@ impl GlyphBlit {
         match self {
             GlyphBlit::Invisible { location, .. } => *location,
             GlyphBlit::Visible { blit, .. } => blit.top_left().location,
+            GlyphBlit::Pending { rect, .. } => rect.origin,
         }
     }
 
@@ -582,6 +1404,7 @@ impl GlyphBlit {
         match self {
             GlyphBlit::Invisible { location, width } => Point::new(location.x + *width, bottom),
             GlyphBlit::Visible { blit, .. } => blit.bottom_right().location,
+            GlyphBlit::Pending { rect, .. } => rect.origin + rect.size,
         }
     }
 }
@@ -589,25 +1412,33 @@ impl GlyphBlit {
 impl CanRenderTo for GlyphBlit {
     fn can_render_to(&self, kludgine: &Kludgine) -> bool {
         match self {
-            GlyphBlit::Invisible { .. } => true,
+            GlyphBlit::Invisible { .. } | GlyphBlit::Pending { .. } => true,
             GlyphBlit::Visible { glyph, .. } => glyph.texture.can_render_to(kludgine),
         }
     }
 }
 
+/// Measures the text in `buffer`, or the scratch buffer of `text` if `buffer`
+/// is `None`.
+///
+/// Visible glyphs are measured without uploading them to the GPU, and are
+/// returned as [`GlyphBlit::Pending`].
 pub(crate) fn measure_text<Unit, const COLLECT_GLYPHS: bool>(
     buffer: Option<&cosmic_text::Buffer>,
     color: Color,
-    kludgine: &mut Kludgine,
-    device: &wgpu::Device,
-    queue: &wgpu::Queue,
-    glyphs: &mut HashMap<cosmic_text::CacheKey, CachedGlyphHandle, DefaultHasher>,
+    text: &mut TextSystem,
+    scale: Fraction,
 ) -> MeasuredText<Unit>
 where
     Unit: figures::ScreenUnit,
 {
-    // TODO the returned type should be able to be drawn, so that we don't have to call update_scratch_buffer again.
-    let line_height = Unit::from_lp(kludgine.text.line_height, kludgine.effective_scale);
+    let truncated = buffer.is_none() && text.scratch_truncated;
+    let buffer = buffer.unwrap_or_else(|| text.scratch.as_ref().expect("no buffer"));
+    let line_count = buffer.layout_runs().count();
+    let line_height = Unit::from_lp(text.line_height, scale);
+    let metrics = buffer.metrics();
+    let line_height_offset = Point::new(Px::ZERO, Px::from(metrics.line_height)).round();
+    let rasterization = text.rasterization;
     let mut min = Point::new(Px::MAX, Px::MAX);
     let mut last_baseline = Px::MIN;
     let mut max = Point::new(Px::MIN, Px::MIN);
@@ -615,15 +1446,35 @@ where
     let mut descent = Px::ZERO;
     let mut first_baseline = Px::ZERO;
     let mut measured_glyphs = Vec::new();
-    map_each_glyph(
-        buffer,
-        color,
-        TextOrigin::TopLeft,
-        kludgine,
-        device,
-        queue,
-        glyphs,
-        |blit, glyph, line_index, baseline, line_width, _kludgine| {
+    for run in buffer.layout_runs() {
+        let line_index = (run.line_top / metrics.line_height).round().cast::<usize>();
+        let baseline = Px::from(run.line_y);
+        let line_width = Px::from(run.line_w.ceil());
+        let run_origin = Point::new(Px::ZERO, baseline) - line_height_offset;
+        for glyph in run.glyphs {
+            let physical = physical_glyph(glyph, run_origin, rasterization.subpixel_positioning);
+            let Some(image) = glyph_image(
+                &mut text.fonts,
+                &mut text.swash_cache,
+                &mut text.scale_context,
+                rasterization.hinting,
+                physical.cache_key,
+            ) else {
+                continue;
+            };
+            let blit = if is_invisible(image) || image.content == SwashContent::SubpixelMask {
+                GlyphBlit::Invisible {
+                    location: Point::new(physical.x, physical.y).cast::<Px>(),
+                    width: glyph.w.cast(),
+                }
+            } else {
+                GlyphBlit::Pending {
+                    rect: glyph_rect(&physical, image, line_height_offset),
+                    cache_key: physical.cache_key,
+                    color: glyph_color(glyph, image, color),
+                }
+            };
+
             last_baseline = last_baseline.max(baseline);
             min = min.min(blit.top_left());
             max.x = max.x.max(line_width);
@@ -639,8 +1490,8 @@ where
                     info: GlyphInfo::new(glyph, line_index, line_width),
                 });
             }
-        },
-    );
+        }
+    }
 
     if min == Point::new(Px::MAX, Px::MAX) {
         MeasuredText {
@@ -649,20 +1500,25 @@ where
             left: Unit::default(),
             line_height,
             size: Size::new(Unit::default(), line_height),
+            line_count,
+            truncated,
             glyphs: Vec::new(),
+            layout: None,
         }
     } else {
         MeasuredText {
-            ascent: Unit::from_px(ascent, kludgine.effective_scale),
-            descent: Unit::from_px(descent, kludgine.effective_scale),
-            left: Unit::from_px(min.x, kludgine.effective_scale),
+            ascent: Unit::from_px(ascent, scale),
+            descent: Unit::from_px(descent, scale),
+            left: Unit::from_px(min.x, scale),
             size: Size {
-                width: Unit::from_px(max.x, kludgine.effective_scale),
-                height: Unit::from_px(max.y.max(last_baseline), kludgine.effective_scale)
-                    .max(line_height),
+                width: Unit::from_px(max.x, scale),
+                height: Unit::from_px(max.y.max(last_baseline), scale).max(line_height),
             },
-            line_height: Unit::from_px(first_baseline, kludgine.effective_scale),
+            line_height: Unit::from_px(first_baseline, scale),
+            line_count,
+            truncated,
             glyphs: measured_glyphs,
+            layout: None,
         }
     }
 }
@@ -790,8 +1646,71 @@ pub struct MeasuredText<Unit> {
     pub line_height: Unit,
     /// The total size of the measured text, encompassing all lines.
     pub size: Size<Unit>,
+    /// The number of lines the text was laid out into.
+    pub line_count: usize,
+    /// True if the text was shortened and had its ellipsis appended to fit
+    /// within its [maximum number of lines](Text::max_lines).
+    pub truncated: bool,
     /// The individual glyhs that were laid out.
     pub glyphs: Vec<MeasuredGlyph>,
+    /// The shaped text, which allows [`rewrap()`](Self::rewrap) to lay out the
+    /// text without shaping it again.
+    pub(crate) layout: Option<Arc<RetainedLayout>>,
+}
+
+impl<Unit> MeasuredText<Unit>
+where
+    Unit: figures::ScreenUnit,
+{
+    /// Lays out this text again, wrapping it at `width`.
+    ///
+    /// Only line breaking and glyph positioning are performed: the text is
+    /// not shaped again. This makes rewrapping much less expensive than
+    /// measuring the text again, which is useful when the available width
+    /// changes frequently, such as while a window is being resized.
+    ///
+    /// Returns false without changing this text if it cannot be rewrapped.
+    /// Only text measured by
+    /// [`Renderer::measure_text()`](crate::drawing::Renderer::measure_text),
+    /// [`Kludgine::measure_text()`], or [`TextMeasurer::measure()`] at the
+    /// current scale can be rewrapped, and text that was
+    /// [truncated](Self::truncated) must be measured again to truncate it at
+    /// the new width. Text measured by a [`TextMeasurer`] should be rewrapped
+    /// using [`TextMeasurer::rewrap()`].
+    pub fn rewrap(&mut self, width: Option<Unit>, graphics: &mut Graphics<'_>) -> bool {
+        let scale = graphics.kludgine.effective_scale;
+        self.rewrap_inner(width, &mut graphics.kludgine.text, scale)
+    }
+
+    fn rewrap_inner(
+        &mut self,
+        width: Option<Unit>,
+        text: &mut TextSystem,
+        scale: Fraction,
+    ) -> bool {
+        let Some(layout) = self.layout.as_mut().filter(|layout| layout.scale == scale) else {
+            return false;
+        };
+        let layout = Arc::make_mut(layout);
+        layout.buffer.set_size(
+            &mut text.fonts,
+            width.map(|width| width.into_px(scale).cast()),
+            None,
+        );
+        let mut rewrapped =
+            measure_text::<Unit, true>(Some(&layout.buffer), layout.color, text, scale);
+        rewrapped.layout = self.layout.take();
+        *self = rewrapped;
+        true
+    }
+}
+
+/// Shaped text retained by [`MeasuredText`].
+#[derive(Debug, Clone)]
+pub(crate) struct RetainedLayout {
+    pub buffer: cosmic_text::Buffer,
+    pub color: Color,
+    pub scale: Fraction,
 }
 
 impl<Unit> CanRenderTo for MeasuredText<Unit> {
@@ -804,6 +1723,164 @@ impl<Unit> CanRenderTo for MeasuredText<Unit> {
 
 impl<Unit> DrawableSource for MeasuredText<Unit> {}
 
+/// Measures and wraps text without a graphics device.
+///
+/// Measuring text only requires a [`cosmic_text::FontSystem`], which makes
+/// this type useful for layout code that runs before a [`Kludgine`] instance
+/// exists or on machines without a GPU. The returned [`MeasuredText`] can be
+/// drawn using
+/// [`Renderer::draw_measured_text()`](crate::drawing::Renderer::draw_measured_text),
+/// which uploads each glyph to the GPU the first time it is drawn.
+///
+/// Glyphs are identified using the ids of the fonts that contain them. Text
+/// measured by a `TextMeasurer` should only be drawn by a [`Kludgine`] whose
+/// font system has loaded the same fonts in the same order.
+#[derive(Debug)]
+pub struct TextMeasurer {
+    text: TextSystem,
+    scale: Fraction,
+}
+
+impl TextMeasurer {
+    /// Returns a new measurer that uses `fonts` to measure text at `scale`.
+    #[must_use]
+    pub fn new(fonts: cosmic_text::FontSystem, scale: impl Into<Fraction>) -> Self {
+        Self {
+            text: TextSystem::new(fonts),
+            scale: scale.into(),
+        }
+    }
+
+    /// Returns a mutable reference to the [`cosmic_text::FontSystem`] used
+    /// when measuring text.
+    pub fn font_system(&mut self) -> &mut cosmic_text::FontSystem {
+        &mut self.text.fonts
+    }
+
+    /// Returns the font system used by this measurer.
+    #[must_use]
+    pub fn into_font_system(self) -> cosmic_text::FontSystem {
+        self.text.fonts
+    }
+
+    /// Returns the scale text is measured at.
+    #[must_use]
+    pub const fn scale(&self) -> Fraction {
+        self.scale
+    }
+
+    /// Sets the scale text is measured at.
+    pub fn set_scale(&mut self, scale: impl Into<Fraction>) {
+        self.scale = scale.into();
+        self.text.scale_changed(self.scale);
+    }
+
+    /// Sets the font size.
+    pub fn set_font_size(&mut self, size: impl figures::ScreenScale<Lp = Lp>) {
+        self.text
+            .set_font_size(figures::ScreenScale::into_lp(size, self.scale), self.scale);
+    }
+
+    /// Returns the current font size.
+    #[must_use]
+    pub const fn font_size(&self) -> Lp {
+        self.text.font_size
+    }
+
+    /// Sets the line height for multi-line layout.
+    pub fn set_line_height(&mut self, size: impl figures::ScreenScale<Lp = Lp>) {
+        self.text
+            .set_line_height(figures::ScreenScale::into_lp(size, self.scale), self.scale);
+    }
+
+    /// Returns the current line height.
+    #[must_use]
+    pub const fn line_height(&self) -> Lp {
+        self.text.line_height
+    }
+
+    /// Sets the distance between tab stops.
+    pub fn set_tab_width(&mut self, width: TabWidth) {
+        self.text.tab_width = width;
+    }
+
+    /// Returns the current distance between tab stops.
+    #[must_use]
+    pub const fn tab_width(&self) -> TabWidth {
+        self.text.tab_width
+    }
+
+    /// Sets the options used when rasterizing glyphs.
+    ///
+    /// Glyphs are measured using the bounds of their rasterized images, so
+    /// these options should match the options of the [`Kludgine`] instance
+    /// that draws the measured text.
+    pub fn set_glyph_rasterization(&mut self, rasterization: GlyphRasterization) {
+        if self.text.rasterization != rasterization {
+            self.text.rasterization = rasterization;
+            self.text.swash_cache.image_cache.clear();
+        }
+    }
+
+    /// Returns the options used when rasterizing glyphs.
+    #[must_use]
+    pub const fn glyph_rasterization(&self) -> GlyphRasterization {
+        self.text.rasterization
+    }
+
+    /// Returns the current text attributes.
+    #[must_use]
+    pub fn text_attrs(&self) -> Attrs<'_> {
+        self.text.attrs.as_attrs()
+    }
+
+    /// Sets the current text attributes.
+    pub fn set_text_attributes(&mut self, attrs: Attrs<'_>) {
+        self.text.attrs = AttrsOwned::new(attrs);
+    }
+
+    /// Measures `text` using the current text settings.
+    ///
+    /// The measured text keeps the shaped text, which allows it to be
+    /// [rewrapped](Self::rewrap) without shaping it again.
+    pub fn measure<'a, Unit>(&mut self, text: impl Into<Text<'a, Unit>>) -> MeasuredText<Unit>
+    where
+        Unit: figures::ScreenUnit,
+    {
+        self.text.measure(&text.into(), self.scale)
+    }
+
+    /// Measures `buffer`, using `default_color` when the buffer has no color
+    /// associated with text.
+    pub fn measure_buffer<Unit>(
+        &mut self,
+        buffer: &cosmic_text::Buffer,
+        default_color: Color,
+    ) -> MeasuredText<Unit>
+    where
+        Unit: figures::ScreenUnit,
+    {
+        measure_text::<Unit, true>(Some(buffer), default_color, &mut self.text, self.scale)
+    }
+
+    /// Lays out `text` again, wrapping it at `width`.
+    ///
+    /// This function behaves the same as [`MeasuredText::rewrap()`], except
+    /// that it uses this measurer's font system.
+    pub fn rewrap<Unit>(&mut self, text: &mut MeasuredText<Unit>, width: Option<Unit>) -> bool
+    where
+        Unit: figures::ScreenUnit,
+    {
+        text.rewrap_inner(width, &mut self.text, self.scale)
+    }
+}
+
+impl Default for TextMeasurer {
+    fn default() -> Self {
+        Self::new(cosmic_text::FontSystem::new(), Fraction::ONE)
+    }
+}
+
 /// Instructions for drawing a laid out glyph.
 #[derive(Clone)]
 pub struct MeasuredGlyph {
@@ -833,7 +1910,10 @@ impl MeasuredGlyph {
     /// whitespace or padding.
     #[must_use]
     pub const fn visible(&self) -> bool {
-        matches!(self.blit, GlyphBlit::Visible { .. })
+        matches!(
+            self.blit,
+            GlyphBlit::Visible { .. } | GlyphBlit::Pending { .. }
+        )
     }
 }
 
@@ -888,6 +1968,12 @@ pub struct Text<'a, Unit> {
     /// The width to wrap the text at. If `None`, no wrapping is performed.
     pub(crate) wrap_at: Option<Unit>,
     pub(crate) align: Option<Align>,
+    /// How lines are broken when wrapping.
+    pub(crate) wrap: TextWrap,
+    /// The maximum number of lines to lay out before truncating.
+    pub(crate) max_lines: Option<usize>,
+    /// The text appended to truncated text.
+    pub(crate) ellipsis: &'a str,
 }
 
 impl<'a, Unit> Text<'a, Unit> {
@@ -900,6 +1986,9 @@ impl<'a, Unit> Text<'a, Unit> {
             origin: TextOrigin::TopLeft,
             wrap_at: None,
             align: None,
+            wrap: TextWrap::WordOrGlyph,
+            max_lines: None,
+            ellipsis: "\u{2026}",
         }
     }
 
@@ -925,6 +2014,237 @@ impl<'a, Unit> Text<'a, Unit> {
         self.align = Some(align);
         self
     }
+
+    /// Sets how lines are broken when this text is wrapped and returns self.
+    #[must_use]
+    pub fn wrap_mode(mut self, wrap: TextWrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Limits this text to at most `lines` lines and returns self.
+    ///
+    /// When the laid out text needs more lines than this, or when a line is
+    /// wider than the [wrapping width](Self::wrap_at), the text is shortened
+    /// and the [ellipsis](Self::ellipsis) is appended so that the result fits.
+    /// A limit of 0 is treated as 1.
+    #[must_use]
+    pub fn max_lines(mut self, lines: usize) -> Self {
+        self.max_lines = Some(lines.max(1));
+        self
+    }
+
+    /// Sets the text appended when this text is truncated and returns self.
+    ///
+    /// The default ellipsis is `…`.
+    #[must_use]
+    pub fn ellipsis(mut self, ellipsis: &'a str) -> Self {
+        self.ellipsis = ellipsis;
+        self
+    }
+}
+
+/// Controls where lines are broken when wrapping [`Text`].
+#[derive(Default, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum TextWrap {
+    /// Lines are broken between words. Words that are wider than the wrapping
+    /// width are broken between glyphs.
+    #[default]
+    WordOrGlyph,
+    /// Lines are only broken between words. Words that are wider than the
+    /// wrapping width overflow it.
+    Word,
+    /// Lines are broken between any two glyphs.
+    Glyph,
+}
+
+impl From<TextWrap> for cosmic_text::Wrap {
+    fn from(wrap: TextWrap) -> Self {
+        match wrap {
+            TextWrap::WordOrGlyph => cosmic_text::Wrap::WordOrGlyph,
+            TextWrap::Word => cosmic_text::Wrap::Word,
+            TextWrap::Glyph => cosmic_text::Wrap::Glyph,
+        }
+    }
+}
+
+impl<Unit> Text<'_, Unit>
+where
+    Unit: figures::ScreenUnit,
+{
+    /// Renders this text into a new texture that is sized to fit it.
+    ///
+    /// The text is laid out using the current text settings of `graphics`,
+    /// and is rendered at its current scale. The texture's top-left corner is
+    /// the top-left of the text's layout, regardless of this text's
+    /// [origin](Self::origin). The rest of the texture is transparent.
+    ///
+    /// The text is rendered immediately rather than as part of the current
+    /// frame, and the returned texture can be drawn like any other texture.
+    /// This allows text that rarely changes to be drawn without laying it out
+    /// each frame.
+    #[must_use]
+    pub fn render_to_texture(&self, graphics: &mut Graphics<'_>) -> Texture {
+        let scale = graphics.kludgine.effective_scale;
+        graphics.kludgine.update_scratch_buffer(self);
+        let measured =
+            measure_text::<Px, false>(None, self.color, &mut graphics.kludgine.text, scale);
+        // Textures can't be empty.
+        let size = Size::new(
+            measured.size.width.ceil().into_upx(scale).max(UPx::new(1)),
+            measured.size.height.ceil().into_upx(scale).max(UPx::new(1)),
+        );
+        let prepared = graphics.prepare_text_inner(None, self.color, TextOrigin::TopLeft);
+
+        let format = graphics.kludgine.format;
+        let texture = Texture::new_generic(
+            &*graphics,
+            1,
+            size,
+            format,
+            wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::COPY_SRC,
+            wgpu::FilterMode::Linear,
+            Some("kludgine::rendered text"),
+        );
+        graphics.render_into_texture(
+            &texture,
+            &mut None,
+            "kludgine::rendered text",
+            &prepared,
+            |prepared, rendering| prepared.render(rendering),
+        );
+
+        texture
+    }
+
+    /// Returns the outlines of this text's glyphs as paths.
+    ///
+    /// The text is laid out using the current text settings of `graphics`,
+    /// and is measured at its current scale. Each glyph with an outline
+    /// produces one path, which may contain several subpaths, such as the
+    /// inner and outer edges of `o`. The paths are positioned relative to the
+    /// top-left of the text's layout, regardless of this text's
+    /// [origin](Self::origin), and their endpoints use the glyph's color.
+    ///
+    /// No glyphs are rasterized, which makes this useful for text drawn at
+    /// very large sizes. The returned paths can be filled or stroked like any
+    /// other path, or combined with other paths using
+    /// [`Path::combine()`]. Glyphs without outlines, such as whitespace and
+    /// bitmap emoji, are skipped.
+    #[must_use]
+    pub fn to_paths(&self, graphics: &mut Graphics<'_>) -> Vec<Path<Unit, false>>
+    where
+        Unit: FloatConversion<Float = f32>,
+    {
+        let scale = graphics.kludgine.effective_scale;
+        graphics.kludgine.update_scratch_buffer(self);
+        let units_per_pixel = shapes::scaled_pixel_tolerance::<Unit>(1., scale);
+        let text = &mut graphics.kludgine.text;
+        let buffer = text.scratch.as_ref().assert("initialized above");
+        let mut paths = Vec::new();
+        for run in buffer.layout_runs() {
+            for glyph in run.glyphs {
+                let physical = glyph.physical((0., 0.), 1.);
+                let Some(commands) = text
+                    .swash_cache
+                    .get_outline_commands(&mut text.fonts, physical.cache_key)
+                else {
+                    continue;
+                };
+                let origin = Point::new(
+                    glyph.font_size.mul_add(glyph.x_offset, glyph.x),
+                    (-glyph.font_size).mul_add(glyph.y_offset, run.line_y + glyph.y),
+                );
+                let skewed = physical
+                    .cache_key
+                    .flags
+                    .contains(cosmic_text::CacheKeyFlags::FAKE_ITALIC);
+                let color = glyph.color_opt.map_or(self.color, Color::from);
+                if let Some(path) = outline_path(commands, origin, skewed, color, units_per_pixel) {
+                    paths.push(path);
+                }
+            }
+        }
+        paths
+    }
+}
+
+/// Converts a glyph outline into a path whose baseline starts at `origin`,
+/// measured in pixels.
+///
+/// Outlines are measured with the y axis pointing upwards, which is flipped
+/// to match Kludgine's coordinate system. When `skewed` is true, the outline
+/// is slanted in the same way as synthesized italic glyphs are rasterized.
+fn outline_path<Unit>(
+    commands: &[swash::zeno::Command],
+    origin: Point<f32>,
+    skewed: bool,
+    color: Color,
+    units_per_pixel: f32,
+) -> Option<Path<Unit, false>>
+where
+    Unit: FloatConversion<Float = f32>,
+{
+    let slant = if skewed {
+        14_f32.to_radians().tan()
+    } else {
+        0.
+    };
+    let point = |vector: swash::zeno::Vector| {
+        Point::new(
+            Unit::from_float((origin.x + slant.mul_add(vector.y, vector.x)) * units_per_pixel),
+            Unit::from_float((origin.y - vector.y) * units_per_pixel),
+        )
+    };
+
+    let mut events = Vec::with_capacity(commands.len() + 1);
+    let mut open = false;
+    for command in commands {
+        match *command {
+            swash::zeno::Command::MoveTo(to) => {
+                if open {
+                    events.push(PathEvent::End { close: false });
+                }
+                events.push(PathEvent::Begin {
+                    at: Endpoint::new(point(to), color),
+                    texture: Point::ZERO,
+                });
+                open = true;
+            }
+            swash::zeno::Command::LineTo(to) if open => events.push(PathEvent::Line {
+                to: Endpoint::new(point(to), color),
+                texture: Point::ZERO,
+            }),
+            swash::zeno::Command::QuadTo(ctrl, to) if open => {
+                events.push(PathEvent::Quadratic {
+                    ctrl: point(ctrl),
+                    to: Endpoint::new(point(to), color),
+                    texture: Point::ZERO,
+                });
+            }
+            swash::zeno::Command::CurveTo(ctrl1, ctrl2, to) if open => {
+                events.push(PathEvent::Cubic {
+                    ctrl1: point(ctrl1),
+                    ctrl2: point(ctrl2),
+                    to: Endpoint::new(point(to), color),
+                    texture: Point::ZERO,
+                });
+            }
+            swash::zeno::Command::Close if open => {
+                events.push(PathEvent::End { close: true });
+                open = false;
+            }
+            // Commands must follow the start of a contour.
+            _ => {}
+        }
+    }
+    if open {
+        events.push(PathEvent::End { close: false });
+    }
+
+    (!events.is_empty()).then(|| events.into_iter().collect())
 }
 
 impl<'a, Unit> From<&'a str> for Text<'a, Unit> {
@@ -940,3 +2260,173 @@ impl<'a, Unit> From<&'a String> for Text<'a, Unit> {
 }
 
 impl<Unit> DrawableSource for Text<'_, Unit> {}
+
+#[cfg(all(test, feature = "app"))]
+fn test_instance(device: &wgpu::Device, queue: &wgpu::Queue) -> Kludgine {
+    Kludgine::new(
+        device,
+        queue,
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        wgpu::MultisampleState::default(),
+        Size::upx(16, 16),
+        1.,
+    )
+}
+
+#[cfg(all(test, feature = "app"))]
+fn test_glyph_key(glyph_id: u16) -> GlyphKey {
+    GlyphKey {
+        font: FontKey::Shared(0),
+        glyph_id,
+        font_size_bits: 0,
+        x_bin: cosmic_text::SubpixelBin::Zero,
+        y_bin: cosmic_text::SubpixelBin::Zero,
+        flags: cosmic_text::CacheKeyFlags::empty(),
+        hinting: false,
+        gamma_bits: 0,
+    }
+}
+
+/// Caches a 4x4 glyph identified by `glyph_id` in `cache`, returning its
+/// handle.
+#[cfg(all(test, feature = "app"))]
+fn cache_test_glyph(
+    cache: &mut GlyphCache,
+    glyph_id: u16,
+    collection: &mut TextureCollection,
+    graphics: &Graphics<'_>,
+) -> CachedGlyphHandle {
+    cache
+        .get_or_insert(test_glyph_key(glyph_id), || {
+            Some((
+                collection.push_texture(
+                    &[0; 16],
+                    wgpu::ImageDataLayout {
+                        offset: 0,
+                        bytes_per_row: Some(4),
+                        rows_per_image: None,
+                    },
+                    Size::upx(4, 4),
+                    graphics,
+                ),
+                true,
+            ))
+        })
+        .expect("glyph cached")
+}
+
+#[test]
+#[cfg(feature = "app")]
+fn glyph_cache_evicts_least_recently_used_glyphs() {
+    let Ok(window) = crate::app::headless_window(Size::upx(16, 16), |_renderer, _window| true)
+    else {
+        // No graphics adapter is available.
+        return;
+    };
+    let mut kludgine = test_instance(window.device(), window.queue());
+    let graphics = Graphics::new(&mut kludgine, window.device(), window.queue());
+    let mut collection = TextureCollection::new(
+        Size::upx(64, 64),
+        wgpu::TextureFormat::R8Unorm,
+        wgpu::FilterMode::Nearest,
+        &graphics,
+    );
+    let mut cache = GlyphCache::default();
+
+    // Each glyph occupies 16 pixels. Only the second glyph remains in use.
+    let mut handles = (0..4)
+        .map(|glyph| cache_test_glyph(&mut cache, glyph, &mut collection, &graphics))
+        .collect::<Vec<_>>();
+    let in_use = handles.swap_remove(1);
+    drop(handles);
+    assert_eq!(cache.area, 64);
+    assert_eq!(cache.evict_unused(64), 0);
+
+    // Drawing the first glyph again makes it the most recently used.
+    drop(cache_test_glyph(&mut cache, 0, &mut collection, &graphics));
+    assert_eq!(cache.evict_unused(32), 2);
+    assert_eq!(cache.area, 32);
+    assert!(cache.contains(&test_glyph_key(0)));
+    assert!(cache.contains(&test_glyph_key(1)));
+    assert!(!cache.contains(&test_glyph_key(2)));
+    assert!(!cache.contains(&test_glyph_key(3)));
+
+    // Glyphs that are in use are kept, even when they exceed the budget.
+    assert_eq!(cache.evict_unused(0), 1);
+    assert!(cache.contains(&test_glyph_key(1)));
+    assert_eq!(cache.area, 16);
+
+    drop(in_use);
+    assert_eq!(cache.evict_unused(0), 1);
+    assert_eq!(cache.area, 0);
+    assert_eq!(cache.evictions, 4);
+}
+
+#[test]
+#[cfg(feature = "app")]
+fn glyph_atlas_reports_rasterized_and_evicted_glyphs() {
+    let Ok(window) = crate::app::headless_window(Size::upx(16, 16), |_renderer, _window| true)
+    else {
+        // No graphics adapter is available.
+        return;
+    };
+    let mut textures = test_instance(window.device(), window.queue());
+    let graphics = Graphics::new(&mut textures, window.device(), window.queue());
+    let mut collection = TextureCollection::new(
+        Size::upx(64, 64),
+        wgpu::TextureFormat::R8Unorm,
+        wgpu::FilterMode::Nearest,
+        &graphics,
+    );
+
+    let mut kludgine = test_instance(window.device(), window.queue());
+    kludgine.set_glyph_atlas_budget(16);
+    let mut data = kludgine.glyph_atlas.data.lock();
+    for glyph in 0..3 {
+        drop(cache_test_glyph(
+            &mut data.glyphs,
+            glyph,
+            &mut collection,
+            &graphics,
+        ));
+    }
+    drop(data);
+    kludgine.glyph_atlas.rasterized = 3;
+
+    // Beginning a frame reports the glyphs rasterized during the previous
+    // frame and evicts glyphs until the cache fits within its budget.
+    drop(kludgine.next_frame());
+    let stats = kludgine.last_frame_stats();
+    assert_eq!(stats.glyphs_rasterized, 3);
+    assert_eq!(stats.glyphs_evicted, 2);
+
+    drop(kludgine.next_frame());
+    let stats = kludgine.last_frame_stats();
+    assert_eq!(stats.glyphs_rasterized, 0);
+    assert_eq!(stats.glyphs_evicted, 0);
+}
+
+#[test]
+#[cfg(feature = "app")]
+fn glyph_atlases_require_the_same_device() {
+    let (Ok(first), Ok(second)) = (
+        crate::app::headless_window(Size::upx(16, 16), |_renderer, _window| true),
+        crate::app::headless_window(Size::upx(16, 16), |_renderer, _window| true),
+    ) else {
+        // No graphics adapter is available.
+        return;
+    };
+    let mut owner = test_instance(first.device(), first.queue());
+    let atlas = owner.shared_glyph_atlas();
+
+    let mut same_device = test_instance(first.device(), first.queue());
+    same_device.set_glyph_atlas(&atlas).expect("same device");
+    assert_eq!(same_device.shared_glyph_atlas(), atlas);
+
+    let mut other_device = test_instance(second.device(), second.queue());
+    assert!(matches!(
+        other_device.set_glyph_atlas(&atlas),
+        Err(Error::DeviceMismatch)
+    ));
+    assert_ne!(other_device.shared_glyph_atlas(), atlas);
+}
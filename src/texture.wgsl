@@ -0,0 +1,21 @@
+@group(0)
+@binding(1)
+var r_texture: texture_2d<f32>;
+@group(0)
+@binding(2)
+var r_sampler: sampler;
+
+@vertex
+fn vertex(input: VertexInput) -> VertexOutput {
+    var outval = transform(input);
+    outval.texture = u32(0);
+    return outval;
+}
+
+fn sample_texture(uv: vec2<f32>, texture: u32) -> vec4<f32> {
+    return textureSample(
+        r_texture,
+        r_sampler,
+        uv / vec2<f32>(textureDimensions(r_texture)),
+    );
+}
@@ -0,0 +1,35 @@
+// The array's length must match `TEXTURE_ARRAY_SIZE` in pipeline.rs.
+@group(0)
+@binding(1)
+var r_textures: binding_array<texture_2d<f32>, 8>;
+@group(0)
+@binding(2)
+var r_nearest_sampler: sampler;
+@group(0)
+@binding(3)
+var r_linear_sampler: sampler;
+
+@vertex
+fn vertex(input: VertexInput, @location(3) texture: u32) -> VertexOutput {
+    var outval = transform(input);
+    outval.texture = texture;
+    return outval;
+}
+
+fn sample_texture(uv: vec2<f32>, texture: u32) -> vec4<f32> {
+    // Must match `TEXTURE_SLOT_LINEAR` in pipeline.rs.
+    let slot_linear = u32(1) << u32(8);
+
+    // Each vertex provides its texture's slot, which allows draws using
+    // different textures in the same array to be merged.
+    let index = texture & u32(7);
+    let coords = uv / vec2<f32>(textureDimensions(r_textures[index]));
+    // The slot is not uniform, so the derivatives must be computed before
+    // choosing the sampler.
+    let ddx = dpdx(coords);
+    let ddy = dpdy(coords);
+    if (texture & slot_linear) != u32(0) {
+        return textureSampleGrad(r_textures[index], r_linear_sampler, coords, ddx, ddy);
+    }
+    return textureSampleGrad(r_textures[index], r_nearest_sampler, coords, ddx, ddy);
+}
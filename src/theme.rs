@@ -0,0 +1,246 @@
+use crate::Color;
+
+/// Whether colors should be chosen for a light or dark appearance.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Default)]
+pub enum ThemeMode {
+    /// Dark text on light backgrounds.
+    Light,
+    /// Light text on dark backgrounds.
+    #[default]
+    Dark,
+}
+
+impl ThemeMode {
+    /// Returns the opposite mode.
+    #[must_use]
+    pub const fn inverse(self) -> Self {
+        match self {
+            ThemeMode::Light => ThemeMode::Dark,
+            ThemeMode::Dark => ThemeMode::Light,
+        }
+    }
+}
+
+#[cfg(feature = "app")]
+impl From<appit::winit::window::Theme> for ThemeMode {
+    fn from(theme: appit::winit::window::Theme) -> Self {
+        match theme {
+            appit::winit::window::Theme::Light => ThemeMode::Light,
+            appit::winit::window::Theme::Dark => ThemeMode::Dark,
+        }
+    }
+}
+
+#[cfg(feature = "app")]
+impl From<ThemeMode> for appit::winit::window::Theme {
+    fn from(mode: ThemeMode) -> Self {
+        match mode {
+            ThemeMode::Light => appit::winit::window::Theme::Light,
+            ThemeMode::Dark => appit::winit::window::Theme::Dark,
+        }
+    }
+}
+
+/// A color with separate values for light and dark themes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct VariableColor {
+    /// The color used by [`ThemeMode::Light`].
+    pub light: Color,
+    /// The color used by [`ThemeMode::Dark`].
+    pub dark: Color,
+}
+
+impl VariableColor {
+    /// Returns a color that is `light` in light mode and `dark` in dark mode.
+    #[must_use]
+    pub const fn new(light: Color, dark: Color) -> Self {
+        Self { light, dark }
+    }
+
+    /// Returns a color that is `color` in both modes.
+    #[must_use]
+    pub const fn uniform(color: Color) -> Self {
+        Self::new(color, color)
+    }
+
+    /// Returns a color that is `light` in light mode and an automatically
+    /// derived variant in dark mode.
+    ///
+    /// The dark variant mirrors the perceived lightness of `light`: a near
+    /// white background becomes near black, while mid-tone accent colors are
+    /// mostly unchanged. The hue is preserved.
+    #[must_use]
+    pub fn adaptive(light: Color) -> Self {
+        Self::new(light, light.lighten(1. - 2. * light.lightness()))
+    }
+
+    /// Returns the color for `mode`.
+    #[must_use]
+    pub const fn get(self, mode: ThemeMode) -> Color {
+        match mode {
+            ThemeMode::Light => self.light,
+            ThemeMode::Dark => self.dark,
+        }
+    }
+
+    /// Returns this color with both variants lightened by `amount`.
+    #[must_use]
+    pub fn lighten(self, amount: f32) -> Self {
+        Self::new(self.light.lighten(amount), self.dark.lighten(amount))
+    }
+
+    /// Returns this color with both variants darkened by `amount`.
+    #[must_use]
+    pub fn darken(self, amount: f32) -> Self {
+        self.lighten(-amount)
+    }
+
+    /// Returns this color adjusted by `amount` to contrast more with the
+    /// background of each mode.
+    ///
+    /// The light variant is darkened and the dark variant is lightened. A
+    /// negative `amount` blends each variant towards its background instead.
+    #[must_use]
+    pub fn emphasize(self, amount: f32) -> Self {
+        Self::new(self.light.darken(amount), self.dark.lighten(amount))
+    }
+}
+
+impl From<Color> for VariableColor {
+    fn from(color: Color) -> Self {
+        Self::uniform(color)
+    }
+}
+
+/// A set of named colors for light and dark themes.
+///
+/// Kludgine's `gui` module and applications can share a palette
+/// to keep their colors consistent. Use [`Palette::colors()`] to resolve the
+/// palette for a [`ThemeMode`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Palette {
+    /// The color drawn behind all other content.
+    pub background: VariableColor,
+    /// The color of panels and controls drawn above the background.
+    pub surface: VariableColor,
+    /// The color of text and icons.
+    pub text: VariableColor,
+    /// The color of secondary text, such as hints and disabled labels.
+    pub muted_text: VariableColor,
+    /// The color of borders and dividers.
+    pub outline: VariableColor,
+    /// The color used to highlight selected or primary content.
+    pub primary: VariableColor,
+    /// The color used for errors.
+    pub error: VariableColor,
+    /// The color used for warnings.
+    pub warning: VariableColor,
+    /// The color used to indicate success.
+    pub success: VariableColor,
+}
+
+impl Palette {
+    /// Returns a palette whose dark colors are all derived from `light` using
+    /// [`VariableColor::adaptive()`].
+    #[must_use]
+    pub fn adaptive(light: ColorSet) -> Self {
+        Self {
+            background: VariableColor::adaptive(light.background),
+            surface: VariableColor::adaptive(light.surface),
+            text: VariableColor::adaptive(light.text),
+            muted_text: VariableColor::adaptive(light.muted_text),
+            outline: VariableColor::adaptive(light.outline),
+            primary: VariableColor::adaptive(light.primary),
+            error: VariableColor::adaptive(light.error),
+            warning: VariableColor::adaptive(light.warning),
+            success: VariableColor::adaptive(light.success),
+        }
+    }
+
+    /// Returns the colors of this palette for `mode`.
+    #[must_use]
+    pub const fn colors(&self, mode: ThemeMode) -> ColorSet {
+        ColorSet {
+            background: self.background.get(mode),
+            surface: self.surface.get(mode),
+            text: self.text.get(mode),
+            muted_text: self.muted_text.get(mode),
+            outline: self.outline.get(mode),
+            primary: self.primary.get(mode),
+            error: self.error.get(mode),
+            warning: self.warning.get(mode),
+            success: self.success.get(mode),
+        }
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            background: VariableColor::new(
+                Color::new(245, 245, 247, 255),
+                Color::new(24, 24, 28, 255),
+            ),
+            surface: VariableColor::new(
+                Color::new(228, 228, 233, 255),
+                Color::new(50, 50, 56, 255),
+            ),
+            text: VariableColor::new(Color::new(28, 28, 32, 255), Color::new(230, 230, 235, 255)),
+            muted_text: VariableColor::new(
+                Color::new(110, 110, 118, 255),
+                Color::new(150, 150, 158, 255),
+            ),
+            outline: VariableColor::new(
+                Color::new(170, 170, 178, 255),
+                Color::new(100, 100, 110, 255),
+            ),
+            primary: VariableColor::new(
+                Color::new(40, 100, 220, 255),
+                Color::new(82, 139, 255, 255),
+            ),
+            error: VariableColor::new(Color::new(200, 40, 40, 255), Color::new(255, 96, 96, 255)),
+            warning: VariableColor::new(
+                Color::new(190, 120, 0, 255),
+                Color::new(255, 190, 70, 255),
+            ),
+            success: VariableColor::new(
+                Color::new(30, 140, 60, 255),
+                Color::new(90, 210, 120, 255),
+            ),
+        }
+    }
+}
+
+/// The colors of a [`Palette`] for a single [`ThemeMode`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct ColorSet {
+    /// The color drawn behind all other content.
+    pub background: Color,
+    /// The color of panels and controls drawn above the background.
+    pub surface: Color,
+    /// The color of text and icons.
+    pub text: Color,
+    /// The color of secondary text, such as hints and disabled labels.
+    pub muted_text: Color,
+    /// The color of borders and dividers.
+    pub outline: Color,
+    /// The color used to highlight selected or primary content.
+    pub primary: Color,
+    /// The color used for errors.
+    pub error: Color,
+    /// The color used for warnings.
+    pub warning: Color,
+    /// The color used to indicate success.
+    pub success: Color,
+}
+
+#[test]
+fn adaptive_colors() {
+    let background = VariableColor::adaptive(Color::WHITE);
+    assert_eq!(background.get(ThemeMode::Light), Color::WHITE);
+    assert!(background.get(ThemeMode::Dark).lightness() < 0.01);
+
+    let emphasized = VariableColor::uniform(Color::GRAY).emphasize(0.1);
+    assert!(emphasized.light.lightness() < Color::GRAY.lightness());
+    assert!(emphasized.dark.lightness() > Color::GRAY.lightness());
+}
@@ -0,0 +1,158 @@
+use figures::units::UPx;
+use figures::Size;
+
+/// A coarse classification of a graphics device's capabilities.
+///
+/// Tiers are determined by heuristics based on the adapter's type, backend,
+/// and limits. They are intended to pick reasonable defaults the first time
+/// an application runs, not to replace user-configurable settings.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum DeviceTier {
+    /// Software renderers, WebGL-class devices, and devices with small
+    /// texture limits.
+    Low,
+    /// Integrated and virtual GPUs.
+    Medium,
+    /// Discrete GPUs supporting large textures.
+    High,
+}
+
+impl DeviceTier {
+    /// Returns the tier of a device described by `info` and `limits`.
+    #[must_use]
+    pub fn classify(info: &wgpu::AdapterInfo, limits: &wgpu::Limits) -> Self {
+        if info.device_type == wgpu::DeviceType::Cpu
+            || info.backend == wgpu::Backend::Gl
+            || limits.max_texture_dimension_2d < 4096
+            || limits.max_storage_buffers_per_shader_stage == 0
+        {
+            Self::Low
+        } else if info.device_type == wgpu::DeviceType::DiscreteGpu
+            && limits.max_texture_dimension_2d >= 16384
+        {
+            Self::High
+        } else {
+            Self::Medium
+        }
+    }
+
+    /// Returns the tier of `adapter`.
+    #[must_use]
+    pub fn of(adapter: &wgpu::Adapter) -> Self {
+        Self::classify(&adapter.get_info(), &adapter.limits())
+    }
+
+    /// Returns the settings suggested for this tier, restricted to what
+    /// `limits` allow.
+    ///
+    /// This function assumes that multisampling is supported. Use
+    /// [`SuggestedSettings::for_adapter()`] to also verify the multisample
+    /// count is supported by a specific texture format.
+    #[must_use]
+    pub fn suggested_settings(self, limits: &wgpu::Limits) -> SuggestedSettings {
+        let (atlas_size, msaa_samples, post_effects) = match self {
+            DeviceTier::Low => (1024, 1, false),
+            DeviceTier::Medium => (2048, 4, false),
+            DeviceTier::High => (4096, 4, true),
+        };
+        SuggestedSettings {
+            tier: self,
+            atlas_size: Size::squared(UPx::new(atlas_size.min(limits.max_texture_dimension_2d))),
+            msaa_samples,
+            post_effects,
+        }
+    }
+}
+
+/// Rendering settings suggested for a [`DeviceTier`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SuggestedSettings {
+    /// The tier these settings were chosen for.
+    pub tier: DeviceTier,
+    /// The initial size of texture atlases, such as a
+    /// [`TextureCollection`](crate::TextureCollection).
+    pub atlas_size: Size<UPx>,
+    /// The number of samples to use for multisample anti-aliasing. A value of
+    /// 1 disables multisampling.
+    pub msaa_samples: u32,
+    /// Whether full-screen post-processing effects should be enabled.
+    pub post_effects: bool,
+}
+
+impl SuggestedSettings {
+    /// Returns the suggested settings for rendering to `format` using
+    /// `adapter`.
+    #[must_use]
+    pub fn for_adapter(adapter: &wgpu::Adapter, format: wgpu::TextureFormat) -> Self {
+        let mut settings = DeviceTier::of(adapter).suggested_settings(&adapter.limits());
+        if !adapter
+            .get_texture_format_features(format)
+            .flags
+            .sample_count_supported(settings.msaa_samples)
+        {
+            settings.msaa_samples = 1;
+        }
+        settings
+    }
+
+    /// Returns a multisample state using [`msaa_samples`](Self::msaa_samples).
+    #[must_use]
+    pub fn multisample_state(&self) -> wgpu::MultisampleState {
+        wgpu::MultisampleState {
+            count: self.msaa_samples,
+            ..wgpu::MultisampleState::default()
+        }
+    }
+}
+
+#[test]
+fn tiers() {
+    let info = |device_type, backend| wgpu::AdapterInfo {
+        name: String::new(),
+        vendor: 0,
+        device: 0,
+        device_type,
+        driver: String::new(),
+        driver_info: String::new(),
+        backend,
+    };
+    let desktop = wgpu::Limits::default();
+    assert_eq!(
+        DeviceTier::classify(
+            &info(wgpu::DeviceType::DiscreteGpu, wgpu::Backend::Vulkan),
+            &wgpu::Limits {
+                max_texture_dimension_2d: 16384,
+                ..desktop.clone()
+            }
+        ),
+        DeviceTier::High
+    );
+    assert_eq!(
+        DeviceTier::classify(
+            &info(wgpu::DeviceType::IntegratedGpu, wgpu::Backend::Metal),
+            &desktop
+        ),
+        DeviceTier::Medium
+    );
+    assert_eq!(
+        DeviceTier::classify(
+            &info(wgpu::DeviceType::DiscreteGpu, wgpu::Backend::Gl),
+            &desktop
+        ),
+        DeviceTier::Low
+    );
+    assert_eq!(
+        DeviceTier::classify(
+            &info(wgpu::DeviceType::IntegratedGpu, wgpu::Backend::Vulkan),
+            &wgpu::Limits::downlevel_webgl2_defaults()
+        ),
+        DeviceTier::Low
+    );
+
+    let settings = DeviceTier::High.suggested_settings(&wgpu::Limits {
+        max_texture_dimension_2d: 2048,
+        ..desktop
+    });
+    assert_eq!(settings.atlas_size, Size::squared(UPx::new(2048)));
+    assert!(settings.post_effects);
+}
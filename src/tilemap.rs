@@ -5,7 +5,7 @@ use std::ops::{Deref, DerefMut, Index, IndexMut};
 use std::time::Duration;
 
 use alot::{LotId, OrderedLots};
-use figures::{Fraction, Ranged, Zero};
+use figures::{Fraction, Ranged, Round, Zero};
 use intentional::Cast;
 
 use crate::drawing::Renderer;
@@ -14,7 +14,7 @@ use crate::figures::{IntoSigned, Point, Rect, Size};
 use crate::shapes::{PathBuilder, Shape, StrokeOptions};
 use crate::sprite::Sprite;
 use crate::text::Text;
-use crate::{AnyTexture, Assert, Color, DrawableExt};
+use crate::{AnyTexture, Assert, Color, DrawableExt, TexelSampling};
 
 pub const TILE_SIZE: Px = Px::new(32);
 
@@ -34,6 +34,13 @@ pub fn translate_coordinates(
     Point::new(coordinate.x / effective_zoom, coordinate.y / effective_zoom)
 }
 
+/// Draws `layers` centered on `focus`.
+///
+/// The layers are drawn in a [`SpriteBatch`](crate::drawing::SpriteBatch).
+/// When `graphics` is a batch created with
+/// [`Renderer::batched()`](Renderer::batched), the tiles and any sprites drawn
+/// through the batch that share a texture are merged into as few draw calls as
+/// possible.
 pub fn draw(
     layers: &mut impl Layers,
     focus: TileMapFocus,
@@ -41,6 +48,53 @@ pub fn draw(
     elapsed: Duration,
     graphics: &mut Renderer<'_, '_>,
 ) -> Option<Duration> {
+    draw_with_mode(
+        layers,
+        focus,
+        zoom,
+        elapsed,
+        TileRenderMode::default(),
+        graphics,
+    )
+}
+
+/// Controls how tiles are positioned when the tile map is rendered.
+#[derive(Default, Clone, Copy, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TileRenderMode {
+    /// Tiles are rendered at their exact locations, which may be between
+    /// pixels at fractional zoom levels. This can cause hairline cracks to
+    /// appear between tiles.
+    #[default]
+    Exact,
+    /// Each tile's edges are snapped to whole pixels such that adjacent tiles
+    /// always share an edge, and textures are sampled using
+    /// [`TexelSampling::Centers`]. This eliminates seams between tiles at
+    /// fractional zoom levels.
+    SeamFree,
+}
+
+/// Draws `layers` centered on `focus`, positioning tiles using `mode`.
+///
+/// [`draw()`] renders using [`TileRenderMode::Exact`]. When the map is drawn
+/// at a fractional zoom or display scale, tile edges can fall between pixels,
+/// which can leave hairline cracks between tiles. Rendering with
+/// [`TileRenderMode::SeamFree`] snaps each tile to whole pixels so that
+/// neighboring tiles always share an edge.
+///
+/// `zoom` is applied in addition to the scale of `graphics`. Like [`draw()`],
+/// the layers are drawn in a [`SpriteBatch`](crate::drawing::SpriteBatch).
+/// Returns the amount of time until the next animated tile's frame should be
+/// shown, if any tiles are animating.
+pub fn draw_with_mode(
+    layers: &mut impl Layers,
+    focus: TileMapFocus,
+    zoom: f32,
+    elapsed: Duration,
+    mode: TileRenderMode,
+    graphics: &mut Renderer<'_, '_>,
+) -> Option<Duration> {
+    let mut graphics = graphics.batched();
     let effective_zoom = graphics.scale().into_f32() * zoom;
     let mut remaining_until_next_frame = None;
 
@@ -61,7 +115,8 @@ pub fn draw(
         visible_rect: visible_region,
         zoom,
         elapsed,
-        renderer: graphics,
+        mode,
+        renderer: &mut graphics,
     };
     for index in 0.. {
         let Some(layer) = layers.layer_mut(index) else {
@@ -176,6 +231,7 @@ pub struct LayerContext<'render, 'ctx, 'pass> {
     visible_rect: Rect<Px>,
     zoom: f32,
     elapsed: Duration,
+    mode: TileRenderMode,
     renderer: &'render mut Renderer<'ctx, 'pass>,
 }
 
@@ -214,6 +270,168 @@ impl LayerContext<'_, '_, '_> {
     pub const fn zoom(&self) -> f32 {
         self.zoom
     }
+
+    #[must_use]
+    pub const fn render_mode(&self) -> TileRenderMode {
+        self.mode
+    }
+
+    /// Returns the texel sampling mode textured tiles should be drawn with.
+    #[must_use]
+    pub const fn texel_sampling(&self) -> TexelSampling {
+        match self.mode {
+            TileRenderMode::Exact => TexelSampling::Edges,
+            TileRenderMode::SeamFree => TexelSampling::Centers,
+        }
+    }
+
+    /// Returns the rectangle a tile whose top-left corner is at `origin`
+    /// should be drawn in, honoring the current [`TileRenderMode`].
+    #[must_use]
+    pub fn tile_rect(&self, origin: Point<Px>) -> Rect<Px> {
+        match self.mode {
+            TileRenderMode::Exact => Rect::new(origin, Size::squared(self.tile_size)),
+            TileRenderMode::SeamFree => snapped_tile_rect(origin, self.tile_size),
+        }
+    }
+}
+
+fn snapped_tile_rect(origin: Point<Px>, tile_size: Px) -> Rect<Px> {
+    // Rounding both edges independently guarantees that the right edge of a
+    // tile is the same pixel as the left edge of its neighbor.
+    let top_left = origin.round();
+    let bottom_right = (origin + Point::squared(tile_size)).round();
+    Rect::new(
+        top_left,
+        Size::new(bottom_right.x - top_left.x, bottom_right.y - top_left.y),
+    )
+}
+
+#[test]
+fn seam_free_tiles_share_edges() {
+    for zoom in [
+        0.3, 0.5, 0.75, 1., 1.1, 1.25, 1.333, 1.5, 1.75, 2.2, 2.5, 3.7,
+    ] {
+        let tile_size = TILE_SIZE * zoom;
+        let mut x = Px::new(-7) * zoom;
+        let mut previous: Option<Rect<Px>> = None;
+        for _ in 0..64 {
+            let rect = snapped_tile_rect(Point::new(x, x), tile_size);
+            assert_eq!(rect.origin, rect.origin.round());
+            if let Some(previous) = previous {
+                let (_, previous_bottom_right) = previous.extents();
+                assert_eq!(previous_bottom_right, rect.origin, "seam at zoom {zoom}");
+            }
+            previous = Some(rect);
+            x += tile_size;
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "app")]
+fn seam_free_tiles_render_without_gaps() {
+    use figures::UPx2D;
+
+    use crate::Texture;
+
+    /// Covers the entire visible area with a single kind of tile.
+    #[derive(Debug)]
+    struct Everywhere(TileKind);
+
+    impl TileSource for Everywhere {
+        fn render(
+            &mut self,
+            _coordinate: Point<isize>,
+            rect: Rect<Px>,
+            context: &mut LayerContext<'_, '_, '_>,
+        ) -> Option<Duration> {
+            self.0.render(rect, context)
+        }
+    }
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+    const WHITE: [u8; 4] = [255; 4];
+
+    // The left column of the tile is red, the right column is blue, and the
+    // rest is white. Linear filtering within a tile only blends white with one
+    // of the edge colors, so any other color is either a gap that shows the
+    // black background, or a tile sampling texels from its opposite edge.
+    let mut tile = Vec::with_capacity(32 * 32 * 4);
+    for _y in 0..32 {
+        for x in 0..32 {
+            tile.extend_from_slice(match x {
+                0 => &RED,
+                31 => &BLUE,
+                _ => &WHITE,
+            });
+        }
+    }
+    let is_white_or_blended_with = |pixel: &[u8], edge: [u8; 4]| {
+        // Channels that are saturated in the edge color remain saturated, and
+        // the remaining channels fade from white together.
+        let mut faded = pixel
+            .iter()
+            .zip(edge)
+            .filter(|(_, edge)| *edge == 0)
+            .map(|(&channel, _)| channel);
+        let first = faded.next();
+        pixel
+            .iter()
+            .zip(edge)
+            .all(|(&channel, edge)| edge == 0 || channel == 255)
+            && faded.all(|channel| Some(channel) == first)
+    };
+
+    for zoom in [0.75, 1.1, 1.333, 1.5, 2.2, 3.7] {
+        let tile = tile.clone();
+        let Ok(mut window) =
+            crate::app::headless_window(Size::upx(128, 128), move |mut renderer, _window| {
+                let texture = Texture::new_with_data(
+                    &renderer,
+                    Size::upx(32, 32),
+                    wgpu::TextureFormat::Rgba8UnormSrgb,
+                    wgpu::TextureUsages::TEXTURE_BINDING,
+                    wgpu::FilterMode::Linear,
+                    &tile,
+                );
+                draw_with_mode(
+                    &mut Everywhere(TileKind::Texture(AnyTexture::from(texture))),
+                    TileMapFocus::Point(Point::new(Px::new(5), Px::new(7))),
+                    zoom,
+                    Duration::ZERO,
+                    TileRenderMode::SeamFree,
+                    &mut renderer,
+                );
+                true
+            })
+        else {
+            eprintln!("skipping seam_free_tiles_render_without_gaps: no graphics adapter");
+            return;
+        };
+        window.render();
+        let pixels = window.read_pixels().expect("pixels read");
+        if let Some(gap) = pixels.chunks_exact(4).position(|pixel| {
+            !is_white_or_blended_with(pixel, RED) && !is_white_or_blended_with(pixel, BLUE)
+        }) {
+            panic!(
+                "seam at ({}, {}) at zoom {zoom}: {:?}",
+                gap % 128,
+                gap / 128,
+                &pixels[gap * 4..gap * 4 + 4]
+            );
+        }
+        // Both edges of each tile must still be drawn.
+        for edge in [RED, BLUE] {
+            assert!(
+                pixels
+                    .chunks_exact(4)
+                    .any(|pixel| pixel != WHITE && is_white_or_blended_with(pixel, edge)),
+                "{edge:?} edge missing at zoom {zoom}"
+            );
+        }
+    }
 }
 
 impl<'ctx, 'pass> Deref for LayerContext<'_, 'ctx, 'pass> {
@@ -284,7 +502,7 @@ where
             for y_index in top..=bottom {
                 let mut x = x;
                 for x_index in left..=right {
-                    let tile_rect = Rect::new(Point::new(x, y), Size::squared(context.tile_size()));
+                    let tile_rect = context.tile_rect(Point::new(x, y));
                     remaining_until_next_frame = minimum_duration(
                         remaining_until_next_frame,
                         self.render(Point::new(x_index, y_index), tile_rect, context),
@@ -399,7 +617,8 @@ impl TileKind {
             TileKind::Texture(texture) => {
                 // TODO support other scaling options like
                 // aspect-fit rather than fill.
-                context.draw_texture(texture, tile_rect, 1.);
+                let sampling = context.texel_sampling();
+                context.draw_texture_sampled(texture, tile_rect, sampling, 1.);
                 None
             }
             TileKind::Color(color) => {
@@ -408,7 +627,8 @@ impl TileKind {
             }
             TileKind::Sprite(sprite) => {
                 if let Ok(frame) = sprite.get_frame(Some(context.elapsed())) {
-                    context.draw_texture(&frame, tile_rect, 1.);
+                    let sampling = context.texel_sampling();
+                    context.draw_texture_sampled(&frame, tile_rect, sampling, 1.);
                     sprite.remaining_frame_duration().ok().flatten()
                 } else {
                     // TODO show a broken image?
@@ -592,3 +812,62 @@ impl TileSource for DebugGrid {
         None
     }
 }
+
+#[test]
+#[cfg(feature = "app")]
+fn batched_tiles_and_sprites() {
+    use figures::{Px2D, UPx2D};
+
+    use crate::{SharedTexture, Texture, TextureRegion};
+
+    let Ok(mut window) =
+        crate::app::headless_window(Size::upx(128, 128), |mut renderer, _window| {
+            let texture = SharedTexture::from(Texture::new_with_data(
+                &renderer,
+                Size::upx(64, 32),
+                wgpu::TextureFormat::Rgba8UnormSrgb,
+                wgpu::TextureUsages::TEXTURE_BINDING,
+                wgpu::FilterMode::Nearest,
+                &[255; 64 * 32 * 4],
+            ));
+            let grass = TextureRegion::new(
+                texture.clone(),
+                Rect::new(Point::upx(0, 0), Size::upx(32, 32)),
+            );
+            let mut map = TileArray::new(
+                2,
+                (0..4)
+                    .map(|_| TileKind::Texture(AnyTexture::from(grass.clone())))
+                    .collect::<Vec<_>>(),
+            );
+            let mut player = Sprite::single_frame(texture);
+
+            let mut batch = renderer.batched();
+            draw(
+                &mut map,
+                TileMapFocus::default(),
+                1.,
+                Duration::ZERO,
+                &mut batch,
+            );
+            batch.draw_shape(&Shape::filled_rect(
+                Rect::new(Point::px(0, 0), Size::px(16, 16)),
+                Color::RED,
+            ));
+            batch.draw_sprite(
+                &mut player,
+                Rect::new(Point::px(80, 80), Size::px(32, 32)),
+                None,
+                1.,
+            );
+            true
+        })
+    else {
+        // No graphics adapter is available.
+        return;
+    };
+    window.render();
+    // The four tiles and the sprite above them are drawn in a single draw
+    // call, and the shape that doesn't overlap the sprite in a second.
+    assert_eq!(window.behavior().drawing().command_count(), 2);
+}
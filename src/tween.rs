@@ -0,0 +1,694 @@
+use std::collections::HashMap;
+use std::sync::atomic::{self, AtomicU64};
+use std::time::{Duration, Instant};
+
+use crate::Color;
+
+/// A value that can be interpolated between two instances of itself.
+pub trait Interpolate: Sized {
+    /// Returns the value `fraction` of the way from `self` to `target`.
+    ///
+    /// `fraction` ranges from 0.0 to 1.0. A value of 0.0 returns `self` and
+    /// 1.0 returns `target`.
+    #[must_use]
+    fn interpolate(&self, target: &Self, fraction: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    fn interpolate(&self, target: &Self, fraction: f32) -> Self {
+        self + (target - self) * fraction
+    }
+}
+
+/// Colors are interpolated in the perceptual Oklab color space using
+/// [`Color::mix_oklab`].
+impl Interpolate for Color {
+    fn interpolate(&self, target: &Self, fraction: f32) -> Self {
+        self.mix_oklab(*target, fraction)
+    }
+}
+
+/// A function that adjusts the rate of change of a [`Tween`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Default)]
+#[non_exhaustive]
+pub enum Easing {
+    /// Changes at a constant rate.
+    #[default]
+    Linear,
+    /// Starts slowly and accelerates.
+    EaseIn,
+    /// Starts quickly and decelerates.
+    EaseOut,
+    /// Starts slowly, accelerates, and decelerates at the end.
+    EaseInOut,
+    /// Overshoots the end and settles on it like a damped spring.
+    Spring,
+}
+
+impl Easing {
+    /// Returns the eased progress for `progress`, which is clamped to
+    /// `0.0..=1.0`.
+    #[must_use]
+    pub fn ease(self, progress: f32) -> f32 {
+        let progress = progress.clamp(0., 1.);
+        match self {
+            Easing::Linear => progress,
+            Easing::EaseIn => progress * progress * progress,
+            Easing::EaseOut => 1. - (1. - progress).powi(3),
+            Easing::EaseInOut => {
+                if progress < 0.5 {
+                    4. * progress * progress * progress
+                } else {
+                    1. - (-2. * progress + 2.).powi(3) / 2.
+                }
+            }
+            Easing::Spring => {
+                if progress >= 1. {
+                    1.
+                } else {
+                    1. - (-6. * progress).exp() * (progress * 4.5 * std::f32::consts::PI).cos()
+                }
+            }
+        }
+    }
+}
+
+/// A value that animates from a start value to an end value over a duration.
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use kludgine::tween::{Easing, Tween};
+/// use kludgine::Color;
+///
+/// let mut flash = Tween::new(Color::RED, Color::WHITE, Duration::from_millis(200))
+///     .with_easing(Easing::EaseOut);
+/// let tint = flash.advance(Duration::from_millis(16));
+/// # let _ = tint;
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tween<T> {
+    start: T,
+    end: T,
+    duration: Duration,
+    elapsed: Duration,
+    easing: Easing,
+}
+
+impl<T> Tween<T>
+where
+    T: Interpolate + Clone,
+{
+    /// Returns a new tween from `start` to `end` taking `duration`.
+    #[must_use]
+    pub const fn new(start: T, end: T, duration: Duration) -> Self {
+        Self {
+            start,
+            end,
+            duration,
+            elapsed: Duration::ZERO,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Sets the easing function and returns self.
+    #[must_use]
+    pub const fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Advances this tween by `elapsed` and returns the current value.
+    pub fn advance(&mut self, elapsed: Duration) -> T {
+        self.elapsed = self.elapsed.saturating_add(elapsed).min(self.duration);
+        self.value()
+    }
+
+    /// Restarts this tween from its start value.
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+    }
+
+    /// Returns the current value of this tween.
+    #[must_use]
+    pub fn value(&self) -> T {
+        if self.is_complete() {
+            self.end.clone()
+        } else {
+            self.start
+                .interpolate(&self.end, self.easing.ease(self.progress()))
+        }
+    }
+
+    /// Returns the linear progress of this tween, ranging from 0.0 to 1.0.
+    #[must_use]
+    pub fn progress(&self) -> f32 {
+        if self.duration.is_zero() {
+            1.
+        } else {
+            self.elapsed.as_secs_f32() / self.duration.as_secs_f32()
+        }
+    }
+
+    /// Returns true if this tween has reached its end value.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Returns the amount of time remaining before this tween completes.
+    #[must_use]
+    pub fn remaining(&self) -> Duration {
+        self.duration.saturating_sub(self.elapsed)
+    }
+}
+
+/// A value that is pulled towards a target by a damped spring.
+///
+/// Unlike a [`Tween`], a spring has no fixed duration. Changing the target of
+/// a spring while it is moving preserves its velocity, which makes springs
+/// well suited for values that follow user input.
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use kludgine::tween::Spring;
+///
+/// let mut offset = Spring::new(0.);
+/// offset.set_target(100.);
+/// while !offset.is_settled() {
+///     offset.advance(Duration::from_millis(16));
+/// }
+/// assert_eq!(offset.value(), 100.);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spring {
+    value: f32,
+    velocity: f32,
+    target: f32,
+    stiffness: f32,
+    damping: f32,
+}
+
+impl Spring {
+    /// The default damping of a spring.
+    pub const DEFAULT_DAMPING: f32 = 26.;
+    /// The default stiffness of a spring.
+    pub const DEFAULT_STIFFNESS: f32 = 170.;
+    /// The longest step a spring is advanced by at once. Longer durations are
+    /// simulated using multiple steps to keep the simulation stable.
+    const MAX_STEP: Duration = Duration::from_millis(4);
+    /// The distance and velocity below which a spring snaps to its target.
+    const REST_THRESHOLD: f32 = 0.001;
+
+    /// Returns a spring at rest at `value`.
+    #[must_use]
+    pub const fn new(value: f32) -> Self {
+        Self {
+            value,
+            velocity: 0.,
+            target: value,
+            stiffness: Self::DEFAULT_STIFFNESS,
+            damping: Self::DEFAULT_DAMPING,
+        }
+    }
+
+    /// Sets the stiffness and damping of this spring and returns self.
+    ///
+    /// Stiffer springs move more quickly, and springs with less damping
+    /// oscillate more before settling.
+    #[must_use]
+    pub const fn with_parameters(mut self, stiffness: f32, damping: f32) -> Self {
+        self.stiffness = stiffness;
+        self.damping = damping;
+        self
+    }
+
+    /// Sets the value this spring is pulled towards.
+    pub fn set_target(&mut self, target: f32) {
+        self.target = target;
+    }
+
+    /// Returns the value this spring is pulled towards.
+    #[must_use]
+    pub const fn target(&self) -> f32 {
+        self.target
+    }
+
+    /// Moves this spring to `value` and stops it.
+    pub fn jump_to(&mut self, value: f32) {
+        self.value = value;
+        self.target = value;
+        self.velocity = 0.;
+    }
+
+    /// Returns the current value of this spring.
+    #[must_use]
+    pub const fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Returns the current velocity of this spring, in units per second.
+    #[must_use]
+    pub const fn velocity(&self) -> f32 {
+        self.velocity
+    }
+
+    /// Returns true if this spring is at rest at its target.
+    #[must_use]
+    #[allow(clippy::float_cmp)] // Springs come to rest by snapping to their target exactly.
+    pub fn is_settled(&self) -> bool {
+        self.value == self.target && self.velocity == 0.
+    }
+
+    /// Advances this spring by `elapsed` and returns the current value.
+    pub fn advance(&mut self, mut elapsed: Duration) -> f32 {
+        while !elapsed.is_zero() && !self.is_settled() {
+            let step = elapsed.min(Self::MAX_STEP);
+            elapsed -= step;
+
+            let step = step.as_secs_f32();
+            let acceleration =
+                self.stiffness * (self.target - self.value) - self.damping * self.velocity;
+            self.velocity += acceleration * step;
+            self.value += self.velocity * step;
+
+            if (self.target - self.value).abs() < Self::REST_THRESHOLD
+                && self.velocity.abs() < Self::REST_THRESHOLD
+            {
+                self.jump_to(self.target);
+            }
+        }
+        self.value
+    }
+}
+
+/// A timer that fires after a duration elapses.
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use kludgine::tween::Timer;
+///
+/// let mut spawn = Timer::repeating(Duration::from_millis(500));
+/// assert_eq!(spawn.advance(Duration::from_millis(1200)), 2);
+/// assert_eq!(spawn.remaining(), Duration::from_millis(300));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Timer {
+    duration: Duration,
+    elapsed: Duration,
+    repeating: bool,
+    finished: bool,
+}
+
+impl Timer {
+    /// Returns a timer that fires once after `duration`.
+    #[must_use]
+    pub const fn once(duration: Duration) -> Self {
+        Self {
+            duration,
+            elapsed: Duration::ZERO,
+            repeating: false,
+            finished: false,
+        }
+    }
+
+    /// Returns a timer that fires every `duration`.
+    #[must_use]
+    pub const fn repeating(duration: Duration) -> Self {
+        Self {
+            repeating: true,
+            ..Self::once(duration)
+        }
+    }
+
+    /// Advances this timer by `elapsed`, returning the number of times the
+    /// timer fired.
+    ///
+    /// A timer that fires once returns at most 1, and then stops advancing.
+    /// A repeating timer with a zero duration fires once per call.
+    pub fn advance(&mut self, elapsed: Duration) -> u32 {
+        if self.finished {
+            return 0;
+        }
+        self.elapsed = self.elapsed.saturating_add(elapsed);
+        if self.elapsed < self.duration {
+            0
+        } else if !self.repeating {
+            self.elapsed = self.duration;
+            self.finished = true;
+            1
+        } else if self.duration.is_zero() {
+            self.elapsed = Duration::ZERO;
+            1
+        } else {
+            let duration = self.duration.as_nanos();
+            let elapsed = self.elapsed.as_nanos();
+            self.elapsed = Duration::from_nanos(u64::try_from(elapsed % duration).unwrap_or(0));
+            u32::try_from(elapsed / duration).unwrap_or(u32::MAX)
+        }
+    }
+
+    /// Restarts this timer.
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+        self.finished = false;
+    }
+
+    /// Returns true if this timer fires once and has already fired.
+    #[must_use]
+    pub const fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Returns the progress towards the next time this timer fires, ranging
+    /// from 0.0 to 1.0.
+    #[must_use]
+    pub fn progress(&self) -> f32 {
+        if self.duration.is_zero() {
+            1.
+        } else {
+            self.elapsed.as_secs_f32() / self.duration.as_secs_f32()
+        }
+    }
+
+    /// Returns the time remaining until this timer next fires.
+    #[must_use]
+    pub fn remaining(&self) -> Duration {
+        self.duration.saturating_sub(self.elapsed)
+    }
+}
+
+/// A clock that measures the time animations should advance by each frame.
+///
+/// Animations that advance by a clock's [`delta()`](Self::delta) move at the
+/// same speed regardless of the frame rate. Each [`Kludgine`](crate::Kludgine)
+/// instance has a clock, accessible through
+/// [`Kludgine::clock()`](crate::Kludgine::clock). The `app` feature's windows
+/// tick their clocks before each frame is prepared and pause them while the
+/// window is occluded.
+///
+/// The time between frames is limited to [`max_delta()`](Self::max_delta),
+/// which prevents animations from jumping after a long frame. No time passes
+/// while the clock is paused, including the time between pausing the clock and
+/// the first frame after it resumes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AnimationClock {
+    elapsed: Duration,
+    delta: Duration,
+    max_delta: Duration,
+    time_scale: f32,
+    paused: bool,
+    occluded: bool,
+    skip_next: bool,
+}
+
+impl Default for AnimationClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnimationClock {
+    /// Returns a new, running clock.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            elapsed: Duration::ZERO,
+            delta: Duration::ZERO,
+            max_delta: Duration::from_millis(250),
+            time_scale: 1.,
+            paused: false,
+            occluded: false,
+            skip_next: false,
+        }
+    }
+
+    /// Advances this clock by `elapsed` real time, returning the new
+    /// [`delta()`](Self::delta).
+    pub fn tick(&mut self, elapsed: Duration) -> Duration {
+        self.delta = if self.is_paused() || std::mem::take(&mut self.skip_next) {
+            Duration::ZERO
+        } else {
+            elapsed.min(self.max_delta).mul_f32(self.time_scale)
+        };
+        self.elapsed = self.elapsed.saturating_add(self.delta);
+        self.delta
+    }
+
+    /// Returns the amount of time animations should advance by this frame.
+    #[must_use]
+    pub const fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    /// Returns the total time that has elapsed on this clock.
+    #[must_use]
+    pub const fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Pauses or resumes this clock.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.update_paused(|clock| clock.paused = paused);
+    }
+
+    /// Sets whether the surface this clock is animating is hidden from view.
+    /// The clock is paused while occluded.
+    pub fn set_occluded(&mut self, occluded: bool) {
+        self.update_paused(|clock| clock.occluded = occluded);
+    }
+
+    fn update_paused(&mut self, update: impl FnOnce(&mut Self)) {
+        let was_paused = self.is_paused();
+        update(self);
+        if was_paused && !self.is_paused() {
+            self.skip_next = true;
+        }
+    }
+
+    /// Returns true if this clock is paused or occluded.
+    #[must_use]
+    pub const fn is_paused(&self) -> bool {
+        self.paused || self.occluded
+    }
+
+    /// Sets the rate time passes on this clock, where 1.0 is real time.
+    ///
+    /// Negative values are treated as 0.0.
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        self.time_scale = time_scale.max(0.);
+    }
+
+    /// Returns the rate time passes on this clock, where 1.0 is real time.
+    #[must_use]
+    pub const fn time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    /// Sets the longest time a single tick can advance this clock by.
+    pub fn set_max_delta(&mut self, max_delta: Duration) {
+        self.max_delta = max_delta;
+    }
+
+    /// Returns the longest time a single tick can advance this clock by.
+    ///
+    /// The default is 250 milliseconds.
+    #[must_use]
+    pub const fn max_delta(&self) -> Duration {
+        self.max_delta
+    }
+}
+
+/// A unique identifier for an animation in an [`AnimationRegistry`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct AnimationId(u64);
+
+impl AnimationId {
+    /// Returns a new identifier that is unique for this process.
+    #[must_use]
+    pub fn new_unique() -> Self {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        Self(COUNTER.fetch_add(1, atomic::Ordering::Relaxed))
+    }
+}
+
+impl Default for AnimationId {
+    fn default() -> Self {
+        Self::new_unique()
+    }
+}
+
+/// Tracks the animations that require a window to keep redrawing.
+///
+/// Each [`Kludgine`](crate::Kludgine) instance has a registry, accessible
+/// through [`Kludgine::animations_mut()`](crate::Kludgine::animations_mut).
+/// Drawables register how long they need to be redrawn for, and the `app`
+/// feature's windows automatically redraw while any animation is active.
+/// Once every animation has finished, windows stop redrawing until another
+/// event requests a redraw.
+///
+/// Animations registered while preparing a frame are scheduled immediately.
+/// Animations registered in response to other events are scheduled when the
+/// window is next redrawn.
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use kludgine::tween::{AnimationId, Tween};
+/// use kludgine::Color;
+///
+/// # fn draw(renderer: &mut kludgine::drawing::Renderer<'_, '_>) {
+/// let id = AnimationId::new_unique();
+/// let flash = Tween::new(Color::RED, Color::WHITE, Duration::from_millis(200));
+/// renderer.animations_mut().animate_for(id, flash.remaining());
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AnimationRegistry {
+    active: HashMap<AnimationId, Option<Duration>>,
+}
+
+impl AnimationRegistry {
+    /// Keeps redrawing for `duration`, replacing any existing deadline for
+    /// `id`.
+    pub fn animate_for(&mut self, id: AnimationId, duration: Duration) {
+        self.active.insert(id, Some(duration));
+    }
+
+    /// Keeps redrawing until `deadline`, replacing any existing deadline for
+    /// `id`.
+    pub fn animate_until(&mut self, id: AnimationId, deadline: Instant) {
+        self.animate_for(id, deadline.saturating_duration_since(Instant::now()));
+    }
+
+    /// Keeps redrawing until [`stop()`](Self::stop) is called for `id`.
+    pub fn animate_continuously(&mut self, id: AnimationId) {
+        self.active.insert(id, None);
+    }
+
+    /// Stops the animation `id`. Returns true if the animation was active.
+    pub fn stop(&mut self, id: AnimationId) -> bool {
+        self.active.remove(&id).is_some()
+    }
+
+    /// Stops all animations.
+    pub fn clear(&mut self) {
+        self.active.clear();
+    }
+
+    /// Returns true if the animation `id` is active.
+    #[must_use]
+    pub fn is_active(&self, id: AnimationId) -> bool {
+        self.active.contains_key(&id)
+    }
+
+    /// Returns true if any animations are active.
+    #[must_use]
+    pub fn is_animating(&self) -> bool {
+        !self.active.is_empty()
+    }
+
+    /// Returns the number of active animations.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.active.len()
+    }
+
+    /// Returns true if no animations are active.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.active.is_empty()
+    }
+
+    /// Advances all animations by `elapsed`, removing any animations that
+    /// have finished.
+    ///
+    /// Windows call this at the start of each frame. A frame is still drawn
+    /// after an animation's deadline passes, allowing the animation to be
+    /// drawn in its final state.
+    pub fn advance(&mut self, elapsed: Duration) {
+        self.active.retain(|_, remaining| match remaining {
+            Some(remaining) => {
+                if let Some(still_remaining) = remaining.checked_sub(elapsed) {
+                    *remaining = still_remaining;
+                    !still_remaining.is_zero()
+                } else {
+                    false
+                }
+            }
+            None => true,
+        });
+    }
+}
+
+#[test]
+fn tween_progress() {
+    let mut tween = Tween::new(0_f32, 10., Duration::from_secs(1));
+    assert!((tween.advance(Duration::from_millis(500)) - 5.).abs() < f32::EPSILON);
+    assert!(!tween.is_complete());
+    assert!((tween.advance(Duration::from_secs(5)) - 10.).abs() < f32::EPSILON);
+    assert!(tween.is_complete());
+
+    for easing in [
+        Easing::Linear,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+        Easing::Spring,
+    ] {
+        assert!(easing.ease(0.).abs() < f32::EPSILON);
+        assert!((easing.ease(1.) - 1.).abs() < f32::EPSILON);
+    }
+}
+
+#[test]
+fn animation_registry() {
+    let mut registry = AnimationRegistry::default();
+    let fade = AnimationId::new_unique();
+    let spinner = AnimationId::new_unique();
+    assert!(!registry.is_animating());
+
+    registry.animate_for(fade, Duration::from_millis(100));
+    registry.animate_continuously(spinner);
+    assert_eq!(registry.len(), 2);
+
+    registry.advance(Duration::from_millis(60));
+    assert!(registry.is_active(fade));
+    registry.advance(Duration::from_millis(60));
+    assert!(!registry.is_active(fade));
+    assert!(registry.is_animating());
+
+    assert!(registry.stop(spinner));
+    assert!(!registry.stop(spinner));
+    assert!(!registry.is_animating());
+}
+
+#[test]
+fn springs_and_timers() {
+    let mut spring = Spring::new(0.);
+    spring.set_target(10.);
+    spring.advance(Duration::from_millis(50));
+    assert!(spring.value() > 0. && spring.value() < 10.);
+    spring.advance(Duration::from_secs(5));
+    assert!(spring.is_settled());
+    assert!((spring.value() - 10.).abs() < f32::EPSILON);
+
+    let mut once = Timer::once(Duration::from_millis(100));
+    assert_eq!(once.advance(Duration::from_millis(250)), 1);
+    assert!(once.is_finished());
+    assert_eq!(once.advance(Duration::from_millis(250)), 0);
+
+    let mut clock = AnimationClock::new();
+    assert_eq!(clock.tick(Duration::from_secs(1)), clock.max_delta());
+    clock.set_occluded(true);
+    assert_eq!(clock.tick(Duration::from_millis(16)), Duration::ZERO);
+    clock.set_occluded(false);
+    // The first tick after resuming covers the time spent paused.
+    assert_eq!(clock.tick(Duration::from_secs(10)), Duration::ZERO);
+    assert_eq!(
+        clock.tick(Duration::from_millis(16)),
+        Duration::from_millis(16)
+    );
+}
@@ -0,0 +1,409 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+
+use figures::units::UPx;
+use figures::{Point, Size};
+use intentional::Cast;
+
+use crate::{CollectedTexture, Texture};
+
+/// The loading state of a texture's contents.
+///
+/// When [asynchronous texture
+/// uploads](crate::Kludgine::set_async_texture_uploads) are enabled, the
+/// contents of large textures are uploaded over several frames. Until the
+/// upload completes, the texture can be drawn, but its contents are
+/// incomplete.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum TextureLoadState {
+    /// The texture's contents are still being uploaded.
+    Loading,
+    /// The texture's contents have been uploaded.
+    Ready,
+}
+
+/// Tracks whether the contents of a texture have finished uploading.
+#[derive(Debug, Clone, Default)]
+pub struct LoadProgress(Option<Arc<AtomicBool>>);
+
+impl LoadProgress {
+    pub fn loading() -> Self {
+        Self(Some(Arc::new(AtomicBool::new(false))))
+    }
+
+    pub fn state(&self) -> TextureLoadState {
+        match &self.0 {
+            Some(ready) if !ready.load(Ordering::Acquire) => TextureLoadState::Loading,
+            _ => TextureLoadState::Ready,
+        }
+    }
+
+    fn finish(&self) {
+        if let Some(ready) = &self.0 {
+            ready.store(true, Ordering::Release);
+        }
+    }
+}
+
+/// The texture an upload writes into.
+#[derive(Debug)]
+pub enum UploadTarget {
+    /// A texture that is skipped if it is dropped before the upload finishes.
+    Texture(Weak<Texture>),
+    /// A region of a collection. The region is kept allocated until the
+    /// upload finishes, and the page's texture is looked up each frame
+    /// because it is replaced when the collection grows.
+    Collected(CollectedTexture),
+}
+
+impl UploadTarget {
+    fn with_texture(&self, with_texture: impl FnOnce(&wgpu::Texture)) {
+        match self {
+            UploadTarget::Texture(texture) => {
+                if let Some(texture) = texture.upgrade() {
+                    with_texture(&texture.data.wgpu);
+                }
+            }
+            UploadTarget::Collected(texture) => {
+                texture.with_collection_texture(|texture| with_texture(&texture.data.wgpu));
+            }
+        }
+    }
+}
+
+/// Texture data waiting to be uploaded.
+pub struct PendingUpload {
+    pub target: UploadTarget,
+    pub origin: Point<UPx>,
+    pub size: Size<UPx>,
+    pub format: wgpu::TextureFormat,
+    pub data: Arc<dyn AsRef<[u8]> + Send + Sync>,
+    pub layout: wgpu::ImageDataLayout,
+    pub progress: LoadProgress,
+}
+
+impl Debug for PendingUpload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PendingUpload")
+            .field("target", &self.target)
+            .field("origin", &self.origin)
+            .field("size", &self.size)
+            .field("format", &self.format)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An upload that has been partially copied to its texture.
+#[derive(Debug)]
+struct Upload {
+    pending: PendingUpload,
+    row_size: u64,
+    padded_row_size: u64,
+    next_row: u32,
+}
+
+impl Upload {
+    fn remaining_bytes(&self) -> u64 {
+        u64::from(self.pending.size.height.get() - self.next_row) * self.padded_row_size
+    }
+
+    /// Copies `rows` rows starting at `next_row` into `staging`, padding each
+    /// row to the alignment required for buffer to texture copies.
+    fn write_rows(&self, staging: &mut [u8], rows: u32) {
+        let data = AsRef::<[u8]>::as_ref(&*self.pending.data);
+        let row_size = self.row_size.cast::<usize>();
+        let padded_row_size = self.padded_row_size.cast::<usize>();
+        let stride = self
+            .pending
+            .layout
+            .bytes_per_row
+            .map_or(row_size, |bytes| bytes.cast::<usize>());
+        let offset = self.pending.layout.offset.cast::<usize>();
+        for row in 0..rows.cast::<usize>() {
+            let source = offset + (self.next_row.cast::<usize>() + row) * stride;
+            let dest = row * padded_row_size;
+            staging[dest..dest + row_size].copy_from_slice(&data[source..source + row_size]);
+        }
+    }
+}
+
+/// A pool of mapped buffers that texture data is written into before it is
+/// copied into textures.
+///
+/// Once a frame's copies have been submitted, its buffer is mapped again
+/// asynchronously and returned to the pool for reuse.
+#[derive(Debug, Default)]
+struct StagingBelt {
+    free: Arc<Mutex<Vec<Arc<wgpu::Buffer>>>>,
+}
+
+impl StagingBelt {
+    fn chunk(&self, size: u64, device: &wgpu::Device) -> Arc<wgpu::Buffer> {
+        let mut free = self.free.lock().unwrap_or_else(PoisonError::into_inner);
+        // Chunks that are too small are dropped, which allows the pool to
+        // adapt when the upload budget changes.
+        while let Some(chunk) = free.pop() {
+            if chunk.size() >= size {
+                return chunk;
+            }
+        }
+        drop(free);
+
+        Arc::new(device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("kludgine::texture upload staging"),
+            size,
+            usage: wgpu::BufferUsages::MAP_WRITE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: true,
+        }))
+    }
+
+    fn recall(&self, chunk: Arc<wgpu::Buffer>) {
+        let free = self.free.clone();
+        let mapped = chunk.clone();
+        chunk
+            .slice(..)
+            .map_async(wgpu::MapMode::Write, move |result| {
+                if result.is_ok() {
+                    free.lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .push(mapped);
+                }
+            });
+    }
+}
+
+/// Returns the size of the staging buffer used to upload up to `budget` of the
+/// `remaining` bytes, where `first_row` is the padded size of the next row to
+/// upload.
+///
+/// Padded rows are multiples of [`wgpu::COPY_BYTES_PER_ROW_ALIGNMENT`], so the
+/// size is rounded down to that alignment, which only excludes partial rows
+/// and keeps the size a multiple of [`wgpu::COPY_BUFFER_ALIGNMENT`] as
+/// required for buffers that are mapped when created. At least one row is
+/// always uploaded, even when it exceeds the budget, to ensure every upload
+/// eventually finishes.
+fn staging_capacity(remaining: u64, budget: Option<u64>, first_row: u64) -> u64 {
+    let alignment = u64::from(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+    (remaining.min(budget.unwrap_or(u64::MAX)) / alignment * alignment).max(first_row)
+}
+
+/// Texture data that is streamed to the GPU over several frames.
+#[derive(Debug, Default)]
+pub struct TextureUploads(Mutex<Uploads>);
+
+#[derive(Debug, Default)]
+struct Uploads {
+    budget: Option<u64>,
+    pending: VecDeque<Upload>,
+    uploaded_this_frame: bool,
+    belt: StagingBelt,
+}
+
+impl TextureUploads {
+    pub fn budget(&self) -> Option<u64> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).budget
+    }
+
+    pub fn set_budget(&mut self, budget: Option<u64>) {
+        self.0
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .budget = budget;
+    }
+
+    pub fn pending(&self) -> usize {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pending
+            .len()
+    }
+
+    pub fn new_frame(&mut self) {
+        self.0
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .uploaded_this_frame = false;
+    }
+
+    /// Returns true if data of `format` and `size` should be uploaded
+    /// asynchronously.
+    pub fn accepts(&self, format: wgpu::TextureFormat, size: Size<UPx>) -> bool {
+        self.budget().is_some()
+            && format.block_dimensions() == (1, 1)
+            && format.block_copy_size(None).is_some()
+            && size.width > 0
+            && size.height > 0
+    }
+
+    /// Queues `upload` to be copied into its texture over the next frames.
+    ///
+    /// The data must be in a format [accepted](Self::accepts) by this type.
+    pub fn push(&self, upload: PendingUpload) {
+        let bytes_per_pixel = upload
+            .format
+            .block_copy_size(None)
+            .expect("format not accepted");
+        let row_size = u64::from(upload.size.width.get() * bytes_per_pixel);
+        let alignment = u64::from(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pending
+            .push_back(Upload {
+                pending: upload,
+                row_size,
+                padded_row_size: row_size.div_ceil(alignment) * alignment,
+                next_row: 0,
+            });
+    }
+
+    /// Copies up to the budget's worth of pending texture data into their
+    /// textures.
+    ///
+    /// This only uploads data once per frame. If asynchronous uploads have
+    /// been disabled, all remaining data is uploaded.
+    pub fn upload(&self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        let mut uploads = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        let Uploads {
+            budget,
+            pending,
+            uploaded_this_frame,
+            belt,
+        } = &mut *uploads;
+        if *uploaded_this_frame || pending.is_empty() {
+            return;
+        }
+        *uploaded_this_frame = true;
+
+        let remaining = pending.iter().map(Upload::remaining_bytes).sum::<u64>();
+        let capacity = staging_capacity(remaining, *budget, pending[0].padded_row_size);
+        let chunk = belt.chunk(capacity, device);
+
+        let mut copies = Vec::new();
+        let mut offset = 0;
+        {
+            let mut staging = chunk.slice(..).get_mapped_range_mut();
+            for (index, upload) in pending.iter_mut().enumerate() {
+                let rows = ((capacity - offset) / upload.padded_row_size)
+                    .min(u64::from(
+                        upload.pending.size.height.get() - upload.next_row,
+                    ))
+                    .cast::<u32>();
+                if rows == 0 {
+                    break;
+                }
+                upload.write_rows(&mut staging[offset.cast::<usize>()..], rows);
+                copies.push((index, upload.next_row, rows, offset));
+                upload.next_row += rows;
+                offset += upload.padded_row_size * u64::from(rows);
+            }
+        }
+        chunk.unmap();
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("kludgine::texture uploads"),
+        });
+        for (index, first_row, rows, offset) in copies {
+            let upload = &pending[index];
+            upload.pending.target.with_texture(|texture| {
+                encoder.copy_buffer_to_texture(
+                    wgpu::ImageCopyBuffer {
+                        buffer: &chunk,
+                        layout: wgpu::ImageDataLayout {
+                            offset,
+                            bytes_per_row: Some(upload.padded_row_size.cast::<u32>()),
+                            rows_per_image: Some(rows),
+                        },
+                    },
+                    wgpu::ImageCopyTexture {
+                        texture,
+                        mip_level: 0,
+                        origin: wgpu::Origin3d {
+                            x: upload.pending.origin.x.get(),
+                            y: upload.pending.origin.y.get() + first_row,
+                            z: 0,
+                        },
+                        aspect: wgpu::TextureAspect::All,
+                    },
+                    wgpu::Extent3d {
+                        width: upload.pending.size.width.get(),
+                        height: rows,
+                        depth_or_array_layers: 1,
+                    },
+                );
+            });
+        }
+        queue.submit([encoder.finish()]);
+        belt.recall(chunk);
+
+        pending.retain(|upload| {
+            if upload.next_row < upload.pending.size.height.get() {
+                true
+            } else {
+                upload.pending.progress.finish();
+                false
+            }
+        });
+    }
+}
+
+#[test]
+fn load_progress() {
+    assert_eq!(LoadProgress::default().state(), TextureLoadState::Ready);
+    let progress = LoadProgress::loading();
+    let clone = progress.clone();
+    assert_eq!(clone.state(), TextureLoadState::Loading);
+    progress.finish();
+    assert_eq!(clone.state(), TextureLoadState::Ready);
+}
+
+#[test]
+fn staging_capacity_alignment() {
+    let row = u64::from(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT) * 4;
+    let capacity = staging_capacity(row * 1_000, Some(1_000_001), row);
+    assert_eq!(capacity % wgpu::COPY_BUFFER_ALIGNMENT, 0);
+    assert!(capacity <= 1_000_001);
+    assert_eq!(capacity / row, 1_000_001 / row);
+    // At least one row is uploaded, even when it exceeds the budget.
+    assert_eq!(staging_capacity(row * 1_000, Some(1), row), row);
+    assert_eq!(staging_capacity(row * 3, None, row), row * 3);
+}
+
+#[test]
+#[cfg(feature = "app")]
+fn odd_upload_budget() {
+    use figures::UPx2D;
+
+    let texture = crate::LazyTexture::from_data(
+        Size::upx(1000, 600),
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        wgpu::TextureUsages::TEXTURE_BINDING,
+        wgpu::FilterMode::Nearest,
+        vec![255; 1000 * 600 * 4],
+    );
+    let ready = Arc::new(AtomicBool::new(false));
+    let Ok(mut window) = crate::app::headless_window(Size::upx(16, 16), {
+        let ready = ready.clone();
+        move |mut renderer, _window| {
+            renderer.set_async_texture_uploads(Some(1_000_001));
+            ready.store(
+                texture.load_state(&renderer) == TextureLoadState::Ready,
+                Ordering::Relaxed,
+            );
+            true
+        }
+    }) else {
+        // No graphics adapter is available.
+        return;
+    };
+    // Each frame uploads less than a third of the texture.
+    for _ in 0..3 {
+        window.render();
+        assert!(!ready.load(Ordering::Relaxed));
+    }
+    window.render();
+    assert!(ready.load(Ordering::Relaxed));
+}